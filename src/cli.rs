@@ -1,15 +1,56 @@
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use comfy_table::{Cell, Table};
-use dialoguer::{Input, Select};
+use dialoguer::{Confirm, Input, Select};
+use indicatif::{ProgressBar, ProgressStyle};
 
-use crate::config::{self, SpotifyConfig};
-use crate::core::{parser, scanner, tagger};
-use crate::models::TrackInfo;
+use crate::config::{
+    self, AlbumEdition, BackupMode, Config, ConflictStrategy, MergePolicy, MtimePolicy,
+    SpotifyConfig, TrustConfig,
+};
+use crate::core::art_index::ArtIndex;
+use crate::core::artwork::ArtInfo;
+use crate::core::fetch_state::{FetchState, AUTO_STATE_FILE, FETCH_STATE_FILE};
+use crate::core::folder_art::FolderArt;
+#[cfg(feature = "library")]
+use crate::core::library;
+use crate::core::listing::{Column, Field};
+use crate::core::matcher::{Aliases, Tier};
+use crate::core::output::{format_bytes, OutputFormat};
+use crate::core::parser::{FilenamePattern, QueryVariant};
+use crate::core::renamer::{self, RenameOutcome};
+use crate::core::report::{EntryStatus, Report};
+use crate::core::review::{ReviewCandidate, ReviewItem, ReviewQueue};
+use crate::core::scanner::Inputs;
+use crate::core::skip_list::SkipList;
+use crate::core::staging::{self, StagedChanges};
+use crate::core::{
+    art_index, artwork, audio, audiobook, backup, cancel, compilation, fingerprint, flac,
+    folder_art, history, inspect, legacy, listing, longpath, manifest, matcher, nfo, output,
+    parser, scanner, tag_sync, tagger, totals, variants,
+};
+use crate::exit;
+use crate::i18n::{self, tr, Lang};
+use crate::integrations::media_server::MediaServer;
+use crate::integrations::now_playing::{self, Player};
+use crate::models::{split_artists, Mp3File, TrackInfo};
+use crate::sources::art_search::{self, ArtCandidate};
+use crate::sources::cache::{RememberedMatch, SourceCache};
+use crate::sources::cover_art::CoverArtClient;
+use crate::sources::enrich::Enricher;
+use crate::sources::http::ReqwestClient;
+use crate::sources::melon::{self, MelonClient};
+use crate::sources::open_library::OpenLibraryClient;
+use crate::sources::selectors::Selectors;
 use crate::sources::spotify::SpotifyClient;
-use crate::sources::MusicSource;
+use crate::sources::spotify_auth::{self, Pkce};
+use crate::sources::{self, MusicSource, Page, SourceKind};
 
 #[derive(Parser)]
 #[command(name = "mp3tag", about = "Spotify 연동 MP3 ID3 태그 편집기")]
@@ -21,22 +62,97 @@ pub struct Cli {
     #[arg(long)]
     pub gui: bool,
 
-    /// GUI 모드에서 열 디렉토리
-    #[arg(value_name = "DIRECTORY")]
-    pub directory: Option<PathBuf>,
+    /// GUI 모드에서 열 디렉토리 또는 MP3 파일. 파일을 하나만 주면 그 폴더를 열고 파일을 선택하며,
+    /// 여러 개를 주면 주어진 파일만 목록에 올린다 ("연결 프로그램"으로 등록할 때 사용)
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// 태그 기록 후 파일 수정 시각 처리 방식 (기본값: 설정 파일)
+    #[arg(long, value_enum, global = true)]
+    pub mtime: Option<MtimePolicy>,
+
+    /// 읽기 전용 파일도 임시로 권한을 바꿔 기록
+    #[arg(long, global = true)]
+    pub force: bool,
+
+    /// 앨범 아트의 짧은 변 최소 픽셀 수. 더 작은 아트는 기록하지 않음 (기본값: 설정 파일)
+    #[arg(long, value_name = "PX", global = true)]
+    pub min_art_size: Option<u32>,
+
+    /// MusicBrainz 릴리스 ID를 알면 Cover Art Archive의 원본 커버 사용
+    #[arg(long, global = true)]
+    pub cover_art_archive: bool,
+
+    /// 네트워크를 쓰지 않음. 캐시된 검색 결과/앨범 아트와 파일명 추론만 사용 (기본값: 설정 파일)
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// 태그를 기록하기 전에 원본을 백업 디렉토리에 보관 (기본값: 설정 파일)
+    #[arg(long, value_enum, global = true)]
+    pub backup: Option<BackupMode>,
+
+    /// 기록한 변경을 디렉토리마다 .mp3tag-history.jsonl에 남김 (기본값: 설정 파일)
+    #[arg(long, global = true)]
+    pub history: bool,
+
+    /// 출력 언어 (기본값: MP3TAG_LANG 환경 변수, 없으면 로캘, 그것도 없으면 한국어)
+    #[arg(long, value_enum, global = true)]
+    pub lang: Option<Lang>,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 pub enum Commands {
     /// 디렉토리를 스캔하여 태그 현황 표시
     Scan {
-        /// 스캔할 디렉토리
-        directory: PathBuf,
+        /// 스캔할 디렉토리 또는 파일. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        /// (기본값: 설정의 music_dir)
+        paths: Vec<PathBuf>,
+        /// MP3 프레임을 끝까지 따라가며 잘린 파일, 태그 뒤 불필요한 데이터 등 손상 확인
+        #[arg(long)]
+        verify: bool,
+        /// 표에 넣을 열 (쉼표로 구분, 예: path,title,artist,year,bitrate)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Vec<Column>,
+        /// 정렬 기준 열 (기본값: 경로순)
+        #[arg(long, value_enum)]
+        sort: Option<Column>,
+        /// 태그가 없는 파일만 표시
+        #[arg(long)]
+        untagged_only: bool,
+        /// 앨범 아트가 없는 파일만 표시
+        #[arg(long)]
+        missing_art: bool,
+        /// 출력 형식 (csv, tsv, json은 머리글과 행만 출력)
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// 파일의 ID3 태그를 프레임 단위로 모두 표시 (TXXX/PRIV/알 수 없는 프레임, 이미지, 패딩 포함)
+    Show {
+        /// 확인할 MP3 파일
+        file: PathBuf,
+    },
+    /// 프레임 ID로 ID3 프레임을 직접 기록/삭제 (태그 모델이 다루지 않는 필드용)
+    Frame {
+        /// 편집할 MP3 파일
+        file: PathBuf,
+        /// 프레임 값 기록, 여러 번 지정 가능 (예: TIT3=부제, TXXX:MOOD=calm, WOAR=URL, COMM=메모)
+        #[arg(long, value_name = "ID=값")]
+        set: Vec<String>,
+        /// 프레임 삭제, 여러 번 지정 가능 (예: PRIV, TXXX:MOOD)
+        #[arg(long, value_name = "ID")]
+        delete: Vec<String>,
+        /// 편집 후 프레임 목록 표시 (--set/--delete가 없으면 항상 표시)
+        #[arg(long)]
+        list: bool,
     },
     /// 파일의 태그 편집
     Edit {
-        /// 편집할 MP3 파일
+        /// 편집할 MP3 파일 (--preset과 함께면 디렉토리의 모든 MP3)
         file: PathBuf,
+        /// config.toml의 [presets.이름]에 저장한 태그 묶음을 먼저 채움 (다른 옵션이 우선)
+        #[arg(long)]
+        preset: Option<String>,
         #[arg(long)]
         title: Option<String>,
         #[arg(long)]
@@ -49,258 +165,6894 @@ pub enum Commands {
         track: Option<u32>,
         #[arg(long)]
         year: Option<i32>,
+        /// 발매일 (예: 2019-11-18). 연도를 따로 주지 않으면 발매일의 연도를 쓴다.
+        #[arg(long)]
+        date: Option<String>,
+        /// 최초 발매 연도 (리마스터/재발매반의 원래 연도)
+        #[arg(long, name = "original-year")]
+        original_year: Option<i32>,
         #[arg(long)]
         genre: Option<String>,
+        /// 콘텐츠 그룹 (TIT1)
+        #[arg(long)]
+        grouping: Option<String>,
+        /// 클래식 작품명 (예: "Symphony No. 5 in C minor, Op. 67")
+        #[arg(long)]
+        work: Option<String>,
+        /// 악장 이름 (예: "Allegro con brio")
+        #[arg(long)]
+        movement: Option<String>,
+        /// 악장 번호 ("1" 또는 전체 악장 수를 붙인 "1/4")
+        #[arg(long, name = "movement-number")]
+        movement_number: Option<String>,
         #[arg(long, name = "album-art")]
         album_art: Option<PathBuf>,
     },
-    /// Spotify에서 태그 가져오기
+    /// 외부 소스에서 곡 검색 (파일은 바꾸지 않음)
+    Search {
+        /// 검색어 (예: "아이유 밤편지")
+        query: String,
+        /// 검색할 소스 (spotify, melon 또는 config.toml에 등록한 명령 소스 이름)
+        /// (기본값: 설정의 default_source, 없으면 spotify)
+        #[arg(long)]
+        source: Option<SourceKind>,
+        /// 한 페이지에 보여 줄 결과 수 (1~50)
+        #[arg(long, default_value_t = Page::LIMIT, value_parser = parse_page_limit)]
+        limit: usize,
+        /// 보여 줄 페이지 (1부터). 첫 페이지에 원하는 곡이 없으면 다음 페이지를 확인
+        #[arg(
+            long,
+            default_value_t = 1,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        page: usize,
+        /// 출력 형식 (csv, tsv, json은 머리글과 행만 출력)
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Spotify 등 외부 소스에서 태그 가져오기
     Fetch {
-        /// MP3 파일 또는 디렉토리
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 검색할 소스 (spotify, melon 또는 config.toml에 등록한 명령 소스 이름)
+        /// (기본값: 설정의 default_source, 없으면 spotify)
+        #[arg(long)]
+        source: Option<SourceKind>,
+        /// 선택 없이 첫 번째 검색 결과를 자동 적용 (중단 후 다시 실행하면 이어서 처리)
+        #[arg(long)]
+        auto: bool,
+        /// --auto 진행 상태를 무시하고 처음부터 다시 처리
+        #[arg(long, requires = "auto")]
+        restart: bool,
+        /// 태그가 있는 파일도 모두 다시 검색 (검색어는 기존 태그의 아티스트/제목, 빠진 값은 파일명)
+        #[arg(long, conflicts_with_all = ["id", "liked", "playlist"])]
+        retag: bool,
+        /// 태그가 없는 파일과 함께 연도, 앨범 아트, 앨범이 빠진 파일도 검색.
+        /// 확인할 필드를 지정할 수 있음 (예: --incomplete=year,art)
+        #[arg(
+            long,
+            value_enum,
+            value_name = "FIELDS",
+            num_args = 0..=1,
+            require_equals = true,
+            value_delimiter = ',',
+            conflicts_with_all = ["retag", "id", "liked", "playlist"]
+        )]
+        incomplete: Option<Vec<Field>>,
+        /// 이전에 "이 파일 건너뛰기"를 고른 파일도 다시 물음
+        #[arg(long, conflicts_with_all = ["auto", "id", "liked", "playlist"])]
+        include_skipped: bool,
+        /// 검색 대신 트랙 ID 또는 URL로 지정한 곡의 태그를 적용 (단일 파일)
+        #[arg(long, conflicts_with = "auto")]
+        id: Option<String>,
+        /// Spotify에서 좋아요 표시한 곡과 일치하는 파일에 태그 적용 ('mp3tag login' 필요)
+        #[arg(long, conflicts_with_all = ["auto", "id"])]
+        liked: bool,
+        /// Spotify 플레이리스트(URL 또는 ID)와 일치하는 파일에 태그와 플레이리스트 순서의 트랙 번호 적용
+        #[arg(long, value_name = "URL", conflicts_with_all = ["auto", "id", "liked"])]
+        playlist: Option<String>,
+        /// 파일별 처리 결과를 HTML(.html) 또는 Markdown(.md) 보고서로 저장
+        #[arg(long, value_name = "FILE")]
+        report: Option<PathBuf>,
+        /// 바로 기록하지 않고 저장 대기 목록에 쌓음 ('mp3tag staged'로 확인 후 저장/취소)
+        #[arg(long, conflicts_with_all = ["id", "liked", "playlist"])]
+        stage: bool,
+        /// 이미 값이 있는 필드의 처리: overwrite(덮어씀), fill(빈 필드만 채움), interactive(필드마다 물음)
+        /// (기본값: 설정 파일)
+        #[arg(long, value_enum)]
+        merge: Option<MergePolicy>,
+    },
+    /// 앨범을 검색하여 폴더 전체에 트랙 목록 기준으로 태그 적용
+    Album {
+        /// 앨범 폴더
+        directory: PathBuf,
+        /// 앨범 검색어 (기본값: 태그의 아티스트/앨범 또는 폴더명)
+        #[arg(long)]
+        query: Option<String>,
+        /// 검색할 소스 (spotify, melon 또는 config.toml에 등록한 명령 소스 이름)
+        /// (기본값: 설정의 default_source, 없으면 melon)
+        #[arg(long)]
+        source: Option<SourceKind>,
+    },
+    /// 폴더 하나를 오디오북 한 권으로 태그 (앨범=책 제목, 아티스트=저자, 장르 Audiobook,
+    /// 파일 순서대로 트랙 번호와 챕터)
+    Audiobook {
+        /// 오디오북 폴더. 하위 폴더를 포함한 파일명의 자연 순서가 챕터 순서
+        directory: PathBuf,
+        /// 책 제목 (기본값: Open Library 검색 결과 또는 한 번 묻기, 폴더명 "저자 - 제목")
+        #[arg(long)]
+        title: Option<String>,
+        /// 저자
+        #[arg(long)]
+        author: Option<String>,
+        /// Open Library에서 책을 검색해 제목, 저자, 첫 출간 연도, 표지를 채움
+        #[arg(long)]
+        lookup: bool,
+        /// 셔플이나 이름순 정렬에도 순서가 유지되도록 파일명을 "001 - 챕터.mp3"로 변경
+        #[arg(long)]
+        rename: bool,
+        /// 기록하지 않고 챕터 순서만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// "Various Artists" 같은 모음 앨범 폴더의 곡마다 원래 앨범을 찾아 앨범, 연도, 트랙 번호,
+    /// 앨범 아트를 다시 기록
+    Split {
+        /// 모음 앨범 폴더
+        directory: PathBuf,
+        /// 검색할 소스. 여러 번 지정 가능 (기본값: 사용할 수 있는 모든 소스)
+        #[arg(long = "source")]
+        sources: Vec<SourceKind>,
+        /// 원래 앨범으로 볼 최소 일치 점수 (0.0~1.0)
+        #[arg(long, default_value_t = 0.8)]
+        min_score: f64,
+        /// 이 디렉토리(보통 음악 라이브러리 최상위) 아래 "{앨범 아티스트}/{앨범}/" 폴더로 옮겨 앨범별로 나눔
+        #[arg(long, value_name = "DIR")]
+        organize: Option<PathBuf>,
+        /// 기록/이동 없이 찾은 앨범만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 네트워크 없이 파일명과 폴더 구조만으로 태그 기록
+    FromFilename {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 경로 패턴 (예: "{artist}/{album}/{track} - {title}"). 없으면 파일명과 폴더명으로 추론
+        #[arg(long)]
+        pattern: Option<String>,
+        /// 기록하지 않고 추론 결과만 표시
+        #[arg(long)]
+        dry_run: bool,
+        /// 이미 값이 있는 필드도 추론한 값으로 덮어쓰기 (--merge overwrite와 같음)
+        #[arg(long, conflicts_with = "merge")]
+        overwrite: bool,
+        /// 이미 값이 있는 필드의 처리: fill(기본값, 빈 필드만 채움), overwrite, interactive(필드마다 물음)
+        #[arg(long, value_enum)]
+        merge: Option<MergePolicy>,
+    },
+    /// 스캔부터 검색, 자동 적용, 앨범 아트, 파일명 변경, 보고까지 한 번에 실행
+    Auto(AutoArgs),
+    /// 앨범 아트 사용량 분석/정리
+    Art {
+        #[command(subcommand)]
+        command: ArtCommand,
+    },
+    /// 파일 끝에 남은 APEv2/Lyrics3 태그의 값을 ID3로 옮기고 레거시 태그를 삭제
+    MigrateLegacy {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 기록하지 않고 옮길 내용만 표시
+        #[arg(long)]
+        dry_run: bool,
+        /// ID3에 이미 값이 있는 필드도 레거시 태그 값으로 덮어쓰기
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// 앨범 폴더마다 실제 파일을 세어 비어 있거나 맞지 않는 전체 트랙/디스크 수("3/?")를 채움
+    Totals {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 기록하지 않고 바뀔 내용만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 오디오 지문(Chromaprint)이 비슷한 파일을 같은 곡으로 묶어 보고. 길이나 비트레이트가 조금 다른
+    /// 라디오 에디트/앨범 버전, 다른 인코딩도 함께 묶음 (fpcalc 필요)
+    Dedupe {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 같은 곡으로 볼 지문 유사도 (0.0~1.0, 기본값: 설정의 dedupe.threshold, 없으면 0.85)
+        #[arg(long)]
+        threshold: Option<f64>,
+        /// 출력 형식 (csv, tsv, json은 머리글과 행만 출력)
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// 스캔 결과, 변경 기록, 건너뜀/기억한 결과를 모아 두는 SQLite 라이브러리 데이터베이스 (library 기능)
+    Library {
+        #[command(subcommand)]
+        command: LibraryCommand,
+    },
+    /// 라이브러리 데이터베이스에서 검색식과 맞는 파일 찾기 (예: 'artist:IU year:>=2015 missing:art')
+    Query {
+        /// 검색식. 여러 단어는 모두 만족하는 파일 (필드:값, year:>=2015, year:2015..2019, missing:art, has:art,
+        /// -필드:값, 필드 없는 단어는 제목/아티스트/앨범)
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// 표에 넣을 열 (쉼표로 구분, 예: path,title,artist,year,bitrate)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Vec<Column>,
+        /// 정렬 기준 열 (기본값: 경로순)
+        #[arg(long, value_enum)]
+        sort: Option<Column>,
+        /// 출력 형식 (csv, tsv, json은 머리글과 행만 출력)
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// 원본 트리의 태그와 앨범 아트를 사본 트리(휴대용 기기 등)의 같은 곡에 옮겨 기록. 오디오 해시, 없으면
+    /// 상대 경로로 짝지으며 오디오는 다시 인코딩하지 않음
+    Sync {
+        /// 원본(태그를 가져올) 디렉토리
+        #[arg(long)]
+        from: PathBuf,
+        /// 사본(태그를 기록할) 디렉토리
+        #[arg(long)]
+        to: PathBuf,
+        /// 앨범 아트는 옮기지 않음
+        #[arg(long)]
+        no_art: bool,
+        /// 원본보다 나중에 수정된 사본도 덮어씀 (기본: 건너뜀)
+        #[arg(long)]
+        overwrite_newer: bool,
+        /// 기록하지 않고 바뀔 내용만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// FLAC 원본의 Vorbis comment와 앨범 아트를 변환한 MP3에 ID3로 옮겨 기록. 확장자를 뺀 상대 경로,
+    /// 없으면 파일 이름으로 짝지음
+    ConvertTags {
+        /// FLAC 원본 디렉토리
+        #[arg(long)]
+        from: PathBuf,
+        /// 변환한 MP3 디렉토리
+        #[arg(long)]
+        to: PathBuf,
+        /// 앨범 아트는 옮기지 않음
+        #[arg(long)]
+        no_art: bool,
+        /// 기록하지 않고 바뀔 내용만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 태그를 뺀 오디오 데이터의 체크섬 목록을 만들고 확인 (태그 편집이 오디오를 건드리지 않았는지, 비트 손상 확인)
+    Checksum {
+        #[command(subcommand)]
+        command: ChecksumCommand,
+    },
+    /// 태그 기반으로 파일명을 "{아티스트} - {제목}.mp3"로 변경
+    Rename {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 대상 파일이 이미 존재할 때의 처리 방식 (기본값: 설정 파일)
+        #[arg(long, value_enum)]
+        on_conflict: Option<ConflictStrategy>,
+    },
+    /// `fetch --stage`나 GUI에서 쌓아 둔 저장 대기 변경을 확인하고 기록하거나 취소
+    Staged {
+        /// 대상 디렉토리 (또는 그 안의 MP3 파일)
         path: PathBuf,
+        /// 변경을 파일에 기록
+        #[arg(long)]
+        save: bool,
+        /// 변경을 기록하지 않고 버림
+        #[arg(long, conflicts_with = "save")]
+        discard: bool,
+        /// 처리할 파일 (여러 번 지정 가능, 생략하면 전체)
+        #[arg(long = "file", value_name = "FILE")]
+        files: Vec<PathBuf>,
+    },
+    /// 디렉토리의 변경 기록(.mp3tag-history.jsonl)을 최신순으로 표시
+    History {
+        /// 기록을 볼 디렉토리
+        directory: PathBuf,
+        /// 이 파일 이름의 기록만 표시
+        #[arg(long, value_name = "NAME")]
+        file: Option<String>,
+        /// 표시할 최대 기록 수
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// 백업해 둔 원본으로 파일을 되돌림 (기본값: 가장 최근 백업)
+    Restore {
+        /// 되돌릴 MP3 파일
+        file: PathBuf,
+        /// 되돌리지 않고 이 파일의 백업 목록만 표시
+        #[arg(long)]
+        list: bool,
+        /// 되돌릴 백업 파일 (--list로 확인)
+        #[arg(long, value_name = "BACKUP")]
+        from: Option<PathBuf>,
+    },
+    /// MPD/MPRIS에서 지금 재생 중인 파일의 태그를 바로 가져오거나 편집
+    Now {
+        /// 검색할 소스 (spotify, melon 또는 config.toml에 등록한 명령 소스 이름)
+        /// (기본값: 설정의 default_source, 없으면 spotify)
+        #[arg(long)]
+        source: Option<SourceKind>,
+        /// 조회할 플레이어 (기본값: MPD를 먼저 시도하고 실패하면 MPRIS)
+        #[arg(long, value_enum)]
+        player: Option<Player>,
+    },
+    /// 로컬 HTTP+JSON API 서버 실행 (scan/search/apply/rename)
+    Serve {
+        /// 바인딩할 주소. 요청에는 시작할 때 출력되는 토큰이 필요하다
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        addr: String,
+        /// 바인딩 주소 외에 Host로 받아들일 이름 (예: `nas.local`, `192.168.0.10`). 여러 번 지정 가능.
+        /// `0.0.0.0`에 바인딩해 다른 기기에서 접속할 때 쓴다
+        #[arg(long = "allow-host", value_name = "HOST")]
+        allow_hosts: Vec<String>,
+    },
+    /// 설정 확인/변경. 하위 명령 없이 실행하면 Spotify/Genius 자격증명을 대화형으로 입력
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommand>,
+    },
+    /// Spotify 사용자 로그인 (좋아요 표시한 곡 등 사용자 범위 기능용)
+    Login,
+}
+
+/// `mp3tag art` 하위 명령어.
+#[derive(Subcommand)]
+pub enum ArtCommand {
+    /// 앨범별로 같은 이미지가 중복 저장된 크기를 보고
+    Stats {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 출력 형식 (csv, tsv, json은 머리글과 행만 출력, 크기는 바이트 수)
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// 앨범마다 대표 이미지 하나를 골라(필요하면 줄여서) 모든 파일에 같은 이미지로 다시 기록
+    Dedupe {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 대표 이미지의 긴 변이 이 픽셀보다 크면 줄여서 JPEG로 다시 인코딩
+        #[arg(long, value_name = "PX")]
+        max_size: Option<u32>,
+        /// 기록하지 않고 결과만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 앨범 안에서 같은 커버의 다른 스캔/인코딩이 섞인 곳을 지각 해시로 찾아 대표 이미지로 통일
+    Check {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 앨범마다 확인하지 않고 모두 통일
+        #[arg(long)]
+        yes: bool,
+        /// 기록하지 않고 결과만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 해상도가 낮은 앨범 아트를 소스에서 찾은 같은 앨범의 더 큰 커버로 교체
+    Upgrade {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 짧은 변이 이 픽셀보다 작은 아트를 교체 대상으로 삼음 (기본값: art.min_size, 설정하지 않았으면 500)
+        #[arg(long, value_name = "PX")]
+        below: Option<u32>,
+        /// 검색할 소스. 여러 번 지정 가능 (기본값: 사용할 수 있는 모든 소스)
+        #[arg(long = "source")]
+        sources: Vec<SourceKind>,
+        /// 앨범마다 확인하지 않고 모두 교체
+        #[arg(long)]
+        yes: bool,
+        /// 기록하지 않고 찾은 커버만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 앨범 아트가 없는 파일에 앨범마다 한 번 찾은 커버를 기록
+    Fill {
+        /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// 검색할 소스. 여러 번 지정 가능 (기본값: 사용할 수 있는 모든 소스)
+        #[arg(long = "source")]
+        sources: Vec<SourceKind>,
+        /// 기록하지 않고 찾은 커버만 표시
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// `mp3tag library` 하위 명령어.
+#[derive(Subcommand)]
+pub enum LibraryCommand {
+    /// 파일을 데이터베이스와 맞추고(바뀐 파일만 다시 읽음) 변경 기록과 건너뜀/기억한 결과를 가져옴
+    Scan {
+        /// MP3 파일 또는 디렉토리 (기본값: 설정의 music_dir)
+        paths: Vec<PathBuf>,
+    },
+    /// 데이터베이스 통계: 파일/앨범 수, 빠진 태그, 출처별 변경 기록
+    Stats,
+    /// 건너뛴 파일과 기억한 결과를 오디오 해시별 JSON Lines로 내보냄 (다른 컴퓨터의 같은 파일에 다시 쓰기용)
+    Export {
+        /// 기록할 파일 (-는 표준 출력)
+        output: PathBuf,
+    },
+    /// `library export`로 내보낸 결정을 가져옴. 이후 fetch/auto가 같은 오디오에 그대로 씀
+    Import {
+        /// 가져올 파일 (-는 표준 입력)
+        input: PathBuf,
+        /// 이 경로의 파일 중 가져온 결과와 오디오가 같은 파일에 바로 태그를 기록
+        #[arg(long, num_args = 1.., value_name = "PATH")]
+        apply: Vec<PathBuf>,
+        /// --apply로 기록하지 않고 적용할 파일만 표시
+        #[arg(long, requires = "apply")]
+        dry_run: bool,
+    },
+}
+
+/// `mp3tag checksum` 하위 명령어.
+#[derive(Subcommand)]
+pub enum ChecksumCommand {
+    /// 디렉토리의 MP3마다 오디오 체크섬과 태그 해시를 계산해 목록 파일에 기록 (있으면 새로 씀)
+    Create {
+        /// MP3 디렉토리
+        directory: PathBuf,
+        /// 목록 파일 (기본값: <디렉토리>/.mp3tag-audio.sha256)
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+    },
+    /// 목록 파일과 지금 오디오를 비교해 바뀐 파일, 없어진 파일, 새 파일을 보고
+    Verify {
+        /// MP3 디렉토리
+        directory: PathBuf,
+        /// 목록 파일 (기본값: <디렉토리>/.mp3tag-audio.sha256)
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+        /// 정상인 파일도 표시
+        #[arg(long)]
+        all: bool,
+        /// 출력 형식 (csv, tsv, json은 머리글과 행만 출력)
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// 목록을 만든 뒤 태그가 바뀐 파일의 상대 경로를 한 줄에 하나씩 출력 (오디오는 읽지 않음; 증분 백업/동기화용)
+    Modified {
+        /// MP3 디렉토리
+        directory: PathBuf,
+        /// 비교할 목록 파일 (기본값: <디렉토리>/.mp3tag-audio.sha256)
+        #[arg(long)]
+        since: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// 기본값을 채운 현재 설정 표시 (비밀 값은 가림)
+    Show,
+    /// 설정 검사: 알 수 없는 키, 파일명/폴더 템플릿, Melon 선택자 파일, Spotify 자격증명(토큰 발급 시도)
+    Check,
+    /// 실제 Melon 페이지를 불러와 선택자마다 요소를 찾는지 확인 (사이트 구조가 바뀌었는지 진단)
+    TestSelectors {
+        /// 곡/앨범 검색어. 결과가 있는 검색어를 줘야 상세 페이지까지 확인한다
+        #[arg(long, default_value = melon::SELF_TEST_QUERY)]
+        query: String,
     },
-    /// Spotify 자격증명 설정
-    Config,
+    /// 처음 실행 설정 마법사를 다시 실행 (기본 소스, 자격증명, 파일명 템플릿, 음악 디렉토리)
+    Setup,
+    /// 설정 값 하나를 바꿔 저장 (예: mp3tag config set write.atomic false)
+    Set {
+        /// "섹션.키" 형식의 설정 키
+        key: String,
+        /// TOML 값 (true, 500, ["a", "b"]). TOML로 읽을 수 없으면 문자열로 기록
+        value: String,
+    },
+}
+
+/// `mp3tag auto`의 단계별 옵션.
+#[derive(clap::Args)]
+pub struct AutoArgs {
+    /// MP3 파일 또는 디렉토리. 여러 개, 글롭 패턴, 표준 입력 목록(-)을 줄 수 있음
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
+    /// 검색할 소스. 여러 번 지정 가능 (기본값: 사용할 수 있는 모든 소스)
+    #[arg(long = "source")]
+    pub sources: Vec<SourceKind>,
+    /// 자동 적용할 최소 일치 점수 (0.0~1.0)
+    #[arg(long, default_value_t = 0.8)]
+    pub min_score: f64,
+    /// 이 점수 이상 --min-score 미만인 결과는 끝에 모아 직접 고른다 (--min-score 이상이면 검토하지 않음)
+    #[arg(long, default_value_t = 0.5)]
+    pub review_score: f64,
+    /// 제목/아티스트/앨범이 모두 있는 파일도 다시 처리
+    #[arg(long)]
+    pub all: bool,
+    /// 온라인 검색 없이 파일명/폴더 추론만 사용
+    #[arg(long)]
+    pub no_search: bool,
+    /// Genius 크레딧/가사, Cover Art Archive, 아트 최소 크기 기준을 적용하지 않음
+    #[arg(long)]
+    pub no_enrich: bool,
+    /// 앨범 아트를 기록하지 않음
+    #[arg(long)]
+    pub no_art: bool,
+    /// 파일명을 바꾸지 않음
+    #[arg(long)]
+    pub no_rename: bool,
+    /// 대상 디렉토리 아래 "{앨범 아티스트}/{앨범}/" 폴더로 옮겨 정리
+    #[arg(long)]
+    pub organize: bool,
+    /// 기록/변경 없이 결과만 보고
+    #[arg(long)]
+    pub dry_run: bool,
+    /// 이전 실행의 진행 상태를 무시하고 처음부터 처리
+    #[arg(long)]
+    pub restart: bool,
+    /// 같은 오디오에 이전에 적용한 결과를 다시 쓰지 않고 새로 검색
+    #[arg(long)]
+    pub no_remember: bool,
+    /// 파일별 처리 결과를 HTML(.html) 또는 Markdown(.md) 보고서로 저장
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+    /// 신뢰 등급(exact/high/medium/low/unmatched)을 TXXX:MP3TAG_MATCH_TIER 프레임에 기록
+    #[arg(long)]
+    pub write_tier: bool,
+    /// 제목의 버전(Live, Remix, Acoustic, Sped Up, Radio Edit)이 파일과 다른 결과도 후보로 씀
+    #[arg(long)]
+    pub ignore_version: bool,
+}
+
+/// `search --limit` 값. 페이지 offset과 "다음 결과" 안내가 실제로 받은 수와 어긋나지 않도록
+/// 소스가 한 번에 돌려주는 최대 수(`Page::MAX_LIMIT`)를 넘는 값은 받지 않는다.
+fn parse_page_limit(value: &str) -> std::result::Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(limit @ 1..=Page::MAX_LIMIT) => Ok(limit),
+        _ => Err(tr!(
+            "1에서 {} 사이의 수여야 합니다",
+            "must be a number from 1 to {}",
+            Page::MAX_LIMIT
+        )),
+    }
+}
+
+/// `search --page`(1부터)와 `--limit`로 가져올 구간을 만든다. offset이 넘치면 에러.
+fn search_page(page: usize, limit: usize) -> Result<Page> {
+    let offset = page
+        .checked_sub(1)
+        .and_then(|p| p.checked_mul(limit))
+        .with_context(|| tr!("--page {}가 너무 큽니다", "--page {} is too large", page))?;
+    Ok(Page { offset, limit })
 }
 
 /// CLI 명령어를 분기하여 실행한다.
+/// 설정 파일을 한 번 읽고 전역 옵션으로 덮어쓴 뒤 각 명령어에 전달한다.
 pub fn run(cli: Cli) -> Result<()> {
+    let mut cfg = config::load_config()?;
+    if let Some(policy) = cli.mtime {
+        cfg.write.mtime = policy;
+    }
+    if cli.force {
+        cfg.write.force = true;
+    }
+    if let Some(size) = cli.min_art_size {
+        cfg.art.min_size = size;
+    }
+    if cli.cover_art_archive {
+        cfg.art.cover_art_archive = true;
+    }
+    if cli.offline {
+        cfg.network.offline = true;
+    }
+    if let Some(mode) = cli.backup {
+        cfg.write.backup = mode;
+    }
+    if cli.history {
+        cfg.write.history = true;
+    }
+
     match cli.command {
-        Some(Commands::Scan { directory }) => cmd_scan(&directory),
+        Some(Commands::Scan {
+            paths,
+            verify,
+            columns,
+            sort,
+            untagged_only,
+            missing_art,
+            format,
+        }) => {
+            let inputs = match paths.as_slice() {
+                [] => Inputs::one(&cfg.music_dir.clone().with_context(|| {
+                    tr!(
+                        "스캔할 디렉토리를 지정하거나 'mp3tag config set music_dir <경로>'로 설정하세요",
+                        "Give a directory to scan or set one with 'mp3tag config set music_dir <path>'"
+                    )
+                })?),
+                paths => Inputs::expand(paths)?,
+            };
+            let view = ScanView {
+                columns,
+                sort,
+                untagged_only,
+                missing_art,
+                format,
+            };
+            listing::set_art_warnings(&cfg.art);
+            cmd_scan(&cfg, &inputs, verify, &view)
+        }
+        Some(Commands::Show { file }) => cmd_show(&file),
+        Some(Commands::Frame {
+            file,
+            set,
+            delete,
+            list,
+        }) => cmd_frame(&cfg, &file, &set, &delete, list),
         Some(Commands::Edit {
             file,
+            preset,
             title,
             artist,
             album,
             album_artist,
             track,
             year,
+            date,
+            original_year,
             genre,
+            grouping,
+            work,
+            movement,
+            movement_number,
             album_art,
         }) => cmd_edit(
+            &cfg,
             &file,
+            preset.as_deref(),
             title,
             artist,
             album,
             album_artist,
             track,
             year,
+            date,
+            original_year,
             genre,
+            ClassicalFields {
+                grouping,
+                work,
+                movement,
+                movement_number,
+            },
             album_art,
         ),
-        Some(Commands::Fetch { path }) => cmd_fetch(&path),
-        Some(Commands::Config) => cmd_config(),
+        Some(Commands::Search {
+            query,
+            source,
+            limit,
+            page,
+            format,
+        }) => {
+            let source = source_or_default(source, &cfg, SourceKind::Spotify);
+            let page = search_page(page, limit)?;
+            cmd_search(&cfg, &query, source, page, format)
+        }
+        Some(Commands::Fetch {
+            paths,
+            source,
+            auto,
+            restart,
+            retag,
+            incomplete,
+            include_skipped,
+            id,
+            liked,
+            playlist,
+            report,
+            stage,
+            merge,
+        }) => {
+            if let Some(policy) = merge {
+                cfg.write.merge = policy;
+            }
+            let source = source_or_default(source, &cfg, SourceKind::Spotify);
+            let inputs = Inputs::expand(&paths)?;
+            let mut report =
+                Report::new(report, &format!("mp3tag fetch {}", inputs.root().display()))?;
+            let result = match (id, playlist) {
+                (Some(id), _) => {
+                    let [file] = inputs.paths() else {
+                        anyhow::bail!(i18n::pick(
+                            "--id는 파일 하나에만 적용할 수 있습니다",
+                            "--id can only be used with a single file"
+                        ));
+                    };
+                    cmd_fetch_by_id(&cfg, file, source, &id, &mut report)
+                }
+                (_, Some(playlist)) => cmd_fetch_playlist(&cfg, &inputs, &playlist, &mut report),
+                _ if liked => cmd_fetch_liked(&cfg, &inputs, &mut report),
+                _ => {
+                    let target = match incomplete {
+                        Some(fields) if fields.is_empty() => {
+                            FetchTarget::Incomplete(Field::ALL.to_vec())
+                        }
+                        Some(fields) => FetchTarget::Incomplete(fields),
+                        None if retag => FetchTarget::All,
+                        None => FetchTarget::Untagged,
+                    };
+                    cmd_fetch(
+                        &cfg,
+                        &inputs,
+                        source,
+                        &target,
+                        auto,
+                        restart,
+                        stage,
+                        include_skipped,
+                        &mut report,
+                    )
+                }
+            };
+            // 일부 실패나 중단으로 끝나도 그때까지의 보고서는 남긴다
+            report.save()?;
+            result
+        }
+        Some(Commands::Album {
+            directory,
+            query,
+            source,
+        }) => {
+            let source = source_or_default(source, &cfg, SourceKind::Melon);
+            cmd_album(&cfg, &directory, query, source)
+        }
+        Some(Commands::Audiobook {
+            directory,
+            title,
+            author,
+            lookup,
+            rename,
+            dry_run,
+        }) => cmd_audiobook(
+            &cfg,
+            &directory,
+            BookInfo {
+                title,
+                author,
+                ..Default::default()
+            },
+            lookup,
+            rename,
+            dry_run,
+        ),
+        Some(Commands::Split {
+            directory,
+            sources,
+            min_score,
+            organize,
+            dry_run,
+        }) => cmd_split(
+            &cfg,
+            &directory,
+            &sources,
+            min_score,
+            organize.as_deref(),
+            dry_run,
+        ),
+        Some(Commands::FromFilename {
+            paths,
+            pattern,
+            dry_run,
+            overwrite,
+            merge,
+        }) => {
+            let policy = match merge {
+                Some(policy) => policy,
+                None if overwrite => MergePolicy::Overwrite,
+                None => MergePolicy::Fill,
+            };
+            cmd_from_filename(
+                &cfg,
+                &Inputs::expand(&paths)?,
+                pattern.as_deref(),
+                dry_run,
+                policy,
+            )
+        }
+        Some(Commands::Auto(args)) => cmd_auto(&cfg, &args),
+        Some(Commands::Art { command }) => match command {
+            ArtCommand::Stats { paths, format } => {
+                cmd_art_stats(&cfg, &Inputs::expand(&paths)?, format)
+            }
+            ArtCommand::Dedupe {
+                paths,
+                max_size,
+                dry_run,
+            } => cmd_art_dedupe(&cfg, &Inputs::expand(&paths)?, max_size, dry_run),
+            ArtCommand::Check {
+                paths,
+                yes,
+                dry_run,
+            } => cmd_art_check(&cfg, &Inputs::expand(&paths)?, yes, dry_run),
+            ArtCommand::Upgrade {
+                paths,
+                below,
+                sources,
+                yes,
+                dry_run,
+            } => cmd_art_upgrade(
+                &cfg,
+                &Inputs::expand(&paths)?,
+                below,
+                &sources,
+                yes,
+                dry_run,
+            ),
+            ArtCommand::Fill {
+                paths,
+                sources,
+                dry_run,
+            } => cmd_art_fill(&cfg, &Inputs::expand(&paths)?, &sources, dry_run),
+        },
+        Some(Commands::MigrateLegacy {
+            paths,
+            dry_run,
+            overwrite,
+        }) => cmd_migrate_legacy(&cfg, &Inputs::expand(&paths)?, dry_run, overwrite),
+        Some(Commands::Totals { paths, dry_run }) => {
+            cmd_totals(&cfg, &Inputs::expand(&paths)?, dry_run)
+        }
+        Some(Commands::Dedupe {
+            paths,
+            threshold,
+            format,
+        }) => cmd_dedupe(&cfg, &Inputs::expand(&paths)?, threshold, format),
+        Some(Commands::Library { command }) => match command {
+            LibraryCommand::Scan { paths } => {
+                let inputs = match paths.as_slice() {
+                    [] => Inputs::one(&cfg.music_dir.clone().with_context(|| {
+                        tr!(
+                            "스캔할 디렉토리를 지정하거나 'mp3tag config set music_dir <경로>'로 설정하세요",
+                            "Give a directory to scan or set one with 'mp3tag config set music_dir <path>'"
+                        )
+                    })?),
+                    paths => Inputs::expand(paths)?,
+                };
+                cmd_library_scan(&cfg, &inputs)
+            }
+            LibraryCommand::Stats => cmd_library_stats(&cfg),
+            LibraryCommand::Export { output } => cmd_library_export(&cfg, &output),
+            LibraryCommand::Import {
+                input,
+                apply,
+                dry_run,
+            } => cmd_library_import(&cfg, &input, &apply, dry_run),
+        },
+        Some(Commands::Query {
+            query,
+            columns,
+            sort,
+            format,
+        }) => {
+            listing::set_art_warnings(&cfg.art);
+            cmd_query(&cfg, &query.join(" "), &columns, sort, format)
+        }
+        Some(Commands::Sync {
+            from,
+            to,
+            no_art,
+            overwrite_newer,
+            dry_run,
+        }) => cmd_sync(&cfg, &from, &to, !no_art, overwrite_newer, dry_run),
+        Some(Commands::ConvertTags {
+            from,
+            to,
+            no_art,
+            dry_run,
+        }) => cmd_convert_tags(&cfg, &from, &to, !no_art, dry_run),
+        Some(Commands::Checksum { command }) => match command {
+            ChecksumCommand::Create {
+                directory,
+                manifest,
+            } => cmd_checksum_create(&directory, manifest),
+            ChecksumCommand::Verify {
+                directory,
+                manifest,
+                all,
+                format,
+            } => cmd_checksum_verify(&directory, manifest, all, format),
+            ChecksumCommand::Modified { directory, since } => {
+                cmd_checksum_modified(&directory, since)
+            }
+        },
+        Some(Commands::Rename { paths, on_conflict }) => {
+            cmd_rename(&cfg, &Inputs::expand(&paths)?, on_conflict)
+        }
+        Some(Commands::Staged {
+            path,
+            save,
+            discard,
+            files,
+        }) => cmd_staged(&cfg, &path, save, discard, &files),
+        Some(Commands::History {
+            directory,
+            file,
+            limit,
+        }) => cmd_history(&directory, file.as_deref(), limit),
+        Some(Commands::Restore { file, list, from }) => cmd_restore(&cfg, &file, list, from),
+        Some(Commands::Now { source, player }) => {
+            let source = source_or_default(source, &cfg, SourceKind::Spotify);
+            cmd_now(&cfg, source, player)
+        }
+        Some(Commands::Serve { addr, allow_hosts }) => {
+            crate::server::serve(cfg, &addr, &allow_hosts)
+        }
+        Some(Commands::Config { command }) => match command {
+            None => cmd_config(),
+            Some(ConfigCommand::Show) => cmd_config_show(),
+            Some(ConfigCommand::Check) => cmd_config_check(&cfg),
+            Some(ConfigCommand::TestSelectors { query }) => {
+                cfg.network
+                    .ensure_online(i18n::pick("Melon 페이지를 확인할", "check the Melon pages"))?;
+                cmd_test_selectors(&cfg, &query)
+            }
+            Some(ConfigCommand::Setup) => cmd_setup(&cfg),
+            Some(ConfigCommand::Set { key, value }) => {
+                if key.starts_with("rename.") && key.ends_with("_template") {
+                    renamer::check_template(&value)?;
+                }
+                config::set_value(&key, &value)?;
+                println!("{}", tr!("{} 설정을 저장했습니다.", "Saved {}.", key));
+                Ok(())
+            }
+        },
+        Some(Commands::Login) => {
+            cfg.network
+                .ensure_online(i18n::pick("Spotify에 로그인할", "log in to Spotify"))?;
+            cmd_login()
+        }
         None => {
             if cli.gui {
                 #[cfg(feature = "gui")]
                 {
-                    crate::gui::launch(cli.directory);
+                    crate::gui::launch(cli.paths);
                     Ok(())
                 }
                 #[cfg(not(feature = "gui"))]
                 {
-                    anyhow::bail!(
-                        "GUI 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features gui"
-                    );
+                    anyhow::bail!(i18n::pick(
+                        "GUI 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features gui",
+                        "GUI is not enabled. Rebuild with: cargo build --features gui"
+                    ));
                 }
+            } else if !config::config_exists() && std::io::stdin().is_terminal() {
+                cmd_setup(&cfg)
             } else {
-                println!("사용법: mp3tag <명령어> 또는 mp3tag --gui");
-                println!("자세한 정보는 mp3tag --help를 실행하세요.");
+                println!(
+                    "{}",
+                    i18n::pick(
+                        "사용법: mp3tag <명령어> 또는 mp3tag --gui",
+                        "Usage: mp3tag <COMMAND> or mp3tag --gui"
+                    )
+                );
+                println!(
+                    "{}",
+                    i18n::pick(
+                        "자세한 정보는 mp3tag --help를 실행하세요.",
+                        "Run mp3tag --help for more information."
+                    )
+                );
                 Ok(())
             }
         }
     }
 }
 
+/// `--source`를 주지 않았으면 설정의 default_source를, 그것도 없으면 `fallback`을 쓴다.
+fn source_or_default(source: Option<SourceKind>, cfg: &Config, fallback: SourceKind) -> SourceKind {
+    source
+        .or_else(|| cfg.default_source.as_deref()?.parse().ok())
+        .unwrap_or(fallback)
+}
+
+/// `scan` 표의 열, 정렬, 필터 옵션.
+struct ScanView {
+    columns: Vec<Column>,
+    sort: Option<Column>,
+    untagged_only: bool,
+    missing_art: bool,
+    format: OutputFormat,
+}
+
 /// 디렉토리를 스캔하여 MP3 파일의 태그 현황을 테이블로 출력한다.
-fn cmd_scan(directory: &PathBuf) -> Result<()> {
-    let files = scanner::scan_directory(directory)?;
+/// `verify`이면 오디오 프레임 무결성도 검사해 손상된 파일을 따로 보고한다.
+fn cmd_scan(cfg: &Config, inputs: &Inputs, verify: bool, view: &ScanView) -> Result<()> {
+    let mut files = scan_with_progress(cfg, inputs)?;
 
     if files.is_empty() {
-        println!("{}에서 MP3 파일을 찾을 수 없습니다", directory.display());
-        return Ok(());
+        return Err(exit::nothing_matched(tr!(
+            "{}에서 MP3 파일을 찾을 수 없습니다",
+            "No MP3 files found in {}",
+            inputs.root().display()
+        )));
     }
 
-    let mut table = Table::new();
-    table.set_header(vec!["파일", "제목", "아티스트", "앨범", "태그"]);
+    let total = files.len();
+    let tagged = files.iter().filter(|f| f.has_tags).count();
+    files.retain(|f| {
+        (!view.untagged_only || !f.has_tags) && (!view.missing_art || listing::missing_art(f))
+    });
+    if let Some(column) = view.sort {
+        listing::sort_files(&mut files, column);
+    }
 
-    for file in &files {
-        let tags_status = if file.has_tags { "있음" } else { "없음" };
-        let (title, artist, album) = match &file.current_tags {
-            Some(t) => (
-                t.display_title().to_string(),
-                t.display_artist().to_string(),
-                t.display_album().to_string(),
-            ),
-            None => ("-".to_string(), "-".to_string(), "-".to_string()),
-        };
+    let legacy_files: Vec<(&Mp3File, String)> = files
+        .iter()
+        .filter_map(|f| {
+            let found = legacy::detect(&f.path).ok()??;
+            Some((f, found.kinds.join(", ")))
+        })
+        .collect();
 
-        table.add_row(vec![
-            Cell::new(file.filename()),
-            Cell::new(&title),
-            Cell::new(&artist),
-            Cell::new(&album),
-            Cell::new(tags_status),
-        ]);
+    let mut corrupt = Vec::new();
+    if verify {
+        let pb = progress_bar(files.len(), i18n::pick("무결성 검사", "Verifying"));
+        for file in &files {
+            pb.inc(1);
+            match audio::verify(&file.path) {
+                Ok(problems) if problems.is_empty() => {}
+                Ok(problems) => corrupt.push((
+                    file,
+                    problems
+                        .iter()
+                        .map(|p| p.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )),
+                Err(e) => corrupt.push((file, tr!("읽기 실패: {}", "read failed: {}", e))),
+            }
+        }
+        pb.finish_and_clear();
     }
 
-    println!("{table}");
+    let columns = match view.columns.as_slice() {
+        [] => listing::DEFAULT_COLUMNS,
+        columns => columns,
+    };
+    let rows: Vec<Vec<String>> = files
+        .iter()
+        .map(|file| columns.iter().map(|c| c.value(file)).collect())
+        .collect();
+
+    if view.format.is_delimited() {
+        // 파이프로 넘기는 출력이므로 머리글은 언어와 상관없는 열 이름을 쓰고, 요약은 생략한다
+        let header: Vec<&str> = columns.iter().map(|c| c.name()).collect();
+        print!("{}", output::render(view.format, &header, &rows));
+        for (file, reason) in &corrupt {
+            eprintln!("{}: {}", file.path.display(), reason);
+        }
+        return check_filtered(&files);
+    }
+
+    let header: Vec<&str> = columns.iter().map(|c| c.header()).collect();
+    if !files.is_empty() {
+        print!("{}", output::render(OutputFormat::Table, &header, &rows));
+    }
     println!(
-        "\n총 {} 파일 (태그 있음: {}, 태그 없음: {})",
-        files.len(),
-        files.iter().filter(|f| f.has_tags).count(),
-        files.iter().filter(|f| !f.has_tags).count(),
+        "\n{}",
+        tr!(
+            "총 {} 파일 (태그 있음: {}, 태그 없음: {})",
+            "{} files (tagged: {}, untagged: {})",
+            total,
+            tagged,
+            total - tagged
+        )
+    );
+    if files.len() < total {
+        println!("\n{}", tr!("{}개 파일 표시", "{} files shown", files.len()));
+    }
+
+    if !legacy_files.is_empty() {
+        println!(
+            "\n{}",
+            tr!(
+                "APEv2/Lyrics3 태그가 남은 파일 {}개 (mp3tag migrate-legacy로 ID3에 옮기고 삭제):",
+                "{} files still have APEv2/Lyrics3 tags (move them to ID3 with mp3tag migrate-legacy):",
+                legacy_files.len()
+            )
+        );
+        for (file, kinds) in &legacy_files {
+            println!("  {}: {}", file.path.display(), kinds);
+        }
+    }
+
+    if verify {
+        if corrupt.is_empty() {
+            println!("\n{}", i18n::pick("손상된 파일 없음", "No corrupt files"));
+        } else {
+            println!(
+                "\n{}",
+                tr!("손상된 파일 {}개:", "{} corrupt files:", corrupt.len())
+            );
+            for (file, reason) in &corrupt {
+                println!("  {}: {}", file.path.display(), reason);
+            }
+        }
+    }
+
+    check_filtered(&files)
+}
+
+/// `scan`의 필터에 맞는 파일이 없으면 대상 없음으로 끝낸다.
+fn check_filtered(files: &[Mp3File]) -> Result<()> {
+    if files.is_empty() {
+        return Err(exit::nothing_matched(i18n::pick(
+            "조건에 맞는 파일이 없습니다",
+            "No files match the filters",
+        )));
+    }
+    Ok(())
+}
+
+/// 파일의 ID3 태그 구성을 프레임 단위로 출력한다.
+fn cmd_show(file: &Path) -> Result<()> {
+    let dump = inspect::dump(file).with_context(|| {
+        tr!(
+            "태그를 읽을 수 없습니다: {}",
+            "Cannot read tags: {}",
+            file.display()
+        )
+    })?;
+    let file_len = std::fs::metadata(file)?.len();
+    println!(
+        "{}",
+        tr!(
+            "파일: {} ({}바이트)",
+            "File: {} ({} bytes)",
+            file.display(),
+            file_len
+        )
     );
 
+    match dump.version {
+        Some(ref version) => {
+            println!(
+                "{}",
+                tr!(
+                    "태그: {}, {}바이트 (패딩 {}바이트), 프레임 {}개",
+                    "Tag: {}, {} bytes ({} bytes padding), {} frames",
+                    version,
+                    dump.size,
+                    dump.padding,
+                    dump.frames.len()
+                )
+            );
+            print_frames(&dump);
+        }
+        None => println!(
+            "{}",
+            i18n::pick("태그: ID3v2 태그 없음", "Tag: no ID3v2 tag")
+        ),
+    }
+
+    println!("ID3v1: {}", yes_no(dump.id3v1));
+    if let Some(found) = legacy::detect(file)? {
+        println!(
+            "{}",
+            tr!("레거시 태그: {}", "Legacy tags: {}", found.kinds.join(", "))
+        );
+    }
+    if let Some(encoder) = audio::encoder_info(file) {
+        println!("{}", tr!("인코더: {}", "Encoder: {}", encoder.summary()));
+    }
+    if let Some(ms) = audio::duration_ms(file) {
+        println!(
+            "{}",
+            tr!(
+                "재생 시간: {}:{:02}",
+                "Duration: {}:{:02}",
+                ms / 60_000,
+                ms / 1000 % 60
+            )
+        );
+    }
+    Ok(())
+}
+
+/// 태그 유무를 "있음"/"없음"으로 표시한다.
+fn yes_no(present: bool) -> &'static str {
+    match present {
+        true => i18n::pick("있음", "yes"),
+        false => i18n::pick("없음", "no"),
+    }
+}
+
+/// 프레임 ID와 내용 요약을 표로 출력한다.
+fn print_frames(dump: &inspect::TagDump) {
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("프레임", "Frame"),
+        i18n::pick("내용", "Content"),
+    ]);
+    for (id, value) in &dump.frames {
+        table.add_row(vec![id.as_str(), value.as_str()]);
+    }
+    println!("{table}");
+}
+
+/// 지정한 프레임을 삭제하고 값을 기록한 뒤, 필요하면 프레임 목록을 출력한다.
+fn cmd_frame(
+    cfg: &Config,
+    file: &Path,
+    set: &[String],
+    delete: &[String],
+    list: bool,
+) -> Result<()> {
+    let mut edits: Vec<tagger::FrameEdit> = delete
+        .iter()
+        .map(|id| tagger::FrameEdit::Delete(id.clone()))
+        .collect();
+    for entry in set {
+        let (id, value) = entry.split_once('=').with_context(|| {
+            tr!(
+                "--set은 ID=값 형식이어야 합니다: {}",
+                "--set must be ID=VALUE: {}",
+                entry
+            )
+        })?;
+        edits.push(tagger::FrameEdit::Set(id.to_string(), value.to_string()));
+    }
+
+    if !edits.is_empty() {
+        tagger::edit_frames(file, &edits, &cfg.write).with_context(|| {
+            tr!(
+                "프레임을 기록할 수 없습니다: {}",
+                "Cannot write frames: {}",
+                file.display()
+            )
+        })?;
+        println!(
+            "{}",
+            tr!(
+                "프레임 변경 완료: 기록 {}개, 삭제 {}개",
+                "Frames changed: {} written, {} removed",
+                set.len(),
+                delete.len()
+            )
+        );
+    }
+    if list || edits.is_empty() {
+        let dump = inspect::dump(file).with_context(|| {
+            tr!(
+                "태그를 읽을 수 없습니다: {}",
+                "Cannot read tags: {}",
+                file.display()
+            )
+        })?;
+        if dump.frames.is_empty() {
+            println!("{}", i18n::pick("프레임 없음", "No frames"));
+        } else {
+            print_frames(&dump);
+        }
+    }
     Ok(())
 }
 
+/// `edit`의 클래식 음악 필드.
+struct ClassicalFields {
+    grouping: Option<String>,
+    work: Option<String>,
+    movement: Option<String>,
+    movement_number: Option<String>,
+}
+
+/// "1" 또는 "1/4" 형식의 악장 번호를 (번호, 전체 악장 수)로 나눈다.
+fn parse_movement_number(value: &str) -> Result<(Option<u32>, Option<u32>)> {
+    let parse = |s: &str| {
+        s.trim().parse::<u32>().with_context(|| {
+            tr!(
+                "악장 번호 형식이 올바르지 않습니다: {} (예: 1/4)",
+                "Invalid movement number: {} (e.g. 1/4)",
+                value
+            )
+        })
+    };
+    match value.split_once('/') {
+        Some((number, total)) => Ok((Some(parse(number)?), Some(parse(total)?))),
+        None => Ok((Some(parse(value)?), None)),
+    }
+}
+
 /// 지정된 필드를 MP3 파일의 ID3 태그에 기록한다.
 #[allow(clippy::too_many_arguments)]
 fn cmd_edit(
-    file: &PathBuf,
+    cfg: &Config,
+    file: &Path,
+    preset: Option<&str>,
     title: Option<String>,
     artist: Option<String>,
     album: Option<String>,
     album_artist: Option<String>,
     track: Option<u32>,
     year: Option<i32>,
+    date: Option<String>,
+    original_year: Option<i32>,
     genre: Option<String>,
+    classical: ClassicalFields,
     album_art_path: Option<PathBuf>,
 ) -> Result<()> {
-    let mp3 = scanner::load_single_file(file)?;
+    let preset_info = preset
+        .map(|name| cfg.preset(name)?.track_info())
+        .transpose()?;
+    if file.is_dir() {
+        if preset.is_none() {
+            anyhow::bail!(tr!(
+                "디렉토리는 --preset과 함께만 편집할 수 있습니다: {}",
+                "A directory can only be edited with --preset: {}",
+                file.display()
+            ));
+        }
+        if title.is_some() || track.is_some() {
+            anyhow::bail!(i18n::pick(
+                "디렉토리 전체에 같은 제목이나 트랙 번호를 기록할 수 없습니다",
+                "Cannot write the same title or track number to a whole directory"
+            ));
+        }
+    }
+
+    let release_date = match date {
+        Some(date) => Some(parser::normalize_release_date(&date).with_context(|| {
+            tr!(
+                "발매일 형식이 올바르지 않습니다: {} (예: 2019-11-18)",
+                "Invalid release date: {} (e.g. 2019-11-18)",
+                date
+            )
+        })?),
+        None => None,
+    };
+    let date_year = release_date.as_deref().and_then(|d| d[..4].parse().ok());
+    let (movement_number, movement_total) = match classical.movement_number {
+        Some(ref value) => parse_movement_number(value)?,
+        None => (None, None),
+    };
+    if let (Some(year), Some(date_year)) = (year, date_year) {
+        if year != date_year {
+            anyhow::bail!(tr!(
+                "--year({})와 --date의 연도({})가 다릅니다",
+                "--year ({}) does not match the year of --date ({})",
+                year,
+                date_year
+            ));
+        }
+    }
 
     let album_art = if let Some(ref art_path) = album_art_path {
-        Some(std::fs::read(art_path).context("앨범 아트 이미지를 읽을 수 없습니다")?)
+        Some(std::fs::read(art_path).context(i18n::pick(
+            "앨범 아트 이미지를 읽을 수 없습니다",
+            "Cannot read the album art image",
+        ))?)
     } else {
         None
     };
 
     let new_info = TrackInfo {
         title,
-        artist,
+        artists: artist.as_deref().map(split_artists).unwrap_or_default(),
         album,
         album_artist,
         track_number: track,
-        year,
+        year: year.or(date_year),
+        release_date,
+        original_year,
         genre,
+        grouping: classical.grouping,
+        work: classical.work,
+        movement: classical.movement,
+        movement_number,
+        movement_total,
         album_art,
-        album_art_url: None,
         source: "manual".to_string(),
+        ..Default::default()
     };
+    let new_info = match preset_info {
+        Some(preset_info) => {
+            tagger::merge_tags(&Some(preset_info), &new_info, MergePolicy::Overwrite)
+        }
+        None => new_info,
+    };
+
+    if file.is_dir() {
+        let files = scan_with_progress(cfg, &Inputs::one(file))?;
+        cancel::install();
+        let report = tagger::write_batch(
+            files.iter().map(|f| (f.path.as_path(), &new_info)),
+            &cfg.write,
+        );
+        print_batch_failures(&report);
+        println!(
+            "{}",
+            tr!(
+                "프리셋 '{}' 적용 완료: {}",
+                "Preset '{}' applied: {}",
+                preset.unwrap_or_default(),
+                report.summary()
+            )
+        );
+        refresh_media_server(cfg, report.written.len());
+        return exit::check_failures(report.failed.len(), files.len());
+    }
+
+    let mp3 = scanner::load_single_file(file)?;
+    let merged = tagger::merge_tags(&mp3.current_tags, &new_info, MergePolicy::Overwrite);
+    tagger::write_tags(file, &merged, &cfg.write)?;
+
+    println!(
+        "{}",
+        tr!(
+            "태그가 업데이트되었습니다: {}",
+            "Tags updated: {}",
+            file.display()
+        )
+    );
+    Ok(())
+}
+
+/// 태그가 없는 파일을 소스에서 검색하여 사용자 선택 후 적용한다.
+/// `auto`이면 선택 없이 첫 번째 결과를 적용한다.
+/// `fetch`가 검색할 파일.
+enum FetchTarget {
+    /// 태그가 없는 파일 (기본값)
+    Untagged,
+    /// 태그가 없거나 지정한 필드가 빠진 파일 (`--incomplete`)
+    Incomplete(Vec<Field>),
+    /// 모든 파일 (`--retag`)
+    All,
+}
+
+impl FetchTarget {
+    fn includes(&self, file: &Mp3File) -> bool {
+        match self {
+            FetchTarget::Untagged => !file.has_tags,
+            FetchTarget::Incomplete(fields) => {
+                !file.has_tags || fields.iter().any(|f| f.is_missing(file))
+            }
+            FetchTarget::All => true,
+        }
+    }
+
+    /// "태그가 없는 파일 N개를 찾았습니다"의 앞부분.
+    fn describe(&self) -> &'static str {
+        match self {
+            FetchTarget::Untagged => i18n::pick("태그가 없는 파일", "files without tags"),
+            FetchTarget::Incomplete(_) => i18n::pick(
+                "태그가 없거나 필드가 빠진 파일",
+                "files with no tags or missing fields",
+            ),
+            FetchTarget::All => i18n::pick("다시 검색할 파일", "files to search again"),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_fetch(
+    cfg: &Config,
+    inputs: &Inputs,
+    source: SourceKind,
+    target: &FetchTarget,
+    auto: bool,
+    restart: bool,
+    stage: bool,
+    include_skipped: bool,
+    report: &mut Report,
+) -> Result<()> {
+    let client = sources::create_source(&source, cfg)?;
+    let files = scan_with_progress(cfg, inputs)?;
+    let targets: Vec<_> = files.into_iter().filter(|f| target.includes(f)).collect();
+
+    if targets.is_empty() {
+        match target {
+            FetchTarget::Untagged => println!(
+                "{}",
+                i18n::pick(
+                    "모든 파일에 이미 태그가 있습니다.",
+                    "All files already have tags."
+                )
+            ),
+            _ => println!(
+                "{}",
+                i18n::pick("처리할 파일이 없습니다.", "No files to process.")
+            ),
+        }
+        return Ok(());
+    }
+
+    // 검색을 시작하기 전에 기록할 수 없는 파일을 걸러낸다
+    let (targets, blocked) = partition_writable(targets, cfg);
+    report_blocked(&blocked);
+    for (file, reason) in &blocked {
+        report.push(
+            &file.path,
+            EntryStatus::Skipped,
+            reason.as_str(),
+            None,
+            None,
+        );
+    }
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    let mut staged = stage
+        .then(|| StagedChanges::load(inputs.root()))
+        .transpose()?;
+    if auto {
+        let result = fetch_auto(
+            cfg,
+            client.as_ref(),
+            inputs.root(),
+            targets,
+            restart,
+            staged.as_mut(),
+            report,
+        );
+        finish_staging(staged.as_ref(), inputs.root())?;
+        return result;
+    }
+
+    // 이전에 건너뛴 파일은 다시 묻지 않는다. 목록이 비어 있으면 오디오 해시를 계산하지 않는다
+    let mut skip_list = SkipList::load(&cfg.network.cache_dir());
+    let targets = if include_skipped || skip_list.is_empty() {
+        targets
+    } else {
+        let (remembered, targets): (Vec<_>, Vec<_>) = targets.into_iter().partition(|f| {
+            audio::content_hash(&f.path).is_some_and(|hash| skip_list.contains(&hash))
+        });
+        if !remembered.is_empty() {
+            println!(
+                "{}",
+                tr!(
+                    "이전에 건너뛴 파일 {}개는 묻지 않습니다 (--include-skipped로 다시 확인).",
+                    "Not asking again about {} previously skipped files (--include-skipped to check them again).",
+                    remembered.len()
+                )
+            );
+        }
+        for file in &remembered {
+            report.push(
+                &file.path,
+                EntryStatus::Skipped,
+                i18n::pick(
+                    "이전에 건너뜀 (--include-skipped로 다시 확인)",
+                    "skipped earlier (--include-skipped to check again)",
+                ),
+                None,
+                None,
+            );
+        }
+        targets
+    };
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    let root = inference_root(inputs.root());
+    println!(
+        "{}",
+        tr!(
+            "{} {}개를 찾았습니다.\n",
+            "Found {1} {0}.\n",
+            target.describe(),
+            targets.len()
+        )
+    );
+    let enricher = Enricher::new(cfg)?;
+    let mut applied = 0;
+
+    for file in &targets {
+        println!("--- {} ---", file.filename());
+
+        let parsed = parser::search_info(file.current_tags.as_ref(), &file.path);
+        let query = parser::build_search_query(&parsed);
+
+        if query.is_empty() {
+            println!(
+                "{}",
+                i18n::pick(
+                    "  태그와 파일명에서 검색어를 생성할 수 없습니다. 건너뜁니다.\n",
+                    "  Cannot build a search query from the tags or file name. Skipping.\n"
+                )
+            );
+            report.push(
+                &file.path,
+                EntryStatus::Skipped,
+                i18n::pick(
+                    "태그와 파일명에서 검색어를 만들 수 없음",
+                    "cannot build a search query from the tags or file name",
+                ),
+                None,
+                None,
+            );
+            continue;
+        }
+
+        let fetched = fetch_interactive(
+            cfg,
+            &source,
+            client.as_ref(),
+            &enricher,
+            file,
+            root,
+            &parsed,
+            staged.as_mut(),
+            report,
+        );
+        // 중간에 멈춰도 그때까지 쌓은 변경은 남긴다
+        if fetched.is_err() {
+            finish_staging(staged.as_ref(), inputs.root())?;
+        }
+        match fetched? {
+            Fetched::Applied => {
+                applied += 1;
+                if !skip_list.is_empty() {
+                    if let Some(hash) = audio::content_hash(&file.path) {
+                        skip_list.remove(&hash);
+                        skip_list.save()?;
+                    }
+                }
+            }
+            Fetched::Skipped => {
+                if let Some(hash) = audio::content_hash(&file.path) {
+                    skip_list.add(hash, &file.path);
+                    skip_list.save()?;
+                }
+            }
+            // 검색이 실패했거나 결과가 없었으면 다음 실행에서 다시 묻는다
+            Fetched::NotFound => {}
+        }
+    }
+
+    println!("{}", i18n::pick("완료!", "Done!"));
+    if staged.is_none() {
+        refresh_media_server(cfg, applied);
+    }
+    finish_staging(staged.as_ref(), inputs.root())
+}
+
+/// `fetch --stage`로 쌓은 파일의 보고서 안내.
+fn staged_note() -> String {
+    i18n::pick(
+        "저장 대기 ('mp3tag staged --save'로 기록)",
+        "staged (write with 'mp3tag staged --save')",
+    )
+    .to_string()
+}
+
+/// 태그를 기록한다. `staged`가 있으면 기록하지 않고 저장 대기 목록에 쌓는다.
+fn write_or_stage(
+    cfg: &Config,
+    staged: Option<&mut StagedChanges>,
+    path: &Path,
+    track: &TrackInfo,
+) -> Result<()> {
+    match staged {
+        Some(staged) => {
+            staged.stage(path, track.clone(), &cfg.trust);
+            Ok(())
+        }
+        None => tagger::write_tags(path, track, &cfg.write),
+    }
+}
+
+/// `[write] protected`로 건너뛴 필드 안내 문구.
+fn protected_note(fields: &[&str]) -> Option<String> {
+    (!fields.is_empty()).then(|| {
+        tr!(
+            "보호됨, 건너뜀: {}",
+            "protected, skipped: {}",
+            fields.join(", ")
+        )
+    })
+}
+
+/// 소스 결과를 파일의 현재 태그와 `[write] merge`(`--merge`)에 따라 합친다.
+/// 덮어쓰기면 보호 필드만 되돌리고 소스 결과를 그대로 기록한다.
+fn merge_with_current(cfg: &Config, current: &Option<TrackInfo>, track: TrackInfo) -> TrackInfo {
+    let mut track = track;
+    tagger::keep_protected(current, &mut track, &cfg.write);
+    if cfg.write.merge == MergePolicy::Overwrite {
+        return track;
+    }
+    keep_current_fields(cfg.write.merge, current, &mut track);
+    tagger::merge_tags(current, &track, MergePolicy::Overwrite)
+}
+
+/// `track`에서 현재 태그 값을 지킬 필드를 비운다. `Interactive`는 값이 다른 필드마다 묻되,
+/// 터미널이 아니면 `Fill`처럼 모두 지킨다.
+fn keep_current_fields(policy: MergePolicy, current: &Option<TrackInfo>, track: &mut TrackInfo) {
+    let ask = policy == MergePolicy::Interactive && std::io::stdin().is_terminal();
+    tagger::drop_existing_fields(current, track, |field, old, new| match policy {
+        MergePolicy::Overwrite => false,
+        _ if ask => ask_keep_current(field, old, new),
+        _ => true,
+    });
+}
+
+/// 현재 값을 지킬지 묻는다. 답을 받지 못하면 지킨다.
+fn ask_keep_current(field: &str, old: &str, new: &str) -> bool {
+    let short = |value: &str| {
+        let mut text: String = value.replace('\n', " ").chars().take(40).collect();
+        if value.chars().count() > 40 {
+            text.push('…');
+        }
+        text
+    };
+    Select::new()
+        .with_prompt(tr!("  {} 값이 다릅니다", "  {} differs", field))
+        .items(&[
+            tr!("기존 값 유지: {}", "Keep existing: {}", short(old)),
+            tr!("새 값 사용: {}", "Use new: {}", short(new)),
+        ])
+        .default(0)
+        .interact()
+        .map(|i| i == 0)
+        .unwrap_or(true)
+}
+
+/// 쌓은 변경을 저장하고 확인 방법을 안내한다. `staged`가 없으면 아무것도 하지 않는다.
+fn finish_staging(staged: Option<&StagedChanges>, path: &Path) -> Result<()> {
+    let Some(staged) = staged else {
+        return Ok(());
+    };
+    staged.save()?;
+    if !staged.is_empty() {
+        println!(
+            "{}",
+            tr!(
+                "저장 대기 {}건: 'mp3tag staged {}'로 확인하고 --save로 기록하거나 --discard로 취소하세요.",
+                "{} staged: review with 'mp3tag staged {}', then --save to write or --discard to drop.",
+                staged.len(),
+                path.display()
+            )
+        );
+    }
+    Ok(())
+}
+
+/// `search`(아티스트/제목)로 `kind` 소스를 검색해 사용자가 고른 결과를 파일 하나에 적용한다.
+/// 검색 실패나 결과가 마음에 들지 않으면 검색어를 고치거나 다른 소스로 다시 검색할 수 있고,
+/// 사용자가 건너뛰면 결과 목록을 보고 건너뛰었는지(`Fetched::Skipped`), 보여 줄 결과가 없었는지
+/// (`Fetched::NotFound`)를 반환한다.
+#[allow(clippy::too_many_arguments)]
+fn fetch_interactive(
+    cfg: &Config,
+    kind: &SourceKind,
+    client: &dyn MusicSource,
+    enricher: &Enricher,
+    file: &Mp3File,
+    root: &Path,
+    search: &TrackInfo,
+    staged: Option<&mut StagedChanges>,
+    report: &mut Report,
+) -> Result<Fetched> {
+    let preference = cfg.matching.album_preference();
+    // 검색어를 고치거나 소스를 바꾸면 처음부터 다시 검색한다
+    let mut search = search.clone();
+    let mut switched: Option<(SourceKind, Box<dyn MusicSource>)> = None;
+    let (selected, retry_note) = 'search: loop {
+        let (kind, client) = match switched {
+            Some((ref kind, ref client)) => (kind, client.as_ref()),
+            None => (kind, client),
+        };
+        println!(
+            "{}",
+            tr!(
+                "  검색 중 ({}): {}",
+                "  Searching ({}): {}",
+                kind.label(),
+                parser::build_search_query(&search)
+            )
+        );
+
+        let (mut results, retried, failure) =
+            match search_or_infer(cfg, client, file, root, &search) {
+                Ok((results, retried)) => (results, retried, None),
+                Err(e) => {
+                    println!("{}", tr!("  검색 실패: {}", "  Search failed: {}", e));
+                    (
+                        Vec::new(),
+                        None,
+                        Some(tr!("검색 실패: {}", "Search failed: {}", e)),
+                    )
+                }
+            };
+        if results.is_empty() && failure.is_none() {
+            println!("{}", i18n::pick("  검색 결과가 없습니다.", "  No results."));
+        }
+
+        // 결과가 한 페이지를 채웠으면 같은 검색어로 다음 페이지를 더 가져올 수 있다
+        let query = retried
+            .as_ref()
+            .map(|v| v.query.clone())
+            .unwrap_or_else(|| parser::build_search_query(&search));
+        let mut has_more = results.len() >= Page::LIMIT;
+        let retry_note = retried.map(|v| retry_note(&v));
+        if let Some(ref note) = retry_note {
+            println!("  {}", note);
+        }
+
+        // 아트 크기 확인을 고르면 후보마다 아트를 받아 목록에 덧붙이고 다시 묻는다
+        let mut arts: Option<Vec<String>> = None;
+        // 같은 곡이 여러 앨범 판에 실렸으면 선호하는 판 하나만 보이고, 펼치면 나머지를 그 아래에 보인다
+        let mut expanded = false;
+        loop {
+            let item = |i: usize| {
+                let r = &results[i];
+                let mut item = format!(
+                    "{}{}  ({})",
+                    r.summary(),
+                    variants::marker_suffix(r),
+                    r.details()
+                );
+                if let Some(ref arts) = arts {
+                    item += &tr!(" 아트: {}", " art: {}", arts[i]);
+                }
+                item
+            };
+            let groups = variants::group_results(&results, &preference);
+            let mut choices: Vec<(String, Candidate)> = Vec::new();
+            for group in &groups {
+                let mut primary = item(group.primary);
+                if !expanded && !group.others.is_empty() {
+                    primary += &tr!(
+                        " (다른 앨범 {}개)",
+                        " ({} other albums)",
+                        group.others.len()
+                    );
+                }
+                choices.push((primary, Candidate::Track(group.primary)));
+                if expanded {
+                    for &i in &group.others {
+                        let edition = variants::edition(&results[i]).label();
+                        choices.push((
+                            format!("  └ {} [{}]", item(i), edition),
+                            Candidate::Track(i),
+                        ));
+                    }
+                }
+            }
+            if groups.iter().any(|g| !g.others.is_empty()) {
+                let label = match expanded {
+                    false => i18n::pick(
+                        "같은 곡의 다른 앨범 펼치기",
+                        "Show other albums of the same song",
+                    ),
+                    true => i18n::pick(
+                        "같은 곡의 다른 앨범 접기",
+                        "Hide other albums of the same song",
+                    ),
+                };
+                choices.push((label.to_string(), Candidate::ToggleVariants));
+            }
+            if has_more {
+                choices.push((
+                    i18n::pick("결과 더 보기", "Show more results").to_string(),
+                    Candidate::ShowMore,
+                ));
+            }
+            if arts.is_none() && !results.is_empty() {
+                choices.push((
+                    i18n::pick("앨범 아트 크기 확인", "Check album art size").to_string(),
+                    Candidate::PreviewArt,
+                ));
+            }
+            choices.push((
+                i18n::pick(
+                    "검색어 수정 후 다시 검색",
+                    "Edit the query and search again",
+                )
+                .to_string(),
+                Candidate::EditQuery,
+            ));
+            choices.push((
+                i18n::pick("다른 소스로 다시 검색", "Search another source").to_string(),
+                Candidate::SwitchSource,
+            ));
+            choices.push((
+                i18n::pick("이 파일 건너뛰기", "Skip this file").to_string(),
+                Candidate::Skip,
+            ));
+
+            let items: Vec<&str> = choices.iter().map(|(item, _)| item.as_str()).collect();
+            let choice = Select::new()
+                .with_prompt(i18n::pick("  트랙을 선택하세요", "  Choose a track"))
+                .items(&items)
+                .default(0)
+                .interact()?;
+            match choices[choice].1 {
+                Candidate::Track(i) => {
+                    break 'search (with_detail(client, &results[i]), retry_note);
+                }
+                Candidate::ToggleVariants => expanded = !expanded,
+                Candidate::ShowMore => {
+                    let page = Page {
+                        offset: results.len(),
+                        limit: Page::LIMIT,
+                    };
+                    match client.search_page(&query, page) {
+                        Ok(more) => {
+                            has_more = more.len() >= page.limit;
+                            if more.is_empty() {
+                                println!(
+                                    "{}",
+                                    i18n::pick(
+                                        "  더 이상 검색 결과가 없습니다.",
+                                        "  No more results."
+                                    )
+                                );
+                            }
+                            results.extend(more);
+                            arts = None;
+                        }
+                        Err(e) => println!("{}", tr!("  검색 실패: {}", "  Search failed: {}", e)),
+                    }
+                }
+                Candidate::PreviewArt => arts = Some(preview_arts(cfg, client, &results)),
+                Candidate::EditQuery => {
+                    let query: String = Input::new()
+                        .with_prompt(i18n::pick("  검색어", "  Search query"))
+                        .with_initial_text(parser::build_search_query(&search))
+                        .interact_text()?;
+                    // 빈 검색어는 무시하고 목록으로 돌아간다
+                    if let Some(edited) = edited_search(&query) {
+                        search = edited;
+                        continue 'search;
+                    }
+                }
+                Candidate::SwitchSource => {
+                    let others = other_sources(cfg, kind);
+                    let labels: Vec<&str> = others.iter().map(|k| k.label()).collect();
+                    let Some(i) = Select::new()
+                        .with_prompt(i18n::pick("  검색할 소스", "  Source to search"))
+                        .items(&labels)
+                        .default(0)
+                        .interact_opt()?
+                    else {
+                        continue;
+                    };
+                    match sources::create_source(&others[i], cfg) {
+                        Ok(client) => {
+                            switched = Some((others[i].clone(), client));
+                            continue 'search;
+                        }
+                        Err(e) => {
+                            println!(
+                                "{}",
+                                tr!(
+                                    "  {}을(를) 사용할 수 없습니다: {}",
+                                    "  Cannot use {}: {}",
+                                    others[i].label(),
+                                    e
+                                )
+                            )
+                        }
+                    }
+                }
+                Candidate::Skip => {
+                    println!("{}", i18n::pick("  건너뛰었습니다.\n", "  Skipped.\n"));
+                    let (reason, fetched) = skip_reason(failure, results.is_empty());
+                    report.push(&file.path, EntryStatus::Skipped, reason, None, None);
+                    return Ok(fetched);
+                }
+            }
+        }
+    };
+
+    let track = selected;
+    let (track, note) = enricher.apply(file.current_tags.as_ref(), track);
+    if let Some(ref note) = note {
+        println!("  {}", note);
+    }
+    let track = merge_with_current(cfg, &file.current_tags, track);
+    let staging = staged.is_some();
+    write_or_stage(cfg, staged, &file.path, &track)?;
+    let note = if staging {
+        println!(
+            "{}",
+            tr!(
+                "  저장 대기에 추가했습니다: {}\n",
+                "  Staged: {}\n",
+                track.summary()
+            )
+        );
+        join_notes(join_notes(retry_note, note), Some(staged_note()))
+    } else {
+        println!(
+            "{}",
+            tr!(
+                "  태그가 적용되었습니다: {}\n",
+                "  Tags applied: {}\n",
+                track.summary()
+            )
+        );
+        join_notes(retry_note, note)
+    };
+    report_applied(report, &file.path, &track, note.as_deref(), None, None);
+    Ok(Fetched::Applied)
+}
+
+/// `fetch_interactive`의 결과.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fetched {
+    /// 고른 결과를 기록했거나 저장 대기에 넣음
+    Applied,
+    /// 결과 목록을 보고 건너뜀. 건너뛴 파일 목록에 남겨 다음 실행에서 묻지 않는다
+    Skipped,
+    /// 검색이 실패했거나 결과가 없어 건너뜀. 다음 실행에서 다시 검색한다
+    NotFound,
+}
+
+/// "검색어 수정"으로 입력한 검색어를 검색할 태그로 만든다. 아티스트/제목을 나누지 않고
+/// 입력한 그대로 검색한다. 비어 있으면 None.
+fn edited_search(query: &str) -> Option<TrackInfo> {
+    let query = query.trim();
+    (!query.is_empty()).then(|| TrackInfo {
+        title: Some(query.to_string()),
+        ..Default::default()
+    })
+}
+
+/// "다른 소스로 다시 검색"에서 고를 수 있는 소스 (지금 검색한 `kind` 제외).
+fn other_sources(cfg: &Config, kind: &SourceKind) -> Vec<SourceKind> {
+    SourceKind::all(cfg)
+        .into_iter()
+        .filter(|k| k != kind)
+        .collect()
+}
+
+/// 건너뛴 파일의 보고서 사유와 결과. 결과 목록을 보고 건너뛴 것만 `Fetched::Skipped`다.
+fn skip_reason(failure: Option<String>, no_results: bool) -> (String, Fetched) {
+    match (failure, no_results) {
+        (Some(failure), _) => (failure, Fetched::NotFound),
+        (None, true) => (
+            i18n::pick("검색 결과 없음", "no results").to_string(),
+            Fetched::NotFound,
+        ),
+        (None, false) => (
+            i18n::pick("사용자가 건너뜀", "skipped by user").to_string(),
+            Fetched::Skipped,
+        ),
+    }
+}
+
+/// `fetch_interactive`의 선택 목록 항목.
+#[derive(Clone, Copy)]
+enum Candidate {
+    Track(usize),
+    ToggleVariants,
+    ShowMore,
+    PreviewArt,
+    EditQuery,
+    SwitchSource,
+    Skip,
+}
+
+/// 후보마다 앨범 아트를 받아 "1000x1000 JPEG 245.3 KB" 형식으로 요약한다. `[art]` 경고 기준을 넘으면 덧붙인다.
+fn preview_arts(cfg: &Config, client: &dyn MusicSource, results: &[TrackInfo]) -> Vec<String> {
+    println!(
+        "{}",
+        i18n::pick("  앨범 아트를 확인하는 중...", "  Checking album art...")
+    );
+    results
+        .iter()
+        .map(|track| {
+            if track.album_art.is_none() && track.album_art_url.is_none() {
+                return i18n::pick("없음", "none").to_string();
+            }
+            let data = match &track.album_art {
+                Some(data) => data.clone(),
+                None => match client.fetch_album_art(track) {
+                    Ok(data) => data,
+                    Err(_) => return i18n::pick("받을 수 없음", "unavailable").to_string(),
+                },
+            };
+            let info = ArtInfo::of(&data);
+            match info.warning(cfg.art.warn_max_bytes(), cfg.art.warn_min_size()) {
+                Some(warning) => format!("{} ({})", info.summary(), warning),
+                None => info.summary(),
+            }
+        })
+        .collect()
+}
+
+/// 플레이어에서 지금 재생 중인 파일을 찾아 온라인 검색 또는 직접 편집으로 태그를 고친다.
+fn cmd_now(cfg: &Config, source: SourceKind, player: Option<Player>) -> Result<()> {
+    let path = now_playing::current_file(&cfg.mpd, player)?;
+    let file = scanner::load_single_file(&path)?;
+    tagger::check_writable(&file.path, &cfg.write)?;
+
+    println!("{}", tr!("재생 중: {}", "Playing: {}", file.path.display()));
+    match file.current_tags {
+        Some(ref tags) => println!(
+            "{}",
+            tr!("현재 태그: {}\n", "Current tags: {}\n", tags.summary())
+        ),
+        None => println!(
+            "{}",
+            i18n::pick("현재 태그: 없음\n", "Current tags: none\n")
+        ),
+    }
+
+    let actions = [
+        i18n::pick("온라인에서 태그 가져오기", "Fetch tags online"),
+        i18n::pick("직접 편집", "Edit manually"),
+        i18n::pick("취소", "Cancel"),
+    ];
+    let action = Select::new()
+        .with_prompt(i18n::pick("무엇을 할까요?", "What would you like to do?"))
+        .items(&actions)
+        .default(0)
+        .interact()?;
+
+    match action {
+        0 => {
+            let default_query = file
+                .current_tags
+                .as_ref()
+                .map(parser::build_search_query)
+                .filter(|q| !q.is_empty())
+                .unwrap_or_else(|| parser::build_search_query(&parser::parse_filename(&file.path)));
+            let query: String = Input::new()
+                .with_prompt(i18n::pick("검색어", "Search query"))
+                .with_initial_text(default_query)
+                .interact_text()?;
+
+            let client = sources::create_source(&source, cfg)?;
+            let enricher = Enricher::new(cfg)?;
+            let root = cfg
+                .mpd
+                .music_dir
+                .as_deref()
+                .filter(|dir| file.path.starts_with(dir))
+                .unwrap_or_else(|| inference_root(&file.path));
+            let search = TrackInfo {
+                title: Some(query),
+                ..Default::default()
+            };
+            fetch_interactive(
+                cfg,
+                &source,
+                client.as_ref(),
+                &enricher,
+                &file,
+                root,
+                &search,
+                None,
+                &mut Report::new(None, "")?,
+            )?;
+        }
+        1 => edit_interactive(cfg, &file)?,
+        _ => println!("{}", i18n::pick("취소했습니다.", "Cancelled.")),
+    }
+    Ok(())
+}
+
+/// 주요 필드를 현재 값을 기본값으로 하나씩 입력받아 기록한다. 빈 값은 기존 태그를 유지한다.
+fn edit_interactive(cfg: &Config, file: &Mp3File) -> Result<()> {
+    let current = file.current_tags.clone().unwrap_or_default();
+    let prompt = |label: &str, value: Option<String>| -> Result<Option<String>> {
+        let input: String = Input::new()
+            .with_prompt(label)
+            .with_initial_text(value.unwrap_or_default())
+            .allow_empty(true)
+            .interact_text()?;
+        Ok(Some(input.trim().to_string()).filter(|s| !s.is_empty()))
+    };
+
+    let new_info = TrackInfo {
+        title: prompt(i18n::pick("제목", "Title"), current.title.clone())?,
+        artists: prompt(i18n::pick("아티스트", "Artist"), current.artist())?
+            .as_deref()
+            .map(split_artists)
+            .unwrap_or_default(),
+        album: prompt(i18n::pick("앨범", "Album"), current.album.clone())?,
+        album_artist: prompt(
+            i18n::pick("앨범 아티스트", "Album artist"),
+            current.album_artist.clone(),
+        )?,
+        track_number: prompt(
+            i18n::pick("트랙 번호", "Track number"),
+            current.track_number.map(|n| n.to_string()),
+        )?
+        .and_then(|s| s.parse().ok()),
+        year: prompt(
+            i18n::pick("연도", "Year"),
+            current.year.map(|y| y.to_string()),
+        )?
+        .and_then(|s| s.parse().ok()),
+        release_date: prompt(
+            i18n::pick("발매일", "Release date"),
+            current.release_date.clone(),
+        )?
+        .and_then(|s| parser::normalize_release_date(&s)),
+        genre: prompt(i18n::pick("장르", "Genre"), current.genre.clone())?,
+        source: "manual".to_string(),
+        ..Default::default()
+    };
+
+    let merged = tagger::merge_tags(&file.current_tags, &new_info, MergePolicy::Overwrite);
+    tagger::write_tags(&file.path, &merged, &cfg.write)?;
+    println!(
+        "{}",
+        tr!(
+            "태그가 업데이트되었습니다: {}",
+            "Tags updated: {}",
+            merged.summary()
+        )
+    );
+    Ok(())
+}
+
+/// 검색 없이 트랙 ID(또는 URL)로 곡을 가져와 파일 하나에 태그를 적용한다.
+fn cmd_fetch_by_id(
+    cfg: &Config,
+    path: &Path,
+    source: SourceKind,
+    id: &str,
+    report: &mut Report,
+) -> Result<()> {
+    if path.is_dir() {
+        anyhow::bail!(tr!(
+            "--id는 파일 하나에만 사용할 수 있습니다: {}",
+            "--id can only be used with a single file: {}",
+            path.display()
+        ));
+    }
+    tagger::check_writable(path, &cfg.write)?;
+
+    let client = sources::create_source(&source, cfg)?;
+    let track = client.get_track(id)?;
+    println!(
+        "{}",
+        tr!(
+            "{}에서 찾은 곡: {}",
+            "Found on {}: {}",
+            source.label(),
+            track.summary()
+        )
+    );
+
+    let track = with_detail(client.as_ref(), &track);
+    let existing = tagger::read_tags(path).ok().flatten();
+    let (track, note) = Enricher::new(cfg)?.apply(existing.as_ref(), track);
+    if let Some(ref note) = note {
+        println!("{}", note);
+    }
+    let track = merge_with_current(cfg, &existing, track);
+    tagger::write_tags(path, &track, &cfg.write)?;
+    println!(
+        "{}",
+        tr!(
+            "태그가 적용되었습니다: {}",
+            "Tags applied: {}",
+            track.summary()
+        )
+    );
+    report_applied(report, path, &track, note.as_deref(), None, None);
+    Ok(())
+}
+
+/// Spotify에서 좋아요 표시한 곡을 가져와 제목/아티스트가 일치하는 로컬 파일에 태그를 적용한다.
+/// 이미 태그가 있는 파일도 대상에 포함하며, 적용 전에 매칭 결과를 확인받는다.
+fn cmd_fetch_liked(cfg: &Config, inputs: &Inputs, report: &mut Report) -> Result<()> {
+    cfg.network
+        .ensure_online(i18n::pick("좋아요 표시한 곡을 가져올", "fetch liked songs"))?;
+    let client = SpotifyClient::with_user_login(&cfg.spotify)?;
+    let files = library_files(cfg, inputs)?;
+
+    let pb = progress_bar(0, i18n::pick("좋아요 표시한 곡", "Liked songs"));
+    let liked = client.liked_tracks(|done, total| {
+        pb.set_length(total as u64);
+        pb.set_position(done as u64);
+    })?;
+    pb.finish_and_clear();
+    println!(
+        "{}",
+        tr!(
+            "좋아요 표시한 곡 {}개를 가져왔습니다.",
+            "Fetched {} liked songs.",
+            liked.len()
+        )
+    );
+
+    apply_library_tracks(cfg, &client, &files, &liked, false, report)
+}
+
+/// Spotify 플레이리스트의 트랙 목록을 가져와 제목/아티스트/재생 시간이 일치하는 로컬 파일에
+/// 태그를 적용한다. 트랙 번호는 플레이리스트 순서로 기록한다.
+/// 로그인되어 있으면 비공개 플레이리스트도 읽을 수 있다.
+fn cmd_fetch_playlist(
+    cfg: &Config,
+    inputs: &Inputs,
+    playlist: &str,
+    report: &mut Report,
+) -> Result<()> {
+    cfg.network
+        .ensure_online(i18n::pick("플레이리스트를 가져올", "fetch the playlist"))?;
+    let client = if cfg.spotify.refresh_token.is_some() {
+        SpotifyClient::with_user_login(&cfg.spotify)?
+    } else if cfg.spotify.is_configured() {
+        SpotifyClient::new(&cfg.spotify)?
+    } else {
+        return Err(exit::config_error(i18n::pick(
+            "Spotify가 설정되지 않았습니다. 먼저 'mp3tag config'를 실행하세요.",
+            "Spotify is not set up. Run 'mp3tag config' first.",
+        )));
+    };
+    let files = library_files(cfg, inputs)?;
+
+    let pb = progress_bar(0, i18n::pick("플레이리스트", "Playlist"));
+    let (name, tracks) = client.playlist(playlist, |done, total| {
+        pb.set_length(total as u64);
+        pb.set_position(done as u64);
+    })?;
+    pb.finish_and_clear();
+    println!(
+        "{}",
+        tr!(
+            "플레이리스트 '{}'에서 {}곡을 가져왔습니다.",
+            "Fetched playlist '{}' ({} tracks).",
+            name,
+            tracks.len()
+        )
+    );
+
+    apply_library_tracks(cfg, &client, &files, &tracks, true, report)
+}
+
+/// 좋아요/플레이리스트 태깅 대상 파일을 스캔한다. 기록할 수 없는 파일은 사유와 함께 알리고 제외한다.
+fn library_files(cfg: &Config, inputs: &Inputs) -> Result<Vec<Mp3File>> {
+    let files = scan_with_progress(cfg, inputs)?;
+    let (files, blocked) = partition_writable(files, cfg);
+    report_blocked(&blocked);
+    if files.is_empty() {
+        return Err(exit::nothing_matched(tr!(
+            "{}에서 태그를 기록할 MP3 파일을 찾을 수 없습니다",
+            "No MP3 files to tag found in {}",
+            inputs.root().display()
+        )));
+    }
+    Ok(files)
+}
+
+/// 기록할 수 없어 건너뛰는 파일 목록을 출력한다.
+fn report_blocked(blocked: &[(Mp3File, String)]) {
+    if blocked.is_empty() {
+        return;
+    }
+    println!(
+        "{}",
+        tr!(
+            "기록할 수 없는 파일 {}개를 건너뜁니다:",
+            "Skipping {} files that cannot be written:",
+            blocked.len()
+        )
+    );
+    for (file, reason) in blocked {
+        println!("  {}: {}", file.filename(), reason);
+    }
+    println!();
+}
+
+/// Spotify 트랙 목록(좋아요 표시한 곡, 플레이리스트)과 일치하는 파일을 보여주고 확인받은 뒤 태그를 적용한다.
+/// `playlist_order`이면 트랙 번호를 목록 순서로, 전체 트랙 수를 목록 길이로 기록한다.
+fn apply_library_tracks(
+    cfg: &Config,
+    client: &SpotifyClient,
+    files: &[Mp3File],
+    tracks: &[TrackInfo],
+    playlist_order: bool,
+    report: &mut Report,
+) -> Result<()> {
+    let durations: Vec<Option<u64>> = files.iter().map(|f| audio::duration_ms(&f.path)).collect();
+    let assignment = matcher::match_library_tracks(files, &durations, tracks);
+    let matched: Vec<(&Mp3File, usize)> = files
+        .iter()
+        .zip(&assignment)
+        .filter_map(|(file, j)| j.map(|j| (file, j)))
+        .collect();
+    if matched.is_empty() {
+        return Err(exit::nothing_matched(i18n::pick(
+            "목록의 곡과 일치하는 파일이 없습니다",
+            "No files match the songs in the list",
+        )));
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("파일", "File"),
+        i18n::pick("번호", "No."),
+        i18n::pick("곡", "Track"),
+    ]);
+    for (file, j) in &matched {
+        table.add_row(vec![
+            Cell::new(file.filename()),
+            Cell::new(j + 1),
+            Cell::new(tracks[*j].summary()),
+        ]);
+    }
+    println!("\n{table}");
+    println!(
+        "{}",
+        tr!(
+            "{}개 중 {}개 파일이 일치합니다.",
+            "{} of {} files matched.",
+            files.len(),
+            matched.len()
+        )
+    );
+
+    if !Confirm::new()
+        .with_prompt(tr!(
+            "{}개 파일에 태그를 적용할까요?",
+            "Apply tags to {} files?",
+            matched.len()
+        ))
+        .default(true)
+        .interact()?
+    {
+        return Ok(());
+    }
+
+    let enricher = Enricher::new(cfg)?;
+    let mut applied = 0;
+    let mut failed = 0;
+    cancel::install();
+    let pb = progress_bar(matched.len(), i18n::pick("태그 적용", "Applying tags"));
+    for (file, j) in matched {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let track = client
+            .fetch_detail(&tracks[j])
+            .unwrap_or_else(|_| tracks[j].clone());
+        let (mut track, note) = enricher.apply(file.current_tags.as_ref(), track);
+        if let Some(ref note) = note {
+            pb.suspend(|| println!("{}: {}", file.filename(), note));
+        }
+        if playlist_order {
+            track.track_number = Some(j as u32 + 1);
+            track.total_tracks = Some(tracks.len() as u32);
+        }
+        let track = pb.suspend(|| merge_with_current(cfg, &file.current_tags, track));
+        match tagger::write_tags(&file.path, &track, &cfg.write) {
+            Ok(()) => {
+                applied += 1;
+                report_applied(report, &file.path, &track, note.as_deref(), None, None);
+            }
+            Err(e) => {
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 기록 실패 ({})",
+                            "{}: write failed ({})",
+                            file.filename(),
+                            e
+                        )
+                    )
+                });
+                report.push(&file.path, EntryStatus::Failed, e.to_string(), None, None);
+                failed += 1;
+            }
+        }
+    }
+    pb.finish_and_clear();
+
+    println!(
+        "\n{}",
+        tr!(
+            "태그 적용 완료: 성공 {}건, 실패 {}건",
+            "Tags applied: {} succeeded, {} failed",
+            applied,
+            failed
+        )
+    );
+    refresh_media_server(cfg, applied);
+    exit::check_failures(failed, applied + failed)
+}
+
+/// 파일을 검색한다. 결과가 없으면 단순화한 검색어로 다시 검색하고, 그 검색어로 찾았으면 함께 반환한다.
+/// 오프라인 모드에서 캐시된 검색 결과가 없으면
+/// 파일명과 폴더 구조(`root` 기준)로 추론한 태그를 유일한 결과로 사용한다.
+fn search_or_infer(
+    cfg: &Config,
+    source: &dyn MusicSource,
+    file: &Mp3File,
+    root: &Path,
+    search: &TrackInfo,
+) -> Result<(Vec<TrackInfo>, Option<QueryVariant>)> {
+    let result = sources::search_variants(source, search);
+    if !cfg.network.offline || matches!(result, Ok((ref r, _)) if !r.is_empty()) {
+        return result;
+    }
+    let inferred = parser::infer_from_path(&file.path, root);
+    if inferred.title.is_none() {
+        return Ok((Vec::new(), None));
+    }
+    Ok((
+        vec![TrackInfo {
+            source: "filename".to_string(),
+            ..inferred
+        }],
+        None,
+    ))
+}
+
+/// 단순화한 검색어로 찾았다는 안내.
+fn retry_note(variant: &QueryVariant) -> String {
+    tr!(
+        "{}한 검색어로 찾음: {}",
+        "Found after {}: {}",
+        variant.label,
+        variant.query
+    )
+}
+
+/// 두 안내를 "; "로 잇는다.
+fn join_notes(a: Option<String>, b: Option<String>) -> Option<String> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(format!("{}; {}", a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// 파일명 추론에서 앨범/아티스트 폴더를 찾을 기준 디렉토리. 파일이면 그 상위 디렉토리.
+fn inference_root(path: &Path) -> &Path {
+    if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    }
+}
+
+/// 기록한 트랙을 보고서에 추가한다. `score`는 auto의 일치 점수다.
+/// 보충 단계의 안내가 있거나 파일명 추론만 적용했으면 확인 필요로 표시한다.
+fn report_applied(
+    report: &mut Report,
+    path: &Path,
+    track: &TrackInfo,
+    note: Option<&str>,
+    score: Option<f64>,
+    new_path: Option<&Path>,
+) {
+    let mut detail = format!("{} [{}]", track.summary(), track.source);
+    if let Some(score) = score {
+        detail.push_str(&tr!(" 점수 {:.2}", " score {:.2}", score));
+    }
+    detail.push_str(&format!(" ({})", Tier::of(score).label()));
+    if let Some(note) = note {
+        detail.push_str(&format!(" — {}", note));
+    }
+    let status = if note.is_some() || track.source == "filename" {
+        EntryStatus::Flagged
+    } else {
+        EntryStatus::Changed
+    };
+    report.push(path, status, detail, new_path, track.album_art.as_deref());
+}
+
+/// 트랙의 상세 정보(장르, 음반사, 앨범 아트 등)를 가져온다.
+/// 실패하면 안내만 출력하고 원래 트랙을 그대로 사용한다.
+fn with_detail(source: &dyn MusicSource, track: &TrackInfo) -> TrackInfo {
+    match source.fetch_detail(track) {
+        Ok(detailed) => {
+            if detailed.album_art.is_some() {
+                println!(
+                    "{}",
+                    i18n::pick("  앨범 아트를 다운로드했습니다.", "  Downloaded album art.")
+                );
+            }
+            detailed
+        }
+        Err(e) => {
+            println!(
+                "{}",
+                tr!(
+                    "  상세 정보 가져오기 실패: {}",
+                    "  Failed to fetch details: {}",
+                    e
+                )
+            );
+            track.clone()
+        }
+    }
+}
+
+/// 초당 처리 속도와 남은 시간을 표시하는 진행률 표시줄을 만든다.
+/// 터미널이 아니면 indicatif가 자동으로 숨긴다.
+fn progress_bar(len: usize, msg: &'static str) -> ProgressBar {
+    let pb = ProgressBar::new(len as u64);
+    pb.set_style(
+        ProgressStyle::with_template(i18n::pick(
+            "{msg} [{bar:40.cyan/blue}] {pos}/{len} ({per_sec}, 남은 시간 {eta})",
+            "{msg} [{bar:40.cyan/blue}] {pos}/{len} ({per_sec}, {eta} left)",
+        ))
+        .expect("진행률 템플릿이 올바르지 않습니다")
+        .progress_chars("=> "),
+    );
+    pb.set_message(msg);
+    pb
+}
+
+/// 진행률을 표시하며 입력 경로를 스캔한다. 디렉토리는 재귀 탐색하고 파일은 그대로 로드한다.
+/// `[library] enabled`면 라이브러리 데이터베이스로 바뀐 파일만 읽고, 쓸 수 없으면 경고 후 모두 읽는다.
+fn scan_with_progress(cfg: &Config, inputs: &Inputs) -> Result<Vec<Mp3File>> {
+    let paths = inputs.mp3_paths()?;
+    let pb = progress_bar(paths.len(), i18n::pick("스캔", "Scanning"));
+    if cfg.library.enabled {
+        match sync_library(cfg, inputs, &paths, &pb) {
+            Ok((files, _)) => {
+                pb.finish_and_clear();
+                return Ok(files);
+            }
+            Err(e) => {
+                pb.suspend(|| {
+                    eprintln!(
+                        "{}",
+                        tr!(
+                            "경고: 라이브러리 데이터베이스를 쓸 수 없어 파일을 모두 읽습니다: {:#}",
+                            "Warning: cannot write the library database, reading every file: {:#}",
+                            e
+                        )
+                    )
+                });
+                pb.set_position(0);
+            }
+        }
+    }
+    let files = scanner::load_files(&paths, |_| pb.inc(1));
+    pb.finish_and_clear();
+    Ok(files)
+}
+
+/// 스캔한 경로를 라이브러리 데이터베이스와 맞춘다. 입력한 디렉토리에서 없어진 파일은 데이터베이스에서 지운다.
+#[cfg(feature = "library")]
+fn sync_library(
+    cfg: &Config,
+    inputs: &Inputs,
+    paths: &[PathBuf],
+    pb: &ProgressBar,
+) -> Result<(Vec<Mp3File>, library::SyncSummary)> {
+    let mut db = library::Library::open(&cfg.library_path())?;
+    db.sync(paths, inputs.paths(), |_| pb.inc(1))
+}
+
+#[cfg(not(feature = "library"))]
+fn sync_library(
+    _cfg: &Config,
+    _inputs: &Inputs,
+    _paths: &[PathBuf],
+    _pb: &ProgressBar,
+) -> Result<(Vec<Mp3File>, ())> {
+    anyhow::bail!(i18n::pick(
+        "라이브러리 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features library",
+        "The library is not enabled. Rebuild with: cargo build --features library"
+    ))
+}
+
+/// 파일을 기록 가능한 것과 불가능한 것(사유 포함)으로 나눈다.
+fn partition_writable(files: Vec<Mp3File>, cfg: &Config) -> (Vec<Mp3File>, Vec<(Mp3File, String)>) {
+    let mut writable = Vec::new();
+    let mut blocked = Vec::new();
+    for file in files {
+        match tagger::check_writable(&file.path, &cfg.write) {
+            Ok(()) => writable.push(file),
+            Err(e) => blocked.push((file, e.to_string())),
+        }
+    }
+    (writable, blocked)
+}
+
+/// 각 파일의 첫 번째 검색 결과를 자동으로 적용한다.
+/// 처리한 파일은 상태 파일에 기록되어, 중단 후 다시 실행하면 남은 파일부터 이어서 처리한다.
+/// 검색/기록 실패는 기록하지 않으므로 다음 실행에서 다시 시도된다.
+fn fetch_auto(
+    cfg: &Config,
+    source: &dyn MusicSource,
+    path: &Path,
+    targets: Vec<Mp3File>,
+    restart: bool,
+    mut staged: Option<&mut StagedChanges>,
+    report: &mut Report,
+) -> Result<()> {
+    let enricher = Enricher::new(cfg)?;
+    let mut state = if restart {
+        FetchState::restart(path, FETCH_STATE_FILE)?
+    } else {
+        FetchState::load(path, FETCH_STATE_FILE)?
+    };
+
+    let total = targets.len();
+    let targets: Vec<_> = targets
+        .into_iter()
+        .filter(|f| !state.is_done(&f.path))
+        .collect();
+    if targets.len() < total {
+        println!(
+            "{}",
+            tr!(
+                "이전 실행에서 처리한 {}개 파일을 건너뜁니다 (--restart로 처음부터 다시 처리)",
+                "Skipping {} files handled in a previous run (--restart to start over)",
+                total - targets.len()
+            )
+        );
+    }
+
+    let mut applied = 0;
+    let mut unmatched = 0;
+    let mut failed = 0;
+
+    let root = inference_root(path);
+    cancel::install();
+    let pb = progress_bar(targets.len(), i18n::pick("자동 태깅", "Auto tagging"));
+    for file in &targets {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let parsed = parser::search_info(file.current_tags.as_ref(), &file.path);
+
+        let (results, retried) = match search_or_infer(cfg, source, file, root, &parsed) {
+            Ok(r) => r,
+            Err(e) => {
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 검색 실패 ({})",
+                            "{}: search failed ({})",
+                            file.filename(),
+                            e
+                        )
+                    )
+                });
+                report.push(
+                    &file.path,
+                    EntryStatus::Failed,
+                    tr!("검색 실패: {}", "Search failed: {}", e),
+                    None,
+                    None,
+                );
+                failed += 1;
+                continue;
+            }
+        };
+
+        let Some(track) = results.into_iter().next() else {
+            pb.suspend(|| {
+                println!(
+                    "{}",
+                    tr!("{}: 검색 결과 없음", "{}: no results", file.filename())
+                )
+            });
+            report.push(
+                &file.path,
+                EntryStatus::Skipped,
+                i18n::pick("검색 결과 없음", "No results"),
+                None,
+                None,
+            );
+            unmatched += 1;
+            state.mark_done(&file.path)?;
+            continue;
+        };
+
+        let track = source.fetch_detail(&track).unwrap_or(track);
+        let (track, note) = enricher.apply(file.current_tags.as_ref(), track);
+        let track = pb.suspend(|| merge_with_current(cfg, &file.current_tags, track));
+        let note = join_notes(retried.map(|v| retry_note(&v)), note);
+        if let Some(ref note) = note {
+            pb.suspend(|| println!("{}: {}", file.filename(), note));
+        }
+        let note = match staged {
+            Some(_) => join_notes(note, Some(staged_note())),
+            None => note,
+        };
+
+        match write_or_stage(cfg, staged.as_deref_mut(), &file.path, &track) {
+            Ok(()) => {
+                pb.suspend(|| println!("{} → {}", file.filename(), track.summary()));
+                report_applied(report, &file.path, &track, note.as_deref(), None, None);
+                applied += 1;
+                state.mark_done(&file.path)?;
+            }
+            Err(e) => {
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 기록 실패 ({})",
+                            "{}: write failed ({})",
+                            file.filename(),
+                            e
+                        )
+                    )
+                });
+                report.push(&file.path, EntryStatus::Failed, e.to_string(), None, None);
+                failed += 1;
+            }
+        }
+    }
+    pb.finish_and_clear();
+
+    println!(
+        "\n{}",
+        tr!(
+            "자동 태깅 완료: {} {}건, 결과 없음 {}건, 실패 {}건",
+            "Auto tagging done: {} {}, {} without results, {} failed",
+            if staged.is_some() {
+                i18n::pick("저장 대기", "staged")
+            } else {
+                i18n::pick("적용", "applied")
+            },
+            applied,
+            unmatched,
+            failed
+        )
+    );
+    if staged.is_none() {
+        refresh_media_server(cfg, applied);
+    }
+    exit::check_cancelled()?;
+    if failed == 0 {
+        state.clear()?;
+    } else {
+        println!(
+            "{}",
+            i18n::pick(
+                "실패한 파일은 다시 실행하면 재시도합니다.",
+                "Run again to retry the failed files."
+            )
+        );
+    }
+    if failed == 0 && applied == 0 && unmatched > 0 {
+        return Err(exit::nothing_matched(i18n::pick(
+            "검색 결과를 찾은 파일이 없습니다",
+            "No file had search results",
+        )));
+    }
+    exit::check_failures(failed, applied + unmatched + failed)
+}
+
+/// 스캔 → 파일명/경로 추론 → 여러 소스 검색 → 점수 기반 자동 적용 → 보충/앨범 아트 →
+/// 파일명 변경/정리 → 보고를 한 번에 실행한다. 각 단계는 `AutoArgs`의 옵션으로 끌 수 있다.
+/// 중단 후 다시 실행하면 이전에 처리한 파일은 건너뛴다.
+fn cmd_auto(cfg: &Config, args: &AutoArgs) -> Result<()> {
+    let mut cfg = cfg.clone();
+    cfg.write.match_tier = args.write_tier;
+    let cfg = &cfg;
+    let inputs = Inputs::expand(&args.paths)?;
+    let title = format!(
+        "mp3tag auto {}{}",
+        inputs.root().display(),
+        if args.dry_run { " (--dry-run)" } else { "" }
+    );
+    let mut report = Report::new(args.report.clone(), &title)?;
+    let root = inference_root(inputs.root());
+    let files = scan_with_progress(cfg, &inputs)?;
+    let state = if args.dry_run {
+        FetchState::new(inputs.root(), AUTO_STATE_FILE)
+    } else if args.restart {
+        FetchState::restart(inputs.root(), AUTO_STATE_FILE)?
+    } else {
+        FetchState::load(inputs.root(), AUTO_STATE_FILE)?
+    };
+    let total = files.len();
+    let targets: Vec<_> = files
+        .into_iter()
+        .filter(|f| args.all || !is_complete(f))
+        .collect();
+    let pending: Vec<_> = targets
+        .iter()
+        .filter(|f| !state.is_done(&f.path))
+        .cloned()
+        .collect();
+    if pending.len() < targets.len() {
+        println!(
+            "{}",
+            tr!(
+                "이전 실행에서 처리한 {}개 파일을 건너뜁니다 (--restart로 처음부터 다시 처리)",
+                "Skipping {} files handled in a previous run (--restart to start over)",
+                targets.len() - pending.len()
+            )
+        );
+    }
+    let (pending, blocked) = partition_writable(pending, cfg);
+    report_blocked(&blocked);
+    for (file, reason) in &blocked {
+        report.push(
+            &file.path,
+            EntryStatus::Skipped,
+            reason.as_str(),
+            None,
+            None,
+        );
+    }
+    if pending.is_empty() {
+        println!(
+            "{}",
+            tr!(
+                "{}개 파일 중 처리할 파일이 없습니다.",
+                "Nothing to process among {} files.",
+                total
+            )
+        );
+        return report.save();
+    }
+
+    let clients = if args.no_search {
+        Vec::new()
+    } else {
+        let clients = search_sources(cfg, &args.sources)?;
+        if clients.is_empty() {
+            return Err(exit::config_error(i18n::pick(
+                "사용할 수 있는 소스가 없습니다. 'mp3tag config'로 Spotify를 설정하거나 --no-search를 사용하세요.",
+                "No sources available. Set up Spotify with 'mp3tag config' or use --no-search.",
+            )));
+        }
+        clients
+    };
+    let enricher = if args.no_enrich {
+        None
+    } else {
+        Some(Enricher::new(cfg)?)
+    };
+    let aliases = Aliases::new(&cfg.matching.aliases);
+    let preference = cfg.matching.album_preference();
+    let timeout = cfg.network.search_timeout();
+    // 일괄 처리 중에는 묻지 않고 건너뛴다
+    let mut rename_cfg = cfg.rename.clone();
+    if rename_cfg.on_conflict == ConflictStrategy::Prompt {
+        rename_cfg.on_conflict = ConflictStrategy::Skip;
+    }
+
+    let mut run = AutoRun {
+        cfg,
+        args,
+        root,
+        enricher,
+        rename_cfg,
+        state,
+        report,
+        review: ReviewQueue::new(inputs.root()),
+        memory: (!args.no_remember).then(|| SourceCache::new(cfg.network.cache_dir())),
+        table: Table::new(),
+        folder_art: FolderArt::default(),
+        organized: BTreeSet::new(),
+        tiers: BTreeMap::new(),
+        matched: 0,
+        inferred: 0,
+        skipped: 0,
+        failed: 0,
+        moved: 0,
+        waiting: 0,
+    };
+    run.table.set_header(vec![
+        i18n::pick("파일", "File"),
+        i18n::pick("결과", "Result"),
+        i18n::pick("출처", "Source"),
+        i18n::pick("점수", "Score"),
+        i18n::pick("등급", "Tier"),
+        i18n::pick("새 경로", "New path"),
+    ]);
+
+    let mut queue = Vec::new();
+    cancel::install();
+    let pb = progress_bar(pending.len(), i18n::pick("자동 처리", "Auto processing"));
+    for file in &pending {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        // 파일명이 바뀌었거나 옮겨진 같은 오디오는 이전에 고른 결과를 검색 없이 다시 쓴다
+        let remembered = run
+            .memory
+            .as_ref()
+            .and_then(|memory| memory.load_match(&audio::content_hash(&file.path)?));
+        if let Some(remembered) = remembered {
+            let note = Some(remembered_note());
+            run.apply(
+                &pb,
+                file,
+                remembered.track,
+                i18n::pick("기억", "remembered"),
+                Some(remembered.score),
+                note,
+            )?;
+            continue;
+        }
+        match auto_pick(
+            args,
+            &clients,
+            timeout,
+            &aliases,
+            &preference,
+            &run.cfg.trust,
+            file,
+            root,
+        ) {
+            Some(AutoPick::Matched(track, score, note)) => run.apply(
+                &pb,
+                file,
+                track,
+                i18n::pick("적용", "applied"),
+                Some(score),
+                note,
+            )?,
+            Some(AutoPick::Inferred(track)) => {
+                run.apply(&pb, file, track, i18n::pick("추론", "inferred"), None, None)?
+            }
+            Some(AutoPick::Review(item)) => queue.push((file, item)),
+            None => run.skip(file, i18n::pick("결과 없음", "no results"))?,
+        }
+    }
+    pb.finish_and_clear();
+    review_auto_picks(&mut run, &clients, queue)?;
+
+    println!("{}", run.table);
+    save_folder_art(&run.folder_art, &run.rename_cfg.folder_art, args.dry_run);
+    if run.rename_cfg.nfo {
+        save_nfo(root, &run.organized, args.dry_run);
+    }
+    println!(
+        "\n{}",
+        tr!(
+            "자동 처리 완료: 검색 적용 {}건, 파일명 추론 {}건, 건너뜀 {}건, 검토 대기 {}건, 실패 {}건, 파일명 변경 {}건",
+            "Auto processing done: {} matched, {} inferred from file names, {} skipped, {} awaiting review, {} failed, {} renamed",
+            run.matched,
+            run.inferred,
+            run.skipped,
+            run.waiting,
+            run.failed,
+            run.moved
+        )
+    );
+    print_tiers(&run.tiers);
+    run.report.save()?;
+    let processed = run.matched + run.inferred + run.skipped + run.waiting + run.failed;
+    if args.dry_run {
+        println!(
+            "{}",
+            i18n::pick(
+                "--dry-run: 파일을 기록하거나 옮기지 않았습니다.",
+                "--dry-run: no files written or moved."
+            )
+        );
+        return exit::check_failures(run.failed, processed);
+    }
+    refresh_media_server(cfg, run.matched + run.inferred);
+    run.review.save()?;
+    if run.waiting > 0 {
+        println!(
+            "{}",
+            tr!(
+                "검토하지 않은 파일은 'mp3tag --gui {}'의 검토 탭에서 이어서 고를 수 있습니다.",
+                "Files not reviewed can be picked later in the review tab of 'mp3tag --gui {}'.",
+                inputs.root().display()
+            )
+        );
+    }
+    exit::check_cancelled()?;
+    if run.failed == 0 && run.waiting == 0 {
+        run.state.clear()?;
+    } else {
+        println!(
+            "{}",
+            i18n::pick(
+                "실패하거나 검토하지 않은 파일은 다시 실행하면 다시 처리합니다.",
+                "Run again to process files that failed or were not reviewed."
+            )
+        );
+    }
+    exit::check_failures(run.failed, processed)
+}
+
+/// auto의 신뢰 등급별 파일 수와, 비어 있지 않은 등급의 파일 목록을 출력한다.
+fn print_tiers(tiers: &BTreeMap<Tier, Vec<String>>) {
+    let counts: Vec<String> = Tier::ALL
+        .iter()
+        .map(|tier| {
+            let count = tiers.get(tier).map_or(0, Vec::len);
+            tr!("{} {}건", "{} {}", tier.label(), count)
+        })
+        .collect();
+    println!(
+        "{}",
+        tr!("신뢰 등급: {}", "Confidence tiers: {}", counts.join(", "))
+    );
+    for (tier, files) in tiers {
+        println!("  [{}] {}", tier.label(), files.join(", "));
+    }
+}
+
+/// 기억해 둔 결과를 다시 적용했을 때의 안내.
+fn remembered_note() -> String {
+    i18n::pick(
+        "같은 오디오에 이전에 적용한 결과 (--no-remember로 다시 검색)",
+        "result applied earlier to the same audio (--no-remember to search again)",
+    )
+    .to_string()
+}
+
+/// auto에서 한 번에 검토할 후보 수.
+const REVIEW_CANDIDATES: usize = 5;
+
+/// `auto_pick`의 결과.
+enum AutoPick {
+    /// 점수가 `--min-score` 이상인 검색 결과 (상세 정보, 점수, 단순화한 검색어로 찾았으면 그 안내)
+    Matched(TrackInfo, f64, Option<String>),
+    /// 점수가 `--review-score` 이상 `--min-score` 미만이라 끝에 사용자가 검토할 결과
+    Review(ReviewItem),
+    /// 파일명/폴더 추론으로 채운 태그
+    Inferred(TrackInfo),
+}
+
+/// auto 실행 중 파일별 결과를 표, 보고서, 진행 상태에 모은다.
+struct AutoRun<'a> {
+    cfg: &'a Config,
+    args: &'a AutoArgs,
+    root: &'a Path,
+    enricher: Option<Enricher>,
+    rename_cfg: config::RenameConfig,
+    state: FetchState,
+    report: Report,
+    /// 검토하지 않은 파일. 끝나면 대상 디렉토리에 저장해 GUI에서 이어서 검토한다.
+    review: ReviewQueue,
+    /// 오디오 내용별로 적용한 결과를 기억하는 캐시. `--no-remember`면 None.
+    memory: Option<SourceCache>,
+    table: Table,
+    /// `--organize`로 옮긴 폴더별 앨범 아트 (`[rename] folder_art`)
+    folder_art: FolderArt,
+    /// `--organize`로 옮긴 폴더 (`[rename] nfo`)
+    organized: BTreeSet<PathBuf>,
+    /// 신뢰 등급별 파일 이름
+    tiers: BTreeMap<Tier, Vec<String>>,
+    matched: usize,
+    inferred: usize,
+    skipped: usize,
+    failed: usize,
+    moved: usize,
+    waiting: usize,
+}
+
+impl AutoRun<'_> {
+    /// 태그를 보충해 기록하고 파일명을 바꾼다. 점수가 있으면 검색 적용, 없으면 추론으로 센다.
+    /// 검색 적용한 결과는 오디오 내용별로 기억해 두고 다음에 같은 오디오를 만나면 다시 쓴다.
+    fn apply(
+        &mut self,
+        pb: &ProgressBar,
+        file: &Mp3File,
+        track: TrackInfo,
+        result: &str,
+        score: Option<f64>,
+        retried: Option<String>,
+    ) -> Result<()> {
+        let (mut track, note) = match self.enricher {
+            Some(ref enricher) => enricher.apply(file.current_tags.as_ref(), track),
+            None => (track, None),
+        };
+        track.match_score = score;
+        let tier = Tier::of(score);
+        self.tiers
+            .entry(tier)
+            .or_default()
+            .push(file.filename().to_string());
+        if self.args.no_art {
+            track.album_art = None;
+        }
+        let kept = tagger::keep_protected(&file.current_tags, &mut track, &self.cfg.write);
+        let note = join_notes(join_notes(retried, note), protected_note(&kept));
+        if let Some(ref note) = note {
+            pb.suspend(|| println!("{}: {}", file.filename(), note));
+        }
+
+        if !self.args.dry_run {
+            if let Err(e) = tagger::write_tags(&file.path, &track, &self.cfg.write) {
+                self.table.add_row(vec![
+                    Cell::new(file.filename()),
+                    Cell::new(tr!("실패: {}", "Failed: {}", e)),
+                    Cell::new(&track.source),
+                    Cell::new("-"),
+                    Cell::new(tier.label()),
+                    Cell::new("-"),
+                ]);
+                self.report
+                    .push(&file.path, EntryStatus::Failed, e.to_string(), None, None);
+                self.failed += 1;
+                return Ok(());
+            }
+            if let (Some(score), Some(memory)) = (score, self.memory.as_ref()) {
+                if let Some(hash) = audio::content_hash(&file.path) {
+                    let remembered = RememberedMatch {
+                        track: track.clone(),
+                        score,
+                    };
+                    memory.store_match(&hash, &remembered);
+                }
+            }
+        }
+        if score.is_some() {
+            self.matched += 1;
+        } else {
+            self.inferred += 1;
+        }
+
+        let new_path = if self.args.no_rename {
+            None
+        } else {
+            match auto_move(self.args, &self.rename_cfg, self.root, &file.path, &track) {
+                Ok(path) => path,
+                Err(e) => {
+                    pb.suspend(|| {
+                        println!(
+                            "{}",
+                            tr!(
+                                "{}: 파일명 변경 실패 ({})",
+                                "{}: rename failed ({})",
+                                file.filename(),
+                                e
+                            )
+                        )
+                    });
+                    None
+                }
+            }
+        };
+        if new_path.is_some() {
+            self.moved += 1;
+        }
+        if self.args.organize && !self.args.no_rename {
+            if let Some(dir) =
+                renamer::organize_dir(self.root, &track, &self.rename_cfg.organize_template)
+            {
+                let art = track.album_art.as_deref().or_else(|| artwork::art_of(file));
+                if let (false, Some(art)) = (self.rename_cfg.folder_art.is_empty(), art) {
+                    self.folder_art.add(&dir, art);
+                }
+                self.organized.insert(dir);
+            }
+        }
+        if !self.args.dry_run {
+            self.state
+                .mark_done(new_path.as_deref().unwrap_or(&file.path))?;
+        }
+        report_applied(
+            &mut self.report,
+            &file.path,
+            &track,
+            note.as_deref(),
+            score,
+            new_path.as_deref(),
+        );
+
+        self.table.add_row(vec![
+            Cell::new(file.filename()),
+            Cell::new(result),
+            Cell::new(&track.source),
+            Cell::new(
+                score
+                    .map(|s| format!("{:.2}", s))
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cell::new(tier.label()),
+            Cell::new(
+                new_path
+                    .map(|p| {
+                        p.strip_prefix(self.root)
+                            .unwrap_or(&p)
+                            .display()
+                            .to_string()
+                    })
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+        ]);
+        Ok(())
+    }
+
+    /// 적용하지 않고 처리한 것으로 표시한다.
+    fn skip(&mut self, file: &Mp3File, reason: &str) -> Result<()> {
+        self.table.add_row(vec![
+            Cell::new(file.filename()),
+            Cell::new(reason),
+            Cell::new("-"),
+            Cell::new("-"),
+            Cell::new(Tier::Unmatched.label()),
+            Cell::new("-"),
+        ]);
+        self.tiers
+            .entry(Tier::Unmatched)
+            .or_default()
+            .push(file.filename().to_string());
+        self.report
+            .push(&file.path, EntryStatus::Skipped, reason, None, None);
+        self.skipped += 1;
+        if !self.args.dry_run {
+            self.state.mark_done(&file.path)?;
+        }
+        Ok(())
+    }
+
+    /// 검토하지 않은 파일. 처리한 것으로 표시하지 않아 다음 실행에서 다시 검토한다.
+    fn wait(&mut self, file: &Mp3File, item: &ReviewItem) {
+        let best = &item.candidates[0];
+        let tier = Tier::of(Some(best.score));
+        self.table.add_row(vec![
+            Cell::new(file.filename()),
+            Cell::new(i18n::pick("검토 대기", "Awaiting review")),
+            Cell::new(&best.track.source),
+            Cell::new(format!("{:.2}", best.score)),
+            Cell::new(tier.label()),
+            Cell::new("-"),
+        ]);
+        self.tiers
+            .entry(tier)
+            .or_default()
+            .push(file.filename().to_string());
+        self.report.push(
+            &file.path,
+            EntryStatus::Skipped,
+            tr!(
+                "검토 대기: {} [{}] 점수 {:.2}",
+                "Awaiting review: {} [{}] score {:.2}",
+                best.track.summary(),
+                best.track.source,
+                best.score
+            ),
+            None,
+            None,
+        );
+        self.review.items.push(item.clone());
+        self.waiting += 1;
+    }
+}
+
+/// 검토로 미룬 파일을 하나씩 보여 주고 적용할 후보를 고르게 한다.
+/// `--dry-run`이거나 터미널에서 실행하지 않았으면 묻지 않고 검토 대기로 남긴다.
+fn review_auto_picks(
+    run: &mut AutoRun,
+    clients: &[Arc<dyn MusicSource>],
+    queue: Vec<(&Mp3File, ReviewItem)>,
+) -> Result<()> {
+    let mut interactive = !queue.is_empty()
+        && !run.args.dry_run
+        && !cancel::requested()
+        && std::io::stdin().is_terminal();
+    if interactive {
+        println!(
+            "\n{}",
+            tr!(
+                "점수가 {:.2} 이상 {:.2} 미만인 파일 {}개를 검토합니다.",
+                "Reviewing {2} files scoring at least {0:.2} and below {1:.2}.",
+                run.args.review_score,
+                run.args.min_score,
+                queue.len()
+            )
+        );
+    }
+    let pb = ProgressBar::hidden();
+    let total = queue.len();
+    for (n, (file, item)) in queue.into_iter().enumerate() {
+        if !interactive {
+            run.wait(file, &item);
+            continue;
+        }
+        println!("\n[{}/{}] {}", n + 1, total, file.path.display());
+        println!(
+            "{}",
+            tr!("  현재: {}", "  Current: {}", item.local.summary())
+        );
+        let mut items: Vec<String> = item
+            .candidates
+            .iter()
+            .map(|c| {
+                tr!(
+                    "{}{} [{}] 점수 {:.2}",
+                    "{}{} [{}] score {:.2}",
+                    c.track.summary(),
+                    variants::marker_suffix(&c.track),
+                    c.track.source,
+                    c.score
+                )
+            })
+            .collect();
+        let infer_index = item.can_infer.then_some(items.len());
+        if item.can_infer {
+            items.push(tr!(
+                "추론 값만 적용: {}",
+                "Apply inferred values only: {}",
+                item.local.summary()
+            ));
+        }
+        let skip_index = items.len();
+        items.push(i18n::pick("이 파일 건너뛰기", "Skip this file").to_string());
+        items.push(
+            i18n::pick(
+                "남은 파일은 나중에 검토",
+                "Review the remaining files later",
+            )
+            .to_string(),
+        );
+
+        let selection = Select::new()
+            .with_prompt(i18n::pick(
+                "  적용할 결과를 선택하세요",
+                "  Choose a result to apply",
+            ))
+            .items(&items)
+            .default(0)
+            .interact()?;
+        if let Some(candidate) = item.candidates.get(selection) {
+            let detailed = clients[candidate.client]
+                .fetch_detail(&candidate.track)
+                .unwrap_or_else(|_| candidate.track.clone());
+            let track = tagger::merge_trusted(&Some(item.local.clone()), &detailed, &run.cfg.trust);
+            run.apply(
+                &pb,
+                file,
+                track,
+                i18n::pick("검토 후 적용", "applied after review"),
+                Some(candidate.score),
+                candidate.note.clone(),
+            )?;
+        } else if Some(selection) == infer_index {
+            run.apply(
+                &pb,
+                file,
+                item.local,
+                i18n::pick("추론", "inferred"),
+                None,
+                None,
+            )?;
+        } else if selection == skip_index {
+            run.skip(file, i18n::pick("검토에서 건너뜀", "skipped in review"))?;
+        } else {
+            interactive = false;
+            run.wait(file, &item);
+        }
+    }
+    Ok(())
+}
+
+/// 제목, 아티스트, 앨범이 모두 태그에 있는지 확인한다.
+fn is_complete(file: &Mp3File) -> bool {
+    file.current_tags
+        .as_ref()
+        .is_some_and(|t| t.title.is_some() && !t.artists.is_empty() && t.album.is_some())
+}
+
+/// auto, art upgrade 등에서 검색할 소스를 만든다. 지정하지 않으면 만들 수 있는 소스(설정된 Spotify, Melon,
+/// 외부 소스)를 모두 쓰며, 하나도 없으면 빈 목록을 반환한다.
+fn search_sources(cfg: &Config, kinds: &[SourceKind]) -> Result<Vec<Arc<dyn MusicSource>>> {
+    if !kinds.is_empty() {
+        return kinds
+            .iter()
+            .map(|kind| sources::create_source(kind, cfg).map(Arc::from))
+            .collect();
+    }
+    Ok(SourceKind::all(cfg)
+        .iter()
+        .filter_map(|kind| sources::create_source(kind, cfg).ok())
+        .map(Arc::from)
+        .collect())
+}
+
+/// 파일에 적용할 태그를 고른다. 모든 소스의 검색 결과 중 점수가 가장 높은 결과가
+/// `min_score` 이상이면 상세 정보를 가져와 `Matched`로, `review_score` 이상이면
+/// 그 이상인 결과를 점수순으로 모아 `Review`로 반환한다. 소스는 동시에 검색하며 `timeout`까지만 기다린다.
+/// 아니면 파일명/폴더 추론으로 채울 수 있는 값이 있을 때 `Inferred`를 반환한다.
+#[allow(clippy::too_many_arguments)]
+fn auto_pick(
+    args: &AutoArgs,
+    clients: &[Arc<dyn MusicSource>],
+    timeout: Duration,
+    aliases: &Aliases,
+    preference: &[AlbumEdition],
+    trust: &TrustConfig,
+    file: &Mp3File,
+    root: &Path,
+) -> Option<AutoPick> {
+    // 기존 태그가 추론 값보다 우선한다
+    let existing = file.current_tags.clone().unwrap_or_default();
+    let local = TrackInfo {
+        source: "filename".to_string(),
+        ..tagger::merge_tags(
+            &Some(parser::infer_from_path(&file.path, root)),
+            &existing,
+            MergePolicy::Overwrite,
+        )
+    };
+
+    let mut candidates: Vec<ReviewCandidate> = Vec::new();
+    if !clients.is_empty() {
+        let duration = audio::duration_ms(&file.path);
+        let mut found: Vec<Vec<ReviewCandidate>> = clients.iter().map(|_| Vec::new()).collect();
+        let query = local.clone();
+        let hint = matcher::local_hint(file);
+        // 모든 소스를 동시에 검색하고, 응답하지 않는 소스는 timeout까지만 기다린다
+        sources::race(
+            clients,
+            timeout,
+            move |client| sources::search_variants(client, &query),
+            |i, result| {
+                let Ok((results, retried)) = result else {
+                    return false;
+                };
+                let note = retried.map(|v| retry_note(&v));
+                for track in results {
+                    // 라이브/리믹스 등 버전이 다른 결과는 점수가 높아도 다른 녹음이다
+                    if !args.ignore_version && !matcher::versions_agree(&local, &track) {
+                        continue;
+                    }
+                    // 모음 앨범, 해외판, 반주 버전은 파일이 그 판이 아니면 원래 앨범보다 뒤로 미룬다
+                    let score = matcher::match_score(&local, duration, &track, aliases)
+                        * (1.0 - matcher::release_penalty(&hint, &track));
+                    found[i].push(ReviewCandidate {
+                        score,
+                        track,
+                        note: note.clone(),
+                        client: i,
+                    });
+                }
+                // 완전히 일치하면 나머지 소스는 기다리지 않는다
+                found[i].iter().any(|c| c.score >= 0.999)
+            },
+        );
+        candidates = found.into_iter().flatten().collect();
+    }
+    // 점수가 같으면 선호하는 앨범 판(`[matching] album_preference`), 그다음 앞 소스의 결과가 앞에 온다
+    candidates.sort_by(|a, b| {
+        b.score.total_cmp(&a.score).then_with(|| {
+            variants::rank(&a.track, preference).cmp(&variants::rank(&b.track, preference))
+        })
+    });
+
+    if let Some(best) = candidates.first().filter(|c| c.score >= args.min_score) {
+        let detailed = clients[best.client]
+            .fetch_detail(&best.track)
+            .unwrap_or_else(|_| best.track.clone());
+        return Some(AutoPick::Matched(
+            tagger::merge_trusted(&Some(local), &detailed, trust),
+            best.score,
+            best.note.clone(),
+        ));
+    }
+
+    // 추론으로 새로 채워지는 값이 있을 때만 적용한다
+    let adds = |new: bool, old: bool| new && !old;
+    let can_infer = adds(local.title.is_some(), existing.title.is_some())
+        || adds(!local.artists.is_empty(), !existing.artists.is_empty())
+        || adds(local.album.is_some(), existing.album.is_some())
+        || adds(
+            local.track_number.is_some(),
+            existing.track_number.is_some(),
+        )
+        || adds(local.year.is_some(), existing.year.is_some());
+
+    candidates.retain(|c| c.score >= args.review_score);
+    if !candidates.is_empty() {
+        candidates.truncate(REVIEW_CANDIDATES);
+        return Some(AutoPick::Review(ReviewItem {
+            path: file.path.clone(),
+            local,
+            can_infer,
+            candidates,
+        }));
+    }
+    can_infer.then_some(AutoPick::Inferred(local))
+}
+
+/// 태그 기반으로 파일명을 바꾸고, `organize`이면 정리 템플릿의 폴더(기본 `root/{앨범 아티스트}/{앨범}/`)로 옮긴다.
+/// 바뀐 경로를 반환한다 (`dry_run`이면 바뀔 경로). 이름이 그대로이거나 건너뛰면 None.
+fn auto_move(
+    args: &AutoArgs,
+    rename_cfg: &config::RenameConfig,
+    root: &Path,
+    path: &Path,
+    track: &TrackInfo,
+) -> Result<Option<PathBuf>> {
+    let Some(file_name) = renamer::build_filename(track, &rename_cfg.file_template) else {
+        return Ok(None);
+    };
+    let current_dir = path.parent().unwrap_or(root);
+    let dir = if args.organize {
+        renamer::organize_dir(root, track, &rename_cfg.organize_template)
+            .unwrap_or_else(|| current_dir.to_path_buf())
+    } else {
+        current_dir.to_path_buf()
+    };
+
+    if args.dry_run {
+        let new_path = dir.join(&file_name);
+        return Ok((new_path != path).then_some(new_path));
+    }
+    std::fs::create_dir_all(&dir).with_context(|| {
+        tr!(
+            "디렉토리를 만들 수 없습니다: {}",
+            "Cannot create directory: {}",
+            dir.display()
+        )
+    })?;
+    match renamer::move_file(path, &dir, &file_name, rename_cfg)? {
+        RenameOutcome::Renamed(new_path) => Ok(Some(new_path)),
+        _ => Ok(None),
+    }
+}
+
+/// 정리 폴더마다 대표 앨범 아트를 `names`(`[rename] folder_art`)의 파일로 저장한다. 이미 있는 파일은 두며,
+/// `dry_run`이면 저장할 파일만 보여 준다. 저장하지 못해도 태그 작업은 실패로 세지 않는다.
+fn save_folder_art(arts: &FolderArt, names: &[String], dry_run: bool) {
+    let mut saved = 0;
+    for (dir, art) in arts.picks() {
+        if dry_run {
+            for path in folder_art::planned(dir, art, names) {
+                println!(
+                    "{}",
+                    tr!(
+                        "폴더 아트 저장 예정: {}",
+                        "Folder art to save: {}",
+                        path.display()
+                    )
+                );
+                saved += 1;
+            }
+            continue;
+        }
+        match folder_art::write(dir, art, names) {
+            Ok(paths) => saved += paths.len(),
+            Err(e) => println!(
+                "{}",
+                tr!(
+                    "{}: 폴더 아트 저장 실패 ({:#})",
+                    "{}: failed to save folder art ({:#})",
+                    dir.display(),
+                    e
+                )
+            ),
+        }
+    }
+    if saved > 0 && !dry_run {
+        println!(
+            "{}",
+            tr!(
+                "폴더 아트 {}개를 저장했습니다.",
+                "Saved {} folder art images.",
+                saved
+            )
+        );
+    }
+}
+
+/// 정리 폴더마다 album.nfo를, 그 위 아티스트 폴더(`root` 아래일 때)에 artist.nfo를 만든다 (`[rename] nfo`).
+/// 폴더의 파일을 다시 읽으므로 이번에 옮기지 않은 곡도 들어간다. 직접 쓴 NFO는 두며, `dry_run`이면 만들 파일만
+/// 보여 준다. 저장하지 못해도 태그 작업은 실패로 세지 않는다.
+fn save_nfo(root: &Path, dirs: &BTreeSet<PathBuf>, dry_run: bool) {
+    let artist_dir = |dir: &Path| {
+        dir.parent()
+            .filter(|parent| *parent != root && parent.starts_with(root))
+            .map(Path::to_path_buf)
+    };
+    if dry_run {
+        let mut planned = BTreeSet::new();
+        for dir in dirs {
+            planned.insert(dir.join(nfo::ALBUM_NFO));
+            if let Some(parent) = artist_dir(dir) {
+                planned.insert(parent.join(nfo::ARTIST_NFO));
+            }
+        }
+        for path in planned.iter().filter(|path| nfo::should_write(path)) {
+            println!(
+                "{}",
+                tr!("NFO 저장 예정: {}", "NFO to save: {}", path.display())
+            );
+        }
+        return;
+    }
+
+    // 아티스트 폴더 → (이름, 앨범들의 장르)
+    let mut artists: BTreeMap<PathBuf, (String, Vec<String>)> = BTreeMap::new();
+    let mut saved = 0;
+    let mut save = |path: PathBuf, text: String| match nfo::write(&path, &text) {
+        Ok(written) => saved += written as usize,
+        Err(e) => println!("{:#}", e),
+    };
+    for dir in dirs {
+        let files = match scanner::scan_directory(dir) {
+            Ok(files) => files,
+            Err(e) => {
+                println!(
+                    "{}",
+                    tr!(
+                        "{}: NFO 저장 실패 ({:#})",
+                        "{}: failed to save NFO ({:#})",
+                        dir.display(),
+                        e
+                    )
+                );
+                continue;
+            }
+        };
+        let tracks: Vec<(&TrackInfo, Option<u64>)> = files
+            .iter()
+            .filter_map(|f| Some((f.current_tags.as_ref()?, audio::duration_ms(&f.path))))
+            .collect();
+        let Some(text) = nfo::album_nfo(&tracks) else {
+            continue;
+        };
+        save(dir.join(nfo::ALBUM_NFO), text);
+        let name = tracks.iter().find_map(|(t, _)| {
+            t.album_artist
+                .clone()
+                .or_else(|| t.artists.first().cloned())
+        });
+        if let (Some(parent), Some(name)) = (artist_dir(dir), name) {
+            let (_, genres) = artists.entry(parent).or_insert_with(|| (name, Vec::new()));
+            for genre in tracks.iter().filter_map(|(t, _)| t.genre.as_ref()) {
+                if !genres.contains(genre) {
+                    genres.push(genre.clone());
+                }
+            }
+        }
+    }
+    for (dir, (name, genres)) in artists {
+        save(dir.join(nfo::ARTIST_NFO), nfo::artist_nfo(&name, &genres));
+    }
+    if saved > 0 {
+        println!(
+            "{}",
+            tr!("NFO {}개를 저장했습니다.", "Saved {} NFO files.", saved)
+        );
+    }
+}
+
+/// 소스에서 곡을 검색해 한 페이지를 표로 출력한다.
+fn cmd_search(
+    cfg: &Config,
+    query: &str,
+    source: SourceKind,
+    page: Page,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = sources::create_source(&source, cfg)?;
+    let results = client.search_page(query, page)?;
+    if results.is_empty() {
+        return Err(exit::nothing_matched(match page.offset {
+            0 => tr!("검색 결과가 없습니다: {}", "No results: {}", query),
+            _ => tr!(
+                "{}번째 이후 검색 결과가 없습니다: {}",
+                "No results after the first {}: {}",
+                page.offset,
+                query
+            ),
+        }));
+    }
+
+    let header = vec![
+        "#",
+        i18n::pick("제목", "Title"),
+        i18n::pick("아티스트", "Artist"),
+        i18n::pick("앨범", "Album"),
+        i18n::pick("연도", "Year"),
+        i18n::pick("길이", "Length"),
+        "ID",
+    ];
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .enumerate()
+        .map(|(i, track)| {
+            vec![
+                (page.offset + i + 1).to_string(),
+                track.display_title().to_string() + &variants::marker_suffix(track),
+                track.display_artist(),
+                track.display_album().to_string(),
+                track.year.map(|y| y.to_string()).unwrap_or_default(),
+                track
+                    .duration_ms
+                    .map(|ms| format!("{}:{:02}", ms / 60_000, ms / 1000 % 60))
+                    .unwrap_or_default(),
+                track.source_id.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print!("{}", output::render(format, &header, &rows));
+    if !format.is_delimited() && results.len() >= page.limit {
+        println!(
+            "{}",
+            tr!(
+                "다음 결과: --page {}",
+                "Next results: --page {}",
+                page.next().offset / page.limit + 1
+            )
+        );
+    }
+    Ok(())
+}
+
+/// 앨범 폴더를 소스의 앨범 하나와 짝지어 트랙 목록 기준으로 모든 파일에 태그를 적용한다.
+/// 파일과 트랙은 제목 → 트랙 번호 → 순서로 매칭하며, 적용 전에 매칭 결과를 확인받는다.
+fn cmd_album(
+    cfg: &Config,
+    directory: &Path,
+    query: Option<String>,
+    source: SourceKind,
+) -> Result<()> {
+    let files = scan_with_progress(cfg, &Inputs::one(directory))?;
+    if files.is_empty() {
+        println!(
+            "{}",
+            tr!(
+                "{}에서 MP3 파일을 찾을 수 없습니다",
+                "No MP3 files found in {}",
+                directory.display()
+            )
+        );
+        return Ok(());
+    }
+
+    let query = query.unwrap_or_else(|| album_query(&files, directory));
+    println!("{}", tr!("앨범 검색 중: {}", "Searching albums: {}", query));
+
+    let client = sources::create_source(&source, cfg)?;
+    let albums = client.search_album(&query)?;
+    if albums.is_empty() {
+        println!(
+            "{}",
+            i18n::pick(
+                "검색 결과가 없습니다. --query로 검색어를 지정해 보세요.",
+                "No results. Try giving a search query with --query."
+            )
+        );
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = albums.iter().map(|a| a.summary()).collect();
+    items.push(i18n::pick("취소", "Cancel").to_string());
+    let selection = Select::new()
+        .with_prompt(i18n::pick("앨범을 선택하세요", "Choose an album"))
+        .items(&items)
+        .default(0)
+        .interact()?;
+    if selection >= albums.len() {
+        return Ok(());
+    }
+
+    let album = client.fetch_album(&albums[selection])?;
+    if album.tracks.is_empty() {
+        println!(
+            "{}",
+            i18n::pick(
+                "앨범의 트랙 목록을 가져오지 못했습니다.",
+                "Could not fetch the album's track list."
+            )
+        );
+        return Ok(());
+    }
+
+    let assignment = matcher::match_album_tracks(&files, &album.tracks);
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("파일", "File"),
+        i18n::pick("번호", "No."),
+        i18n::pick("제목", "Title"),
+    ]);
+    for (file, track_idx) in files.iter().zip(&assignment) {
+        let (number, title) = match track_idx.map(|j| &album.tracks[j]) {
+            Some(t) => (
+                t.track_number.map(|n| n.to_string()).unwrap_or_default(),
+                t.display_title().to_string(),
+            ),
+            None => (
+                "-".to_string(),
+                i18n::pick("(매칭 없음)", "(no match)").to_string(),
+            ),
+        };
+        table.add_row(vec![
+            Cell::new(file.filename()),
+            Cell::new(number),
+            Cell::new(title),
+        ]);
+    }
+    println!("\n{}\n{table}", album.summary());
+
+    if !Confirm::new()
+        .with_prompt(i18n::pick("이대로 태그를 적용할까요?", "Apply these tags?"))
+        .default(true)
+        .interact()?
+    {
+        return Ok(());
+    }
+
+    let enricher = Enricher::new(cfg)?;
+    let mut applied = 0;
+    let mut failed = 0;
+    cancel::install();
+    for (file, track_idx) in files.iter().zip(&assignment) {
+        if cancel::requested() {
+            break;
+        }
+        let Some(j) = track_idx else {
+            continue;
+        };
+        let mut track = album.tracks[*j].clone();
+        track.album_art = album.album_art.clone();
+        let (track, note) = enricher.apply(file.current_tags.as_ref(), track);
+        if let Some(note) = note {
+            println!("{}: {}", file.filename(), note);
+        }
+
+        match tagger::write_tags(&file.path, &track, &cfg.write) {
+            Ok(()) => applied += 1,
+            Err(e) => {
+                println!(
+                    "{}",
+                    tr!(
+                        "{}: 기록 실패 ({})",
+                        "{}: write failed ({})",
+                        file.filename(),
+                        e
+                    )
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "\n{}",
+        tr!(
+            "앨범 태그 적용 완료: 성공 {}건, 실패 {}건",
+            "Album tags applied: {} succeeded, {} failed",
+            applied,
+            failed
+        )
+    );
+    refresh_media_server(cfg, applied);
+    exit::check_failures(failed, applied + failed)
+}
+
+/// `audiobook`으로 기록할 책 정보.
+#[derive(Default)]
+struct BookInfo {
+    title: Option<String>,
+    author: Option<String>,
+    year: Option<i32>,
+    cover: Option<Vec<u8>>,
+}
+
+/// 폴더를 오디오북 한 권으로 태그한다. 파일을 자연 순서로 정렬해 트랙 번호와 챕터(CHAP/CTOC)를 매기고,
+/// 모든 파일에 같은 책 제목(앨범), 저자(아티스트), 장르를 기록한다. 제목/저자는 인자, Open Library 검색,
+/// 한 번의 입력("저자 - 제목") 순으로 정한다.
+fn cmd_audiobook(
+    cfg: &Config,
+    directory: &Path,
+    mut book: BookInfo,
+    lookup: bool,
+    rename: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let mut files = scan_with_progress(cfg, &Inputs::one(directory))?;
+    if files.is_empty() {
+        return Err(exit::nothing_matched(tr!(
+            "{}에서 MP3 파일을 찾을 수 없습니다",
+            "No MP3 files found in {}",
+            directory.display()
+        )));
+    }
+    audiobook::sort_chapters(&mut files);
+
+    let (folder_author, folder_title) =
+        audiobook::split_folder_name(&audiobook::folder_name(directory));
+    if lookup {
+        lookup_book(cfg, &mut book, &folder_title, folder_author.as_deref())?;
+    }
+    if book.title.is_none() || book.author.is_none() {
+        let default = match (&book.author, &folder_author) {
+            (Some(author), _) | (None, Some(author)) => format!(
+                "{} - {}",
+                author,
+                book.title.as_deref().unwrap_or(&folder_title)
+            ),
+            (None, None) => book.title.clone().unwrap_or(folder_title),
+        };
+        let answer = if std::io::stdin().is_terminal() {
+            Input::<String>::new()
+                .with_prompt(i18n::pick("저자 - 책 제목", "Author - Book title"))
+                .default(default)
+                .interact_text()?
+        } else {
+            default
+        };
+        let (author, title) = audiobook::split_folder_name(&answer);
+        book.title = book.title.or(Some(title)).filter(|t| !t.is_empty());
+        book.author = book.author.or(author);
+    }
+    let title = book.title.clone().context(i18n::pick(
+        "책 제목이 없습니다. --title로 지정하세요",
+        "No book title. Set one with --title",
+    ))?;
+
+    let total = files.len();
+    let chapters: Vec<String> = files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| audiobook::chapter_title(file, &title, i + 1))
+        .collect();
+    let mut table = Table::new();
+    let mut header = vec![
+        i18n::pick("번호", "No."),
+        i18n::pick("파일", "File"),
+        i18n::pick("챕터", "Chapter"),
+    ];
+    if rename {
+        header.push(i18n::pick("새 파일명", "New file name"));
+    }
+    table.set_header(header);
+    for (i, (file, chapter)) in files.iter().zip(&chapters).enumerate() {
+        let mut row = vec![
+            (i + 1).to_string(),
+            file.filename().to_string(),
+            chapter.clone(),
+        ];
+        if rename {
+            row.push(audiobook::chapter_file_name(i + 1, total, chapter));
+        }
+        table.add_row(row);
+    }
+    println!("{table}");
+    println!(
+        "\n{}",
+        tr!(
+            "{}{} ({}개 챕터)",
+            "{}{} ({} chapters)",
+            title,
+            book.author
+                .as_ref()
+                .map(|a| format!(" - {}", a))
+                .unwrap_or_default(),
+            total
+        )
+    );
+    if dry_run {
+        println!(
+            "{}",
+            i18n::pick(
+                "--dry-run: 기록하지 않았습니다.",
+                "--dry-run: nothing written."
+            )
+        );
+        return Ok(());
+    }
+
+    let mut applied = 0;
+    let mut failed = 0;
+    cancel::install();
+    let pb = progress_bar(total, i18n::pick("오디오북 태그", "Audiobook tags"));
+    for (i, (file, chapter)) in files.iter().zip(&chapters).enumerate() {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let info = TrackInfo {
+            title: Some(chapter.clone()),
+            artists: book.author.iter().cloned().collect(),
+            album: Some(title.clone()),
+            album_artist: book.author.clone(),
+            track_number: Some(i as u32 + 1),
+            total_tracks: Some(total as u32),
+            year: book.year,
+            genre: Some(audiobook::GENRE.to_string()),
+            album_art: book.cover.clone(),
+            source: if lookup { "openlibrary" } else { "manual" }.to_string(),
+            ..Default::default()
+        };
+        let result = (|| -> Result<()> {
+            tagger::write_tags(&file.path, &info, &cfg.write)?;
+            let duration = audio::duration_ms(&file.path).unwrap_or(0);
+            tagger::write_chapter(&file.path, chapter, duration, &cfg.write)?;
+            if rename {
+                let name = audiobook::chapter_file_name(i + 1, total, chapter);
+                let dir = file.path.parent().unwrap_or_else(|| Path::new("."));
+                if let RenameOutcome::Skipped(target) | RenameOutcome::Conflict(target) =
+                    renamer::move_file(&file.path, dir, &name, &cfg.rename)?
+                {
+                    pb.suspend(|| {
+                        println!(
+                            "{}",
+                            tr!(
+                                "{}: 이미 존재하여 이름을 바꾸지 않음 ({})",
+                                "{}: already exists, not renamed ({})",
+                                file.filename(),
+                                target.display()
+                            )
+                        )
+                    });
+                }
+            }
+            Ok(())
+        })();
+        match result {
+            Ok(()) => applied += 1,
+            Err(e) => {
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 기록 실패 ({:#})",
+                            "{}: write failed ({:#})",
+                            file.filename(),
+                            e
+                        )
+                    )
+                });
+                failed += 1;
+            }
+        }
+    }
+    pb.finish_and_clear();
+
+    println!(
+        "\n{}",
+        tr!(
+            "오디오북 태그 완료: 성공 {}건, 실패 {}건",
+            "Audiobook tags done: {} succeeded, {} failed",
+            applied,
+            failed
+        )
+    );
+    refresh_media_server(cfg, applied);
+    exit::check_failures(failed, applied + failed)
+}
+
+/// Open Library에서 책을 검색해 고른 결과로 `book`의 빈 값을 채운다.
+/// 검색어는 인자로 준 제목/저자, 없으면 폴더 이름이다. 터미널이 아니면 첫 결과를 쓴다.
+fn lookup_book(
+    cfg: &Config,
+    book: &mut BookInfo,
+    folder_title: &str,
+    folder_author: Option<&str>,
+) -> Result<()> {
+    cfg.network.ensure_online(i18n::pick(
+        "Open Library에서 책을 찾을",
+        "look up books on Open Library",
+    ))?;
+    let client = OpenLibraryClient::new()?;
+    let query = [
+        book.title.as_deref().or(Some(folder_title)),
+        book.author.as_deref().or(folder_author),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+    let books = client.search(&query)?;
+    if books.is_empty() {
+        println!(
+            "{}",
+            tr!(
+                "Open Library 검색 결과가 없습니다: {}",
+                "No Open Library results: {}",
+                query
+            )
+        );
+        return Ok(());
+    }
+
+    let selection = if std::io::stdin().is_terminal() {
+        let mut items: Vec<String> = books.iter().map(|b| b.summary()).collect();
+        items.push(i18n::pick("사용 안 함", "Don't use").to_string());
+        Select::new()
+            .with_prompt(i18n::pick("책을 선택하세요", "Choose a book"))
+            .items(&items)
+            .default(0)
+            .interact()?
+    } else {
+        0
+    };
+    let Some(found) = books.get(selection) else {
+        return Ok(());
+    };
+    book.title = book.title.take().or(Some(found.title.clone()));
+    book.author = book.author.take().or(found.author.clone());
+    book.year = found.year;
+    book.cover = match client.fetch_cover(found) {
+        Ok(cover) => cover,
+        Err(e) => {
+            println!(
+                "{}",
+                tr!(
+                    "표지를 가져오지 못했습니다: {:#}",
+                    "Could not fetch the cover: {:#}",
+                    e
+                )
+            );
+            None
+        }
+    };
+    Ok(())
+}
+
+/// 모음 앨범 폴더의 곡마다 모든 소스를 검색해 모음 앨범이 아닌 원래 앨범의 결과를 고르고,
+/// 앨범 관련 필드(`compilation::reassign`)를 그 앨범의 값으로 바꾼다. 찾은 앨범별로 묶어 보여 주고
+/// 확인받은 뒤 기록하며, `organize`가 있으면 그 아래 정리 템플릿 폴더로 옮긴다.
+fn cmd_split(
+    cfg: &Config,
+    directory: &Path,
+    kinds: &[SourceKind],
+    min_score: f64,
+    organize: Option<&Path>,
+    dry_run: bool,
+) -> Result<()> {
+    let files = scan_with_progress(cfg, &Inputs::one(directory))?;
+    if files.is_empty() {
+        return Err(exit::nothing_matched(tr!(
+            "{}에서 MP3 파일을 찾을 수 없습니다",
+            "No MP3 files found in {}",
+            directory.display()
+        )));
+    }
+    let clients = search_sources(cfg, kinds)?;
+    if clients.is_empty() {
+        return Err(exit::config_error(i18n::pick(
+            "사용할 수 있는 소스가 없습니다. 'mp3tag config'로 Spotify를 설정하세요.",
+            "No sources available. Set up Spotify with 'mp3tag config'.",
+        )));
+    }
+    let compilation_albums = compilation::compilation_albums(&files);
+    let aliases = Aliases::new(&cfg.matching.aliases);
+    let timeout = cfg.network.search_timeout();
+    let root = inference_root(directory);
+
+    // (파일, 원래 앨범으로 바꾼 태그, 점수)
+    let mut found: Vec<(&Mp3File, TrackInfo, f64)> = Vec::new();
+    let mut unmatched = Vec::new();
+    cancel::install();
+    let pb = progress_bar(
+        files.len(),
+        i18n::pick("원래 앨범 검색", "Finding original albums"),
+    );
+    for file in &files {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let local = tagger::merge_tags(
+            &Some(parser::infer_from_path(&file.path, root)),
+            &file.current_tags.clone().unwrap_or_default(),
+            MergePolicy::Overwrite,
+        );
+        let duration = audio::duration_ms(&file.path);
+        let mut candidates: Vec<(usize, TrackInfo, f64)> = Vec::new();
+        let query = local.clone();
+        sources::race(
+            &clients,
+            timeout,
+            move |client| sources::search_variants(client, &query),
+            |i, result| {
+                let Ok((results, _)) = result else {
+                    return false;
+                };
+                candidates.extend(
+                    results
+                        .into_iter()
+                        .filter(|t| !compilation::is_compilation(t, &compilation_albums))
+                        .map(|t| {
+                            let score = matcher::match_score(&local, duration, &t, &aliases);
+                            (i, t, score)
+                        }),
+                );
+                false
+            },
+        );
+        // 점수가 같으면 먼저 나온 결과(앞 소스, 소스의 관련도순)가 앞에 온다
+        candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+        match candidates.into_iter().next().filter(|c| c.2 >= min_score) {
+            Some((i, track, score)) => {
+                let detailed = clients[i].fetch_detail(&track).unwrap_or(track);
+                let mut track = compilation::reassign(file.current_tags.as_ref(), &detailed);
+                track.match_score = Some(score);
+                found.push((file, track, score));
+            }
+            None => unmatched.push(file),
+        }
+    }
+    pb.finish_and_clear();
+
+    found.sort_by(|a, b| {
+        (a.1.display_album(), a.1.disc_number, a.1.track_number).cmp(&(
+            b.1.display_album(),
+            b.1.disc_number,
+            b.1.track_number,
+        ))
+    });
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("파일", "File"),
+        i18n::pick("원래 앨범", "Original album"),
+        i18n::pick("번호", "No."),
+        i18n::pick("연도", "Year"),
+        i18n::pick("점수", "Score"),
+    ]);
+    for (file, track, score) in &found {
+        table.add_row(vec![
+            file.filename().to_string(),
+            format!(
+                "{} - {}",
+                track
+                    .album_artist
+                    .as_deref()
+                    .unwrap_or(i18n::pick("알 수 없음", "Unknown")),
+                track.display_album()
+            ),
+            track
+                .track_number
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+            track.year.map(|y| y.to_string()).unwrap_or_default(),
+            format!("{:.2}", score),
+        ]);
+    }
+    for file in &unmatched {
+        table.add_row(vec![
+            file.filename().to_string(),
+            i18n::pick("(찾지 못함)", "(not found)").to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ]);
+    }
+    println!("{table}");
+    let mut albums: Vec<&str> = found.iter().map(|(_, t, _)| t.display_album()).collect();
+    albums.dedup();
+    println!(
+        "\n{}",
+        tr!(
+            "{}개 파일 → 원래 앨범 {}개, 찾지 못함 {}개",
+            "{} files → {} original albums, {} not found",
+            found.len() + unmatched.len(),
+            albums.len(),
+            unmatched.len()
+        )
+    );
+    exit::check_cancelled()?;
+    if found.is_empty() {
+        return Err(exit::nothing_matched(i18n::pick(
+            "원래 앨범을 찾은 파일이 없습니다",
+            "No files matched an original album",
+        )));
+    }
+    if dry_run {
+        println!(
+            "{}",
+            i18n::pick(
+                "--dry-run: 기록하지 않았습니다.",
+                "--dry-run: nothing written."
+            )
+        );
+        return Ok(());
+    }
+    if std::io::stdin().is_terminal()
+        && !Confirm::new()
+            .with_prompt(i18n::pick("이대로 태그를 적용할까요?", "Apply these tags?"))
+            .default(true)
+            .interact()?
+    {
+        return Ok(());
+    }
+
+    // 일괄 처리 중에는 묻지 않고 건너뛴다
+    let mut rename_cfg = cfg.rename.clone();
+    if rename_cfg.on_conflict == ConflictStrategy::Prompt {
+        rename_cfg.on_conflict = ConflictStrategy::Skip;
+    }
+    let mut applied = 0;
+    let mut failed = 0;
+    let mut arts = FolderArt::default();
+    let mut organized = BTreeSet::new();
+    let pb = progress_bar(
+        found.len(),
+        i18n::pick("원래 앨범 기록", "Writing original albums"),
+    );
+    for (file, track, _) in &found {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let result = (|| -> Result<()> {
+            tagger::write_tags(&file.path, track, &cfg.write)?;
+            let Some(root) = organize else {
+                return Ok(());
+            };
+            let Some(dir) = renamer::organize_dir(root, track, &rename_cfg.organize_template)
+            else {
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 정리 폴더를 만들 태그가 없어 옮기지 않음",
+                            "{}: no tags to build the folder from, not moved",
+                            file.filename()
+                        )
+                    )
+                });
+                return Ok(());
+            };
+            std::fs::create_dir_all(&dir).with_context(|| {
+                tr!(
+                    "디렉토리를 만들 수 없습니다: {}",
+                    "Cannot create directory: {}",
+                    dir.display()
+                )
+            })?;
+            if let Some(art) = track.album_art.as_deref().or_else(|| artwork::art_of(file)) {
+                arts.add(&dir, art);
+            }
+            organized.insert(dir.clone());
+            if let RenameOutcome::Skipped(target) | RenameOutcome::Conflict(target) =
+                renamer::move_file(&file.path, &dir, file.filename(), &rename_cfg)?
+            {
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 이미 존재하여 옮기지 않음 ({})",
+                            "{}: already exists, not moved ({})",
+                            file.filename(),
+                            target.display()
+                        )
+                    )
+                });
+            }
+            Ok(())
+        })();
+        match result {
+            Ok(()) => applied += 1,
+            Err(e) => {
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 기록 실패 ({:#})",
+                            "{}: write failed ({:#})",
+                            file.filename(),
+                            e
+                        )
+                    )
+                });
+                failed += 1;
+            }
+        }
+    }
+    pb.finish_and_clear();
+    save_folder_art(&arts, &rename_cfg.folder_art, false);
+    if let (true, Some(root)) = (rename_cfg.nfo, organize) {
+        save_nfo(root, &organized, false);
+    }
+
+    println!(
+        "\n{}",
+        tr!(
+            "모음 앨범 분리 완료: 적용 {}건, 찾지 못함 {}건, 실패 {}건",
+            "Compilation split done: {} applied, {} not found, {} failed",
+            applied,
+            unmatched.len(),
+            failed
+        )
+    );
+    refresh_media_server(cfg, applied);
+    exit::check_failures(failed, applied + unmatched.len() + failed)
+}
+
+/// 파일명/폴더 구조(또는 `pattern`)로 추론한 태그를 기록한다.
+/// 기본적으로 비어 있는 필드만 채우고, `overwrite`이면 추론한 값으로 덮어쓴다.
+fn cmd_from_filename(
+    cfg: &Config,
+    inputs: &Inputs,
+    pattern: Option<&str>,
+    dry_run: bool,
+    policy: MergePolicy,
+) -> Result<()> {
+    let pattern = pattern.map(FilenamePattern::parse).transpose()?;
+    // 기록하지 않을 때는 묻지 않는다
+    let policy = match policy {
+        MergePolicy::Interactive if dry_run => MergePolicy::Fill,
+        policy => policy,
+    };
+    let files = scan_with_progress(cfg, inputs)?;
+    let root = inference_root(inputs.root());
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("파일", "File"),
+        i18n::pick("제목", "Title"),
+        i18n::pick("아티스트", "Artist"),
+        i18n::pick("앨범", "Album"),
+        i18n::pick("트랙", "Track"),
+        i18n::pick("연도", "Year"),
+    ]);
+    let mut changes = Vec::new();
+    let mut protected = Vec::new();
+    let mut unmatched = 0;
+    for file in &files {
+        let inferred = match pattern {
+            Some(ref pattern) => match pattern.apply(&file.path) {
+                Some(info) => info,
+                None => {
+                    unmatched += 1;
+                    continue;
+                }
+            },
+            None => parser::infer_from_path(&file.path, root),
+        };
+
+        // 실제로 바뀌는 필드만 남긴다
+        let mut update = TrackInfo {
+            title: inferred.title,
+            artists: inferred.artists,
+            album: inferred.album,
+            track_number: inferred.track_number,
+            year: inferred.year,
+            source: "filename".to_string(),
+            ..Default::default()
+        };
+        let kept = tagger::keep_protected(&file.current_tags, &mut update, &cfg.write);
+        if let Some(note) = protected_note(&kept) {
+            protected.push(format!("{}: {}", file.filename(), note));
+        }
+        keep_current_fields(policy, &file.current_tags, &mut update);
+        if update.title.is_none()
+            && update.artists.is_empty()
+            && update.album.is_none()
+            && update.track_number.is_none()
+            && update.year.is_none()
+        {
+            continue;
+        }
+
+        let show = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+        table.add_row(vec![
+            Cell::new(file.filename()),
+            Cell::new(show(&update.title)),
+            Cell::new(show(&update.artist())),
+            Cell::new(show(&update.album)),
+            Cell::new(show(&update.track_number.map(|n| n.to_string()))),
+            Cell::new(show(&update.year.map(|y| y.to_string()))),
+        ]);
+        changes.push((file, update));
+    }
+
+    if changes.is_empty() {
+        println!(
+            "{}",
+            i18n::pick("바꿀 태그가 없습니다.", "No tags to change.")
+        );
+    } else {
+        println!("{table}");
+    }
+    for line in &protected {
+        println!("{}", line);
+    }
+    if unmatched > 0 {
+        println!(
+            "{}",
+            tr!(
+                "패턴과 맞지 않는 파일 {}개는 건너뜁니다.",
+                "Skipping {} files that do not match the pattern.",
+                unmatched
+            )
+        );
+    }
+    if dry_run || changes.is_empty() {
+        if dry_run {
+            println!(
+                "\n{}",
+                tr!(
+                    "--dry-run: {}개 파일을 기록하지 않았습니다.",
+                    "--dry-run: {} files not written.",
+                    changes.len()
+                )
+            );
+        }
+        return Ok(());
+    }
+
+    cancel::install();
+    let report = tagger::write_batch(
+        changes
+            .iter()
+            .map(|(file, update)| (file.path.as_path(), update)),
+        &cfg.write,
+    );
+    print_batch_failures(&report);
+    println!(
+        "\n{}",
+        tr!(
+            "파일명 기반 태그 기록 완료: {}",
+            "Tags written from file names: {}",
+            report.summary()
+        )
+    );
+    refresh_media_server(cfg, report.written.len());
+    exit::check_failures(report.failed.len(), changes.len())
+}
+
+/// 앨범별 앨범 아트 사용량과 중복 저장된 크기를 표로 출력한다.
+/// CSV/TSV면 크기를 바이트 수 그대로 쓰고 요약은 생략한다.
+fn cmd_art_stats(cfg: &Config, inputs: &Inputs, format: OutputFormat) -> Result<()> {
+    let files = scan_with_progress(cfg, inputs)?;
+    let header = match (format.is_delimited(), i18n::lang()) {
+        (true, _) => vec![
+            "album",
+            "files",
+            "with_art",
+            "distinct",
+            "art_bytes",
+            "duplicate_bytes",
+        ],
+        (false, Lang::Ko) => vec![
+            "앨범",
+            "파일",
+            "아트 있음",
+            "서로 다른 이미지",
+            "아트 크기",
+            "중복",
+        ],
+        (false, Lang::En) => vec![
+            "Album",
+            "Files",
+            "With art",
+            "Distinct images",
+            "Art size",
+            "Duplicate",
+        ],
+    };
+    let size = |bytes: u64| match format.is_delimited() {
+        true => bytes.to_string(),
+        false => format_bytes(bytes),
+    };
+    let mut rows = Vec::new();
+    let mut total = 0;
+    let mut duplicate = 0;
+    for (label, album) in artwork::group_by_album(&files) {
+        let usage = artwork::album_art_usage(&album);
+        if usage.with_art == 0 {
+            continue;
+        }
+        total += usage.art_bytes;
+        duplicate += usage.duplicate_bytes;
+        rows.push(vec![
+            label,
+            usage.files.to_string(),
+            usage.with_art.to_string(),
+            usage.distinct.to_string(),
+            size(usage.art_bytes),
+            size(usage.duplicate_bytes),
+        ]);
+    }
+
+    if format.is_delimited() {
+        print!("{}", output::render(format, &header, &rows));
+        return Ok(());
+    }
+    if total == 0 {
+        println!(
+            "{}",
+            i18n::pick(
+                "앨범 아트가 있는 파일이 없습니다.",
+                "No files have album art."
+            )
+        );
+        return Ok(());
+    }
+    print!("{}", output::render(format, &header, &rows));
+    println!(
+        "\n{}",
+        tr!(
+            "앨범 아트 총 {}, 같은 이미지의 중복 저장 {}",
+            "Album art {} in total, {} stored more than once",
+            format_bytes(total),
+            format_bytes(duplicate)
+        )
+    );
+    Ok(())
+}
+
+/// 앨범마다 대표 아트를 한 번만 준비해 아트가 다른 파일에 같은 이미지를 다시 기록한다.
+/// 아트가 없는 파일은 건드리지 않는다.
+fn cmd_art_dedupe(
+    cfg: &Config,
+    inputs: &Inputs,
+    max_size: Option<u32>,
+    dry_run: bool,
+) -> Result<()> {
+    #[cfg(not(feature = "resize"))]
+    if max_size.is_some() {
+        anyhow::bail!(i18n::pick(
+            "이미지 크기 조정 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features resize",
+            "Image resizing is not enabled. Rebuild with: cargo build --features resize"
+        ));
+    }
+
+    let files = scan_with_progress(cfg, inputs)?;
+    let mut before = 0;
+    let mut after = 0;
+    let mut applied = 0;
+    let mut failed = 0;
+    cancel::install();
+    for (label, album) in artwork::group_by_album(&files) {
+        if cancel::requested() {
+            break;
+        }
+        let Some(canonical) = artwork::canonical_art(&album) else {
+            continue;
+        };
+        let canonical = match max_size {
+            #[cfg(feature = "resize")]
+            Some(size) => artwork::resize(canonical, size)?,
+            _ => canonical.to_vec(),
+        };
+        let targets: Vec<&Mp3File> = album
+            .iter()
+            .copied()
+            .filter(|f| {
+                let art = f.current_tags.as_ref().and_then(|t| t.album_art.as_deref());
+                art.is_some_and(|a| a != canonical.as_slice())
+            })
+            .collect();
+        if targets.is_empty() {
+            continue;
+        }
+
+        let old: u64 = targets
+            .iter()
+            .filter_map(|f| f.current_tags.as_ref()?.album_art.as_ref())
+            .map(|a| a.len() as u64)
+            .sum();
+        let dimensions = artwork::image_dimensions(&canonical)
+            .map(|(w, h)| format!("{}x{} ", w, h))
+            .unwrap_or_default();
+        println!(
+            "{}",
+            tr!(
+                "{}: {}개 파일 → {}{}",
+                "{}: {} files → {}{}",
+                label,
+                targets.len(),
+                dimensions,
+                format_bytes(canonical.len() as u64)
+            )
+        );
+        before += old;
+        after += canonical.len() as u64 * targets.len() as u64;
+        if dry_run {
+            continue;
+        }
+
+        let update = TrackInfo {
+            album_art: Some(canonical.clone()),
+            source: "manual".to_string(),
+            ..Default::default()
+        };
+        let report = tagger::write_batch(
+            targets.iter().map(|file| (file.path.as_path(), &update)),
+            &cfg.write,
+        );
+        print_batch_failures(&report);
+        applied += report.written.len();
+        failed += report.failed.len();
+    }
+
+    if before == 0 {
+        println!(
+            "{}",
+            i18n::pick("정리할 앨범 아트가 없습니다.", "No album art to clean up.")
+        );
+        return Ok(());
+    }
+    println!(
+        "\n{}",
+        tr!(
+            "앨범 아트 {} → {}",
+            "Album art {} → {}",
+            format_bytes(before),
+            format_bytes(after)
+        )
+    );
+    if dry_run {
+        println!(
+            "{}",
+            i18n::pick(
+                "--dry-run: 기록하지 않았습니다.",
+                "--dry-run: nothing written."
+            )
+        );
+        return Ok(());
+    }
+    println!(
+        "{}",
+        tr!(
+            "앨범 아트 정리 완료: 성공 {}건, 실패 {}건",
+            "Album art cleaned up: {} succeeded, {} failed",
+            applied,
+            failed
+        )
+    );
+    refresh_media_server(cfg, applied);
+    exit::check_failures(failed, applied + failed)
+}
+
+/// 앨범마다 대표 아트와 다른 이미지를 지각 해시로 비교해, 같은 커버의 다른 스캔/인코딩이 섞인 앨범을 보고하고
+/// 확인을 받아 대표 이미지로 통일한다. 아예 다른 이미지(디스크별 커버 등)는 보고만 한다.
+/// 해시는 캐시 디렉토리의 색인(`art_index`)에 저장해 다시 검사할 때 이미지를 디코딩하지 않는다.
+fn cmd_art_check(cfg: &Config, inputs: &Inputs, yes: bool, dry_run: bool) -> Result<()> {
+    if !cfg!(feature = "resize") {
+        anyhow::bail!(i18n::pick(
+            "이미지 비교 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features resize",
+            "Image comparison is not enabled. Rebuild with: cargo build --features resize"
+        ));
+    }
+
+    let files = scan_with_progress(cfg, inputs)?;
+    let mut index = ArtIndex::load(&cfg.network.cache_dir());
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("앨범", "Album"),
+        i18n::pick("대표 아트", "Main art"),
+        i18n::pick("다른 스캔", "Other scans"),
+        i18n::pick("다른 이미지", "Different images"),
+    ]);
+    // (앨범, 대표 아트, 다른 스캔을 쓰는 파일)
+    let mut pending: Vec<(String, Vec<u8>, Vec<&Mp3File>)> = Vec::new();
+    let mut different = 0;
+    cancel::install();
+    let groups = artwork::group_by_album(&files);
+    let pb = progress_bar(
+        groups.len(),
+        i18n::pick("앨범 아트 비교", "Comparing album art"),
+    );
+    for (label, album) in groups {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let Some(canonical) = artwork::canonical_art(&album) else {
+            continue;
+        };
+        let variants = art_index::variants(
+            &mut index,
+            canonical,
+            album.iter().filter_map(|f| artwork::art_of(f)),
+        );
+        if variants.is_empty() {
+            continue;
+        }
+        let (rescans, others): (Vec<_>, Vec<_>) = variants.iter().partition(|v| v.is_rescan());
+        let count = |list: &[&art_index::ArtVariant]| list.iter().map(|v| v.files).sum::<usize>();
+        different += count(&others);
+        let describe = |data: &[u8]| {
+            let dimensions = artwork::image_dimensions(data)
+                .map(|(w, h)| format!("{}x{} ", w, h))
+                .unwrap_or_default();
+            format!("{}{}", dimensions, format_bytes(data.len() as u64))
+        };
+        table.add_row(vec![
+            label.clone(),
+            describe(canonical),
+            tr!("{}개 파일", "{} files", count(&rescans)),
+            tr!("{}개 파일", "{} files", count(&others)),
+        ]);
+        if rescans.is_empty() {
+            continue;
+        }
+        let targets: Vec<&Mp3File> = album
+            .iter()
+            .copied()
+            .filter(|f| artwork::art_of(f).is_some_and(|a| rescans.iter().any(|v| v.data == a)))
+            .collect();
+        pending.push((label, canonical.to_vec(), targets));
+    }
+    pb.finish_and_clear();
+    index.save()?;
+
+    if table.row_iter().next().is_none() {
+        println!(
+            "{}",
+            i18n::pick(
+                "앨범 아트가 섞인 앨범이 없습니다.",
+                "No albums have mixed album art."
+            )
+        );
+        return exit::check_cancelled();
+    }
+    println!("{table}");
+    println!(
+        "\n{}",
+        tr!(
+            "같은 커버의 다른 스캔이 섞인 앨범 {}개 ({}개 파일), 다른 이미지를 쓰는 파일 {}개",
+            "{} albums mix other scans of the same cover ({} files), {} files use a different image",
+            pending.len(),
+            pending.iter().map(|(_, _, t)| t.len()).sum::<usize>(),
+            different
+        )
+    );
+    if different > 0 {
+        println!(
+            "{}",
+            i18n::pick(
+                "다른 이미지(디스크별 커버 등)는 바꾸지 않습니다. 하나로 맞추려면 'mp3tag art dedupe'를 쓰세요.",
+                "Different images (per-disc covers etc.) are left alone. Use 'mp3tag art dedupe' to make them one."
+            )
+        );
+    }
+    exit::check_cancelled()?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+    if dry_run {
+        println!(
+            "{}",
+            i18n::pick(
+                "--dry-run: 기록하지 않았습니다.",
+                "--dry-run: nothing written."
+            )
+        );
+        return Ok(());
+    }
+    if !yes && !std::io::stdin().is_terminal() {
+        println!(
+            "{}",
+            i18n::pick(
+                "통일하려면 --yes를 붙여 다시 실행하세요.",
+                "Run again with --yes to unify."
+            )
+        );
+        return Ok(());
+    }
+
+    let mut applied = 0;
+    let mut failed = 0;
+    for (label, canonical, targets) in &pending {
+        if cancel::requested() {
+            break;
+        }
+        if !yes
+            && !Confirm::new()
+                .with_prompt(tr!(
+                    "{}: {}개 파일의 아트를 대표 이미지로 통일할까요?",
+                    "{}: unify the art of {} files to the main image?",
+                    label,
+                    targets.len()
+                ))
+                .default(true)
+                .interact()?
+        {
+            continue;
+        }
+        let update = TrackInfo {
+            album_art: Some(canonical.clone()),
+            source: "manual".to_string(),
+            ..Default::default()
+        };
+        let report = tagger::write_batch(
+            targets.iter().map(|file| (file.path.as_path(), &update)),
+            &cfg.write,
+        );
+        print_batch_failures(&report);
+        applied += report.written.len();
+        failed += report.failed.len();
+    }
+    println!(
+        "{}",
+        tr!(
+            "앨범 아트 통일 완료: 성공 {}건, 실패 {}건",
+            "Album art unified: {} succeeded, {} failed",
+            applied,
+            failed
+        )
+    );
+    refresh_media_server(cfg, applied);
+    exit::check_failures(failed, applied + failed)
+}
+
+/// 앨범 커버를 찾는 소스와 Cover Art Archive 클라이언트 (`art upgrade`, `art fill`).
+struct CoverSearch {
+    clients: Vec<Arc<dyn MusicSource>>,
+    cover_art: Option<CoverArtClient>,
+}
+
+impl CoverSearch {
+    fn new(cfg: &Config, kinds: &[SourceKind]) -> Result<Self> {
+        let clients = search_sources(cfg, kinds)?;
+        if clients.is_empty() {
+            return Err(exit::config_error(i18n::pick(
+                "사용할 수 있는 소스가 없습니다. 'mp3tag config'로 Spotify를 설정하세요.",
+                "No sources available. Set up Spotify with 'mp3tag config'.",
+            )));
+        }
+        let cover_art = if cfg.network.offline {
+            None
+        } else {
+            CoverArtClient::new().ok()
+        };
+        Ok(Self { clients, cover_art })
+    }
+
+    /// 태그의 앨범과 같은 앨범의 커버 후보를 큰 순서로 모은다. MusicBrainz 릴리스 ID가 있으면
+    /// Cover Art Archive 원본을 먼저 넣는다. 앨범 태그가 없으면 빈 목록을 반환한다.
+    fn candidates(&self, tags: &TrackInfo) -> Vec<ArtCandidate> {
+        let Some(album) = tags.album.as_deref() else {
+            return Vec::new();
+        };
+        let artist = tags
+            .album_artist
+            .clone()
+            .or_else(|| tags.artist())
+            .unwrap_or_default();
+        let mut first = Vec::new();
+        if let (Some(client), Some(id)) = (&self.cover_art, &tags.musicbrainz_album_id) {
+            if let Ok(data) = client.fetch_front(id) {
+                let name = format!("{} - {}", artist, album);
+                first.push(ArtCandidate::new("coverartarchive", name, data));
+            }
+        }
+        art_search::search_album_art(&self.clients, &artist, album, first)
+    }
+}
+
+/// `art upgrade`에서 `--below`도 art.min_size도 없을 때 교체 대상으로 보는 짧은 변 픽셀 수.
+const DEFAULT_UPGRADE_BELOW: u32 = 500;
+
+/// 앨범마다 짧은 변이 `below` 픽셀보다 작은 아트를 찾아, 소스에서 같은 앨범의 더 큰 커버를 검색하고
+/// 확인을 받아 교체한다. 검색은 앨범마다 한 번만 하며, 아트가 없는 파일은 건드리지 않는다.
+fn cmd_art_upgrade(
+    cfg: &Config,
+    inputs: &Inputs,
+    below: Option<u32>,
+    kinds: &[SourceKind],
+    yes: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let below = below.unwrap_or(match cfg.art.min_size {
+        0 => DEFAULT_UPGRADE_BELOW,
+        size => size,
+    });
+    let search = CoverSearch::new(cfg, kinds)?;
+    let art_side = |file: &Mp3File| {
+        file.current_tags
+            .as_ref()
+            .and_then(|t| t.album_art.as_deref())
+            .and_then(artwork::short_side)
+    };
+
+    let files = scan_with_progress(cfg, inputs)?;
+    let mut low = 0;
+    let mut applied = 0;
+    let mut failed = 0;
+    let mut not_found = Vec::new();
+    cancel::install();
+    for (label, album) in artwork::group_by_album(&files) {
+        if cancel::requested() {
+            break;
+        }
+        let targets: Vec<&Mp3File> = album
+            .iter()
+            .copied()
+            .filter(|f| art_side(f).is_some_and(|side| side < below))
+            .collect();
+        let Some(tags) = targets.first().and_then(|f| f.current_tags.as_ref()) else {
+            continue;
+        };
+        low += 1;
+        if tags.album.is_none() {
+            not_found.push(tr!("{} (앨범 태그 없음)", "{} (no album tag)", label));
+            continue;
+        }
+        let current = targets
+            .iter()
+            .filter_map(|f| art_side(f))
+            .max()
+            .unwrap_or(0);
+        let Some(best) = art_search::pick_larger(search.candidates(tags), current) else {
+            not_found.push(label);
+            continue;
+        };
+
+        println!(
+            "{}",
+            tr!(
+                "{}: {}개 파일, 짧은 변 {}px → {} ({}: {})",
+                "{}: {} files, short side {}px → {} ({}: {})",
+                label,
+                targets.len(),
+                current,
+                best.size_label(),
+                best.source,
+                best.album
+            )
+        );
+        if dry_run {
+            continue;
+        }
+        if !yes
+            && !Confirm::new()
+                .with_prompt(i18n::pick(
+                    "이 커버로 교체할까요?",
+                    "Replace with this cover?",
+                ))
+                .default(true)
+                .interact()?
+        {
+            continue;
+        }
+
+        let update = TrackInfo {
+            album_art: Some(best.data),
+            source: best.source,
+            ..Default::default()
+        };
+        let report = tagger::write_batch(
+            targets.iter().map(|file| (file.path.as_path(), &update)),
+            &cfg.write,
+        );
+        print_batch_failures(&report);
+        applied += report.written.len();
+        failed += report.failed.len();
+    }
+
+    if low == 0 {
+        println!(
+            "{}",
+            tr!(
+                "짧은 변이 {}px보다 작은 앨범 아트가 없습니다.",
+                "No album art with a short side below {}px.",
+                below
+            )
+        );
+        return Ok(());
+    }
+    if !not_found.is_empty() {
+        println!(
+            "\n{}",
+            tr!(
+                "더 큰 커버를 찾지 못한 앨범 {}개:",
+                "No larger cover found for {} albums:",
+                not_found.len()
+            )
+        );
+        for label in &not_found {
+            println!("  {}", label);
+        }
+    }
+    if dry_run {
+        println!(
+            "\n{}",
+            i18n::pick(
+                "--dry-run: 기록하지 않았습니다.",
+                "--dry-run: nothing written."
+            )
+        );
+        return Ok(());
+    }
+    println!(
+        "\n{}",
+        tr!(
+            "앨범 아트 교체 완료: 성공 {}건, 실패 {}건",
+            "Album art replaced: {} succeeded, {} failed",
+            applied,
+            failed
+        )
+    );
+    refresh_media_server(cfg, applied);
+    exit::check_failures(failed, applied + failed)
+}
+
+/// `art fill`에서 앨범 하나에 쓸 커버를 정한다. 같은 앨범의 다른 파일에 짧은 변이 `min_size` 이상인
+/// 아트가 있으면 검색하지 않고 그것을 쓰고, 없으면 앨범 태그로 한 번만 검색한다.
+/// 찾지 못하면 그 사유를 반환한다.
+fn album_cover(
+    search: &CoverSearch,
+    album: &[&Mp3File],
+    min_size: u32,
+) -> Result<ArtCandidate, &'static str> {
+    let adequate = |data: &[u8]| artwork::meets_min_size(data, min_size);
+    if let Some(art) = artwork::canonical_art(album).filter(|art| adequate(art)) {
+        let name = i18n::pick("앨범의 다른 파일", "another file in the album").to_string();
+        return Ok(ArtCandidate::new("id3", name, art.to_vec()));
+    }
+    let tags = album.iter().find_map(|f| f.current_tags.as_ref());
+    let Some(tags) = tags.filter(|t| t.album.is_some()) else {
+        return Err(i18n::pick("앨범 태그 없음", "no album tag"));
+    };
+    let candidates = search.candidates(tags);
+    if candidates.is_empty() {
+        return Err(i18n::pick("검색 결과 없음", "no results"));
+    }
+    candidates
+        .into_iter()
+        .find(|c| adequate(&c.data))
+        .ok_or(i18n::pick("커버가 너무 작음", "cover too small"))
+}
+
+/// 앨범 아트가 없는 파일만 골라 앨범마다 커버를 한 번만 찾아 기록한다. 같은 앨범의 다른 파일에
+/// 아트가 있으면 검색하지 않고 그 아트를 쓰며, 짧은 변이 art.min_size보다 작은 커버는 쓰지 않는다.
+fn cmd_art_fill(cfg: &Config, inputs: &Inputs, kinds: &[SourceKind], dry_run: bool) -> Result<()> {
+    let search = CoverSearch::new(cfg, kinds)?;
+    let has_art = |file: &Mp3File| {
+        file.current_tags
+            .as_ref()
+            .is_some_and(|t| t.album_art.is_some())
+    };
+
+    let files = scan_with_progress(cfg, inputs)?;
+    let mut missing = 0;
+    let mut applied = 0;
+    let mut failed = 0;
+    let mut not_found = Vec::new();
+    cancel::install();
+    for (label, album) in artwork::group_by_album(&files) {
+        if cancel::requested() {
+            break;
+        }
+        let targets: Vec<&Mp3File> = album.iter().copied().filter(|f| !has_art(f)).collect();
+        if targets.is_empty() {
+            continue;
+        }
+        missing += targets.len();
+
+        let cover = match album_cover(&search, &album, cfg.art.min_size) {
+            Ok(cover) => cover,
+            Err(reason) => {
+                not_found.push(format!("{} ({})", label, reason));
+                continue;
+            }
+        };
+
+        println!(
+            "{}",
+            tr!(
+                "{}: {}개 파일 ← {} ({}: {})",
+                "{}: {} files ← {} ({}: {})",
+                label,
+                targets.len(),
+                cover.size_label(),
+                cover.source,
+                cover.album
+            )
+        );
+        if dry_run {
+            continue;
+        }
+        let update = TrackInfo {
+            album_art: Some(cover.data),
+            source: cover.source,
+            ..Default::default()
+        };
+        let report = tagger::write_batch(
+            targets.iter().map(|file| (file.path.as_path(), &update)),
+            &cfg.write,
+        );
+        print_batch_failures(&report);
+        applied += report.written.len();
+        failed += report.failed.len();
+    }
+
+    if missing == 0 {
+        println!(
+            "{}",
+            i18n::pick(
+                "앨범 아트가 없는 파일이 없습니다.",
+                "No files are missing album art."
+            )
+        );
+        return Ok(());
+    }
+    if !not_found.is_empty() {
+        println!(
+            "\n{}",
+            tr!(
+                "알맞은 커버를 찾지 못한 앨범 {}개:",
+                "No suitable cover found for {} albums:",
+                not_found.len()
+            )
+        );
+        for label in &not_found {
+            println!("  {}", label);
+        }
+    }
+    if dry_run {
+        println!(
+            "\n{}",
+            i18n::pick(
+                "--dry-run: 기록하지 않았습니다.",
+                "--dry-run: nothing written."
+            )
+        );
+        return Ok(());
+    }
+    println!(
+        "\n{}",
+        tr!(
+            "앨범 아트 채우기 완료: 성공 {}건, 실패 {}건",
+            "Album art filled in: {} succeeded, {} failed",
+            applied,
+            failed
+        )
+    );
+    refresh_media_server(cfg, applied);
+    exit::check_failures(failed, applied + failed)
+}
+
+/// 앨범 폴더마다 전체 트랙 수와 전체 디스크 수를 실제 파일에 맞춰 기록한다.
+fn cmd_totals(cfg: &Config, inputs: &Inputs, dry_run: bool) -> Result<()> {
+    let files = scan_with_progress(cfg, inputs)?;
+    let albums = artwork::group_by_album(&files);
+    let changes: Vec<totals::TotalsChange> = albums
+        .values()
+        .flat_map(|album| totals::normalize_totals(album))
+        .collect();
+    if changes.is_empty() {
+        println!(
+            "{}",
+            i18n::pick(
+                "전체 트랙/디스크 수를 고칠 파일이 없습니다.",
+                "No files need total track/disc counts."
+            )
+        );
+        return Ok(());
+    }
+
+    let position = |number: Option<u32>, total: Option<u32>| {
+        if number.is_none() && total.is_none() {
+            return "-".to_string();
+        }
+        let show = |n: Option<u32>| n.map_or("?".to_string(), |n| n.to_string());
+        format!("{}/{}", show(number), show(total))
+    };
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("파일", "File"),
+        i18n::pick("트랙", "Track"),
+        i18n::pick("디스크", "Disc"),
+    ]);
+    for change in &changes {
+        let Some(info) = change.file.current_tags.as_ref() else {
+            continue;
+        };
+        let track = position(info.track_number, info.total_tracks);
+        let disc = position(info.disc_number, info.total_discs);
+        let arrow = |before: String, total: Option<u32>, number: Option<u32>| match total {
+            Some(total) => format!("{} → {}", before, position(number, Some(total))),
+            None => before,
+        };
+        table.add_row(vec![
+            change.file.filename().to_string(),
+            arrow(track, change.total_tracks, info.track_number),
+            arrow(disc, change.total_discs, info.disc_number),
+        ]);
+    }
+    println!("{table}");
+
+    if dry_run {
+        println!(
+            "\n{}",
+            tr!(
+                "--dry-run: {}개 파일을 기록하지 않았습니다.",
+                "--dry-run: {} files not written.",
+                changes.len()
+            )
+        );
+        return Ok(());
+    }
+    let updates: Vec<TrackInfo> = changes.iter().map(|c| c.update()).collect();
+    cancel::install();
+    let report = tagger::write_batch(
+        changes
+            .iter()
+            .zip(&updates)
+            .map(|(change, update)| (change.file.path.as_path(), update)),
+        &cfg.write,
+    );
+    print_batch_failures(&report);
+    println!(
+        "\n{}",
+        tr!(
+            "전체 트랙/디스크 수 기록 완료: {}",
+            "Total track/disc counts written: {}",
+            report.summary()
+        )
+    );
+    refresh_media_server(cfg, report.written.len());
+    exit::check_failures(report.failed.len(), updates.len())
+}
+
+/// 오디오 지문이 비슷한 파일을 묶어 같은 곡의 여러 판을 보고한다. 지문은 태그를 뺀 오디오 해시별로
+/// 캐시 디렉토리에 저장해 두어 다시 실행할 때 fpcalc를 부르지 않는다. 파일은 바꾸지 않는다.
+fn cmd_dedupe(
+    cfg: &Config,
+    inputs: &Inputs,
+    threshold: Option<f64>,
+    format: OutputFormat,
+) -> Result<()> {
+    let fpcalc = cfg.dedupe.fpcalc();
+    let length = cfg.dedupe.length();
+    let threshold = threshold.unwrap_or_else(|| cfg.dedupe.threshold());
+    if !(0.0..=1.0).contains(&threshold) {
+        anyhow::bail!(i18n::pick(
+            "--threshold는 0.0~1.0 사이여야 합니다",
+            "--threshold must be between 0.0 and 1.0"
+        ));
+    }
+    fingerprint::check_fpcalc(fpcalc).map_err(|e| {
+        exit::config_error(tr!(
+            "{:#}. Chromaprint의 fpcalc를 설치하거나 config.toml의 [dedupe] fpcalc에 경로를 지정하세요",
+            "{:#}. Install Chromaprint's fpcalc or set its path under [dedupe] fpcalc in config.toml",
+            e
+        ))
+    })?;
+
+    let files = scan_with_progress(cfg, inputs)?;
+    let cache = SourceCache::new(cfg.network.cache_dir());
+    let mut fingerprinted = Vec::new();
+    let mut fingerprints = Vec::new();
+    let mut failed = 0;
+    cancel::install();
+    let pb = progress_bar(files.len(), i18n::pick("지문 계산", "Fingerprinting"));
+    for file in &files {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let hash = audio::content_hash(&file.path);
+        let cached = hash
+            .as_deref()
+            .and_then(|hash| cache.load_fingerprint(hash, length));
+        let fp = match cached {
+            Some(fp) => fp,
+            None => match fingerprint::compute(fpcalc, &file.path, length) {
+                Ok(fp) => {
+                    if let Some(ref hash) = hash {
+                        cache.store_fingerprint(hash, length, &fp);
+                    }
+                    fp
+                }
+                Err(e) => {
+                    pb.suspend(|| println!("{}: {:#}", file.filename(), e));
+                    failed += 1;
+                    continue;
+                }
+            },
+        };
+        fingerprinted.push(file);
+        fingerprints.push(fp);
+    }
+    pb.finish_and_clear();
+    exit::check_cancelled()?;
+
+    let clusters = fingerprint::clusters(&fingerprints, threshold);
+    let header = match (format.is_delimited(), i18n::lang()) {
+        (true, _) => vec!["group", "path", "duration", "bitrate", "similarity"],
+        (false, Lang::Ko) => vec!["묶음", "파일", "재생 시간", "비트레이트", "유사도"],
+        (false, Lang::En) => vec!["Group", "File", "Duration", "Bitrate", "Similarity"],
+    };
+    let mut rows = Vec::new();
+    for (n, cluster) in clusters.iter().enumerate() {
+        for &(i, score) in &cluster.members {
+            let path = &fingerprinted[i].path;
+            rows.push(vec![
+                (n + 1).to_string(),
+                path.display().to_string(),
+                audio::duration_ms(path)
+                    .map(|ms| format!("{}:{:02}", ms / 60_000, ms / 1000 % 60))
+                    .unwrap_or_else(|| "-".to_string()),
+                audio::bitrate_kbps(path)
+                    .map(|b| format!("{} kbps", b))
+                    .unwrap_or_else(|| "-".to_string()),
+                format!("{:.0}%", score * 100.0),
+            ]);
+        }
+    }
+
+    if format.is_delimited() {
+        print!("{}", output::render(format, &header, &rows));
+    } else if clusters.is_empty() {
+        println!(
+            "{}",
+            i18n::pick(
+                "같은 곡으로 보이는 파일이 없습니다.",
+                "No files look like the same song."
+            )
+        );
+    } else {
+        print!("{}", output::render(format, &header, &rows));
+        println!(
+            "\n{}",
+            tr!(
+                "같은 곡으로 보이는 묶음 {}개 (파일 {}개, 유사도 {:.0}% 이상)",
+                "{} groups that look like the same song ({} files, {:.0}% similar or more)",
+                clusters.len(),
+                rows.len(),
+                threshold * 100.0
+            )
+        );
+    }
+    exit::check_failures(failed, files.len())
+}
+
+/// 라이브러리 데이터베이스를 입력 경로와 맞추고, 스캔한 디렉토리의 변경 기록과 캐시의 건너뜀/기억한 결과를 가져온다.
+#[cfg(feature = "library")]
+fn cmd_library_scan(cfg: &Config, inputs: &Inputs) -> Result<()> {
+    let paths = inputs.mp3_paths()?;
+    cancel::install();
+    let pb = progress_bar(paths.len(), i18n::pick("라이브러리 스캔", "Library scan"));
+    let (files, summary) = sync_library(cfg, inputs, &paths, &pb)?;
+    pb.finish_and_clear();
+    exit::check_cancelled()?;
+
+    let db = library::Library::open(&cfg.library_path())?;
+    let dirs: BTreeSet<&Path> = files
+        .iter()
+        .map(|f| f.path.parent().unwrap_or(Path::new(".")))
+        .collect();
+    let history = db.import_history(dirs)?;
+    db.import_decisions(&cfg.network.cache_dir())?;
+    println!(
+        "{}",
+        tr!(
+            "{}: 추가 {}, 갱신 {}, 그대로 {}, 삭제 {} (변경 기록 {}건 가져옴)",
+            "{}: {} added, {} updated, {} unchanged, {} removed ({} history entries imported)",
+            cfg.library_path().display(),
+            summary.added,
+            summary.updated,
+            summary.unchanged,
+            summary.removed,
+            history
+        )
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "library"))]
+fn cmd_library_scan(_cfg: &Config, _inputs: &Inputs) -> Result<()> {
+    anyhow::bail!(i18n::pick(
+        "라이브러리 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features library",
+        "The library is not enabled. Rebuild with: cargo build --features library"
+    ))
+}
+
+/// 라이브러리 데이터베이스의 통계를 출력한다.
+#[cfg(feature = "library")]
+fn cmd_library_stats(cfg: &Config) -> Result<()> {
+    let path = cfg.library_path();
+    if !path.exists() {
+        return Err(exit::nothing_matched(tr!(
+            "라이브러리 데이터베이스가 없습니다: {}. 먼저 'mp3tag library scan'을 실행하세요",
+            "No library database: {}. Run 'mp3tag library scan' first",
+            path.display()
+        )));
+    }
+    let stats = library::Library::open(&path)?.stats()?;
+    let mut table = Table::new();
+    table.set_header(vec![i18n::pick("항목", "Item"), i18n::pick("값", "Value")]);
+    let minutes = stats.duration_ms / 60_000;
+    let rows = [
+        (i18n::pick("파일", "Files"), stats.files.to_string()),
+        (
+            i18n::pick("태그 없음", "Untagged"),
+            stats.untagged.to_string(),
+        ),
+        (i18n::pick("앨범", "Albums"), stats.albums.to_string()),
+        (i18n::pick("아티스트", "Artists"), stats.artists.to_string()),
+        (
+            i18n::pick("재생 시간", "Duration"),
+            tr!("{}시간 {}분", "{}h {}m", minutes / 60, minutes % 60),
+        ),
+        (i18n::pick("크기", "Size"), format_bytes(stats.bytes)),
+        (
+            i18n::pick("연도 없음", "Missing year"),
+            stats.missing_year.to_string(),
+        ),
+        (
+            i18n::pick("앨범 아트 없음", "Missing album art"),
+            stats.missing_art.to_string(),
+        ),
+        (
+            i18n::pick("앨범 없음", "Missing album"),
+            stats.missing_album.to_string(),
+        ),
+        (
+            i18n::pick("연도가 빠진 앨범", "Albums missing a year"),
+            stats.albums_missing_year.to_string(),
+        ),
+        (
+            i18n::pick("건너뛴 파일", "Skipped files"),
+            stats.skipped.to_string(),
+        ),
+        (
+            i18n::pick("기억한 결과", "Remembered results"),
+            stats.remembered.to_string(),
+        ),
+    ];
+    for (name, value) in rows {
+        table.add_row(vec![Cell::new(name), Cell::new(value)]);
+    }
+    for (source, count) in &stats.history {
+        table.add_row(vec![
+            Cell::new(tr!("변경 기록 ({})", "History ({})", source)),
+            Cell::new(count),
+        ]);
+    }
+    if let Some(secs) = stats.last_scan {
+        let time = std::time::UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64);
+        let (y, mo, d, h, mi, _) = tagger::utc_date_time(time);
+        table.add_row(vec![
+            Cell::new(i18n::pick("마지막 스캔", "Last scan")),
+            Cell::new(format!("{}-{:02}-{:02} {:02}:{:02} UTC", y, mo, d, h, mi)),
+        ]);
+    }
+    println!("{}", path.display());
+    println!("{table}");
+    Ok(())
+}
+
+/// 원본 트리의 태그를 사본 트리의 같은 곡에 기록한다. 원본보다 나중에 수정된 사본은 `overwrite_newer`가 아니면 건너뛴다.
+fn cmd_sync(
+    cfg: &Config,
+    from: &Path,
+    to: &Path,
+    copy_art: bool,
+    overwrite_newer: bool,
+    dry_run: bool,
+) -> Result<()> {
+    for dir in [from, to] {
+        if !dir.is_dir() {
+            anyhow::bail!(tr!(
+                "디렉토리가 아닙니다: {}",
+                "Not a directory: {}",
+                dir.display()
+            ));
+        }
+    }
+    let masters = scan_with_progress(cfg, &Inputs::one(from))?;
+    let copies = scan_with_progress(cfg, &Inputs::one(to))?;
+    cancel::install();
+    let pb = progress_bar(
+        masters.len() + copies.len(),
+        i18n::pick("오디오 비교", "Comparing audio"),
+    );
+    let hashed = |files: &[Mp3File]| -> Vec<(PathBuf, Option<String>)> {
+        files
+            .iter()
+            .take_while(|_| !cancel::requested())
+            .map(|f| {
+                pb.inc(1);
+                (f.path.clone(), audio::content_hash(&f.path))
+            })
+            .collect()
+    };
+    let master_keys = hashed(&masters);
+    let copy_keys = hashed(&copies);
+    pb.finish_and_clear();
+    exit::check_cancelled()?;
+
+    let pairs = tag_sync::pair_files(&master_keys, from, &copy_keys, to);
+    if pairs.is_empty() {
+        return Err(exit::nothing_matched(tr!(
+            "{}와 {}에서 짝지을 수 있는 파일이 없습니다",
+            "No files to pair between {} and {}",
+            from.display(),
+            to.display()
+        )));
+    }
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("사본", "Copy"),
+        i18n::pick("짝", "Pair"),
+        i18n::pick("결과", "Result"),
+    ]);
+    let mut updates = Vec::new();
+    let mut newer = 0;
+    for pair in &pairs {
+        let (master, copy) = (&masters[pair.master], &copies[pair.copy]);
+        let Some(info) = tag_sync::tags_to_copy(master, copy, copy_art) else {
+            continue;
+        };
+        let name = copy.path.strip_prefix(to).unwrap_or(&copy.path);
+        if !overwrite_newer && modified(&copy.path) > modified(&master.path) {
+            table.add_row(vec![
+                Cell::new(name.display()),
+                Cell::new(pair.matched_by.label()),
+                Cell::new(i18n::pick(
+                    "사본이 더 나중에 바뀜, 건너뜀",
+                    "copy changed later, skipped",
+                )),
+            ]);
+            newer += 1;
+            continue;
+        }
+        let fields: Vec<&str> = staging::changed_fields(copy.current_tags.as_ref(), &info)
+            .into_iter()
+            .map(|(field, _, _)| field)
+            .collect();
+        table.add_row(vec![
+            Cell::new(name.display()),
+            Cell::new(pair.matched_by.label()),
+            Cell::new(fields.join(", ")),
+        ]);
+        updates.push((copy, info));
+    }
+    if updates.len() + newer > 0 {
+        println!("{table}");
+    }
+    let by_hash = pairs
+        .iter()
+        .filter(|p| p.matched_by == tag_sync::MatchedBy::Hash)
+        .count();
+    println!(
+        "{}",
+        tr!(
+            "짝지은 파일 {}개 (오디오 {}, 경로 {}): 바꿀 파일 {}, 같음 {}, 사본이 더 나중 {}",
+            "{} files paired ({} by audio, {} by path): {} to update, {} identical, {} copies newer",
+            pairs.len(),
+            by_hash,
+            pairs.len() - by_hash,
+            updates.len(),
+            pairs.len() - updates.len() - newer,
+            newer
+        )
+    );
+    if copies.len() > pairs.len() {
+        println!(
+            "{}",
+            tr!(
+                "원본과 짝이 없는 사본 {}개는 건드리지 않습니다.",
+                "Leaving {} copies without an original alone.",
+                copies.len() - pairs.len()
+            )
+        );
+    }
+    if dry_run || updates.is_empty() {
+        return Ok(());
+    }
+
+    let report = tagger::write_batch(
+        updates.iter().map(|(f, info)| (f.path.as_path(), info)),
+        &cfg.write,
+    );
+    print_batch_failures(&report);
+    println!("{}", report.summary());
+    refresh_media_server(cfg, report.written.len());
+    exit::check_cancelled()?;
+    exit::check_failures(report.failed.len(), updates.len())
+}
+
+/// FLAC 원본의 태그를 변환한 MP3에 기록한다. 원본에 값이 있고 MP3와 다른 필드만 기록한다.
+fn cmd_convert_tags(
+    cfg: &Config,
+    from: &Path,
+    to: &Path,
+    copy_art: bool,
+    dry_run: bool,
+) -> Result<()> {
+    for dir in [from, to] {
+        if !dir.is_dir() {
+            anyhow::bail!(tr!(
+                "디렉토리가 아닙니다: {}",
+                "Not a directory: {}",
+                dir.display()
+            ));
+        }
+    }
+    let flacs = flac::find_paths(from)?;
+    if flacs.is_empty() {
+        return Err(exit::nothing_matched(tr!(
+            "{}에서 FLAC 파일을 찾을 수 없습니다",
+            "No FLAC files found in {}",
+            from.display()
+        )));
+    }
+    let mp3s = scan_with_progress(cfg, &Inputs::one(to))?;
+    let mp3_paths: Vec<PathBuf> = mp3s.iter().map(|f| f.path.clone()).collect();
+    let pairs = tag_sync::pair_converted(&flacs, from, &mp3_paths, to);
+    if pairs.is_empty() {
+        return Err(exit::nothing_matched(tr!(
+            "{}의 MP3 중 FLAC 원본과 짝지을 수 있는 파일이 없습니다",
+            "No MP3s in {} pair with a FLAC original",
+            to.display()
+        )));
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "MP3",
+        i18n::pick("짝", "Pair"),
+        i18n::pick("결과", "Result"),
+    ]);
+    let mut updates = Vec::new();
+    let mut failed = 0;
+    for pair in &pairs {
+        let mp3 = &mp3s[pair.copy];
+        let name = mp3.path.strip_prefix(to).unwrap_or(&mp3.path);
+        let source = match flac::read_tags(&flacs[pair.master]) {
+            Ok(info) => Mp3File {
+                path: flacs[pair.master].clone(),
+                has_tags: true,
+                current_tags: Some(info),
+                encoder: None,
+            },
+            Err(e) => {
+                table.add_row(vec![
+                    Cell::new(name.display()),
+                    Cell::new(pair.matched_by.label()),
+                    Cell::new(tr!("실패: {:#}", "Failed: {:#}", e)),
+                ]);
+                failed += 1;
+                continue;
+            }
+        };
+        let Some(mut info) = tag_sync::tags_to_copy(&source, mp3, copy_art) else {
+            continue;
+        };
+        info.source = "flac".to_string();
+        let fields: Vec<&str> = staging::changed_fields(mp3.current_tags.as_ref(), &info)
+            .into_iter()
+            .map(|(field, _, _)| field)
+            .collect();
+        table.add_row(vec![
+            Cell::new(name.display()),
+            Cell::new(pair.matched_by.label()),
+            Cell::new(fields.join(", ")),
+        ]);
+        updates.push((mp3, info));
+    }
+    if updates.len() + failed > 0 {
+        println!("{table}");
+    }
+    println!(
+        "{}",
+        tr!(
+            "짝지은 파일 {}개: 바꿀 파일 {}, 같음 {}, 읽기 실패 {}",
+            "{} files paired: {} to update, {} identical, {} unreadable",
+            pairs.len(),
+            updates.len(),
+            pairs.len() - updates.len() - failed,
+            failed
+        )
+    );
+    if mp3s.len() > pairs.len() {
+        println!(
+            "{}",
+            tr!(
+                "FLAC 원본과 짝이 없는 MP3 {}개는 건드리지 않습니다.",
+                "Leaving {} MP3s without a FLAC original alone.",
+                mp3s.len() - pairs.len()
+            )
+        );
+    }
+    if dry_run || updates.is_empty() {
+        return exit::check_failures(failed, pairs.len());
+    }
+
+    cancel::install();
+    let report = tagger::write_batch(
+        updates.iter().map(|(f, info)| (f.path.as_path(), info)),
+        &cfg.write,
+    );
+    print_batch_failures(&report);
+    println!("{}", report.summary());
+    refresh_media_server(cfg, report.written.len());
+    exit::check_cancelled()?;
+    exit::check_failures(failed + report.failed.len(), pairs.len())
+}
+
+/// 디렉토리의 MP3마다 `hash`를 계산한다 (상대 경로 → 값).
+fn hash_files<T>(directory: &Path, hash: impl Fn(&Path) -> T) -> Result<BTreeMap<String, T>> {
+    let paths = scanner::find_mp3_paths(directory)?;
+    cancel::install();
+    let pb = progress_bar(
+        paths.len(),
+        i18n::pick("체크섬 계산", "Computing checksums"),
+    );
+    let mut sums = BTreeMap::new();
+    for path in &paths {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        sums.insert(manifest::relative_key(path, directory), hash(path));
+    }
+    pb.finish_and_clear();
+    exit::check_cancelled()?;
+    Ok(sums)
+}
+
+/// 오디오 체크섬 목록을 만든다. 읽지 못한 파일은 목록에 넣지 않고 알린다.
+fn cmd_checksum_create(directory: &Path, manifest: Option<PathBuf>) -> Result<()> {
+    let target = manifest.unwrap_or_else(|| directory.join(manifest::MANIFEST_FILE));
+    let sums = hash_files(directory, |path| {
+        (audio::content_hash(path), audio::tag_hash(path))
+    })?;
+    if sums.is_empty() {
+        return Err(exit::nothing_matched(tr!(
+            "{}에서 MP3 파일을 찾을 수 없습니다",
+            "No MP3 files found in {}",
+            directory.display()
+        )));
+    }
+    let mut list = manifest::Manifest::default();
+    let mut failed = 0;
+    for (path, (audio, tags)) in sums {
+        match audio {
+            Some(audio) => {
+                list.entries.insert(path, manifest::Entry { audio, tags });
+            }
+            None => {
+                println!("{}", tr!("{}: 읽기 실패", "{}: read failed", path));
+                failed += 1;
+            }
+        }
+    }
+    list.save(&target)?;
+    println!(
+        "{}",
+        tr!(
+            "{}: 파일 {}개의 오디오 체크섬을 기록했습니다.",
+            "{}: recorded audio checksums for {} files.",
+            target.display(),
+            list.entries.len()
+        )
+    );
+    exit::check_failures(failed, list.entries.len() + failed)
+}
+
+/// 오디오 체크섬 목록과 지금 파일을 비교한다. 바뀌거나 없어지거나 읽지 못한 파일이 있으면 부분 실패로 끝낸다.
+fn cmd_checksum_verify(
+    directory: &Path,
+    manifest: Option<PathBuf>,
+    all: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let source = manifest.unwrap_or_else(|| directory.join(manifest::MANIFEST_FILE));
+    let list = manifest::Manifest::load(&source)?;
+    let results = list.verify(&hash_files(directory, audio::content_hash)?);
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .filter(|(_, status)| all || *status != manifest::Status::Ok)
+        .map(|(path, status)| vec![path.clone(), status.label().to_string()])
+        .collect();
+    let count = |wanted: manifest::Status| results.iter().filter(|(_, s)| *s == wanted).count();
+    let problems = results.iter().filter(|(_, s)| s.is_problem()).count();
+
+    if format.is_delimited() {
+        print!("{}", output::render(format, &["path", "status"], &rows));
+    } else {
+        if !rows.is_empty() {
+            print!(
+                "{}",
+                output::render(
+                    format,
+                    &[i18n::pick("파일", "File"), i18n::pick("결과", "Result")],
+                    &rows
+                )
+            );
+        }
+        println!(
+            "{}",
+            tr!(
+                "정상 {}, 오디오 바뀜 {}, 없음 {}, 새 파일 {}, 읽기 실패 {}",
+                "{} OK, {} audio changed, {} missing, {} new, {} unreadable",
+                count(manifest::Status::Ok),
+                count(manifest::Status::Changed),
+                count(manifest::Status::Missing),
+                count(manifest::Status::New),
+                count(manifest::Status::Unreadable)
+            )
+        );
+    }
+    exit::check_failures(problems, list.entries.len())
+}
+
+/// 목록을 만든 뒤 태그가 바뀐 파일을 출력한다. 태그 부분만 읽으므로 오디오 체크섬 확인보다 훨씬 빠르다.
+fn cmd_checksum_modified(directory: &Path, since: Option<PathBuf>) -> Result<()> {
+    let source = since.unwrap_or_else(|| directory.join(manifest::MANIFEST_FILE));
+    let list = manifest::Manifest::load(&source)?;
+    let modified = list.modified(&hash_files(directory, audio::tag_hash)?);
+    if modified.is_empty() {
+        return Err(exit::nothing_matched(tr!(
+            "{} 이후 태그가 바뀐 파일이 없습니다",
+            "No files have changed tags since {}",
+            source.display()
+        )));
+    }
+    for path in &modified {
+        println!("{}", path);
+    }
+    Ok(())
+}
+
+/// 캐시 디렉토리의 결정(건너뜀, 기억한 결과)을 JSON Lines로 내보낸다.
+#[cfg(feature = "library")]
+fn cmd_library_export(cfg: &Config, output: &Path) -> Result<()> {
+    let db = library::Library::open(&cfg.library_path())?;
+    let records = db.export_records(&cfg.network.cache_dir())?;
+    let mut lines = String::new();
+    for record in &records {
+        lines += &serde_json::to_string(record)?;
+        lines.push('\n');
+    }
+    if output.as_os_str() == "-" {
+        print!("{}", lines);
+        return Ok(());
+    }
+    std::fs::write(output, lines).with_context(|| {
+        tr!(
+            "기록할 수 없습니다: {}",
+            "Cannot write: {}",
+            output.display()
+        )
+    })?;
+    let skipped = records
+        .iter()
+        .filter(|r| r.kind == library::DECISION_SKIP)
+        .count();
+    println!(
+        "{}",
+        tr!(
+            "{}: 건너뛴 파일 {}개, 기억한 결과 {}개를 내보냈습니다.",
+            "{}: exported {} skipped files and {} remembered results.",
+            output.display(),
+            skipped,
+            records.len() - skipped
+        )
+    );
+    Ok(())
+}
+
+/// 내보낸 결정을 가져오고, `apply`가 있으면 오디오가 같은 파일에 기억한 결과를 바로 기록한다.
+#[cfg(feature = "library")]
+fn cmd_library_import(cfg: &Config, input: &Path, apply: &[PathBuf], dry_run: bool) -> Result<()> {
+    let text = if input.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(input)
+            .with_context(|| tr!("읽을 수 없습니다: {}", "Cannot read: {}", input.display()))?
+    };
+    let records = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| {
+            serde_json::from_str::<library::DecisionRecord>(line).with_context(|| {
+                tr!(
+                    "{}번째 줄을 해석할 수 없습니다",
+                    "Cannot parse line {}",
+                    n + 1
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let db = library::Library::open(&cfg.library_path())?;
+    let summary = db.import_records(&cfg.network.cache_dir(), &records)?;
+    println!(
+        "{}",
+        tr!(
+            "건너뛴 파일 {}개, 기억한 결과 {}개를 가져왔습니다.",
+            "Imported {} skipped files and {} remembered results.",
+            summary.skipped,
+            summary.remembered
+        )
+    );
+    if apply.is_empty() {
+        return Ok(());
+    }
+
+    let imported: std::collections::HashSet<&str> = records
+        .iter()
+        .filter(|r| r.remembered.is_some())
+        .map(|r| r.hash.as_str())
+        .collect();
+    let files = scan_with_progress(cfg, &Inputs::expand(apply)?)?;
+    let cache = SourceCache::new(cfg.network.cache_dir());
+    cancel::install();
+    let pb = progress_bar(files.len(), i18n::pick("오디오 비교", "Comparing audio"));
+    let mut targets = Vec::new();
+    for file in &files {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let Some(hash) = audio::content_hash(&file.path) else {
+            continue;
+        };
+        if imported.contains(hash.as_str()) {
+            // 가져오면서 캐시에 넣은 앨범 아트까지 채워진 결과
+            if let Some(found) = cache.load_match(&hash) {
+                targets.push((file, found.track));
+            }
+        }
+    }
+    pb.finish_and_clear();
+    exit::check_cancelled()?;
+    if targets.is_empty() {
+        return Err(exit::nothing_matched(i18n::pick(
+            "가져온 결과와 오디오가 같은 파일이 없습니다",
+            "No files have the same audio as the imported results",
+        )));
+    }
+    for (file, track) in &targets {
+        println!(
+            "{} → {} - {}",
+            file.path.display(),
+            track.display_artist(),
+            track.display_title()
+        );
+    }
+    if dry_run {
+        println!(
+            "\n{}",
+            tr!(
+                "{}개 파일에 적용할 수 있습니다 (--dry-run).",
+                "{} files can be applied (--dry-run).",
+                targets.len()
+            )
+        );
+        return Ok(());
+    }
+    let report = tagger::write_batch(
+        targets.iter().map(|(f, track)| (f.path.as_path(), track)),
+        &cfg.write,
+    );
+    print_batch_failures(&report);
+    println!("\n{}", report.summary());
+    refresh_media_server(cfg, report.written.len());
+    exit::check_cancelled()?;
+    exit::check_failures(report.failed.len(), targets.len())
+}
+
+#[cfg(not(feature = "library"))]
+fn cmd_library_export(_cfg: &Config, _output: &Path) -> Result<()> {
+    anyhow::bail!(i18n::pick(
+        "라이브러리 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features library",
+        "The library is not enabled. Rebuild with: cargo build --features library"
+    ))
+}
+
+#[cfg(not(feature = "library"))]
+fn cmd_library_import(
+    _cfg: &Config,
+    _input: &Path,
+    _apply: &[PathBuf],
+    _dry_run: bool,
+) -> Result<()> {
+    anyhow::bail!(i18n::pick(
+        "라이브러리 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features library",
+        "The library is not enabled. Rebuild with: cargo build --features library"
+    ))
+}
+
+/// 라이브러리 데이터베이스에서 검색식과 맞는 파일을 `scan`과 같은 열로 출력한다. 파일은 읽지 않는다.
+#[cfg(feature = "library")]
+fn cmd_query(
+    cfg: &Config,
+    query: &str,
+    columns: &[Column],
+    sort: Option<Column>,
+    format: OutputFormat,
+) -> Result<()> {
+    let query = crate::core::query::Query::parse(query)?;
+    let path = cfg.library_path();
+    if !path.exists() {
+        return Err(exit::nothing_matched(tr!(
+            "라이브러리 데이터베이스가 없습니다: {}. 먼저 'mp3tag library scan'을 실행하세요",
+            "No library database: {}. Run 'mp3tag library scan' first",
+            path.display()
+        )));
+    }
+    let mut files = library::Library::open(&path)?.query(&query)?;
+    if let Some(column) = sort {
+        listing::sort_files(&mut files, column);
+    }
+    let columns = match columns {
+        [] => &[
+            Column::Path,
+            Column::Title,
+            Column::Artist,
+            Column::Album,
+            Column::Year,
+        ][..],
+        columns => columns,
+    };
+    let rows: Vec<Vec<String>> = files
+        .iter()
+        .map(|file| columns.iter().map(|c| c.value(file)).collect())
+        .collect();
+
+    if format.is_delimited() {
+        let header: Vec<&str> = columns.iter().map(|c| c.name()).collect();
+        print!("{}", output::render(format, &header, &rows));
+    } else if !files.is_empty() {
+        let header: Vec<&str> = columns.iter().map(|c| c.header()).collect();
+        print!("{}", output::render(format, &header, &rows));
+        println!("\n{}", tr!("{}개 파일", "{} files", files.len()));
+    }
+    check_filtered(&files)
+}
+
+#[cfg(not(feature = "library"))]
+fn cmd_query(
+    _cfg: &Config,
+    _query: &str,
+    _columns: &[Column],
+    _sort: Option<Column>,
+    _format: OutputFormat,
+) -> Result<()> {
+    anyhow::bail!(i18n::pick(
+        "라이브러리 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features library",
+        "The library is not enabled. Rebuild with: cargo build --features library"
+    ))
+}
+
+#[cfg(not(feature = "library"))]
+fn cmd_library_stats(_cfg: &Config) -> Result<()> {
+    anyhow::bail!(i18n::pick(
+        "라이브러리 기능이 활성화되지 않았습니다. 다시 빌드하세요: cargo build --features library",
+        "The library is not enabled. Rebuild with: cargo build --features library"
+    ))
+}
+
+/// APEv2/Lyrics3 태그의 값 중 ID3에 없는(`overwrite`면 다른) 필드를 ID3로 옮기고 레거시 블록을 잘라 낸다.
+/// 옮길 값이 없어도 레거시 블록은 지운다. 기록 전 백업 설정을 따른다.
+fn cmd_migrate_legacy(cfg: &Config, inputs: &Inputs, dry_run: bool, overwrite: bool) -> Result<()> {
+    let files = scan_with_progress(cfg, inputs)?;
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("파일", "File"),
+        i18n::pick("레거시 태그", "Legacy tags"),
+        i18n::pick("옮길 필드", "Fields to move"),
+    ]);
+    let mut targets = Vec::new();
+    for file in &files {
+        let found = match legacy::detect(&file.path) {
+            Ok(Some(found)) => found,
+            Ok(None) => continue,
+            Err(e) => {
+                println!(
+                    "{}",
+                    tr!(
+                        "{}: 읽기 실패 ({})",
+                        "{}: read failed ({})",
+                        file.filename(),
+                        e
+                    )
+                );
+                continue;
+            }
+        };
+        let mut info = found.info;
+        let kept = tagger::keep_protected(&file.current_tags, &mut info, &cfg.write);
+        let update = legacy_update(&info, file.current_tags.as_ref(), overwrite);
+        let fields = legacy_fields(&update);
+        let mut cell = if fields.is_empty() {
+            "-".to_string()
+        } else {
+            fields.join(", ")
+        };
+        if let Some(note) = protected_note(&kept) {
+            cell = format!("{} ({})", cell, note);
+        }
+        table.add_row(vec![
+            Cell::new(file.filename()),
+            Cell::new(found.kinds.join(", ")),
+            Cell::new(cell),
+        ]);
+        targets.push((file, update, !fields.is_empty()));
+    }
+
+    if targets.is_empty() {
+        println!(
+            "{}",
+            i18n::pick(
+                "APEv2/Lyrics3 태그가 있는 파일이 없습니다.",
+                "No files have APEv2/Lyrics3 tags."
+            )
+        );
+        return Ok(());
+    }
+    println!("{table}");
+    if dry_run {
+        println!(
+            "\n{}",
+            tr!(
+                "--dry-run: {}개 파일을 기록하지 않았습니다.",
+                "--dry-run: {} files not written.",
+                targets.len()
+            )
+        );
+        return Ok(());
+    }
 
-    let merged = tagger::merge_tags(&mp3.current_tags, &new_info);
-    tagger::write_tags(file, &merged)?;
-
-    println!("태그가 업데이트되었습니다: {}", file.display());
-    Ok(())
+    let mut migrated = 0;
+    let mut failed = 0;
+    cancel::install();
+    for (file, update, has_fields) in &targets {
+        if cancel::requested() {
+            break;
+        }
+        let result = (|| -> Result<()> {
+            tagger::check_writable(&file.path, &cfg.write)?;
+            if *has_fields {
+                tagger::write_tags(&file.path, update, &cfg.write)?;
+            } else {
+                backup::backup(&file.path, &cfg.write)?;
+            }
+            // ID3v2 크기가 바뀌었을 수 있으므로 위치를 다시 찾는다
+            if let Some(found) = legacy::detect(&file.path)? {
+                legacy::strip(&file.path, &found)?;
+            }
+            Ok(())
+        })();
+        match result {
+            Ok(()) => migrated += 1,
+            Err(e) => {
+                println!(
+                    "{}",
+                    tr!(
+                        "{}: 정리 실패 ({:#})",
+                        "{}: cleanup failed ({:#})",
+                        file.filename(),
+                        e
+                    )
+                );
+                failed += 1;
+            }
+        }
+    }
+    println!(
+        "\n{}",
+        tr!(
+            "레거시 태그 정리 완료: 성공 {}건, 실패 {}건",
+            "Legacy tags cleaned up: {} succeeded, {} failed",
+            migrated,
+            failed
+        )
+    );
+    refresh_media_server(cfg, migrated);
+    exit::check_failures(failed, migrated + failed)
 }
 
-/// 태그가 없는 파일을 Spotify에서 검색하여 사용자 선택 후 적용한다.
-fn cmd_fetch(path: &PathBuf) -> Result<()> {
-    let cfg = config::load_config();
-
-    if !cfg.spotify.is_configured() {
-        println!("Spotify가 설정되지 않았습니다. 먼저 'mp3tag config'를 실행하세요.");
-        return Ok(());
+/// 레거시 태그 값 중 ID3로 옮길 필드만 남긴다.
+fn legacy_update(found: &TrackInfo, existing: Option<&TrackInfo>, overwrite: bool) -> TrackInfo {
+    let existing = existing.cloned().unwrap_or_default();
+    fn pick<T: Clone + PartialEq>(new: &Option<T>, old: &Option<T>, overwrite: bool) -> Option<T> {
+        new.clone()
+            .filter(|n| (overwrite || old.is_none()) && Some(n) != old.as_ref())
+    }
+    TrackInfo {
+        title: pick(&found.title, &existing.title, overwrite),
+        artists: pick(&found.artist(), &existing.artist(), overwrite)
+            .as_deref()
+            .map(split_artists)
+            .unwrap_or_default(),
+        album: pick(&found.album, &existing.album, overwrite),
+        album_artist: pick(&found.album_artist, &existing.album_artist, overwrite),
+        track_number: pick(&found.track_number, &existing.track_number, overwrite),
+        total_tracks: pick(&found.total_tracks, &existing.total_tracks, overwrite),
+        year: pick(&found.year, &existing.year, overwrite),
+        genre: pick(&found.genre, &existing.genre, overwrite),
+        label: pick(&found.label, &existing.label, overwrite),
+        composer: pick(&found.composer, &existing.composer, overwrite),
+        lyrics: pick(&found.lyrics, &existing.lyrics, overwrite),
+        album_art: pick(&found.album_art, &existing.album_art, overwrite),
+        source: found.source.clone(),
+        ..Default::default()
     }
+}
 
-    let client = SpotifyClient::new(&cfg.spotify)?;
-    let files = scanner::scan_path(path)?;
-    let targets: Vec<_> = files.into_iter().filter(|f| !f.has_tags).collect();
+/// 값이 있는 필드 이름 목록.
+fn legacy_fields(update: &TrackInfo) -> Vec<&'static str> {
+    [
+        (i18n::pick("제목", "Title"), update.title.is_some()),
+        (i18n::pick("아티스트", "Artist"), !update.artists.is_empty()),
+        (i18n::pick("앨범", "Album"), update.album.is_some()),
+        (
+            i18n::pick("앨범 아티스트", "Album artist"),
+            update.album_artist.is_some(),
+        ),
+        (
+            i18n::pick("트랙", "Track"),
+            update.track_number.is_some() || update.total_tracks.is_some(),
+        ),
+        (i18n::pick("연도", "Year"), update.year.is_some()),
+        (i18n::pick("장르", "Genre"), update.genre.is_some()),
+        (i18n::pick("음반사", "Label"), update.label.is_some()),
+        (i18n::pick("작곡", "Composer"), update.composer.is_some()),
+        (i18n::pick("가사", "Lyrics"), update.lyrics.is_some()),
+        (
+            i18n::pick("앨범 아트", "Album art"),
+            update.album_art.is_some(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, present)| *present)
+    .map(|(name, _)| name)
+    .collect()
+}
 
-    if targets.is_empty() {
-        println!("모든 파일에 이미 태그가 있습니다.");
-        return Ok(());
+/// 앨범 검색어를 만든다. 태그에 앨범이 있으면 "앨범 아티스트(또는 아티스트) 앨범", 없으면 폴더명.
+fn album_query(files: &[Mp3File], directory: &Path) -> String {
+    let tagged = files
+        .iter()
+        .filter_map(|f| f.current_tags.as_ref())
+        .find(|t| t.album.is_some());
+
+    if let Some(tags) = tagged {
+        let artist = tags.album_artist.clone().or_else(|| tags.artist());
+        let album = tags.album.as_deref().unwrap_or_default();
+        return match artist {
+            Some(artist) => format!("{} {}", artist, album),
+            None => album.to_string(),
+        };
     }
 
-    println!("태그가 없는 파일 {}개를 찾았습니다.\n", targets.len());
+    directory
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
 
-    for file in &targets {
-        println!("--- {} ---", file.filename());
+/// 태그가 있는 파일의 이름을 "{아티스트} - {제목}.mp3" 형식으로 일괄 변경한다.
+/// 대상 파일이 이미 있으면 `on_conflict`(없으면 설정값) 전략으로 처리한다.
+fn cmd_rename(cfg: &Config, inputs: &Inputs, on_conflict: Option<ConflictStrategy>) -> Result<()> {
+    let mut rename_cfg = cfg.rename.clone();
+    if let Some(strategy) = on_conflict {
+        rename_cfg.on_conflict = strategy;
+    }
 
-        let parsed = parser::parse_filename(&file.path);
-        let query = parser::build_search_query(&parsed);
+    let files = scan_with_progress(cfg, inputs)?;
+    let mut success = 0;
+    let mut failed = 0;
+    let mut skipped = 0;
 
-        if query.is_empty() {
-            println!("  파일명에서 검색어를 생성할 수 없습니다. 건너뜁니다.\n");
+    cancel::install();
+    let pb = progress_bar(files.len(), i18n::pick("파일명 변경", "Renaming"));
+    for file in &files {
+        if cancel::requested() {
+            break;
+        }
+        pb.inc(1);
+        let Some(ref tags) = file.current_tags else {
+            skipped += 1;
+            continue;
+        };
+        if tags.artists.is_empty() || tags.title.is_none() {
+            skipped += 1;
             continue;
         }
 
-        println!("  검색 중: {}", query);
+        let mut outcome = renamer::rename_file(&file.path, tags, &rename_cfg);
 
-        let results = match client.search(&query) {
-            Ok(r) => r,
+        // Prompt 전략: 사용자가 고른 전략으로 다시 시도
+        if let Ok(RenameOutcome::Conflict(ref target)) = outcome {
+            let choices = [
+                (
+                    i18n::pick("번호 붙이기", "Add a number"),
+                    ConflictStrategy::Suffix,
+                ),
+                (
+                    i18n::pick(
+                        "기존 파일이 저음질이면 덮어쓰기",
+                        "Overwrite if the existing file is lower quality",
+                    ),
+                    ConflictStrategy::OverwriteSmaller,
+                ),
+                (i18n::pick("건너뛰기", "Skip"), ConflictStrategy::Skip),
+            ];
+            let labels: Vec<&str> = choices.iter().map(|(label, _)| *label).collect();
+            let selection = pb.suspend(|| {
+                Select::new()
+                    .with_prompt(tr!(
+                        "{} → 파일이 이미 존재합니다: {}",
+                        "{} → file already exists: {}",
+                        file.filename(),
+                        target.display()
+                    ))
+                    .items(&labels)
+                    .default(0)
+                    .interact()
+            })?;
+
+            let mut retry_cfg = rename_cfg.clone();
+            retry_cfg.on_conflict = choices[selection].1;
+            outcome = renamer::rename_file(&file.path, tags, &retry_cfg);
+        }
+
+        match outcome {
+            Ok(RenameOutcome::Renamed(new_path)) => {
+                pb.suspend(|| println!("{} → {}", file.filename(), new_path.display()));
+                success += 1;
+            }
+            Ok(RenameOutcome::Skipped(target)) | Ok(RenameOutcome::Conflict(target)) => {
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 이미 존재하여 건너뜀 ({})",
+                            "{}: already exists, skipped ({})",
+                            file.filename(),
+                            target.display()
+                        )
+                    )
+                });
+                skipped += 1;
+            }
+            Ok(RenameOutcome::Unchanged) => {
+                skipped += 1;
+            }
             Err(e) => {
-                println!("  검색 실패: {}. 건너뜁니다.\n", e);
-                continue;
+                pb.suspend(|| {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 변경 실패 ({})",
+                            "{}: rename failed ({})",
+                            file.filename(),
+                            e
+                        )
+                    )
+                });
+                failed += 1;
             }
-        };
+        }
+    }
+    pb.finish_and_clear();
 
-        if results.is_empty() {
-            println!("  검색 결과가 없습니다. 건너뜁니다.\n");
-            continue;
+    println!(
+        "\n{}",
+        tr!(
+            "파일명 변경 완료: 성공 {}건, 실패 {}건, 스킵 {}건",
+            "Renamed: {} succeeded, {} failed, {} skipped",
+            success,
+            failed,
+            skipped
+        )
+    );
+    refresh_media_server(cfg, success);
+    exit::check_failures(failed, success + failed + skipped)
+}
+
+/// 백업 목록을 보여 주거나 지정한(없으면 가장 최근) 백업으로 파일을 되돌린다.
+fn cmd_restore(cfg: &Config, file: &Path, list: bool, from: Option<PathBuf>) -> Result<()> {
+    let backups = backup::list(file, &cfg.write)?;
+    if list {
+        if backups.is_empty() {
+            println!(
+                "{}",
+                tr!("{}의 백업이 없습니다", "No backup of {}", file.display())
+            );
+        }
+        for path in &backups {
+            println!("{}", path.display());
         }
+        return Ok(());
+    }
 
-        let items: Vec<String> = results.iter().map(|r| r.summary()).collect();
-        let mut items_with_skip = items.clone();
-        items_with_skip.push("이 파일 건너뛰기".to_string());
+    let source = match from {
+        Some(path) => path,
+        None => backups.into_iter().next().with_context(|| {
+            tr!(
+                "{}의 백업이 없습니다 ({})",
+                "No backup of {} ({})",
+                file.display(),
+                cfg.write.backup_dir().display()
+            )
+        })?,
+    };
+    backup::restore(file, &source)?;
+    println!("{} ← {}", file.display(), source.display());
+    Ok(())
+}
 
-        let selection = Select::new()
-            .with_prompt("  트랙을 선택하세요")
-            .items(&items_with_skip)
-            .default(0)
-            .interact()?;
+/// 디렉토리의 변경 기록을 최신순으로 최대 `limit`건 표로 출력한다.
+fn cmd_history(directory: &Path, file: Option<&str>, limit: usize) -> Result<()> {
+    let entries: Vec<_> = history::load(directory)?
+        .into_iter()
+        .rev()
+        .filter(|e| file.is_none_or(|f| e.file == f))
+        .take(limit)
+        .collect();
+    if entries.is_empty() {
+        println!(
+            "{}",
+            i18n::pick(
+                "변경 기록이 없습니다 ([write] history = true 또는 --history로 기록을 남깁니다).",
+                "No change history ([write] history = true or --history records it)."
+            )
+        );
+        return Ok(());
+    }
 
-        if selection >= results.len() {
-            println!("  건너뛰었습니다.\n");
-            continue;
+    let mut table = Table::new();
+    table.set_header(match i18n::lang() {
+        Lang::Ko => vec!["시각", "파일", "출처", "필드", "이전", "새 값"],
+        Lang::En => vec!["Time", "File", "Source", "Field", "Old", "New"],
+    });
+    for entry in &entries {
+        for (i, change) in entry.changes.iter().enumerate() {
+            let first = |s: &str| if i == 0 { s.to_string() } else { String::new() };
+            table.add_row(vec![
+                first(&entry.time),
+                first(&entry.file),
+                first(&entry.source),
+                change.field.clone(),
+                change.old.clone().unwrap_or_default(),
+                change.new.clone(),
+            ]);
         }
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// 저장 대기 변경을 표로 보여 주거나, `save`면 파일에 기록하고 `discard`면 버린다.
+/// `files`가 있으면 그 파일들만 처리한다.
+fn cmd_staged(
+    cfg: &Config,
+    path: &Path,
+    save: bool,
+    discard: bool,
+    files: &[PathBuf],
+) -> Result<()> {
+    let mut staged = StagedChanges::load(path)?;
+    if staged.is_empty() {
+        println!(
+            "{}",
+            i18n::pick("저장 대기 중인 변경이 없습니다.", "No staged changes.")
+        );
+        return Ok(());
+    }
 
-        let mut track = results[selection].clone();
+    // 목록의 경로는 대상 디렉토리 기준이므로 --file은 절대 경로로 비교한다
+    let canonical = |p: &Path| {
+        std::fs::canonicalize(p).map_or_else(|_| p.to_path_buf(), |c| longpath::normalize(&c))
+    };
+    let all: Vec<PathBuf> = staged.iter().map(|(p, _)| p.clone()).collect();
+    let selected: Vec<PathBuf> = if files.is_empty() {
+        all
+    } else {
+        files
+            .iter()
+            .map(|file| {
+                let wanted = canonical(file);
+                all.iter()
+                    .find(|p| canonical(p) == wanted)
+                    .cloned()
+                    .with_context(|| {
+                        tr!(
+                            "저장 대기 목록에 없는 파일입니다: {}",
+                            "Not in the staged list: {}",
+                            file.display()
+                        )
+                    })
+            })
+            .collect::<Result<_>>()?
+    };
 
-        // 앨범 아트 가져오기
-        match client.fetch_album_art(&track) {
-            Ok(art) => {
-                track.album_art = Some(art);
-                println!("  앨범 아트를 다운로드했습니다.");
-            }
-            Err(e) => {
-                println!("  앨범 아트 다운로드 실패: {}", e);
+    if discard {
+        if files.is_empty() {
+            staged.discard_all();
+        } else {
+            for file in &selected {
+                staged.discard(file);
             }
         }
+        staged.save()?;
+        println!(
+            "{}",
+            tr!(
+                "저장 대기 변경 {}건을 취소했습니다.",
+                "Discarded {} staged changes.",
+                selected.len()
+            )
+        );
+        return Ok(());
+    }
 
-        tagger::write_tags(&file.path, &track)?;
-        println!("  태그가 적용되었습니다: {}\n", track.summary());
+    if save {
+        let mut saved = 0;
+        let mut failed = 0;
+        cancel::install();
+        let results = if files.is_empty() {
+            staged.commit_all(&cfg.write)
+        } else {
+            selected
+                .iter()
+                .take_while(|_| !cancel::requested())
+                .filter_map(|file| Some((file.clone(), staged.commit(file, &cfg.write)?)))
+                .collect()
+        };
+        for (file, result) in results {
+            match result {
+                Ok(info) => {
+                    println!("{} → {}", file.display(), info.summary());
+                    saved += 1;
+                }
+                Err(e) => {
+                    println!(
+                        "{}",
+                        tr!(
+                            "{}: 기록 실패 ({:#})",
+                            "{}: write failed ({:#})",
+                            file.display(),
+                            e
+                        )
+                    );
+                    failed += 1;
+                }
+            }
+        }
+        staged.save()?;
+        println!(
+            "\n{}",
+            tr!(
+                "기록 {}건, 실패 {}건",
+                "{} written, {} failed",
+                saved,
+                failed
+            )
+        );
+        if failed > 0 || cancel::requested() {
+            println!(
+                "{}",
+                i18n::pick(
+                    "기록하지 못한 변경은 저장 대기 목록에 남아 있습니다.",
+                    "Changes that could not be written remain in the staged list."
+                )
+            );
+        }
+        refresh_media_server(cfg, saved);
+        return exit::check_failures(failed, saved + failed);
     }
 
-    println!("완료!");
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("파일", "File"),
+        i18n::pick("필드", "Field"),
+        i18n::pick("현재", "Current"),
+        i18n::pick("새 값", "New"),
+    ]);
+    for file in &selected {
+        let Some(info) = staged.get(file) else {
+            continue;
+        };
+        let current = tagger::read_tags(file).ok().flatten();
+        let name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut info = info.clone();
+        let kept = tagger::keep_protected(&current, &mut info, &cfg.write);
+        let mut changes = staging::changed_fields(current.as_ref(), &info);
+        changes.extend(kept.into_iter().map(|field| {
+            (
+                field,
+                String::new(),
+                i18n::pick("보호됨, 건너뜀", "protected, skipped").to_string(),
+            )
+        }));
+        if changes.is_empty() {
+            table.add_row(vec![
+                name.clone(),
+                i18n::pick("(변경 없음)", "(no change)").to_string(),
+                String::new(),
+                String::new(),
+            ]);
+        }
+        for (i, (field, old, new)) in changes.into_iter().enumerate() {
+            let name = if i == 0 { name.clone() } else { String::new() };
+            table.add_row(vec![name, field.to_string(), old, new]);
+        }
+    }
+    println!("{table}");
+    println!(
+        "\n{}",
+        tr!(
+            "저장 대기 {}건. --save로 기록하거나 --discard로 취소하세요 (--file로 일부만).",
+            "{} staged. Use --save to write or --discard to drop (--file for some only).",
+            selected.len()
+        )
+    );
     Ok(())
 }
 
+/// 일괄 기록에서 실패한 파일과 이유를 출력한다.
+fn print_batch_failures(report: &tagger::BatchReport) {
+    for (path, e) in &report.failed {
+        let name = path.file_name().unwrap_or(path.as_os_str());
+        println!(
+            "{}",
+            tr!(
+                "{}: 기록 실패 ({:#})",
+                "{}: write failed ({:#})",
+                name.to_string_lossy(),
+                e
+            )
+        );
+    }
+}
+
+/// 일괄 작업으로 바뀐 파일이 있으면 설정된 미디어 서버에 라이브러리 스캔을 요청한다.
+/// 요청이 실패해도 작업 결과에는 영향을 주지 않고 안내만 출력한다. 오프라인 모드에서는 요청하지 않는다.
+fn refresh_media_server(cfg: &Config, changed: usize) {
+    if changed == 0 || cfg.network.offline {
+        return;
+    }
+    let result = MediaServer::from_config(&cfg.media_server).and_then(|server| match server {
+        Some(server) => server.refresh().map(|_| Some(server.label())),
+        None => Ok(None),
+    });
+    match result {
+        Ok(Some(label)) => println!(
+            "{}",
+            tr!(
+                "{} 라이브러리 스캔을 요청했습니다.",
+                "Requested a {} library scan.",
+                label
+            )
+        ),
+        Ok(None) => {}
+        Err(e) => println!(
+            "{}",
+            tr!(
+                "미디어 서버 라이브러리 스캔 요청 실패: {:#}",
+                "Media server library scan request failed: {:#}",
+                e
+            )
+        ),
+    }
+}
+
 /// Spotify API 자격증명을 대화형으로 입력받아 저장한다.
 fn cmd_config() -> Result<()> {
-    let mut cfg = config::load_config();
+    let mut cfg = config::load_config()?;
 
-    println!("Spotify API 설정");
-    println!("(자격증명은 https://developer.spotify.com/dashboard 에서 발급받으세요)\n");
+    println!("{}", i18n::pick("Spotify API 설정", "Spotify API setup"));
+    println!(
+        "{}",
+        i18n::pick(
+            "(자격증명은 https://developer.spotify.com/dashboard 에서 발급받으세요)\n",
+            "(Get credentials at https://developer.spotify.com/dashboard)\n"
+        )
+    );
 
-    let current_id = cfg
-        .spotify
-        .client_id
-        .clone()
-        .unwrap_or_default();
+    let current_id = cfg.spotify.client_id.clone().unwrap_or_default();
 
     let client_id: String = Input::new()
         .with_prompt("Client ID")
         .with_initial_text(current_id)
         .interact_text()?;
 
-    let current_secret = cfg
-        .spotify
-        .client_secret
-        .clone()
-        .unwrap_or_default();
+    let current_secret = cfg.spotify.client_secret.clone().unwrap_or_default();
 
     let client_secret: String = Input::new()
         .with_prompt("Client Secret")
@@ -310,9 +7062,559 @@ fn cmd_config() -> Result<()> {
     cfg.spotify = SpotifyConfig {
         client_id: Some(client_id),
         client_secret: Some(client_secret),
+        ..cfg.spotify
     };
 
+    println!(
+        "\n{}",
+        i18n::pick(
+            "Genius API 설정 (선택, 크레딧/가사 보충용)",
+            "Genius API setup (optional, fills in credits/lyrics)"
+        )
+    );
+    println!(
+        "{}",
+        i18n::pick(
+            "(토큰은 https://genius.com/api-clients 에서 발급받으세요. 비워두면 사용하지 않습니다)\n",
+            "(Get a token at https://genius.com/api-clients. Leave empty to skip)\n"
+        )
+    );
+
+    let genius_token: String = Input::new()
+        .with_prompt("Genius Access Token")
+        .with_initial_text(cfg.genius.access_token.clone().unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()?;
+    cfg.genius.access_token = Some(genius_token).filter(|t| !t.trim().is_empty());
+
     config::save_config(&cfg)?;
-    println!("\n설정이 저장되었습니다!");
+    println!(
+        "\n{}",
+        i18n::pick("설정이 저장되었습니다!", "Settings saved!")
+    );
+    Ok(())
+}
+
+/// 처음 실행 설정 마법사. 기본 검색 소스, 자격증명(선택), 파일명 템플릿, 음악 디렉토리를 차례로 묻고 저장한다.
+fn cmd_setup(cfg: &Config) -> Result<()> {
+    let mut setup = config::Setup::from_config(&config::load_config()?);
+    println!(
+        "{}",
+        i18n::pick(
+            "mp3tag 처음 설정 ('mp3tag config setup'으로 언제든 다시 실행할 수 있습니다)\n",
+            "mp3tag first-time setup (run again any time with 'mp3tag config setup')\n"
+        )
+    );
+
+    let kinds = SourceKind::all(cfg);
+    let labels: Vec<&str> = kinds.iter().map(|k| k.label()).collect();
+    let current = kinds
+        .iter()
+        .position(|k| k.name() == setup.default_source)
+        .unwrap_or(0);
+    let choice = Select::new()
+        .with_prompt(i18n::pick("기본 검색 소스", "Default search source"))
+        .items(&labels)
+        .default(current)
+        .interact()?;
+    setup.default_source = kinds[choice].name().to_string();
+
+    println!(
+        "\n{}",
+        i18n::pick(
+            "Spotify 자격증명 (선택, https://developer.spotify.com/dashboard 에서 발급)",
+            "Spotify credentials (optional, get them at https://developer.spotify.com/dashboard)"
+        )
+    );
+    println!(
+        "{}",
+        i18n::pick(
+            "비워두면 Spotify를 쓰지 않습니다. Melon은 자격증명 없이 쓸 수 있습니다.",
+            "Leave empty to skip Spotify. Melon works without credentials."
+        )
+    );
+    setup.client_id = Input::new()
+        .with_prompt("Client ID")
+        .with_initial_text(setup.client_id)
+        .allow_empty(true)
+        .interact_text()?;
+    if !setup.client_id.trim().is_empty() {
+        setup.client_secret = Input::new()
+            .with_prompt("Client Secret")
+            .with_initial_text(setup.client_secret)
+            .allow_empty(true)
+            .interact_text()?;
+    }
+    setup.genius_token = Input::new()
+        .with_prompt(i18n::pick(
+            "Genius Access Token (선택, 크레딧/가사 보충)",
+            "Genius Access Token (optional, fills in credits/lyrics)",
+        ))
+        .with_initial_text(setup.genius_token)
+        .allow_empty(true)
+        .interact_text()?;
+
+    let mut templates: Vec<&str> = config::TEMPLATE_CHOICES.to_vec();
+    if !templates.contains(&setup.file_template.as_str()) {
+        templates.insert(0, &setup.file_template);
+    }
+    let current = templates
+        .iter()
+        .position(|t| *t == setup.file_template)
+        .unwrap_or(0);
+    let mut items = templates.clone();
+    items.push(i18n::pick("직접 입력", "Enter manually"));
+    let choice = Select::new()
+        .with_prompt(i18n::pick(
+            "\n파일명 형식 (rename, auto에서 사용)",
+            "\nFile name format (used by rename and auto)",
+        ))
+        .items(&items)
+        .default(current)
+        .interact()?;
+    setup.file_template = match templates.get(choice) {
+        Some(template) => template.to_string(),
+        None => Input::new()
+            .with_prompt(i18n::pick(
+                "템플릿 (예: {track}. {artist} - {title})",
+                "Template (e.g. {track}. {artist} - {title})",
+            ))
+            .validate_with(|s: &String| renamer::check_template(s).map_err(|e| e.to_string()))
+            .interact_text()?,
+    };
+
+    setup.music_dir = Input::new()
+        .with_prompt(i18n::pick(
+            "\n음악 디렉토리 (선택, scan과 GUI의 기본 디렉토리)",
+            "\nMusic directory (optional, default directory for scan and the GUI)",
+        ))
+        .with_initial_text(setup.music_dir)
+        .allow_empty(true)
+        .validate_with(|s: &String| {
+            if s.trim().is_empty() || Path::new(s.trim()).is_dir() {
+                Ok(())
+            } else {
+                Err(i18n::pick("디렉토리가 아닙니다", "Not a directory"))
+            }
+        })
+        .interact_text()?;
+
+    config::update_config(|c| setup.apply(c))?;
+    println!(
+        "\n{}",
+        i18n::pick(
+            "설정이 저장되었습니다! 'mp3tag config show'로 확인할 수 있습니다.",
+            "Settings saved! Check them with 'mp3tag config show'."
+        )
+    );
+    Ok(())
+}
+
+/// 기본값을 채운 현재 설정을 TOML로 출력한다. 비밀 값은 가린다.
+fn cmd_config_show() -> Result<()> {
+    print!("{}", config::redacted_toml(&config::load_config()?)?);
+    Ok(())
+}
+
+/// 설정 파일을 검사한다. 모르는 키는 경고만 하고, 값/템플릿 오류나 Spotify 인증 실패가 있으면
+/// 에러로 끝난다.
+fn cmd_config_check(cfg: &Config) -> Result<()> {
+    let mut errors = 0;
+    match config::read_config_value()? {
+        None => println!(
+            "{}",
+            i18n::pick(
+                "설정 파일: 없음 (기본값 사용)",
+                "Config file: none (using defaults)"
+            )
+        ),
+        Some(raw) => match config::unknown_keys(&raw) {
+            Ok(unknown) if unknown.is_empty() => {
+                println!("{}", i18n::pick("설정 파일: 정상", "Config file: OK"))
+            }
+            Ok(unknown) => {
+                println!(
+                    "{}",
+                    tr!(
+                        "설정 파일: 알 수 없는 키 {}개 (무시됨)",
+                        "Config file: {} unknown keys (ignored)",
+                        unknown.len()
+                    )
+                );
+                for key in &unknown {
+                    println!("  {}", key);
+                }
+            }
+            Err(e) => {
+                println!(
+                    "{}",
+                    tr!("설정 파일: 오류 - {}", "Config file: error - {}", e)
+                );
+                errors += 1;
+            }
+        },
+    }
+
+    let templates = [
+        ("rename.file_template", &cfg.rename.file_template),
+        ("rename.organize_template", &cfg.rename.organize_template),
+    ];
+    for (key, template) in templates {
+        match renamer::check_template(template) {
+            Ok(()) => println!("{}: {}", key, i18n::pick("정상", "OK")),
+            Err(e) => {
+                println!("{}: {} - {}", key, i18n::pick("오류", "error"), e);
+                errors += 1;
+            }
+        }
+    }
+
+    if let Some(ref path) = cfg.melon.selectors {
+        match Selectors::load(melon::DEFAULT_SELECTORS, Some(path)) {
+            Ok(_) => println!("melon.selectors: {}", i18n::pick("정상", "OK")),
+            Err(e) => {
+                println!("melon.selectors: {} - {:#}", i18n::pick("오류", "error"), e);
+                errors += 1;
+            }
+        }
+    }
+
+    if !cfg.spotify.is_configured() {
+        println!(
+            "Spotify: {}",
+            i18n::pick("자격증명 없음 (건너뜀)", "no credentials (skipped)")
+        );
+    } else if cfg.network.offline {
+        println!(
+            "Spotify: {}",
+            i18n::pick("오프라인 모드 (건너뜀)", "offline mode (skipped)")
+        );
+    } else {
+        match SpotifyClient::new(&cfg.spotify) {
+            Ok(_) => println!("Spotify: {}", i18n::pick("토큰 발급 성공", "token issued")),
+            Err(e) => {
+                println!("Spotify: {} - {:#}", i18n::pick("오류", "error"), e);
+                errors += 1;
+            }
+        }
+    }
+
+    if errors > 0 {
+        return Err(exit::config_error(tr!(
+            "설정 오류 {}개",
+            "{} config errors",
+            errors
+        )));
+    }
+    Ok(())
+}
+
+/// Melon 곡/앨범 검색과 첫 결과의 상세 페이지를 불러와 선택자별로 찾은 요소 수를 보고한다.
+/// 아무것도 찾지 못한 선택자가 있으면 실패(종료 코드 2)로 끝난다.
+fn cmd_test_selectors(cfg: &Config, query: &str) -> Result<()> {
+    let client = MelonClient::new(&cfg.melon)?;
+    let checks = client.check_selectors(query)?;
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        i18n::pick("키", "Key"),
+        i18n::pick("선택자", "Selector"),
+        i18n::pick("찾은 요소", "Matches"),
+        i18n::pick("결과", "Result"),
+    ]);
+    for check in &checks {
+        let result = match check.matches {
+            None => i18n::pick("페이지 없음", "no page"),
+            Some(0) => i18n::pick("찾지 못함", "not found"),
+            Some(_) => i18n::pick("정상", "OK"),
+        };
+        table.add_row(vec![
+            Cell::new(&check.key),
+            Cell::new(&check.selector),
+            Cell::new(check.matches.map_or("-".to_string(), |n| n.to_string())),
+            Cell::new(result),
+        ]);
+    }
+    println!("{}", table);
+
+    let failed = checks.iter().filter(|c| !c.is_ok()).count();
+    if failed > 0 {
+        println!(
+            "{}",
+            tr!(
+                "\n선택자 {}개가 요소를 찾지 못했습니다. 검색어에 결과가 있는데도 찾지 못했다면 Melon 페이지 구조가 바뀐 것입니다.\n브라우저 개발자 도구로 새 선택자를 찾아 [melon] selectors 파일에 해당 키만 적으세요. \"페이지 없음\"은 앞 단계에서 결과를 찾지 못해 확인하지 못한 것입니다.",
+                "\n{} selectors matched nothing. If the query has results, the Melon page layout has changed.\nFind the new selectors with the browser developer tools and put only those keys in the [melon] selectors file. \"no page\" means an earlier step found no result to open.",
+                failed
+            )
+        );
+    } else {
+        println!(
+            "{}",
+            i18n::pick("\n모든 선택자가 정상입니다.", "\nAll selectors OK.")
+        );
+    }
+    exit::check_failures(failed, checks.len())
+}
+
+/// Authorization Code + PKCE 흐름으로 Spotify 사용자 로그인을 하고 refresh token을 저장한다.
+/// 브라우저 로그인 후 Spotify가 로컬 콜백 주소로 리다이렉트하면 code를 받아 토큰으로 교환한다.
+fn cmd_login() -> Result<()> {
+    let cfg = config::load_config()?;
+    let client_id = cfg
+        .spotify
+        .client_id
+        .clone()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| {
+            exit::config_error(i18n::pick(
+                "Spotify client_id가 설정되지 않았습니다. 먼저 'mp3tag config'를 실행하세요.",
+                "Spotify client_id is not set. Run 'mp3tag config' first.",
+            ))
+        })?;
+    let redirect_uri = cfg.spotify.redirect_uri();
+
+    let pkce = Pkce::generate();
+    let state = spotify_auth::random_string(16);
+    let url = spotify_auth::authorize_url(&client_id, &redirect_uri, &pkce, &state);
+
+    println!(
+        "{}",
+        i18n::pick(
+            "브라우저에서 아래 주소를 열어 Spotify에 로그인하세요:\n",
+            "Open this address in a browser to log in to Spotify:\n"
+        )
+    );
+    println!("{}\n", url);
+    println!(
+        "{}",
+        tr!(
+            "(Spotify 대시보드의 Redirect URIs에 {} 가 등록되어 있어야 합니다)",
+            "({} must be registered under Redirect URIs in the Spotify dashboard)",
+            redirect_uri
+        )
+    );
+    println!(
+        "{}",
+        i18n::pick("로그인을 기다리는 중...", "Waiting for login...")
+    );
+
+    let code = spotify_auth::wait_for_code(cfg.spotify.redirect_port(), &state)?;
+    let tokens = spotify_auth::exchange_code(
+        &ReqwestClient::default(),
+        &client_id,
+        &code,
+        &pkce,
+        &redirect_uri,
+    )?;
+    let refresh_token = tokens.refresh_token.context(i18n::pick(
+        "Spotify가 refresh token을 발급하지 않았습니다",
+        "Spotify did not issue a refresh token",
+    ))?;
+
+    config::update_config(|c| c.spotify.refresh_token = Some(refresh_token))?;
+    println!(
+        "\n{}",
+        i18n::pick(
+            "로그인되었습니다! 이제 'mp3tag fetch --liked <디렉토리>'를 사용할 수 있습니다.",
+            "Logged in! You can now use 'mp3tag fetch --liked <DIR>'."
+        )
+    );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 검색어와 상관없이 같은 결과를 돌려주는 소스.
+    struct FixedSource(Vec<TrackInfo>);
+
+    impl MusicSource for FixedSource {
+        fn search(&self, _query: &str) -> Result<Vec<TrackInfo>> {
+            Ok(self.0.clone())
+        }
+
+        fn fetch_album_art(&self, _track: &TrackInfo) -> Result<Vec<u8>> {
+            anyhow::bail!("없음")
+        }
+    }
+
+    /// 앨범 커버로 `width`×`width` PNG 헤더를 돌려주고 검색 횟수를 세는 소스.
+    struct CoverSource {
+        width: u32,
+        searches: std::sync::atomic::AtomicUsize,
+    }
+
+    impl MusicSource for CoverSource {
+        fn search(&self, _query: &str) -> Result<Vec<TrackInfo>> {
+            self.searches
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Ok(vec![TrackInfo {
+                album: Some("Love poem".to_string()),
+                artists: vec!["IU".to_string()],
+                ..Default::default()
+            }])
+        }
+
+        fn fetch_album_art(&self, _track: &TrackInfo) -> Result<Vec<u8>> {
+            Ok(png(self.width))
+        }
+    }
+
+    fn png(width: u32) -> Vec<u8> {
+        let mut data = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&width.to_be_bytes());
+        data
+    }
+
+    fn blueming() -> TrackInfo {
+        TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["IU".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn auto_args(options: &[&str]) -> AutoArgs {
+        let args = ["mp3tag", "auto", "music"].iter().chain(options);
+        match Cli::try_parse_from(args).unwrap().command {
+            Some(Commands::Auto(args)) => args,
+            _ => unreachable!(),
+        }
+    }
+
+    fn pick(args: &AutoArgs, file: &Mp3File) -> Option<AutoPick> {
+        let clients: Vec<Arc<dyn MusicSource>> = vec![Arc::new(FixedSource(vec![blueming()]))];
+        auto_pick(
+            args,
+            &clients,
+            Duration::from_secs(5),
+            &Aliases::new(&[]),
+            &[],
+            &TrustConfig::default(),
+            file,
+            Path::new("music"),
+        )
+    }
+
+    #[test]
+    fn test_auto_pick_queues_mid_confidence_for_review() {
+        let file = Mp3File {
+            path: PathBuf::from("music/IU - Blueming.mp3"),
+            current_tags: Some(blueming()),
+            has_tags: true,
+            encoder: None,
+        };
+        assert!(matches!(
+            pick(&auto_args(&[]), &file),
+            Some(AutoPick::Matched(..))
+        ));
+
+        // --min-score에 못 미치고 --review-score 이상이면 끝에 검토할 후보로 미룬다
+        let review = auto_args(&["--min-score", "1.5", "--review-score", "0.5"]);
+        let Some(AutoPick::Review(item)) = pick(&review, &file) else {
+            panic!("검토 대기로 미루지 않았습니다");
+        };
+        assert_eq!(item.path, file.path);
+        assert_eq!(item.candidates.len(), 1);
+        assert!(!item.can_infer);
+
+        // 검토 점수에도 못 미치면 추론으로 채울 값도 없으므로 건너뛴다
+        let low = auto_args(&["--min-score", "1.5", "--review-score", "1.5"]);
+        assert!(pick(&low, &file).is_none());
+        let untagged = Mp3File {
+            current_tags: None,
+            has_tags: false,
+            ..file
+        };
+        assert!(matches!(pick(&low, &untagged), Some(AutoPick::Inferred(_))));
+    }
+
+    #[test]
+    fn test_album_cover_searches_once_per_album() {
+        let source = Arc::new(CoverSource {
+            width: 600,
+            searches: Default::default(),
+        });
+        let search = CoverSearch {
+            clients: vec![source.clone()],
+            cover_art: None,
+        };
+        let searches = || source.searches.load(std::sync::atomic::Ordering::Relaxed);
+        let track = |album: Option<&str>, art: Option<Vec<u8>>| Mp3File {
+            path: PathBuf::from("music/Love poem/song.mp3"),
+            current_tags: Some(TrackInfo {
+                album: album.map(String::from),
+                artists: vec!["IU".to_string()],
+                album_art: art,
+                ..Default::default()
+            }),
+            has_tags: true,
+            encoder: None,
+        };
+
+        // 같은 앨범의 다른 파일에 충분히 큰 아트가 있으면 검색하지 않는다
+        let with_art = track(Some("Love poem"), Some(png(500)));
+        let without = track(Some("Love poem"), None);
+        let cover = album_cover(&search, &[&with_art, &without], 300).unwrap();
+        assert_eq!((cover.source.as_str(), cover.data), ("id3", png(500)));
+        assert_eq!(searches(), 0);
+
+        // 기존 아트가 너무 작으면 앨범 전체에 대해 한 번만 검색한다
+        let small = track(Some("Love poem"), Some(png(100)));
+        let cover = album_cover(&search, &[&small, &without, &without], 300).unwrap();
+        assert_eq!(cover.data, png(600));
+        assert_eq!(searches(), 1);
+
+        assert_eq!(
+            album_cover(&search, &[&without], 1000).unwrap_err(),
+            "커버가 너무 작음"
+        );
+        let no_album = track(None, None);
+        assert_eq!(
+            album_cover(&search, &[&no_album], 300).unwrap_err(),
+            "앨범 태그 없음"
+        );
+    }
+
+    #[test]
+    fn test_fetch_retry_helpers() {
+        let search = edited_search("  아이유 밤편지 ").unwrap();
+        assert_eq!(parser::build_search_query(&search), "아이유 밤편지");
+        assert!(search.artists.is_empty());
+        assert!(edited_search("   ").is_none());
+
+        let cfg = Config::default();
+        assert_eq!(
+            other_sources(&cfg, &SourceKind::Melon),
+            [SourceKind::Spotify]
+        );
+
+        // 검색 실패나 결과 없음은 다음 실행에서 다시 검색하도록 건너뛴 목록에 남기지 않는다
+        let failed = skip_reason(Some("검색 실패: 시간 초과".to_string()), true);
+        assert_eq!(
+            failed,
+            ("검색 실패: 시간 초과".to_string(), Fetched::NotFound)
+        );
+        assert_eq!(skip_reason(None, true).1, Fetched::NotFound);
+        assert_eq!(
+            skip_reason(None, false),
+            ("사용자가 건너뜀".to_string(), Fetched::Skipped)
+        );
+    }
+
+    #[test]
+    fn test_search_page_bounds() {
+        let parse = |page: &str| Cli::try_parse_from(["mp3tag", "search", "--page", page, "IU"]);
+        assert!(parse("1").is_ok());
+        assert!(parse("0").is_err());
+        assert!(parse("-1").is_err());
+        assert!(parse(&usize::MAX.to_string()).is_ok());
+
+        let page = search_page(3, 20).unwrap();
+        assert_eq!((page.offset, page.limit), (40, 20));
+        assert!(search_page(usize::MAX, 20).is_err());
+    }
+}
@@ -1,13 +1,55 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::exit;
+use crate::models::{split_artists, TrackInfo};
+
 /// 앱 전체 설정.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
+    /// fetch/now/album에서 `--source`를 주지 않을 때 쓸 소스 이름. GUI의 기본 검색 소스이기도 하다.
+    pub default_source: Option<String>,
+    /// 음악 라이브러리 디렉토리. `scan`에 디렉토리를 주지 않거나 GUI에서 연 디렉토리가 없을 때 사용한다.
+    pub music_dir: Option<PathBuf>,
     #[serde(default)]
     pub spotify: SpotifyConfig,
+    #[serde(default)]
+    pub rename: RenameConfig,
+    #[serde(default)]
+    pub write: WriteConfig,
+    #[serde(default)]
+    pub melon: ScrapeConfig,
+    #[serde(default)]
+    pub genius: GeniusConfig,
+    #[serde(default)]
+    pub art: ArtConfig,
+    /// 외부 실행 파일로 동작하는 추가 소스 (`[[command_sources]]`)
+    #[serde(default)]
+    pub command_sources: Vec<CommandSourceConfig>,
+    #[serde(default)]
+    pub plugins: PluginConfig,
+    #[serde(default)]
+    pub media_server: MediaServerConfig,
+    #[serde(default)]
+    pub mpd: MpdConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub matching: MatchingConfig,
+    #[serde(default)]
+    pub dedupe: DedupeConfig,
+    #[serde(default)]
+    pub library: LibraryConfig,
+    #[serde(default)]
+    pub trust: TrustConfig,
+    /// 이름 붙인 태그 묶음 (`[presets.이름]`)
+    #[serde(default)]
+    pub presets: BTreeMap<String, TagPreset>,
+    #[serde(default)]
+    pub gui: GuiConfig,
 }
 
 /// Spotify API 자격증명 설정.
@@ -15,6 +57,11 @@ pub struct Config {
 pub struct SpotifyConfig {
     pub client_id: Option<String>,
     pub client_secret: Option<String>,
+    /// `mp3tag login`으로 발급받은 사용자 refresh token (좋아요 표시한 곡 등 사용자 범위 기능용)
+    pub refresh_token: Option<String>,
+    /// 로그인 콜백을 받을 로컬 포트. Spotify 대시보드에 `http://127.0.0.1:{포트}/callback`을
+    /// Redirect URI로 등록해야 한다. 기본값 8888.
+    pub redirect_port: Option<u16>,
 }
 
 impl SpotifyConfig {
@@ -23,6 +70,734 @@ impl SpotifyConfig {
         self.client_id.as_ref().is_some_and(|s| !s.is_empty())
             && self.client_secret.as_ref().is_some_and(|s| !s.is_empty())
     }
+
+    /// 로그인 콜백을 받을 로컬 포트.
+    pub fn redirect_port(&self) -> u16 {
+        self.redirect_port.unwrap_or(8888)
+    }
+
+    /// 로그인 콜백 Redirect URI.
+    pub fn redirect_uri(&self) -> String {
+        format!("http://127.0.0.1:{}/callback", self.redirect_port())
+    }
+}
+
+/// Genius API 설정. 토큰이 있으면 가져온 태그에 작곡/프로듀서 크레딧과 가사를 보충한다.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GeniusConfig {
+    /// https://genius.com/api-clients 에서 발급받은 Client Access Token
+    pub access_token: Option<String>,
+}
+
+/// 앨범 아트 설정.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ArtConfig {
+    /// MusicBrainz 릴리스 ID를 알면 소스 썸네일 대신 Cover Art Archive의 원본 앞면 커버를 사용한다.
+    #[serde(default)]
+    pub cover_art_archive: bool,
+    /// 앨범 아트의 짧은 변 최소 픽셀 수. 이보다 작은 아트는 기록하지 않는다 (기존 아트 유지).
+    /// 0이면 제한하지 않는다.
+    #[serde(default)]
+    pub min_size: u32,
+    /// 이 바이트 수보다 큰 앨범 아트는 scan의 art-info 열과 GUI에서 경고한다. 기본값은 1 MB, 0이면 경고하지 않는다.
+    pub warn_max_bytes: Option<u64>,
+    /// 짧은 변이 이 픽셀보다 작은 앨범 아트는 scan의 art-info 열과 GUI에서 경고한다. 기본값은 300, 0이면 경고하지 않는다.
+    pub warn_min_size: Option<u32>,
+}
+
+impl ArtConfig {
+    pub fn warn_max_bytes(&self) -> u64 {
+        self.warn_max_bytes.unwrap_or(1024 * 1024)
+    }
+
+    pub fn warn_min_size(&self) -> u32 {
+        self.warn_min_size.unwrap_or(300)
+    }
+}
+
+/// 외부 실행 파일 소스 설정. `--source <name>`으로 선택한다.
+/// 실행 파일은 stdin으로 JSON 요청을 받아 stdout으로 트랙 목록 JSON을 출력해야 한다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandSourceConfig {
+    /// 소스 이름 (`--source`와 TrackInfo.source에 사용)
+    pub name: String,
+    /// 실행할 프로그램 경로
+    pub command: String,
+    /// 프로그램에 넘길 인자
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// 동적 라이브러리 소스 플러그인 설정.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PluginConfig {
+    /// 플러그인(.so/.dylib/.dll)을 찾을 디렉토리. 파일명(`lib` 접두사 제외)이 소스 이름이 된다.
+    pub dir: Option<PathBuf>,
+}
+
+/// 미디어 서버 연동 설정. 일괄 작업으로 파일이 바뀌면 라이브러리 스캔을 요청한다.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MediaServerConfig {
+    /// 서버 종류. 없으면 연동하지 않는다.
+    pub kind: Option<MediaServerKind>,
+    /// 서버 주소 (예: "http://nas:8096")
+    pub url: Option<String>,
+    /// Jellyfin API 키 (대시보드 → API 키)
+    pub api_key: Option<String>,
+    /// Subsonic(Navidrome) 사용자 이름
+    pub username: Option<String>,
+    /// Subsonic(Navidrome) 비밀번호. 요청에는 솔트를 더한 MD5 토큰만 보낸다.
+    pub password: Option<String>,
+}
+
+/// 연동할 미디어 서버 종류.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MediaServerKind {
+    Jellyfin,
+    /// Subsonic API를 제공하는 서버 (Navidrome, Airsonic 등)
+    Subsonic,
+}
+
+/// `mp3tag now`에서 재생 중인 곡을 조회할 MPD 설정.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MpdConfig {
+    /// 기본값 "127.0.0.1"
+    pub host: Option<String>,
+    /// 기본값 6600
+    pub port: Option<u16>,
+    pub password: Option<String>,
+    /// MPD의 music_directory. MPD가 알려주는 상대 경로를 로컬 경로로 바꾸는 데 사용한다.
+    pub music_dir: Option<PathBuf>,
+}
+
+impl MpdConfig {
+    pub fn host(&self) -> &str {
+        self.host.as_deref().unwrap_or("127.0.0.1")
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or(6600)
+    }
+}
+
+/// 검색 결과 점수 설정.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MatchingConfig {
+    /// 같은 대상으로 볼 이름 묶음 (예: `[["아이유", "IU"], ["방탄소년단", "BTS"]]`).
+    /// 한글은 로마자로 옮겨 비교하므로 "소격동"과 "Sogyeokdong"은 따로 적지 않아도 같다.
+    #[serde(default)]
+    pub aliases: Vec<Vec<String>>,
+    /// 같은 곡이 여러 앨범 판에 실렸을 때 앞에 둘 판의 순서 (예: `["original", "remaster"]`).
+    /// 적지 않은 판은 기본 순서(`AlbumEdition::DEFAULT_ORDER`)대로 뒤에 온다.
+    #[serde(default)]
+    pub album_preference: Vec<AlbumEdition>,
+}
+
+/// 검색 결과 앨범의 판 구분 (`[matching] album_preference`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlbumEdition {
+    /// 정규 앨범/EP
+    Original,
+    /// 디럭스, 리패키지, 스페셜 에디션
+    Deluxe,
+    /// 리마스터, 기념반
+    Remaster,
+    /// 싱글 (곡 이름과 같은 앨범 또는 3곡 이하)
+    Single,
+    /// 일본반 등 다른 나라에서 다시 낸 앨범
+    Region,
+    /// 라이브 앨범
+    Live,
+    /// 베스트, 여러 아티스트 모음 앨범
+    Compilation,
+}
+
+impl AlbumEdition {
+    pub const DEFAULT_ORDER: [AlbumEdition; 7] = [
+        AlbumEdition::Original,
+        AlbumEdition::Deluxe,
+        AlbumEdition::Remaster,
+        AlbumEdition::Single,
+        AlbumEdition::Region,
+        AlbumEdition::Live,
+        AlbumEdition::Compilation,
+    ];
+
+    /// 화면 표시용 이름.
+    pub fn label(self) -> &'static str {
+        match self {
+            AlbumEdition::Original => "정규",
+            AlbumEdition::Deluxe => "디럭스",
+            AlbumEdition::Remaster => "리마스터",
+            AlbumEdition::Single => "싱글",
+            AlbumEdition::Region => "해외판",
+            AlbumEdition::Live => "라이브",
+            AlbumEdition::Compilation => "모음",
+        }
+    }
+}
+
+impl MatchingConfig {
+    /// 모든 판을 선호하는 순서대로. 설정에 적은 판이 먼저, 나머지는 기본 순서.
+    pub fn album_preference(&self) -> Vec<AlbumEdition> {
+        let mut order: Vec<AlbumEdition> = Vec::new();
+        for edition in self
+            .album_preference
+            .iter()
+            .chain(&AlbumEdition::DEFAULT_ORDER)
+        {
+            if !order.contains(edition) {
+                order.push(*edition);
+            }
+        }
+        order
+    }
+}
+
+/// `mp3tag dedupe`의 오디오 지문 비교 설정.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DedupeConfig {
+    /// 같은 곡으로 볼 지문 유사도 (0.0~1.0). 기본값 0.85
+    pub threshold: Option<f64>,
+    /// Chromaprint `fpcalc` 실행 파일. 기본값 "fpcalc" (PATH에서 찾음)
+    pub fpcalc: Option<String>,
+    /// 지문을 계산할 앞부분 길이 (초). 기본값 120
+    pub length: Option<u32>,
+}
+
+impl DedupeConfig {
+    pub fn threshold(&self) -> f64 {
+        self.threshold.unwrap_or(0.85)
+    }
+
+    pub fn fpcalc(&self) -> &str {
+        self.fpcalc.as_deref().unwrap_or("fpcalc")
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length.unwrap_or(120)
+    }
+}
+
+/// 스캔 결과를 SQLite 데이터베이스에 두는 라이브러리 설정 (`library` 기능).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LibraryConfig {
+    /// 스캔하는 명령이 라이브러리 데이터베이스를 쓸지. 기본값 false
+    #[serde(default)]
+    pub enabled: bool,
+    /// 데이터베이스 파일. 기본값은 캐시 디렉토리의 library.db
+    pub path: Option<PathBuf>,
+}
+
+impl Config {
+    /// 라이브러리 데이터베이스 경로.
+    #[cfg(feature = "library")]
+    pub fn library_path(&self) -> PathBuf {
+        self.library
+            .path
+            .clone()
+            .unwrap_or_else(|| self.network.cache_dir().join("library.db"))
+    }
+
+    /// 이름으로 프리셋을 찾는다. 없으면 등록된 이름을 안내하는 에러.
+    pub fn preset(&self, name: &str) -> Result<&TagPreset> {
+        self.presets.get(name).with_context(|| {
+            let names: Vec<&str> = self.presets.keys().map(String::as_str).collect();
+            format!(
+                "프리셋 '{}'이(가) 없습니다 (등록된 프리셋: {})",
+                name,
+                if names.is_empty() {
+                    "없음, config.toml에 [presets.이름]으로 추가".to_string()
+                } else {
+                    names.join(", ")
+                }
+            )
+        })
+    }
+}
+
+/// 처음 실행 설정에서 고를 수 있는 파일명 템플릿.
+pub const TEMPLATE_CHOICES: &[&str] = &[
+    "{artist} - {title}",
+    "{track}. {title}",
+    "{track} - {artist} - {title}",
+    "{album_artist} - {album} - {track} - {title}",
+];
+
+/// 처음 실행할 때 고르는 기본 설정. CLI 마법사와 GUI 설정 창이 함께 쓴다.
+/// 빈 문자열은 설정하지 않은 값이다.
+#[derive(Debug, Clone, Default)]
+pub struct Setup {
+    pub default_source: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub genius_token: String,
+    pub file_template: String,
+    pub music_dir: String,
+}
+
+impl Setup {
+    pub fn from_config(cfg: &Config) -> Self {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        Self {
+            default_source: cfg
+                .default_source
+                .clone()
+                .unwrap_or_else(|| "spotify".to_string()),
+            client_id: text(&cfg.spotify.client_id),
+            client_secret: text(&cfg.spotify.client_secret),
+            genius_token: text(&cfg.genius.access_token),
+            file_template: cfg.rename.file_template.clone(),
+            music_dir: cfg
+                .music_dir
+                .as_ref()
+                .map(|d| d.display().to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 고른 값을 설정에 반영한다.
+    pub fn apply(&self, cfg: &mut Config) {
+        let value = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        cfg.default_source = value(&self.default_source);
+        cfg.spotify.client_id = value(&self.client_id);
+        cfg.spotify.client_secret = value(&self.client_secret);
+        cfg.genius.access_token = value(&self.genius_token);
+        if let Some(template) = value(&self.file_template) {
+            cfg.rename.file_template = template;
+        }
+        cfg.music_dir = value(&self.music_dir).map(PathBuf::from);
+    }
+}
+
+/// 설정 파일이 있는지 확인한다. 없으면 처음 실행으로 본다.
+pub fn config_exists() -> bool {
+    config_path().exists()
+}
+
+/// 여러 파일에 반복해서 적용하는 태그 묶음 (예: 같은 공연의 부틀렉 음원).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TagPreset {
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub year: Option<i32>,
+    pub genre: Option<String>,
+    pub label: Option<String>,
+    pub grouping: Option<String>,
+    /// 앨범 아트 이미지 파일 경로
+    pub album_art: Option<PathBuf>,
+}
+
+impl TagPreset {
+    /// 프리셋 값을 직접 편집한 태그로 만든다. 앨범 아트 파일을 읽지 못하면 에러.
+    pub fn track_info(&self) -> Result<TrackInfo> {
+        let album_art = match self.album_art {
+            Some(ref path) => Some(std::fs::read(path).with_context(|| {
+                format!("프리셋의 앨범 아트를 읽을 수 없습니다: {}", path.display())
+            })?),
+            None => None,
+        };
+        Ok(TrackInfo {
+            artists: self
+                .artist
+                .as_deref()
+                .map(split_artists)
+                .unwrap_or_default(),
+            album: self.album.clone(),
+            album_artist: self.album_artist.clone(),
+            year: self.year,
+            genre: self.genre.clone(),
+            label: self.label.clone(),
+            grouping: self.grouping.clone(),
+            album_art,
+            source: "manual".to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// 여러 소스의 결과를 합칠 때 필드별로 믿을 소스 순서 (`[trust]`).
+/// 키는 필드 이름(`genre`, `year` 등), 값은 앞에 있을수록 우선하는 소스 이름 목록이다.
+/// `default` 키는 따로 적지 않은 필드에 쓴다. 목록에 없는 소스는 목록의 모든 소스보다 뒤이며,
+/// 순위가 같으면 나중에 가져온 값이 이긴다.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct TrustConfig {
+    pub fields: BTreeMap<String, Vec<String>>,
+}
+
+impl TrustConfig {
+    /// `field`에서 `source`의 순위. 0이 가장 높고, 목록에 없으면 None.
+    fn rank(&self, field: &str, source: &str) -> Option<usize> {
+        let order = self
+            .fields
+            .get(field)
+            .or_else(|| self.fields.get("default"))?;
+        order.iter().position(|s| s.eq_ignore_ascii_case(source))
+    }
+
+    /// `field`에서 기존 값의 출처가 새 값의 출처보다 믿을 만하면 true.
+    pub fn prefers(&self, field: &str, existing: &str, new: &str) -> bool {
+        match (self.rank(field, existing), self.rank(field, new)) {
+            (Some(a), Some(b)) => a < b,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// GUI 세션 상태. 창을 닫을 때 저장하고 다음 실행 때 복원한다.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GuiConfig {
+    /// 창 크기 [가로, 세로]
+    pub window_size: Option<[f32; 2]>,
+    /// 마지막으로 연 디렉토리. 디렉토리 없이 실행하면 이 디렉토리를 연다.
+    pub last_dir: Option<PathBuf>,
+    /// 검색 소스 이름 ("spotify", "melon", 외부 소스 이름)
+    pub source: Option<String>,
+    /// 한 소스 대신 모든 소스를 동시에 검색
+    #[serde(default)]
+    pub search_all: bool,
+    /// 파일 목록 정렬 기준
+    #[serde(default)]
+    pub sort: FileSort,
+    /// 내림차순 정렬
+    #[serde(default)]
+    pub sort_descending: bool,
+    /// 폴더 트리 패널을 접어 둘지
+    #[serde(default)]
+    pub hide_folders: bool,
+    /// 폴더 트리 패널 너비
+    pub folder_panel_width: Option<f32>,
+    /// 파일 목록 패널 너비
+    pub file_panel_width: Option<f32>,
+}
+
+/// GUI 파일 목록 정렬 기준.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileSort {
+    /// 파일 경로
+    #[default]
+    Path,
+    Title,
+    Artist,
+    Album,
+}
+
+/// 네트워크 사용 설정.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NetworkConfig {
+    /// 오프라인 모드. 소스 클라이언트를 만들지 않고 캐시된 검색 결과/앨범 아트와
+    /// 파일명 추론만 사용한다.
+    #[serde(default)]
+    pub offline: bool,
+    /// 검색 결과와 앨범 아트 캐시 디렉토리. 기본값은 현재 디렉토리의 `.mp3tag-cache`.
+    pub cache_dir: Option<PathBuf>,
+    /// 여러 소스를 동시에 검색할 때 응답을 기다리는 최대 시간 (초). 기본값은 20.
+    pub search_timeout_secs: Option<u64>,
+}
+
+impl NetworkConfig {
+    pub fn cache_dir(&self) -> PathBuf {
+        self.cache_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(".mp3tag-cache"))
+    }
+
+    pub fn search_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.search_timeout_secs.unwrap_or(20))
+    }
+
+    /// 오프라인 모드이면 `action` 수 없다는 에러를 반환한다.
+    pub fn ensure_online(&self, action: &str) -> Result<()> {
+        if self.offline {
+            anyhow::bail!(crate::i18n::tr!(
+                "오프라인 모드에서는 {} 수 없습니다 (--offline 또는 config.toml의 [network] offline)",
+                "Cannot {} in offline mode (--offline or [network] offline in config.toml)",
+                action
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 파일명 변경 설정.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameConfig {
+    /// 변경 후 전체 경로의 최대 길이 (문자 수). 0이면 제한하지 않는다.
+    /// 기본값은 Windows MAX_PATH인 260.
+    #[serde(default = "default_max_path_len")]
+    pub max_path_len: usize,
+    /// 대상 파일명이 이미 존재할 때의 기본 처리 방식.
+    #[serde(default)]
+    pub on_conflict: ConflictStrategy,
+    /// 파일명 템플릿 (확장자 제외). 사용 가능 필드는 `renamer::template_field` 참고.
+    #[serde(default = "default_file_template")]
+    pub file_template: String,
+    /// `auto --organize`로 옮길 폴더 템플릿. '/'로 폴더 단계를 나눈다.
+    #[serde(default = "default_organize_template")]
+    pub organize_template: String,
+    /// 정리 폴더로 옮길 때 앨범 아트를 함께 저장할 파일 이름 (예: `["folder.jpg", "cover.jpg"]`).
+    /// 비어 있으면 저장하지 않는다.
+    #[serde(default)]
+    pub folder_art: Vec<String>,
+    /// 정리 폴더로 옮길 때 Kodi 형식 album.nfo(앨범 폴더)와 artist.nfo(아티스트 폴더)를 함께 만든다.
+    #[serde(default)]
+    pub nfo: bool,
+}
+
+impl Default for RenameConfig {
+    fn default() -> Self {
+        Self {
+            max_path_len: default_max_path_len(),
+            on_conflict: ConflictStrategy::default(),
+            file_template: default_file_template(),
+            organize_template: default_organize_template(),
+            folder_art: Vec::new(),
+            nfo: false,
+        }
+    }
+}
+
+/// 파일명 변경 시 대상 파일이 이미 존재할 때의 처리 방식.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictStrategy {
+    /// 에러로 처리
+    #[default]
+    Error,
+    /// " (2)", " (3)" 등 번호를 붙여 저장
+    Suffix,
+    /// 기존 파일이 저음질이면 덮어쓰고, 아니면 건너뜀.
+    /// 비트레이트가 낮으면 저음질로 보고, 비트레이트가 같거나 알 수 없으면 파일이 더 작은 쪽을 저음질로 본다.
+    OverwriteSmaller,
+    /// 조용히 건너뜀
+    Skip,
+    /// 사용자에게 물어봄 (CLI 전용)
+    Prompt,
+}
+
+fn default_max_path_len() -> usize {
+    260
+}
+
+fn default_file_template() -> String {
+    "{artist} - {title}".to_string()
+}
+
+fn default_organize_template() -> String {
+    "{album_artist}/{album}".to_string()
+}
+
+/// 태그 기록 설정.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteConfig {
+    /// 임시 파일에 기록한 뒤 원본 위로 rename한다. 기록 도중 중단되어도 원본이 손상되지 않는다.
+    /// rename을 지원하지 않는 파일시스템에서는 false로 끈다.
+    #[serde(default = "default_true")]
+    pub atomic: bool,
+    /// 태그 기록 후 파일 수정 시각(mtime) 처리 방식.
+    #[serde(default)]
+    pub mtime: MtimePolicy,
+    /// 읽기 전용 파일도 임시로 쓰기 권한을 주어 기록한다.
+    #[serde(default)]
+    pub force: bool,
+    /// 태그 기록 직전에 실행할 셸 명령. 0이 아닌 코드로 끝나면 그 파일은 기록하지 않는다.
+    /// 경로와 태그 값은 `MP3TAG_PATH`, `MP3TAG_TITLE` 등의 환경 변수로 전달된다.
+    #[serde(default)]
+    pub pre_write_hook: Option<String>,
+    /// 태그 기록 직후 실행할 셸 명령 (라이브러리 재스캔, git-annex 커밋 등).
+    #[serde(default)]
+    pub post_write_hook: Option<String>,
+    /// 기록 전에 원본을 백업하는 방식. `mp3tag restore`로 되돌릴 수 있다.
+    #[serde(default)]
+    pub backup: BackupMode,
+    /// 백업 디렉토리. 기본값은 현재 디렉토리의 `.mp3tag-backup`.
+    #[serde(default)]
+    pub backup_dir: Option<PathBuf>,
+    /// 한글 제목/아티스트/앨범을 로마자로 옮겨 정렬용 프레임(TSOT, TSOP, TSOA, TSO2)에 기록한다.
+    #[serde(default)]
+    pub romanize_sort: bool,
+    /// 기록한 내용을 디스크에 동기화(fsync)하는 시점.
+    #[serde(default)]
+    pub fsync: FsyncPolicy,
+    /// 기록한 변경을 디렉토리마다 `.mp3tag-history.jsonl`에 남긴다 (시각, 출처, 필드별 이전/새 값).
+    #[serde(default)]
+    pub history: bool,
+    /// 자동 작업(소스 적용, 파일명 추론, sync 등)이 파일을 고칠 때마다 TXXX:MP3TAG_PROVENANCE 프레임에
+    /// 날짜, 출처, 일치 점수를 덧붙인다. 직접 편집은 남기지 않는다.
+    #[serde(default)]
+    pub provenance: bool,
+    /// 일치 점수의 신뢰 등급(`matcher::Tier`)을 TXXX:MP3TAG_MATCH_TIER 프레임에 기록한다.
+    /// 설정 파일이 아니라 `auto --write-tier`로만 켠다.
+    #[serde(skip)]
+    pub match_tier: bool,
+    /// 아티스트가 여럿일 때 TPE1에 이어 붙일 구분자 (기본값 ", ").
+    #[serde(default = "default_artist_separator")]
+    pub artist_separator: String,
+    /// 구분자 대신 ID3v2.4의 다중 값(널 문자 구분)으로 아티스트를 기록한다.
+    /// 지원하지 않는 플레이어는 첫 번째 아티스트만 보여 줄 수 있다.
+    #[serde(default)]
+    pub artist_multi_value: bool,
+    /// iTunes 12.5.4 이후 방식으로 그룹은 GRP1에, 작품명은 TIT1에도 기록한다.
+    /// 끄면 그룹은 TIT1에, 작품명은 TXXX:WORK에만 기록한다.
+    #[serde(default)]
+    pub itunes_grouping: bool,
+    /// `fetch`가 소스 결과를 파일의 기존 태그와 합치는 방식 (`--merge`로 덮어쓸 수 있음).
+    #[serde(default)]
+    pub merge: MergePolicy,
+    /// 자동 작업(소스 적용, 파일명 추론, 레거시 태그 이전 등)이 기존 값을 덮어쓰지 않을 필드
+    /// (예: `["genre", "lyrics"]`). 비어 있는 필드는 채우며, 직접 편집한 값은 막지 않는다.
+    #[serde(default)]
+    pub protected: Vec<String>,
+}
+
+fn default_artist_separator() -> String {
+    ", ".to_string()
+}
+
+impl Default for WriteConfig {
+    fn default() -> Self {
+        Self {
+            atomic: true,
+            mtime: MtimePolicy::default(),
+            force: false,
+            pre_write_hook: None,
+            post_write_hook: None,
+            backup: BackupMode::default(),
+            backup_dir: None,
+            romanize_sort: false,
+            fsync: FsyncPolicy::default(),
+            history: false,
+            provenance: false,
+            match_tier: false,
+            artist_separator: default_artist_separator(),
+            artist_multi_value: false,
+            itunes_grouping: false,
+            merge: MergePolicy::default(),
+            protected: Vec::new(),
+        }
+    }
+}
+
+impl WriteConfig {
+    pub fn backup_dir(&self) -> PathBuf {
+        self.backup_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(".mp3tag-backup"))
+    }
+}
+
+/// 태그 기록 전 원본 백업 방식.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackupMode {
+    /// 백업하지 않음 (기본 동작)
+    #[default]
+    Off,
+    /// 파일 전체를 복사
+    File,
+    /// ID3v2 태그 바이트만 복사
+    Tags,
+}
+
+/// 소스에서 가져온 태그와 파일의 기존 태그에 모두 값이 있는 필드를 합치는 방식.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergePolicy {
+    /// 새 값으로 덮어씀 (기본 동작)
+    #[default]
+    Overwrite,
+    /// 비어 있는 필드만 채우고 기존 값은 그대로 둠
+    Fill,
+    /// 값이 다른 필드마다 어느 쪽을 쓸지 물음 (물을 수 없으면 fill과 같음)
+    Interactive,
+}
+
+/// 태그 기록 후 파일 수정 시각(mtime) 처리 방식.
+/// 플레이어들은 mtime을 "최근 추가됨" 정렬에 사용한다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum MtimePolicy {
+    /// 기록 시각으로 갱신 (기본 동작)
+    #[default]
+    Update,
+    /// 기록 전 mtime 유지
+    Preserve,
+    /// 발매 연도의 1월 1일로 설정 (연도가 없으면 기존 mtime 유지)
+    ReleaseYear,
+}
+
+/// 태그를 기록한 파일을 디스크에 동기화하는 시점.
+/// 느린 USB/네트워크 드라이브에서는 파일마다 동기화하면 일괄 기록이 크게 느려진다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FsyncPolicy {
+    /// 파일마다 기록 직후 동기화 (기본 동작)
+    #[default]
+    EachFile,
+    /// 일괄 기록에서는 모든 파일을 기록한 뒤 한 번에 동기화 (파일 하나만 기록할 때는 each-file과 같음).
+    /// 원자적 기록(`atomic`)은 rename 전에 임시 파일을 동기화해야 하므로 each-file과 같다
+    Batch,
+    /// 동기화하지 않고 운영체제에 맡긴다
+    Never,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 스크래핑 소스(Melon 등)의 요청 속도 설정.
+/// 대량 가져오기에서 IP가 차단되지 않도록 요청 사이에 간격을 둔다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrapeConfig {
+    /// 같은 호스트에 대한 요청 사이의 최소 간격 (밀리초)
+    #[serde(default = "default_delay_ms")]
+    pub delay_ms: u64,
+    /// 간격에 더할 무작위 지터의 최대값 (밀리초)
+    #[serde(default = "default_jitter_ms")]
+    pub jitter_ms: u64,
+    /// 네트워크 오류, 429, 5xx 응답 시 최대 재시도 횟수
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// 요청에 보낼 User-Agent. 없으면 소스의 기본값 (사이트가 봇 차단 기준을 바꿨을 때 조정)
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// 모든 요청에 더할 헤더 (예: 성인 인증 페이지용 "Cookie", "Accept-Language")
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// CSS 선택자를 덮어쓸 TOML 파일 (Melon). 바꿀 키만 쓰면 나머지는 기본값
+    #[serde(default)]
+    pub selectors: Option<PathBuf>,
+}
+
+impl Default for ScrapeConfig {
+    fn default() -> Self {
+        Self {
+            delay_ms: default_delay_ms(),
+            jitter_ms: default_jitter_ms(),
+            max_retries: default_max_retries(),
+            user_agent: None,
+            headers: BTreeMap::new(),
+            selectors: None,
+        }
+    }
+}
+
+fn default_delay_ms() -> u64 {
+    500
+}
+
+fn default_jitter_ms() -> u64 {
+    300
+}
+
+fn default_max_retries() -> u32 {
+    2
 }
 
 /// 설정 파일 경로를 반환한다. 현재 디렉토리의 config.toml.
@@ -31,17 +806,50 @@ fn config_path() -> PathBuf {
 }
 
 /// 설정 파일을 읽어 Config를 반환한다. 파일이 없으면 기본값.
-pub fn load_config() -> Config {
+/// 파일을 읽지 못하거나 해석할 수 없으면 설정 오류(종료 코드 4)를 낸다.
+pub fn load_config() -> Result<Config> {
     let path = config_path();
-    if !path.exists() {
-        return Config::default();
+    match read_config_file(&path)? {
+        Some(content) => parse_config(&content, &path),
+        None => Ok(Config::default()),
     }
-    match std::fs::read_to_string(&path) {
-        Ok(content) => toml::from_str(&content).unwrap_or_default(),
-        Err(_) => Config::default(),
+}
+
+/// 설정 파일 내용을 읽는다. 파일이 없으면 None.
+fn read_config_file(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(exit::config_error(format!(
+            "설정 파일을 읽을 수 없습니다: {}: {}",
+            path.display(),
+            e
+        ))),
     }
 }
 
+/// 설정 파일 내용을 TOML 표로 해석한다.
+fn parse_config_table(content: &str, path: &Path) -> Result<toml::Table> {
+    toml::from_str(content).map_err(|e| {
+        exit::config_error(format!(
+            "설정 파일을 해석할 수 없습니다: {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// 설정 파일 내용을 Config로 해석한다.
+fn parse_config(content: &str, path: &Path) -> Result<Config> {
+    parse_config_table(content, path)?.try_into().map_err(|e| {
+        exit::config_error(format!(
+            "설정 파일을 해석할 수 없습니다: {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
 /// Config를 설정 파일에 저장한다.
 pub fn save_config(config: &Config) -> Result<()> {
     let path = config_path();
@@ -49,3 +857,382 @@ pub fn save_config(config: &Config) -> Result<()> {
     std::fs::write(&path, content)?;
     Ok(())
 }
+
+/// 설정 파일을 다시 읽어 `f`로 일부만 바꾼 뒤 저장한다.
+/// 명령줄 옵션으로 덮어쓴 메모리상의 Config가 파일에 섞여 들어가지 않도록 하고,
+/// `f`가 바꾼 키만 원래 TOML에 덮어써 나머지 내용은 그대로 둔다.
+/// 파일을 해석할 수 없으면 기본값으로 덮어쓰지 않고 에러를 낸다.
+pub fn update_config(f: impl FnOnce(&mut Config)) -> Result<()> {
+    let path = config_path();
+    let content = read_config_file(&path)?;
+    let updated = patch_config(content.as_deref(), &path, f)?;
+    std::fs::write(&path, updated)
+        .with_context(|| format!("설정 파일을 저장할 수 없습니다: {}", path.display()))
+}
+
+/// `content`(없으면 기본 설정)에 `f`가 바꾼 값만 반영한 TOML 텍스트를 만든다.
+fn patch_config(content: Option<&str>, path: &Path, f: impl FnOnce(&mut Config)) -> Result<String> {
+    let Some(content) = content else {
+        let mut config = Config::default();
+        f(&mut config);
+        return Ok(toml::to_string_pretty(&config)?);
+    };
+    let mut raw = parse_config_table(content, path)?;
+    let mut config = parse_config(content, path)?;
+    let before = toml::Table::try_from(&config)?;
+    f(&mut config);
+    let after = toml::Table::try_from(&config)?;
+    merge_changes(&mut raw, &before, &after);
+    Ok(toml::to_string_pretty(&raw)?)
+}
+
+/// `before`와 `after`가 다른 키만 `raw`에 반영한다. 표는 키 단위로 내려가며 비교하고,
+/// `after`에서 사라진 키(None이 된 옵션)는 `raw`에서도 지운다.
+fn merge_changes(raw: &mut toml::Table, before: &toml::Table, after: &toml::Table) {
+    let keys: Vec<String> = before
+        .keys()
+        .chain(after.keys().filter(|k| !before.contains_key(*k)))
+        .cloned()
+        .collect();
+    for key in keys {
+        match (before.get(&key), after.get(&key)) {
+            (old, new) if old == new => {}
+            (Some(toml::Value::Table(old)), Some(toml::Value::Table(new))) => {
+                match raw
+                    .entry(key.clone())
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                {
+                    toml::Value::Table(table) => merge_changes(table, old, new),
+                    value => *value = toml::Value::Table(new.clone()),
+                }
+            }
+            (_, Some(new)) => {
+                raw.insert(key, new.clone());
+            }
+            (Some(_), None) => {
+                raw.remove(&key);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// `config show`에서 값을 가리는 비밀 설정 키.
+const SECRET_KEYS: &[&str] = &[
+    "client_secret",
+    "refresh_token",
+    "access_token",
+    "api_key",
+    "password",
+];
+
+/// 설정 파일을 TOML 값 그대로 읽는다. 파일이 없으면 None, 문법이 틀리면 에러.
+/// `load_config`와 달리 해석 오류를 기본값으로 덮지 않는다.
+pub fn read_config_value() -> Result<Option<toml::Value>> {
+    let path = config_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("설정 파일을 읽을 수 없습니다: {}", path.display()))?;
+    let value = toml::from_str(&content)
+        .with_context(|| format!("설정 파일을 해석할 수 없습니다: {}", path.display()))?;
+    Ok(Some(value))
+}
+
+/// 기본값을 채운 설정을 TOML로 보여 준다. 비밀 값은 "****"로 가린다.
+pub fn redacted_toml(config: &Config) -> Result<String> {
+    let mut value = toml::Value::try_from(config)?;
+    redact(&mut value);
+    Ok(toml::to_string_pretty(&value)?)
+}
+
+/// 모든 값을 가리는 표. `[melon] headers`에는 Cookie, Authorization 등 이름을 알 수 없는 비밀이 들어간다.
+const SECRET_TABLES: &[&str] = &["headers"];
+
+fn redact(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                let secret = SECRET_KEYS.contains(&key.as_str())
+                    && value.as_str().is_some_and(|s| !s.is_empty());
+                if secret {
+                    *value = toml::Value::String("****".to_string());
+                } else if SECRET_TABLES.contains(&key.as_str()) {
+                    if let toml::Value::Table(secrets) = value {
+                        for (_, v) in secrets.iter_mut() {
+                            *v = toml::Value::String("****".to_string());
+                        }
+                    }
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        toml::Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// 설정 파일에 있지만 Config가 쓰지 않는 키를 "section.key" 형식으로 반환한다.
+/// 파일을 Config로 읽은 뒤 다시 직렬화했을 때 사라지는 키를 모른다고 본다.
+pub fn unknown_keys(raw: &toml::Value) -> Result<Vec<String>> {
+    let config: Config = raw.clone().try_into()?;
+    let known = toml::Value::try_from(&config)?;
+    let mut unknown = Vec::new();
+    collect_unknown(raw, &known, "", &mut unknown);
+    Ok(unknown)
+}
+
+fn collect_unknown(raw: &toml::Value, known: &toml::Value, prefix: &str, out: &mut Vec<String>) {
+    match (raw, known) {
+        (toml::Value::Table(raw), toml::Value::Table(known)) => {
+            for (key, value) in raw {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                match known.get(key) {
+                    Some(known) => collect_unknown(value, known, &path, out),
+                    None => out.push(path),
+                }
+            }
+        }
+        (toml::Value::Array(raw), toml::Value::Array(known)) => {
+            for (i, (raw, known)) in raw.iter().zip(known).enumerate() {
+                collect_unknown(raw, known, &format!("{}[{}]", prefix, i), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 설정 파일의 `key`("write.atomic" 형식)를 `value`로 바꿔 저장한다. 다른 키와 값은 그대로 둔다.
+/// 값은 TOML 값(`true`, `500`, `["a", "b"]`)으로 읽고, 아니면 문자열로 기록한다.
+pub fn set_value(key: &str, value: &str) -> Result<()> {
+    let mut raw = read_config_value()?.unwrap_or_else(|| toml::Value::Table(Default::default()));
+    set_in(&mut raw, key, value)?;
+    std::fs::write(config_path(), toml::to_string_pretty(&raw)?)?;
+    Ok(())
+}
+
+fn set_in(raw: &mut toml::Value, key: &str, value: &str) -> Result<()> {
+    let parts: Vec<&str> = key.split('.').map(str::trim).collect();
+    if parts.iter().any(|p| p.is_empty()) {
+        anyhow::bail!(
+            "설정 키 형식이 올바르지 않습니다: {} (예: write.atomic)",
+            key
+        );
+    }
+    let parsed = toml::from_str::<toml::Table>(&format!("v = {}", value))
+        .ok()
+        .and_then(|mut t| t.remove("v"));
+    // 문자열 필드에 숫자처럼 보이는 값을 넣은 경우를 위해 문자열로도 시도한다
+    let mut error = None;
+    for candidate in [parsed, Some(toml::Value::String(value.to_string()))]
+        .into_iter()
+        .flatten()
+    {
+        let mut updated = raw.clone();
+        insert_at(&mut updated, &parts, candidate)?;
+        if let Err(e) = updated.clone().try_into::<Config>() {
+            error = Some(e);
+            continue;
+        }
+        let unknown = unknown_keys(&updated)?;
+        if unknown
+            .iter()
+            .any(|u| key == u || key.starts_with(&format!("{}.", u)))
+        {
+            anyhow::bail!("알 수 없는 설정 키입니다: {}", key);
+        }
+        *raw = updated;
+        return Ok(());
+    }
+    let message = error.map(|e| e.message().to_string()).unwrap_or_default();
+    anyhow::bail!("{} 값이 올바르지 않습니다: {}", key, message)
+}
+
+/// 점으로 나눈 경로의 마지막 키에 값을 넣는다. 중간 섹션이 없으면 만든다.
+fn insert_at(raw: &mut toml::Value, parts: &[&str], value: toml::Value) -> Result<()> {
+    let (last, sections) = parts.split_last().context("설정 키가 비어 있습니다")?;
+    let mut table = raw
+        .as_table_mut()
+        .context("설정 파일의 최상위가 테이블이 아닙니다")?;
+    for section in sections {
+        table = table
+            .entry(section.to_string())
+            .or_insert_with(|| toml::Value::Table(Default::default()))
+            .as_table_mut()
+            .with_context(|| format!("{}은(는) 섹션이 아닙니다", section))?;
+    }
+    table.insert(last.to_string(), value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_broken_config_is_config_error() {
+        let path = Path::new("config.toml");
+        let err = parse_config("[spotify\nclient_id = \"abc\"\n", path).unwrap_err();
+        assert_eq!(exit::code(&err), exit::ExitCode::Config);
+        let err = parse_config("[write]\natomic = \"yes\"\n", path).unwrap_err();
+        assert_eq!(exit::code(&err), exit::ExitCode::Config);
+        // 해석할 수 없는 파일은 기본값으로 덮어쓰지 않는다
+        let err = patch_config(Some("[spotify\n"), path, |c| c.gui.last_dir = None).unwrap_err();
+        assert_eq!(exit::code(&err), exit::ExitCode::Config);
+    }
+
+    #[test]
+    fn test_update_patches_only_changed_keys() {
+        let path = Path::new("config.toml");
+        let content = "default_source = \"melon\"\ntypo_key = 1\n\
+                       [spotify]\nclient_id = \"abc\"\n\
+                       [gui]\nlast_dir = \"/music/old\"\nsearch_all = true\n";
+        let patched = patch_config(Some(content), path, |c| {
+            c.gui.last_dir = Some(PathBuf::from("/music/new"));
+            c.gui.search_all = false;
+        })
+        .unwrap();
+        let value: toml::Table = toml::from_str(&patched).unwrap();
+        assert_eq!(value["typo_key"].as_integer(), Some(1));
+        assert_eq!(value["default_source"].as_str(), Some("melon"));
+        assert_eq!(value["spotify"]["client_id"].as_str(), Some("abc"));
+        assert_eq!(value["gui"]["last_dir"].as_str(), Some("/music/new"));
+        assert_eq!(value["gui"]["search_all"].as_bool(), Some(false));
+        // 기본값으로 채워진 다른 섹션은 파일에 새로 쓰지 않는다
+        assert!(!value.contains_key("write"));
+
+        let patched = patch_config(Some(&patched), path, |c| c.gui.last_dir = None).unwrap();
+        let value: toml::Table = toml::from_str(&patched).unwrap();
+        assert!(!value["gui"].as_table().unwrap().contains_key("last_dir"));
+    }
+
+    #[test]
+    fn test_gui_session_roundtrip() {
+        let path = Path::new("config.toml");
+        let session = GuiConfig {
+            window_size: Some([1280.0, 800.0]),
+            last_dir: Some(PathBuf::from("/music/IU")),
+            source: Some("melon".to_string()),
+            search_all: true,
+            sort: FileSort::Album,
+            sort_descending: true,
+            hide_folders: true,
+            folder_panel_width: Some(180.0),
+            file_panel_width: Some(420.0),
+        };
+        // 설정 파일이 없으면 새로 만들고, 있으면 [gui]만 바꾼다
+        let saved = patch_config(None, path, |c| c.gui = session.clone()).unwrap();
+        let saved = patch_config(Some(&saved), path, |c| c.gui.last_dir = None).unwrap();
+        assert!(saved.contains("sort = \"album\""));
+
+        let restored = parse_config(&saved, path).unwrap().gui;
+        assert_eq!(restored.window_size, session.window_size);
+        assert_eq!(restored.last_dir, None);
+        assert_eq!(restored.source, session.source);
+        assert!(restored.search_all && restored.sort_descending && restored.hide_folders);
+        assert_eq!(restored.sort, FileSort::Album);
+        assert_eq!(restored.folder_panel_width, Some(180.0));
+        assert_eq!(restored.file_panel_width, Some(420.0));
+    }
+
+    #[test]
+    fn test_presets() {
+        let dir = TempDir::new("presets");
+        let art = dir.join("cover.jpg");
+        std::fs::write(&art, b"JPEG").unwrap();
+        let content = format!(
+            "[presets.live2023]\nartist = \"IU, SUGA\"\nalbum = \"Live Bootleg 2023\"\n\
+             year = 2023\ngenre = \"Live\"\nalbum_art = {:?}\n\
+             [presets.broken]\nalbum_art = {:?}\n",
+            art,
+            dir.join("missing.jpg")
+        );
+        let cfg = parse_config(&content, Path::new("config.toml")).unwrap();
+
+        let info = cfg.preset("live2023").unwrap().track_info().unwrap();
+        assert_eq!(info.artists, ["IU", "SUGA"]);
+        assert_eq!(info.album.as_deref(), Some("Live Bootleg 2023"));
+        assert_eq!(info.year, Some(2023));
+        assert_eq!(info.genre.as_deref(), Some("Live"));
+        assert_eq!(info.album_art.as_deref(), Some(b"JPEG".as_slice()));
+        // 프리셋에 없는 필드는 비워 두어 파일의 기존 값을 덮어쓰지 않는다
+        assert_eq!(info.title, None);
+
+        assert!(cfg.preset("broken").unwrap().track_info().is_err());
+        let err = cfg.preset("live").unwrap_err().to_string();
+        assert!(err.contains("broken, live2023"), "{}", err);
+    }
+
+    #[test]
+    fn test_config_keys_and_redaction() {
+        let mut raw: toml::Value = toml::from_str(
+            "[spotify]\nclient_id = \"abc\"\nclient_secret = \"s3cret\"\n\
+             [write]\natomic = true\ntypo_key = 1\n[colour]\nx = 1\n",
+        )
+        .unwrap();
+        assert_eq!(unknown_keys(&raw).unwrap(), ["colour", "write.typo_key"]);
+
+        set_in(&mut raw, "write.atomic", "false").unwrap();
+        set_in(&mut raw, "art.min_size", "500").unwrap();
+        // 문자열 필드에는 숫자처럼 보이는 값도 문자열로 기록한다
+        set_in(&mut raw, "spotify.client_id", "12345").unwrap();
+        assert!(set_in(&mut raw, "art.min_size", "big").is_err());
+        assert!(set_in(&mut raw, "art.no_such_key", "1").is_err());
+        let config: Config = raw.clone().try_into().unwrap();
+        assert!(!config.write.atomic);
+        assert_eq!(config.art.min_size, 500);
+        assert_eq!(config.spotify.client_id.as_deref(), Some("12345"));
+
+        let shown = redacted_toml(&config).unwrap();
+        assert!(shown.contains("client_id = \"12345\""));
+        assert!(shown.contains("client_secret = \"****\""));
+        assert!(!shown.contains("s3cret"));
+
+        let mut config = config;
+        config
+            .melon
+            .headers
+            .insert("Cookie".to_string(), "PCID=abc123".to_string());
+        config
+            .melon
+            .headers
+            .insert("X-Custom".to_string(), "token".to_string());
+        let shown = redacted_toml(&config).unwrap();
+        assert!(shown.contains("Cookie = \"****\""));
+        assert!(shown.contains("X-Custom = \"****\""));
+        assert!(!shown.contains("PCID") && !shown.contains("= \"token\""));
+    }
+
+    #[test]
+    fn test_setup_apply() {
+        let mut config = Config::default();
+        let mut setup = Setup::from_config(&config);
+        assert_eq!(setup.default_source, "spotify");
+        setup.default_source = "melon".to_string();
+        setup.client_id = "  abc ".to_string();
+        setup.music_dir = "/music".to_string();
+        setup.file_template = String::new();
+        setup.apply(&mut config);
+        assert_eq!(config.default_source.as_deref(), Some("melon"));
+        assert_eq!(config.spotify.client_id.as_deref(), Some("abc"));
+        assert_eq!(config.spotify.client_secret, None);
+        assert_eq!(config.music_dir, Some(PathBuf::from("/music")));
+        // 비운 템플릿은 기존 값을 유지한다
+        assert_eq!(
+            config.rename.file_template,
+            Config::default().rename.file_template
+        );
+
+        // 최상위 키는 섹션보다 먼저 직렬화되어야 TOML로 쓸 수 있다
+        let text = toml::to_string_pretty(&config).unwrap();
+        let back: Config = toml::from_str(&text).unwrap();
+        assert_eq!(back.music_dir, config.music_dir);
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::core::artwork;
+
+/// 캐시 디렉토리에 두는 지각 해시 색인 파일 이름.
+pub const INDEX_FILE: &str = "art-hashes.json";
+
+/// 지각 해시 거리가 이 이하이면 같은 커버의 다른 스캔/인코딩으로 본다.
+pub const SIMILAR_DISTANCE: u32 = 10;
+
+/// 앨범 아트의 지각 해시 색인. 이미지 바이트의 SHA-256 → dHash를 저장해 두어,
+/// 다시 검사할 때 이미 본 이미지는 디코딩하지 않는다.
+pub struct ArtIndex {
+    path: PathBuf,
+    hashes: BTreeMap<String, u64>,
+    changed: bool,
+}
+
+impl ArtIndex {
+    /// `cache_dir`의 색인을 읽는다. 없거나 읽을 수 없으면 빈 색인으로 시작한다.
+    pub fn load(cache_dir: &Path) -> Self {
+        let path = cache_dir.join(INDEX_FILE);
+        let hashes = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            hashes,
+            changed: false,
+        }
+    }
+
+    /// 이미지의 지각 해시. 색인에 없으면 계산해 넣는다. 디코딩할 수 없는 이미지는 None.
+    pub fn hash(&mut self, data: &[u8]) -> Option<u64> {
+        let key: String = Sha256::digest(data)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        if let Some(&hash) = self.hashes.get(&key) {
+            return Some(hash);
+        }
+        #[cfg(feature = "resize")]
+        {
+            let hash = artwork::perceptual_hash(data).ok()?;
+            self.hashes.insert(key, hash);
+            self.changed = true;
+            Some(hash)
+        }
+        #[cfg(not(feature = "resize"))]
+        None
+    }
+
+    /// 새로 계산한 해시가 있으면 색인 파일에 기록한다.
+    pub fn save(&self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("디렉토리를 만들 수 없습니다: {}", dir.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string(&self.hashes)?)
+            .with_context(|| format!("아트 색인을 기록할 수 없습니다: {}", self.path.display()))
+    }
+}
+
+/// 앨범 안의 서로 다른 아트 하나와 그 이미지를 쓰는 파일 수.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArtVariant<'a> {
+    pub data: &'a [u8],
+    pub files: usize,
+    /// 대표 이미지와의 지각 해시 거리. 디코딩할 수 없으면 None.
+    pub distance: Option<u32>,
+}
+
+impl ArtVariant<'_> {
+    /// 대표 이미지와 같은 커버의 다른 스캔/인코딩인지.
+    pub fn is_rescan(&self) -> bool {
+        self.distance.is_some_and(|d| d <= SIMILAR_DISTANCE)
+    }
+}
+
+/// 앨범 파일들의 아트를 서로 다른 이미지별로 모으고 대표 이미지(`canonical`)와의 거리를 매긴다.
+/// 대표 이미지 자신은 빼고, 쓰는 파일이 많은 순으로 반환한다.
+pub fn variants<'a>(
+    index: &mut ArtIndex,
+    canonical: &[u8],
+    arts: impl IntoIterator<Item = &'a [u8]>,
+) -> Vec<ArtVariant<'a>> {
+    let mut found: Vec<ArtVariant<'a>> = Vec::new();
+    for art in arts {
+        if art == canonical {
+            continue;
+        }
+        match found.iter_mut().find(|v| v.data == art) {
+            Some(variant) => variant.files += 1,
+            None => found.push(ArtVariant {
+                data: art,
+                files: 1,
+                distance: None,
+            }),
+        }
+    }
+    let base = index.hash(canonical);
+    for variant in &mut found {
+        variant.distance = base
+            .zip(index.hash(variant.data))
+            .map(|(a, b)| artwork::hash_distance(a, b));
+    }
+    found.sort_by_key(|v| std::cmp::Reverse(v.files));
+    found
+}
+
+#[cfg(all(test, feature = "resize"))]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn png(invert: bool, size: u32) -> Vec<u8> {
+        let img = image::GrayImage::from_fn(size, size, |x, _| {
+            let v = (x * 255 / size) as u8;
+            image::Luma([if invert { 255 - v } else { v }])
+        });
+        let mut out = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut out, image::ImageFormat::Png).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_variants_and_index() {
+        let dir = TempDir::new("art-index");
+        let (cover, rescan, other) = (png(false, 500), png(false, 200), png(true, 500));
+        let mut index = ArtIndex::load(&dir);
+        let arts = [&cover, &rescan, &rescan, &other, &cover].map(|a| a.as_slice());
+        let found = variants(&mut index, &cover, arts);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].data, found[0].files), (rescan.as_slice(), 2));
+        assert!(found[0].is_rescan());
+        assert!(!found[1].is_rescan());
+
+        // 저장한 색인에서 다시 읽으면 계산하지 않는다
+        index.save().unwrap();
+        let mut reloaded = ArtIndex::load(&dir);
+        assert_eq!(reloaded.hashes.len(), 3);
+        assert_eq!(reloaded.hash(&other), index.hash(&other));
+        assert!(!reloaded.changed);
+    }
+}
@@ -0,0 +1,546 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::core::output;
+use crate::i18n::tr;
+use crate::models::Mp3File;
+
+/// 앨범 아트 이미지 형식. 헤더의 시그니처로 판별한다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtFormat {
+    Jpeg,
+    Png,
+    Gif,
+    Bmp,
+    WebP,
+    Avif,
+}
+
+impl ArtFormat {
+    /// 이미지 형식을 판별한다. 알 수 없는 데이터는 None.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if data.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+            Some(Self::Png)
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            Some(Self::Gif)
+        } else if data.starts_with(b"BM") && data.len() > 26 {
+            Some(Self::Bmp)
+        } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+            Some(Self::WebP)
+        } else if data.get(4..8) == Some(b"ftyp")
+            && matches!(data.get(8..12), Some(b"avif" | b"avis"))
+        {
+            Some(Self::Avif)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::Gif => "image/gif",
+            Self::Bmp => "image/bmp",
+            Self::WebP => "image/webp",
+            Self::Avif => "image/avif",
+        }
+    }
+
+    /// 화면 표시용 이름.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Jpeg => "JPEG",
+            Self::Png => "PNG",
+            Self::Gif => "GIF",
+            Self::Bmp => "BMP",
+            Self::WebP => "WebP",
+            Self::Avif => "AVIF",
+        }
+    }
+
+    /// 파일로 저장할 때의 확장자.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::Gif => "gif",
+            Self::Bmp => "bmp",
+            Self::WebP => "webp",
+            Self::Avif => "avif",
+        }
+    }
+
+    /// ID3v2 규격이 권하고 대부분의 플레이어가 표시하는 형식(JPEG, PNG)인지.
+    pub fn is_embeddable(self) -> bool {
+        matches!(self, Self::Jpeg | Self::Png)
+    }
+}
+
+/// 플레이어가 표시하지 못할 수 있는 형식(WebP, AVIF, GIF, BMP)의 아트를 JPEG(품질 90)로,
+/// 투명도가 있으면 PNG로 다시 인코딩한다. JPEG/PNG나 형식을 알 수 없는 데이터는 그대로 반환한다.
+pub fn to_embeddable(data: &[u8]) -> anyhow::Result<Cow<'_, [u8]>> {
+    let Some(format) = ArtFormat::detect(data).filter(|f| !f.is_embeddable()) else {
+        return Ok(Cow::Borrowed(data));
+    };
+    #[cfg(feature = "resize")]
+    {
+        use anyhow::Context;
+
+        let img = image::load_from_memory(data)
+            .with_context(|| format!("{} 앨범 아트를 읽을 수 없습니다", format.name()))?;
+        let mut out = std::io::Cursor::new(Vec::new());
+        if img.color().has_alpha() {
+            img.write_to(&mut out, image::ImageFormat::Png)
+                .context("앨범 아트를 PNG로 인코딩할 수 없습니다")?;
+        } else {
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, 90)
+                .encode_image(&img.to_rgb8())
+                .context("앨범 아트를 JPEG로 인코딩할 수 없습니다")?;
+        }
+        Ok(Cow::Owned(out.into_inner()))
+    }
+    #[cfg(not(feature = "resize"))]
+    anyhow::bail!(
+        "{} 앨범 아트를 JPEG/PNG로 변환하려면 이미지 기능이 필요합니다. 다시 빌드하세요: cargo build --features resize",
+        format.name()
+    )
+}
+
+/// 앨범 아트 한 장의 형식, 픽셀 크기, 바이트 크기 (scan의 art-info 열, GUI 아트 패널).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtInfo {
+    pub format: Option<ArtFormat>,
+    pub dimensions: Option<(u32, u32)>,
+    pub bytes: u64,
+}
+
+impl ArtInfo {
+    pub fn of(data: &[u8]) -> Self {
+        Self {
+            format: ArtFormat::detect(data),
+            dimensions: image_dimensions(data),
+            bytes: data.len() as u64,
+        }
+    }
+
+    /// "1400x1400 JPEG 512.0 KB". 알 수 없는 값은 뺀다.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some((width, height)) = self.dimensions {
+            parts.push(format!("{}x{}", width, height));
+        }
+        if let Some(format) = self.format {
+            parts.push(format.name().to_string());
+        }
+        parts.push(output::format_bytes(self.bytes));
+        parts.join(" ")
+    }
+
+    /// 바이트 크기가 `max_bytes`보다 크거나 짧은 변이 `min_size`보다 작으면 경고 문구.
+    /// 기준이 0이면 그 기준은 보지 않는다.
+    pub fn warning(&self, max_bytes: u64, min_size: u32) -> Option<String> {
+        let mut warnings = Vec::new();
+        if max_bytes > 0 && self.bytes > max_bytes {
+            warnings.push(tr!("{} 초과", "over {}", output::format_bytes(max_bytes)));
+        }
+        if let Some((width, height)) = self.dimensions {
+            if min_size > 0 && width.min(height) < min_size {
+                warnings.push(tr!("{}px 미만", "under {}px", min_size));
+            }
+        }
+        (!warnings.is_empty()).then(|| warnings.join(", "))
+    }
+}
+
+/// 이미지 바이너리의 헤더에서 (가로, 세로) 픽셀 크기를 읽는다. JPEG, PNG, GIF, WebP를 지원한다.
+pub fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if data.starts_with(&[0x89, b'P', b'N', b'G']) {
+        png_dimensions(data)
+    } else if data.starts_with(&[0xFF, 0xD8]) {
+        jpeg_dimensions(data)
+    } else {
+        match ArtFormat::detect(data)? {
+            ArtFormat::Gif => Some((read_u16_le(data, 6)? as u32, read_u16_le(data, 8)? as u32)),
+            ArtFormat::WebP => webp_dimensions(data),
+            _ => None,
+        }
+    }
+}
+
+/// 이미지의 짧은 변이 `min_size` 픽셀 이상인지 확인한다.
+/// `min_size`가 0이면 항상 통과하고, 크기를 알 수 없는 형식은 통과시키지 않는다.
+pub fn meets_min_size(data: &[u8], min_size: u32) -> bool {
+    if min_size == 0 {
+        return true;
+    }
+    short_side(data).is_some_and(|side| side >= min_size)
+}
+
+/// 이미지의 짧은 변 픽셀 수. 크기를 알 수 없는 형식은 None.
+pub fn short_side(data: &[u8]) -> Option<u32> {
+    image_dimensions(data).map(|(w, h)| w.min(h))
+}
+
+/// PNG: 시그니처(8) + IHDR 길이/타입(8) 뒤에 가로/세로가 온다.
+fn png_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((read_u32(data, 16)?, read_u32(data, 20)?))
+}
+
+/// JPEG: 마커 세그먼트를 따라가며 SOF(프레임 시작) 세그먼트의 크기를 읽는다.
+fn jpeg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            return None;
+        }
+        let marker = data[pos + 1];
+        // 채우기 바이트
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        // 길이가 없는 마커 (TEM, RSTn)
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            pos += 2;
+            continue;
+        }
+
+        let len = u16::from_be_bytes([data[pos + 2], *data.get(pos + 3)?]) as usize;
+        // SOF0~SOF15 중 DHT(C4), JPG(C8), DAC(CC)를 제외한 것
+        if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let height = u16::from_be_bytes([*data.get(pos + 5)?, *data.get(pos + 6)?]);
+            let width = u16::from_be_bytes([*data.get(pos + 7)?, *data.get(pos + 8)?]);
+            return Some((width as u32, height as u32));
+        }
+        pos += 2 + len;
+    }
+    None
+}
+
+/// 앨범 하나의 앨범 아트 사용량.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlbumArtUsage {
+    pub files: usize,
+    /// 앨범 아트가 있는 파일 수
+    pub with_art: usize,
+    /// 서로 다른 이미지 수
+    pub distinct: usize,
+    /// 모든 파일에 들어 있는 아트의 총 크기
+    pub art_bytes: u64,
+    /// 같은 이미지를 두 번째 이후로 저장한 크기
+    pub duplicate_bytes: u64,
+}
+
+/// 파일을 (폴더, 앨범 태그) 단위로 묶는다. 키는 "폴더 [앨범]" 형식의 표시용 이름이다.
+pub fn group_by_album(files: &[Mp3File]) -> BTreeMap<String, Vec<&Mp3File>> {
+    let mut groups: BTreeMap<(PathBuf, String), Vec<&Mp3File>> = BTreeMap::new();
+    for file in files {
+        let dir = file.path.parent().map(PathBuf::from).unwrap_or_default();
+        let album = file
+            .current_tags
+            .as_ref()
+            .and_then(|t| t.album.clone())
+            .unwrap_or_default();
+        groups.entry((dir, album)).or_default().push(file);
+    }
+    groups
+        .into_iter()
+        .map(|((dir, album), files)| {
+            let label = if album.is_empty() {
+                dir.display().to_string()
+            } else {
+                format!("{} [{}]", dir.display(), album)
+            };
+            (label, files)
+        })
+        .collect()
+}
+
+/// 파일 태그의 앨범 아트.
+pub fn art_of(file: &Mp3File) -> Option<&[u8]> {
+    file.current_tags.as_ref()?.album_art.as_deref()
+}
+
+/// 앨범 파일들의 아트 사용량을 계산한다.
+pub fn album_art_usage(files: &[&Mp3File]) -> AlbumArtUsage {
+    let mut counts: Vec<(&[u8], usize)> = Vec::new();
+    for art in files.iter().filter_map(|f| art_of(f)) {
+        match counts.iter_mut().find(|(data, _)| *data == art) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((art, 1)),
+        }
+    }
+    AlbumArtUsage {
+        files: files.len(),
+        with_art: counts.iter().map(|(_, n)| n).sum(),
+        distinct: counts.len(),
+        art_bytes: counts.iter().map(|(d, n)| (d.len() * n) as u64).sum(),
+        duplicate_bytes: counts.iter().map(|(d, n)| (d.len() * (n - 1)) as u64).sum(),
+    }
+}
+
+/// 앨범 전체에 기록할 대표 이미지를 고른다. 픽셀 수가 가장 큰 것, 같으면 가장 많이 쓰인 것.
+pub fn canonical_art<'a>(files: &[&'a Mp3File]) -> Option<&'a [u8]> {
+    let arts: Vec<&[u8]> = files.iter().filter_map(|f| art_of(f)).collect();
+    arts.iter()
+        .max_by_key(|art| {
+            let pixels = image_dimensions(art).map_or(0, |(w, h)| w as u64 * h as u64);
+            let uses = arts.iter().filter(|a| a == art).count();
+            (pixels, uses)
+        })
+        .copied()
+}
+
+/// 긴 변이 `max_size` 픽셀보다 크면 줄여서 JPEG(품질 90)로 다시 인코딩한다. 작으면 그대로 반환한다.
+#[cfg(feature = "resize")]
+pub fn resize(data: &[u8], max_size: u32) -> anyhow::Result<Vec<u8>> {
+    use anyhow::Context;
+
+    if image_dimensions(data).is_some_and(|(w, h)| w.max(h) <= max_size) {
+        return Ok(data.to_vec());
+    }
+    let img = image::load_from_memory(data).context("앨범 아트를 읽을 수 없습니다")?;
+    if img.width().max(img.height()) <= max_size {
+        return Ok(data.to_vec());
+    }
+    let resized = img
+        .resize(max_size, max_size, image::imageops::FilterType::Lanczos3)
+        .to_rgb8();
+    let mut out = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, 90)
+        .encode_image(&resized)
+        .context("앨범 아트를 JPEG로 인코딩할 수 없습니다")?;
+    Ok(out)
+}
+
+/// 이미지의 64비트 지각 해시(dHash). 9x8 흑백으로 줄인 뒤 가로로 이웃한 픽셀의 밝기 비교를 비트로 모은다.
+/// 같은 커버를 다른 해상도/압축률로 저장했거나 다시 스캔한 이미지는 해밍 거리가 작다.
+#[cfg(feature = "resize")]
+pub fn perceptual_hash(data: &[u8]) -> anyhow::Result<u64> {
+    use anyhow::Context;
+
+    let img = image::load_from_memory(data).context("앨범 아트를 읽을 수 없습니다")?;
+    let small = img
+        .resize_exact(9, 8, image::imageops::FilterType::Triangle)
+        .to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let brighter = small.get_pixel(x, y)[0] < small.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | brighter as u64;
+        }
+    }
+    Ok(hash)
+}
+
+/// 두 지각 해시의 해밍 거리 (다른 비트 수, 0~64).
+pub fn hash_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// WebP: RIFF 헤더(12) 뒤 첫 청크의 종류(손실 VP8, 무손실 VP8L, 확장 VP8X)에 따라 크기 위치가 다르다.
+fn webp_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let u24 = |pos: usize| -> Option<u32> {
+        let b = data.get(pos..pos + 3)?;
+        Some(u32::from_le_bytes([b[0], b[1], b[2], 0]))
+    };
+    match data.get(12..16)? {
+        // 프레임 태그(3) + 시작 코드(3) 뒤에 14비트 가로/세로
+        b"VP8 " if data.get(23..26)? == [0x9D, 0x01, 0x2A] => Some((
+            (read_u16_le(data, 26)? & 0x3FFF) as u32,
+            (read_u16_le(data, 28)? & 0x3FFF) as u32,
+        )),
+        // 시그니처(0x2F) 뒤에 14비트씩 (가로-1, 세로-1)
+        b"VP8L" if *data.get(20)? == 0x2F => {
+            let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        // 플래그(4) 뒤에 24비트씩 (가로-1, 세로-1)
+        b"VP8X" => Some((u24(24)? + 1, u24(27)? + 1)),
+        _ => None,
+    }
+}
+
+fn read_u16_le(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(pos..pos + 4)?.try_into().ok()?;
+    Some(u32::from_be_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        data.extend_from_slice(&13u32.to_be_bytes());
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data
+    }
+
+    fn jpeg(width: u16, height: u16) -> Vec<u8> {
+        let mut data = vec![0xFF, 0xD8];
+        // APP0 (JFIF) 세그먼트
+        data.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x06, b'J', b'F', b'I', b'F']);
+        // SOF0: 길이, 정밀도, 세로, 가로
+        data.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x11, 0x08]);
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&width.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_image_dimensions() {
+        assert_eq!(image_dimensions(&png(1400, 1400)), Some((1400, 1400)));
+        assert_eq!(image_dimensions(&jpeg(640, 480)), Some((640, 480)));
+        assert_eq!(image_dimensions(b"GIF89a"), None);
+        assert_eq!(
+            image_dimensions(b"GIF89a\x40\x01\xf0\x00"),
+            Some((320, 240))
+        );
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\x10\0\0\0".to_vec();
+        webp.extend_from_slice(&[0x7F, 0x02, 0x00, 0xDF, 0x01, 0x00]);
+        assert_eq!(image_dimensions(&webp), Some((640, 480)));
+    }
+
+    fn file(path: &str, album: &str, art: Option<Vec<u8>>) -> Mp3File {
+        Mp3File {
+            path: PathBuf::from(path),
+            current_tags: Some(crate::models::TrackInfo {
+                album: Some(album.to_string()),
+                album_art: art,
+                ..Default::default()
+            }),
+            has_tags: true,
+            encoder: None,
+        }
+    }
+
+    #[test]
+    fn test_album_art_usage() {
+        let small = jpeg(300, 300);
+        let large = png(1000, 1000);
+        let files = vec![
+            file("a/1.mp3", "Palette", Some(small.clone())),
+            file("a/2.mp3", "Palette", Some(small.clone())),
+            file("a/3.mp3", "Palette", Some(large.clone())),
+            file("a/4.mp3", "Palette", None),
+            file("b/1.mp3", "Palette", Some(small.clone())),
+        ];
+        let groups = group_by_album(&files);
+        assert_eq!(groups.len(), 2);
+        let album = &groups["a [Palette]"];
+        assert_eq!(
+            album_art_usage(album),
+            AlbumArtUsage {
+                files: 4,
+                with_art: 3,
+                distinct: 2,
+                art_bytes: (small.len() * 2 + large.len()) as u64,
+                duplicate_bytes: small.len() as u64,
+            }
+        );
+        assert_eq!(canonical_art(album), Some(&large[..]));
+    }
+
+    #[cfg(feature = "resize")]
+    #[test]
+    fn test_perceptual_hash() {
+        let encode = |img: image::RgbImage, format| {
+            let mut out = std::io::Cursor::new(Vec::new());
+            img.write_to(&mut out, format).unwrap();
+            out.into_inner()
+        };
+        let gradient = |size: u32, invert: bool| {
+            image::RgbImage::from_fn(size, size, |x, y| {
+                let v = ((x + y) * 255 / (2 * size)) as u8;
+                let v = if invert { 255 - v } else { v };
+                image::Rgb([v, v / 2, 255 - v])
+            })
+        };
+        let original = perceptual_hash(&encode(gradient(600, false), image::ImageFormat::Png));
+        let rescan = perceptual_hash(&encode(gradient(300, false), image::ImageFormat::Jpeg));
+        let other = perceptual_hash(&encode(gradient(600, true), image::ImageFormat::Png));
+        let (original, rescan, other) = (original.unwrap(), rescan.unwrap(), other.unwrap());
+        assert!(hash_distance(original, rescan) <= 4);
+        assert!(hash_distance(original, other) > 32);
+        assert!(perceptual_hash(b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_art_format() {
+        assert_eq!(ArtFormat::detect(&jpeg(10, 10)), Some(ArtFormat::Jpeg));
+        let mut exif = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        exif.extend_from_slice(b"Exif");
+        assert_eq!(ArtFormat::detect(&exif), Some(ArtFormat::Jpeg));
+        assert_eq!(ArtFormat::detect(&png(1, 1)), Some(ArtFormat::Png));
+        assert_eq!(
+            ArtFormat::detect(b"RIFF\x10\0\0\0WEBPVP8 "),
+            Some(ArtFormat::WebP)
+        );
+        assert_eq!(
+            ArtFormat::detect(b"\0\0\0\x1cftypavif\0\0\0\0"),
+            Some(ArtFormat::Avif)
+        );
+        assert_eq!(ArtFormat::detect(b"GIF89a"), Some(ArtFormat::Gif));
+        assert_eq!(ArtFormat::detect(b"unknown"), None);
+        assert_eq!(ArtFormat::WebP.mime_type(), "image/webp");
+        // 변환하지 않는 형식은 그대로
+        assert!(matches!(to_embeddable(b"unknown"), Ok(Cow::Borrowed(_))));
+    }
+
+    #[cfg(feature = "resize")]
+    #[test]
+    fn test_to_embeddable_converts_webp() {
+        let encode = |img: image::DynamicImage| {
+            let mut out = std::io::Cursor::new(Vec::new());
+            img.write_to(&mut out, image::ImageFormat::WebP).unwrap();
+            out.into_inner()
+        };
+        let opaque = encode(image::RgbImage::new(40, 30).into());
+        assert_eq!(image_dimensions(&opaque), Some((40, 30)));
+        let converted = to_embeddable(&opaque).unwrap();
+        assert_eq!(ArtFormat::detect(&converted), Some(ArtFormat::Jpeg));
+        assert_eq!(image_dimensions(&converted), Some((40, 30)));
+
+        let transparent = encode(image::RgbaImage::new(40, 30).into());
+        assert_eq!(image_dimensions(&transparent), Some((40, 30)));
+        let converted = to_embeddable(&transparent).unwrap();
+        assert_eq!(ArtFormat::detect(&converted), Some(ArtFormat::Png));
+
+        let mut broken = opaque.clone();
+        broken.truncate(16);
+        assert!(to_embeddable(&broken).is_err());
+    }
+
+    #[test]
+    fn test_art_info() {
+        let info = ArtInfo::of(&png(1400, 1400));
+        assert_eq!(info.summary(), format!("1400x1400 PNG {} B", info.bytes));
+        assert_eq!(info.warning(1024, 300), None);
+        assert_eq!(info.warning(10, 2000).unwrap(), "10 B 초과, 2000px 미만");
+        assert_eq!(info.warning(0, 0), None);
+        assert_eq!(ArtInfo::of(b"unknown").summary(), "7 B");
+        assert_eq!(ArtInfo::of(b"unknown").warning(1024, 300), None);
+    }
+
+    #[test]
+    fn test_meets_min_size() {
+        assert!(meets_min_size(&png(1000, 1200), 1000));
+        assert!(!meets_min_size(&jpeg(1200, 640), 1000));
+        assert!(!meets_min_size(b"unknown", 1000));
+        assert!(meets_min_size(b"unknown", 0));
+    }
+}
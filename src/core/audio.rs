@@ -0,0 +1,565 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::models::EncoderInfo;
+
+/// 첫 프레임을 찾을 때 읽는 최대 바이트 수.
+const SCAN_LIMIT: usize = 64 * 1024;
+
+/// Layer III 비트레이트 표 (kbps). [MPEG1, MPEG2/2.5]
+const BITRATES: [[u32; 15]; 2] = [
+    [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ],
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+];
+
+/// MPEG 오디오 프레임 헤더 (Layer III만 다룬다).
+#[derive(Debug, PartialEq)]
+struct FrameHeader {
+    mpeg1: bool,
+    mono: bool,
+    bitrate_kbps: u32,
+    sample_rate: u32,
+    padding: bool,
+}
+
+impl FrameHeader {
+    /// 4바이트 프레임 헤더를 해석한다. Layer III가 아니거나 예약값이면 None.
+    fn parse(b: &[u8]) -> Option<Self> {
+        if b.len() < 4 || b[0] != 0xFF || b[1] & 0xE0 != 0xE0 {
+            return None;
+        }
+
+        let version = (b[1] >> 3) & 0x03; // 3: MPEG1, 2: MPEG2, 0: MPEG2.5
+        let layer = (b[1] >> 1) & 0x03; // 1: Layer III
+        if version == 1 || layer != 1 {
+            return None;
+        }
+        let mpeg1 = version == 3;
+
+        let bitrate_index = (b[2] >> 4) as usize;
+        let rate_index = ((b[2] >> 2) & 0x03) as usize;
+        if bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+            return None;
+        }
+
+        let base_rate = [44100, 48000, 32000][rate_index];
+        let sample_rate = match version {
+            3 => base_rate,
+            2 => base_rate / 2,
+            _ => base_rate / 4,
+        };
+
+        Some(Self {
+            mpeg1,
+            mono: b[3] >> 6 == 3,
+            bitrate_kbps: BITRATES[if mpeg1 { 0 } else { 1 }][bitrate_index],
+            sample_rate,
+            padding: b[2] & 0x02 != 0,
+        })
+    }
+
+    /// 헤더를 포함한 프레임 전체 길이 (바이트).
+    fn frame_len(&self) -> u64 {
+        let coefficient = if self.mpeg1 { 144 } else { 72 };
+        coefficient * self.bitrate_kbps as u64 * 1000 / self.sample_rate as u64
+            + self.padding as u64
+    }
+
+    fn samples_per_frame(&self) -> u64 {
+        if self.mpeg1 {
+            1152
+        } else {
+            576
+        }
+    }
+
+    /// 헤더 뒤 side information의 크기. Xing 헤더는 그 바로 뒤에 있다.
+    fn side_info_len(&self) -> usize {
+        match (self.mpeg1, self.mono) {
+            (true, false) => 32,
+            (true, true) => 17,
+            (false, false) => 17,
+            (false, true) => 9,
+        }
+    }
+}
+
+/// MP3 파일의 재생 시간(밀리초)을 계산한다.
+/// VBR이면 Xing/Info 또는 VBRI 헤더의 프레임 수를, 없으면 첫 프레임의 비트레이트(CBR)를 사용한다.
+/// 프레임을 찾지 못하면 None.
+pub fn duration_ms(path: &Path) -> Option<u64> {
+    audio_len_and_duration(path).map(|(_, duration)| duration)
+}
+
+/// 평균 비트레이트(kbps). 오디오 길이를 재생 시간으로 나누므로 VBR 파일에도 맞는다.
+pub fn bitrate_kbps(path: &Path) -> Option<u32> {
+    let (audio_len, duration) = audio_len_and_duration(path)?;
+    (duration > 0).then(|| (audio_len * 8 / duration) as u32)
+}
+
+/// 태그를 뺀 오디오 데이터 길이(바이트)와 재생 시간(밀리초).
+fn audio_len_and_duration(path: &Path) -> Option<(u64, u64)> {
+    let mut file = File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+
+    let (audio_start, buf) = read_audio_head(&mut file)?;
+
+    let mut audio_len = file_len.saturating_sub(audio_start);
+    if has_id3v1(&mut file, file_len) {
+        audio_len = audio_len.saturating_sub(128);
+    }
+
+    Some((audio_len, duration_from_frames(&buf, audio_len)?))
+}
+
+/// 태그를 뺀 오디오 데이터(ID3v2 뒤부터 ID3v1 앞까지)의 SHA-256을 16진수로 반환한다.
+/// 태그를 고치거나 파일명을 바꾸거나 옮겨도 같은 값이 나온다. 읽지 못하면 None.
+pub fn content_hash(path: &Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+    let audio_start = id3v2_len(&mut file)?;
+    let mut audio_len = file_len.saturating_sub(audio_start);
+    if has_id3v1(&mut file, file_len) {
+        audio_len = audio_len.saturating_sub(128);
+    }
+
+    file.seek(SeekFrom::Start(audio_start)).ok()?;
+    let mut hasher = Sha256::new();
+    let mut audio = file.take(audio_len);
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = audio.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Some(hex_digest(hasher))
+}
+
+/// 태그 부분(ID3v2와 ID3v1 바이트)만의 SHA-256을 16진수로 반환한다. 오디오는 읽지 않으므로 빠르다.
+/// 어느 프레임이든 바뀌면 값이 달라지고, 태그가 없는 파일은 빈 데이터의 해시가 된다. 읽지 못하면 None.
+pub fn tag_hash(path: &Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+    let tag_len = id3v2_len(&mut file)?.min(file_len);
+    let mut hasher = Sha256::new();
+    let mut tag = vec![0u8; tag_len as usize];
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_exact(&mut tag).ok()?;
+    hasher.update(&tag);
+    if has_id3v1(&mut file, file_len) {
+        let mut v1 = [0u8; 128];
+        file.seek(SeekFrom::Start(file_len - 128)).ok()?;
+        file.read_exact(&mut v1).ok()?;
+        hasher.update(v1);
+    }
+    Some(hex_digest(hasher))
+}
+
+fn hex_digest(hasher: Sha256) -> String {
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 첫 프레임의 LAME 태그에서 인코더 정보를 읽는다. LAME 태그가 없으면 None.
+pub fn encoder_info(path: &Path) -> Option<EncoderInfo> {
+    let mut file = File::open(path).ok()?;
+    let (_, buf) = read_audio_head(&mut file)?;
+    let (offset, header) = first_frame(&buf)?;
+    lame_info(&buf[offset..], &header)
+}
+
+/// ID3v2 태그 뒤 오디오 데이터의 시작 위치와 앞부분(최대 `SCAN_LIMIT`바이트)을 읽는다.
+fn read_audio_head(file: &mut File) -> Option<(u64, Vec<u8>)> {
+    let audio_start = id3v2_len(file)?;
+    file.seek(SeekFrom::Start(audio_start)).ok()?;
+    let mut buf = Vec::with_capacity(SCAN_LIMIT);
+    file.by_ref()
+        .take(SCAN_LIMIT as u64)
+        .read_to_end(&mut buf)
+        .ok()?;
+    Some((audio_start, buf))
+}
+
+/// 파일 앞의 ID3v2 태그 길이(헤더/푸터 포함)를 반환한다. 태그가 없으면 0.
+pub fn id3v2_len(file: &mut File) -> Option<u64> {
+    let mut header = [0u8; 10];
+    if file.read_exact(&mut header).is_err() || &header[..3] != b"ID3" {
+        return Some(0);
+    }
+    let size = header[6..10]
+        .iter()
+        .fold(0u64, |acc, &b| (acc << 7) | (b & 0x7F) as u64);
+    let footer = if header[5] & 0x10 != 0 { 10 } else { 0 };
+    Some(10 + size + footer)
+}
+
+/// 파일 끝에 ID3v1 태그(128바이트 "TAG")가 있는지 확인한다.
+pub fn has_id3v1(file: &mut File, file_len: u64) -> bool {
+    if file_len < 128 || file.seek(SeekFrom::Start(file_len - 128)).is_err() {
+        return false;
+    }
+    let mut marker = [0u8; 3];
+    file.read_exact(&mut marker).is_ok() && &marker == b"TAG"
+}
+
+/// 오디오 데이터 앞부분(`buf`)과 전체 오디오 길이로 재생 시간을 계산한다.
+fn duration_from_frames(buf: &[u8], audio_len: u64) -> Option<u64> {
+    let (offset, header) = first_frame(buf)?;
+
+    let frame = &buf[offset..];
+    if let Some(frames) = xing_frames(frame, &header).or_else(|| vbri_frames(frame)) {
+        return Some(frames * header.samples_per_frame() * 1000 / header.sample_rate as u64);
+    }
+
+    let bytes = audio_len.saturating_sub(offset as u64);
+    Some(bytes * 8 / header.bitrate_kbps as u64)
+}
+
+/// 첫 번째 프레임 헤더의 위치와 내용.
+fn first_frame(buf: &[u8]) -> Option<(usize, FrameHeader)> {
+    (0..buf.len().saturating_sub(4)).find_map(|i| FrameHeader::parse(&buf[i..]).map(|h| (i, h)))
+}
+
+/// Xing/Info 헤더의 전체 프레임 수.
+fn xing_frames(frame: &[u8], header: &FrameHeader) -> Option<u64> {
+    let pos = 4 + header.side_info_len();
+    let tag = frame.get(pos..pos + 4)?;
+    if tag != b"Xing" && tag != b"Info" {
+        return None;
+    }
+    let flags = read_u32(frame, pos + 4)?;
+    if flags & 0x01 == 0 {
+        return None;
+    }
+    read_u32(frame, pos + 8).map(u64::from)
+}
+
+/// Xing/Info 헤더 뒤에 붙는 LAME 태그(36바이트)를 해석한다.
+/// FFmpeg(Lavc/Lavf)도 같은 구조로 기록한다.
+fn lame_info(frame: &[u8], header: &FrameHeader) -> Option<EncoderInfo> {
+    let pos = 4 + header.side_info_len();
+    let tag = frame.get(pos..pos + 4)?;
+    if tag != b"Xing" && tag != b"Info" {
+        return None;
+    }
+    // 플래그에 따라 프레임 수, 바이트 수, TOC(100바이트), 품질 필드가 이어진다
+    let flags = read_u32(frame, pos + 4)?;
+    let lame = pos
+        + 8
+        + [(0x01, 4), (0x02, 4), (0x04, 100), (0x08, 4)]
+            .iter()
+            .filter(|(flag, _)| flags & flag != 0)
+            .map(|(_, len)| len)
+            .sum::<usize>();
+    let tag = frame.get(lame..lame + 36)?;
+    if !tag.starts_with(b"LAME") && !tag.starts_with(b"Lav") {
+        return None;
+    }
+
+    let encoder = String::from_utf8_lossy(&tag[..9])
+        .trim_end_matches(['\0', ' '])
+        .to_string();
+    let mode = match tag[9] & 0x0F {
+        1 | 8 => Some("CBR"),
+        2 | 9 => Some("ABR"),
+        3..=6 => Some("VBR"),
+        _ => None,
+    };
+    let preset = match ((tag[26] as u16 & 0x07) << 8) | tag[27] as u16 {
+        0 => None,
+        n @ 8..=320 => Some(format!("{}kbps", n)),
+        n @ 410..=500 if n % 10 == 0 => Some(format!("V{}", (500 - n) / 10)),
+        1000 => Some("r3mix".to_string()),
+        1001 => Some("standard".to_string()),
+        1002 => Some("extreme".to_string()),
+        1003 => Some("insane".to_string()),
+        1004 => Some("fast standard".to_string()),
+        1005 => Some("fast extreme".to_string()),
+        1006 => Some("medium".to_string()),
+        1007 => Some("fast medium".to_string()),
+        _ => None,
+    };
+    Some(EncoderInfo {
+        encoder,
+        mode: mode.map(str::to_string),
+        preset,
+        delay: ((tag[21] as u16) << 4) | (tag[22] as u16 >> 4),
+        padding: ((tag[22] as u16 & 0x0F) << 8) | tag[23] as u16,
+    })
+}
+
+/// VBRI(Fraunhofer) 헤더의 전체 프레임 수. 항상 프레임 헤더 뒤 32바이트에 있다.
+fn vbri_frames(frame: &[u8]) -> Option<u64> {
+    if frame.get(36..40)? != b"VBRI" {
+        return None;
+    }
+    read_u32(frame, 36 + 14).map(u64::from)
+}
+
+/// 오디오 데이터의 손상 유형.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Corruption {
+    /// 태그 뒤에서 MP3 프레임을 찾지 못함
+    NoFrames,
+    /// 태그와 첫 프레임 사이에 프레임이 아닌 바이트가 있음
+    JunkBeforeAudio(u64),
+    /// 프레임이 이어지지 않고 끊김 (파일 내 오프셋)
+    LostSync(u64),
+    /// 마지막 프레임이 잘림 (모자란 바이트 수)
+    Truncated(u64),
+}
+
+impl fmt::Display for Corruption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Corruption::NoFrames => write!(f, "오디오 프레임 없음"),
+            Corruption::JunkBeforeAudio(n) => write!(f, "첫 프레임 앞 불필요한 데이터 {}바이트", n),
+            Corruption::LostSync(offset) => write!(f, "오프셋 {}에서 프레임 동기화 끊김", offset),
+            Corruption::Truncated(n) => write!(f, "마지막 프레임 {}바이트 잘림", n),
+        }
+    }
+}
+
+/// 파일 전체의 프레임 동기를 따라가며 손상을 찾는다. 문제가 없으면 빈 목록.
+/// 파일 끝의 ID3v1, APEv2, Lyrics3 태그는 오디오 데이터로 보지 않는다.
+pub fn verify(path: &Path) -> std::io::Result<Vec<Corruption>> {
+    let mut file = File::open(path)?;
+    let file_len = file.metadata()?.len();
+    let audio_start = id3v2_len(&mut file).unwrap_or(0).min(file_len);
+    let mut audio_end = file_len;
+    if has_id3v1(&mut file, file_len) {
+        audio_end -= 128;
+    }
+
+    file.seek(SeekFrom::Start(audio_start))?;
+    let mut data = Vec::new();
+    file.take(audio_end.saturating_sub(audio_start))
+        .read_to_end(&mut data)?;
+    Ok(verify_frames(&data)
+        .into_iter()
+        .map(|c| match c {
+            Corruption::LostSync(offset) => Corruption::LostSync(audio_start + offset),
+            c => c,
+        })
+        .collect())
+}
+
+/// 오디오 데이터(`data`, ID3 태그 제외)의 프레임을 처음부터 끝까지 따라간다.
+fn verify_frames(data: &[u8]) -> Vec<Corruption> {
+    let limit = data.len().min(SCAN_LIMIT).saturating_sub(3);
+    let Some(first) = (0..limit).find(|&i| FrameHeader::parse(&data[i..]).is_some()) else {
+        return vec![Corruption::NoFrames];
+    };
+
+    let mut problems = Vec::new();
+    if first > 0 {
+        problems.push(Corruption::JunkBeforeAudio(first as u64));
+    }
+    let mut pos = first;
+    while pos < data.len() {
+        let rest = &data[pos..];
+        let Some(header) = FrameHeader::parse(rest) else {
+            if !is_trailing_tag(rest) {
+                problems.push(Corruption::LostSync(pos as u64));
+            }
+            break;
+        };
+        let len = header.frame_len() as usize;
+        if len > rest.len() {
+            problems.push(Corruption::Truncated((len - rest.len()) as u64));
+            break;
+        }
+        pos += len;
+    }
+    problems
+}
+
+/// 오디오 뒤에 붙는 APEv2/Lyrics3 태그인지 확인한다.
+fn is_trailing_tag(rest: &[u8]) -> bool {
+    rest.starts_with(b"APETAGEX") || rest.starts_with(b"LYRICSBEGIN")
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Option<u32> {
+    let bytes: [u8; 4] = buf.get(pos..pos + 4)?.try_into().ok()?;
+    Some(u32::from_be_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    /// MPEG1 Layer III, 128kbps, 44.1kHz, 스테레오 프레임 헤더.
+    const HEADER_128K: [u8; 4] = [0xFF, 0xFB, 0x90, 0x00];
+
+    #[test]
+    fn test_parse_frame_header() {
+        let header = FrameHeader::parse(&HEADER_128K).unwrap();
+        assert_eq!(
+            header,
+            FrameHeader {
+                mpeg1: true,
+                mono: false,
+                bitrate_kbps: 128,
+                sample_rate: 44100,
+                padding: false,
+            }
+        );
+        assert!(FrameHeader::parse(&[0xFF, 0xFB, 0xF0, 0x00]).is_none());
+        assert!(FrameHeader::parse(b"ID3\x04").is_none());
+    }
+
+    #[test]
+    fn test_cbr_duration() {
+        let mut buf = vec![0u8; 3];
+        buf.extend_from_slice(&HEADER_128K);
+        buf.resize(1024, 0);
+        // 128kbps로 16000바이트 = 1초
+        assert_eq!(duration_from_frames(&buf, 16_003), Some(1000));
+    }
+
+    #[test]
+    fn test_xing_duration() {
+        let mut buf = HEADER_128K.to_vec();
+        buf.resize(36, 0);
+        buf.extend_from_slice(b"Xing");
+        buf.extend_from_slice(&1u32.to_be_bytes());
+        buf.extend_from_slice(&1000u32.to_be_bytes());
+        buf.resize(1024, 0);
+        // 1000 프레임 × 1152 샘플 / 44100Hz
+        assert_eq!(duration_from_frames(&buf, 1_000_000), Some(26_122));
+    }
+
+    /// 128kbps 44.1kHz 프레임 `count`개 (패딩 없음, 417바이트씩).
+    fn frames(count: usize) -> Vec<u8> {
+        let mut frame = HEADER_128K.to_vec();
+        frame.resize(417, 0);
+        frame.repeat(count)
+    }
+
+    #[test]
+    fn test_verify_frames() {
+        assert!(verify_frames(&frames(3)).is_empty());
+        assert!(verify_frames(&[frames(2), b"APETAGEX".to_vec()].concat()).is_empty());
+        assert_eq!(verify_frames(&[0u8; 100]), vec![Corruption::NoFrames]);
+
+        let junk = [b"JUNK".to_vec(), frames(2)].concat();
+        assert_eq!(verify_frames(&junk), vec![Corruption::JunkBeforeAudio(4)]);
+
+        let mut truncated = frames(2);
+        truncated.truncate(417 + 100);
+        assert_eq!(verify_frames(&truncated), vec![Corruption::Truncated(317)]);
+
+        let broken = [frames(1), vec![0u8; 417], frames(1)].concat();
+        assert_eq!(verify_frames(&broken), vec![Corruption::LostSync(417)]);
+    }
+
+    /// Xing(프레임 수만) + LAME 태그가 든 첫 프레임. V0, 지연 576, 패딩 1200.
+    fn lame_frame() -> Vec<u8> {
+        let mut frame = HEADER_128K.to_vec();
+        frame.resize(36, 0);
+        frame.extend_from_slice(b"Xing");
+        frame.extend_from_slice(&1u32.to_be_bytes());
+        frame.extend_from_slice(&100u32.to_be_bytes());
+        let mut lame = b"LAME3.100".to_vec();
+        lame.push(0x14); // 태그 버전 1, VBR(rh)
+        lame.resize(21, 0);
+        lame.extend_from_slice(&[0x24, 0x04, 0xB0]); // 576, 1200
+        lame.resize(26, 0);
+        lame.extend_from_slice(&500u16.to_be_bytes());
+        lame.resize(36, 0);
+        frame.extend_from_slice(&lame);
+        frame.resize(417, 0);
+        frame
+    }
+
+    #[test]
+    fn test_lame_info() {
+        let frame = lame_frame();
+        let header = FrameHeader::parse(&frame).unwrap();
+        let info = lame_info(&frame, &header).unwrap();
+        assert_eq!(
+            info,
+            EncoderInfo {
+                encoder: "LAME3.100".to_string(),
+                mode: Some("VBR".to_string()),
+                preset: Some("V0".to_string()),
+                delay: 576,
+                padding: 1200,
+            }
+        );
+        assert_eq!(info.summary(), "LAME3.100 VBR V0");
+        assert!(lame_info(&frames(1), &header).is_none());
+    }
+
+    #[test]
+    fn test_tag_rewrite_keeps_lame_info() {
+        use crate::config::WriteConfig;
+        use crate::core::tagger;
+        use crate::models::TrackInfo;
+
+        let dir = TempDir::new("audio-lame");
+        let audio = [lame_frame(), frames(3)].concat();
+        let info = TrackInfo {
+            title: Some("Blueming".to_string()),
+            ..Default::default()
+        };
+        for atomic in [true, false] {
+            let path = dir.join(format!("atomic-{}.mp3", atomic));
+            std::fs::write(&path, &audio).unwrap();
+            let config = WriteConfig {
+                atomic,
+                ..Default::default()
+            };
+            let before = encoder_info(&path);
+            let hash = content_hash(&path).unwrap();
+            let untagged = tag_hash(&path).unwrap();
+            tagger::write_tags(&path, &info, &config).unwrap();
+            let tagged = tag_hash(&path).unwrap();
+            assert_ne!(tagged, untagged);
+            // 두 번째 기록은 기존 태그를 고쳐 쓴다
+            tagger::write_tags(&path, &info, &config).unwrap();
+
+            let data = std::fs::read(&path).unwrap();
+            let tag_len = id3v2_len(&mut File::open(&path).unwrap()).unwrap() as usize;
+            assert!(tag_len > 0);
+            assert_eq!(&data[tag_len..], &audio[..]);
+            assert_eq!(encoder_info(&path), before);
+            // 태그가 바뀌어도 오디오 내용 해시는 같다
+            assert_eq!(content_hash(&path).unwrap(), hash);
+            assert!(verify(&path).unwrap().is_empty());
+
+            let retitled = TrackInfo {
+                title: Some("Blueming (Inst.)".to_string()),
+                ..Default::default()
+            };
+            tagger::write_tags(&path, &retitled, &config).unwrap();
+            assert_ne!(tag_hash(&path).unwrap(), tagged);
+            assert_eq!(content_hash(&path).unwrap(), hash);
+        }
+    }
+
+    #[test]
+    fn test_duration_skips_id3v2_tag() {
+        let dir = TempDir::new("audio-id3");
+        let path = dir.join("song.mp3");
+        let mut data = b"ID3\x04\x00\x00\x00\x00\x01\x00".to_vec(); // 태그 크기 128
+        data.resize(10 + 128, 0);
+        data.extend_from_slice(&HEADER_128K);
+        data.resize(10 + 128 + 32_000, 0);
+        std::fs::write(&path, &data).unwrap();
+
+        assert_eq!(duration_ms(&path), Some(2000));
+    }
+}
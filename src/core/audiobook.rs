@@ -0,0 +1,170 @@
+use std::cmp::Ordering;
+use std::path::Path;
+
+use crate::core::renamer;
+use crate::models::Mp3File;
+
+/// 오디오북 파일에 기록하는 장르.
+pub const GENRE: &str = "Audiobook";
+
+/// 숫자 부분은 수로 비교하는 문자열 순서 ("Chapter 2" < "Chapter 10").
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(x), Some(y)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        let ordering = if x.is_ascii_digit() && y.is_ascii_digit() {
+            let (x_digits, x_rest) = split_digits(a);
+            let (y_digits, y_rest) = split_digits(b);
+            a = x_rest;
+            b = y_rest;
+            let (x_digits, y_digits) = (
+                x_digits.trim_start_matches('0'),
+                y_digits.trim_start_matches('0'),
+            );
+            x_digits
+                .len()
+                .cmp(&y_digits.len())
+                .then_with(|| x_digits.cmp(y_digits))
+        } else {
+            a = &a[x.len_utf8()..];
+            b = &b[y.len_utf8()..];
+            x.to_lowercase().cmp(y.to_lowercase())
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+/// 파일을 재생 순서(경로의 자연 순서)로 정렬한다. "CD1/…", "CD2/…" 같은 하위 폴더도 차례로 놓인다.
+pub fn sort_chapters(files: &mut [Mp3File]) {
+    files.sort_by(|a, b| natural_cmp(&a.path.to_string_lossy(), &b.path.to_string_lossy()));
+}
+
+/// "저자 - 제목" 형식의 폴더 이름을 (저자, 제목)으로 나눈다. 구분자가 없으면 이름 전체가 제목.
+pub fn split_folder_name(name: &str) -> (Option<String>, String) {
+    match name.split_once(" - ") {
+        Some((author, title)) if !author.trim().is_empty() && !title.trim().is_empty() => {
+            (Some(author.trim().to_string()), title.trim().to_string())
+        }
+        _ => (None, name.trim().to_string()),
+    }
+}
+
+/// 챕터 제목. 책 제목과 다른 제목 태그가 있으면 그대로 쓰고, 없으면 파일명에서 앞의 번호를 뗀 이름,
+/// 그것도 비면 "Chapter N".
+pub fn chapter_title(file: &Mp3File, book_title: &str, number: usize) -> String {
+    let tagged = file
+        .current_tags
+        .as_ref()
+        .and_then(|t| t.title.as_deref())
+        .map(str::trim)
+        .filter(|t| !t.is_empty() && *t != book_title);
+    if let Some(title) = tagged {
+        return title.to_string();
+    }
+    let stem = file.path.file_stem().unwrap_or_default().to_string_lossy();
+    let stripped = stem.trim_start_matches(|c: char| c.is_ascii_digit() || " -_.".contains(c));
+    if stripped.is_empty() || stripped == book_title {
+        format!("Chapter {}", number)
+    } else {
+        stripped.to_string()
+    }
+}
+
+/// 셔플이나 이름순 정렬에도 순서가 유지되는 파일명 ("007 - 제목.mp3"). 번호 자릿수는 전체 챕터 수에 맞춘다.
+pub fn chapter_file_name(number: usize, total: usize, title: &str) -> String {
+    let width = total.to_string().len().max(2);
+    format!(
+        "{:0width$} - {}.mp3",
+        number,
+        renamer::sanitize_field(title),
+        width = width
+    )
+}
+
+/// 폴더 이름 (경로가 `.`처럼 이름이 없으면 현재 디렉토리 이름).
+pub fn folder_name(dir: &Path) -> String {
+    let name = |path: &Path| path.file_name().map(|n| n.to_string_lossy().into_owned());
+    name(dir)
+        .or_else(|| std::fs::canonicalize(dir).ok().as_deref().and_then(name))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TrackInfo;
+    use std::path::PathBuf;
+
+    fn file(path: &str, title: Option<&str>) -> Mp3File {
+        Mp3File {
+            path: PathBuf::from(path),
+            current_tags: title.map(|t| TrackInfo {
+                title: Some(t.to_string()),
+                ..Default::default()
+            }),
+            has_tags: title.is_some(),
+            encoder: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_chapters() {
+        let mut files = vec![
+            file("book/CD2/01.mp3", None),
+            file("book/CD1/Chapter 10.mp3", None),
+            file("book/CD1/Chapter 2.mp3", None),
+            file("book/CD1/chapter 02b.mp3", None),
+        ];
+        sort_chapters(&mut files);
+        let paths: Vec<_> = files.iter().map(|f| f.path.to_str().unwrap()).collect();
+        assert_eq!(
+            paths,
+            [
+                "book/CD1/Chapter 2.mp3",
+                "book/CD1/chapter 02b.mp3",
+                "book/CD1/Chapter 10.mp3",
+                "book/CD2/01.mp3"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chapter_title() {
+        assert_eq!(
+            chapter_title(&file("b/03 - 서문.mp3", None), "책", 3),
+            "서문"
+        );
+        assert_eq!(chapter_title(&file("b/03.mp3", None), "책", 3), "Chapter 3");
+        assert_eq!(
+            chapter_title(&file("b/03.mp3", Some("1장")), "책", 3),
+            "1장"
+        );
+        assert_eq!(
+            chapter_title(&file("b/03.mp3", Some("책")), "책", 3),
+            "Chapter 3"
+        );
+        assert_eq!(chapter_file_name(7, 120, "1장 시작"), "007 - 1장 시작.mp3");
+        assert_eq!(chapter_file_name(7, 9, "Intro"), "07 - Intro.mp3");
+    }
+
+    #[test]
+    fn test_split_folder_name() {
+        assert_eq!(
+            split_folder_name("한강 - 채식주의자"),
+            (Some("한강".to_string()), "채식주의자".to_string())
+        );
+        assert_eq!(
+            split_folder_name("채식주의자"),
+            (None, "채식주의자".to_string())
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::config::{BackupMode, WriteConfig};
+use crate::core::{audio, tagger};
+
+/// 태그만 백업한 파일의 확장자.
+const TAGS_EXT: &str = "id3";
+/// 파일 전체 복사본의 확장자. 백업 디렉토리가 스캔 범위 안에 있어도 MP3로 잡히지 않게 한다.
+const FILE_EXT: &str = "bak";
+
+/// 기록 전에 원본을 `<백업 디렉토리>/<날짜>/<시각>-<경로 해시>-<파일명>.bak`으로 복사한다.
+/// `BackupMode::Tags`이면 파일 앞의 ID3v2 태그 바이트만 `.id3` 파일로 저장한다.
+/// 백업하지 않는 설정이면 None.
+pub fn backup(path: &Path, config: &WriteConfig) -> Result<Option<PathBuf>> {
+    let name = match config.backup {
+        BackupMode::Off => return Ok(None),
+        BackupMode::File => format!("{}.{}", file_name(path)?, FILE_EXT),
+        BackupMode::Tags => format!("{}.{}", file_name(path)?, TAGS_EXT),
+    };
+    let (year, month, day, hour, minute, second) = tagger::utc_date_time(SystemTime::now());
+    let dir = config
+        .backup_dir()
+        .join(format!("{:04}-{:02}-{:02}", year, month, day));
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("백업 디렉토리를 만들 수 없습니다: {}", dir.display()))?;
+
+    let key = path_key(path)?;
+    let time = format!("{:02}{:02}{:02}", hour, minute, second);
+    // 같은 초에 같은 파일을 여러 번 백업하면 "<시각>.2", "<시각>.3"처럼 번호를 붙인다
+    let marker = format!("-{}-", key);
+    let earlier = std::fs::read_dir(&dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|n| n.starts_with(&time) && n.contains(&marker))
+        .count();
+    let target = match earlier {
+        0 => dir.join(format!("{}-{}-{}", time, key, name)),
+        n => dir.join(format!("{}.{}-{}-{}", time, n + 1, key, name)),
+    };
+
+    let result = match config.backup {
+        BackupMode::Tags => std::fs::write(&target, read_id3v2(path)?),
+        _ => std::fs::copy(path, &target).map(|_| ()),
+    };
+    result.with_context(|| format!("백업하지 못했습니다: {}", target.display()))?;
+    Ok(Some(target))
+}
+
+/// 파일의 백업 목록을 최신순으로 반환한다.
+pub fn list(path: &Path, config: &WriteConfig) -> Result<Vec<PathBuf>> {
+    let marker = format!("-{}-", path_key(path)?);
+    let Ok(dates) = std::fs::read_dir(config.backup_dir()) else {
+        return Ok(Vec::new());
+    };
+    let mut backups: Vec<PathBuf> = dates
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| std::fs::read_dir(e.path()).ok())
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|n| n.to_string_lossy().contains(&marker))
+        })
+        .collect();
+    backups.sort_by_key(|p| std::cmp::Reverse(order(p)));
+    Ok(backups)
+}
+
+/// 백업으로 파일을 되돌린다. 태그 백업이면 현재 오디오 데이터 앞의 ID3v2 태그만 바꾼다.
+/// 임시 파일에 쓴 뒤 원본 위로 rename하므로 도중에 실패해도 원본은 그대로다.
+pub fn restore(path: &Path, backup: &Path) -> Result<()> {
+    let data = std::fs::read(backup)
+        .with_context(|| format!("백업을 읽을 수 없습니다: {}", backup.display()))?;
+    let content = if backup.extension().is_some_and(|e| e == TAGS_EXT) {
+        if !path.exists() {
+            bail!(
+                "태그 백업은 원본 파일이 있어야 되돌릴 수 있습니다: {}",
+                path.display()
+            );
+        }
+        let mut file = File::open(path)?;
+        let tag_len = audio::id3v2_len(&mut file).unwrap_or(0);
+        let mut audio_data = Vec::new();
+        File::open(path)?.read_to_end(&mut audio_data)?;
+        let start = (tag_len as usize).min(audio_data.len());
+        [data.as_slice(), &audio_data[start..]].concat()
+    } else {
+        data
+    };
+
+    let tmp = tagger::temp_path(path);
+    let result = std::fs::write(&tmp, &content).and_then(|_| std::fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result.with_context(|| format!("되돌리지 못했습니다: {}", path.display()))
+}
+
+/// 백업 파일의 (날짜, 시각, 같은 초 안의 번호).
+fn order(backup: &Path) -> (String, String, u32) {
+    let date = backup
+        .parent()
+        .and_then(|d| d.file_name())
+        .map(|d| d.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = backup
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stamp = name.split('-').next().unwrap_or_default();
+    let (time, n) = match stamp.split_once('.') {
+        Some((time, n)) => (time, n.parse().unwrap_or(1)),
+        None => (stamp, 1),
+    };
+    (date, time.to_string(), n)
+}
+
+/// 파일 앞의 ID3v2 태그 바이트. 태그가 없으면 빈 값.
+fn read_id3v2(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let len = audio::id3v2_len(&mut file).unwrap_or(0);
+    let mut tag = Vec::new();
+    File::open(path)?.take(len).read_to_end(&mut tag)?;
+    Ok(tag)
+}
+
+fn file_name(path: &Path) -> Result<String> {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .with_context(|| format!("파일 이름이 없습니다: {}", path.display()))
+}
+
+/// 같은 이름의 다른 파일과 구분하기 위한 절대 경로의 해시 (16자리).
+fn path_key(path: &Path) -> Result<String> {
+    let absolute = std::path::absolute(path)?;
+    let digest = Sha256::digest(absolute.to_string_lossy().as_bytes());
+    Ok(digest[..8].iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn setup(name: &str, mode: BackupMode) -> (TempDir, PathBuf, WriteConfig) {
+        let dir = TempDir::new(&format!("backup-{}", name));
+        let path = dir.join("song.mp3");
+        // 태그 크기 4바이트짜리 ID3v2 헤더 + 태그 + 오디오
+        let mut data = b"ID3\x04\x00\x00\x00\x00\x00\x04".to_vec();
+        data.extend_from_slice(b"OLD!AUDIO-DATA");
+        std::fs::write(&path, data).unwrap();
+        let config = WriteConfig {
+            backup: mode,
+            backup_dir: Some(dir.join("backup")),
+            ..Default::default()
+        };
+        (dir, path, config)
+    }
+
+    #[test]
+    fn test_restore_tags_backup_keeps_audio() {
+        let (_dir, path, config) = setup("tags", BackupMode::Tags);
+        let original = std::fs::read(&path).unwrap();
+        let saved = backup(&path, &config).unwrap().unwrap();
+        assert_eq!(std::fs::read(&saved).unwrap(), &original[..14]);
+
+        // 태그가 커지고 오디오도 바뀐 파일에 태그만 되돌린다
+        let mut changed = b"ID3\x04\x00\x00\x00\x00\x00\x08".to_vec();
+        changed.extend_from_slice(b"NEW-TAG!AUDIO-EDIT");
+        std::fs::write(&path, changed).unwrap();
+        restore(&path, &saved).unwrap();
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            b"ID3\x04\x00\x00\x00\x00\x00\x04OLD!AUDIO-EDIT"
+        );
+    }
+
+    #[test]
+    fn test_file_backup_list_and_restore() {
+        let (_dir, path, config) = setup("file", BackupMode::File);
+        let original = std::fs::read(&path).unwrap();
+        let first = backup(&path, &config).unwrap().unwrap();
+        std::fs::write(&path, b"MODIFIED").unwrap();
+        let tags_config = WriteConfig {
+            backup: BackupMode::Tags,
+            ..config.clone()
+        };
+        let second = backup(&path, &tags_config).unwrap().unwrap();
+        let third = backup(&path, &config).unwrap().unwrap();
+
+        assert_eq!(
+            list(&path, &config).unwrap(),
+            vec![third, second, first.clone()]
+        );
+        assert!(list(&path.with_file_name("other.mp3"), &config)
+            .unwrap()
+            .is_empty());
+
+        restore(&path, &first).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_backup_off() {
+        let (_dir, path, config) = setup("off", BackupMode::Off);
+        assert_eq!(backup(&path, &config).unwrap(), None);
+    }
+}
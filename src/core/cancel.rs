@@ -0,0 +1,96 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Once, OnceLock};
+
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+static INSTALL: Once = Once::new();
+/// 핸들러 안에서는 언어를 고르지 않도록 설치할 때 정해 둔다.
+static NOTICE: OnceLock<&str> = OnceLock::new();
+
+/// Ctrl-C 핸들러를 설치한다. 일괄 작업을 시작할 때 부른다 (여러 번 불러도 한 번만 설치).
+///
+/// 처음 누르면 중단 요청만 기록해 지금 기록 중인 파일은 끝까지 쓰고 다음 파일 전에 멈춘다.
+/// 한 번 더 누르면 그 자리에서 종료한다 (종료 코드 130).
+pub fn install() {
+    INSTALL.call_once(|| {
+        let notice = crate::i18n::pick(
+            "\n중단 요청: 지금 파일까지 기록하고 멈춥니다 (한 번 더 누르면 즉시 종료)\n",
+            "\nInterrupt: stopping after the current file (press again to quit immediately)\n",
+        );
+        let _ = NOTICE.set(notice);
+        platform::install();
+    });
+}
+
+/// Ctrl-C로 중단을 요청받았는지. 일괄 작업은 파일마다 이 값을 보고 다음 파일 전에 멈춘다.
+pub fn requested() -> bool {
+    REQUESTED.load(Ordering::SeqCst)
+}
+
+/// 중단 요청을 기록한다. 이미 요청된 상태였으면 true.
+fn request() -> bool {
+    REQUESTED.swap(true, Ordering::SeqCst)
+}
+
+fn notice() -> &'static str {
+    NOTICE.get().copied().unwrap_or_default()
+}
+
+#[cfg(unix)]
+mod platform {
+    extern "C" fn on_sigint(_: libc::c_int) {
+        if super::request() {
+            // SAFETY: _exit는 시그널 핸들러에서 불러도 안전하다
+            unsafe { libc::_exit(130) };
+        }
+        let notice = super::notice();
+        // SAFETY: write는 시그널 핸들러에서 불러도 안전하다
+        unsafe { libc::write(libc::STDERR_FILENO, notice.as_ptr().cast(), notice.len()) };
+    }
+
+    pub fn install() {
+        // SAFETY: 핸들러는 원자 변수와 async-signal-safe 함수만 쓴다
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = on_sigint as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            // 중단 중인 read/write가 EINTR로 실패하지 않도록 다시 시작한다
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(libc::SIGINT, &action, std::ptr::null_mut());
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    const CTRL_C_EVENT: u32 = 0;
+    const CTRL_BREAK_EVENT: u32 = 1;
+
+    extern "system" {
+        fn SetConsoleCtrlHandler(
+            handler: Option<unsafe extern "system" fn(u32) -> i32>,
+            add: i32,
+        ) -> i32;
+    }
+
+    // 콘솔 핸들러는 별도 스레드에서 불리므로 일반 출력과 종료를 써도 된다
+    unsafe extern "system" fn on_ctrl(event: u32) -> i32 {
+        if event != CTRL_C_EVENT && event != CTRL_BREAK_EVENT {
+            return 0;
+        }
+        if super::request() {
+            std::process::exit(130);
+        }
+        eprint!("{}", super::notice());
+        1
+    }
+
+    pub fn install() {
+        // SAFETY: 핸들러는 원자 변수와 표준 오류 출력만 쓴다
+        unsafe { SetConsoleCtrlHandler(Some(on_ctrl), 1) };
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod platform {
+    pub fn install() {}
+}
@@ -0,0 +1,171 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::core::matcher;
+use crate::models::{Mp3File, TrackInfo};
+
+/// 여러 아티스트 모음 앨범에 쓰는 앨범 아티스트 이름 (정규화한 값).
+const VARIOUS_ARTISTS: &[&str] = &[
+    "variousartists",
+    "various",
+    "va",
+    "여러아티스트",
+    "여러가수",
+    "다양한아티스트",
+    "verschiedeneinterpreten",
+    "artistesdivers",
+];
+
+/// 앨범 아티스트가 "Various Artists" 류인지.
+pub fn is_various(album_artist: Option<&str>) -> bool {
+    album_artist.is_some_and(|a| VARIOUS_ARTISTS.contains(&matcher::normalize(a).as_str()))
+}
+
+/// 폴더 파일들에 태그된 모음 앨범 이름 (정규화). 앨범 아티스트가 "Various Artists" 류이거나
+/// 아티스트가 다른 파일끼리 같은 앨범이면 모음 앨범으로 본다. 이미 원래 앨범으로 태그된 파일의 앨범은 넣지 않는다.
+pub fn compilation_albums(files: &[Mp3File]) -> Vec<String> {
+    let mut albums: BTreeMap<String, (bool, BTreeSet<String>)> = BTreeMap::new();
+    for tags in files.iter().filter_map(|f| f.current_tags.as_ref()) {
+        let Some(album) = tags.album.as_deref().map(matcher::normalize) else {
+            continue;
+        };
+        if album.is_empty() {
+            continue;
+        }
+        let entry = albums.entry(album).or_default();
+        entry.0 |= is_various(tags.album_artist.as_deref());
+        entry
+            .1
+            .extend(tags.artists.iter().map(|a| matcher::normalize(a)));
+    }
+    albums
+        .into_iter()
+        .filter(|(_, (various, names))| *various || names.len() > 1)
+        .map(|(album, _)| album)
+        .collect()
+}
+
+/// 검색 결과가 원래 앨범이 아니라 모음 앨범인지 확인한다. 앨범 아티스트가 "Various Artists" 류이거나
+/// 앨범 이름이 정리하려는 폴더의 모음 앨범(`compilation_albums`)과 같으면 모음 앨범으로 본다.
+pub fn is_compilation(track: &TrackInfo, compilation_albums: &[String]) -> bool {
+    is_various(track.album_artist.as_deref())
+        || track
+            .album
+            .as_deref()
+            .is_some_and(|a| compilation_albums.contains(&matcher::normalize(a)))
+}
+
+/// 파일의 현재 태그에서 앨범 관련 필드(앨범, 앨범 아티스트, 트랙/디스크 번호, 연도, 발매일, 음반사,
+/// 앨범 아트)를 원래 앨범의 값으로 바꾼다. 제목, 아티스트, 가사 등 곡 자체의 값은 그대로 두고 비어 있을 때만 채운다.
+pub fn reassign(current: Option<&TrackInfo>, original: &TrackInfo) -> TrackInfo {
+    let current = current.cloned().unwrap_or_default();
+    TrackInfo {
+        title: current.title.or_else(|| original.title.clone()),
+        artists: if current.artists.is_empty() {
+            original.artists.clone()
+        } else {
+            current.artists
+        },
+        album: original.album.clone(),
+        album_artist: original
+            .album_artist
+            .clone()
+            .or_else(|| original.artists.first().cloned()),
+        track_number: original.track_number,
+        total_tracks: original.total_tracks,
+        disc_number: original.disc_number,
+        total_discs: original.total_discs,
+        year: original.year,
+        release_date: original.release_date.clone(),
+        original_year: original.original_year,
+        original_date: original.original_date.clone(),
+        label: original.label.clone(),
+        musicbrainz_album_id: original.musicbrainz_album_id.clone(),
+        album_art: original.album_art.clone(),
+        album_art_url: original.album_art_url.clone(),
+        source: original.source.clone(),
+        ..current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(album: &str, album_artist: Option<&str>) -> TrackInfo {
+        TrackInfo {
+            album: Some(album.to_string()),
+            album_artist: album_artist.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_compilation() {
+        let local = vec![matcher::normalize("K-Pop Hits 2019")];
+        assert!(is_compilation(
+            &track("Summer Best", Some("Various Artists")),
+            &local
+        ));
+        assert!(is_compilation(&track("Summer Best", Some("V.A.")), &local));
+        assert!(is_compilation(&track("K-POP HITS 2019", None), &local));
+        assert!(!is_compilation(&track("Love poem", Some("IU")), &local));
+        assert!(!is_compilation(&track("Love poem", None), &local));
+    }
+
+    #[test]
+    fn test_compilation_albums() {
+        let file = |artist: &str, album: &str, album_artist: Option<&str>| Mp3File {
+            path: format!("{}.mp3", artist).into(),
+            current_tags: Some(TrackInfo {
+                artists: vec![artist.to_string()],
+                album: Some(album.to_string()),
+                album_artist: album_artist.map(str::to_string),
+                ..Default::default()
+            }),
+            has_tags: true,
+            encoder: None,
+        };
+        let files = [
+            file("IU", "Hits", None),
+            file("BTS", "Hits", None),
+            file("BTS", "BE", Some("BTS")),
+            file("Zion.T", "OST Best", Some("Various Artists")),
+        ];
+        assert_eq!(compilation_albums(&files), ["hits", "ostbest"]);
+    }
+
+    #[test]
+    fn test_reassign_keeps_song_fields() {
+        let current = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["IU".to_string()],
+            album: Some("K-Pop Hits 2019".to_string()),
+            album_artist: Some("Various Artists".to_string()),
+            track_number: Some(17),
+            lyrics: Some("가사".to_string()),
+            ..Default::default()
+        };
+        let original = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["아이유".to_string()],
+            album: Some("Love poem".to_string()),
+            track_number: Some(4),
+            total_tracks: Some(6),
+            year: Some(2019),
+            album_art: Some(vec![1, 2, 3]),
+            source: "spotify".to_string(),
+            ..Default::default()
+        };
+        let merged = reassign(Some(&current), &original);
+        assert_eq!(merged.title.as_deref(), Some("Blueming"));
+        assert_eq!(merged.artists, ["IU"]);
+        assert_eq!(merged.lyrics.as_deref(), Some("가사"));
+        assert_eq!(merged.album.as_deref(), Some("Love poem"));
+        assert_eq!(merged.album_artist.as_deref(), Some("아이유"));
+        assert_eq!(merged.track_number, Some(4));
+        assert_eq!(merged.total_tracks, Some(6));
+        assert_eq!(merged.year, Some(2019));
+        assert_eq!(merged.album_art, Some(vec![1, 2, 3]));
+        assert_eq!(merged.source, "spotify");
+    }
+}
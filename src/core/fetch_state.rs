@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// `fetch --auto`의 상태 파일 이름. 대상 디렉토리에 생성된다.
+pub const FETCH_STATE_FILE: &str = ".mp3tag-fetch-state";
+/// `auto`의 상태 파일 이름.
+pub const AUTO_STATE_FILE: &str = ".mp3tag-auto-state";
+
+/// 상태 파일을 둘 디렉토리. 대상이 파일이면 상위 디렉토리.
+pub fn state_dir(target: &Path) -> PathBuf {
+    if target.is_dir() {
+        target.to_path_buf()
+    } else {
+        target
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf()
+    }
+}
+
+/// 자동 가져오기(`fetch --auto`, `auto`)의 진행 상태.
+/// 처리가 끝난 파일을 한 줄씩 추가 기록하여, 중단 후 다시 실행하면 이어서 처리한다.
+/// 경로는 대상 디렉토리 기준 상대 경로로 저장하므로 실행 위치가 달라도 유지된다.
+pub struct FetchState {
+    root: PathBuf,
+    state_path: PathBuf,
+    processed: HashSet<PathBuf>,
+}
+
+impl FetchState {
+    /// 대상 경로의 상태 파일을 읽는다. 파일이 없으면 빈 상태로 시작한다.
+    /// 대상이 파일이면 상위 디렉토리에 상태 파일을 둔다.
+    pub fn load(target: &Path, file_name: &str) -> Result<Self> {
+        let mut state = Self::new(target, file_name);
+        if state.state_path.exists() {
+            let content = std::fs::read_to_string(&state.state_path)
+                .context("상태 파일을 읽을 수 없습니다")?;
+            state.processed = content
+                .lines()
+                .filter(|l| !l.is_empty())
+                .map(PathBuf::from)
+                .collect();
+        }
+        Ok(state)
+    }
+
+    /// 기존 상태 파일을 지우고 처음부터 시작한다 (`--restart`).
+    /// 지우지 않으면 다시 시작한 실행이 중단됐을 때 이전 실행의 기록까지 건너뛴다.
+    pub fn restart(target: &Path, file_name: &str) -> Result<Self> {
+        let state = Self::new(target, file_name);
+        if state.state_path.exists() {
+            std::fs::remove_file(&state.state_path).context("상태 파일을 삭제할 수 없습니다")?;
+        }
+        Ok(state)
+    }
+
+    /// 상태 파일을 읽거나 고치지 않고 빈 상태로 시작한다 (`--dry-run`).
+    pub fn new(target: &Path, file_name: &str) -> Self {
+        let root = state_dir(target);
+        Self {
+            state_path: root.join(file_name),
+            root,
+            processed: HashSet::new(),
+        }
+    }
+
+    /// 이전 실행에서 이미 처리된 파일인지 확인한다.
+    pub fn is_done(&self, path: &Path) -> bool {
+        self.processed.contains(&self.key(path))
+    }
+
+    /// 파일을 처리 완료로 표시하고 상태 파일에 즉시 추가 기록한다.
+    pub fn mark_done(&mut self, path: &Path) -> Result<()> {
+        let key = self.key(path);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.state_path)
+            .context("상태 파일을 열 수 없습니다")?;
+        writeln!(file, "{}", key.display())?;
+        file.sync_data()?;
+        self.processed.insert(key);
+        Ok(())
+    }
+
+    /// 모든 파일 처리가 끝났을 때 상태 파일을 삭제한다.
+    pub fn clear(self) -> Result<()> {
+        if self.state_path.exists() {
+            std::fs::remove_file(&self.state_path).context("상태 파일을 삭제할 수 없습니다")?;
+        }
+        Ok(())
+    }
+
+    /// 상태 파일에 저장할 키 (대상 디렉토리 기준 상대 경로).
+    fn key(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.root).unwrap_or(path).to_path_buf()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_resume_after_reload() {
+        let dir = TempDir::new("state-resume");
+        let song = dir.join("a").join("song.mp3");
+
+        let mut state = FetchState::load(&dir, FETCH_STATE_FILE).unwrap();
+        assert!(!state.is_done(&song));
+        state.mark_done(&song).unwrap();
+
+        let reloaded = FetchState::load(&dir, FETCH_STATE_FILE).unwrap();
+        assert!(reloaded.is_done(&song));
+        assert!(!reloaded.is_done(&dir.join("other.mp3")));
+    }
+
+    #[test]
+    fn test_new_ignores_existing_state() {
+        let dir = TempDir::new("state-restart");
+        let song = dir.join("song.mp3");
+        FetchState::load(&dir, FETCH_STATE_FILE)
+            .unwrap()
+            .mark_done(&song)
+            .unwrap();
+
+        assert!(!FetchState::new(&dir, FETCH_STATE_FILE).is_done(&song));
+    }
+
+    #[test]
+    fn test_restart_then_resume() {
+        let dir = TempDir::new("state-restart-resume");
+        let (first, second) = (dir.join("a.mp3"), dir.join("b.mp3"));
+        FetchState::load(&dir, FETCH_STATE_FILE)
+            .unwrap()
+            .mark_done(&first)
+            .unwrap();
+
+        // 다시 시작한 실행이 b만 처리하고 중단된 뒤 이어서 실행하면 a부터 다시 처리한다
+        let mut state = FetchState::restart(&dir, FETCH_STATE_FILE).unwrap();
+        assert!(!state.is_done(&first));
+        state.mark_done(&second).unwrap();
+
+        let resumed = FetchState::load(&dir, FETCH_STATE_FILE).unwrap();
+        assert!(!resumed.is_done(&first));
+        assert!(resumed.is_done(&second));
+    }
+
+    #[test]
+    fn test_clear_removes_state_file() {
+        let dir = TempDir::new("state-clear");
+        let mut state = FetchState::load(&dir, FETCH_STATE_FILE).unwrap();
+        state.mark_done(&dir.join("song.mp3")).unwrap();
+        assert!(dir.join(FETCH_STATE_FILE).exists());
+
+        state.clear().unwrap();
+        assert!(!dir.join(FETCH_STATE_FILE).exists());
+    }
+}
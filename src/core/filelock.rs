@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Result};
+
+/// 다른 프로그램이 파일을 쓰고 있을 때 다시 시도하는 횟수. 시도마다 기다리는 시간이 늘어난다.
+const LOCK_RETRIES: u32 = 5;
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// 스캔한 시점의 파일 크기와 수정 시각. 기록 직전에 비교해 그 사이 다른 프로그램이 바꿨는지 본다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some(Self {
+            len: meta.len(),
+            modified: meta.modified().ok(),
+        })
+    }
+}
+
+/// 이번 실행에서 읽은 파일의 상태. 스캔하지 않은 파일(GUI 드래그, 서버 요청 등)은 비교하지 않는다.
+static SEEN: Mutex<BTreeMap<PathBuf, FileStamp>> = Mutex::new(BTreeMap::new());
+
+fn with_seen<T>(f: impl FnOnce(&mut BTreeMap<PathBuf, FileStamp>) -> T) -> T {
+    f(&mut SEEN.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// 파일의 현재 크기와 수정 시각을 기억한다. 태그를 읽기 전과 기록한 뒤에 부른다.
+pub fn remember(path: &Path) {
+    match FileStamp::read(path) {
+        Some(stamp) => with_seen(|seen| seen.insert(path.to_path_buf(), stamp)),
+        None => with_seen(|seen| seen.remove(path)),
+    };
+}
+
+/// 기억해 둔 뒤로 파일의 크기나 수정 시각이 바뀌었으면 에러. 읽은 태그를 바탕으로 만든 변경을
+/// 다른 프로그램이 바꾼 파일 위에 덮어쓰지 않도록 기록 직전에 확인한다.
+pub fn check_unchanged(path: &Path) -> Result<()> {
+    let Some(seen) = with_seen(|seen| seen.get(path).copied()) else {
+        return Ok(());
+    };
+    if FileStamp::read(path) != Some(seen) {
+        bail!("스캔한 뒤 다른 프로그램이 파일을 바꿨습니다 (크기/수정 시각이 다름). 다시 스캔한 뒤 실행하세요");
+    }
+    Ok(())
+}
+
+/// 기록하는 동안 잡아 두는 파일 잠금. 놓으면 풀린다.
+#[derive(Debug)]
+pub struct FileLock {
+    _file: Option<File>,
+}
+
+/// 다른 프로그램이 쓰고 있지 않은지 확인하고 파일을 잠근다. 사용 중이면 잠시 기다리며 다시 시도하고,
+/// 끝내 풀리지 않으면 에러. 미디어 서버가 같은 폴더를 색인하는 동안 기록이 섞이지 않게 한다.
+///
+/// Unix에서는 `flock` 배타 잠금을 기록이 끝날 때까지 잡는다 (다른 mp3tag 실행과 잠금을 쓰는 프로그램만 막음).
+/// Windows에서는 다른 프로세스가 파일을 열어 두었는지 배타 모드로 열어 보고 바로 닫는다.
+pub fn acquire(path: &Path) -> Result<FileLock> {
+    for attempt in 0..=LOCK_RETRIES {
+        if attempt > 0 {
+            std::thread::sleep(LOCK_RETRY_DELAY * attempt);
+        }
+        match platform::try_lock(path) {
+            Ok(Some(lock)) => return Ok(lock),
+            Ok(None) => continue,
+            // 열 수 없는 파일(없음, 권한)은 실제 기록에서 원래 에러로 보고한다
+            Err(_) => return Ok(FileLock { _file: None }),
+        }
+    }
+    bail!("다른 프로그램이 파일을 사용 중입니다. 미디어 서버가 색인 중이면 끝난 뒤 다시 실행하세요")
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::fs::File;
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+
+    use super::FileLock;
+
+    /// 잠갔으면 Some, 다른 프로세스가 잠가 두었으면 None.
+    pub fn try_lock(path: &Path) -> std::io::Result<Option<FileLock>> {
+        let file = File::open(path)?;
+        // SAFETY: 열린 파일 디스크립터에 대한 flock 호출
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+            return Ok(Some(FileLock { _file: Some(file) }));
+        }
+        let err = std::io::Error::last_os_error();
+        if err.kind() == std::io::ErrorKind::WouldBlock {
+            return Ok(None);
+        }
+        Err(err)
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::fs::OpenOptions;
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::path::Path;
+
+    use super::FileLock;
+
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    const ERROR_LOCK_VIOLATION: i32 = 33;
+
+    /// 다른 프로세스가 열어 두지 않았으면 Some, 사용 중이면 None.
+    /// 잡아 두면 이후 기록도 막히므로 확인만 하고 바로 닫는다.
+    pub fn try_lock(path: &Path) -> std::io::Result<Option<FileLock>> {
+        match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .share_mode(0)
+            .open(path)
+        {
+            Ok(_) => Ok(Some(FileLock { _file: None })),
+            Err(e)
+                if matches!(
+                    e.raw_os_error(),
+                    Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod platform {
+    use std::path::Path;
+
+    use super::FileLock;
+
+    pub fn try_lock(_path: &Path) -> std::io::Result<Option<FileLock>> {
+        Ok(Some(FileLock { _file: None }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_check_unchanged() {
+        let dir = TempDir::new("filelock");
+        let path = dir.join("song.mp3");
+        std::fs::write(&path, b"abc").unwrap();
+        // 읽은 적 없는 파일은 비교하지 않는다
+        std::fs::write(&path, b"abcd").unwrap();
+        assert!(check_unchanged(&path).is_ok());
+
+        remember(&path);
+        assert!(check_unchanged(&path).is_ok());
+        std::fs::write(&path, b"abcde").unwrap();
+        assert!(check_unchanged(&path).is_err());
+        remember(&path);
+        assert!(check_unchanged(&path).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_acquire_conflict() {
+        let dir = TempDir::new("filelock-busy");
+        let path = dir.join("song.mp3");
+        std::fs::write(&path, b"abc").unwrap();
+        let held = acquire(&path).unwrap();
+        // 같은 파일을 따로 열어 잠그면 다른 프로세스처럼 막힌다
+        assert!(platform::try_lock(&path).unwrap().is_none());
+        drop(held);
+        assert!(acquire(&path).is_ok());
+    }
+}
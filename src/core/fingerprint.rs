@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+/// 이 항목 수(약 20초) 이상 같은 위치에서 맞아야 같은 곡으로 본다. 지문 항목 하나는 약 0.124초.
+const WINDOW: usize = 160;
+/// 앞뒤로 밀어 볼 최대 항목 수 (약 30초). 라디오 에디트의 줄인 인트로, 앞뒤 무음 차이를 맞춘다.
+const MAX_OFFSET: usize = 240;
+/// 재생 속도가 조금 다른 판(테이프 전사, PAL 속도 보정 등)을 맞춰 볼 시간 비율.
+const STRETCH: &[f64] = &[1.0, 0.99, 1.01, 0.98, 1.02];
+/// 비교할 후보로 삼으려면 공유해야 하는 지문 값 수.
+const MIN_SHARED: usize = 10;
+
+#[derive(Deserialize)]
+struct FpcalcOutput {
+    fingerprint: Vec<i64>,
+}
+
+/// `fpcalc`(Chromaprint)를 실행할 수 있는지 확인한다.
+pub fn check_fpcalc(fpcalc: &str) -> Result<()> {
+    let output = Command::new(fpcalc)
+        .arg("-version")
+        .output()
+        .with_context(|| format!("{}을(를) 실행할 수 없습니다", fpcalc))?;
+    if !output.status.success() {
+        bail!("{} -version이 실패했습니다", fpcalc);
+    }
+    Ok(())
+}
+
+/// 파일 앞부분 `length`초의 Chromaprint 원시 지문을 `fpcalc -raw -json`으로 계산한다.
+pub fn compute(fpcalc: &str, path: &Path, length: u32) -> Result<Vec<u32>> {
+    let output = Command::new(fpcalc)
+        .args(["-raw", "-json", "-length", &length.to_string()])
+        .arg(path)
+        .output()
+        .with_context(|| format!("{}을(를) 실행할 수 없습니다", fpcalc))?;
+    if !output.status.success() {
+        bail!(
+            "지문을 계산할 수 없습니다: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse(&output.stdout)
+}
+
+fn parse(json: &[u8]) -> Result<Vec<u32>> {
+    let output: FpcalcOutput =
+        serde_json::from_slice(json).context("fpcalc 출력을 해석할 수 없습니다")?;
+    // 버전에 따라 부호 있는 정수로 나오기도 한다
+    Ok(output.fingerprint.into_iter().map(|v| v as u32).collect())
+}
+
+/// 두 지문의 유사도 (0.0~1.0). 위치를 앞뒤로 밀고 재생 속도를 조금씩 바꿔 가며, 가장 잘 맞는
+/// `WINDOW` 구간의 일치 비트 비율을 쓴다. 구간 단위로 보므로 중간을 잘라 낸 라디오 에디트와 앨범 버전도
+/// 맞는 구간이 있으면 높게 나온다. 다른 곡끼리는 0.5 안팎이다.
+pub fn similarity(a: &[u32], b: &[u32]) -> f64 {
+    let window = WINDOW.min(a.len()).min(b.len());
+    if window == 0 {
+        return 0.0;
+    }
+    let mut best_errors = u32::MAX;
+    for &ratio in STRETCH {
+        for offset in -(MAX_OFFSET as isize)..=MAX_OFFSET as isize {
+            // a[i]와 b[i * ratio + offset]를 비교한 비트 오류 수
+            let errors: Vec<u32> = (0..a.len())
+                .filter_map(|i| {
+                    let j = (i as f64 * ratio).round() as isize + offset;
+                    let y = b.get(usize::try_from(j).ok()?)?;
+                    Some((a[i] ^ y).count_ones())
+                })
+                .collect();
+            if errors.len() < window {
+                continue;
+            }
+            let mut sum: u32 = errors[..window].iter().sum();
+            let mut best = sum;
+            for k in window..errors.len() {
+                sum = sum + errors[k] - errors[k - window];
+                best = best.min(sum);
+            }
+            best_errors = best_errors.min(best);
+        }
+    }
+    if best_errors == u32::MAX {
+        return 0.0;
+    }
+    1.0 - best_errors as f64 / (32 * window) as f64
+}
+
+/// 같은 곡으로 보이는 파일 묶음. `members`는 (파일 번호, 묶음의 다른 파일과의 가장 높은 유사도).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cluster {
+    pub members: Vec<(usize, f64)>,
+}
+
+/// 유사도가 `threshold` 이상인 지문끼리 이어 묶는다. 파일이 둘 이상인 묶음만 첫 파일 순으로 반환한다.
+/// 모든 쌍을 비교하지 않고, 지문 값(상위 20비트)을 충분히 공유하는 쌍만 비교한다.
+pub fn clusters(fingerprints: &[Vec<u32>], threshold: f64) -> Vec<Cluster> {
+    let n = fingerprints.len();
+    let mut postings: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, fp) in fingerprints.iter().enumerate() {
+        let mut keys: Vec<u32> = fp.iter().map(|v| v >> 12).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        for key in keys {
+            postings.entry(key).or_default().push(i);
+        }
+    }
+    // 무음처럼 거의 모든 파일에 있는 값은 후보를 가리지 못한다
+    let common = (n / 10).max(16);
+    let mut shared: HashMap<(usize, usize), usize> = HashMap::new();
+    for files in postings.values().filter(|files| files.len() <= common) {
+        for (k, &x) in files.iter().enumerate() {
+            for &y in &files[k + 1..] {
+                *shared.entry((x, y)).or_default() += 1;
+            }
+        }
+    }
+
+    let mut parent: Vec<usize> = (0..n).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    let mut best = vec![0.0f64; n];
+    let mut pairs: Vec<(usize, usize)> = shared
+        .into_iter()
+        .filter(|&(_, count)| count >= MIN_SHARED)
+        .map(|(pair, _)| pair)
+        .collect();
+    pairs.sort_unstable();
+    for (x, y) in pairs {
+        let score = similarity(&fingerprints[x], &fingerprints[y]);
+        if score < threshold {
+            continue;
+        }
+        best[x] = best[x].max(score);
+        best[y] = best[y].max(score);
+        let (rx, ry) = (root(&mut parent, x), root(&mut parent, y));
+        parent[rx.max(ry)] = rx.min(ry);
+    }
+
+    let mut groups: HashMap<usize, Vec<(usize, f64)>> = HashMap::new();
+    for (i, score) in best.into_iter().enumerate() {
+        let r = root(&mut parent, i);
+        groups.entry(r).or_default().push((i, score));
+    }
+    let mut clusters: Vec<Cluster> = groups
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .map(|members| Cluster { members })
+        .collect();
+    clusters.sort_by_key(|c| c.members[0].0);
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 재현 가능한 의사 난수 지문.
+    fn fingerprint(seed: u32, len: usize) -> Vec<u32> {
+        let mut x = seed;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_similarity() {
+        let album = fingerprint(1, 900);
+        // 다른 비트레이트: 항목마다 1비트씩 다름
+        let reencoded: Vec<u32> = album.iter().map(|v| v ^ 1).collect();
+        assert!(similarity(&album, &reencoded) > 0.95);
+        // 라디오 에디트: 인트로 10초를 자르고 중간 절을 뺌
+        let edit: Vec<u32> = album[80..400]
+            .iter()
+            .chain(&album[600..])
+            .copied()
+            .collect();
+        assert!(similarity(&album, &edit) > 0.95);
+        // 다른 곡
+        assert!(similarity(&album, &fingerprint(2, 900)) < 0.6);
+        assert_eq!(similarity(&album, &[]), 0.0);
+    }
+
+    #[test]
+    fn test_clusters() {
+        let album = fingerprint(1, 600);
+        let edit: Vec<u32> = album[40..500].to_vec();
+        let other = fingerprint(7, 600);
+        let found = clusters(&[other, album, edit], 0.85);
+        assert_eq!(found.len(), 1);
+        let files: Vec<usize> = found[0].members.iter().map(|m| m.0).collect();
+        assert_eq!(files, [1, 2]);
+        assert!(found[0].members[0].1 > 0.99);
+    }
+
+    #[test]
+    fn test_parse_fpcalc_output() {
+        let json = br#"{"duration": 212.5, "fingerprint": [1, -2, 3]}"#;
+        assert_eq!(parse(json).unwrap(), [1, u32::MAX - 1, 3]);
+    }
+}
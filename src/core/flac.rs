@@ -0,0 +1,292 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::core::parser;
+use crate::models::{self, TrackInfo};
+
+/// 메타데이터 블록 종류: Vorbis comment
+const BLOCK_VORBIS_COMMENT: u8 = 4;
+/// 메타데이터 블록 종류: 그림
+const BLOCK_PICTURE: u8 = 6;
+/// 그림 종류: 앞 표지
+const PICTURE_FRONT_COVER: u32 = 3;
+
+/// 디렉토리를 재귀 탐색하여 FLAC 파일 경로를 정렬하여 반환한다.
+pub fn find_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    fn collect(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)
+            .with_context(|| format!("디렉토리를 읽을 수 없습니다: {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.is_dir() {
+                collect(&path, paths)?;
+            } else if is_flac(&path) {
+                paths.push(path);
+            }
+        }
+        Ok(())
+    }
+    let mut paths = Vec::new();
+    collect(dir, &mut paths)?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// 확장자가 .flac인지 확인한다 (대소문자 무시).
+pub fn is_flac(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("flac"))
+}
+
+/// FLAC 파일의 Vorbis comment와 그림(앞 표지, 없으면 첫 그림)을 읽는다. 오디오 프레임은 읽지 않는다.
+pub fn read_tags(path: &Path) -> Result<TrackInfo> {
+    let file = File::open(path).with_context(|| format!("열 수 없습니다: {}", path.display()))?;
+    parse(&mut BufReader::new(file))
+        .with_context(|| format!("FLAC 태그를 읽을 수 없습니다: {}", path.display()))
+}
+
+fn parse(reader: &mut impl Read) -> Result<TrackInfo> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != b"fLaC" {
+        bail!("FLAC 파일이 아닙니다");
+    }
+    let mut info = TrackInfo {
+        source: "flac".to_string(),
+        ..Default::default()
+    };
+    let mut cover: Option<(u32, Vec<u8>)> = None;
+    loop {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header)?;
+        let last = header[0] & 0x80 != 0;
+        let kind = header[0] & 0x7F;
+        let len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        let mut block = vec![0u8; len];
+        reader.read_exact(&mut block)?;
+        match kind {
+            BLOCK_VORBIS_COMMENT => parse_comments(&block, &mut info)?,
+            // 앞 표지가 있으면 그것을, 없으면 처음 나온 그림을 쓴다
+            BLOCK_PICTURE => {
+                let (picture_type, data) = parse_picture(&block)?;
+                let better = match &cover {
+                    None => true,
+                    Some((kept, _)) => {
+                        *kept != PICTURE_FRONT_COVER && picture_type == PICTURE_FRONT_COVER
+                    }
+                };
+                if better {
+                    cover = Some((picture_type, data));
+                }
+            }
+            _ => {}
+        }
+        if last {
+            break;
+        }
+    }
+    info.album_art = cover.map(|(_, data)| data);
+    Ok(info)
+}
+
+/// 블록 안에서 읽는 위치를 옮겨 가며 값을 꺼낸다.
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .pos
+            .checked_add(len)
+            .and_then(|end| self.data.get(self.pos..end))
+            .context("메타데이터 블록이 잘렸습니다")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u32_le(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u32_be(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into()?))
+    }
+}
+
+/// Vorbis comment: 벤더 문자열 뒤에 "KEY=값" 목록 (길이는 리틀 엔디언).
+fn parse_comments(block: &[u8], info: &mut TrackInfo) -> Result<()> {
+    let mut cursor = Cursor {
+        data: block,
+        pos: 0,
+    };
+    let vendor_len = cursor.u32_le()? as usize;
+    cursor.take(vendor_len)?;
+    let count = cursor.u32_le()?;
+    for _ in 0..count {
+        let len = cursor.u32_le()? as usize;
+        let comment = String::from_utf8_lossy(cursor.take(len)?);
+        if let Some((key, value)) = comment.split_once('=') {
+            apply_comment(info, &key.to_uppercase(), value.trim());
+        }
+    }
+    Ok(())
+}
+
+/// 그림 블록: 종류, MIME, 설명, 가로/세로/색 깊이/색 수, 이미지 (길이는 빅 엔디언).
+fn parse_picture(block: &[u8]) -> Result<(u32, Vec<u8>)> {
+    let mut cursor = Cursor {
+        data: block,
+        pos: 0,
+    };
+    let picture_type = cursor.u32_be()?;
+    let mime_len = cursor.u32_be()? as usize;
+    cursor.take(mime_len)?;
+    let description_len = cursor.u32_be()? as usize;
+    cursor.take(description_len)?;
+    cursor.take(16)?;
+    let data_len = cursor.u32_be()? as usize;
+    Ok((picture_type, cursor.take(data_len)?.to_vec()))
+}
+
+/// Vorbis comment 키(대문자)에 맞는 필드를 채운다. 같은 키가 여러 번 나오면 아티스트는 모두 넣고, 나머지는 처음 값을 쓴다.
+fn apply_comment(info: &mut TrackInfo, key: &str, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    let set = |field: &mut Option<String>| {
+        field.get_or_insert_with(|| value.to_string());
+    };
+    let number = |field: &mut Option<u32>| {
+        if field.is_none() {
+            *field = value.split('/').next().and_then(|n| n.trim().parse().ok());
+        }
+    };
+    // "3/12"처럼 전체 수가 함께 들어 있는 번호
+    let total = |field: &mut Option<u32>| {
+        if field.is_none() {
+            *field = value
+                .split_once('/')
+                .and_then(|(_, n)| n.trim().parse().ok());
+        }
+    };
+    match key {
+        "TITLE" => set(&mut info.title),
+        "ARTIST" => {
+            // ARTIST 필드 하나가 한 아티스트다. 값 안의 ", "는 나누지 않는다
+            for artist in models::artist_values([value]) {
+                if !info.artists.contains(&artist) {
+                    info.artists.push(artist);
+                }
+            }
+        }
+        "ALBUM" => set(&mut info.album),
+        "ALBUMARTIST" | "ALBUM ARTIST" => set(&mut info.album_artist),
+        "TRACKNUMBER" => {
+            number(&mut info.track_number);
+            total(&mut info.total_tracks);
+        }
+        "TRACKTOTAL" | "TOTALTRACKS" => number(&mut info.total_tracks),
+        "DISCNUMBER" => {
+            number(&mut info.disc_number);
+            total(&mut info.total_discs);
+        }
+        "DISCTOTAL" | "TOTALDISCS" => number(&mut info.total_discs),
+        "DATE" | "YEAR" if info.year.is_none() => {
+            info.year = value.get(..4).and_then(|y| y.parse().ok());
+            info.release_date = parser::normalize_release_date(value);
+        }
+        "ORIGINALDATE" | "ORIGINALYEAR" if info.original_year.is_none() => {
+            info.original_year = value.get(..4).and_then(|y| y.parse().ok());
+            info.original_date = parser::normalize_release_date(value);
+        }
+        "GENRE" => set(&mut info.genre),
+        "GROUPING" => set(&mut info.grouping),
+        "WORK" => set(&mut info.work),
+        "MOVEMENTNAME" => set(&mut info.movement),
+        "MOVEMENT" => number(&mut info.movement_number),
+        "MOVEMENTTOTAL" => number(&mut info.movement_total),
+        "LABEL" | "ORGANIZATION" | "PUBLISHER" => set(&mut info.label),
+        "COMPOSER" => set(&mut info.composer),
+        "PRODUCER" => set(&mut info.producer),
+        "LYRICS" | "UNSYNCEDLYRICS" => set(&mut info.lyrics),
+        "MUSICBRAINZ_ALBUMID" => set(&mut info.musicbrainz_album_id),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(kind: u8, last: bool, body: &[u8]) -> Vec<u8> {
+        let len = (body.len() as u32).to_be_bytes();
+        let mut out = vec![kind | if last { 0x80 } else { 0 }, len[1], len[2], len[3]];
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn comments(items: &[&str]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&4u32.to_le_bytes());
+        out.extend_from_slice(b"test");
+        out.extend_from_slice(&(items.len() as u32).to_le_bytes());
+        for item in items {
+            out.extend_from_slice(&(item.len() as u32).to_le_bytes());
+            out.extend_from_slice(item.as_bytes());
+        }
+        out
+    }
+
+    fn picture(picture_type: u32, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&picture_type.to_be_bytes());
+        out.extend_from_slice(&10u32.to_be_bytes());
+        out.extend_from_slice(b"image/jpeg");
+        out.extend_from_slice(&0u32.to_be_bytes());
+        out.extend_from_slice(&[0u8; 16]);
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+
+    #[test]
+    fn test_parse_flac_metadata() {
+        let mut file = b"fLaC".to_vec();
+        file.extend(block(0, false, &[0u8; 34]));
+        file.extend(block(6, false, &picture(4, b"back")));
+        file.extend(block(
+            4,
+            false,
+            &comments(&[
+                "TITLE=Blueming",
+                "artist=IU",
+                "ARTIST=SUGA",
+                "ALBUM=Love poem",
+                "TRACKNUMBER=4/6",
+                "DISCNUMBER=1",
+                "DATE=2019-11-18",
+                "GENRE=K-Pop",
+            ]),
+        ));
+        file.extend(block(6, true, &picture(3, b"front")));
+        file.extend_from_slice(&[0xFF, 0xF8]);
+
+        let info = parse(&mut file.as_slice()).unwrap();
+        assert_eq!(info.title.as_deref(), Some("Blueming"));
+        assert_eq!(info.artists, ["IU", "SUGA"]);
+        assert_eq!((info.track_number, info.total_tracks), (Some(4), Some(6)));
+        assert_eq!(info.disc_number, Some(1));
+        assert_eq!(info.year, Some(2019));
+        assert_eq!(info.release_date.as_deref(), Some("2019-11-18"));
+        assert_eq!(info.album_art.as_deref(), Some(&b"front"[..]));
+
+        assert!(parse(&mut &b"ID3\x04"[..]).is_err());
+        assert!(parse(&mut &b"fLaC\x84\x00\x00\x10"[..]).is_err());
+    }
+}
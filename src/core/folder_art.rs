@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::core::artwork::{self, ArtFormat};
+
+/// 정리 폴더별로 모은 앨범 아트. 같은 이미지는 한 번만 저장하고 쓰인 파일 수를 센다.
+#[derive(Debug, Default)]
+pub struct FolderArt {
+    dirs: BTreeMap<PathBuf, Vec<(Vec<u8>, usize)>>,
+}
+
+impl FolderArt {
+    /// `dir`로 옮긴 파일의 앨범 아트를 더한다.
+    pub fn add(&mut self, dir: &Path, art: &[u8]) {
+        let arts = self.dirs.entry(dir.to_path_buf()).or_default();
+        match arts.iter_mut().find(|(data, _)| data == art) {
+            Some((_, count)) => *count += 1,
+            None => arts.push((art.to_vec(), 1)),
+        }
+    }
+
+    /// 폴더마다 저장할 대표 이미지. 픽셀 수가 가장 큰 것, 같으면 가장 많이 쓰인 것 (`artwork::canonical_art`와 같은 기준).
+    pub fn picks(&self) -> impl Iterator<Item = (&Path, &[u8])> {
+        self.dirs.iter().filter_map(|(dir, arts)| {
+            let (art, _) = arts.iter().max_by_key(|(data, count)| {
+                let pixels =
+                    artwork::image_dimensions(data).map_or(0, |(w, h)| w as u64 * h as u64);
+                (pixels, *count)
+            })?;
+            Some((dir.as_path(), art.as_slice()))
+        })
+    }
+}
+
+/// `dir`에 새로 만들 폴더 아트 파일 경로. 확장자는 이미지 형식에 맞춘다 (PNG 아트면 `folder.jpg` → `folder.png`).
+/// 이미 있는 파일은 직접 넣은 것일 수 있으므로 넣지 않는다.
+pub fn planned(dir: &Path, art: &[u8], names: &[String]) -> Vec<PathBuf> {
+    let extension = ArtFormat::detect(art).map_or("jpg", ArtFormat::extension);
+    let mut paths: Vec<PathBuf> = names
+        .iter()
+        .map(|name| dir.join(Path::new(name).with_extension(extension)))
+        .filter(|path| !path.exists())
+        .collect();
+    paths.dedup();
+    paths
+}
+
+/// `planned`의 경로에 앨범 아트를 기록한다. 기록한 경로를 반환한다.
+pub fn write(dir: &Path, art: &[u8], names: &[String]) -> Result<Vec<PathBuf>> {
+    let paths = planned(dir, art, names);
+    for path in &paths {
+        std::fs::write(path, art)
+            .with_context(|| format!("폴더 아트를 기록할 수 없습니다: {}", path.display()))?;
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    const PNG_1X1: &[u8] = &[
+        0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13, b'I', b'H', b'D', b'R', 0, 0,
+        0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0,
+    ];
+    const PNG_2X2: &[u8] = &[
+        0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13, b'I', b'H', b'D', b'R', 0, 0,
+        0, 2, 0, 0, 0, 2, 8, 6, 0, 0, 0,
+    ];
+
+    #[test]
+    fn test_folder_art_picks_and_writes() {
+        let dir = TempDir::new("folder-art");
+        let album = dir.join("IU/Love poem");
+        std::fs::create_dir_all(&album).unwrap();
+
+        let mut arts = FolderArt::default();
+        arts.add(&album, PNG_1X1);
+        arts.add(&album, PNG_1X1);
+        arts.add(&album, PNG_2X2);
+        let picks: Vec<(&Path, &[u8])> = arts.picks().collect();
+        assert_eq!(picks, [(album.as_path(), PNG_2X2)]);
+
+        std::fs::write(album.join("cover.png"), b"mine").unwrap();
+        let names = ["folder.jpg".to_string(), "cover.jpg".to_string()];
+        let written = write(&album, PNG_2X2, &names).unwrap();
+        assert_eq!(written, [album.join("folder.png")]);
+        assert_eq!(std::fs::read(album.join("cover.png")).unwrap(), b"mine");
+        // 다시 실행해도 이미 있는 파일은 건드리지 않는다
+        assert!(planned(&album, PNG_2X2, &names).is_empty());
+    }
+}
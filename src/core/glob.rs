@@ -0,0 +1,207 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+
+/// 셸 글롭 문자(`*`, `?`, `[`)가 들어 있는지 확인한다.
+pub fn is_pattern(text: &str) -> bool {
+    text.contains(['*', '?', '['])
+}
+
+/// 경로 한 조각(`name`)이 글롭 패턴과 맞는지 확인한다.
+/// `*`는 0개 이상, `?`는 한 글자, `[abc]`/`[a-z]`/`[!a]`는 글자 집합과 맞는다.
+/// Windows에서는 대소문자를 구분하지 않는다.
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let fold = |s: &str| -> Vec<char> {
+        if cfg!(windows) {
+            s.to_lowercase().chars().collect()
+        } else {
+            s.chars().collect()
+        }
+    };
+    matches_chars(&fold(pattern), &fold(name))
+}
+
+fn matches_chars(pattern: &[char], name: &[char]) -> bool {
+    // 마지막 `*`의 위치에서 다시 시도하는 방식으로 되돌아가며 비교한다
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+                continue;
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+                continue;
+            }
+            Some('[') => {
+                if let Some((matched, len)) = match_class(&pattern[p..], name[n]) {
+                    if matched {
+                        p += len;
+                        n += 1;
+                        continue;
+                    }
+                } else if name[n] == '[' {
+                    // 닫히지 않은 `[`는 글자 그대로 비교한다
+                    p += 1;
+                    n += 1;
+                    continue;
+                }
+            }
+            Some(&c) if c == name[n] => {
+                p += 1;
+                n += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match star {
+            Some((star_p, star_n)) => {
+                p = star_p + 1;
+                n = star_n + 1;
+                star = Some((star_p, star_n + 1));
+            }
+            None => return false,
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// `[...]` 글자 집합과 `c`를 비교한다. 집합이 닫히지 않았으면 None, 아니면 (일치 여부, 패턴 길이).
+fn match_class(pattern: &[char], c: char) -> Option<(bool, usize)> {
+    let mut i = 1;
+    let negate = matches!(pattern.get(i), Some('!' | '^'));
+    if negate {
+        i += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+    while i < pattern.len() {
+        let start = pattern[i];
+        if start == ']' && !first {
+            return Some((matched != negate, i + 1));
+        }
+        first = false;
+        if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2).is_some_and(|&e| e != ']') {
+            matched |= (start..=pattern[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= start == c;
+            i += 1;
+        }
+    }
+    None
+}
+
+/// 글롭 패턴에 맞는 경로를 정렬해 반환한다. `**`는 0개 이상의 하위 디렉토리와 맞는다.
+/// 셸이 글롭을 펼치지 않는 Windows나 따옴표로 감싼 패턴에 쓴다.
+pub fn expand(pattern: &Path) -> Result<Vec<PathBuf>> {
+    // 글롭 문자가 없는 앞부분은 그대로 시작 디렉토리로 쓴다
+    let mut base = PathBuf::new();
+    let mut rest = Vec::new();
+    for component in pattern.components() {
+        match component {
+            Component::Normal(part) if rest.is_empty() && !is_pattern(&part.to_string_lossy()) => {
+                base.push(part)
+            }
+            Component::Normal(part) => rest.push(part.to_string_lossy().into_owned()),
+            other if rest.is_empty() => base.push(other),
+            _ => anyhow::bail!("지원하지 않는 글롭 패턴입니다: {}", pattern.display()),
+        }
+    }
+
+    let mut current = vec![base];
+    for part in &rest {
+        let mut next = Vec::new();
+        for dir in &current {
+            let dir_or_cwd = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir.as_path()
+            };
+            if part == "**" {
+                next.push(dir.clone());
+                collect_dirs(dir_or_cwd, dir, &mut next);
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(dir_or_cwd) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                // 셸처럼 `.`으로 시작하는 숨김 파일은 패턴이 `.`으로 시작할 때만 맞춘다
+                if name.starts_with('.') && !part.starts_with('.') {
+                    continue;
+                }
+                if matches(part, &name) {
+                    next.push(dir.join(&name));
+                }
+            }
+        }
+        current = next;
+    }
+    current.sort();
+    current.dedup();
+    Ok(current)
+}
+
+/// `dir` 아래의 모든 디렉토리를 `prefix` 기준 경로로 모은다 (`**` 처리용).
+fn collect_dirs(dir: &Path, prefix: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if entry.file_type().is_ok_and(|t| t.is_dir()) && !name.to_string_lossy().starts_with('.') {
+            let path = prefix.join(&name);
+            out.push(path.clone());
+            collect_dirs(&entry.path(), &path, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("*.mp3", "01 - Song.mp3"));
+        assert!(!matches("*.mp3", "cover.jpg"));
+        assert!(matches("0?-*", "03-track"));
+        assert!(matches("[0-9][0-9] *", "12 Song"));
+        assert!(!matches("[!0-9]*", "1 Song"));
+        assert!(matches("*Live*", "Palette (Live)"));
+        assert!(!matches("[2019] *", "[2019] Love poem"));
+        assert!(matches("[[]2019] *", "[2019] Love poem"));
+        assert!(matches("", ""));
+        assert!(!matches("a", ""));
+    }
+
+    #[test]
+    fn test_expand() {
+        let dir = TempDir::new("glob");
+        std::fs::create_dir_all(dir.join("A/CD1")).unwrap();
+        std::fs::create_dir_all(dir.join("B")).unwrap();
+        for file in ["A/1.mp3", "A/CD1/2.mp3", "B/3.mp3", "B/cover.jpg"] {
+            std::fs::write(dir.join(file), b"").unwrap();
+        }
+
+        let found = expand(&dir.join("*/*.mp3")).unwrap();
+        assert_eq!(found, [dir.join("A/1.mp3"), dir.join("B/3.mp3")]);
+        let found = expand(&dir.join("**/*.mp3")).unwrap();
+        assert_eq!(
+            found,
+            [
+                dir.join("A/1.mp3"),
+                dir.join("A/CD1/2.mp3"),
+                dir.join("B/3.mp3")
+            ]
+        );
+        assert!(expand(&dir.join("C*/*.mp3")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{staging, tagger};
+use crate::models::TrackInfo;
+
+/// 디렉토리마다 남기는 변경 기록 파일 이름 (JSON Lines).
+pub const HISTORY_FILE: &str = ".mp3tag-history.jsonl";
+
+/// 기록한 변경 하나. 한 줄에 하나씩 JSON으로 저장한다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// 기록 시각 (UTC, "2026-10-15T09:30:00Z")
+    pub time: String,
+    /// 기록 파일과 같은 디렉토리 안의 파일 이름
+    pub file: String,
+    /// 태그 출처 ("spotify", "melon", "manual" 등)
+    pub source: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// 필드 하나의 변경. 앨범 아트는 바이너리 대신 크기("85KB")를 남긴다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    /// 기록 전 값. 없었으면 None.
+    pub old: Option<String>,
+    pub new: String,
+}
+
+/// 파일에 기록한 변경을 그 디렉토리의 `.mp3tag-history.jsonl`에 한 줄 추가한다.
+/// `before`는 기록 전 태그이며, 바뀐 필드가 없으면 아무것도 남기지 않는다.
+pub fn record(path: &Path, before: Option<&TrackInfo>, info: &TrackInfo) -> Result<()> {
+    let changes: Vec<FieldChange> = staging::changed_fields(before, info)
+        .into_iter()
+        .map(|(field, old, new)| FieldChange {
+            field: field.to_string(),
+            old: (!old.is_empty()).then_some(old),
+            new,
+        })
+        .collect();
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let (year, month, day, hour, minute, second) = tagger::utc_date_time(SystemTime::now());
+    let entry = HistoryEntry {
+        time: format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year, month, day, hour, minute, second
+        ),
+        file: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        source: info.source.clone(),
+        changes,
+    };
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+
+    let history_path = history_path(path);
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history_path)
+        .and_then(|mut f| f.write_all(line.as_bytes()))
+        .with_context(|| format!("변경 기록을 남길 수 없습니다: {}", history_path.display()))
+}
+
+/// 디렉토리의 변경 기록을 오래된 순서로 읽는다. 기록이 없으면 빈 목록.
+/// 형식이 맞지 않는 줄은 건너뛴다.
+pub fn load(dir: &Path) -> Result<Vec<HistoryEntry>> {
+    let path = dir.join(HISTORY_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("변경 기록을 읽을 수 없습니다: {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// 파일이 속한 디렉토리의 변경 기록 경로.
+fn history_path(path: &Path) -> PathBuf {
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+    dir.unwrap_or_else(|| Path::new(".")).join(HISTORY_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_record_and_load() {
+        let dir = TempDir::new("history");
+        let path = dir.join("song.mp3");
+
+        let before = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["IU".to_string()],
+            ..Default::default()
+        };
+        let info = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["아이유".to_string()],
+            year: Some(2019),
+            source: "melon".to_string(),
+            ..Default::default()
+        };
+        record(&path, Some(&before), &info).unwrap();
+        // 바뀐 필드가 없으면 남기지 않는다
+        record(&path, Some(&info), &info).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.join(HISTORY_FILE))
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        let entries = load(&dir).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].file, "song.mp3");
+        assert_eq!(entries[0].source, "melon");
+        assert_eq!(
+            entries[0].changes,
+            [
+                FieldChange {
+                    field: "아티스트".to_string(),
+                    old: Some("IU".to_string()),
+                    new: "아이유".to_string(),
+                },
+                FieldChange {
+                    field: "연도".to_string(),
+                    old: None,
+                    new: "2019".to_string(),
+                },
+            ]
+        );
+        assert!(load(&dir.join("missing")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::models::TrackInfo;
+
+/// 태그 기록 훅 명령을 셸로 실행한다.
+/// 대상 파일 경로와 기록할 태그 값을 `MP3TAG_*` 환경 변수로 전달하며,
+/// 값이 없는 필드는 빈 문자열로 넘긴다. 0이 아닌 종료 코드는 에러로 반환한다.
+pub fn run(command: &str, path: &Path, info: &TrackInfo) -> Result<()> {
+    let mut cmd = shell(command);
+    for (key, value) in env_vars(path, info) {
+        cmd.env(key, value);
+    }
+
+    let output = cmd
+        .output()
+        .with_context(|| format!("훅 실행에 실패했습니다: {}", command))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() {
+            stdout.trim()
+        } else {
+            stderr.trim()
+        };
+        bail!("훅이 실패했습니다 ({}): {}", output.status, message);
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// 훅에 넘기는 환경 변수 목록.
+fn env_vars(path: &Path, info: &TrackInfo) -> Vec<(&'static str, String)> {
+    fn text(value: &Option<String>) -> String {
+        value.clone().unwrap_or_default()
+    }
+    fn number<T: ToString>(value: Option<T>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+
+    vec![
+        ("MP3TAG_PATH", path.display().to_string()),
+        ("MP3TAG_TITLE", text(&info.title)),
+        ("MP3TAG_ARTIST", text(&info.artist())),
+        ("MP3TAG_ALBUM", text(&info.album)),
+        ("MP3TAG_ALBUM_ARTIST", text(&info.album_artist)),
+        ("MP3TAG_TRACK", number(info.track_number)),
+        ("MP3TAG_YEAR", number(info.year)),
+        ("MP3TAG_DATE", text(&info.release_date)),
+        ("MP3TAG_GENRE", text(&info.genre)),
+        ("MP3TAG_SOURCE", info.source.clone()),
+    ]
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_receives_fields() {
+        let info = TrackInfo {
+            title: Some("밤편지".to_string()),
+            year: Some(2017),
+            ..Default::default()
+        };
+        let path = Path::new("/music/a.mp3");
+
+        run(
+            r#"test "$MP3TAG_PATH" = /music/a.mp3 && test "$MP3TAG_TITLE" = 밤편지 && test "$MP3TAG_YEAR" = 2017 && test -z "$MP3TAG_ALBUM""#,
+            path,
+            &info,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_hook_failure_reports_output() {
+        let err = run(
+            "echo '장르가 비어 있습니다' >&2; exit 1",
+            Path::new("a.mp3"),
+            &TrackInfo::default(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("장르가 비어 있습니다"), "{}", err);
+    }
+}
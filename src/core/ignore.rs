@@ -0,0 +1,168 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::glob;
+
+/// 스캔에서 뺄 경로를 적는 파일. 그 디렉토리와 하위 디렉토리에 적용된다.
+pub const IGNORE_FILE: &str = ".mp3tagignore";
+
+/// `.mp3tagignore` 한 줄.
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    /// `/`로 나눈 글롭 조각. `**`는 0개 이상의 디렉토리와 맞는다.
+    segments: Vec<String>,
+    /// 중간이나 앞에 `/`가 있으면 파일 위치 기준, 없으면 어느 깊이의 이름과도 맞는다
+    anchored: bool,
+    /// 끝에 `/`가 있으면 디렉토리에만 맞는다
+    dir_only: bool,
+    /// `!`로 시작하면 앞 규칙으로 뺀 경로를 다시 넣는다
+    negate: bool,
+}
+
+impl Rule {
+    /// gitignore 형식의 한 줄을 읽는다. 빈 줄과 `#` 주석은 None.
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negate, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        // `\#`, `\!`로 시작하는 이름
+        let line = line.strip_prefix('\\').unwrap_or(line);
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.strip_prefix('/').unwrap_or(line);
+        if line.is_empty() {
+            return None;
+        }
+        Some(Self {
+            segments: line.split('/').map(str::to_string).collect(),
+            anchored,
+            dir_only,
+            negate,
+        })
+    }
+
+    /// `relative`(규칙 파일이 있는 디렉토리 기준 경로 조각)와 맞는지.
+    fn matches(&self, relative: &[String], is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        if self.anchored {
+            matches_segments(&self.segments, relative)
+        } else {
+            relative
+                .last()
+                .is_some_and(|name| glob::matches(&self.segments[0], name))
+        }
+    }
+}
+
+fn matches_segments(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| matches_segments(rest, &path[skip..]))
+        }
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(name, path)| glob::matches(first, name) && matches_segments(rest, path)),
+    }
+}
+
+/// 한 디렉토리의 `.mp3tagignore` 규칙.
+#[derive(Debug, Clone)]
+pub struct IgnoreFile {
+    dir: PathBuf,
+    rules: Vec<Rule>,
+}
+
+impl IgnoreFile {
+    /// `dir`의 `.mp3tagignore`를 읽는다. 없거나 규칙이 없으면 None.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let text = std::fs::read_to_string(dir.join(IGNORE_FILE)).ok()?;
+        Self::parse(dir, &text)
+    }
+
+    fn parse(dir: &Path, text: &str) -> Option<Self> {
+        let rules: Vec<Rule> = text.lines().filter_map(Rule::parse).collect();
+        (!rules.is_empty()).then(|| Self {
+            dir: dir.to_path_buf(),
+            rules,
+        })
+    }
+
+    /// 마지막으로 맞는 규칙의 결과. 빼면 Some(true), `!`로 다시 넣으면 Some(false), 맞는 규칙이 없으면 None.
+    fn verdict(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let relative: Vec<String> = path
+            .strip_prefix(&self.dir)
+            .ok()?
+            .iter()
+            .map(|part| part.to_string_lossy().into_owned())
+            .collect();
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(&relative, is_dir))
+            .map(|rule| !rule.negate)
+    }
+}
+
+/// 스캔에서 빼야 하는 경로인지 확인한다. `files`는 바깥 디렉토리부터 쌓은 규칙이며,
+/// gitignore처럼 더 깊은 파일과 뒤쪽 줄의 규칙이 앞선다.
+pub fn is_ignored(files: &[IgnoreFile], path: &Path, is_dir: bool) -> bool {
+    files
+        .iter()
+        .rev()
+        .find_map(|file| file.verdict(path, is_dir))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ignored() {
+        let root = Path::new("/music");
+        let text = "# 음악이 아닌 폴더\nVoice Memos/\n/Audiobooks\n*.part.mp3\nLive/**/demo*.mp3\n\n!keep.part.mp3\n";
+        let files = vec![IgnoreFile::parse(root, text).unwrap()];
+        let ignored = |path: &str, is_dir| is_ignored(&files, &root.join(path), is_dir);
+
+        assert!(ignored("Voice Memos", true));
+        assert!(ignored("IU/Voice Memos", true));
+        assert!(!ignored("Voice Memos", false));
+        assert!(ignored("Audiobooks", true));
+        // `/`로 시작하면 규칙 파일이 있는 디렉토리에서만 맞는다
+        assert!(!ignored("IU/Audiobooks", true));
+        assert!(ignored("IU/a.part.mp3", false));
+        assert!(!ignored("IU/keep.part.mp3", false));
+        assert!(ignored("Live/demo1.mp3", false));
+        assert!(ignored("Live/2019/Seoul/demo2.mp3", false));
+        assert!(!ignored("IU/Live/demo1.mp3", false));
+        assert!(!ignored("IU/Palette.mp3", false));
+
+        // 하위 디렉토리의 규칙이 앞선다
+        let mut files = files;
+        files.push(IgnoreFile::parse(&root.join("IU"), "!*.part.mp3\n").unwrap());
+        assert!(!is_ignored(&files, &root.join("IU/a.part.mp3"), false));
+        assert!(is_ignored(&files, &root.join("BTS/a.part.mp3"), false));
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!(Rule::parse("# comment"), None);
+        assert_eq!(Rule::parse("   "), None);
+        let rule = Rule::parse(r"\#1 Hits/").unwrap();
+        assert_eq!(rule.segments, ["#1 Hits"]);
+        assert!(rule.dir_only && !rule.anchored && !rule.negate);
+        let rule = Rule::parse("!/Live/*.mp3  ").unwrap();
+        assert_eq!(rule.segments, ["Live", "*.mp3"]);
+        assert!(rule.anchored && rule.negate && !rule.dir_only);
+    }
+}
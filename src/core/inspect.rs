@@ -0,0 +1,170 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use anyhow::Result;
+use id3::{Content, Tag};
+
+use crate::core::{artwork, audio};
+
+/// 프레임 내용을 한 줄로 보여 줄 때의 최대 글자 수.
+const MAX_VALUE_CHARS: usize = 80;
+
+/// `mp3tag show`로 보여 줄 ID3 태그의 전체 구성.
+pub struct TagDump {
+    /// 태그 버전 ("ID3v2.4" 등). ID3v2 태그가 없으면 None.
+    pub version: Option<String>,
+    /// ID3v2 태그 크기 (헤더 포함, 바이트)
+    pub size: u64,
+    /// 프레임 뒤에 남겨 둔 패딩 크기 (바이트)
+    pub padding: u64,
+    /// 파일에 기록된 순서대로의 (프레임 ID, 내용 요약)
+    pub frames: Vec<(String, String)>,
+    /// 파일 끝에 ID3v1 태그가 있는지
+    pub id3v1: bool,
+}
+
+/// 파일의 ID3 태그를 프레임 단위로 읽는다. 알 수 없는 프레임과 TXXX/PRIV도 모두 포함한다.
+pub fn dump(path: &Path) -> Result<TagDump> {
+    let mut file = File::open(path)?;
+    let file_len = file.metadata()?.len();
+    let mut dump = TagDump {
+        version: None,
+        size: 0,
+        padding: 0,
+        frames: Vec::new(),
+        id3v1: audio::has_id3v1(&mut file, file_len),
+    };
+
+    file.seek(SeekFrom::Start(0))?;
+    let mut header = [0u8; 10];
+    if file.read_exact(&mut header).is_err() || &header[..3] != b"ID3" {
+        return Ok(dump);
+    }
+    let body_len = syncsafe(&header[6..10]);
+    let mut body = Vec::new();
+    file.take(body_len).read_to_end(&mut body)?;
+    dump.size = 10 + body_len;
+    dump.padding = padding_len(header[3], header[5], &body);
+
+    let tag = Tag::read_from_path(path)?;
+    dump.version = Some(tag.version().to_string());
+    dump.frames = tag
+        .frames()
+        .map(|frame| (frame.id().to_string(), describe(frame.content())))
+        .collect();
+    Ok(dump)
+}
+
+/// 프레임 내용을 한 줄 요약으로 만든다. 이미지는 종류/형식/크기, 바이너리는 길이를 보여 준다.
+fn describe(content: &Content) -> String {
+    let text = match content {
+        Content::Picture(picture) => {
+            let dimensions = artwork::image_dimensions(&picture.data)
+                .map(|(w, h)| format!(", {}x{}", w, h))
+                .unwrap_or_default();
+            let mut text = format!(
+                "{}, {}, {}바이트{}",
+                picture.picture_type,
+                picture.mime_type,
+                picture.data.len(),
+                dimensions
+            );
+            if !picture.description.is_empty() {
+                text = format!("{} ({})", text, picture.description);
+            }
+            text
+        }
+        Content::Private(private) => format!(
+            "{}: {}바이트",
+            private.owner_identifier,
+            private.private_data.len()
+        ),
+        Content::EncapsulatedObject(object) => format!(
+            "{} {} ({}): {}바이트",
+            object.mime_type,
+            object.filename,
+            object.description,
+            object.data.len()
+        ),
+        Content::Lyrics(lyrics) => format!(
+            "[{}] {}줄: {}",
+            lyrics.lang,
+            lyrics.text.lines().count(),
+            lyrics.text
+        ),
+        Content::SynchronisedLyrics(lyrics) => format!(
+            "[{}] {}, 타임스탬프 {}개",
+            lyrics.lang,
+            lyrics.content_type,
+            lyrics.content.len()
+        ),
+        Content::Unknown(unknown) => format!(
+            "해석하지 않은 프레임 ({}바이트): {}",
+            unknown.data.len(),
+            String::from_utf8_lossy(unknown.data.get(1..).unwrap_or_default())
+        ),
+        content => content.to_string(),
+    };
+    let text = text.replace(['\0', '\r', '\n'], " ");
+    if text.chars().count() > MAX_VALUE_CHARS {
+        let mut short: String = text.chars().take(MAX_VALUE_CHARS).collect();
+        short.push('…');
+        short
+    } else {
+        text
+    }
+}
+
+/// ID3v2 태그 본문에서 마지막 프레임 뒤의 패딩(0으로 채운 영역) 크기를 구한다.
+/// `major`는 태그 버전(2, 3, 4), `flags`는 헤더 플래그다.
+fn padding_len(major: u8, flags: u8, body: &[u8]) -> u64 {
+    let header_len = if major == 2 { 6 } else { 10 };
+    let big_endian = |bytes: &[u8]| bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    let mut pos = 0;
+    // 확장 헤더: v2.3은 크기 필드 4바이트를 뺀 크기, v2.4는 자신을 포함한 크기를 적는다
+    if major >= 3 && flags & 0x40 != 0 && body.len() >= 4 {
+        pos = match major {
+            3 => 4 + u32::from_be_bytes([body[0], body[1], body[2], body[3]]) as usize,
+            _ => syncsafe(&body[..4]) as usize,
+        };
+    }
+    while pos + header_len <= body.len() && body[pos] != 0 {
+        let size = match major {
+            2 => big_endian(&body[pos + 3..pos + 6]),
+            3 => big_endian(&body[pos + 4..pos + 8]),
+            _ => syncsafe(&body[pos + 4..pos + 8]),
+        };
+        pos += header_len + size as usize;
+    }
+    body.len().saturating_sub(pos) as u64
+}
+
+/// 7비트씩 나눠 적은 크기(syncsafe integer)를 읽는다.
+fn syncsafe(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0u64, |acc, &b| (acc << 7) | (b & 0x7F) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padding_len() {
+        // v2.3: TIT2 프레임(본문 5바이트) 뒤 패딩 7바이트
+        let mut body = b"TIT2\0\0\0\x05\0\0\0Test".to_vec();
+        body.extend([0u8; 7]);
+        assert_eq!(padding_len(3, 0, &body), 7);
+
+        // v2.4: 크기가 syncsafe (0x01 0x00 = 128바이트)
+        let mut body = b"TXXX\0\0\x01\x00\0\0".to_vec();
+        body.extend([b'a'; 128]);
+        body.extend([0u8; 3]);
+        assert_eq!(padding_len(4, 0, &body), 3);
+
+        // 패딩이 없는 태그
+        assert_eq!(padding_len(3, 0, b"TIT2\0\0\0\x01\0\0\0"), 0);
+    }
+}
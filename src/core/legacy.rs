@@ -0,0 +1,375 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::core::{parser, tagger};
+use crate::models::{self, TrackInfo};
+
+/// APEv2 푸터/헤더 크기.
+const APE_FOOTER_LEN: u64 = 32;
+/// Lyrics3 v1 본문의 최대 크기.
+const LYRICS3V1_MAX: u64 = 5100;
+
+/// 파일 끝(ID3v1 앞)에 남아 있는 APEv2/Lyrics3 태그 블록.
+#[derive(Debug, Clone)]
+pub struct LegacyTags {
+    /// 발견한 태그 종류 ("APEv2", "APEv1", "Lyrics3v2", "Lyrics3v1"), 파일 앞쪽 순서
+    pub kinds: Vec<&'static str>,
+    /// 태그에서 읽은 값 (source = "legacy")
+    pub info: TrackInfo,
+    /// 블록 시작 위치
+    pub start: u64,
+    /// 블록 끝 위치 (ID3v1이 있으면 그 시작 위치)
+    pub end: u64,
+}
+
+/// 파일 끝의 APEv2/Lyrics3 태그를 찾아 읽는다. 여러 블록이 이어져 있으면 하나로 합친다.
+/// UTF-8이 아닌 텍스트는 옛 국내 리핑 프로그램이 쓰던 CP949로 읽는다.
+pub fn detect(path: &Path) -> std::io::Result<Option<LegacyTags>> {
+    let mut file = File::open(path)?;
+    let file_len = file.metadata()?.len();
+    let mut end = file_len;
+    if file_len >= 128 && read_at(&mut file, file_len - 128, 3)? == b"TAG" {
+        end -= 128;
+    }
+    let block_end = end;
+
+    let mut kinds = Vec::new();
+    let mut info = TrackInfo {
+        source: "legacy".to_string(),
+        ..Default::default()
+    };
+    // 뒤에서부터 블록을 하나씩 벗겨 낸다
+    loop {
+        if let Some((start, kind)) = read_ape(&mut file, end, &mut info)? {
+            kinds.insert(0, kind);
+            end = start;
+        } else if let Some(start) = read_lyrics3v2(&mut file, end, &mut info)? {
+            kinds.insert(0, "Lyrics3v2");
+            end = start;
+        } else if let Some(start) =
+            read_lyrics3v1(&mut file, end, block_end != file_len, &mut info)?
+        {
+            kinds.insert(0, "Lyrics3v1");
+            end = start;
+        } else {
+            break;
+        }
+    }
+
+    if kinds.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(LegacyTags {
+        kinds,
+        info,
+        start: end,
+        end: block_end,
+    }))
+}
+
+/// 레거시 태그 블록을 잘라 낸다. ID3v2/오디오/ID3v1은 그대로 두고,
+/// 임시 파일에 쓴 뒤 원본 위로 rename한다. 권한과 수정 시각은 유지한다.
+pub fn strip(path: &Path, legacy: &LegacyTags) -> Result<()> {
+    let metadata = std::fs::metadata(path)?;
+    let data = std::fs::read(path)?;
+    let (start, end) = (legacy.start as usize, legacy.end as usize);
+    if start > end || end > data.len() {
+        anyhow::bail!(
+            "레거시 태그 위치가 파일과 맞지 않습니다: {}",
+            path.display()
+        );
+    }
+    let content = [&data[..start], &data[end..]].concat();
+
+    let tmp = tagger::temp_path(path);
+    let result = (|| -> Result<()> {
+        std::fs::write(&tmp, &content)?;
+        std::fs::set_permissions(&tmp, metadata.permissions())?;
+        if let Ok(mtime) = metadata.modified() {
+            File::options()
+                .write(true)
+                .open(&tmp)?
+                .set_modified(mtime)?;
+        }
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result.with_context(|| format!("레거시 태그를 지우지 못했습니다: {}", path.display()))
+}
+
+/// `end`에서 끝나는 APE 태그를 읽는다. 있으면 (블록 시작 위치, 종류).
+fn read_ape(
+    file: &mut File,
+    end: u64,
+    info: &mut TrackInfo,
+) -> std::io::Result<Option<(u64, &'static str)>> {
+    if end < APE_FOOTER_LEN {
+        return Ok(None);
+    }
+    let footer = read_at(file, end - APE_FOOTER_LEN, APE_FOOTER_LEN as usize)?;
+    if &footer[..8] != b"APETAGEX" {
+        return Ok(None);
+    }
+    let version = le_u32(&footer[8..12]);
+    let size = le_u32(&footer[12..16]) as u64; // 항목 + 푸터 (헤더 제외)
+    let flags = le_u32(&footer[20..24]);
+    let header = if flags & 0x8000_0000 != 0 {
+        APE_FOOTER_LEN
+    } else {
+        0
+    };
+    if size < APE_FOOTER_LEN || size + header > end {
+        return Ok(None);
+    }
+    let items = read_at(file, end - size, (size - APE_FOOTER_LEN) as usize)?;
+    parse_ape_items(&items, version < 2000, info);
+    let kind = if version < 2000 { "APEv1" } else { "APEv2" };
+    Ok(Some((end - size - header, kind)))
+}
+
+/// APE 항목 목록: [값 길이 u32][플래그 u32][키 NUL][값]...
+fn parse_ape_items(items: &[u8], v1: bool, info: &mut TrackInfo) {
+    let mut pos = 0;
+    while pos + 8 < items.len() {
+        let len = le_u32(&items[pos..pos + 4]) as usize;
+        let flags = le_u32(&items[pos + 4..pos + 8]);
+        let Some(key_len) = items[pos + 8..].iter().position(|&b| b == 0) else {
+            break;
+        };
+        let key = String::from_utf8_lossy(&items[pos + 8..pos + 8 + key_len]).to_lowercase();
+        let value_start = pos + 8 + key_len + 1;
+        let Some(value) = items.get(value_start..value_start + len) else {
+            break;
+        };
+        pos = value_start + len;
+
+        let binary = !v1 && (flags >> 1) & 0x03 == 1;
+        if binary {
+            // "Cover Art (Front)": 파일명 NUL 이미지
+            if key == "cover art (front)" && info.album_art.is_none() {
+                if let Some(nul) = value.iter().position(|&b| b == 0) {
+                    info.album_art = Some(value[nul + 1..].to_vec());
+                }
+            }
+            continue;
+        }
+        let text = decode(value);
+        let text = text.trim_end_matches('\0').trim();
+        if text.is_empty() {
+            continue;
+        }
+        apply_field(info, &key, text);
+    }
+}
+
+/// 태그 키(소문자)에 맞는 필드를 채운다. 이미 값이 있으면 두지 않는다.
+fn apply_field(info: &mut TrackInfo, key: &str, text: &str) {
+    let set = |field: &mut Option<String>| {
+        field.get_or_insert_with(|| text.to_string());
+    };
+    match key {
+        "title" => set(&mut info.title),
+        "artist" if info.artists.is_empty() => info.artists = models::split_artists(text),
+        "album" => set(&mut info.album),
+        "album artist" | "albumartist" => set(&mut info.album_artist),
+        "genre" => set(&mut info.genre),
+        "composer" => set(&mut info.composer),
+        "publisher" | "label" => set(&mut info.label),
+        "lyrics" | "unsyncedlyrics" => set(&mut info.lyrics),
+        "year" | "date" if info.year.is_none() => {
+            info.year = text.get(..4).and_then(|y| y.parse().ok());
+            info.release_date = parser::normalize_release_date(text);
+        }
+        "originalyear" | "originaldate" | "original date" if info.original_year.is_none() => {
+            info.original_year = text.get(..4).and_then(|y| y.parse().ok());
+            info.original_date = parser::normalize_release_date(text);
+        }
+        "track" => {
+            let mut parts = text.splitn(2, '/');
+            if info.track_number.is_none() {
+                info.track_number = parts.next().and_then(|n| n.trim().parse().ok());
+            }
+            if info.total_tracks.is_none() {
+                info.total_tracks = parts.next().and_then(|n| n.trim().parse().ok());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `end`에서 끝나는 Lyrics3 v2 블록을 읽는다: LYRICSBEGIN [ID(3) 길이(5) 값]... 크기(6) LYRICS200
+fn read_lyrics3v2(file: &mut File, end: u64, info: &mut TrackInfo) -> std::io::Result<Option<u64>> {
+    if end < 15 + 11 || read_at(file, end - 9, 9)? != b"LYRICS200" {
+        return Ok(None);
+    }
+    let Some(size) = ascii_number(&read_at(file, end - 15, 6)?) else {
+        return Ok(None);
+    };
+    if size < 11 || size + 15 > end {
+        return Ok(None);
+    }
+    let start = end - 15 - size;
+    let body = read_at(file, start, size as usize)?;
+    if !body.starts_with(b"LYRICSBEGIN") {
+        return Ok(None);
+    }
+
+    let mut pos = 11;
+    while pos + 8 <= body.len() {
+        let id = &body[pos..pos + 3];
+        let Some(len) = ascii_number(&body[pos + 3..pos + 8]) else {
+            break;
+        };
+        let Some(value) = body.get(pos + 8..pos + 8 + len as usize) else {
+            break;
+        };
+        pos += 8 + len as usize;
+        let key = match id {
+            b"ETT" => "title",
+            b"EAR" => "artist",
+            b"EAL" => "album",
+            b"LYR" => "lyrics",
+            _ => continue,
+        };
+        let text = decode(value);
+        if !text.trim().is_empty() {
+            apply_field(info, key, text.trim());
+        }
+    }
+    Ok(Some(start))
+}
+
+/// `end`에서 끝나는 Lyrics3 v1 블록(LYRICSBEGIN 가사 LYRICSEND)을 읽는다. ID3v1 바로 앞에만 올 수 있다.
+fn read_lyrics3v1(
+    file: &mut File,
+    end: u64,
+    has_id3v1: bool,
+    info: &mut TrackInfo,
+) -> std::io::Result<Option<u64>> {
+    if !has_id3v1 || end < 20 || read_at(file, end - 9, 9)? != b"LYRICSEND" {
+        return Ok(None);
+    }
+    let window = (LYRICS3V1_MAX + 20).min(end);
+    let tail = read_at(file, end - window, window as usize)?;
+    let Some(begin) = tail.windows(11).rposition(|w| w == b"LYRICSBEGIN") else {
+        return Ok(None);
+    };
+    let lyrics = decode(&tail[begin + 11..tail.len() - 9]);
+    if !lyrics.trim().is_empty() {
+        apply_field(info, "lyrics", lyrics.trim());
+    }
+    Ok(Some(end - window + begin as u64))
+}
+
+/// UTF-8이면 그대로, 아니면 CP949(EUC-KR)로 읽는다.
+fn decode(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => encoding_rs::EUC_KR.decode(bytes).0.into_owned(),
+    }
+}
+
+fn ascii_number(bytes: &[u8]) -> Option<u64> {
+    std::str::from_utf8(bytes).ok()?.parse().ok()
+}
+
+fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn read_at(file: &mut File, offset: u64, len: usize) -> std::io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = vec![0u8; len];
+    file.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn ape_item(key: &str, value: &[u8]) -> Vec<u8> {
+        let mut item = (value.len() as u32).to_le_bytes().to_vec();
+        item.extend_from_slice(&0u32.to_le_bytes());
+        item.extend_from_slice(key.as_bytes());
+        item.push(0);
+        item.extend_from_slice(value);
+        item
+    }
+
+    fn ape_tag(items: &[Vec<u8>]) -> Vec<u8> {
+        let body = items.concat();
+        let size = (body.len() + 32) as u32;
+        let block = |flags: u32| {
+            let mut b = b"APETAGEX".to_vec();
+            b.extend_from_slice(&2000u32.to_le_bytes());
+            b.extend_from_slice(&size.to_le_bytes());
+            b.extend_from_slice(&(items.len() as u32).to_le_bytes());
+            b.extend_from_slice(&flags.to_le_bytes());
+            b.extend_from_slice(&[0; 8]);
+            b
+        };
+        [block(0xA000_0000), body, block(0x8000_0000)].concat()
+    }
+
+    fn lyrics3v2(fields: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut body = b"LYRICSBEGIN".to_vec();
+        for (id, value) in fields {
+            body.extend_from_slice(id.as_bytes());
+            body.extend_from_slice(format!("{:05}", value.len()).as_bytes());
+            body.extend_from_slice(value);
+        }
+        let size = format!("{:06}", body.len());
+        [body, size.into_bytes(), b"LYRICS200".to_vec()].concat()
+    }
+
+    #[test]
+    fn test_detect_and_strip_legacy_tags() {
+        let dir = TempDir::new("legacy");
+        let path = dir.join("old.mp3");
+
+        // 제목은 CP949 ("밤편지")
+        let (title, _, _) = encoding_rs::EUC_KR.encode("밤편지");
+        let ape = ape_tag(&[
+            ape_item("Title", &title),
+            ape_item("Artist", "아이유".as_bytes()),
+            ape_item("Track", b"3/12"),
+            ape_item("Year", b"2017-03-24"),
+        ]);
+        let lyrics = lyrics3v2(&[("ETT", b"Other title"), ("LYR", "가사".as_bytes())]);
+        let mut id3v1 = b"TAG".to_vec();
+        id3v1.resize(128, 0);
+        let audio = b"ID3-AND-AUDIO".to_vec();
+        std::fs::write(&path, [audio.clone(), ape, lyrics, id3v1.clone()].concat()).unwrap();
+
+        let legacy = detect(&path).unwrap().unwrap();
+        assert_eq!(legacy.kinds, vec!["APEv2", "Lyrics3v2"]);
+        assert_eq!(legacy.start, audio.len() as u64);
+        // 먼저 읽은 Lyrics3의 제목이 우선한다
+        assert_eq!(legacy.info.title.as_deref(), Some("Other title"));
+        assert_eq!(legacy.info.artist().as_deref(), Some("아이유"));
+        assert_eq!(legacy.info.lyrics.as_deref(), Some("가사"));
+        assert_eq!(legacy.info.track_number, Some(3));
+        assert_eq!(legacy.info.total_tracks, Some(12));
+        assert_eq!(legacy.info.year, Some(2017));
+
+        strip(&path, &legacy).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), [audio, id3v1].concat());
+        assert!(detect(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_ape_cp949_value() {
+        let mut info = TrackInfo::default();
+        let (title, _, _) = encoding_rs::EUC_KR.encode("밤편지");
+        parse_ape_items(&ape_item("TITLE", &title), false, &mut info);
+        assert_eq!(info.title.as_deref(), Some("밤편지"));
+    }
+}
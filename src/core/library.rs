@@ -0,0 +1,705 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::query::Query;
+use crate::core::skip_list::SkipList;
+use crate::core::{artwork, audio, cancel, filelock, history, scanner};
+use crate::models::{EncoderInfo, Mp3File, TrackInfo};
+use crate::sources::cache::{RememberedMatch, SourceCache};
+
+/// 스키마 버전 (`PRAGMA user_version`). 바꾸면 `migrate`에 단계를 추가한다.
+const SCHEMA_VERSION: i32 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE files (
+    path         TEXT PRIMARY KEY,
+    size         INTEGER NOT NULL,
+    mtime        INTEGER NOT NULL,
+    content_hash TEXT,
+    has_tags     INTEGER NOT NULL,
+    tags         TEXT,
+    encoder      TEXT,
+    title        TEXT,
+    artist       TEXT,
+    album        TEXT,
+    album_artist TEXT,
+    genre        TEXT,
+    year         INTEGER,
+    track        INTEGER,
+    disc         INTEGER,
+    art_sha      TEXT,
+    art_width    INTEGER,
+    art_height   INTEGER,
+    art_bytes    INTEGER,
+    duration_ms  INTEGER,
+    bitrate      INTEGER,
+    scanned_at   INTEGER NOT NULL
+);
+CREATE INDEX files_content_hash ON files(content_hash);
+CREATE INDEX files_album ON files(album_artist, album);
+CREATE TABLE art (
+    sha  TEXT PRIMARY KEY,
+    data BLOB NOT NULL
+);
+CREATE TABLE history (
+    path    TEXT NOT NULL,
+    time    TEXT NOT NULL,
+    source  TEXT NOT NULL,
+    changes TEXT NOT NULL,
+    UNIQUE (path, time, changes)
+);
+CREATE TABLE decisions (
+    content_hash TEXT NOT NULL,
+    kind         TEXT NOT NULL,
+    path         TEXT,
+    value        TEXT,
+    PRIMARY KEY (content_hash, kind)
+);
+";
+
+/// 건너뛴 파일 (`fetch`의 "이 파일 건너뛰기")
+pub const DECISION_SKIP: &str = "skip";
+/// 같은 오디오에 적용한 결과 (`auto`의 결과 기억)
+pub const DECISION_MATCH: &str = "match";
+
+/// 스캔한 파일, 태그, 오디오 해시, 변경 기록, 결정(건너뜀, 기억한 결과)을 모아 두는 SQLite 데이터베이스.
+/// 크기와 수정 시각이 그대로인 파일은 다시 읽지 않아 큰 라이브러리도 빠르게 다시 스캔한다.
+/// 앨범 아트는 같은 이미지를 한 번만 저장한다.
+pub struct Library {
+    conn: Connection,
+}
+
+/// `Library::sync` 결과.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+}
+
+/// 다른 컴퓨터로 옮기는 결정 하나. `library export`/`import`의 JSON Lines 한 줄이다.
+/// 오디오 해시로 찾으므로 경로가 다른 같은 파일(노트북과 NAS의 사본)에 그대로 쓸 수 있다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    /// 태그를 뺀 오디오 해시 (`audio::content_hash`)
+    pub hash: String,
+    /// `DECISION_SKIP` 또는 `DECISION_MATCH`
+    pub kind: String,
+    /// 결정할 때의 경로 (참고용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// 기억한 결과 (`match`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remembered: Option<RememberedMatch>,
+    /// 기억한 결과의 앨범 아트 (base64)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub art: Option<String>,
+}
+
+/// `Library::import_records` 결과.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub skipped: usize,
+    pub remembered: usize,
+}
+
+/// `Library::stats` 결과.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LibraryStats {
+    pub files: usize,
+    pub untagged: usize,
+    pub albums: usize,
+    pub artists: usize,
+    pub duration_ms: u64,
+    pub bytes: u64,
+    pub missing_year: usize,
+    pub missing_art: usize,
+    pub missing_album: usize,
+    /// 연도가 빠진 파일이 있는 앨범 수
+    pub albums_missing_year: usize,
+    /// 출처별 변경 기록 수 (많은 순)
+    pub history: Vec<(String, usize)>,
+    pub skipped: usize,
+    pub remembered: usize,
+    /// 마지막 스캔 시각 (유닉스 초)
+    pub last_scan: Option<i64>,
+}
+
+/// 파일의 크기와 수정 시각(유닉스 나노초). 바뀌었는지 비교하는 데 쓴다.
+fn stamp(path: &Path) -> Option<(i64, i64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some((meta.len() as i64, mtime as i64))
+}
+
+/// 데이터베이스 키로 쓰는 절대 경로. 실행 위치가 달라도 같은 파일은 같은 키가 된다.
+fn key(path: &Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// `root` 아래 경로의 키 접두어 ("/music/" 형식).
+fn prefix(root: &Path) -> String {
+    let mut prefix = key(root);
+    if !prefix.ends_with(std::path::MAIN_SEPARATOR) {
+        prefix.push(std::path::MAIN_SEPARATOR);
+    }
+    prefix
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+fn sha256(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+impl Library {
+    /// 데이터베이스를 열고, 없으면 만든다.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("디렉토리를 만들 수 없습니다: {}", dir.display()))?;
+        }
+        let conn = Connection::open(path).with_context(|| {
+            format!(
+                "라이브러리 데이터베이스를 열 수 없습니다: {}",
+                path.display()
+            )
+        })?;
+        Self::init(conn)
+    }
+
+    #[cfg(test)]
+    fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version == 0 {
+            conn.execute_batch(SCHEMA)?;
+            conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        } else if version > SCHEMA_VERSION {
+            anyhow::bail!(
+                "더 새로운 버전의 mp3tag가 만든 라이브러리 데이터베이스입니다 (스키마 {})",
+                version
+            );
+        }
+        Ok(Self { conn })
+    }
+
+    /// `paths`(스캔한 MP3 경로)를 데이터베이스와 맞추고 그 파일들을 반환한다. 크기와 수정 시각이 저장한 값과
+    /// 같은 파일은 다시 읽지 않는다. `roots`(입력 디렉토리) 아래에 있었지만 이번에 찾지 못한 파일은 지운다.
+    /// Ctrl-C로 멈추면 그때까지 읽은 파일만 반영하고 지우지 않는다.
+    pub fn sync(
+        &mut self,
+        paths: &[PathBuf],
+        roots: &[PathBuf],
+        mut on_progress: impl FnMut(&Path),
+    ) -> Result<(Vec<Mp3File>, SyncSummary)> {
+        let tx = self.conn.transaction()?;
+        let mut summary = SyncSummary::default();
+        let mut files = Vec::with_capacity(paths.len());
+        let mut seen = BTreeSet::new();
+        let scanned_at = now();
+        for path in paths {
+            if cancel::requested() {
+                break;
+            }
+            let key = key(path);
+            // 읽는 도중 바뀐 것도 기록 전에 알아챌 수 있도록 비교보다 먼저 기억한다
+            filelock::remember(path);
+            let stamp = stamp(path);
+            let stored: Option<(i64, i64)> = tx
+                .query_row(
+                    "SELECT size, mtime FROM files WHERE path = ?1",
+                    [&key],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            let cached = match (stored, stamp) {
+                (Some(stored), Some(stamp)) if stored == stamp => load_file(&tx, &key, path)?,
+                _ => None,
+            };
+            let file = match cached {
+                Some(file) => {
+                    summary.unchanged += 1;
+                    file
+                }
+                None => {
+                    let file = scanner::load_files(std::slice::from_ref(path), |_| {}).remove(0);
+                    if let Some((size, mtime)) = stamp {
+                        store_file(&tx, &key, size, mtime, &file, scanned_at)?;
+                    }
+                    match stored {
+                        Some(_) => summary.updated += 1,
+                        None => summary.added += 1,
+                    }
+                    file
+                }
+            };
+            on_progress(path);
+            seen.insert(key);
+            files.push(file);
+        }
+
+        if !cancel::requested() {
+            for root in roots.iter().filter(|r| r.is_dir()) {
+                let prefix = prefix(root);
+                let mut stmt =
+                    tx.prepare("SELECT path FROM files WHERE substr(path, 1, ?2) = ?1")?;
+                let under: Vec<String> = stmt
+                    .query_map(params![prefix, prefix.chars().count()], |row| row.get(0))?
+                    .collect::<rusqlite::Result<_>>()?;
+                for path in under.iter().filter(|p| !seen.contains(*p)) {
+                    tx.execute("DELETE FROM files WHERE path = ?1", [path])?;
+                    summary.removed += 1;
+                }
+            }
+            tx.execute(
+                "DELETE FROM art WHERE sha NOT IN (SELECT art_sha FROM files WHERE art_sha IS NOT NULL)",
+                [],
+            )?;
+        }
+        tx.commit()?;
+        Ok((files, summary))
+    }
+
+    /// `root` 아래에 저장된 파일을 읽지 않고 그대로 반환한다 (경로순). GUI가 스캔을 기다리지 않고 목록을 먼저 보일 때 쓴다.
+    pub fn load(&self, root: &Path) -> Result<Vec<Mp3File>> {
+        let prefix = prefix(root);
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path FROM files WHERE substr(path, 1, ?2) = ?1 ORDER BY path")?;
+        let paths: Vec<String> = stmt
+            .query_map(params![prefix, prefix.chars().count()], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut files = Vec::new();
+        for path in paths {
+            if let Some(file) = load_file(&self.conn, &path, Path::new(&path))? {
+                files.push(file);
+            }
+        }
+        Ok(files)
+    }
+
+    /// 검색식과 맞는 파일을 경로순으로 반환한다. 파일은 읽지 않고 저장된 태그를 쓴다.
+    pub fn query(&self, query: &Query) -> Result<Vec<Mp3File>> {
+        let (clause, params) = query.to_sql();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT path FROM files WHERE {} ORDER BY path",
+            clause
+        ))?;
+        let paths: Vec<String> = stmt
+            .query_map(rusqlite::params_from_iter(params), |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut files = Vec::new();
+        for path in paths {
+            if let Some(file) = load_file(&self.conn, &path, Path::new(&path))? {
+                files.push(file);
+            }
+        }
+        Ok(files)
+    }
+
+    /// `dirs`의 `.mp3tag-history.jsonl`을 가져온다. 이미 가져온 기록은 건너뛴다.
+    pub fn import_history<'a>(&self, dirs: impl IntoIterator<Item = &'a Path>) -> Result<usize> {
+        let mut imported = 0;
+        for dir in dirs {
+            for entry in history::load(dir)? {
+                imported += self.conn.execute(
+                    "INSERT OR IGNORE INTO history (path, time, source, changes) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        key(&dir.join(&entry.file)),
+                        entry.time,
+                        entry.source,
+                        serde_json::to_string(&entry.changes)?
+                    ],
+                )?;
+            }
+        }
+        Ok(imported)
+    }
+
+    /// 캐시 디렉토리의 건너뛴 파일 목록과, 저장된 파일의 오디오에 기억해 둔 결과를 가져온다.
+    pub fn import_decisions(&self, cache_dir: &Path) -> Result<()> {
+        let skip_list = SkipList::load(cache_dir);
+        self.conn
+            .execute("DELETE FROM decisions WHERE kind = ?1", [DECISION_SKIP])?;
+        for (hash, path) in skip_list.entries() {
+            self.record_decision(hash, DECISION_SKIP, Some(path), None)?;
+        }
+
+        let cache = SourceCache::new(cache_dir.to_path_buf());
+        let mut stmt = self
+            .conn
+            .prepare("SELECT content_hash, path FROM files WHERE content_hash IS NOT NULL")?;
+        let hashes: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (hash, path) in hashes {
+            if let Some(remembered) = cache.load_match(&hash) {
+                let value = serde_json::to_string(&remembered)?;
+                self.record_decision(&hash, DECISION_MATCH, Some(Path::new(&path)), Some(&value))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 캐시 디렉토리의 결정을 다시 가져온 뒤 모두 내보낸다 (건너뜀, 기억한 결과 순).
+    pub fn export_records(&self, cache_dir: &Path) -> Result<Vec<DecisionRecord>> {
+        self.import_decisions(cache_dir)?;
+        let cache = SourceCache::new(cache_dir.to_path_buf());
+        let mut stmt = self.conn.prepare(
+            "SELECT content_hash, kind, path, value FROM decisions ORDER BY kind DESC, content_hash",
+        )?;
+        let rows: Vec<(String, String, Option<String>, Option<String>)> = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut records = Vec::with_capacity(rows.len());
+        for (hash, kind, path, value) in rows {
+            // 캐시에 있으면 앨범 아트까지 채워진 결과를 쓴다
+            let remembered = match kind.as_str() {
+                DECISION_MATCH => cache
+                    .load_match(&hash)
+                    .or(value.map(|v| serde_json::from_str(&v)).transpose()?),
+                _ => None,
+            };
+            let art = remembered
+                .as_ref()
+                .and_then(|r| r.track.album_art.as_deref())
+                .map(|art| base64::engine::general_purpose::STANDARD.encode(art));
+            records.push(DecisionRecord {
+                hash,
+                kind,
+                path: path.map(PathBuf::from),
+                remembered,
+                art,
+            });
+        }
+        Ok(records)
+    }
+
+    /// 다른 컴퓨터에서 내보낸 결정을 캐시 디렉토리(건너뛴 파일 목록, 기억한 결과)와 데이터베이스에 넣는다.
+    /// 이후 `fetch`는 건너뛴 파일을 다시 묻지 않고, `auto`는 같은 오디오에 기억한 결과를 검색 없이 적용한다.
+    pub fn import_records(
+        &self,
+        cache_dir: &Path,
+        records: &[DecisionRecord],
+    ) -> Result<ImportSummary> {
+        let mut skip_list = SkipList::load(cache_dir);
+        let cache = SourceCache::new(cache_dir.to_path_buf());
+        let mut summary = ImportSummary::default();
+        for record in records {
+            match (record.kind.as_str(), &record.remembered) {
+                (DECISION_SKIP, _) => {
+                    let path = record.path.clone().unwrap_or_default();
+                    skip_list.add(record.hash.clone(), &path);
+                    self.record_decision(&record.hash, DECISION_SKIP, Some(&path), None)?;
+                    summary.skipped += 1;
+                }
+                (DECISION_MATCH, Some(remembered)) => {
+                    let mut remembered = remembered.clone();
+                    remembered.track.album_art = record
+                        .art
+                        .as_deref()
+                        .and_then(|art| base64::engine::general_purpose::STANDARD.decode(art).ok());
+                    cache.store_match(&record.hash, &remembered);
+                    let value = serde_json::to_string(&remembered)?;
+                    self.record_decision(
+                        &record.hash,
+                        DECISION_MATCH,
+                        record.path.as_deref(),
+                        Some(&value),
+                    )?;
+                    summary.remembered += 1;
+                }
+                _ => {}
+            }
+        }
+        skip_list.save()?;
+        Ok(summary)
+    }
+
+    /// 오디오(`audio::content_hash`)에 대한 결정을 기록한다. 같은 종류의 이전 결정은 바꾼다.
+    pub fn record_decision(
+        &self,
+        content_hash: &str,
+        kind: &str,
+        path: Option<&Path>,
+        value: Option<&str>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO decisions (content_hash, kind, path, value) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                content_hash,
+                kind,
+                path.map(|p| p.to_string_lossy().into_owned()),
+                value
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 라이브러리 전체의 통계.
+    pub fn stats(&self) -> Result<LibraryStats> {
+        let count = |sql: &str| -> Result<usize> {
+            Ok(self.conn.query_row(sql, [], |row| row.get::<_, i64>(0))? as usize)
+        };
+        let mut stmt = self.conn.prepare(
+            "SELECT source, COUNT(*) FROM history GROUP BY source ORDER BY COUNT(*) DESC, source",
+        )?;
+        let history = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(LibraryStats {
+            files: count("SELECT COUNT(*) FROM files")?,
+            untagged: count("SELECT COUNT(*) FROM files WHERE has_tags = 0")?,
+            albums: count(
+                "SELECT COUNT(*) FROM (SELECT DISTINCT album_artist, album FROM files WHERE album IS NOT NULL)",
+            )?,
+            artists: count("SELECT COUNT(DISTINCT artist) FROM files")?,
+            duration_ms: count("SELECT COALESCE(SUM(duration_ms), 0) FROM files")? as u64,
+            bytes: count("SELECT COALESCE(SUM(size), 0) FROM files")? as u64,
+            missing_year: count("SELECT COUNT(*) FROM files WHERE year IS NULL")?,
+            missing_art: count("SELECT COUNT(*) FROM files WHERE art_sha IS NULL")?,
+            missing_album: count("SELECT COUNT(*) FROM files WHERE album IS NULL")?,
+            albums_missing_year: count(
+                "SELECT COUNT(*) FROM (SELECT DISTINCT album_artist, album FROM files WHERE album IS NOT NULL AND year IS NULL)",
+            )?,
+            history,
+            skipped: count(&format!(
+                "SELECT COUNT(*) FROM decisions WHERE kind = '{}'",
+                DECISION_SKIP
+            ))?,
+            remembered: count(&format!(
+                "SELECT COUNT(*) FROM decisions WHERE kind = '{}'",
+                DECISION_MATCH
+            ))?,
+            last_scan: self
+                .conn
+                .query_row("SELECT MAX(scanned_at) FROM files", [], |row| row.get(0))?,
+        })
+    }
+}
+
+/// 저장한 행을 Mp3File로 만든다. `path`는 반환할 파일의 경로(입력한 형식 그대로)다.
+fn load_file(conn: &Connection, key: &str, path: &Path) -> Result<Option<Mp3File>> {
+    let row = conn
+        .query_row(
+            "SELECT has_tags, tags, encoder, art.data FROM files
+             LEFT JOIN art ON art.sha = files.art_sha WHERE path = ?1",
+            [key],
+            |row| {
+                let has_tags: bool = row.get(0)?;
+                let tags: Option<String> = row.get(1)?;
+                let encoder: Option<String> = row.get(2)?;
+                let art: Option<Vec<u8>> = row.get(3)?;
+                Ok((has_tags, tags, encoder, art))
+            },
+        )
+        .optional()?;
+    let Some((has_tags, tags, encoder, art)) = row else {
+        return Ok(None);
+    };
+    let current_tags = match tags {
+        Some(json) => {
+            let mut tags: TrackInfo = serde_json::from_str(&json)?;
+            tags.album_art = art;
+            Some(tags)
+        }
+        None => None,
+    };
+    let encoder: Option<EncoderInfo> = encoder.map(|e| serde_json::from_str(&e)).transpose()?;
+    Ok(Some(Mp3File {
+        path: path.to_path_buf(),
+        current_tags,
+        has_tags,
+        encoder,
+    }))
+}
+
+fn store_file(
+    conn: &Connection,
+    key: &str,
+    size: i64,
+    mtime: i64,
+    file: &Mp3File,
+    scanned_at: i64,
+) -> Result<()> {
+    let tags = file.current_tags.as_ref();
+    let art = tags.and_then(|t| t.album_art.as_deref());
+    let art_sha = art.map(sha256);
+    if let (Some(sha), Some(data)) = (&art_sha, art) {
+        conn.execute(
+            "INSERT OR IGNORE INTO art (sha, data) VALUES (?1, ?2)",
+            params![sha, data],
+        )?;
+    }
+    let dimensions = art.and_then(artwork::image_dimensions);
+    conn.execute(
+        "INSERT OR REPLACE INTO files (path, size, mtime, content_hash, has_tags, tags, encoder,
+             title, artist, album, album_artist, genre, year, track, disc,
+             art_sha, art_width, art_height, art_bytes, duration_ms, bitrate, scanned_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15,
+             ?16, ?17, ?18, ?19, ?20, ?21, ?22)",
+        params![
+            key,
+            size,
+            mtime,
+            audio::content_hash(&file.path),
+            file.has_tags,
+            tags.map(serde_json::to_string).transpose()?,
+            file.encoder
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?,
+            tags.and_then(|t| t.title.clone()),
+            tags.and_then(|t| t.artist()),
+            tags.and_then(|t| t.album.clone()),
+            tags.and_then(|t| t.album_artist.clone()),
+            tags.and_then(|t| t.genre.clone()),
+            tags.and_then(|t| t.year),
+            tags.and_then(|t| t.track_number),
+            tags.and_then(|t| t.disc_number),
+            art_sha,
+            dimensions.map(|d| d.0),
+            dimensions.map(|d| d.1),
+            art.map(|a| a.len() as i64),
+            audio::duration_ms(&file.path).map(|ms| ms as i64),
+            audio::bitrate_kbps(&file.path),
+            scanned_at,
+        ],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_export_import_records() {
+        let (laptop, nas) = (TempDir::new("library-laptop"), TempDir::new("library-nas"));
+        let mut skip_list = SkipList::load(&laptop);
+        skip_list.add("aaa".to_string(), Path::new("Live/bootleg.mp3"));
+        skip_list.save().unwrap();
+        let library = Library::in_memory().unwrap();
+        let remembered = RememberedMatch {
+            track: TrackInfo {
+                title: Some("Blueming".to_string()),
+                source: "spotify".to_string(),
+                source_id: Some("1".to_string()),
+                album_art: Some(vec![1, 2, 3]),
+                ..Default::default()
+            },
+            score: 0.95,
+        };
+        let value = serde_json::to_string(&remembered).unwrap();
+        library
+            .record_decision("bbb", DECISION_MATCH, None, Some(&value))
+            .unwrap();
+        SourceCache::new(laptop.to_path_buf()).store_match("bbb", &remembered);
+
+        let records = library.export_records(&laptop).unwrap();
+        let lines: Vec<String> = records
+            .iter()
+            .map(|r| serde_json::to_string(r).unwrap())
+            .collect();
+        let records: Vec<DecisionRecord> = lines
+            .iter()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(records[0].kind, DECISION_SKIP);
+        assert!(records[1].art.is_some());
+
+        let summary = Library::in_memory()
+            .unwrap()
+            .import_records(&nas, &records)
+            .unwrap();
+        assert_eq!((summary.skipped, summary.remembered), (1, 1));
+        assert!(SkipList::load(&nas).contains("aaa"));
+        let imported = SourceCache::new(nas.to_path_buf())
+            .load_match("bbb")
+            .unwrap();
+        assert_eq!(imported.track.title.as_deref(), Some("Blueming"));
+        assert_eq!(imported.track.album_art, Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_incremental_sync() {
+        let dir = TempDir::new("library-sync");
+        let (a, b) = (dir.join("a.mp3"), dir.join("b.mp3"));
+        std::fs::write(&a, vec![0u8; 2000]).unwrap();
+        std::fs::write(&b, vec![0u8; 3000]).unwrap();
+        let info = TrackInfo {
+            title: Some("Blueming".to_string()),
+            year: Some(2019),
+            album_art: Some(vec![0xFF, 0xD8, 0xFF, 0xE0]),
+            ..Default::default()
+        };
+        crate::core::tagger::write_tags(&a, &info, &Default::default()).unwrap();
+
+        let mut library = Library::in_memory().unwrap();
+        let paths = vec![a.clone(), b.clone()];
+        let roots = vec![dir.to_path_buf()];
+        let (files, summary) = library.sync(&paths, &roots, |_| {}).unwrap();
+        assert_eq!((summary.added, summary.unchanged), (2, 0));
+        assert_eq!(files[0].current_tags.as_ref().unwrap().year, Some(2019));
+
+        // 바뀌지 않은 파일은 저장한 태그와 아트를 그대로 쓴다
+        let (files, summary) = library.sync(&paths, &roots, |_| {}).unwrap();
+        assert_eq!((summary.added, summary.unchanged), (0, 2));
+        let tags = files[0].current_tags.as_ref().unwrap();
+        assert_eq!(tags.title.as_deref(), Some("Blueming"));
+        assert_eq!(
+            tags.album_art.as_deref(),
+            Some(&[0xFF, 0xD8, 0xFF, 0xE0][..])
+        );
+        assert!(!files[1].has_tags);
+        assert_eq!(library.load(&dir).unwrap().len(), 2);
+
+        // 없어진 파일은 지운다
+        std::fs::remove_file(&b).unwrap();
+        let (_, summary) = library.sync(&paths[..1], &roots, |_| {}).unwrap();
+        assert_eq!((summary.unchanged, summary.removed), (1, 1));
+
+        let found = library.query(&Query::parse("blue year:>=2019 has:art").unwrap());
+        assert_eq!(found.unwrap().len(), 1);
+        let found = library.query(&Query::parse("missing:tags").unwrap());
+        assert!(found.unwrap().is_empty());
+
+        let stats = library.stats().unwrap();
+        assert_eq!(
+            (stats.files, stats.missing_year, stats.missing_art),
+            (1, 0, 0)
+        );
+        assert_eq!(stats.missing_album, 1);
+    }
+}
@@ -0,0 +1,298 @@
+use std::sync::OnceLock;
+
+use crate::config::ArtConfig;
+use crate::core::artwork::{self, ArtInfo};
+use crate::core::{audio, tagger};
+use crate::i18n;
+use crate::models::Mp3File;
+
+/// `scan` 표에 넣을 수 있는 열. `--columns`와 `--sort`에 쓴다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Column {
+    Path,
+    File,
+    Title,
+    Artist,
+    Album,
+    AlbumArtist,
+    Track,
+    Disc,
+    Year,
+    Genre,
+    Tags,
+    Art,
+    /// 앨범 아트의 크기, 형식, 바이트 수와 경고
+    ArtInfo,
+    Encoder,
+    Bitrate,
+    Duration,
+    /// 자동 작업의 출처 기록 (`[write] provenance`)
+    Provenance,
+    /// `auto --write-tier`가 남긴 신뢰 등급
+    MatchTier,
+}
+
+/// `--columns`를 주지 않을 때의 열 (예전 scan 표와 같음).
+pub const DEFAULT_COLUMNS: &[Column] = &[
+    Column::File,
+    Column::Title,
+    Column::Artist,
+    Column::Album,
+    Column::Tags,
+    Column::Encoder,
+];
+
+/// 정렬 기준 값. 값이 없는 파일은 항상 뒤로 간다.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Number(u64),
+    Text(String),
+    Missing,
+}
+
+impl Column {
+    /// `--columns`에 쓰는 이름 ("album-artist"). CSV/TSV 머리글에도 쓴다.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Path => "path",
+            Column::File => "file",
+            Column::Title => "title",
+            Column::Artist => "artist",
+            Column::Album => "album",
+            Column::AlbumArtist => "album-artist",
+            Column::Track => "track",
+            Column::Disc => "disc",
+            Column::Year => "year",
+            Column::Genre => "genre",
+            Column::Tags => "tags",
+            Column::Art => "art",
+            Column::ArtInfo => "art-info",
+            Column::Encoder => "encoder",
+            Column::Bitrate => "bitrate",
+            Column::Duration => "duration",
+            Column::Provenance => "provenance",
+            Column::MatchTier => "match-tier",
+        }
+    }
+
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Path => i18n::pick("경로", "Path"),
+            Column::File => i18n::pick("파일", "File"),
+            Column::Title => i18n::pick("제목", "Title"),
+            Column::Artist => i18n::pick("아티스트", "Artist"),
+            Column::Album => i18n::pick("앨범", "Album"),
+            Column::AlbumArtist => i18n::pick("앨범 아티스트", "Album artist"),
+            Column::Track => i18n::pick("트랙", "Track"),
+            Column::Disc => i18n::pick("디스크", "Disc"),
+            Column::Year => i18n::pick("연도", "Year"),
+            Column::Genre => i18n::pick("장르", "Genre"),
+            Column::Tags => i18n::pick("태그", "Tags"),
+            Column::Art => i18n::pick("아트", "Art"),
+            Column::ArtInfo => i18n::pick("아트 정보", "Art info"),
+            Column::Encoder => i18n::pick("인코더", "Encoder"),
+            Column::Bitrate => i18n::pick("비트레이트", "Bitrate"),
+            Column::Duration => i18n::pick("재생 시간", "Duration"),
+            Column::Provenance => i18n::pick("출처 기록", "Provenance"),
+            Column::MatchTier => i18n::pick("신뢰 등급", "Match tier"),
+        }
+    }
+
+    /// 표에 표시할 값. 태그가 없거나 값이 없으면 "-".
+    pub fn value(&self, file: &Mp3File) -> String {
+        let tags = file.current_tags.as_ref();
+        let text = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        let numbered = |n: Option<u32>, total: Option<u32>| match (n, total) {
+            (Some(n), Some(total)) => format!("{}/{}", n, total),
+            (Some(n), None) => n.to_string(),
+            _ => "-".to_string(),
+        };
+        match self {
+            Column::Path => file.path.display().to_string(),
+            Column::File => file.filename().to_string(),
+            Column::Title => text(tags.map(|t| t.display_title().to_string())),
+            Column::Artist => text(tags.map(|t| t.display_artist())),
+            Column::Album => text(tags.map(|t| t.display_album().to_string())),
+            Column::AlbumArtist => text(tags.and_then(|t| t.album_artist.clone())),
+            Column::Track => numbered(
+                tags.and_then(|t| t.track_number),
+                tags.and_then(|t| t.total_tracks),
+            ),
+            Column::Disc => numbered(
+                tags.and_then(|t| t.disc_number),
+                tags.and_then(|t| t.total_discs),
+            ),
+            Column::Year => text(tags.and_then(|t| t.year).map(|y| y.to_string())),
+            Column::Genre => text(tags.and_then(|t| t.genre.clone())),
+            Column::Tags => match file.has_tags {
+                true => i18n::pick("있음", "yes").to_string(),
+                false => i18n::pick("없음", "no").to_string(),
+            },
+            Column::Art => match art(file) {
+                Some(data) => match artwork::image_dimensions(data) {
+                    Some((width, height)) => format!("{}x{}", width, height),
+                    None => i18n::pick("있음", "yes").to_string(),
+                },
+                None => "-".to_string(),
+            },
+            Column::ArtInfo => match art(file) {
+                Some(data) => {
+                    let info = ArtInfo::of(data);
+                    let (max_bytes, min_size) = art_warnings();
+                    match info.warning(max_bytes, min_size) {
+                        Some(warning) => format!("{} ({})", info.summary(), warning),
+                        None => info.summary(),
+                    }
+                }
+                None => "-".to_string(),
+            },
+            Column::Encoder => file
+                .encoder
+                .as_ref()
+                .map(|e| e.summary())
+                .unwrap_or_default(),
+            Column::Bitrate => text(audio::bitrate_kbps(&file.path).map(|b| format!("{} kbps", b))),
+            Column::Duration => text(
+                audio::duration_ms(&file.path)
+                    .map(|ms| format!("{}:{:02}", ms / 60_000, ms / 1000 % 60)),
+            ),
+            Column::Provenance => text(tagger::provenance(&file.path)),
+            Column::MatchTier => text(tagger::match_tier(&file.path)),
+        }
+    }
+
+    /// 정렬 기준 값. 번호, 연도, 비트레이트, 재생 시간은 숫자로, 나머지는 대소문자 구분 없이 비교한다.
+    fn sort_key(&self, file: &Mp3File) -> SortKey {
+        let tags = file.current_tags.as_ref();
+        let number = |n: Option<u64>| n.map_or(SortKey::Missing, SortKey::Number);
+        match self {
+            // 디스크가 먼저 오도록 디스크 번호(없으면 1)를 앞자리에 둔다
+            Column::Track => number(tags.and_then(|t| {
+                Some(t.disc_number.unwrap_or(1) as u64 * 10_000 + t.track_number? as u64)
+            })),
+            Column::Disc => number(tags.and_then(|t| t.disc_number).map(u64::from)),
+            Column::Year => number(tags.and_then(|t| t.year).map(|y| y.max(0) as u64)),
+            Column::Bitrate => number(audio::bitrate_kbps(&file.path).map(u64::from)),
+            Column::Duration => number(audio::duration_ms(&file.path)),
+            Column::Art => {
+                number(art(file).map(|data| {
+                    artwork::image_dimensions(data).map_or(0, |(w, h)| w.min(h) as u64)
+                }))
+            }
+            Column::ArtInfo => number(art(file).map(|data| data.len() as u64)),
+            _ => match self.value(file) {
+                value if value == "-" || value.is_empty() => SortKey::Missing,
+                value => SortKey::Text(value.to_lowercase()),
+            },
+        }
+    }
+}
+
+fn art(file: &Mp3File) -> Option<&[u8]> {
+    file.current_tags.as_ref()?.album_art.as_deref()
+}
+
+/// art-info 열의 경고 기준 (바이트 수, 짧은 변 픽셀 수).
+static ART_WARNINGS: OnceLock<(u64, u32)> = OnceLock::new();
+
+/// art-info 열에서 경고할 앨범 아트 크기를 설정에서 정한다. 처음 한 번만 적용된다.
+pub fn set_art_warnings(art: &ArtConfig) {
+    let _ = ART_WARNINGS.set((art.warn_max_bytes(), art.warn_min_size()));
+}
+
+/// `set_art_warnings` 전에는 설정 기본값.
+fn art_warnings() -> (u64, u32) {
+    *ART_WARNINGS.get_or_init(|| {
+        let art = ArtConfig::default();
+        (art.warn_max_bytes(), art.warn_min_size())
+    })
+}
+
+/// 파일에 앨범 아트가 없는지 확인한다.
+pub fn missing_art(file: &Mp3File) -> bool {
+    art(file).is_none()
+}
+
+/// `fetch --incomplete`로 채울 필드.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Field {
+    Year,
+    Art,
+    Album,
+}
+
+impl Field {
+    /// `--incomplete`에 값을 주지 않을 때 확인하는 필드.
+    pub const ALL: &'static [Field] = &[Field::Year, Field::Art, Field::Album];
+
+    /// 파일에 이 필드가 비어 있는지. 태그가 없는 파일은 모든 필드가 비어 있다.
+    pub fn is_missing(&self, file: &Mp3File) -> bool {
+        let Some(tags) = file.current_tags.as_ref() else {
+            return true;
+        };
+        match self {
+            Field::Year => tags.year.is_none() && tags.release_date.is_none(),
+            Field::Art => tags.album_art.is_none(),
+            Field::Album => tags.album.as_deref().is_none_or(|a| a.trim().is_empty()),
+        }
+    }
+}
+
+/// `column` 값으로 파일을 정렬한다. 값이 같으면 원래 순서(경로순)를 유지한다.
+pub fn sort_files(files: &mut [Mp3File], column: Column) {
+    files.sort_by_cached_key(|file| column.sort_key(file));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TrackInfo;
+    use std::path::PathBuf;
+
+    fn file(path: &str, artist: &str, track: Option<u32>, year: Option<i32>) -> Mp3File {
+        Mp3File {
+            path: PathBuf::from(path),
+            current_tags: Some(TrackInfo {
+                artists: vec![artist.to_string()],
+                track_number: track,
+                year,
+                ..Default::default()
+            }),
+            has_tags: true,
+            encoder: None,
+        }
+    }
+
+    fn paths(files: &[Mp3File]) -> Vec<&str> {
+        files.iter().map(|f| f.path.to_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_sort_files() {
+        let mut files = vec![
+            file("a.mp3", "b", Some(10), None),
+            file("b.mp3", "A", Some(2), Some(2019)),
+            file("c.mp3", "c", None, Some(2001)),
+        ];
+        sort_files(&mut files, Column::Artist);
+        assert_eq!(paths(&files), ["b.mp3", "a.mp3", "c.mp3"]);
+        // 숫자로 비교하고 값이 없으면 뒤로 보낸다
+        sort_files(&mut files, Column::Track);
+        assert_eq!(paths(&files), ["b.mp3", "a.mp3", "c.mp3"]);
+        sort_files(&mut files, Column::Year);
+        assert_eq!(paths(&files), ["c.mp3", "b.mp3", "a.mp3"]);
+
+        assert_eq!(Column::Track.value(&files[0]), "-");
+        assert_eq!(Column::Year.value(&files[2]), "-");
+        assert!(missing_art(&files[0]));
+        assert!(Field::Year.is_missing(&files[2]));
+        assert!(!Field::Year.is_missing(&files[0]));
+        assert!(Field::Album.is_missing(&files[0]));
+        let untagged = Mp3File {
+            current_tags: None,
+            has_tags: false,
+            ..file("d.mp3", "d", None, None)
+        };
+        assert!(Field::ALL.iter().all(|f| f.is_missing(&untagged)));
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::renamer;
+
+/// Windows 확장 경로(`\\?\C:\...`, `\\?\UNC\server\share\...`)를 일반 경로(`C:\...`, `\\server\share\...`)로 바꾼다.
+///
+/// 표준 라이브러리가 MAX_PATH(260자)를 넘는 경로를 열 때 확장 경로로 바꿔 주므로 일반 경로로 다뤄도 된다.
+/// 입력 경로를 한 형태로 맞춰 두면 글롭(`?`), 상대 경로 계산(`strip_prefix`), 공통 디렉토리 비교가
+/// 어긋나지 않고, 훅이나 출력에도 익숙한 형태로 보인다. 일반 경로로 쓰면 뜻이 바뀌는 이름
+/// (끝의 마침표/공백, 예약 장치 이름, `/`, `.`/`..`)이 있으면 그대로 둔다. Windows가 아니면 그대로 반환한다.
+pub fn normalize(path: &Path) -> PathBuf {
+    if !cfg!(windows) {
+        return path.to_path_buf();
+    }
+    match path.to_str().and_then(strip_verbatim) {
+        Some(plain) => PathBuf::from(plain),
+        None => path.to_path_buf(),
+    }
+}
+
+/// 경로의 문자 수. 확장 경로 접두사(`\\?\`, `\\?\UNC\`의 `?\UNC\`)는 세지 않는다.
+pub fn char_len(path: &Path) -> usize {
+    let text = path.to_string_lossy();
+    let prefix = if text.starts_with(r"\\?\UNC\") {
+        r"?\UNC\".len()
+    } else if text.starts_with(r"\\?\") {
+        r"\\?\".len()
+    } else {
+        0
+    };
+    text.chars().count() - prefix
+}
+
+/// 확장 경로면 같은 뜻의 일반 경로 문자열을 반환한다.
+fn strip_verbatim(path: &str) -> Option<String> {
+    let (plain, body) = if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        (format!(r"\\{}", rest), rest)
+    } else {
+        let rest = path.strip_prefix(r"\\?\")?;
+        let drive = rest.as_bytes();
+        if drive.len() < 2 || !drive[0].is_ascii_alphabetic() || drive[1] != b':' {
+            return None;
+        }
+        (rest.to_string(), &rest[2..])
+    };
+    let changes_meaning = body.contains('/')
+        || body.split('\\').any(|name| {
+            name == "."
+                || name == ".."
+                || name.ends_with(['.', ' '])
+                || renamer::is_reserved_name(name)
+        });
+    (!changes_meaning).then_some(plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_verbatim() {
+        assert_eq!(
+            strip_verbatim(r"\\?\C:\Music\IU\Palette.mp3").as_deref(),
+            Some(r"C:\Music\IU\Palette.mp3")
+        );
+        assert_eq!(
+            strip_verbatim(r"\\?\UNC\nas\music\IU\Palette.mp3").as_deref(),
+            Some(r"\\nas\music\IU\Palette.mp3")
+        );
+        // 일반 경로와 뜻이 다른 경로, 장치 경로, 이미 일반 경로인 것은 바꾸지 않는다
+        assert_eq!(strip_verbatim(r"\\?\C:\Music\Live."), None);
+        assert_eq!(strip_verbatim(r"\\?\C:\Music\CON.mp3"), None);
+        assert_eq!(strip_verbatim(r"\\?\C:\Music\..\a.mp3"), None);
+        assert_eq!(strip_verbatim(r"\\?\Volume{1234}\Music"), None);
+        assert_eq!(strip_verbatim(r"C:\Music"), None);
+        assert_eq!(strip_verbatim(r"\\nas\music"), None);
+    }
+
+    #[test]
+    fn test_char_len() {
+        assert_eq!(
+            char_len(Path::new(r"\\?\C:\음악")),
+            r"C:\음악".chars().count()
+        );
+        assert_eq!(
+            char_len(Path::new(r"\\?\UNC\nas\music")),
+            r"\\nas\music".len()
+        );
+        assert_eq!(char_len(Path::new("/music/a.mp3")), 12);
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// `checksum create`가 디렉토리에 남기는 기본 목록 파일 이름.
+pub const MANIFEST_FILE: &str = ".mp3tag-audio.sha256";
+
+/// 파일별 오디오 체크섬 목록 (상대 경로 → 해시).
+/// `sha256sum`과 같은 "해시  경로" 줄 형식이지만, 해시는 태그를 뺀 오디오 데이터의 값이다.
+/// 태그 해시를 함께 기록하면 "오디오해시 태그해시  경로"가 된다.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Manifest {
+    pub entries: BTreeMap<String, Entry>,
+}
+
+/// 목록의 한 파일.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// `audio::content_hash`
+    pub audio: String,
+    /// `audio::tag_hash`. 태그 해시 없이 만든 목록이면 None
+    pub tags: Option<String>,
+}
+
+/// 목록과 현재 파일을 비교한 결과.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// 오디오가 그대로임
+    Ok,
+    /// 오디오가 바뀜 (태그 편집이 오디오를 건드렸거나 비트 손상)
+    Changed,
+    /// 목록에 있지만 파일이 없음
+    Missing,
+    /// 목록에 없는 새 파일
+    New,
+    /// 파일을 읽을 수 없음
+    Unreadable,
+}
+
+impl Status {
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "정상",
+            Status::Changed => "오디오 바뀜",
+            Status::Missing => "없음",
+            Status::New => "새 파일",
+            Status::Unreadable => "읽기 실패",
+        }
+    }
+
+    /// 확인에 실패한 것으로 셀 결과인지.
+    pub fn is_problem(self) -> bool {
+        matches!(self, Status::Changed | Status::Missing | Status::Unreadable)
+    }
+}
+
+/// 목록 키로 쓰는 상대 경로. 운영체제와 상관없이 `/`로 구분한다.
+pub fn relative_key(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+impl Manifest {
+    /// 목록 파일을 읽는다. 빈 줄과 `#` 주석은 건너뛴다.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("체크섬 목록을 읽을 수 없습니다: {}", path.display()))?;
+        Self::parse(&text)
+            .with_context(|| format!("체크섬 목록 형식이 잘못됐습니다: {}", path.display()))
+    }
+
+    fn parse(text: &str) -> Result<Self> {
+        let mut entries = BTreeMap::new();
+        for (n, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (hashes, path) = line
+                .split_once("  ")
+                .with_context(|| format!("{}번째 줄에 \"해시  경로\"가 없습니다", n + 1))?;
+            let mut hashes = hashes.split_whitespace().map(str::to_string);
+            let audio = hashes
+                .next()
+                .with_context(|| format!("{}번째 줄에 해시가 없습니다", n + 1))?;
+            entries.insert(
+                path.to_string(),
+                Entry {
+                    audio,
+                    tags: hashes.next(),
+                },
+            );
+        }
+        Ok(Self { entries })
+    }
+
+    /// 경로순으로 목록 파일에 기록한다.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_text())
+            .with_context(|| format!("체크섬 목록을 기록할 수 없습니다: {}", path.display()))
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = String::from("# mp3tag 오디오 체크섬 (태그 제외 SHA-256, 태그 SHA-256)\n");
+        for (path, entry) in &self.entries {
+            match &entry.tags {
+                Some(tags) => text += &format!("{} {}  {}\n", entry.audio, tags, path),
+                None => text += &format!("{}  {}\n", entry.audio, path),
+            }
+        }
+        text
+    }
+
+    /// 현재 파일(상대 경로 → 해시, 읽지 못하면 None)과 비교한다. 경로순으로 반환한다.
+    pub fn verify(&self, current: &BTreeMap<String, Option<String>>) -> Vec<(String, Status)> {
+        let mut results: Vec<(String, Status)> = self
+            .entries
+            .iter()
+            .map(|(path, entry)| {
+                let status = match current.get(path) {
+                    None => Status::Missing,
+                    Some(None) => Status::Unreadable,
+                    Some(Some(now)) if *now == entry.audio => Status::Ok,
+                    Some(Some(_)) => Status::Changed,
+                };
+                (path.clone(), status)
+            })
+            .collect();
+        results.extend(
+            current
+                .keys()
+                .filter(|path| !self.entries.contains_key(*path))
+                .map(|path| (path.clone(), Status::New)),
+        );
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        results
+    }
+
+    /// 목록을 만든 뒤 태그가 바뀐 파일 (현재 상대 경로 → `audio::tag_hash`). 목록에 없는 새 파일, 태그 해시가
+    /// 없는 항목, 읽지 못한 파일도 바뀐 것으로 본다. 없어진 파일은 넣지 않는다. 경로순으로 반환한다.
+    pub fn modified(&self, current: &BTreeMap<String, Option<String>>) -> Vec<String> {
+        current
+            .iter()
+            .filter(|(path, now)| {
+                let recorded = self.entries.get(*path).and_then(|e| e.tags.as_ref());
+                now.is_none() || recorded.is_none() || recorded != now.as_ref()
+            })
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_roundtrip_and_verify() {
+        let entry = |audio: &str, tags: Option<&str>| Entry {
+            audio: audio.to_string(),
+            tags: tags.map(str::to_string),
+        };
+        let mut manifest = Manifest::default();
+        manifest
+            .entries
+            .insert("A/01 intro.mp3".to_string(), entry("aaa", Some("t1")));
+        manifest
+            .entries
+            .insert("A/02.mp3".to_string(), entry("bbb", Some("t2")));
+        manifest
+            .entries
+            .insert("B/03.mp3".to_string(), entry("ccc", None));
+        assert_eq!(Manifest::parse(&manifest.to_text()).unwrap(), manifest);
+
+        let current = BTreeMap::from([
+            ("A/01 intro.mp3".to_string(), Some("aaa".to_string())),
+            ("A/02.mp3".to_string(), Some("xxx".to_string())),
+            ("C/04.mp3".to_string(), Some("ddd".to_string())),
+        ]);
+        let statuses: Vec<Status> = manifest
+            .verify(&current)
+            .into_iter()
+            .map(|(_, s)| s)
+            .collect();
+        assert_eq!(
+            statuses,
+            [Status::Ok, Status::Changed, Status::Missing, Status::New]
+        );
+        assert!(Manifest::parse("no separator").is_err());
+    }
+
+    #[test]
+    fn test_modified_since_manifest() {
+        let mut manifest = Manifest::default();
+        for (path, tags) in [
+            ("a.mp3", Some("t1")),
+            ("b.mp3", Some("t2")),
+            ("c.mp3", None),
+        ] {
+            manifest.entries.insert(
+                path.to_string(),
+                Entry {
+                    audio: "audio".to_string(),
+                    tags: tags.map(str::to_string),
+                },
+            );
+        }
+        let current = BTreeMap::from([
+            ("a.mp3".to_string(), Some("t1".to_string())),
+            ("b.mp3".to_string(), Some("changed".to_string())),
+            ("c.mp3".to_string(), Some("t3".to_string())),
+            ("d.mp3".to_string(), Some("t4".to_string())),
+        ]);
+        assert_eq!(manifest.modified(&current), ["b.mp3", "c.mp3", "d.mp3"]);
+    }
+}
@@ -0,0 +1,748 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::config::AlbumEdition;
+use crate::core::variants::{self, Marker};
+use crate::core::{parser, romanize};
+use crate::i18n;
+use crate::models::{Mp3File, TrackInfo};
+
+/// 비교용으로 문자열을 정규화한다. 소문자로 바꾸고 문자/숫자 외의 문자(공백, 구두점)를 제거한다.
+pub fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// 언어가 다른 표기를 같은 대상으로 보기 위한 별칭 표.
+/// 각 이름은 비교 키(`match_key`)로 바꿔 묶음의 첫 이름 키에 대응시킨다.
+#[derive(Debug, Default)]
+pub struct Aliases {
+    canonical: HashMap<String, String>,
+}
+
+impl Aliases {
+    pub fn new(groups: &[Vec<String>]) -> Self {
+        let mut canonical = HashMap::new();
+        for group in groups {
+            let keys: Vec<String> = group
+                .iter()
+                .map(|name| transliterate(name))
+                .filter(|k| !k.is_empty())
+                .collect();
+            let Some(first) = keys.first() else {
+                continue;
+            };
+            for key in &keys {
+                canonical.insert(key.clone(), first.clone());
+            }
+        }
+        Self { canonical }
+    }
+
+    /// 언어에 상관없이 비교할 수 있는 키. 한글은 로마자로 옮긴 뒤 정규화하고,
+    /// 별칭 표에 있으면 그 묶음의 대표 키를 쓴다 ("아이유"와 "IU", "소격동"과 "Sogyeokdong").
+    pub fn match_key(&self, s: &str) -> String {
+        let key = transliterate(s);
+        self.canonical.get(&key).cloned().unwrap_or(key)
+    }
+}
+
+/// 한글을 로마자로 옮긴 뒤 정규화한다.
+fn transliterate(s: &str) -> String {
+    if romanize::has_hangul(s) {
+        normalize(&romanize::romanize(s))
+    } else {
+        normalize(s)
+    }
+}
+
+/// 재생 시간이 이 이상 차이 나면 같은 곡으로 보지 않는다 (밀리초).
+/// 인코더에 따라 앞뒤 무음 길이가 달라지는 정도는 허용한다.
+pub const DURATION_TOLERANCE_MS: u64 = 5000;
+
+/// 로컬 파일의 제목을 반환한다. 태그가 없으면 파일명에서 파싱한다.
+fn local_title(file: &Mp3File) -> Option<String> {
+    file.current_tags
+        .as_ref()
+        .and_then(|t| t.title.clone())
+        .or_else(|| parser::parse_filename(&file.path).title)
+}
+
+/// 로컬 파일의 아티스트를 반환한다. 태그가 없으면 파일명에서 파싱한다.
+fn local_artist(file: &Mp3File) -> Option<String> {
+    file.current_tags
+        .as_ref()
+        .and_then(|t| t.artist())
+        .or_else(|| parser::parse_filename(&file.path).artist())
+}
+
+/// 트랙의 아티스트 중 하나가 로컬 아티스트와 같거나 서로 포함 관계인지 확인한다.
+/// "IU"와 "아이유 (IU)"처럼 표기가 조금 다른 경우도 일치로 본다.
+fn artist_matches(local: &str, track: &TrackInfo) -> bool {
+    artist_matches_by(local, track, normalize)
+}
+
+/// `key`로 바꾼 아티스트끼리 비교하는 `artist_matches`.
+fn artist_matches_by(local: &str, track: &TrackInfo, key: impl Fn(&str) -> String) -> bool {
+    track
+        .artists
+        .iter()
+        .map(|a| key(a))
+        .any(|a| !a.is_empty() && (a == local || a.contains(local) || local.contains(a.as_str())))
+}
+
+/// 괄호/대괄호로 감싼 부분("(Feat. X)", "[Remastered]")을 제거한다.
+pub fn strip_brackets(s: &str) -> String {
+    let mut depth = 0usize;
+    s.chars()
+        .filter(|&c| match c {
+            '(' | '[' => {
+                depth += 1;
+                false
+            }
+            ')' | ']' => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            _ => depth == 0,
+        })
+        .collect()
+}
+
+/// 두 트랙이 같은 곡인지 판단한다. 괄호 부분을 뺀 제목이 같고,
+/// 양쪽 모두 아티스트가 있으면 그중 하나가 일치해야 한다.
+/// 크레딧 등 보충 정보를 다른 소스에서 가져올 때 검색 결과를 검증하는 데 사용한다.
+pub fn is_same_song(a: &TrackInfo, b: &TrackInfo) -> bool {
+    let title = |t: &TrackInfo| t.title.as_deref().map(|s| normalize(&strip_brackets(s)));
+    match (title(a), title(b)) {
+        (Some(x), Some(y)) if !x.is_empty() && x == y => {}
+        _ => return false,
+    }
+
+    if a.artists.is_empty() || b.artists.is_empty() {
+        return true;
+    }
+    a.artists
+        .iter()
+        .map(|a| normalize(a))
+        .filter(|a| !a.is_empty())
+        .any(|a| artist_matches(&a, b))
+}
+
+/// 검색 결과가 로컬 파일과 얼마나 잘 맞는지 0.0~1.0 점수로 매긴다.
+/// `local`은 파일의 태그(없으면 파일명에서 추론한 값), `duration_ms`는 파일의 재생 시간이다.
+///
+/// 제목 0.5, 아티스트 0.3, 재생 시간 0.2의 가중 평균이며, 로컬에 없는 항목은 빼고 계산한다.
+/// 제목과 아티스트는 `Aliases::match_key`로 비교하므로 한글/로마자 표기와 별칭 표의 이름은 같게 본다.
+/// 제목은 괄호 부분을 뺀 키가 같으면 1, 한쪽이 다른 쪽을 포함하면 0.6을 준다.
+/// 재생 시간은 2초 이내면 1, 허용 오차 이내면 0.5를 준다.
+pub fn match_score(
+    local: &TrackInfo,
+    duration_ms: Option<u64>,
+    candidate: &TrackInfo,
+    aliases: &Aliases,
+) -> f64 {
+    let title = |t: &TrackInfo| {
+        t.title
+            .as_deref()
+            .map(|s| aliases.match_key(&strip_brackets(s)))
+            .filter(|s| !s.is_empty())
+    };
+    let (Some(local_title), Some(candidate_title)) = (title(local), title(candidate)) else {
+        return 0.0;
+    };
+    let title_score = if local_title == candidate_title {
+        1.0
+    } else if local_title.contains(&candidate_title) || candidate_title.contains(&local_title) {
+        0.6
+    } else {
+        0.0
+    };
+
+    let mut total = 0.5 * title_score;
+    let mut weight = 0.5;
+    if let Some(artist) = local
+        .artist()
+        .map(|a| aliases.match_key(&a))
+        .filter(|a| !a.is_empty())
+    {
+        weight += 0.3;
+        if artist_matches_by(&artist, candidate, |a| aliases.match_key(a)) {
+            total += 0.3;
+        }
+    }
+    if let (Some(a), Some(b)) = (duration_ms, candidate.duration_ms) {
+        weight += 0.2;
+        total += match a.abs_diff(b) {
+            0..=2000 => 0.2,
+            gap if gap <= DURATION_TOLERANCE_MS => 0.1,
+            _ => 0.0,
+        };
+    }
+    total / weight
+}
+
+/// 원래 앨범의 스튜디오 녹음이 아닐 가능성이 있는 결과에 곱할 감점 (`release_penalty`).
+const COMPILATION_PENALTY: f64 = 0.1;
+const REGION_PENALTY: f64 = 0.05;
+/// 결과는 반주 버전인데 파일에는 표시가 없을 때
+const INSTRUMENTAL_PENALTY: f64 = 0.15;
+/// 파일은 반주 버전인데 결과는 아닐 때. 노래 버전의 메타데이터를 자동 적용하지 않도록 크게 깎는다.
+const VOCAL_PENALTY: f64 = 0.5;
+/// Explicit/Clean이 서로 다를 때
+const EXPLICIT_PENALTY: f64 = 0.2;
+
+/// 로컬 파일이 어떤 판인지 알 수 있는 문자열 (앨범 폴더 이름, 파일 이름, 기존 태그의 제목/앨범).
+/// `release_penalty`에 쓴다. 라이브러리 위쪽 폴더("~/Music/Best Of 2000s/...")는 판과 상관없으므로 보지 않는다.
+pub fn local_hint(file: &Mp3File) -> String {
+    let folder = file.path.parent().and_then(Path::file_name);
+    let mut hint = [folder, file.path.file_name()]
+        .into_iter()
+        .flatten()
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if let Some(ref tags) = file.current_tags {
+        for value in [&tags.title, &tags.album].into_iter().flatten() {
+            hint.push(' ');
+            hint.push_str(value);
+        }
+    }
+    hint
+}
+
+/// 자동 선택에서 원래 앨범보다 뒤로 미룰 결과의 감점 (0.0~1.0, 점수에 `1 - 감점`을 곱한다).
+/// 모음/베스트 앨범, 해외 재발매반은 `local_hint`(파일 경로와 기존 태그)에 같은 표시가 있으면
+/// 파일도 그 판으로 보고 감점하지 않는다. 버전 표시(Explicit/Clean/Inst.)는 양쪽이 맞지 않으면 감점한다.
+pub fn release_penalty(local_hint: &str, candidate: &TrackInfo) -> f64 {
+    let mut penalty: f64 = 0.0;
+    match variants::edition(candidate) {
+        AlbumEdition::Compilation if !variants::mentions(local_hint, AlbumEdition::Compilation) => {
+            penalty += COMPILATION_PENALTY
+        }
+        AlbumEdition::Region if !variants::mentions(local_hint, AlbumEdition::Region) => {
+            penalty += REGION_PENALTY
+        }
+        _ => {}
+    }
+    penalty + marker_penalty(&variants::markers(local_hint), candidate)
+}
+
+/// 파일의 버전 표시 `local`과 결과의 표시가 맞지 않는 만큼의 감점.
+/// 파일에 Explicit/Clean 표시가 없으면 어느 쪽이든 맞는 것으로 보며, 파일이 Explicit인데 소스가
+/// explicit이 아니라고 알려 준 결과는 Clean으로 본다.
+fn marker_penalty(local: &[Marker], candidate: &TrackInfo) -> f64 {
+    let found = variants::track_markers(candidate);
+    let has = |markers: &[Marker], m: Marker| markers.contains(&m);
+    let mut penalty = 0.0;
+    match (
+        has(local, Marker::Instrumental),
+        has(&found, Marker::Instrumental),
+    ) {
+        (false, true) => penalty += INSTRUMENTAL_PENALTY,
+        (true, false) => penalty += VOCAL_PENALTY,
+        _ => {}
+    }
+    let clean = has(&found, Marker::Clean) || candidate.explicit == Some(false);
+    if (has(local, Marker::Explicit) && clean)
+        || (has(local, Marker::Clean) && has(&found, Marker::Explicit))
+    {
+        penalty += EXPLICIT_PENALTY;
+    }
+    penalty
+}
+
+/// 로컬 파일과 검색 결과의 제목 버전(Live, Remix, Acoustic, Sped Up, Radio Edit)이 같은지.
+/// 양쪽 모두 표시가 없어도 같은 것으로 본다. `auto --ignore-version`이면 확인하지 않는다.
+pub fn versions_agree(local: &TrackInfo, candidate: &TrackInfo) -> bool {
+    variants::track_versions(local) == variants::track_versions(candidate)
+}
+
+/// 자동 처리 결과의 신뢰 등급 (`auto` 요약, `auto --write-tier`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Tier {
+    /// 모든 항목이 맞음 (점수 1.0)
+    Exact,
+    /// 0.9 이상
+    High,
+    /// 0.75 이상
+    Medium,
+    /// 0.75 미만
+    Low,
+    /// 맞는 검색 결과가 없음 (파일명 추론, 결과 없음)
+    Unmatched,
+}
+
+impl Tier {
+    pub const ALL: [Tier; 5] = [
+        Tier::Exact,
+        Tier::High,
+        Tier::Medium,
+        Tier::Low,
+        Tier::Unmatched,
+    ];
+
+    /// `match_score`의 점수로 등급을 정한다. 점수가 없으면 `Unmatched`.
+    pub fn of(score: Option<f64>) -> Self {
+        match score {
+            None => Tier::Unmatched,
+            Some(s) if s >= 0.999 => Tier::Exact,
+            Some(s) if s >= 0.9 => Tier::High,
+            Some(s) if s >= 0.75 => Tier::Medium,
+            Some(_) => Tier::Low,
+        }
+    }
+
+    /// TXXX 프레임과 CSV에 쓰는 이름.
+    pub fn name(self) -> &'static str {
+        match self {
+            Tier::Exact => "exact",
+            Tier::High => "high",
+            Tier::Medium => "medium",
+            Tier::Low => "low",
+            Tier::Unmatched => "unmatched",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Tier::Exact => i18n::pick("정확", "exact"),
+            Tier::High => i18n::pick("높음", "high"),
+            Tier::Medium => i18n::pick("보통", "medium"),
+            Tier::Low => i18n::pick("낮음", "low"),
+            Tier::Unmatched => i18n::pick("일치 없음", "unmatched"),
+        }
+    }
+}
+
+/// 좋아요 표시한 곡이나 플레이리스트처럼 여러 앨범이 섞인 트랙 목록과 로컬 파일을 짝짓는다.
+/// 반환값의 i번째는 `files[i]`에 대응하는 트랙 인덱스다.
+///
+/// 정규화한 제목이 같은 트랙 중 아티스트도 일치하는 트랙을 고른다.
+/// 로컬 파일에 아티스트가 없으면 제목이 같은 트랙이 하나뿐일 때만 짝짓는다.
+/// `durations[i]`는 `files[i]`의 재생 시간(밀리초)으로, 양쪽 모두 알면
+/// 길이가 크게 다른 트랙(라이브, 리믹스 등)을 제외하고 가장 가까운 트랙을 고른다.
+pub fn match_library_tracks(
+    files: &[Mp3File],
+    durations: &[Option<u64>],
+    tracks: &[TrackInfo],
+) -> Vec<Option<usize>> {
+    let titles: Vec<String> = tracks
+        .iter()
+        .map(|t| t.title.as_deref().map(normalize).unwrap_or_default())
+        .collect();
+
+    files
+        .iter()
+        .zip(durations)
+        .map(|(file, &duration)| {
+            let title = normalize(&local_title(file)?);
+            if title.is_empty() {
+                return None;
+            }
+            let gap = |j: usize| match (duration, tracks[j].duration_ms) {
+                (Some(a), Some(b)) => Some(a.abs_diff(b)),
+                _ => None,
+            };
+            let candidates: Vec<usize> = (0..tracks.len())
+                .filter(|&j| titles[j] == title)
+                .filter(|&j| gap(j).is_none_or(|g| g <= DURATION_TOLERANCE_MS))
+                .collect();
+
+            match local_artist(file)
+                .map(|a| normalize(&a))
+                .filter(|a| !a.is_empty())
+            {
+                Some(artist) => candidates
+                    .into_iter()
+                    .filter(|&j| artist_matches(&artist, &tracks[j]))
+                    .min_by_key(|&j| gap(j).unwrap_or(u64::MAX)),
+                None => match candidates[..] {
+                    [only] => Some(only),
+                    _ => None,
+                },
+            }
+        })
+        .collect()
+}
+
+/// 앨범의 트랙 목록과 로컬 파일을 짝짓는다. 반환값의 i번째는 `files[i]`에 대응하는 트랙 인덱스다.
+///
+/// 1. 정규화한 제목이 같은 트랙
+/// 2. 태그의 트랙 번호가 같은 트랙
+/// 3. 남은 파일과 트랙을 순서대로 (파일은 경로순, 트랙은 트랙 번호순)
+pub fn match_album_tracks(files: &[Mp3File], tracks: &[TrackInfo]) -> Vec<Option<usize>> {
+    let mut assigned: Vec<Option<usize>> = vec![None; files.len()];
+    let mut used = vec![false; tracks.len()];
+
+    // 1. 제목 일치
+    for (i, file) in files.iter().enumerate() {
+        let Some(title) = local_title(file).map(|t| normalize(&t)) else {
+            continue;
+        };
+        if title.is_empty() {
+            continue;
+        }
+        if let Some(j) = (0..tracks.len()).find(|&j| {
+            !used[j]
+                && tracks[j]
+                    .title
+                    .as_deref()
+                    .is_some_and(|t| normalize(t) == title)
+        }) {
+            assigned[i] = Some(j);
+            used[j] = true;
+        }
+    }
+
+    // 2. 트랙 번호 일치
+    for (i, file) in files.iter().enumerate() {
+        if assigned[i].is_some() {
+            continue;
+        }
+        let Some(number) = file.current_tags.as_ref().and_then(|t| t.track_number) else {
+            continue;
+        };
+        if let Some(j) =
+            (0..tracks.len()).find(|&j| !used[j] && tracks[j].track_number == Some(number))
+        {
+            assigned[i] = Some(j);
+            used[j] = true;
+        }
+    }
+
+    // 3. 남은 것끼리 순서대로
+    let mut remaining_tracks = (0..tracks.len()).filter(|&j| !used[j]);
+    for slot in assigned.iter_mut().filter(|a| a.is_none()) {
+        match remaining_tracks.next() {
+            Some(j) => *slot = Some(j),
+            None => break,
+        }
+    }
+
+    assigned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::split_artists;
+    use std::path::PathBuf;
+
+    fn file(name: &str, tags: Option<TrackInfo>) -> Mp3File {
+        Mp3File {
+            path: PathBuf::from(name),
+            has_tags: tags.is_some(),
+            current_tags: tags,
+            encoder: None,
+        }
+    }
+
+    fn track(number: u32, title: &str) -> TrackInfo {
+        TrackInfo {
+            title: Some(title.to_string()),
+            track_number: Some(number),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("Blueming (Feat. X)"), "bluemingfeatx");
+        assert_eq!(normalize("밤편지 !"), "밤편지");
+    }
+
+    #[test]
+    fn test_match_by_title_from_filename() {
+        let files = vec![
+            file("01 IU - 밤편지.mp3", None),
+            file("02 IU - Blueming.mp3", None),
+        ];
+        let tracks = vec![track(1, "Blueming"), track(2, "밤편지")];
+        assert_eq!(match_album_tracks(&files, &tracks), vec![Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn test_match_by_track_number() {
+        let tagged = TrackInfo {
+            track_number: Some(2),
+            ..Default::default()
+        };
+        let files = vec![file("a.mp3", Some(tagged))];
+        let tracks = vec![track(1, "One"), track(2, "Two")];
+        assert_eq!(match_album_tracks(&files, &tracks), vec![Some(1)]);
+    }
+
+    #[test]
+    fn test_match_by_order_fallback() {
+        let files = vec![file("track1.mp3", None), file("track2.mp3", None)];
+        let tracks = vec![track(1, "One"), track(2, "Two"), track(3, "Three")];
+        assert_eq!(match_album_tracks(&files, &tracks), vec![Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn test_match_score() {
+        let none = Aliases::default();
+        let local = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["IU".to_string()],
+            ..Default::default()
+        };
+        let candidate = |title: &str, artist: &str, duration_ms: u64| TrackInfo {
+            title: Some(title.to_string()),
+            artists: vec![artist.to_string()],
+            duration_ms: Some(duration_ms),
+            ..Default::default()
+        };
+
+        let exact = match_score(
+            &local,
+            Some(217_000),
+            &candidate("Blueming", "아이유 (IU)", 218_000),
+            &none,
+        );
+        assert!((exact - 1.0).abs() < 1e-9);
+        let live = match_score(
+            &local,
+            Some(217_000),
+            &candidate("Blueming (Live)", "IU", 260_000),
+            &none,
+        );
+        assert!(live < exact && live > 0.5);
+        let other_artist = match_score(&local, None, &candidate("Blueming", "Someone", 0), &none);
+        assert!(other_artist < 0.7);
+        assert!(match_score(&local, None, &candidate("밤편지", "IU", 0), &none) < 0.5);
+    }
+
+    #[test]
+    fn test_match_score_across_languages() {
+        let aliases = Aliases::new(&[vec!["아이유".to_string(), "IU".to_string()]]);
+        let local = TrackInfo {
+            title: Some("소격동".to_string()),
+            artists: vec!["아이유".to_string()],
+            ..Default::default()
+        };
+        let candidate = TrackInfo {
+            title: Some("Sogyeokdong".to_string()),
+            artists: split_artists("IU, Seo Taiji"),
+            ..Default::default()
+        };
+        let score = match_score(&local, None, &candidate, &aliases);
+        assert!((score - 1.0).abs() < 1e-9);
+        // 별칭 표가 없으면 제목만 일치한다
+        let score = match_score(&local, None, &candidate, &Aliases::default());
+        assert!((score - 0.5 / 0.8).abs() < 1e-9);
+        assert_eq!(aliases.match_key("I.U."), aliases.match_key("아이유"));
+    }
+
+    #[test]
+    fn test_tier_of_score() {
+        assert_eq!(Tier::of(Some(1.0)), Tier::Exact);
+        assert_eq!(Tier::of(Some(0.93)), Tier::High);
+        assert_eq!(Tier::of(Some(0.8)), Tier::Medium);
+        assert_eq!(Tier::of(Some(0.5)), Tier::Low);
+        assert_eq!(Tier::of(None), Tier::Unmatched);
+    }
+
+    #[test]
+    fn test_release_penalty() {
+        let result = |album: &str| TrackInfo {
+            title: Some("좋은 날".to_string()),
+            album: Some(album.to_string()),
+            ..Default::default()
+        };
+        let studio = "Music/IU/Real/03 좋은 날.mp3";
+        assert_eq!(release_penalty(studio, &result("Real")), 0.0);
+        assert_eq!(
+            release_penalty(studio, &result("IU Greatest Hits")),
+            COMPILATION_PENALTY
+        );
+        assert_eq!(
+            release_penalty(studio, &result("Real (Japanese Ver.)")),
+            REGION_PENALTY
+        );
+        let karaoke = TrackInfo {
+            title: Some("좋은 날 (Inst.)".to_string()),
+            ..result("Real")
+        };
+        assert_eq!(release_penalty(studio, &karaoke), INSTRUMENTAL_PENALTY);
+
+        // 파일 경로나 태그가 같은 판을 가리키면 감점하지 않는다
+        let hits = "Music/IU/Greatest Hits/03 좋은 날.mp3";
+        assert_eq!(release_penalty(hits, &result("IU Greatest Hits")), 0.0);
+        let inst = file(
+            "03 좋은 날.mp3",
+            Some(TrackInfo {
+                title: Some("좋은 날 (Inst.)".to_string()),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(release_penalty(&local_hint(&inst), &karaoke), 0.0);
+
+        // 앨범 폴더보다 위쪽 경로의 표시는 보지 않는다
+        let nested = file(
+            "/home/me/Greatest Hits Collection/IU/Real/03 좋은 날.mp3",
+            None,
+        );
+        assert_eq!(local_hint(&nested), "Real/03 좋은 날.mp3");
+        assert_eq!(
+            release_penalty(&local_hint(&nested), &result("IU Greatest Hits")),
+            COMPILATION_PENALTY
+        );
+    }
+
+    #[test]
+    fn test_marker_penalty() {
+        let result = |title: &str, explicit: Option<bool>| TrackInfo {
+            title: Some(title.to_string()),
+            album: Some("Love poem".to_string()),
+            explicit,
+            ..Default::default()
+        };
+        // 반주 파일에 노래 버전을 고르지 않는다
+        let inst = "Music/IU/Love poem/02 Blueming (Inst.).mp3";
+        assert_eq!(
+            release_penalty(inst, &result("Blueming", None)),
+            VOCAL_PENALTY
+        );
+        assert_eq!(
+            release_penalty(inst, &result("Blueming (Inst.)", None)),
+            0.0
+        );
+
+        let explicit = "Music/Billie Eilish/01 bad guy (Explicit).mp3";
+        assert_eq!(
+            release_penalty(explicit, &result("bad guy", Some(true))),
+            0.0
+        );
+        assert_eq!(
+            release_penalty(explicit, &result("bad guy", Some(false))),
+            EXPLICIT_PENALTY
+        );
+        let clean = "Music/Billie Eilish/01 bad guy (Clean).mp3";
+        assert_eq!(
+            release_penalty(clean, &result("bad guy", Some(true))),
+            EXPLICIT_PENALTY
+        );
+        // 표시가 없는 파일은 어느 쪽이든 감점하지 않는다
+        let plain = "Music/Billie Eilish/01 bad guy.mp3";
+        assert_eq!(release_penalty(plain, &result("bad guy", Some(true))), 0.0);
+    }
+
+    #[test]
+    fn test_versions_agree() {
+        let title = |t: &str| TrackInfo {
+            title: Some(t.to_string()),
+            artists: vec!["NewJeans".to_string()],
+            ..Default::default()
+        };
+        assert!(versions_agree(&title("Hype Boy"), &title("Hype Boy")));
+        assert!(versions_agree(
+            &title("Hype Boy (Sped Up)"),
+            &title("Hype Boy - Sped Up Ver.")
+        ));
+        assert!(!versions_agree(
+            &title("Hype Boy"),
+            &title("Hype Boy (Sped Up)")
+        ));
+        assert!(!versions_agree(
+            &title("Ditto (Live)"),
+            &title("Ditto (250 Remix)")
+        ));
+        // 점수는 괄호를 빼고 비교하므로 버전이 달라도 같게 나온다
+        assert_eq!(
+            match_score(
+                &title("Ditto (Live)"),
+                None,
+                &title("Ditto"),
+                &Aliases::default()
+            ),
+            1.0
+        );
+        assert!(!versions_agree(&title("Ditto (Live)"), &title("Ditto")));
+    }
+
+    #[test]
+    fn test_match_library_by_title_and_artist() {
+        let liked = |title: &str, artist: &str| TrackInfo {
+            title: Some(title.to_string()),
+            artists: vec![artist.to_string()],
+            ..Default::default()
+        };
+        let tracks = vec![
+            liked("Stay", "Justin Bieber, The Kid LAROI"),
+            liked("Stay", "Rihanna, Mikky Ekko"),
+            liked("밤편지", "IU"),
+        ];
+        let files = vec![
+            file("Rihanna - Stay.mp3", None),
+            file("아이유 (IU) - 밤편지.mp3", None),
+            file("Unknown - Other.mp3", None),
+            file("Stay.mp3", None),
+        ];
+        assert_eq!(
+            match_library_tracks(&files, &[None; 4], &tracks),
+            vec![Some(1), Some(2), None, None]
+        );
+    }
+
+    #[test]
+    fn test_match_library_by_duration() {
+        let version = |duration: u64| TrackInfo {
+            title: Some("Butter".to_string()),
+            artists: vec!["BTS".to_string()],
+            duration_ms: Some(duration),
+            ..Default::default()
+        };
+        // 리믹스(3:45)와 원곡(2:44)
+        let tracks = vec![version(225_000), version(164_000)];
+        let files = vec![
+            file("BTS - Butter.mp3", None),
+            file("Butter.mp3", None),
+            file("BTS - Butter.mp3", None),
+        ];
+        let durations = [Some(165_200), Some(226_000), Some(300_000)];
+        assert_eq!(
+            match_library_tracks(&files, &durations, &tracks),
+            vec![Some(1), Some(0), None]
+        );
+    }
+
+    #[test]
+    fn test_is_same_song() {
+        let song = |title: &str, artist: Option<&str>| TrackInfo {
+            title: Some(title.to_string()),
+            artists: artist.map(split_artists).unwrap_or_default(),
+            ..Default::default()
+        };
+        assert!(is_same_song(
+            &song("Blueming", Some("IU")),
+            &song("Blueming (Remastered)", Some("아이유 (IU)"))
+        ));
+        assert!(is_same_song(
+            &song("Stay", Some("The Kid LAROI, Justin Bieber")),
+            &song("STAY", Some("Justin Bieber"))
+        ));
+        assert!(is_same_song(
+            &song("Stay", None),
+            &song("Stay", Some("Rihanna"))
+        ));
+        assert!(!is_same_song(
+            &song("Stay", Some("Rihanna")),
+            &song("Stay", Some("Zedd"))
+        ));
+        assert!(!is_same_song(
+            &song("Stay", Some("Rihanna")),
+            &song("Stay Gold", Some("Rihanna"))
+        ));
+    }
+
+    #[test]
+    fn test_match_more_files_than_tracks() {
+        let files = vec![file("x.mp3", None), file("y.mp3", None)];
+        let tracks = vec![track(1, "One")];
+        assert_eq!(match_album_tracks(&files, &tracks), vec![Some(0), None]);
+    }
+}
@@ -1,4 +1,40 @@
+pub mod art_index;
+pub mod artwork;
+pub mod audio;
+pub mod audiobook;
+pub mod backup;
+pub mod cancel;
+pub mod compilation;
+pub mod fetch_state;
+pub mod filelock;
+pub mod fingerprint;
+pub mod flac;
+pub mod folder_art;
+pub mod glob;
+pub mod history;
+pub mod hooks;
+pub mod ignore;
+pub mod inspect;
+pub mod legacy;
+#[cfg(feature = "library")]
+pub mod library;
+pub mod listing;
+pub mod longpath;
+pub mod manifest;
+pub mod matcher;
+pub mod nfo;
+pub mod output;
 pub mod parser;
+#[cfg(feature = "library")]
+pub mod query;
 pub mod renamer;
+pub mod report;
+pub mod review;
+pub mod romanize;
 pub mod scanner;
+pub mod skip_list;
+pub mod staging;
+pub mod tag_sync;
 pub mod tagger;
+pub mod totals;
+pub mod variants;
@@ -0,0 +1,188 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::models::TrackInfo;
+
+/// Kodi가 앨범 폴더에서 읽는 앨범 정보 파일.
+pub const ALBUM_NFO: &str = "album.nfo";
+/// Kodi가 아티스트 폴더에서 읽는 아티스트 정보 파일.
+pub const ARTIST_NFO: &str = "artist.nfo";
+/// mp3tag가 만든 NFO라는 표시. 이 표시가 없는 NFO는 직접 쓴 것으로 보고 덮어쓰지 않는다.
+const GENERATOR: &str = "<!-- mp3tag -->";
+
+/// 앨범 폴더 파일들의 태그(와 재생 시간, 밀리초)로 Kodi 형식 album.nfo를 만든다.
+/// 앨범 값은 값이 있는 첫 파일에서, 트랙은 디스크/트랙 번호순으로 넣는다. 앨범 태그가 없으면 None.
+pub fn album_nfo(tracks: &[(&TrackInfo, Option<u64>)]) -> Option<String> {
+    let first = |field: fn(&TrackInfo) -> Option<String>| tracks.iter().find_map(|(t, _)| field(t));
+    let album = first(|t| t.album.clone())?;
+    let album_artist = first(|t| {
+        t.album_artist
+            .clone()
+            .or_else(|| t.artists.first().cloned())
+    });
+
+    let mut xml = header("album");
+    element(&mut xml, 1, "title", Some(&album));
+    element(
+        &mut xml,
+        1,
+        "musicbrainzalbumid",
+        first(|t| t.musicbrainz_album_id.clone()).as_deref(),
+    );
+    element(&mut xml, 1, "artistdesc", album_artist.as_deref());
+    element(&mut xml, 1, "genre", first(|t| t.genre.clone()).as_deref());
+    element(&mut xml, 1, "label", first(|t| t.label.clone()).as_deref());
+    element(
+        &mut xml,
+        1,
+        "year",
+        first(|t| t.year.map(|y| y.to_string())).as_deref(),
+    );
+    element(
+        &mut xml,
+        1,
+        "releasedate",
+        first(|t| t.release_date.clone()).as_deref(),
+    );
+    element(
+        &mut xml,
+        1,
+        "originalreleasedate",
+        first(|t| {
+            t.original_date
+                .clone()
+                .or_else(|| t.original_year.map(|y| y.to_string()))
+        })
+        .as_deref(),
+    );
+    if let Some(ref artist) = album_artist {
+        xml += "  <albumArtistCredits>\n";
+        element(&mut xml, 2, "artist", Some(artist));
+        xml += "  </albumArtistCredits>\n";
+    }
+
+    let mut sorted: Vec<&(&TrackInfo, Option<u64>)> = tracks.iter().collect();
+    sorted.sort_by_key(|(t, _)| (t.disc_number, t.track_number));
+    for (track, duration) in sorted {
+        xml += "  <track>\n";
+        element(
+            &mut xml,
+            2,
+            "position",
+            track.track_number.map(|n| n.to_string()).as_deref(),
+        );
+        element(&mut xml, 2, "title", track.title.as_deref());
+        element(
+            &mut xml,
+            2,
+            "duration",
+            duration
+                .or(track.duration_ms)
+                .map(|ms| format!("{}:{:02}", ms / 60_000, ms / 1000 % 60))
+                .as_deref(),
+        );
+        xml += "  </track>\n";
+    }
+    xml += "</album>\n";
+    Some(xml)
+}
+
+/// 아티스트 이름과 그 아티스트 앨범들의 장르로 Kodi 형식 artist.nfo를 만든다.
+pub fn artist_nfo(name: &str, genres: &[String]) -> String {
+    let mut xml = header("artist");
+    element(&mut xml, 1, "name", Some(name));
+    for genre in genres {
+        element(&mut xml, 1, "genre", Some(genre));
+    }
+    xml += "</artist>\n";
+    xml
+}
+
+/// NFO를 기록할지. 없거나 mp3tag가 만든 파일이면 true, 직접 쓴 파일이면 false.
+pub fn should_write(path: &Path) -> bool {
+    match std::fs::read_to_string(path) {
+        Ok(existing) => existing.contains(GENERATOR),
+        Err(_) => !path.exists(),
+    }
+}
+
+/// `should_write`이면 `text`를 기록한다. 기록했으면 true.
+pub fn write(path: &Path, text: &str) -> Result<bool> {
+    if !should_write(path) {
+        return Ok(false);
+    }
+    std::fs::write(path, text)
+        .with_context(|| format!("NFO를 기록할 수 없습니다: {}", path.display()))?;
+    Ok(true)
+}
+
+fn header(root: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\" ?>\n{}\n<{}>\n",
+        GENERATOR, root
+    )
+}
+
+/// 값이 있으면 `<name>값</name>` 줄을 붙인다.
+fn element(xml: &mut String, depth: usize, name: &str, value: Option<&str>) {
+    if let Some(value) = value.filter(|v| !v.is_empty()) {
+        *xml += &format!(
+            "{}<{name}>{}</{name}>\n",
+            "  ".repeat(depth),
+            escape_xml(value)
+        );
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_album_nfo() {
+        let track = |n: u32, title: &str| TrackInfo {
+            title: Some(title.to_string()),
+            artists: vec!["IU".to_string()],
+            album: Some("Love poem".to_string()),
+            track_number: Some(n),
+            release_date: Some("2019-11-18".to_string()),
+            musicbrainz_album_id: Some("8d5d0b3a".to_string()),
+            ..Default::default()
+        };
+        let second = track(2, "Blueming & <Love>");
+        let first = track(1, "unlucky");
+        let xml = album_nfo(&[(&second, Some(217_000)), (&first, None)]).unwrap();
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<title>Love poem</title>"));
+        assert!(xml.contains("<musicbrainzalbumid>8d5d0b3a</musicbrainzalbumid>"));
+        assert!(xml.contains("<artistdesc>IU</artistdesc>"));
+        assert!(xml.contains("<releasedate>2019-11-18</releasedate>"));
+        assert!(!xml.contains("<genre>"));
+        assert!(xml.contains("<title>Blueming &amp; &lt;Love&gt;</title>"));
+        assert!(xml.contains("<duration>3:37</duration>"));
+        assert!(xml.find("unlucky").unwrap() < xml.find("Blueming").unwrap());
+
+        assert!(album_nfo(&[(&TrackInfo::default(), None)]).is_none());
+    }
+
+    #[test]
+    fn test_keeps_handwritten_nfo() {
+        let dir = TempDir::new("nfo");
+        let path = dir.join(ARTIST_NFO);
+        let generated = artist_nfo("IU", &["K-Pop".to_string()]);
+        assert!(write(&path, &generated).unwrap());
+        assert!(write(&path, &generated).unwrap());
+        std::fs::write(&path, "<artist><name>아이유</name></artist>").unwrap();
+        assert!(!write(&path, &generated).unwrap());
+    }
+}
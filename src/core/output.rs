@@ -0,0 +1,112 @@
+use comfy_table::Table;
+
+/// 표 형식 결과의 출력 형식. CSV/TSV는 스프레드시트나 awk로 바로 넘길 수 있다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// 상자 그림 문자로 그린 표
+    #[default]
+    Table,
+    /// 쉼표로 구분, 필요한 칸은 큰따옴표로 감쌈 (RFC 4180)
+    Csv,
+    /// 탭으로 구분, 칸 안의 탭/줄바꿈은 공백으로 바꿈
+    Tsv,
+    /// 머리글을 키로 하는 객체의 배열
+    Json,
+}
+
+impl OutputFormat {
+    /// 표가 아닌 기계용 형식인지. 이때 요약 문구는 표준 출력에 섞지 않는다.
+    pub fn is_delimited(self) -> bool {
+        self != OutputFormat::Table
+    }
+}
+
+/// 머리글과 행을 `format`에 맞는 문자열로 만든다. CSV/TSV는 줄마다 줄바꿈으로 끝난다.
+pub fn render(format: OutputFormat, header: &[&str], rows: &[Vec<String>]) -> String {
+    let delimited = |separator: &str, escape: fn(&str) -> String| {
+        let line = |cells: Vec<String>| cells.join(separator) + "\n";
+        let mut out = line(header.iter().map(|h| escape(h)).collect());
+        for row in rows {
+            out += &line(row.iter().map(|c| escape(c)).collect());
+        }
+        out
+    };
+    match format {
+        OutputFormat::Table => {
+            let mut table = Table::new();
+            table.set_header(header.to_vec());
+            for row in rows {
+                table.add_row(row.clone());
+            }
+            format!("{table}\n")
+        }
+        OutputFormat::Csv => delimited(",", csv_field),
+        OutputFormat::Tsv => delimited("\t", tsv_field),
+        OutputFormat::Json => {
+            let objects: Vec<serde_json::Map<String, serde_json::Value>> = rows
+                .iter()
+                .map(|row| {
+                    header
+                        .iter()
+                        .zip(row)
+                        .map(|(h, c)| (h.to_string(), serde_json::Value::String(c.clone())))
+                        .collect()
+                })
+                .collect();
+            serde_json::to_string_pretty(&objects).unwrap_or_default() + "\n"
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn tsv_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+/// 바이트 수를 "1.2 MB" 형식으로 표시한다.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_delimited() {
+        let rows = vec![
+            vec!["a.mp3".to_string(), "IU, SUGA".to_string()],
+            vec!["b.mp3".to_string(), "say \"hi\"\tnow".to_string()],
+        ];
+        assert_eq!(
+            render(OutputFormat::Csv, &["File", "Artist"], &rows),
+            "File,Artist\na.mp3,\"IU, SUGA\"\nb.mp3,\"say \"\"hi\"\"\tnow\"\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Tsv, &["File", "Artist"], &rows),
+            "File\tArtist\na.mp3\tIU, SUGA\nb.mp3\tsay \"hi\" now\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &["File", "Artist"], &rows)).unwrap();
+        assert_eq!(json[0]["Artist"], "IU, SUGA");
+        assert_eq!(json.as_array().unwrap().len(), 2);
+    }
+}
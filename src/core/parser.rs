@@ -1,6 +1,10 @@
 use std::path::Path;
 
-use crate::models::TrackInfo;
+use anyhow::{bail, Result};
+
+use crate::core::romanize;
+use crate::i18n;
+use crate::models::{split_artists, TrackInfo};
 
 /// 파일명을 파싱하여 아티스트와 제목이 포함된 TrackInfo를 반환한다.
 ///
@@ -48,8 +52,8 @@ pub fn parse_filename(path: &Path) -> TrackInfo {
 /// TrackInfo에서 검색 쿼리를 생성한다 (Spotify 검색용).
 pub fn build_search_query(info: &TrackInfo) -> String {
     let mut parts = Vec::new();
-    if let Some(ref artist) = info.artist {
-        parts.push(artist.clone());
+    if let Some(artist) = info.artist() {
+        parts.push(artist);
     }
     if let Some(ref title) = info.title {
         parts.push(title.clone());
@@ -60,11 +64,380 @@ pub fn build_search_query(info: &TrackInfo) -> String {
     parts.join(" ")
 }
 
+/// 파일을 검색할 아티스트/제목/앨범. 기존 태그에 있는 값을 쓰고, 빠진 아티스트나 제목은 파일명에서 채운다.
+/// 태그가 없으면 파일명 파싱 결과와 같다.
+pub fn search_info(current: Option<&TrackInfo>, path: &Path) -> TrackInfo {
+    let parsed = parse_filename(path);
+    let Some(tags) = current.filter(|t| t.title.is_some() || !t.artists.is_empty()) else {
+        return parsed;
+    };
+    TrackInfo {
+        title: tags.title.clone().or(parsed.title),
+        artists: if tags.artists.is_empty() {
+            parsed.artists
+        } else {
+            tags.artists.clone()
+        },
+        album: tags.album.clone(),
+        source: "tags".to_string(),
+        ..Default::default()
+    }
+}
+
+/// 검색 결과가 없을 때 차례로 시도할 검색어 하나.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryVariant {
+    /// 원래 검색어에서 무엇을 바꿨는지 ("괄호 제거" 등)
+    pub label: &'static str,
+    pub query: String,
+}
+
+/// 원래 검색어부터 점점 단순하게 만든 검색어 목록을 만든다.
+/// 괄호 제거 → 피처링 아티스트 제거 → 한글 로마자 변환 → 아티스트/제목 순서 바꿈 순이며,
+/// 앞 단계와 같아진 검색어는 뺀다.
+pub fn query_variants(info: &TrackInfo) -> Vec<QueryVariant> {
+    let mut variants = Vec::new();
+    push_variant(
+        &mut variants,
+        i18n::pick("원래 검색어", "original query"),
+        build_search_query(info),
+    );
+
+    let artist = strip_brackets(&info.artist().unwrap_or_default());
+    let title = strip_brackets(info.title.as_deref().unwrap_or_default());
+    push_variant(
+        &mut variants,
+        i18n::pick("괄호 제거", "removing brackets"),
+        join_words(&artist, &title),
+    );
+
+    let artist = main_artist(&artist);
+    let title = drop_featuring(&title);
+    push_variant(
+        &mut variants,
+        i18n::pick("피처링 제거", "removing featured artists"),
+        join_words(&artist, &title),
+    );
+
+    if romanize::has_hangul(&artist) || romanize::has_hangul(&title) {
+        push_variant(
+            &mut variants,
+            i18n::pick("로마자 변환", "romanizing Hangul"),
+            join_words(&romanize::romanize(&artist), &romanize::romanize(&title)),
+        );
+    }
+    if !artist.is_empty() && !title.is_empty() {
+        push_variant(
+            &mut variants,
+            i18n::pick("아티스트/제목 순서 변경", "swapping artist and title"),
+            join_words(&title, &artist),
+        );
+    }
+    variants
+}
+
+fn push_variant(variants: &mut Vec<QueryVariant>, label: &'static str, query: String) {
+    if !query.is_empty() && !variants.iter().any(|v| v.query == query) {
+        variants.push(QueryVariant { label, query });
+    }
+}
+
+fn join_words(first: &str, second: &str) -> String {
+    [first, second]
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// (), [], {}, 전각 괄호로 묶인 부분을 지운다 ("(Feat. 헤이즈)", "[Live]", "(Remastered)").
+fn strip_brackets(s: &str) -> String {
+    let mut out = String::new();
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' | '[' | '{' | '（' | '［' => depth += 1,
+            ')' | ']' | '}' | '）' | '］' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 제목 뒤의 "feat. ...", "ft. ...", "featuring ..."을 지운다.
+fn drop_featuring(title: &str) -> String {
+    let lower = title.to_lowercase();
+    let cut = [" feat.", " feat ", " ft.", " featuring "]
+        .iter()
+        .filter_map(|marker| lower.find(marker))
+        .min();
+    match cut {
+        // 소문자로 바꿔도 ASCII 표지의 바이트 위치는 같다
+        Some(pos) if title.is_char_boundary(pos) => title[..pos].trim().to_string(),
+        _ => title.to_string(),
+    }
+}
+
+/// 여러 아티스트 중 첫 번째만 남긴다 ("아이유, 지드래곤", "A & B", "A feat. B", "A x B").
+fn main_artist(artist: &str) -> String {
+    let without_feat = drop_featuring(artist);
+    let lower = without_feat.to_lowercase();
+    let cut = [",", " & ", " x ", " X ", " with "]
+        .iter()
+        .filter_map(|sep| lower.find(&sep.to_lowercase()))
+        .min();
+    match cut {
+        Some(pos) if without_feat.is_char_boundary(pos) => without_feat[..pos].trim().to_string(),
+        _ => without_feat,
+    }
+}
+
+/// 파일명과 폴더 구조로 태그를 추론한다. 파일명은 `parse_filename`으로 해석하고,
+/// 상위 폴더명을 앨범으로 쓴다 ("아티스트 - 앨범", "앨범 (2019)" 형식도 해석한다).
+/// 파일명에 아티스트가 없으면 `root` 아래 두 단계 이상 깊이일 때 조부모 폴더명을 아티스트로 쓴다.
+pub fn infer_from_path(path: &Path, root: &Path) -> TrackInfo {
+    let mut info = parse_filename(path);
+    // "02 - 제목"은 아티스트가 아니라 트랙 번호로 본다
+    if let [artist] = info.artists.as_slice() {
+        if artist.len() <= 3 && artist.chars().all(|c| c.is_ascii_digit()) {
+            let number = artist.parse().ok();
+            info.artists.clear();
+            info.track_number = info.track_number.or(number);
+        }
+    }
+    let Ok(relative) = path.strip_prefix(root) else {
+        return info;
+    };
+    let dirs: Vec<&str> = relative
+        .parent()
+        .map(|p| p.iter().filter_map(|c| c.to_str()).collect())
+        .unwrap_or_default();
+
+    if let Some(album_dir) = dirs.last() {
+        let (folder_artist, album) = match album_dir.split_once(" - ") {
+            Some((artist, album)) if !artist.trim().is_empty() && !album.trim().is_empty() => {
+                (Some(artist.trim()), album.trim())
+            }
+            _ => (None, album_dir.trim()),
+        };
+        let (album, year) = strip_year_suffix(album);
+        info.album = Some(album.to_string());
+        info.year = info.year.or(year);
+        if info.artists.is_empty() {
+            let parent_artist = (dirs.len() >= 2).then(|| dirs[dirs.len() - 2].trim());
+            info.artists = folder_artist
+                .or(parent_artist)
+                .map(split_artists)
+                .unwrap_or_default();
+        }
+    }
+    info
+}
+
+/// "앨범 (2019)" 또는 "앨범 [2019]"에서 연도를 떼어낸다.
+fn strip_year_suffix(name: &str) -> (&str, Option<i32>) {
+    for (open, close) in [('(', ')'), ('[', ']')] {
+        if let Some(inner) = name.strip_suffix(close) {
+            if let Some(pos) = inner.rfind(open) {
+                let year = &inner[pos + 1..];
+                if year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()) {
+                    let album = inner[..pos].trim();
+                    if !album.is_empty() {
+                        return (album, year.parse().ok());
+                    }
+                }
+            }
+        }
+    }
+    (name, None)
+}
+
+/// 발매일 문자열을 "YYYY-MM-DD" 또는 "YYYY-MM" 형식으로 바꾼다.
+/// 구분자로 '-', '.', '/'를 받으며 ("2007.05.07" → "2007-05-07"),
+/// 연도만 있거나 월/일이 범위를 벗어나면 None.
+pub fn normalize_release_date(value: &str) -> Option<String> {
+    let parts: Vec<&str> = value.trim().split(['-', '.', '/']).collect();
+    if !(2..=3).contains(&parts.len())
+        || parts[0].len() != 4
+        || !parts
+            .iter()
+            .all(|p| !p.is_empty() && p.len() <= 4 && p.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    let year: i32 = parts[0].parse().ok()?;
+    let month: u32 = parts[1].parse().ok()?;
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+    match parts.get(2) {
+        None => Some(format!("{:04}-{:02}", year, month)),
+        Some(day) => {
+            let day: u32 = day.parse().ok()?;
+            (1..=31)
+                .contains(&day)
+                .then(|| format!("{:04}-{:02}-{:02}", year, month, day))
+        }
+    }
+}
+
+/// `{artist}/{album}/{track} - {title}` 형식의 경로 패턴.
+/// 파일의 경로 끝부분(확장자 제외)과 맞춰 태그를 뽑는다. `{_}`는 아무 값이나 건너뛴다.
+#[derive(Debug)]
+pub struct FilenamePattern {
+    tokens: Vec<PatternToken>,
+    /// 패턴이 차지하는 경로 단계 수
+    depth: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum PatternToken {
+    Literal(String),
+    Field(String),
+}
+
+/// 패턴에서 쓸 수 있는 필드 이름.
+const PATTERN_FIELDS: [&str; 8] = [
+    "artist",
+    "album",
+    "album_artist",
+    "title",
+    "track",
+    "year",
+    "genre",
+    "_",
+];
+
+impl FilenamePattern {
+    /// 패턴 문자열을 해석한다. 알 수 없는 필드나 연속된 필드는 에러.
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim().trim_end_matches(".mp3").replace('\\', "/");
+        let mut tokens = Vec::new();
+        let mut rest = pattern.as_str();
+        while !rest.is_empty() {
+            match rest.find('{') {
+                Some(0) => {
+                    let end = rest
+                        .find('}')
+                        .ok_or_else(|| anyhow::anyhow!("닫는 }}가 없습니다: {}", pattern))?;
+                    let name = &rest[1..end];
+                    if !PATTERN_FIELDS.contains(&name) {
+                        bail!(
+                            "알 수 없는 필드입니다: {{{}}} (사용 가능: {})",
+                            name,
+                            PATTERN_FIELDS.join(", ")
+                        );
+                    }
+                    if matches!(tokens.last(), Some(PatternToken::Field(_))) {
+                        bail!("필드 사이에는 구분 문자가 있어야 합니다: {}", pattern);
+                    }
+                    tokens.push(PatternToken::Field(name.to_string()));
+                    rest = &rest[end + 1..];
+                }
+                Some(pos) => {
+                    tokens.push(PatternToken::Literal(rest[..pos].to_string()));
+                    rest = &rest[pos..];
+                }
+                None => {
+                    tokens.push(PatternToken::Literal(rest.to_string()));
+                    rest = "";
+                }
+            }
+        }
+        if !tokens.iter().any(|t| matches!(t, PatternToken::Field(_))) {
+            bail!("패턴에 필드가 없습니다: {}", pattern);
+        }
+        Ok(Self {
+            depth: pattern.matches('/').count() + 1,
+            tokens,
+        })
+    }
+
+    /// 파일 경로에 패턴을 적용한다. 맞지 않으면 None.
+    pub fn apply(&self, path: &Path) -> Option<TrackInfo> {
+        let stem = path.with_extension("");
+        let parts: Vec<&str> = stem.iter().filter_map(|c| c.to_str()).collect();
+        if parts.len() < self.depth {
+            return None;
+        }
+        let target = parts[parts.len() - self.depth..].join("/");
+
+        let mut captures = Vec::new();
+        if !match_tokens(&self.tokens, &target, &mut captures) {
+            return None;
+        }
+
+        let mut info = TrackInfo {
+            source: "filename".to_string(),
+            ..Default::default()
+        };
+        for (name, value) in captures {
+            let value = value.trim().to_string();
+            match name.as_str() {
+                "artist" => info.artists = split_artists(&value),
+                "album" => info.album = Some(value),
+                "album_artist" => info.album_artist = Some(value),
+                "title" => info.title = Some(value),
+                "genre" => info.genre = Some(value),
+                "track" => {
+                    let (track, total) = value.split_once('/').unwrap_or((&value, ""));
+                    info.track_number = Some(track.trim().parse().ok()?);
+                    info.total_tracks = total.trim().parse().ok();
+                }
+                "year" => info.year = Some(value.get(..4)?.parse().ok()?),
+                _ => {}
+            }
+        }
+        Some(info)
+    }
+}
+
+/// 토큰을 문자열 전체에 맞춘다. 필드는 비어 있지 않고 `/`를 포함하지 않는 가장 짧은 값부터 시도한다.
+fn match_tokens(tokens: &[PatternToken], s: &str, captures: &mut Vec<(String, String)>) -> bool {
+    let Some((first, rest)) = tokens.split_first() else {
+        return s.is_empty();
+    };
+    match first {
+        PatternToken::Literal(lit) => s
+            .strip_prefix(lit.as_str())
+            .is_some_and(|remaining| match_tokens(rest, remaining, captures)),
+        PatternToken::Field(name) => {
+            let limit = s.find('/').unwrap_or(s.len());
+            for (end, _) in s[..limit]
+                .char_indices()
+                .skip(1)
+                .chain(std::iter::once((limit, ' ')))
+            {
+                let value = &s[..end];
+                if value.trim().is_empty() {
+                    continue;
+                }
+                captures.push((name.clone(), value.to_string()));
+                if match_tokens(rest, &s[end..], captures) {
+                    return true;
+                }
+                captures.pop();
+            }
+            false
+        }
+    }
+}
+
 /// "01 아티스트 - 제목" 또는 "01. 아티스트 - 제목" 패턴을 시도한다.
 fn try_numbered_artist_title(stem: &str) -> Option<TrackInfo> {
     // 패턴: "01. 아티스트 - 제목" 또는 "01 아티스트 - 제목"
     let rest = strip_track_number(stem)?;
-    try_artist_title(rest)
+    let mut info = try_artist_title(rest)?;
+    info.track_number = leading_number(stem);
+    Some(info)
+}
+
+/// 문자열 앞의 숫자를 트랙 번호로 읽는다.
+fn leading_number(stem: &str) -> Option<u32> {
+    let digits: String = stem.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
 }
 
 /// "아티스트 - 제목" 패턴을 시도한다. " - "로 분리.
@@ -84,7 +457,7 @@ fn try_artist_title(stem: &str) -> Option<TrackInfo> {
 
     Some(TrackInfo {
         title: Some(title.to_string()),
-        artist: Some(artist.to_string()),
+        artists: split_artists(artist),
         source: "filename".to_string(),
         ..Default::default()
     })
@@ -99,6 +472,7 @@ fn try_numbered_title(stem: &str) -> Option<TrackInfo> {
     }
     Some(TrackInfo {
         title: Some(title.to_string()),
+        track_number: leading_number(stem),
         source: "filename".to_string(),
         ..Default::default()
     })
@@ -138,10 +512,69 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_query_variants() {
+        let info = TrackInfo {
+            artists: split_artists("아이유, 지드래곤"),
+            title: Some("팔레트 (Feat. G-DRAGON)".to_string()),
+            ..Default::default()
+        };
+        let queries: Vec<(&str, String)> = query_variants(&info)
+            .into_iter()
+            .map(|v| (v.label, v.query))
+            .collect();
+        assert_eq!(
+            queries,
+            vec![
+                (
+                    "원래 검색어",
+                    "아이유, 지드래곤 팔레트 (Feat. G-DRAGON)".to_string()
+                ),
+                ("괄호 제거", "아이유, 지드래곤 팔레트".to_string()),
+                ("피처링 제거", "아이유 팔레트".to_string()),
+                ("로마자 변환", "aiyu palleteu".to_string()),
+                ("아티스트/제목 순서 변경", "팔레트 아이유".to_string()),
+            ]
+        );
+
+        // 괄호가 없으면 괄호 제거 단계는 원래 검색어와 같아 빠진다
+        let info = TrackInfo {
+            artists: vec!["IU".to_string()],
+            title: Some("Blueming ft. Someone".to_string()),
+            ..Default::default()
+        };
+        let queries: Vec<String> = query_variants(&info).into_iter().map(|v| v.query).collect();
+        assert_eq!(
+            queries,
+            vec!["IU Blueming ft. Someone", "IU Blueming", "Blueming IU"]
+        );
+    }
+
+    #[test]
+    fn test_search_info() {
+        let path = PathBuf::from("IU - Blueming.mp3");
+        assert_eq!(build_search_query(&search_info(None, &path)), "IU Blueming");
+        // 태그의 값이 파일명보다 앞서고, 빠진 값만 파일명에서 채운다
+        let tags = TrackInfo {
+            artists: vec!["아이유".to_string()],
+            album: Some("Love poem".to_string()),
+            ..Default::default()
+        };
+        let info = search_info(Some(&tags), &path);
+        assert_eq!(build_search_query(&info), "아이유 Blueming");
+        assert_eq!(info.album.as_deref(), Some("Love poem"));
+        // 아티스트/제목이 없는 태그(연도만 있는 등)는 파일명으로 검색한다
+        let tags = TrackInfo {
+            year: Some(2019),
+            ..Default::default()
+        };
+        assert_eq!(search_info(Some(&tags), &path).source, "filename");
+    }
+
     #[test]
     fn test_artist_title() {
         let info = parse_filename(&PathBuf::from("IU - Blueming.mp3"));
-        assert_eq!(info.artist.as_deref(), Some("IU"));
+        assert_eq!(info.artist().as_deref(), Some("IU"));
         assert_eq!(info.title.as_deref(), Some("Blueming"));
     }
 
@@ -149,13 +582,13 @@ mod tests {
     fn test_numbered_title() {
         let info = parse_filename(&PathBuf::from("01. Blueming.mp3"));
         assert_eq!(info.title.as_deref(), Some("Blueming"));
-        assert!(info.artist.is_none());
+        assert!(info.artists.is_empty());
     }
 
     #[test]
     fn test_numbered_artist_title() {
         let info = parse_filename(&PathBuf::from("01 IU - Blueming.mp3"));
-        assert_eq!(info.artist.as_deref(), Some("IU"));
+        assert_eq!(info.artist().as_deref(), Some("IU"));
         assert_eq!(info.title.as_deref(), Some("Blueming"));
     }
 
@@ -163,14 +596,91 @@ mod tests {
     fn test_fallback() {
         let info = parse_filename(&PathBuf::from("SomeSong.mp3"));
         assert_eq!(info.title.as_deref(), Some("SomeSong"));
-        assert!(info.artist.is_none());
+        assert!(info.artists.is_empty());
+    }
+
+    #[test]
+    fn test_numbered_sets_track_number() {
+        let info = parse_filename(&PathBuf::from("07 IU - Blueming.mp3"));
+        assert_eq!(info.track_number, Some(7));
+        let info = parse_filename(&PathBuf::from("03. Blueming.mp3"));
+        assert_eq!(info.track_number, Some(3));
+    }
+
+    #[test]
+    fn test_infer_from_path() {
+        let root = Path::new("/music");
+        let info = infer_from_path(
+            Path::new("/music/IU/Love poem (2019)/02. Blueming.mp3"),
+            root,
+        );
+        assert_eq!(info.artist().as_deref(), Some("IU"));
+        assert_eq!(info.album.as_deref(), Some("Love poem"));
+        assert_eq!(info.year, Some(2019));
+        assert_eq!(info.track_number, Some(2));
+        assert_eq!(info.title.as_deref(), Some("Blueming"));
+
+        let info = infer_from_path(Path::new("/music/아이유 - Palette/01 이 지금.mp3"), root);
+        assert_eq!(info.artist().as_deref(), Some("아이유"));
+        assert_eq!(info.album.as_deref(), Some("Palette"));
+        let info = infer_from_path(Path::new("/music/IU/Palette/02 - Palette.mp3"), root);
+        assert_eq!(info.artist().as_deref(), Some("IU"));
+        assert_eq!(info.track_number, Some(2));
+        assert_eq!(info.title.as_deref(), Some("Palette"));
+
+        // 파일명의 아티스트가 폴더명보다 우선하고, 루트 바로 아래 파일은 앨범을 추론하지 않는다
+        let info = infer_from_path(Path::new("/music/OST/Paul Kim - 모든 날.mp3"), root);
+        assert_eq!(info.artist().as_deref(), Some("Paul Kim"));
+        assert_eq!(info.album.as_deref(), Some("OST"));
+        let info = infer_from_path(Path::new("/music/Blueming.mp3"), root);
+        assert_eq!(info.album, None);
+    }
+
+    #[test]
+    fn test_normalize_release_date() {
+        let date = |s| normalize_release_date(s);
+        assert_eq!(date("2019-11-18").as_deref(), Some("2019-11-18"));
+        assert_eq!(date("2007.5.7").as_deref(), Some("2007-05-07"));
+        assert_eq!(date(" 2019/11 ").as_deref(), Some("2019-11"));
+        assert_eq!(date("2019"), None);
+        assert_eq!(date("2019-13-01"), None);
+        assert_eq!(date("2019-11-32"), None);
+        assert_eq!(date("19-11-18"), None);
+        assert_eq!(date("2019-11-18-01"), None);
+    }
+
+    #[test]
+    fn test_filename_pattern() {
+        let pattern = FilenamePattern::parse("{artist}/{album}/{track} - {title}").unwrap();
+        let info = pattern
+            .apply(Path::new("/music/IU/Love poem/03 - Blueming - Live.mp3"))
+            .unwrap();
+        assert_eq!(info.artist().as_deref(), Some("IU"));
+        assert_eq!(info.album.as_deref(), Some("Love poem"));
+        assert_eq!(info.track_number, Some(3));
+        assert_eq!(info.title.as_deref(), Some("Blueming - Live"));
+
+        assert!(pattern.apply(Path::new("/music/IU/Blueming.mp3")).is_none());
+        assert!(pattern.apply(Path::new("/a/b/xx - Blueming.mp3")).is_none());
+
+        let pattern = FilenamePattern::parse("[{year}] {_} - {title}").unwrap();
+        let info = pattern
+            .apply(Path::new("[2017] 아이유 - 밤편지.mp3"))
+            .unwrap();
+        assert_eq!(info.year, Some(2017));
+        assert_eq!(info.title.as_deref(), Some("밤편지"));
+        assert!(info.artists.is_empty());
+
+        assert!(FilenamePattern::parse("{artist} - {name}").is_err());
+        assert!(FilenamePattern::parse("{artist}{title}").is_err());
+        assert!(FilenamePattern::parse("no fields").is_err());
     }
 
     #[test]
     fn test_search_query() {
         let info = TrackInfo {
             title: Some("Blueming".to_string()),
-            artist: Some("IU".to_string()),
+            artists: vec!["IU".to_string()],
             ..Default::default()
         };
         assert_eq!(build_search_query(&info), "IU Blueming");
@@ -0,0 +1,320 @@
+use anyhow::{bail, Context, Result};
+use rusqlite::types::Value;
+
+/// 라이브러리 데이터베이스 검색식 (`mp3tag query 'artist:IU year:>=2015 missing:art'`).
+///
+/// - `필드:값` — 값을 포함하는 파일 (대소문자 무시). `필드:=값`은 값 전체가 같은 파일
+/// - `year:>=2015`, `year:2015..2019`, `duration:<3:30` — 숫자 필드 비교와 범위
+/// - `missing:필드`, `has:필드` — 필드가 비어 있는/있는 파일
+/// - 필드 없는 단어 — 제목, 아티스트, 앨범 중 하나가 포함하는 파일
+/// - 앞에 `-`를 붙이면 반대 조건. 공백이 있는 값은 큰따옴표로 감싼다 (`album:"Love poem"`)
+///
+/// 모든 조건을 만족하는 파일을 찾는다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    negate: bool,
+    kind: TermKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TermKind {
+    /// 제목, 아티스트, 앨범 중 하나가 포함
+    Any(String),
+    Text {
+        column: &'static str,
+        value: String,
+        exact: bool,
+    },
+    Number {
+        column: &'static str,
+        op: &'static str,
+        value: i64,
+    },
+    Range {
+        column: &'static str,
+        low: i64,
+        high: i64,
+    },
+    Missing(&'static str),
+}
+
+/// 글자 필드 (검색식 이름, 열).
+const TEXT_FIELDS: &[(&str, &str)] = &[
+    ("title", "title"),
+    ("artist", "artist"),
+    ("album", "album"),
+    ("albumartist", "album_artist"),
+    ("album-artist", "album_artist"),
+    ("genre", "genre"),
+    ("path", "path"),
+];
+
+/// 숫자 필드 (검색식 이름, 열).
+const NUMBER_FIELDS: &[(&str, &str)] = &[
+    ("year", "year"),
+    ("track", "track"),
+    ("disc", "disc"),
+    ("bitrate", "bitrate"),
+    ("duration", "duration_ms"),
+];
+
+/// `missing:`/`has:`에 쓰는 필드 (이름, 비어 있는 조건).
+const PRESENCE_FIELDS: &[(&str, &str)] = &[
+    ("title", "title IS NULL"),
+    ("artist", "artist IS NULL"),
+    ("album", "album IS NULL"),
+    ("albumartist", "album_artist IS NULL"),
+    ("album-artist", "album_artist IS NULL"),
+    ("genre", "genre IS NULL"),
+    ("year", "year IS NULL"),
+    ("track", "track IS NULL"),
+    ("disc", "disc IS NULL"),
+    ("art", "art_sha IS NULL"),
+    ("tags", "has_tags = 0"),
+];
+
+fn lookup(fields: &[(&str, &'static str)], name: &str) -> Option<&'static str> {
+    fields.iter().find(|(n, _)| *n == name).map(|(_, c)| *c)
+}
+
+fn field_names(fields: &[(&str, &str)]) -> String {
+    fields
+        .iter()
+        .map(|(n, _)| *n)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 공백으로 나누되 큰따옴표 안의 공백은 값에 넣는다. 따옴표는 뺀다.
+fn split_words(input: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut started = false;
+    for c in input.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if started {
+                    words.push(std::mem::take(&mut word));
+                    started = false;
+                }
+            }
+            c => {
+                word.push(c);
+                started = true;
+            }
+        }
+    }
+    if quoted {
+        bail!("닫는 큰따옴표가 없습니다: {}", input);
+    }
+    if started {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// 숫자 값. `duration`은 초 또는 "분:초"로 받아 밀리초로 바꾼다.
+fn parse_number(column: &str, value: &str) -> Result<i64> {
+    let number = if column == "duration_ms" {
+        let seconds = match value.split_once(':') {
+            Some((m, s)) => m
+                .parse::<i64>()
+                .ok()
+                .zip(s.parse::<i64>().ok())
+                .map(|(m, s)| m * 60 + s),
+            None => value.parse().ok(),
+        };
+        seconds.map(|s| s * 1000)
+    } else {
+        value.parse().ok()
+    };
+    number.with_context(|| format!("숫자가 아닙니다: {}", value))
+}
+
+fn parse_term(word: &str) -> Result<Term> {
+    let (negate, word) = match word.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, word),
+    };
+    let Some((field, value)) = word.split_once(':') else {
+        return Ok(Term {
+            negate,
+            kind: TermKind::Any(word.to_string()),
+        });
+    };
+    let field = field.to_lowercase();
+    let kind = if field == "missing" || field == "has" {
+        let condition = lookup(PRESENCE_FIELDS, &value.to_lowercase()).with_context(|| {
+            format!(
+                "{}:에 쓸 수 없는 필드입니다: {} (가능한 필드: {})",
+                field,
+                value,
+                field_names(PRESENCE_FIELDS)
+            )
+        })?;
+        return Ok(Term {
+            negate: negate != (field == "has"),
+            kind: TermKind::Missing(condition),
+        });
+    } else if let Some(column) = lookup(TEXT_FIELDS, &field) {
+        match value.strip_prefix('=') {
+            Some(exact) => TermKind::Text {
+                column,
+                value: exact.to_string(),
+                exact: true,
+            },
+            None => TermKind::Text {
+                column,
+                value: value.to_string(),
+                exact: false,
+            },
+        }
+    } else if let Some(column) = lookup(NUMBER_FIELDS, &field) {
+        if let Some((low, high)) = value.split_once("..") {
+            TermKind::Range {
+                column,
+                low: parse_number(column, low)?,
+                high: parse_number(column, high)?,
+            }
+        } else {
+            let (op, number) = [">=", "<=", ">", "<", "="]
+                .iter()
+                .find_map(|op| value.strip_prefix(op).map(|rest| (*op, rest)))
+                .unwrap_or(("=", value));
+            TermKind::Number {
+                column,
+                op,
+                value: parse_number(column, number)?,
+            }
+        }
+    } else {
+        bail!(
+            "알 수 없는 필드입니다: {} (가능한 필드: {}, {}, missing, has)",
+            field,
+            field_names(TEXT_FIELDS),
+            field_names(NUMBER_FIELDS)
+        );
+    };
+    Ok(Term { negate, kind })
+}
+
+/// LIKE 패턴의 `%`, `_`, `\`를 글자 그대로 찾도록 감싼다.
+fn like_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+impl Query {
+    /// 검색식을 읽는다. 빈 검색식은 모든 파일과 맞는다.
+    pub fn parse(input: &str) -> Result<Self> {
+        let terms = split_words(input)?
+            .iter()
+            .map(|word| parse_term(word))
+            .collect::<Result<_>>()?;
+        Ok(Self { terms })
+    }
+
+    /// `files` 테이블의 WHERE 절과 매개변수.
+    pub fn to_sql(&self) -> (String, Vec<Value>) {
+        let mut params = Vec::new();
+        let mut clauses = Vec::new();
+        for term in &self.terms {
+            let clause = match &term.kind {
+                TermKind::Any(value) => {
+                    params.push(Value::Text(like_pattern(value)));
+                    let n = params.len();
+                    format!(
+                        "(title LIKE ?{n} ESCAPE '\\' OR artist LIKE ?{n} ESCAPE '\\' OR album LIKE ?{n} ESCAPE '\\')"
+                    )
+                }
+                TermKind::Text {
+                    column,
+                    value,
+                    exact,
+                } => {
+                    if *exact {
+                        params.push(Value::Text(value.clone()));
+                        format!("{} = ?{} COLLATE NOCASE", column, params.len())
+                    } else {
+                        params.push(Value::Text(like_pattern(value)));
+                        format!("{} LIKE ?{} ESCAPE '\\'", column, params.len())
+                    }
+                }
+                TermKind::Number { column, op, value } => {
+                    params.push(Value::Integer(*value));
+                    format!("{} {} ?{}", column, op, params.len())
+                }
+                TermKind::Range { column, low, high } => {
+                    params.push(Value::Integer(*low));
+                    params.push(Value::Integer(*high));
+                    format!(
+                        "{} BETWEEN ?{} AND ?{}",
+                        column,
+                        params.len() - 1,
+                        params.len()
+                    )
+                }
+                TermKind::Missing(condition) => condition.to_string(),
+            };
+            // 값이 없는 열(NULL)은 부정 조건에서도 맞는 것으로 본다 ("-genre:rock"은 장르 없는 파일 포함)
+            clauses.push(match term.negate {
+                true => format!("NOT COALESCE({}, 0)", clause),
+                false => clause,
+            });
+        }
+        if clauses.is_empty() {
+            return ("1".to_string(), params);
+        }
+        (clauses.join(" AND "), params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let (sql, params) = Query::parse("artist:IU year:>=2015 missing:art")
+            .unwrap()
+            .to_sql();
+        assert_eq!(
+            sql,
+            "artist LIKE ?1 ESCAPE '\\' AND year >= ?2 AND art_sha IS NULL"
+        );
+        assert_eq!(
+            params,
+            [Value::Text("%IU%".to_string()), Value::Integer(2015)]
+        );
+
+        let (sql, params) = Query::parse(r#"album:="Love poem" duration:3:00..4:30 -has:genre"#)
+            .unwrap()
+            .to_sql();
+        assert_eq!(
+            sql,
+            "album = ?1 COLLATE NOCASE AND duration_ms BETWEEN ?2 AND ?3 AND genre IS NULL"
+        );
+        assert_eq!(
+            params[1..],
+            [Value::Integer(180_000), Value::Integer(270_000)]
+        );
+
+        assert_eq!(Query::parse("").unwrap().to_sql().0, "1");
+        assert!(Query::parse("mood:happy").is_err());
+        assert!(Query::parse("year:recent").is_err());
+        assert!(Query::parse("album:\"unclosed").is_err());
+    }
+}
@@ -1,11 +1,36 @@
 use std::path::{Path, PathBuf};
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 
+use crate::config::{ConflictStrategy, RenameConfig};
+use crate::core::{audio, longpath};
 use crate::models::TrackInfo;
 
-/// 파일명에 사용할 수 없는 문자를 `_`로 치환한다.
+/// Windows 예약 장치 이름. 확장자가 붙어도 (예: "CON.mp3") 사용할 수 없다.
+const RESERVED_NAMES: [&str; 24] = [
+    "CON", "PRN", "AUX", "NUL", "COM0", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7",
+    "COM8", "COM9", "LPT0", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 경로 구성 요소 하나(파일명이나 폴더 이름 전체)로 쓸 수 있게 만든다.
+///
+/// - 사용할 수 없는 문자는 `_`로 치환 ([`sanitize_field`])
+/// - Windows에서는 끝의 마침표/공백 제거 (Windows가 자동으로 잘라내어 이름이 어긋남)
+/// - 예약 장치 이름(CON, PRN, AUX, NUL, COM0-9, LPT0-9)은 뒤에 `_`를 붙임.
+///   라이브러리가 Windows로 복사될 수 있으므로 모든 플랫폼에서 처리한다.
 pub fn sanitize_filename(s: &str) -> String {
+    let replaced = sanitize_field(s);
+    let trimmed = if cfg!(target_os = "windows") {
+        replaced.trim_end_matches(['.', ' '])
+    } else {
+        &replaced
+    };
+    escape_reserved_name(trimmed)
+}
+
+/// 이름 안에 들어갈 값("t.A.T.u.")에서 파일명에 사용할 수 없는 문자만 `_`로 치환한다.
+/// 뒤에 다른 글자나 확장자가 이어지므로 끝의 마침표는 그대로 둔다.
+pub fn sanitize_field(s: &str) -> String {
     s.chars()
         .map(|c| {
             if c == '/' || c == '\0' {
@@ -27,52 +52,294 @@ pub fn sanitize_filename(s: &str) -> String {
         .collect()
 }
 
-/// TrackInfo에서 `"{artist} - {title}.mp3"` 형식의 파일명을 생성한다.
-/// artist와 title이 모두 있어야 Some을 반환한다.
-pub fn build_filename(info: &TrackInfo) -> Option<String> {
-    let artist = info.artist.as_deref()?.trim();
-    let title = info.title.as_deref()?.trim();
-    if artist.is_empty() || title.is_empty() {
+/// 첫 번째 `.` 앞부분이 예약 장치 이름이면 그 뒤에 `_`를 붙인다.
+fn escape_reserved_name(name: &str) -> String {
+    let (base, rest) = match name.find('.') {
+        Some(pos) => name.split_at(pos),
+        None => (name, ""),
+    };
+    if is_reserved_name(base) {
+        format!("{}_{}", base, rest)
+    } else {
+        name.to_string()
+    }
+}
+
+/// 첫 번째 `.` 앞부분이 Windows 예약 장치 이름인지 확인한다 ("CON", "nul.mp3").
+pub fn is_reserved_name(name: &str) -> bool {
+    let base = name.split('.').next().unwrap_or(name);
+    RESERVED_NAMES
+        .iter()
+        .any(|r| r.eq_ignore_ascii_case(base.trim_end()))
+}
+
+/// 디렉토리와 합친 전체 경로가 `max_len` 문자를 넘지 않도록 파일명을 잘라낸다.
+/// 확장자는 유지하고 stem만 자른다. `max_len`이 0이면 그대로 반환한다.
+/// Windows 확장 경로(`\\?\`) 접두사는 길이에 넣지 않는다.
+/// 디렉토리 경로만으로 한도를 넘어 stem을 남길 수 없으면 None을 반환한다.
+pub fn fit_path_length(dir: &Path, file_name: &str, max_len: usize) -> Option<String> {
+    if max_len == 0 {
+        return Some(file_name.to_string());
+    }
+
+    let dir_len = match longpath::char_len(dir) {
+        0 => 0,
+        n => n + 1, // 구분자
+    };
+    if dir_len + file_name.chars().count() <= max_len {
+        return Some(file_name.to_string());
+    }
+
+    let (stem, ext) = match file_name.rfind('.') {
+        Some(pos) if pos > 0 => file_name.split_at(pos),
+        _ => (file_name, ""),
+    };
+    let available = max_len.checked_sub(dir_len + ext.chars().count())?;
+
+    let truncate = |len: usize| -> String {
+        let cut: String = stem.chars().take(len).collect();
+        cut.trim_end_matches(['.', ' ']).to_string()
+    };
+    let mut truncated = truncate(available);
+    // 잘라서 예약 장치 이름이 되면 ("CONCERT" → "CON") `_`를 붙일 자리를 남기고 다시 자른다
+    if is_reserved_name(&truncated) {
+        truncated = escape_reserved_name(&truncate(available.saturating_sub(1)));
+    }
+    if truncated.is_empty() {
         return None;
     }
-    Some(format!(
-        "{} - {}.mp3",
-        sanitize_filename(artist),
-        sanitize_filename(title)
-    ))
+    Some(format!("{}{}", truncated, ext))
 }
 
-/// 파일명을 `"{artist} - {title}.mp3"` 형식으로 변경한다.
-/// 이미 같은 이름이면 현재 경로를 그대로 반환한다.
-/// 동일 디렉토리에 같은 이름의 파일이 이미 존재하면 에러를 반환한다.
-pub fn rename_file(old_path: &Path, info: &TrackInfo) -> Result<PathBuf> {
-    let new_name = match build_filename(info) {
+/// 템플릿(기본값 `"{artist} - {title}"`)으로 파일명을 생성하고 `.mp3`를 붙인다.
+/// 템플릿의 필드가 하나라도 비어 있으면 None.
+pub fn build_filename(info: &TrackInfo, template: &str) -> Option<String> {
+    // 끝에 `.mp3`가 붙으므로 stem 끝의 마침표는 지우지 않는다
+    let stem = escape_reserved_name(&sanitize_field(&render_template(template, info)?));
+    (!stem.is_empty()).then(|| format!("{}.mp3", stem))
+}
+
+/// 템플릿(기본값 `"{album_artist}/{album}"`)으로 파일을 정리할 `root` 아래 디렉토리를 만든다.
+/// 템플릿의 필드가 하나라도 비어 있으면 None.
+pub fn organize_dir(root: &Path, info: &TrackInfo, template: &str) -> Option<PathBuf> {
+    let mut dir = root.to_path_buf();
+    for segment in template.split('/').filter(|s| !s.trim().is_empty()) {
+        let name = sanitize_filename(&render_template(segment, info)?);
+        if name.is_empty() {
+            return None;
+        }
+        dir.push(name);
+    }
+    Some(dir)
+}
+
+/// 템플릿의 `{필드}`를 태그 값으로 채운다. 값이 없거나 모르는 필드가 있으면 None.
+fn render_template(template: &str, info: &TrackInfo) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let end = start + rest[start..].find('}')?;
+        let value = template_field(&rest[start + 1..end], info)?;
+        out.push_str(&sanitize_field(value.trim()));
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out.trim().to_string())
+}
+
+/// 템플릿에 쓸 수 있는 필드 이름 (`template_field`와 같은 목록).
+const TEMPLATE_FIELDS: &[&str] = &[
+    "artist",
+    "album_artist",
+    "album",
+    "title",
+    "track",
+    "year",
+    "original_year",
+    "genre",
+    "grouping",
+    "work",
+];
+
+/// 템플릿의 `{`가 모두 닫혀 있고 아는 필드만 쓰는지 확인한다.
+pub fn check_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        bail!("템플릿이 비어 있습니다");
+    }
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start
+            + rest[start..]
+                .find('}')
+                .with_context(|| format!("닫히지 않은 '{{'가 있습니다: {}", template))?;
+        let name = rest[start + 1..end].trim();
+        if !TEMPLATE_FIELDS.contains(&name) {
+            bail!(
+                "알 수 없는 템플릿 필드입니다: {{{}}} (사용 가능: {})",
+                name,
+                TEMPLATE_FIELDS.join(", ")
+            );
+        }
+        rest = &rest[end + 1..];
+    }
+    Ok(())
+}
+
+/// 템플릿 필드의 값.
+/// - `album_artist`: 없으면 아티스트의 첫 번째 이름
+/// - `track`: 두 자리 ("03")
+/// - `year`: 이 음반의 발매 연도, `original_year`: 최초 발매 연도 (없으면 `year`)
+fn template_field(name: &str, info: &TrackInfo) -> Option<String> {
+    let value = match name.trim() {
+        "artist" => info.artist(),
+        "album_artist" => info
+            .album_artist
+            .clone()
+            .or_else(|| info.artists.first().cloned()),
+        "album" => info.album.clone(),
+        "title" => info.title.clone(),
+        "track" => info.track_number.map(|n| format!("{:02}", n)),
+        "year" => info.year.map(|y| y.to_string()),
+        "original_year" => info.original_year.or(info.year).map(|y| y.to_string()),
+        "genre" => info.genre.clone(),
+        "grouping" => info.grouping.clone(),
+        "work" => info.work.clone(),
+        _ => None,
+    };
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// 파일명 변경 결과.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameOutcome {
+    /// 새 경로로 변경됨
+    Renamed(PathBuf),
+    /// 이미 같은 이름이라 변경하지 않음
+    Unchanged,
+    /// 대상 파일이 존재하여 전략에 따라 건너뜀 (대상 경로)
+    Skipped(PathBuf),
+    /// 대상 파일이 존재하고 전략이 Prompt — 호출자가 다른 전략으로 다시 시도해야 함
+    Conflict(PathBuf),
+}
+
+/// 파일명을 `config.file_template` 형식으로 변경한다.
+/// 전체 경로가 `config.max_path_len`을 넘으면 확장자를 유지한 채 파일명을 자른다.
+/// 동일 디렉토리에 같은 이름의 파일이 이미 존재하면 `config.on_conflict`에 따라 처리한다.
+pub fn rename_file(
+    old_path: &Path,
+    info: &TrackInfo,
+    config: &RenameConfig,
+) -> Result<RenameOutcome> {
+    let new_name = match build_filename(info, &config.file_template) {
         Some(name) => name,
-        None => bail!("아티스트와 제목이 모두 필요합니다"),
+        None => bail!(
+            "파일명 템플릿 '{}'에 필요한 태그가 없습니다",
+            config.file_template
+        ),
     };
 
-    let dir = old_path
-        .parent()
-        .unwrap_or_else(|| Path::new("."));
+    let dir = old_path.parent().unwrap_or_else(|| Path::new("."));
+    move_file(old_path, dir, &new_name, config)
+}
+
+/// 파일을 `dir/file_name`으로 옮긴다. 경로 길이 제한과 충돌 전략을 적용한다.
+pub fn move_file(
+    old_path: &Path,
+    dir: &Path,
+    file_name: &str,
+    config: &RenameConfig,
+) -> Result<RenameOutcome> {
+    let new_name = fit_path_length(dir, file_name, config.max_path_len)
+        .context("디렉토리 경로가 너무 길어 파일명을 만들 수 없습니다")?;
     let new_path = dir.join(&new_name);
 
     // 이미 같은 이름이면 그대로 반환
     if old_path == new_path {
-        return Ok(new_path);
+        return Ok(RenameOutcome::Unchanged);
     }
 
-    // 이름 충돌 검사
-    if new_path.exists() {
-        bail!("파일이 이미 존재합니다: {}", new_name);
+    // 대소문자만 다른 경우 등 같은 파일을 가리키면 충돌이 아니다
+    if new_path.exists() && !is_same_file(old_path, &new_path) {
+        match config.on_conflict {
+            ConflictStrategy::Error => bail!("파일이 이미 존재합니다: {}", new_name),
+            ConflictStrategy::Skip => return Ok(RenameOutcome::Skipped(new_path)),
+            ConflictStrategy::Prompt => return Ok(RenameOutcome::Conflict(new_path)),
+            ConflictStrategy::Suffix => {
+                let new_path = next_free_path(dir, file_name, config.max_path_len)?;
+                std::fs::rename(old_path, &new_path)?;
+                return Ok(RenameOutcome::Renamed(new_path));
+            }
+            ConflictStrategy::OverwriteSmaller => {
+                if !is_lower_quality(&new_path, old_path)? {
+                    return Ok(RenameOutcome::Skipped(new_path));
+                }
+                std::fs::remove_file(&new_path)?;
+            }
+        }
     }
 
     std::fs::rename(old_path, &new_path)?;
-    Ok(new_path)
+    Ok(RenameOutcome::Renamed(new_path))
+}
+
+/// `existing`이 `ours`보다 저음질인지 확인한다. 두 파일의 비트레이트를 알고 서로 다르면
+/// 비트레이트로, 아니면 파일 크기로 비교한다 (재생 시간이 다르면 크기만으로는 음질을 알 수 없다).
+fn is_lower_quality(existing: &Path, ours: &Path) -> Result<bool> {
+    if let (Some(a), Some(b)) = (audio::bitrate_kbps(existing), audio::bitrate_kbps(ours)) {
+        if a != b {
+            return Ok(a < b);
+        }
+    }
+    Ok(std::fs::metadata(existing)?.len() < std::fs::metadata(ours)?.len())
+}
+
+/// 두 경로가 파일시스템상 같은 파일인지 확인한다.
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// 확장자 앞에 `" (n)"`을 붙인 파일명을 반환한다.
+fn suffixed_name(file_name: &str, n: u32) -> String {
+    match file_name.rfind('.') {
+        Some(pos) if pos > 0 => {
+            format!("{} ({}){}", &file_name[..pos], n, &file_name[pos..])
+        }
+        _ => format!("{} ({})", file_name, n),
+    }
+}
+
+/// `" (2)"`부터 번호를 올려가며 존재하지 않는 경로를 찾는다.
+fn next_free_path(dir: &Path, file_name: &str, max_len: usize) -> Result<PathBuf> {
+    for n in 2..1000 {
+        // 번호가 잘리지 않도록 번호를 붙이기 전에 길이를 맞춘다
+        let suffix_len = format!(" ({})", n).chars().count();
+        let limit = if max_len == 0 {
+            0
+        } else {
+            max_len.saturating_sub(suffix_len).max(1)
+        };
+        let base = fit_path_length(dir, file_name, limit)
+            .context("디렉토리 경로가 너무 길어 파일명을 만들 수 없습니다")?;
+        let candidate = dir.join(suffixed_name(&base, n));
+        if !candidate.exists() {
+            return Ok(candidate);
+        }
+    }
+    bail!("사용 가능한 파일명을 찾을 수 없습니다: {}", file_name)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::split_artists;
+    use crate::test_support::TempDir;
+
+    const FILE_TEMPLATE: &str = "{artist} - {title}";
+    const ORGANIZE_TEMPLATE: &str = "{album_artist}/{album}";
 
     #[test]
     fn test_sanitize_filename_removes_slash_and_null() {
@@ -89,14 +356,306 @@ mod tests {
         assert_eq!(sanitize_filename("아이유 - 좋은날"), "아이유 - 좋은날");
     }
 
+    #[test]
+    fn test_sanitize_filename_trailing_dots_and_spaces() {
+        if cfg!(target_os = "windows") {
+            assert_eq!(sanitize_filename("Vol. 1... "), "Vol. 1");
+            assert_eq!(sanitize_filename("t.A.T.u."), "t.A.T.u");
+        } else {
+            assert_eq!(sanitize_filename("Vol. 1... "), "Vol. 1... ");
+            assert_eq!(sanitize_filename("t.A.T.u."), "t.A.T.u.");
+        }
+        // 이름 중간에 들어가는 값은 어느 플랫폼에서도 그대로 둔다
+        assert_eq!(sanitize_field("t.A.T.u."), "t.A.T.u.");
+    }
+
+    #[test]
+    fn test_sanitize_filename_reserved_names() {
+        assert_eq!(sanitize_filename("CON"), "CON_");
+        assert_eq!(sanitize_filename("nul"), "nul_");
+        assert_eq!(sanitize_filename("com1.mp3"), "com1_.mp3");
+        assert_eq!(sanitize_filename("CONCERT"), "CONCERT");
+        assert_eq!(sanitize_filename("LPT10"), "LPT10");
+        assert_eq!(sanitize_filename("com0"), "com0_");
+        assert_eq!(sanitize_filename("LPT0.mp3"), "LPT0_.mp3");
+    }
+
+    #[test]
+    fn test_fit_path_length_short_name_unchanged() {
+        let name = fit_path_length(Path::new("/music"), "IU - Good Day.mp3", 260);
+        assert_eq!(name.as_deref(), Some("IU - Good Day.mp3"));
+    }
+
+    #[test]
+    fn test_fit_path_length_preserves_extension() {
+        // "/music/" (7) + stem + ".mp3" (4) <= 20 → stem 9자
+        let name = fit_path_length(Path::new("/music"), "IU - Good Day.mp3", 20);
+        assert_eq!(name.as_deref(), Some("IU - Good.mp3"));
+    }
+
+    #[test]
+    fn test_fit_path_length_counts_chars_not_bytes() {
+        let name = fit_path_length(Path::new(""), "아이유 - 좋은날.mp3", 9);
+        assert_eq!(name.as_deref(), Some("아이유 -.mp3"));
+    }
+
+    #[test]
+    fn test_fit_path_length_avoids_reserved_names() {
+        // "CONCERT"를 3자로 자르면 "CON"이 되므로 한 자 더 잘라 예약 이름을 피한다
+        let name = fit_path_length(Path::new(""), "CONCERT.mp3", 7).unwrap();
+        assert_eq!(name, "CO.mp3");
+        let name = fit_path_length(Path::new(""), "com1 live.mp3", 8).unwrap();
+        assert_eq!(name, "com.mp3");
+        for name in ["CONCERT.mp3", "com1 live.mp3", "LPT0 - Intro.mp3"] {
+            for max_len in 5..name.len() {
+                if let Some(fit) = fit_path_length(Path::new(""), name, max_len) {
+                    assert!(!is_reserved_name(&fit), "{} → {}", name, fit);
+                    assert!(fit.chars().count() <= max_len, "{} → {}", name, fit);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit_path_length_dir_too_long() {
+        assert_eq!(
+            fit_path_length(Path::new("/very/long/dir"), "a.mp3", 10),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fit_path_length_zero_disables() {
+        let name = fit_path_length(Path::new("/music"), "IU - Good Day.mp3", 0);
+        assert_eq!(name.as_deref(), Some("IU - Good Day.mp3"));
+    }
+
+    #[test]
+    fn test_suffixed_name() {
+        assert_eq!(
+            suffixed_name("IU - Good Day.mp3", 2),
+            "IU - Good Day (2).mp3"
+        );
+        assert_eq!(suffixed_name("noext", 3), "noext (3)");
+    }
+
+    /// 임시 디렉토리에 충돌하는 두 파일을 만들고 (디렉토리, 원본, 대상)을 반환한다.
+    fn setup_conflict(name: &str, src_size: usize, dst_size: usize) -> (TempDir, PathBuf, PathBuf) {
+        let dir = TempDir::new(&format!("renamer-{}", name));
+        let src = dir.join("source.mp3");
+        let dst = dir.join("IU - Good Day.mp3");
+        std::fs::write(&src, vec![0u8; src_size]).unwrap();
+        std::fs::write(&dst, vec![0u8; dst_size]).unwrap();
+        (dir, src, dst)
+    }
+
+    fn good_day() -> TrackInfo {
+        TrackInfo {
+            artists: vec!["IU".to_string()],
+            title: Some("Good Day".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn config_with(on_conflict: ConflictStrategy) -> RenameConfig {
+        RenameConfig {
+            on_conflict,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rename_conflict_error() {
+        let (_dir, src, _) = setup_conflict("error", 10, 10);
+        let result = rename_file(&src, &good_day(), &config_with(ConflictStrategy::Error));
+        assert!(result.is_err());
+        assert!(src.exists());
+    }
+
+    #[test]
+    fn test_rename_conflict_suffix() {
+        let (_dir, src, dst) = setup_conflict("suffix", 10, 10);
+        let result =
+            rename_file(&src, &good_day(), &config_with(ConflictStrategy::Suffix)).unwrap();
+        let expected = dst.with_file_name("IU - Good Day (2).mp3");
+        assert_eq!(result, RenameOutcome::Renamed(expected.clone()));
+        assert!(expected.exists());
+        assert!(dst.exists());
+    }
+
+    #[test]
+    fn test_rename_conflict_overwrite_smaller() {
+        let (_dir, src, dst) = setup_conflict("overwrite", 20, 10);
+        let result = rename_file(
+            &src,
+            &good_day(),
+            &config_with(ConflictStrategy::OverwriteSmaller),
+        )
+        .unwrap();
+        assert_eq!(result, RenameOutcome::Renamed(dst.clone()));
+        assert_eq!(std::fs::metadata(&dst).unwrap().len(), 20);
+        assert!(!src.exists());
+    }
+
+    #[test]
+    fn test_rename_conflict_keeps_larger_existing() {
+        let (_dir, src, dst) = setup_conflict("keep", 10, 20);
+        let result = rename_file(
+            &src,
+            &good_day(),
+            &config_with(ConflictStrategy::OverwriteSmaller),
+        )
+        .unwrap();
+        assert_eq!(result, RenameOutcome::Skipped(dst.clone()));
+        assert!(src.exists());
+    }
+
+    /// `kbps`(128 또는 320) CBR 프레임 `count`개짜리 MP3 데이터.
+    fn cbr_frames(kbps: u32, count: usize) -> Vec<u8> {
+        let (bitrate_index, frame_len) = match kbps {
+            128 => (0x90, 417),
+            320 => (0xE0, 1044),
+            _ => unreachable!(),
+        };
+        let mut frame = vec![0xFF, 0xFB, bitrate_index, 0x00];
+        frame.resize(frame_len, 0);
+        frame.repeat(count)
+    }
+
+    #[test]
+    fn test_rename_conflict_compares_bitrate_before_size() {
+        // 기존 파일은 길이가 길어 더 크지만 128kbps, 옮길 파일은 짧은 320kbps
+        let (_dir, src, dst) = setup_conflict("bitrate", 0, 0);
+        std::fs::write(&src, cbr_frames(320, 10)).unwrap();
+        std::fs::write(&dst, cbr_frames(128, 100)).unwrap();
+        assert!(std::fs::metadata(&dst).unwrap().len() > std::fs::metadata(&src).unwrap().len());
+        let config = config_with(ConflictStrategy::OverwriteSmaller);
+
+        let result = rename_file(&src, &good_day(), &config).unwrap();
+        assert_eq!(result, RenameOutcome::Renamed(dst.clone()));
+        assert_eq!(audio::bitrate_kbps(&dst), Some(320));
+
+        // 반대로 고음질 파일이 이미 있으면 크기가 작아도 남긴다
+        std::fs::write(&src, cbr_frames(128, 100)).unwrap();
+        let result = rename_file(&src, &good_day(), &config).unwrap();
+        assert_eq!(result, RenameOutcome::Skipped(dst.clone()));
+        assert!(src.exists());
+    }
+
+    #[test]
+    fn test_rename_conflict_prompt() {
+        let (_dir, src, dst) = setup_conflict("prompt", 10, 10);
+        let result =
+            rename_file(&src, &good_day(), &config_with(ConflictStrategy::Prompt)).unwrap();
+        assert_eq!(result, RenameOutcome::Conflict(dst));
+    }
+
     #[test]
     fn test_build_filename_both_present() {
         let info = TrackInfo {
-            artist: Some("IU".to_string()),
+            artists: vec!["IU".to_string()],
             title: Some("Good Day".to_string()),
             ..Default::default()
         };
-        assert_eq!(build_filename(&info), Some("IU - Good Day.mp3".to_string()));
+        assert_eq!(
+            build_filename(&info, FILE_TEMPLATE),
+            Some("IU - Good Day.mp3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_organize_dir() {
+        let root = Path::new("/music");
+        let info = TrackInfo {
+            artists: split_artists("IU, SUGA"),
+            album: Some("에잇".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            organize_dir(root, &info, ORGANIZE_TEMPLATE),
+            Some(PathBuf::from("/music/IU/에잇"))
+        );
+        let info = TrackInfo {
+            album_artist: Some("Various Artists".to_string()),
+            ..info
+        };
+        assert_eq!(
+            organize_dir(root, &info, ORGANIZE_TEMPLATE),
+            Some(PathBuf::from("/music/Various Artists/에잇"))
+        );
+        let info = TrackInfo {
+            album: None,
+            ..info
+        };
+        assert_eq!(organize_dir(root, &info, ORGANIZE_TEMPLATE), None);
+    }
+
+    #[test]
+    fn test_templates_choose_year() {
+        let root = Path::new("/music");
+        let info = TrackInfo {
+            artists: vec!["Queen".to_string()],
+            title: Some("Bohemian Rhapsody".to_string()),
+            album: Some("A Night at the Opera".to_string()),
+            track_number: Some(11),
+            year: Some(2011),
+            original_year: Some(1975),
+            ..Default::default()
+        };
+        assert_eq!(
+            organize_dir(root, &info, "{artist}/{original_year} - {album}"),
+            Some(PathBuf::from("/music/Queen/1975 - A Night at the Opera"))
+        );
+        assert_eq!(
+            organize_dir(root, &info, "{artist}/{year} - {album}"),
+            Some(PathBuf::from("/music/Queen/2011 - A Night at the Opera"))
+        );
+        assert_eq!(
+            build_filename(&info, "{track}. {title} ({original_year})"),
+            Some("11. Bohemian Rhapsody (1975).mp3".to_string())
+        );
+        // 최초 발매 연도가 없으면 발매 연도를 쓴다
+        let info = TrackInfo {
+            original_year: None,
+            ..info
+        };
+        assert_eq!(
+            build_filename(&info, "{original_year} {title}"),
+            Some("2011 Bohemian Rhapsody.mp3".to_string())
+        );
+        assert_eq!(build_filename(&info, "{genre} {title}"), None);
+        assert_eq!(build_filename(&info, "{unknown}"), None);
+    }
+
+    #[test]
+    fn test_templates_keep_trailing_dots_inside_names() {
+        let info = TrackInfo {
+            artists: vec!["t.A.T.u.".to_string()],
+            title: Some("All The Things She Said".to_string()),
+            album: Some("200 km/h in the Wrong Lane".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_filename(&info, FILE_TEMPLATE),
+            Some("t.A.T.u. - All The Things She Said.mp3".to_string())
+        );
+        assert_eq!(
+            build_filename(&info, "{artist}"),
+            Some("t.A.T.u..mp3".to_string())
+        );
+        let artist_dir = if cfg!(target_os = "windows") {
+            "t.A.T.u"
+        } else {
+            "t.A.T.u."
+        };
+        assert_eq!(
+            organize_dir(Path::new("/music"), &info, "{artist}/{album}"),
+            Some(
+                Path::new("/music")
+                    .join(artist_dir)
+                    .join("200 km_h in the Wrong Lane")
+            )
+        );
     }
 
     #[test]
@@ -105,38 +664,49 @@ mod tests {
             title: Some("Good Day".to_string()),
             ..Default::default()
         };
-        assert_eq!(build_filename(&info), None);
+        assert_eq!(build_filename(&info, FILE_TEMPLATE), None);
     }
 
     #[test]
     fn test_build_filename_missing_title() {
         let info = TrackInfo {
-            artist: Some("IU".to_string()),
+            artists: vec!["IU".to_string()],
             ..Default::default()
         };
-        assert_eq!(build_filename(&info), None);
+        assert_eq!(build_filename(&info, FILE_TEMPLATE), None);
     }
 
     #[test]
     fn test_build_filename_empty_strings() {
         let info = TrackInfo {
-            artist: Some("".to_string()),
+            artists: vec!["".to_string()],
             title: Some("Good Day".to_string()),
             ..Default::default()
         };
-        assert_eq!(build_filename(&info), None);
+        assert_eq!(build_filename(&info, FILE_TEMPLATE), None);
     }
 
     #[test]
     fn test_build_filename_sanitizes() {
         let info = TrackInfo {
-            artist: Some("AC/DC".to_string()),
+            artists: vec!["AC/DC".to_string()],
             title: Some("Back\0Slash".to_string()),
             ..Default::default()
         };
         assert_eq!(
-            build_filename(&info),
+            build_filename(&info, FILE_TEMPLATE),
             Some("AC_DC - Back_Slash.mp3".to_string())
         );
     }
+
+    #[test]
+    fn test_check_template() {
+        assert!(check_template(FILE_TEMPLATE).is_ok());
+        assert!(check_template(ORGANIZE_TEMPLATE).is_ok());
+        assert!(check_template("{track}. {title} ({original_year})").is_ok());
+        assert!(check_template("{artst} - {title}").is_err());
+        assert!(check_template("{artist - {title}").is_err());
+        assert!(check_template("{artist} - {title").is_err());
+        assert!(check_template(" ").is_err());
+    }
 }
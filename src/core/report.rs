@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+use crate::core::tagger;
+
+/// 파일별 처리 결과.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryStatus {
+    /// 태그를 기록함
+    Changed,
+    /// 기록했지만 확인이 필요함 (파일명 추론만 적용, 앨범 아트 제외 등)
+    Flagged,
+    /// 결과가 없거나 사용자가 건너뜀
+    Skipped,
+    /// 기록 실패
+    Failed,
+}
+
+impl EntryStatus {
+    fn label(self) -> &'static str {
+        match self {
+            EntryStatus::Changed => "변경",
+            EntryStatus::Flagged => "확인 필요",
+            EntryStatus::Skipped => "건너뜀",
+            EntryStatus::Failed => "실패",
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            EntryStatus::Changed => "changed",
+            EntryStatus::Flagged => "flagged",
+            EntryStatus::Skipped => "skipped",
+            EntryStatus::Failed => "failed",
+        }
+    }
+}
+
+/// 보고서의 파일 한 줄.
+#[derive(Debug, Clone)]
+pub struct ReportEntry {
+    pub path: PathBuf,
+    pub status: EntryStatus,
+    /// 적용한 곡 요약 또는 건너뛴/실패한 사유
+    pub detail: String,
+    /// 파일명을 바꿨으면 새 경로
+    pub new_path: Option<PathBuf>,
+    /// 기록한 앨범 아트 (`Report::arts` 인덱스)
+    art: Option<usize>,
+}
+
+/// 일괄 작업(fetch, auto)이 끝난 뒤 남기는 HTML/Markdown 보고서.
+/// 경로 없이 만들면 아무것도 모으지 않아, 호출하는 쪽은 항상 `push`하면 된다.
+pub struct Report {
+    path: Option<PathBuf>,
+    title: String,
+    entries: Vec<ReportEntry>,
+    /// 기록한 앨범 아트. 같은 앨범의 아트는 한 번만 보관한다.
+    arts: Vec<Vec<u8>>,
+    art_index: HashMap<Vec<u8>, usize>,
+}
+
+impl Report {
+    /// `path`의 확장자(.html/.htm/.md)로 형식을 정한다. 지원하지 않는 확장자는 에러.
+    pub fn new(path: Option<PathBuf>, title: &str) -> Result<Self> {
+        if let Some(ref path) = path {
+            format_of(path)?;
+        }
+        Ok(Self {
+            path,
+            title: title.to_string(),
+            entries: Vec::new(),
+            arts: Vec::new(),
+            art_index: HashMap::new(),
+        })
+    }
+
+    /// 파일 하나의 결과를 추가한다. `art`는 기록한 앨범 아트로, 보고서에 썸네일로 들어간다.
+    pub fn push(
+        &mut self,
+        path: &Path,
+        status: EntryStatus,
+        detail: impl Into<String>,
+        new_path: Option<&Path>,
+        art: Option<&[u8]>,
+    ) {
+        if self.path.is_none() {
+            return;
+        }
+        let art = art.map(|data| {
+            let key = Sha256::digest(data).to_vec();
+            *self.art_index.entry(key).or_insert_with(|| {
+                self.arts.push(data.to_vec());
+                self.arts.len() - 1
+            })
+        });
+        self.entries.push(ReportEntry {
+            path: path.to_path_buf(),
+            status,
+            detail: detail.into(),
+            new_path: new_path.map(Path::to_path_buf),
+            art,
+        });
+    }
+
+    /// 보고서 경로가 있으면 파일로 저장하고 그 경로를 안내한다.
+    pub fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let content = match format_of(path)? {
+            Format::Html => self.to_html(SystemTime::now()),
+            Format::Markdown => self.to_markdown(path, SystemTime::now())?,
+        };
+        std::fs::write(path, content)
+            .with_context(|| format!("보고서를 저장할 수 없습니다: {}", path.display()))?;
+        println!("보고서를 저장했습니다: {}", path.display());
+        Ok(())
+    }
+
+    fn count(&self, status: EntryStatus) -> usize {
+        self.entries.iter().filter(|e| e.status == status).count()
+    }
+
+    fn summary(&self) -> String {
+        [
+            EntryStatus::Changed,
+            EntryStatus::Flagged,
+            EntryStatus::Skipped,
+            EntryStatus::Failed,
+        ]
+        .iter()
+        .map(|&s| format!("{} {}건", s.label(), self.count(s)))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    /// 앨범 아트를 data URI로 넣은 단일 HTML 파일.
+    fn to_html(&self, now: SystemTime) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!doctype html>\n<html lang=\"ko\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{style}</style>\n</head>\n<body>\n\
+             <h1>{title}</h1>\n<p>{time} · {summary}</p>\n<table>\n\
+             <thead><tr><th>아트</th><th>파일</th><th>결과</th><th>내용</th><th>새 경로</th></tr></thead>\n<tbody>\n",
+            title = escape_html(&self.title),
+            style = HTML_STYLE,
+            time = utc_timestamp(now),
+            summary = self.summary(),
+        );
+        for entry in &self.entries {
+            let art = entry
+                .art
+                .map(|i| {
+                    let data = &self.arts[i];
+                    format!(
+                        "<img src=\"data:{};base64,{}\" alt=\"\">",
+                        tagger::detect_mime_type(data),
+                        base64::engine::general_purpose::STANDARD.encode(data)
+                    )
+                })
+                .unwrap_or_default();
+            let _ = writeln!(
+                html,
+                "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                entry.status.class(),
+                art,
+                escape_html(&entry.path.display().to_string()),
+                entry.status.label(),
+                escape_html(&entry.detail),
+                entry
+                    .new_path
+                    .as_ref()
+                    .map(|p| escape_html(&p.display().to_string()))
+                    .unwrap_or_default(),
+            );
+        }
+        html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+        html
+    }
+
+    /// Markdown 표. 앨범 아트는 보고서 옆 `<이름>-art/` 디렉토리에 저장하고 상대 경로로 연결한다.
+    fn to_markdown(&self, path: &Path, now: SystemTime) -> Result<String> {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "report".to_string());
+        let art_dir_name = format!("{}-art", stem);
+        let mut art_files = Vec::new();
+        if !self.arts.is_empty() {
+            let art_dir = path.with_file_name(&art_dir_name);
+            std::fs::create_dir_all(&art_dir).with_context(|| {
+                format!("아트 디렉토리를 만들 수 없습니다: {}", art_dir.display())
+            })?;
+            for (i, data) in self.arts.iter().enumerate() {
+                let ext = match tagger::detect_mime_type(data).as_str() {
+                    "image/png" => "png",
+                    _ => "jpg",
+                };
+                let name = format!("{:03}.{}", i + 1, ext);
+                std::fs::write(art_dir.join(&name), data)?;
+                art_files.push(format!("{}/{}", art_dir_name, name));
+            }
+        }
+
+        let mut md = format!(
+            "# {}\n\n{} · {}\n\n| 아트 | 파일 | 결과 | 내용 | 새 경로 |\n|---|---|---|---|---|\n",
+            self.title,
+            utc_timestamp(now),
+            self.summary()
+        );
+        for entry in &self.entries {
+            let art = entry
+                .art
+                .map(|i| format!("<img src=\"{}\" width=\"48\">", art_files[i]))
+                .unwrap_or_default();
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} | {} | {} |",
+                art,
+                escape_markdown(&entry.path.display().to_string()),
+                entry.status.label(),
+                escape_markdown(&entry.detail),
+                entry
+                    .new_path
+                    .as_ref()
+                    .map(|p| escape_markdown(&p.display().to_string()))
+                    .unwrap_or_default(),
+            );
+        }
+        Ok(md)
+    }
+}
+
+enum Format {
+    Html,
+    Markdown,
+}
+
+fn format_of(path: &Path) -> Result<Format> {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "html" | "htm" => Ok(Format::Html),
+        "md" | "markdown" => Ok(Format::Markdown),
+        _ => bail!(
+            "보고서 형식을 알 수 없습니다: {} (.html 또는 .md)",
+            path.display()
+        ),
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;font-size:14px;margin:16px}\
+table{border-collapse:collapse;width:100%}\
+th,td{text-align:left;padding:4px 8px;border-bottom:1px solid #eee;vertical-align:middle}\
+img{width:48px;height:48px;object-fit:cover}\
+tr.flagged{background:#fff7d6}tr.skipped{color:#777}tr.failed{background:#fde2e2}";
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 표 칸을 깨뜨리는 `|`와 줄바꿈을 처리한다.
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+/// "2026-10-15 09:30 UTC" 형식의 시각 문자열.
+fn utc_timestamp(time: SystemTime) -> String {
+    let (year, month, day, hour, minute, _) = tagger::utc_date_time(time);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year, month, day, hour, minute
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_utc_timestamp() {
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "1970-01-01 00:00 UTC");
+        let t = UNIX_EPOCH + Duration::from_secs(1_709_217_000); // 2024-02-29 14:30
+        assert_eq!(utc_timestamp(t), "2024-02-29 14:30 UTC");
+    }
+
+    #[test]
+    fn test_html_report() {
+        let mut report = Report::new(Some(PathBuf::from("out.html")), "fetch <music>").unwrap();
+        let art = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        report.push(
+            Path::new("a.mp3"),
+            EntryStatus::Changed,
+            "IU - Blueming",
+            None,
+            Some(&art),
+        );
+        report.push(
+            Path::new("b.mp3"),
+            EntryStatus::Changed,
+            "IU - 시간의 바깥",
+            None,
+            Some(&art),
+        );
+        report.push(
+            Path::new("c.mp3"),
+            EntryStatus::Skipped,
+            "검색 결과 없음",
+            None,
+            None,
+        );
+        assert_eq!(report.arts.len(), 1);
+
+        let html = report.to_html(UNIX_EPOCH);
+        assert!(html.contains("<title>fetch &lt;music&gt;</title>"));
+        assert!(html.contains("변경 2건, 확인 필요 0건, 건너뜀 1건, 실패 0건"));
+        assert_eq!(html.matches("data:image/png;base64,").count(), 2);
+        assert!(html.contains("<tr class=\"skipped\">"));
+    }
+
+    #[test]
+    fn test_disabled_report_collects_nothing() {
+        let mut report = Report::new(None, "auto").unwrap();
+        report.push(Path::new("a.mp3"), EntryStatus::Failed, "x", None, None);
+        assert!(report.entries.is_empty());
+        assert!(report.save().is_ok());
+        assert!(Report::new(Some(PathBuf::from("out.txt")), "auto").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::fetch_state;
+use crate::models::TrackInfo;
+
+/// `auto`에서 검토하지 않은 파일 목록의 파일 이름. 대상 디렉토리에 생성된다.
+pub const REVIEW_FILE: &str = ".mp3tag-review.json";
+
+/// 자동으로 적용하기 애매해 사용자 검토로 미룬 파일.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewItem {
+    pub path: PathBuf,
+    /// 기존 태그에 추론 값을 더한 태그. 후보를 고르면 이 위에 덮어쓴다.
+    pub local: TrackInfo,
+    /// 추론으로 새로 채워지는 값이 있는지
+    pub can_infer: bool,
+    /// 점수순 후보
+    pub candidates: Vec<ReviewCandidate>,
+}
+
+/// 검토할 검색 결과 하나.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewCandidate {
+    pub score: f64,
+    pub track: TrackInfo,
+    /// 단순화한 검색어로 찾았으면 그 안내
+    pub note: Option<String>,
+    /// 이번 실행에서 후보를 찾은 소스의 인덱스. 저장하지 않는다.
+    #[serde(skip)]
+    pub client: usize,
+}
+
+/// 검토 대기 목록. `auto`가 남기고 GUI의 검토 탭에서 이어서 처리한다.
+/// 경로는 대상 디렉토리 기준 상대 경로로 저장한다.
+pub struct ReviewQueue {
+    path: PathBuf,
+    root: PathBuf,
+    pub items: Vec<ReviewItem>,
+}
+
+impl ReviewQueue {
+    /// 빈 목록. 저장하면 기존 목록을 덮어쓴다.
+    pub fn new(target: &Path) -> Self {
+        let root = fetch_state::state_dir(target);
+        Self {
+            path: root.join(REVIEW_FILE),
+            root,
+            items: Vec::new(),
+        }
+    }
+
+    /// 대상 경로의 검토 목록을 읽는다. 파일이 없으면 빈 목록.
+    #[cfg(any(feature = "gui", test))]
+    pub fn load(target: &Path) -> Result<Self> {
+        let mut queue = Self::new(target);
+        if queue.path.exists() {
+            let content = std::fs::read(&queue.path).context("검토 목록을 읽을 수 없습니다")?;
+            let items: Vec<ReviewItem> =
+                serde_json::from_slice(&content).context("검토 목록 형식이 올바르지 않습니다")?;
+            queue.items = items
+                .into_iter()
+                .map(|item| ReviewItem {
+                    path: queue.root.join(&item.path),
+                    ..item
+                })
+                .collect();
+        }
+        Ok(queue)
+    }
+
+    /// 목록을 저장한다. 비어 있으면 파일을 지운다.
+    pub fn save(&self) -> Result<()> {
+        if self.items.is_empty() {
+            if self.path.exists() {
+                std::fs::remove_file(&self.path).context("검토 목록을 삭제할 수 없습니다")?;
+            }
+            return Ok(());
+        }
+        let items: Vec<ReviewItem> = self
+            .items
+            .iter()
+            .map(|item| ReviewItem {
+                path: item
+                    .path
+                    .strip_prefix(&self.root)
+                    .unwrap_or(&item.path)
+                    .to_path_buf(),
+                ..item.clone()
+            })
+            .collect();
+        let content = serde_json::to_vec_pretty(&items)?;
+        std::fs::write(&self.path, content).context("검토 목록을 저장할 수 없습니다")
+    }
+
+    /// 처리한 파일을 목록에서 뺀다.
+    #[cfg(any(feature = "gui", test))]
+    pub fn remove(&mut self, path: &Path) {
+        self.items.retain(|item| item.path != path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_save_load_remove() {
+        let dir = TempDir::new("review");
+        let song = dir.join("a").join("song.mp3");
+
+        let mut queue = ReviewQueue::new(&dir);
+        queue.items.push(ReviewItem {
+            path: song.clone(),
+            local: TrackInfo {
+                title: Some("Blueming".to_string()),
+                ..Default::default()
+            },
+            can_infer: true,
+            candidates: vec![ReviewCandidate {
+                score: 0.75,
+                track: TrackInfo {
+                    title: Some("Blueming Remix".to_string()),
+                    source: "melon".to_string(),
+                    ..Default::default()
+                },
+                note: None,
+                client: 3,
+            }],
+        });
+        queue.save().unwrap();
+        let saved = std::fs::read_to_string(dir.join(REVIEW_FILE)).unwrap();
+        assert!(saved.contains("\"a/song.mp3\""));
+
+        let mut loaded = ReviewQueue::load(&dir).unwrap();
+        assert_eq!(loaded.items.len(), 1);
+        assert_eq!(loaded.items[0].path, song);
+        assert_eq!(loaded.items[0].candidates[0].client, 0);
+
+        loaded.remove(&song);
+        loaded.save().unwrap();
+        assert!(!dir.join(REVIEW_FILE).exists());
+    }
+}
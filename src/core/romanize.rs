@@ -0,0 +1,176 @@
+/// 한글 음절의 초성 로마자 (국어의 로마자 표기법).
+const INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+/// 중성 로마자.
+const MEDIALS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+/// 종성(받침) 로마자. 겹받침은 대표음으로 읽는다.
+const FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+// 자주 쓰는 자모 인덱스
+const INITIAL_N: usize = 2;
+const INITIAL_D: usize = 3;
+const INITIAL_R: usize = 5;
+const INITIAL_M: usize = 6;
+const INITIAL_G: usize = 0;
+const INITIAL_J: usize = 12;
+const INITIAL_SILENT: usize = 11;
+const FINAL_N: usize = 4;
+const FINAL_L: usize = 8;
+const FINAL_NG: usize = 21;
+const FINAL_H: usize = 27;
+
+/// 한글 음절이 하나라도 있는지 확인한다.
+pub fn has_hangul(s: &str) -> bool {
+    s.chars().any(is_syllable)
+}
+
+fn is_syllable(c: char) -> bool {
+    ('가'..='힣').contains(&c)
+}
+
+/// 음절을 (초성, 중성, 종성) 인덱스로 나눈다.
+fn decompose(c: char) -> Option<(usize, usize, usize)> {
+    if !is_syllable(c) {
+        return None;
+    }
+    let index = c as usize - '가' as usize;
+    Some((index / (21 * 28), index % (21 * 28) / 28, index % 28))
+}
+
+/// 한글을 국어의 로마자 표기법(Revised Romanization)으로 옮긴다. 한글이 아닌 글자는 그대로 둔다.
+/// 연음, 받침 ㅎ의 축약/탈락, 비음화, ㄹ 동화(설날 → seollal, 종로 → jongno)를 반영한다.
+/// 대소문자는 바꾸지 않는다 (모두 소문자).
+pub fn romanize(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut syllables: Vec<Option<(usize, usize, usize)>> =
+        chars.iter().map(|&c| decompose(c)).collect();
+
+    // 받침이 다음 음절로 넘어가거나 합쳐지는 경우를 먼저 반영한다
+    for i in 0..syllables.len().saturating_sub(1) {
+        let (Some((ini, med, fin)), Some((next_ini, next_med, next_fin))) =
+            (syllables[i], syllables[i + 1])
+        else {
+            continue;
+        };
+        let (fin, next_ini) = match (fin, next_ini) {
+            (0, _) | (FINAL_NG, _) => continue,
+            (FINAL_H, INITIAL_G) => (0, 15),    // ㅎ+ㄱ → ㅋ
+            (FINAL_H, INITIAL_D) => (0, 16),    // ㅎ+ㄷ → ㅌ
+            (FINAL_H, INITIAL_J) => (0, 14),    // ㅎ+ㅈ → ㅊ
+            (_, INITIAL_SILENT) => linked(fin), // 연음
+            _ => continue,
+        };
+        syllables[i] = Some((ini, med, fin));
+        syllables[i + 1] = Some((next_ini, next_med, next_fin));
+    }
+
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let Some((ini, med, fin)) = syllables[i] else {
+            out.push(c);
+            continue;
+        };
+        let prev_fin = i
+            .checked_sub(1)
+            .and_then(|p| syllables[p])
+            .map(|(_, _, f)| f);
+        let next_ini = syllables.get(i + 1).copied().flatten().map(|(n, _, _)| n);
+
+        out.push_str(initial(ini, prev_fin));
+        out.push_str(MEDIALS[med]);
+        out.push_str(final_sound(fin, next_ini));
+    }
+    out
+}
+
+/// 받침이 뒤 음절의 초성 ㅇ 자리로 넘어갈 때 (남는 받침, 다음 초성).
+fn linked(fin: usize) -> (usize, usize) {
+    match fin {
+        1 => (0, 0),                // ㄱ
+        2 => (0, 1),                // ㄲ
+        3 => (1, 9),                // ㄳ
+        4 => (0, INITIAL_N),        // ㄴ
+        5 => (FINAL_N, INITIAL_J),  // ㄵ
+        6 => (0, INITIAL_N),        // ㄶ
+        7 => (0, INITIAL_D),        // ㄷ
+        8 => (0, INITIAL_R),        // ㄹ
+        9 => (FINAL_L, INITIAL_G),  // ㄺ
+        10 => (FINAL_L, INITIAL_M), // ㄻ
+        11 => (FINAL_L, 7),         // ㄼ
+        12 => (FINAL_L, 9),         // ㄽ
+        13 => (FINAL_L, 16),        // ㄾ
+        14 => (FINAL_L, 17),        // ㄿ
+        15 => (0, INITIAL_R),       // ㅀ
+        16 => (0, INITIAL_M),       // ㅁ
+        17 => (0, 7),               // ㅂ
+        18 => (17, 9),              // ㅄ
+        19 => (0, 9),               // ㅅ
+        20 => (0, 10),              // ㅆ
+        22 => (0, INITIAL_J),       // ㅈ
+        23 => (0, 14),              // ㅊ
+        24 => (0, 15),              // ㅋ
+        25 => (0, 16),              // ㅌ
+        26 => (0, 17),              // ㅍ
+        _ => (0, INITIAL_SILENT),   // ㅎ은 탈락
+    }
+}
+
+/// 앞 음절 받침에 따라 바뀌는 초성.
+fn initial(ini: usize, prev_fin: Option<usize>) -> &'static str {
+    match (ini, prev_fin) {
+        // ㄹ+ㄹ, ㄴ+ㄹ, ㄹ+ㄴ → ll
+        (INITIAL_R, Some(FINAL_L | FINAL_N)) | (INITIAL_N, Some(FINAL_L)) => "l",
+        // 그 밖의 받침 뒤 ㄹ은 ㄴ으로 (종로 → jongno, 백로 → baengno)
+        (INITIAL_R, Some(f)) if f != 0 => "n",
+        _ => INITIALS[ini],
+    }
+}
+
+/// 뒤 음절 초성에 따라 바뀌는 받침 (비음화, ㄹ 동화).
+fn final_sound(fin: usize, next_ini: Option<usize>) -> &'static str {
+    let sound = FINALS[fin];
+    match next_ini {
+        Some(INITIAL_R) if fin == FINAL_N => "l",
+        Some(INITIAL_N | INITIAL_M | INITIAL_R) => match sound {
+            "k" => "ng",
+            "t" => "n",
+            "p" => "m",
+            other => other,
+        },
+        _ => sound,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_romanize() {
+        assert_eq!(romanize("밤편지"), "bampyeonji");
+        assert_eq!(romanize("소격동"), "sogyeokdong");
+        assert_eq!(romanize("좋은 날"), "joeun nal");
+        assert_eq!(romanize("IU 꽃갈피"), "IU kkotgalpi");
+        assert_eq!(romanize("좋다"), "jota");
+        // 비음화와 ㄹ 동화
+        assert_eq!(romanize("국민"), "gungmin");
+        assert_eq!(romanize("종로"), "jongno");
+        assert_eq!(romanize("설날"), "seollal");
+        assert_eq!(romanize("신라"), "silla");
+        assert_eq!(romanize("팔레트"), "palleteu");
+    }
+
+    #[test]
+    fn test_has_hangul() {
+        assert!(has_hangul("IU 밤편지"));
+        assert!(!has_hangul("Blueming"));
+    }
+}
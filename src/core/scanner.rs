@@ -1,36 +1,72 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
-use crate::core::tagger;
+use crate::core::ignore::{self, IgnoreFile};
+use crate::core::{audio, filelock, glob, longpath, tagger};
+use crate::exit;
 use crate::models::Mp3File;
 
 /// 디렉토리를 재귀 탐색하여 모든 MP3 파일을 스캔한다.
 /// 각 파일의 ID3 태그를 읽어 Mp3File 목록을 반환한다.
 pub fn scan_directory(dir: &Path) -> Result<Vec<Mp3File>> {
-    let mut files = Vec::new();
-    collect_mp3_files(dir, &mut files)?;
-    files.sort_by(|a, b| a.path.cmp(&b.path));
-    Ok(files)
+    let paths = find_mp3_paths(dir)?;
+    Ok(load_files(&paths, |_| {}))
 }
 
-/// 디렉토리를 재귀 순회하며 MP3 파일을 수집한다.
-fn collect_mp3_files(dir: &Path, files: &mut Vec<Mp3File>) -> Result<()> {
+/// 디렉토리를 재귀 탐색하여 MP3 파일 경로만 정렬하여 반환한다 (태그는 읽지 않음).
+/// 진행률 표시를 위해 전체 개수를 먼저 알아야 할 때 사용한다.
+/// 탐색하는 디렉토리의 `.mp3tagignore`(gitignore 형식)에 맞는 파일과 폴더는 건너뛴다.
+pub fn find_mp3_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    collect_mp3_paths(dir, &mut paths, &mut Vec::new())?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// 경로 목록의 태그를 읽어 Mp3File 목록을 반환한다. 파일 하나를 읽을 때마다 `on_progress`를 호출한다.
+pub fn load_files(paths: &[PathBuf], mut on_progress: impl FnMut(&Path)) -> Vec<Mp3File> {
+    paths
+        .iter()
+        .map(|path| {
+            let mp3 = load_mp3_file(path);
+            on_progress(path);
+            mp3
+        })
+        .collect()
+}
+
+/// 디렉토리를 재귀 순회하며 MP3 파일 경로를 수집한다.
+/// `ignores`는 바깥 디렉토리부터 읽은 `.mp3tagignore` 규칙이다.
+fn collect_mp3_paths(
+    dir: &Path,
+    paths: &mut Vec<PathBuf>,
+    ignores: &mut Vec<IgnoreFile>,
+) -> Result<()> {
     if !dir.is_dir() {
         anyhow::bail!("{}은(는) 디렉토리가 아닙니다", dir.display());
     }
 
+    let own = IgnoreFile::load(dir);
+    let loaded = own.is_some();
+    ignores.extend(own);
     for entry in std::fs::read_dir(dir)? {
         let entry = entry?;
         let path = entry.path();
+        let is_dir = path.is_dir();
+        if ignore::is_ignored(ignores, &path, is_dir) {
+            continue;
+        }
 
-        if path.is_dir() {
-            collect_mp3_files(&path, files)?;
+        if is_dir {
+            collect_mp3_paths(&path, paths, ignores)?;
         } else if is_mp3(&path) {
-            let mp3 = load_mp3_file(&path);
-            files.push(mp3);
+            paths.push(path);
         }
     }
+    if loaded {
+        ignores.pop();
+    }
 
     Ok(())
 }
@@ -43,38 +79,260 @@ fn is_mp3(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-/// MP3 파일 하나를 로드하여 태그 정보를 포함한 Mp3File을 반환한다.
+/// MP3 파일 하나를 로드하여 태그 정보와 인코더 정보를 포함한 Mp3File을 반환한다.
 fn load_mp3_file(path: &Path) -> Mp3File {
+    // 읽는 도중 바뀐 것도 기록 전에 알아챌 수 있도록 태그보다 먼저 기억한다
+    filelock::remember(path);
+    let encoder = audio::encoder_info(path);
     match tagger::read_tags(path) {
         Ok(Some(tags)) => Mp3File {
             path: path.to_path_buf(),
             has_tags: true,
             current_tags: Some(tags),
+            encoder,
         },
         _ => Mp3File {
             path: path.to_path_buf(),
             has_tags: false,
             current_tags: None,
+            encoder,
         },
     }
 }
 
 /// 단일 MP3 파일을 로드한다. 파일이 없거나 MP3가 아니면 에러.
 pub fn load_single_file(path: &Path) -> Result<Mp3File> {
-    if !path.exists() {
-        anyhow::bail!("파일을 찾을 수 없습니다: {}", path.display());
-    }
-    if !is_mp3(path) {
-        anyhow::bail!("MP3 파일이 아닙니다: {}", path.display());
-    }
+    check_mp3_file(path)?;
     Ok(load_mp3_file(path))
 }
 
 /// 경로가 디렉토리면 재귀 스캔, 파일이면 단일 로드한다.
 pub fn scan_path(path: &Path) -> Result<Vec<Mp3File>> {
+    let path = &longpath::normalize(path);
     if path.is_dir() {
         scan_directory(path)
     } else {
         Ok(vec![load_single_file(path)?])
     }
 }
+
+/// 명령에 준 경로 목록. 셸 글롭 패턴과 `-`(표준 입력으로 받은 줄 단위 파일 목록)를 펼친 결과다.
+#[derive(Debug, Clone)]
+pub struct Inputs {
+    paths: Vec<PathBuf>,
+    root: PathBuf,
+}
+
+impl Inputs {
+    /// 경로 하나로 만든다.
+    pub fn one(path: &Path) -> Self {
+        let path = longpath::normalize(path);
+        Self {
+            paths: vec![path.clone()],
+            root: path,
+        }
+    }
+
+    /// 인자를 펼친다. 글롭 문자가 있고 그 이름의 파일이 없으면 패턴으로 펼치며,
+    /// `-`는 표준 입력에서 파일 목록을 읽는다 (`find`, `fzf` 파이프라인용).
+    pub fn expand(args: &[PathBuf]) -> Result<Self> {
+        Self::expand_with(args, || Ok(std::io::read_to_string(std::io::stdin())?))
+    }
+
+    fn expand_with(args: &[PathBuf], read_stdin: impl FnOnce() -> Result<String>) -> Result<Self> {
+        let mut read_stdin = Some(read_stdin);
+        let mut paths = Vec::new();
+        for arg in args {
+            let arg = &longpath::normalize(arg);
+            if arg.as_os_str() == "-" {
+                let read = read_stdin
+                    .take()
+                    .context("표준 입력(-)은 한 번만 지정할 수 있습니다")?;
+                paths.extend(
+                    read()?
+                        .lines()
+                        .map(|line| line.trim_end_matches('\r'))
+                        .filter(|line| !line.trim().is_empty())
+                        .map(|line| longpath::normalize(Path::new(line))),
+                );
+            } else if !arg.exists() && glob::is_pattern(&arg.to_string_lossy()) {
+                let found: Vec<PathBuf> = glob::expand(arg)?
+                    .into_iter()
+                    .filter(|p| p.is_dir() || is_mp3(p))
+                    .collect();
+                if found.is_empty() {
+                    return Err(exit::nothing_matched(format!(
+                        "패턴과 맞는 MP3 파일이 없습니다: {}",
+                        arg.display()
+                    )));
+                }
+                paths.extend(found);
+            } else {
+                paths.push(arg.clone());
+            }
+        }
+        if paths.is_empty() {
+            anyhow::bail!("처리할 파일이 없습니다");
+        }
+        let root = match paths.as_slice() {
+            [path] => path.clone(),
+            _ => common_dir(&paths),
+        };
+        Ok(Self { paths, root })
+    }
+
+    /// 펼친 경로 목록.
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// 상태 파일, 저장 대기 목록, 파일명 추론의 기준 경로.
+    /// 입력이 하나면 그 경로, 여러 개면 모두를 포함하는 가장 가까운 상위 디렉토리.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 디렉토리는 재귀 탐색하고 파일은 그대로 넣어 중복 없이 MP3 경로를 모은다.
+    /// 디렉토리마다 경로순이며, 입력 순서는 유지한다.
+    pub fn mp3_paths(&self) -> Result<Vec<PathBuf>> {
+        let mut seen = std::collections::HashSet::new();
+        let mut mp3s = Vec::new();
+        for path in &self.paths {
+            let found = if path.is_dir() {
+                find_mp3_paths(path)?
+            } else {
+                check_mp3_file(path)?;
+                vec![path.clone()]
+            };
+            mp3s.extend(found.into_iter().filter(|p| seen.insert(p.clone())));
+        }
+        Ok(mp3s)
+    }
+}
+
+/// 파일이 있고 MP3인지 확인한다.
+fn check_mp3_file(path: &Path) -> Result<()> {
+    if !path.exists() {
+        anyhow::bail!("파일을 찾을 수 없습니다: {}", path.display());
+    }
+    if !is_mp3(path) {
+        anyhow::bail!("MP3 파일이 아닙니다: {}", path.display());
+    }
+    Ok(())
+}
+
+/// 경로들(파일이면 그 상위 디렉토리)을 모두 포함하는 가장 가까운 디렉토리.
+fn common_dir(paths: &[PathBuf]) -> PathBuf {
+    let dirs = paths.iter().map(|p| {
+        if p.is_dir() {
+            p.as_path()
+        } else {
+            p.parent().unwrap_or(Path::new(""))
+        }
+    });
+    let mut common: Option<PathBuf> = None;
+    for dir in dirs {
+        common = Some(match common {
+            None => dir.to_path_buf(),
+            Some(common) => common
+                .components()
+                .zip(dir.components())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect(),
+        });
+    }
+    common
+        .filter(|c| !c.as_os_str().is_empty())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_expand_inputs() {
+        let dir = TempDir::new("inputs");
+        std::fs::create_dir_all(dir.join("A")).unwrap();
+        std::fs::create_dir_all(dir.join("B")).unwrap();
+        for file in ["A/1.mp3", "A/2.mp3", "B/3.mp3", "B/cover.jpg"] {
+            std::fs::write(dir.join(file), b"").unwrap();
+        }
+
+        let stdin = format!(
+            "{}\n\n{}\r\n",
+            dir.join("B/3.mp3").display(),
+            dir.join("A/1.mp3").display()
+        );
+        let inputs =
+            Inputs::expand_with(&[PathBuf::from("-"), dir.join("A")], || Ok(stdin)).unwrap();
+        assert_eq!(inputs.root(), &*dir);
+        // 입력 순서를 지키고 이미 나온 파일은 다시 넣지 않는다
+        assert_eq!(
+            inputs.mp3_paths().unwrap(),
+            [
+                dir.join("B/3.mp3"),
+                dir.join("A/1.mp3"),
+                dir.join("A/2.mp3")
+            ]
+        );
+
+        let inputs = Inputs::expand_with(&[dir.join("*/*")], || unreachable!()).unwrap();
+        assert_eq!(
+            inputs.paths(),
+            [
+                dir.join("A/1.mp3"),
+                dir.join("A/2.mp3"),
+                dir.join("B/3.mp3")
+            ]
+        );
+        assert!(Inputs::expand_with(&[dir.join("C*")], || unreachable!()).is_err());
+
+        let inputs = Inputs::expand_with(&[dir.join("A/1.mp3")], || unreachable!()).unwrap();
+        assert_eq!(inputs.root(), dir.join("A/1.mp3"));
+    }
+
+    #[test]
+    fn test_load_files_reports_each_file() {
+        let dir = TempDir::new("progress");
+        std::fs::create_dir_all(dir.join("B")).unwrap();
+        for file in ["B/2.mp3", "1.mp3", "B/1.mp3", "cover.jpg"] {
+            std::fs::write(dir.join(file), b"").unwrap();
+        }
+
+        let paths = find_mp3_paths(&dir).unwrap();
+        assert_eq!(
+            paths,
+            [dir.join("1.mp3"), dir.join("B/1.mp3"), dir.join("B/2.mp3")]
+        );
+        let mut seen = Vec::new();
+        let files = load_files(&paths, |path| seen.push(path.to_path_buf()));
+        assert_eq!(seen, paths);
+        let loaded: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(loaded, paths);
+    }
+
+    #[test]
+    fn test_find_mp3_paths_skips_ignored() {
+        let dir = TempDir::new("ignore");
+        std::fs::create_dir_all(dir.join("Voice Memos")).unwrap();
+        std::fs::create_dir_all(dir.join("IU")).unwrap();
+        for file in [
+            "Voice Memos/memo.mp3",
+            "IU/1.mp3",
+            "IU/1.part.mp3",
+            "IU/2.part.mp3",
+        ] {
+            std::fs::write(dir.join(file), b"").unwrap();
+        }
+        std::fs::write(dir.join(".mp3tagignore"), "Voice Memos/\n*.part.mp3\n").unwrap();
+        std::fs::write(dir.join("IU/.mp3tagignore"), "!2.part.mp3\n").unwrap();
+
+        assert_eq!(
+            find_mp3_paths(&dir).unwrap(),
+            [dir.join("IU/1.mp3"), dir.join("IU/2.part.mp3")]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// 캐시 디렉토리에 두는 건너뛴 파일 목록 이름.
+pub const SKIP_FILE: &str = "skipped.json";
+
+/// 대화형 `fetch`에서 사용자가 건너뛴 파일 목록. 오디오 내용 해시(`audio::content_hash`) → 건너뛸 때의 경로를
+/// 저장하므로, 파일명을 바꾸거나 옮기거나 태그를 고쳐도 같은 파일로 알아본다.
+pub struct SkipList {
+    path: PathBuf,
+    files: BTreeMap<String, PathBuf>,
+    changed: bool,
+}
+
+impl SkipList {
+    /// `cache_dir`의 목록을 읽는다. 없거나 읽을 수 없으면 빈 목록으로 시작한다.
+    pub fn load(cache_dir: &Path) -> Self {
+        let path = cache_dir.join(SKIP_FILE);
+        let files = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            files,
+            changed: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// 이전에 건너뛴 오디오인지.
+    pub fn contains(&self, content_hash: &str) -> bool {
+        self.files.contains_key(content_hash)
+    }
+
+    /// (오디오 해시, 건너뛸 때의 경로) 목록.
+    #[cfg(feature = "library")]
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.files
+            .iter()
+            .map(|(hash, path)| (hash.as_str(), path.as_path()))
+    }
+
+    /// 건너뛴 파일로 기억한다.
+    pub fn add(&mut self, content_hash: String, path: &Path) {
+        self.files.insert(content_hash, path.to_path_buf());
+        self.changed = true;
+    }
+
+    /// 태그를 적용한 파일은 목록에서 뺀다.
+    pub fn remove(&mut self, content_hash: &str) {
+        self.changed |= self.files.remove(content_hash).is_some();
+    }
+
+    /// 바뀐 내용이 있으면 목록 파일에 기록한다.
+    pub fn save(&self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("디렉토리를 만들 수 없습니다: {}", dir.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.files)?).with_context(|| {
+            format!(
+                "건너뛴 파일 목록을 기록할 수 없습니다: {}",
+                self.path.display()
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_skip_list_roundtrip() {
+        let dir = TempDir::new("skip-list");
+        let mut list = SkipList::load(&dir);
+        assert!(list.is_empty());
+        list.add("abc".to_string(), Path::new("Live/bootleg.mp3"));
+        list.add("def".to_string(), Path::new("Live/encore.mp3"));
+        list.remove("def");
+        list.save().unwrap();
+
+        let reloaded = SkipList::load(&dir);
+        assert!(reloaded.contains("abc"));
+        assert!(!reloaded.contains("def"));
+        assert!(!reloaded.changed);
+    }
+}
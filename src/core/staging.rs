@@ -0,0 +1,330 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::{TrustConfig, WriteConfig};
+use crate::core::{fetch_state, tagger};
+use crate::models::TrackInfo;
+
+/// 저장 대기 목록 디렉토리 이름. 대상 디렉토리에 생성된다.
+pub const STAGED_DIR: &str = ".mp3tag-staged";
+const CHANGES_FILE: &str = "changes.json";
+
+/// 디스크에 저장하는 변경 하나. 앨범 아트는 같은 디렉토리에 `<sha256>` 파일로 따로 둔다.
+#[derive(Serialize, Deserialize)]
+struct StoredChange {
+    path: PathBuf,
+    info: TrackInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    art: Option<String>,
+}
+
+/// 파일에 기록하지 않고 쌓아 둔 태그 변경 (파일별 TrackInfo).
+/// 명시적으로 저장(`commit`, `commit_all`)하거나 취소(`discard`)할 때까지 파일은 그대로다.
+/// CLI(`fetch --stage`, `staged`)와 GUI가 같은 목록을 쓰며, 경로는 대상 디렉토리 기준 상대 경로로 저장한다.
+pub struct StagedChanges {
+    dir: PathBuf,
+    root: PathBuf,
+    pending: BTreeMap<PathBuf, TrackInfo>,
+}
+
+impl StagedChanges {
+    /// 대상 경로의 저장 대기 목록을 읽는다. 없으면 빈 목록.
+    pub fn load(target: &Path) -> Result<Self> {
+        let root = fetch_state::state_dir(target);
+        let mut staged = Self {
+            dir: root.join(STAGED_DIR),
+            root,
+            pending: BTreeMap::new(),
+        };
+        let changes_path = staged.dir.join(CHANGES_FILE);
+        if !changes_path.exists() {
+            return Ok(staged);
+        }
+        let content = std::fs::read(&changes_path).context("저장 대기 목록을 읽을 수 없습니다")?;
+        let changes: Vec<StoredChange> =
+            serde_json::from_slice(&content).context("저장 대기 목록 형식이 올바르지 않습니다")?;
+        for change in changes {
+            let mut info = change.info;
+            if let Some(hash) = change.art {
+                info.album_art =
+                    Some(std::fs::read(staged.dir.join(&hash)).with_context(|| {
+                        format!("저장 대기 중인 앨범 아트가 없습니다: {}", hash)
+                    })?);
+            }
+            staged.pending.insert(staged.root.join(change.path), info);
+        }
+        Ok(staged)
+    }
+
+    /// 목록을 저장한다. 비어 있으면 디렉토리를 지운다.
+    pub fn save(&self) -> Result<()> {
+        if self.pending.is_empty() {
+            if self.dir.exists() {
+                std::fs::remove_dir_all(&self.dir)
+                    .context("저장 대기 목록을 삭제할 수 없습니다")?;
+            }
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.dir).context("저장 대기 디렉토리를 만들 수 없습니다")?;
+
+        let mut changes = Vec::new();
+        let mut arts = Vec::new();
+        for (path, info) in &self.pending {
+            let art = info.album_art.as_deref().map(|data| {
+                let hash: String = Sha256::digest(data)
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect();
+                let art_path = self.dir.join(&hash);
+                if !art_path.exists() {
+                    arts.push((art_path, data));
+                }
+                hash
+            });
+            changes.push(StoredChange {
+                path: path.strip_prefix(&self.root).unwrap_or(path).to_path_buf(),
+                info: info.clone(),
+                art,
+            });
+        }
+        for (art_path, data) in arts {
+            std::fs::write(art_path, data).context("앨범 아트를 저장할 수 없습니다")?;
+        }
+        let content = serde_json::to_vec_pretty(&changes)?;
+        std::fs::write(self.dir.join(CHANGES_FILE), content)
+            .context("저장 대기 목록을 저장할 수 없습니다")?;
+
+        // 더 이상 쓰지 않는 앨범 아트를 지운다
+        let used: Vec<&str> = changes.iter().filter_map(|c| c.art.as_deref()).collect();
+        for entry in std::fs::read_dir(&self.dir)?.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name != CHANGES_FILE && !used.contains(&name.as_str()) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+        Ok(())
+    }
+
+    /// 변경을 쌓는다. 이미 쌓인 변경이 있으면 새 값이 있는 필드만 덮어쓰되,
+    /// `trust`에서 쌓인 값의 출처가 더 믿을 만한 필드는 그대로 둔다.
+    pub fn stage(&mut self, path: &Path, info: TrackInfo, trust: &TrustConfig) {
+        let merged = tagger::merge_trusted(&self.pending.remove(path), &info, trust);
+        self.pending.insert(path.to_path_buf(), merged);
+    }
+
+    /// 파일에 쌓인 변경.
+    pub fn get(&self, path: &Path) -> Option<&TrackInfo> {
+        self.pending.get(path)
+    }
+
+    /// 쌓인 변경을 경로순으로 반환한다.
+    pub fn iter(&self) -> impl Iterator<Item = (&PathBuf, &TrackInfo)> {
+        self.pending.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// 파일의 변경을 기록하지 않고 버린다. 버린 것이 있으면 true.
+    pub fn discard(&mut self, path: &Path) -> bool {
+        self.pending.remove(path).is_some()
+    }
+
+    /// 모든 변경을 버린다.
+    pub fn discard_all(&mut self) {
+        self.pending.clear();
+    }
+
+    /// 파일 하나의 변경을 기록하고 목록에서 뺀다. 실패하면 목록에 남긴다.
+    /// 쌓인 변경이 없으면 None.
+    pub fn commit(&mut self, path: &Path, config: &WriteConfig) -> Option<Result<TrackInfo>> {
+        let info = self.pending.get(path)?.clone();
+        if let Err(e) = tagger::write_tags(path, &info, config) {
+            return Some(Err(e));
+        }
+        self.pending.remove(path);
+        Some(Ok(info))
+    }
+
+    /// 모든 변경을 일괄 기록한다(`tagger::write_batch`). 파일별 결과를 경로순으로 반환하며,
+    /// 실패한 변경은 목록에 남긴다.
+    pub fn commit_all(&mut self, config: &WriteConfig) -> Vec<(PathBuf, Result<TrackInfo>)> {
+        let report = tagger::write_batch(
+            self.pending.iter().map(|(p, info)| (p.as_path(), info)),
+            config,
+        );
+        let mut results: Vec<(PathBuf, Result<TrackInfo>)> = report
+            .failed
+            .into_iter()
+            .map(|(path, e)| (path, Err(e)))
+            .collect();
+        for path in report.written {
+            if let Some(info) = self.pending.remove(&path) {
+                results.push((path, Ok(info)));
+            }
+        }
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        results
+    }
+}
+
+/// 쌓인 변경으로 바뀌는 필드를 (필드 이름, 현재 값, 새 값) 목록으로 반환한다.
+/// 앨범 아트는 바이너리 대신 크기를 보여 준다.
+pub fn changed_fields(
+    current: Option<&TrackInfo>,
+    staged: &TrackInfo,
+) -> Vec<(&'static str, String, String)> {
+    fn text(value: &Option<String>) -> Option<String> {
+        value.clone()
+    }
+    fn number<T: ToString>(value: &Option<T>) -> Option<String> {
+        value.as_ref().map(T::to_string)
+    }
+    fn art(value: &Option<Vec<u8>>) -> Option<String> {
+        value
+            .as_ref()
+            .map(|data| format!("{}KB", data.len().div_ceil(1024)))
+    }
+    type Field = fn(&TrackInfo) -> Option<String>;
+    let fields: [(&str, Field); 16] = [
+        ("제목", |t| text(&t.title)),
+        ("아티스트", |t| t.artist()),
+        ("앨범", |t| text(&t.album)),
+        ("앨범 아티스트", |t| text(&t.album_artist)),
+        ("트랙 번호", |t| number(&t.track_number)),
+        ("연도", |t| number(&t.year)),
+        ("발매일", |t| text(&t.release_date)),
+        ("최초 발매", |t| {
+            text(&t.original_date).or_else(|| number(&t.original_year))
+        }),
+        ("장르", |t| text(&t.genre)),
+        ("그룹", |t| text(&t.grouping)),
+        ("작품", |t| text(&t.work)),
+        ("악장", |t| text(&t.movement)),
+        ("악장 번호", |t| number(&t.movement_number)),
+        ("음반사", |t| text(&t.label)),
+        ("작곡가", |t| text(&t.composer)),
+        ("앨범 아트", |t| art(&t.album_art)),
+    ];
+
+    let mut changes = Vec::new();
+    for (label, value) in fields {
+        let Some(new) = value(staged) else {
+            continue;
+        };
+        let old = current.and_then(value);
+        let same = match label {
+            "앨범 아트" => {
+                current.and_then(|c| c.album_art.as_ref()) == staged.album_art.as_ref()
+            }
+            _ => old.as_deref() == Some(new.as_str()),
+        };
+        if !same {
+            changes.push((label, old.unwrap_or_default(), new));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_changed_fields() {
+        let current = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["IU".to_string()],
+            ..Default::default()
+        };
+        let staged = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["아이유".to_string()],
+            year: Some(2019),
+            album_art: Some(vec![0; 2048]),
+            ..Default::default()
+        };
+        let changes = changed_fields(Some(&current), &staged);
+        assert_eq!(
+            changes,
+            [
+                ("아티스트", "IU".to_string(), "아이유".to_string()),
+                ("연도", String::new(), "2019".to_string()),
+                ("앨범 아트", String::new(), "2KB".to_string()),
+            ]
+        );
+    }
+
+    fn fake_mp3(path: &Path) {
+        std::fs::write(path, [0xFF, 0xFB, 0x90, 0x00].repeat(100)).unwrap();
+    }
+
+    #[test]
+    fn test_stage_save_load_commit() {
+        let dir = TempDir::new("staged");
+        let a = dir.join("a.mp3");
+        let b = dir.join("b.mp3");
+        fake_mp3(&a);
+        fake_mp3(&b);
+
+        let mut staged = StagedChanges::load(&dir).unwrap();
+        staged.stage(
+            &a,
+            TrackInfo {
+                title: Some("Blueming".to_string()),
+                album_art: Some(vec![1, 2, 3]),
+                ..Default::default()
+            },
+            &TrustConfig::default(),
+        );
+        // 다시 쌓으면 새 값만 덮어쓴다
+        staged.stage(
+            &a,
+            TrackInfo {
+                artists: vec!["IU".to_string()],
+                ..Default::default()
+            },
+            &TrustConfig::default(),
+        );
+        staged.stage(
+            &b,
+            TrackInfo {
+                title: Some("Palette".to_string()),
+                ..Default::default()
+            },
+            &TrustConfig::default(),
+        );
+        staged.save().unwrap();
+        // 파일은 아직 그대로
+        assert!(tagger::read_tags(&a).unwrap().is_none());
+
+        let mut loaded = StagedChanges::load(&dir).unwrap();
+        assert_eq!(loaded.len(), 2);
+        let info = loaded.get(&a).unwrap();
+        assert_eq!(info.title.as_deref(), Some("Blueming"));
+        assert_eq!(info.artist().as_deref(), Some("IU"));
+        assert_eq!(info.album_art.as_deref(), Some(&[1u8, 2, 3][..]));
+
+        assert!(loaded.discard(&b));
+        let results = loaded.commit_all(&WriteConfig::default());
+        assert_eq!(results.len(), 1);
+        assert!(results[0].1.is_ok());
+        assert!(loaded.is_empty());
+        let written = tagger::read_tags(&a).unwrap().unwrap();
+        assert_eq!(written.artist().as_deref(), Some("IU"));
+        assert!(tagger::read_tags(&b).unwrap().is_none());
+
+        loaded.save().unwrap();
+        assert!(!dir.join(STAGED_DIR).exists());
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::core::{artwork, staging};
+use crate::models::{Mp3File, TrackInfo};
+
+/// 원본과 사본을 짝지은 방법.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchedBy {
+    /// 태그를 뺀 오디오가 같음
+    Hash,
+    /// 오디오는 다르지만(다시 인코딩한 사본 등) 루트 기준 경로가 같음
+    Path,
+    /// 경로는 다르지만 파일 이름(확장자 제외)이 트리 안에서 하나뿐이고 같음
+    Name,
+}
+
+impl MatchedBy {
+    pub fn label(self) -> &'static str {
+        match self {
+            MatchedBy::Hash => "오디오",
+            MatchedBy::Path => "경로",
+            MatchedBy::Name => "파일명",
+        }
+    }
+}
+
+/// 짝지은 원본(`master`)과 사본(`copy`)의 번호.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair {
+    pub master: usize,
+    pub copy: usize,
+    pub matched_by: MatchedBy,
+}
+
+/// 원본 트리와 사본 트리의 파일을 짝짓는다. 오디오 해시가 같은 파일을 먼저 잇고, 남은 파일은 각 루트에서의
+/// 상대 경로로 잇는다 (대소문자 무시). 원본 하나는 사본 하나와만 짝짓는다. 사본 순서로 반환한다.
+pub fn pair_files(
+    masters: &[(PathBuf, Option<String>)],
+    master_root: &Path,
+    copies: &[(PathBuf, Option<String>)],
+    copy_root: &Path,
+) -> Vec<Pair> {
+    let mut by_hash: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut by_path: HashMap<String, usize> = HashMap::new();
+    for (i, (path, hash)) in masters.iter().enumerate() {
+        if let Some(hash) = hash {
+            by_hash.entry(hash).or_default().push(i);
+        }
+        by_path.insert(relative_key(path, master_root), i);
+    }
+    let mut used = vec![false; masters.len()];
+    let mut pairs: Vec<Option<Pair>> = vec![None; copies.len()];
+    for (c, (_, hash)) in copies.iter().enumerate() {
+        let found = hash
+            .as_deref()
+            .and_then(|h| by_hash.get(h))
+            .and_then(|ms| ms.iter().copied().find(|&m| !used[m]));
+        if let Some(m) = found {
+            used[m] = true;
+            pairs[c] = Some(Pair {
+                master: m,
+                copy: c,
+                matched_by: MatchedBy::Hash,
+            });
+        }
+    }
+    for (c, (path, _)) in copies.iter().enumerate() {
+        if pairs[c].is_some() {
+            continue;
+        }
+        if let Some(&m) = by_path.get(&relative_key(path, copy_root)) {
+            if !used[m] {
+                used[m] = true;
+                pairs[c] = Some(Pair {
+                    master: m,
+                    copy: c,
+                    matched_by: MatchedBy::Path,
+                });
+            }
+        }
+    }
+    pairs.into_iter().flatten().collect()
+}
+
+/// 변환 원본(FLAC 등)과 변환한 파일을 짝짓는다. 확장자를 뺀 상대 경로가 같은 파일을 먼저 잇고, 남은 파일은
+/// 양쪽 트리에서 하나뿐인 파일 이름(확장자 제외)으로 잇는다 (대소문자 무시). 변환한 파일 순서로 반환한다.
+pub fn pair_converted(
+    sources: &[PathBuf],
+    source_root: &Path,
+    targets: &[PathBuf],
+    target_root: &Path,
+) -> Vec<Pair> {
+    let stem_key = |path: &Path, root: &Path| relative_key(&path.with_extension(""), root);
+    let name_key = |path: &Path| {
+        path.file_stem()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+    let by_path: HashMap<String, usize> = sources
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (stem_key(p, source_root), i))
+        .collect();
+    // 이름이 둘 이상인 파일은 어느 쪽인지 알 수 없으므로 이름으로 잇지 않는다
+    let unique_names = |paths: &[PathBuf]| {
+        let mut names: HashMap<String, Option<usize>> = HashMap::new();
+        for (i, path) in paths.iter().enumerate() {
+            names
+                .entry(name_key(path))
+                .and_modify(|found| *found = None)
+                .or_insert(Some(i));
+        }
+        names
+    };
+    let source_names = unique_names(sources);
+    let target_names = unique_names(targets);
+
+    let mut used = vec![false; sources.len()];
+    let mut pairs: Vec<Option<Pair>> = vec![None; targets.len()];
+    for (t, path) in targets.iter().enumerate() {
+        if let Some(&s) = by_path.get(&stem_key(path, target_root)) {
+            used[s] = true;
+            pairs[t] = Some(Pair {
+                master: s,
+                copy: t,
+                matched_by: MatchedBy::Path,
+            });
+        }
+    }
+    for (t, path) in targets.iter().enumerate() {
+        let name = name_key(path);
+        if pairs[t].is_some() || target_names.get(&name) != Some(&Some(t)) {
+            continue;
+        }
+        if let Some(&Some(s)) = source_names.get(&name) {
+            if !used[s] {
+                used[s] = true;
+                pairs[t] = Some(Pair {
+                    master: s,
+                    copy: t,
+                    matched_by: MatchedBy::Name,
+                });
+            }
+        }
+    }
+    pairs.into_iter().flatten().collect()
+}
+
+fn relative_key(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+        .to_lowercase()
+}
+
+/// 사본에 기록할 원본 태그. 원본에 값이 있고 사본과 다른 필드가 없으면 None.
+/// 원본에 없는 필드는 사본 값을 그대로 둔다. 사본의 앨범 아트가 원본보다 해상도가 높거나 `copy_art`가 꺼져 있으면
+/// 아트는 옮기지 않는다.
+pub fn tags_to_copy(master: &Mp3File, copy: &Mp3File, copy_art: bool) -> Option<TrackInfo> {
+    let mut info = master.current_tags.clone()?;
+    let current = copy.current_tags.as_ref();
+    let copy_art_data = current.and_then(|t| t.album_art.as_deref());
+    let keep_copy_art = match (&info.album_art, copy_art_data) {
+        (Some(master_art), Some(copy_art)) => {
+            artwork::short_side(copy_art).unwrap_or(0)
+                > artwork::short_side(master_art).unwrap_or(0)
+        }
+        _ => false,
+    };
+    if !copy_art || keep_copy_art {
+        info.album_art = None;
+    }
+    info.source = "sync".to_string();
+    (!staging::changed_fields(current, &info).is_empty()).then_some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, tags: TrackInfo) -> Mp3File {
+        Mp3File {
+            path: PathBuf::from(path),
+            current_tags: Some(tags),
+            has_tags: true,
+            encoder: None,
+        }
+    }
+
+    #[test]
+    fn test_pair_files() {
+        let masters = vec![
+            (PathBuf::from("/master/A/01.mp3"), Some("h1".to_string())),
+            (PathBuf::from("/master/A/02.mp3"), Some("h2".to_string())),
+            (PathBuf::from("/master/B/01.mp3"), Some("h3".to_string())),
+        ];
+        let copies = vec![
+            // 옮기고 이름을 바꿨지만 오디오가 같음
+            (PathBuf::from("/portable/x.mp3"), Some("h2".to_string())),
+            // 128kbps로 다시 인코딩
+            (
+                PathBuf::from("/portable/a/01.mp3"),
+                Some("other".to_string()),
+            ),
+            (PathBuf::from("/portable/C/01.mp3"), None),
+        ];
+        let pairs = pair_files(
+            &masters,
+            Path::new("/master"),
+            &copies,
+            Path::new("/portable"),
+        );
+        let found: Vec<(usize, usize, MatchedBy)> = pairs
+            .iter()
+            .map(|p| (p.master, p.copy, p.matched_by))
+            .collect();
+        assert_eq!(found, [(1, 0, MatchedBy::Hash), (0, 1, MatchedBy::Path)]);
+    }
+
+    #[test]
+    fn test_pair_converted() {
+        let flacs = vec![
+            PathBuf::from("/lossless/IU/Love poem/04 Blueming.flac"),
+            PathBuf::from("/lossless/IU/Palette/01 이런 엔딩.flac"),
+            PathBuf::from("/lossless/A/01 Intro.flac"),
+            PathBuf::from("/lossless/B/01 Intro.flac"),
+        ];
+        let mp3s = vec![
+            PathBuf::from("/mp3/IU/Love poem/04 Blueming.mp3"),
+            // 폴더 구조를 바꿔 변환
+            PathBuf::from("/mp3/Palette/01 이런 엔딩.mp3"),
+            // 원본에 같은 이름이 둘
+            PathBuf::from("/mp3/C/01 Intro.mp3"),
+        ];
+        let pairs = pair_converted(&flacs, Path::new("/lossless"), &mp3s, Path::new("/mp3"));
+        let found: Vec<(usize, usize, MatchedBy)> = pairs
+            .iter()
+            .map(|p| (p.master, p.copy, p.matched_by))
+            .collect();
+        assert_eq!(found, [(0, 0, MatchedBy::Path), (1, 1, MatchedBy::Name)]);
+    }
+
+    #[test]
+    fn test_tags_to_copy() {
+        let master = file(
+            "/master/a.mp3",
+            TrackInfo {
+                title: Some("Blueming".to_string()),
+                year: Some(2019),
+                ..Default::default()
+            },
+        );
+        let copy = file(
+            "/portable/a.mp3",
+            TrackInfo {
+                title: Some("Blueming".to_string()),
+                genre: Some("K-Pop".to_string()),
+                ..Default::default()
+            },
+        );
+        let info = tags_to_copy(&master, &copy, true).unwrap();
+        assert_eq!(info.year, Some(2019));
+        assert_eq!(info.source, "sync");
+
+        let synced = file("/portable/a.mp3", master.current_tags.clone().unwrap());
+        assert!(tags_to_copy(&master, &synced, true).is_none());
+    }
+}
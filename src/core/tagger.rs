@@ -1,9 +1,27 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use anyhow::Result;
-use id3::{Tag, TagLike, Version};
+use anyhow::{anyhow, bail, Context, Result};
+use id3::{Tag, TagLike, Timestamp, Version};
 
-use crate::models::TrackInfo;
+use crate::config::{FsyncPolicy, MergePolicy, MtimePolicy, TrustConfig, WriteConfig};
+use crate::core::artwork::{self, ArtFormat};
+use crate::core::matcher::Tier;
+use crate::core::{backup, cancel, filelock, history, hooks, romanize};
+use crate::models::{self, TrackInfo};
+
+/// 프로듀서 크레딧을 담는 TXXX 프레임의 description.
+const PRODUCER_DESCRIPTION: &str = "PRODUCER";
+/// MusicBrainz 릴리스 ID를 담는 TXXX 프레임의 description (Picard와 같은 이름).
+const MB_ALBUM_ID_DESCRIPTION: &str = "MusicBrainz Album Id";
+/// 클래식 작품명을 담는 TXXX 프레임의 description (Picard와 같은 이름).
+const WORK_DESCRIPTION: &str = "WORK";
+/// 자동 작업의 출처 기록을 담는 TXXX 프레임의 description (`[write] provenance`).
+const PROVENANCE_DESCRIPTION: &str = "MP3TAG_PROVENANCE";
+/// 자동 처리 신뢰 등급을 담는 TXXX 프레임의 description (`auto --write-tier`).
+const MATCH_TIER_DESCRIPTION: &str = "MP3TAG_MATCH_TIER";
+/// 출처 기록에 남기는 최근 항목 수.
+const PROVENANCE_KEEP: usize = 5;
 
 /// MP3 파일에서 ID3 태그를 읽어 TrackInfo로 변환한다.
 /// 태그가 없거나 제목/아티스트/앨범이 모두 비어있으면 None을 반환한다.
@@ -16,46 +34,533 @@ pub fn read_tags(path: &Path) -> Result<Option<TrackInfo>> {
         }) => return Ok(None),
         Err(e) => return Err(e.into()),
     };
+    Ok(tag_info(&tag))
+}
 
-    let has_any = tag.title().is_some()
-        || tag.artist().is_some()
-        || tag.album().is_some();
+/// 읽어 둔 ID3 태그를 TrackInfo로 변환한다. 제목/아티스트/앨범이 모두 비어있으면 None을 반환한다.
+fn tag_info(tag: &Tag) -> Option<TrackInfo> {
+    let has_any = tag.title().is_some() || tag.artist().is_some() || tag.album().is_some();
 
     if !has_any {
-        return Ok(None);
+        return None;
     }
 
-    let album_art = tag
-        .pictures()
-        .next()
-        .map(|pic| pic.data.clone());
+    let album_art = tag.pictures().next().map(|pic| pic.data.clone());
+    // v2.4의 TDRC를 먼저 보고, 이전 버전으로 기록된 파일은 TYER로 폴백한다
+    let recorded = tag.date_recorded();
+    let original = tag.original_date_released().or_else(|| {
+        tag.get("TORY")
+            .and_then(|f| f.content().text())
+            .and_then(|y| y.trim().parse().ok())
+    });
+    // iTunes 12.5.4 이후 방식(GRP1 = 그룹, TIT1 = 작품명)으로 기록된 파일도 읽는다
+    let text = |id: &str| {
+        tag.get(id)
+            .and_then(|f| f.content().text())
+            .map(str::to_string)
+    };
+    let (grouping, work) = match text("GRP1") {
+        Some(grouping) => (
+            Some(grouping),
+            extended_text(tag, WORK_DESCRIPTION).or_else(|| text("TIT1")),
+        ),
+        None => (text("TIT1"), extended_text(tag, WORK_DESCRIPTION)),
+    };
+    let (movement_number, movement_total) = match raw_text(tag, "MVIN") {
+        Some(value) => {
+            let mut parts = value.splitn(2, '/');
+            let mut number = || parts.next().and_then(|n| n.trim().parse().ok());
+            (number(), number())
+        }
+        None => (None, None),
+    };
 
     let info = TrackInfo {
         title: tag.title().map(|s| s.to_string()),
-        artist: tag.artist().map(|s| s.to_string()),
+        // ID3v2.4의 NUL 구분 값만 나눈다. 구분자로 이어 붙인 TPE1은 "Tyler, The Creator"처럼
+        // 이름에 쉼표가 들어갈 수 있어 그대로 한 아티스트로 둔다.
+        artists: tag.artists().map(models::artist_values).unwrap_or_default(),
         album: tag.album().map(|s| s.to_string()),
         album_artist: tag.album_artist().map(|s| s.to_string()),
         track_number: tag.track(),
-        year: tag.year(),
+        total_tracks: tag.total_tracks(),
+        disc_number: tag.disc(),
+        total_discs: tag.total_discs(),
+        year: recorded.map(|ts| ts.year).or_else(|| tag.year()),
+        release_date: recorded.and_then(|ts| format_release_date(&ts)),
+        original_year: original.map(|ts| ts.year),
+        original_date: original.and_then(|ts| format_release_date(&ts)),
         genre: tag.genre_parsed().map(|s| s.to_string()),
+        grouping,
+        work,
+        movement: raw_text(tag, "MVNM"),
+        movement_number,
+        movement_total,
+        label: tag
+            .get("TPUB")
+            .and_then(|f| f.content().text())
+            .map(|s| s.to_string()),
+        composer: tag
+            .get("TCOM")
+            .and_then(|f| f.content().text())
+            .map(|s| s.to_string()),
+        producer: extended_text(tag, PRODUCER_DESCRIPTION),
+        lyrics: tag.lyrics().next().map(|l| l.text.clone()),
+        musicbrainz_album_id: extended_text(tag, MB_ALBUM_ID_DESCRIPTION),
+        duration_ms: None,
+        explicit: None,
         album_art,
         album_art_url: None,
+        source_id: None,
         source: "id3".to_string(),
+        field_sources: Default::default(),
+        match_score: None,
+    };
+
+    Some(info)
+}
+
+/// description이 일치하는 TXXX 프레임의 값을 반환한다 (대소문자 무시).
+fn extended_text(tag: &Tag, description: &str) -> Option<String> {
+    tag.extended_texts()
+        .find(|t| t.description.eq_ignore_ascii_case(description))
+        .map(|t| t.value.clone())
+}
+
+/// id3 크레이트가 해석하지 않는 iTunes 텍스트 프레임(MVNM, MVIN)의 값을 읽는다.
+/// 첫 바이트는 텍스트 인코딩이며 ISO-8859-1과 UTF-8만 지원한다.
+fn raw_text(tag: &Tag, id: &str) -> Option<String> {
+    let id3::Content::Unknown(unknown) = tag.get(id)?.content() else {
+        return None;
+    };
+    let (&encoding, text) = unknown.data.split_first()?;
+    let text = match encoding {
+        0 => text.iter().map(|&b| b as char).collect(),
+        3 => String::from_utf8_lossy(text).to_string(),
+        _ => return None,
     };
+    Some(text.trim_end_matches('\0').to_string()).filter(|t| !t.is_empty())
+}
 
-    Ok(Some(info))
+/// iTunes 텍스트 프레임을 UTF-8로 기록한다. 해석하지 않는 프레임은 `add_frame`이
+/// 기존 프레임을 대체하지 않으므로 먼저 지운다.
+fn set_raw_text(tag: &mut Tag, id: &str, text: &str) {
+    tag.remove(id);
+    let mut data = vec![3u8];
+    data.extend_from_slice(text.as_bytes());
+    tag.add_frame(id3::Frame::with_content(
+        id,
+        id3::Content::Unknown(id3::frame::Unknown {
+            data,
+            version: Version::Id3v24,
+        }),
+    ));
 }
 
 /// TrackInfo를 MP3 파일에 ID3v2.4 태그로 기록한다.
 /// 기존 태그가 있으면 지정된 필드만 덮어쓴다.
-pub fn write_tags(path: &Path, info: &TrackInfo) -> Result<()> {
+/// `config.atomic`이면 임시 파일에 기록한 뒤 원본 위로 교체하고,
+/// 기록 후 mtime은 `config.mtime` 정책에 따라 조정한다.
+/// 읽기 전용 파일은 `config.force`일 때만 임시로 쓰기 권한을 주고 끝나면 되돌린다.
+/// 설정된 pre-write 훅이 실패하면 기록하지 않고, 기록 후에는 post-write 훅을 실행한다.
+/// `config.backup`이 켜져 있으면 기록 직전에 원본을 백업 디렉토리에 복사한다.
+/// `config.history`가 켜져 있으면 바뀐 필드를 디렉토리의 변경 기록에 남긴다.
+/// 스캔한 뒤 다른 프로그램이 파일을 바꿨거나, 다른 프로그램이 사용 중이라 잠시 기다려도 풀리지 않으면
+/// 기록하지 않고 에러를 반환한다.
+pub fn write_tags(path: &Path, info: &TrackInfo, config: &WriteConfig) -> Result<()> {
+    write_tags_with_sync(path, info, config, config.fsync != FsyncPolicy::Never)
+}
+
+/// `write_tags`와 같되, 기록 직후 파일을 동기화할지를 `sync`로 정한다.
+fn write_tags_with_sync(
+    path: &Path,
+    info: &TrackInfo,
+    config: &WriteConfig,
+    sync: bool,
+) -> Result<()> {
+    check_writable(path, config)?;
+    let _lock = filelock::acquire(path)?;
+    filelock::check_unchanged(path)?;
+
+    let guarded;
+    let info = if config.protected.is_empty() {
+        info
+    } else {
+        let mut kept = info.clone();
+        keep_protected(&read_tags(path).ok().flatten(), &mut kept, config);
+        guarded = kept;
+        &guarded
+    };
+
+    if let Some(hook) = non_empty(&config.pre_write_hook) {
+        hooks::run(hook, path, info)
+            .map_err(|e| anyhow!("pre-write 훅이 기록을 막았습니다: {}", e))?;
+    }
+    backup::backup(path, config)?;
+    let before = if config.history {
+        read_tags(path).ok().flatten()
+    } else {
+        None
+    };
+    write_frames(path, info, config, sync)?;
+    filelock::remember(path);
+    if config.history {
+        history::record(path, before.as_ref(), info)
+            .context("태그는 기록했지만 변경 기록을 남기지 못했습니다")?;
+    }
+    if let Some(hook) = non_empty(&config.post_write_hook) {
+        hooks::run(hook, path, info)
+            .map_err(|e| anyhow!("태그는 기록했지만 post-write 훅이 실패했습니다: {}", e))?;
+    }
+    Ok(())
+}
+
+/// `mp3tag frame`으로 적용할 프레임 단위 변경. ID는 `TIT3`처럼 네 글자이며,
+/// TXXX/WXXX/COMM은 `TXXX:MOOD`처럼 콜론 뒤에 description을 붙일 수 있다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameEdit {
+    /// 텍스트(T***), 링크(W***), 사용자 정의(TXXX, WXXX), 주석(COMM) 프레임에 값을 기록한다
+    Set(String, String),
+    /// 해당 ID(와 description)의 프레임을 모두 지운다
+    Delete(String),
+}
+
+/// TrackInfo가 다루지 않는 프레임을 ID로 직접 수정한다. 모든 변경을 검증한 뒤 한 번에 기록하며,
+/// 백업/훅/변경 기록은 `write_tags`와 같이 동작한다. 지울 프레임이 없거나 값을 기록할 수 없는
+/// 프레임 종류면 아무것도 기록하지 않고 에러를 반환한다.
+pub fn edit_frames(path: &Path, edits: &[FrameEdit], config: &WriteConfig) -> Result<()> {
+    check_writable(path, config)?;
+    let _lock = filelock::acquire(path)?;
+    filelock::check_unchanged(path)?;
+    let mut tag = match Tag::read_from_path(path) {
+        Ok(tag) => tag,
+        Err(id3::Error {
+            kind: id3::ErrorKind::NoTag,
+            ..
+        }) => Tag::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let before = tag_info(&tag);
+    for edit in edits {
+        apply_frame_edit(&mut tag, edit)?;
+    }
+    let mut after = tag_info(&tag).unwrap_or_default();
+    after.source = "manual".to_string();
+
+    if let Some(hook) = non_empty(&config.pre_write_hook) {
+        hooks::run(hook, path, &after)
+            .map_err(|e| anyhow!("pre-write 훅이 기록을 막았습니다: {}", e))?;
+    }
+    backup::backup(path, config)?;
+    save_tag(
+        path,
+        &tag,
+        config,
+        config.fsync != FsyncPolicy::Never,
+        after.year,
+    )?;
+    filelock::remember(path);
+    if config.history {
+        history::record(path, before.as_ref(), &after)
+            .context("태그는 기록했지만 변경 기록을 남기지 못했습니다")?;
+    }
+    if let Some(hook) = non_empty(&config.post_write_hook) {
+        hooks::run(hook, path, &after)
+            .map_err(|e| anyhow!("태그는 기록했지만 post-write 훅이 실패했습니다: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 파일 전체를 챕터 하나로 표시하는 CHAP 프레임(제목 `title`, 0 ~ `duration_ms`)과
+/// 이를 가리키는 최상위 CTOC 프레임을 기록한다. 기존 챕터는 지운다.
+/// 여러 파일로 나뉜 오디오북에서 파일마다 부르며, `write_tags` 다음에 부르는 것을 전제로
+/// 백업/훅/변경 기록은 하지 않는다.
+pub fn write_chapter(
+    path: &Path,
+    title: &str,
+    duration_ms: u64,
+    config: &WriteConfig,
+) -> Result<()> {
+    check_writable(path, config)?;
+    let _lock = filelock::acquire(path)?;
+    filelock::check_unchanged(path)?;
+    let mut tag = match Tag::read_from_path(path) {
+        Ok(tag) => tag,
+        Err(id3::Error {
+            kind: id3::ErrorKind::NoTag,
+            ..
+        }) => Tag::new(),
+        Err(e) => return Err(e.into()),
+    };
+    tag.remove("CHAP");
+    tag.remove("CTOC");
+    let element_id = "ch1".to_string();
+    tag.add_frame(id3::frame::Chapter {
+        element_id: element_id.clone(),
+        start_time: 0,
+        end_time: duration_ms.min(u32::MAX as u64) as u32,
+        // 바이트 위치는 쓰지 않는다 (ID3v2 Chapter 명세의 0xFFFFFFFF)
+        start_offset: u32::MAX,
+        end_offset: u32::MAX,
+        frames: vec![id3::Frame::text("TIT2", title)],
+    });
+    tag.add_frame(id3::frame::TableOfContents {
+        element_id: "toc".to_string(),
+        top_level: true,
+        ordered: true,
+        elements: vec![element_id],
+        frames: Vec::new(),
+    });
+    let year = tag.year();
+    save_tag(path, &tag, config, config.fsync != FsyncPolicy::Never, year)?;
+    filelock::remember(path);
+    Ok(())
+}
+
+fn apply_frame_edit(tag: &mut Tag, edit: &FrameEdit) -> Result<()> {
+    match edit {
+        FrameEdit::Set(spec, value) => {
+            let (id, description) = parse_frame_spec(spec)?;
+            let description = description.unwrap_or_default().to_string();
+            let value = value.clone();
+            match id {
+                "TXXX" => {
+                    remove_frames(tag, id, Some(&description));
+                    tag.add_frame(id3::frame::ExtendedText { description, value });
+                }
+                "WXXX" => {
+                    remove_frames(tag, id, Some(&description));
+                    tag.add_frame(id3::frame::ExtendedLink {
+                        description,
+                        link: value,
+                    });
+                }
+                "COMM" => {
+                    remove_frames(tag, id, Some(&description));
+                    tag.add_frame(id3::frame::Comment {
+                        lang: "XXX".to_string(),
+                        description,
+                        text: value,
+                    });
+                }
+                _ if !description.is_empty() => {
+                    bail!("description은 TXXX, WXXX, COMM 프레임에만 붙일 수 있습니다: {}", spec)
+                }
+                _ if id.starts_with('T') => {
+                    tag.set_text(id, value);
+                }
+                _ if id.starts_with('W') => {
+                    tag.remove(id);
+                    tag.add_frame(id3::Frame::link(id, value));
+                }
+                _ => bail!(
+                    "{} 프레임은 값을 직접 기록할 수 없습니다 (텍스트 T***, 링크 W***, TXXX, WXXX, COMM만 가능)",
+                    id
+                ),
+            }
+        }
+        FrameEdit::Delete(spec) => {
+            let (id, description) = parse_frame_spec(spec)?;
+            if remove_frames(tag, id, description) == 0 {
+                bail!("지울 프레임이 없습니다: {}", spec);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// "ID" 또는 "ID:description"을 나눈다. ID는 영문 대문자와 숫자 네 글자여야 한다.
+fn parse_frame_spec(spec: &str) -> Result<(&str, Option<&str>)> {
+    let (id, description) = match spec.split_once(':') {
+        Some((id, description)) => (id, Some(description)),
+        None => (spec, None),
+    };
+    let valid = id.len() == 4
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit());
+    if !valid {
+        bail!(
+            "프레임 ID 형식이 올바르지 않습니다: {} (예: TIT3, TXXX:MOOD)",
+            spec
+        );
+    }
+    Ok((id, description))
+}
+
+/// ID가 같고, `description`이 주어지면 description도 같은(대소문자 무시) 프레임을 지우고 지운 개수를 반환한다.
+fn remove_frames(tag: &mut Tag, id: &str, description: Option<&str>) -> usize {
+    let mut removed = 0;
+    for frame in tag.remove(id) {
+        let frame_description = match frame.content() {
+            id3::Content::ExtendedText(t) => Some(t.description.as_str()),
+            id3::Content::ExtendedLink(l) => Some(l.description.as_str()),
+            id3::Content::Comment(c) => Some(c.description.as_str()),
+            _ => None,
+        };
+        let matches = match description {
+            Some(description) => {
+                frame_description.is_some_and(|d| d.eq_ignore_ascii_case(description))
+            }
+            None => true,
+        };
+        if matches {
+            removed += 1;
+        } else {
+            tag.add_frame(frame);
+        }
+    }
+    removed
+}
+
+/// 일괄 기록 결과. 기록한 파일과 실패한 파일(이유 포함)을 입력 순서대로 모두 남긴다.
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    pub written: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, anyhow::Error)>,
+    /// Ctrl-C로 중단해 기록하지 않은 파일 수
+    pub cancelled: usize,
+}
+
+impl BatchReport {
+    /// "기록 N건, 실패 M건" 형식의 요약. 중단됐으면 남은 파일 수를 덧붙인다.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "기록 {}건, 실패 {}건",
+            self.written.len(),
+            self.failed.len()
+        );
+        if self.cancelled > 0 {
+            summary += &format!(", 중단으로 남은 파일 {}건", self.cancelled);
+        }
+        summary
+    }
+}
+
+/// 여러 파일에 태그를 기록한다. 한 파일이 실패해도 나머지는 계속 기록하고 결과를 모아 반환한다.
+/// `config.fsync`가 batch면 파일마다 동기화하지 않고, 모두 기록한 뒤 파일과 디렉토리를 한 번에 동기화한다.
+/// 단 원자적 기록은 rename 전에 임시 파일을 동기화해야 하므로 파일마다 동기화하고 마지막 동기화는 건너뛴다.
+/// 동기화에 실패한 파일은 실패로 옮긴다. Ctrl-C로 중단되면 기록 중인 파일까지만 쓰고 멈춘다.
+pub fn write_batch<'a>(
+    items: impl IntoIterator<Item = (&'a Path, &'a TrackInfo)>,
+    config: &WriteConfig,
+) -> BatchReport {
+    write_batch_until(items, config, cancel::requested)
+}
+
+/// `write_batch`와 같되 파일마다 `cancelled`를 확인해 true면 나머지를 기록하지 않는다.
+fn write_batch_until<'a>(
+    items: impl IntoIterator<Item = (&'a Path, &'a TrackInfo)>,
+    config: &WriteConfig,
+    cancelled: impl Fn() -> bool,
+) -> BatchReport {
+    let mut report = BatchReport::default();
+    let sync_each = match config.fsync {
+        FsyncPolicy::EachFile => true,
+        FsyncPolicy::Batch => config.atomic,
+        FsyncPolicy::Never => false,
+    };
+    for (path, info) in items {
+        if cancelled() {
+            report.cancelled += 1;
+            continue;
+        }
+        match write_tags_with_sync(path, info, config, sync_each) {
+            Ok(()) => report.written.push(path.to_path_buf()),
+            Err(e) => report.failed.push((path.to_path_buf(), e)),
+        }
+    }
+    if config.fsync == FsyncPolicy::Batch && !sync_each {
+        sync_written(&mut report);
+    }
+    report
+}
+
+/// 기록한 파일들과 그 디렉토리(rename 반영)를 동기화한다.
+fn sync_written(report: &mut BatchReport) {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    let mut synced = Vec::new();
+    for path in std::mem::take(&mut report.written) {
+        match sync_file(&path) {
+            Ok(()) => {
+                if let Some(dir) = path.parent().filter(|d| !dirs.iter().any(|x| x == d)) {
+                    dirs.push(dir.to_path_buf());
+                }
+                synced.push(path);
+            }
+            Err(e) => {
+                let e = anyhow!(e).context("디스크에 동기화하지 못했습니다");
+                report.failed.push((path, e));
+            }
+        }
+    }
+    // 디렉토리 동기화는 지원하지 않는 플랫폼(Windows)이 있으므로 실패해도 무시한다
+    for dir in dirs {
+        let _ = sync_dir(&dir);
+    }
+    report.written = synced;
+}
+
+fn non_empty(hook: &Option<String>) -> Option<&str> {
+    hook.as_deref().filter(|h| !h.trim().is_empty())
+}
+
+/// TDRC/TDOR에 기록할 타임스탬프. 날짜가 있으면 날짜까지, 없으면 연도만 담는다.
+/// 날짜의 연도가 `year`와 다르면 연도를 우선한다.
+fn date_timestamp(year: Option<i32>, date: Option<&str>) -> Option<Timestamp> {
+    let date = date
+        .and_then(|d| d.parse::<Timestamp>().ok())
+        .filter(|ts| year.is_none_or(|year| year == ts.year));
+    date.or_else(|| {
+        year.map(|year| Timestamp {
+            year,
+            month: None,
+            day: None,
+            hour: None,
+            minute: None,
+            second: None,
+        })
+    })
+}
+
+/// TDRC 타임스탬프를 "YYYY-MM-DD" 또는 "YYYY-MM"으로 바꾼다. 연도만 있으면 None.
+fn format_release_date(ts: &Timestamp) -> Option<String> {
+    let month = ts.month?;
+    Some(match ts.day {
+        Some(day) => format!("{:04}-{:02}-{:02}", ts.year, month, day),
+        None => format!("{:04}-{:02}", ts.year, month),
+    })
+}
+
+/// 한글이 들어간 필드의 로마자 표기를 정렬용 프레임에 기록한다. 한글이 없는 필드는 건드리지 않는다.
+fn set_sort_frames(tag: &mut Tag, info: &TrackInfo) {
+    let artist = info.artist();
+    let fields = [
+        ("TSOT", &info.title),
+        ("TSOP", &artist),
+        ("TSOA", &info.album),
+        ("TSO2", &info.album_artist),
+    ];
+    for (id, value) in fields {
+        if let Some(value) = value.as_deref().filter(|v| romanize::has_hangul(v)) {
+            tag.set_text(id, romanize::romanize(value));
+        }
+    }
+}
+
+/// 태그 프레임을 갱신해 파일에 기록하고 mtime 정책을 적용한다.
+fn write_frames(path: &Path, info: &TrackInfo, config: &WriteConfig, sync: bool) -> Result<()> {
     let mut tag = Tag::read_from_path(path).unwrap_or_else(|_| Tag::new());
 
     if let Some(ref title) = info.title {
         tag.set_title(title);
     }
-    if let Some(ref artist) = info.artist {
-        tag.set_artist(artist);
+    if !info.artists.is_empty() {
+        if config.artist_multi_value {
+            tag.set_text_values("TPE1", info.artists.iter().cloned());
+        } else {
+            tag.set_artist(info.artists.join(&config.artist_separator));
+        }
     }
     if let Some(ref album) = info.album {
         tag.set_album(album);
@@ -66,59 +571,1307 @@ pub fn write_tags(path: &Path, info: &TrackInfo) -> Result<()> {
     if let Some(track) = info.track_number {
         tag.set_track(track);
     }
-    if let Some(year) = info.year {
-        tag.set_year(year);
+    if let Some(total) = info.total_tracks {
+        tag.set_total_tracks(total);
+    }
+    if let Some(disc) = info.disc_number {
+        tag.set_disc(disc);
+    }
+    if let Some(total) = info.total_discs {
+        tag.set_total_discs(total);
+    }
+    // v2.4에서 TYER/TORY는 폐지된 프레임이므로 TDRC/TDOR만 남긴다
+    if let Some(timestamp) = date_timestamp(info.year, info.release_date.as_deref()) {
+        tag.remove_year();
+        tag.set_date_recorded(timestamp);
+    }
+    if let Some(timestamp) = date_timestamp(info.original_year, info.original_date.as_deref()) {
+        tag.remove("TORY");
+        tag.set_original_date_released(timestamp);
     }
     if let Some(ref genre) = info.genre {
         tag.set_genre(genre);
     }
+    if let Some(ref grouping) = info.grouping {
+        tag.set_text(
+            if config.itunes_grouping {
+                "GRP1"
+            } else {
+                "TIT1"
+            },
+            grouping,
+        );
+    }
+    if let Some(ref work) = info.work {
+        tag.add_frame(id3::frame::ExtendedText {
+            description: WORK_DESCRIPTION.to_string(),
+            value: work.clone(),
+        });
+        if config.itunes_grouping {
+            tag.set_text("TIT1", work);
+        }
+    }
+    if let Some(ref movement) = info.movement {
+        set_raw_text(&mut tag, "MVNM", movement);
+    }
+    if let Some(number) = info.movement_number {
+        let value = match info.movement_total {
+            Some(total) => format!("{}/{}", number, total),
+            None => number.to_string(),
+        };
+        set_raw_text(&mut tag, "MVIN", &value);
+    }
+    if let Some(ref label) = info.label {
+        tag.set_text("TPUB", label);
+    }
+    if let Some(ref composer) = info.composer {
+        tag.set_text("TCOM", composer);
+    }
+    if let Some(ref producer) = info.producer {
+        tag.add_frame(id3::frame::ExtendedText {
+            description: PRODUCER_DESCRIPTION.to_string(),
+            value: producer.clone(),
+        });
+    }
+    if let Some(ref id) = info.musicbrainz_album_id {
+        tag.add_frame(id3::frame::ExtendedText {
+            description: MB_ALBUM_ID_DESCRIPTION.to_string(),
+            value: id.clone(),
+        });
+    }
+    if let Some(ref lyrics) = info.lyrics {
+        tag.remove_all_lyrics();
+        tag.add_frame(id3::frame::Lyrics {
+            lang: "XXX".to_string(),
+            description: String::new(),
+            text: lyrics.clone(),
+        });
+    }
     if let Some(ref art_data) = info.album_art {
+        let art_data = artwork::to_embeddable(art_data)?;
         tag.remove_all_pictures();
         tag.add_frame(id3::frame::Picture {
-            mime_type: detect_mime_type(art_data),
+            mime_type: detect_mime_type(&art_data),
             picture_type: id3::frame::PictureType::CoverFront,
             description: String::new(),
-            data: art_data.clone(),
+            data: art_data.into_owned(),
         });
     }
+    if config.romanize_sort {
+        set_sort_frames(&mut tag, info);
+    }
+    if config.provenance && is_automated(&info.source) {
+        let entry = provenance_entry(info, SystemTime::now());
+        let existing = extended_text(&tag, PROVENANCE_DESCRIPTION);
+        tag.add_frame(id3::frame::ExtendedText {
+            description: PROVENANCE_DESCRIPTION.to_string(),
+            value: append_provenance(existing.as_deref(), &entry),
+        });
+    }
+    if config.match_tier && is_automated(&info.source) {
+        tag.add_frame(id3::frame::ExtendedText {
+            description: MATCH_TIER_DESCRIPTION.to_string(),
+            value: Tier::of(info.match_score).name().to_string(),
+        });
+    }
+    save_tag(path, &tag, config, sync, info.year)
+}
+
+/// 직접 편집하거나 파일에서 읽은 값이 아닌, 자동 작업이 만든 태그인지.
+fn is_automated(source: &str) -> bool {
+    !matches!(source, "" | "manual" | "id3")
+}
+
+/// 출처 기록 한 항목: "2026-10-15 spotify+genius 0.93". 필드별로 다른 출처가 있으면 `+`로 잇고,
+/// 점수는 소스 결과를 골랐을 때만 넣는다.
+fn provenance_entry(info: &TrackInfo, now: SystemTime) -> String {
+    let (year, month, day, ..) = utc_date_time(now);
+    let mut sources = vec![info.source.as_str()];
+    for source in info.field_sources.values() {
+        if !sources.contains(&source.as_str()) {
+            sources.push(source);
+        }
+    }
+    let mut entry = format!("{:04}-{:02}-{:02} {}", year, month, day, sources.join("+"));
+    if let Some(score) = info.match_score {
+        entry += &format!(" {:.2}", score);
+    }
+    entry
+}
+
+/// 기존 기록 뒤에 `entry`를 덧붙이고 최근 `PROVENANCE_KEEP`개만 남긴다. 항목은 "; "로 구분한다.
+fn append_provenance(existing: Option<&str>, entry: &str) -> String {
+    let mut entries: Vec<&str> = existing
+        .into_iter()
+        .flat_map(|e| e.split("; "))
+        .filter(|e| !e.trim().is_empty())
+        .collect();
+    entries.push(entry);
+    let skip = entries.len().saturating_sub(PROVENANCE_KEEP);
+    entries[skip..].join("; ")
+}
+
+/// 파일의 출처 기록 (`[write] provenance`가 남긴 TXXX:MP3TAG_PROVENANCE). 없으면 None.
+pub fn provenance(path: &Path) -> Option<String> {
+    extended_text(&Tag::read_from_path(path).ok()?, PROVENANCE_DESCRIPTION)
+}
+
+/// `auto --write-tier`가 남긴 신뢰 등급 이름 (TXXX:MP3TAG_MATCH_TIER). 없으면 None.
+pub fn match_tier(path: &Path) -> Option<String> {
+    extended_text(&Tag::read_from_path(path).ok()?, MATCH_TIER_DESCRIPTION)
+}
+
+/// 완성한 태그를 ID3v2.4로 파일에 기록한다. `config`의 원자적 기록, mtime 정책(`year`는
+/// release-year 정책에 사용), 읽기 전용 파일 강제 기록 설정을 따른다.
+fn save_tag(
+    path: &Path,
+    tag: &Tag,
+    config: &WriteConfig,
+    sync: bool,
+    year: Option<i32>,
+) -> Result<()> {
+    let metadata = std::fs::metadata(path)?;
+    let original_mtime = metadata.modified().ok();
+    let original_perms = metadata.permissions();
+    let new_mtime = match config.mtime {
+        MtimePolicy::Update => None,
+        MtimePolicy::Preserve => original_mtime,
+        MtimePolicy::ReleaseYear => year.map(year_start).or(original_mtime),
+    };
+
+    let forced = original_perms.readonly();
+    if forced {
+        std::fs::set_permissions(path, writable_permissions(&original_perms))
+            .context("쓰기 권한을 임시로 부여할 수 없습니다")?;
+    }
+
+    let result = (|| -> Result<()> {
+        if config.atomic {
+            write_atomic(path, tag, sync)?;
+        } else {
+            tag.write_to_path(path, Version::Id3v24)?;
+            if sync {
+                sync_file(path)?;
+            }
+        }
+        if let Some(mtime) = new_mtime {
+            std::fs::File::options()
+                .write(true)
+                .open(path)?
+                .set_modified(mtime)?;
+        }
+        Ok(())
+    })();
+
+    if forced {
+        let restored = std::fs::set_permissions(path, original_perms)
+            .context("원래 권한으로 되돌리지 못했습니다");
+        result?;
+        restored?;
+        return Ok(());
+    }
+    result
+}
+
+/// 태그를 기록할 수 있는지 미리 확인한다. 일괄 작업 전에 문제 파일을 걸러내는 데 사용한다.
+/// 읽기 전용 파일은 `config.force`가 아니면, 권한이 없는 파일은 항상 에러를 반환한다.
+pub fn check_writable(path: &Path, config: &WriteConfig) -> Result<()> {
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("파일 정보를 읽을 수 없습니다: {}", path.display()))?;
+
+    if metadata.permissions().readonly() {
+        if !config.force {
+            bail!("읽기 전용 파일입니다 (--force로 강제 기록 가능)");
+        }
+    } else if let Err(e) = std::fs::File::options().write(true).open(path) {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            bail!("파일에 쓰기 권한이 없습니다");
+        }
+        return Err(e.into());
+    }
+
+    // 원자적 기록은 같은 디렉토리에 임시 파일을 만들어야 한다
+    if config.atomic {
+        let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+        let dir_readonly = std::fs::metadata(dir.unwrap_or_else(|| Path::new(".")))
+            .map(|m| m.permissions().readonly())
+            .unwrap_or(false);
+        if dir_readonly {
+            bail!("디렉토리에 쓰기 권한이 없습니다 (write.atomic = false로 제자리 기록 가능)");
+        }
+    }
 
-    tag.write_to_path(path, Version::Id3v24)?;
     Ok(())
 }
 
-/// 기존 태그와 새 태그를 병합한다. 새 값이 있으면 우선 적용된다.
-pub fn merge_tags(existing: &Option<TrackInfo>, new_info: &TrackInfo) -> TrackInfo {
-    match existing {
-        Some(existing) => TrackInfo {
-            title: new_info.title.clone().or_else(|| existing.title.clone()),
-            artist: new_info.artist.clone().or_else(|| existing.artist.clone()),
-            album: new_info.album.clone().or_else(|| existing.album.clone()),
-            album_artist: new_info
-                .album_artist
-                .clone()
-                .or_else(|| existing.album_artist.clone()),
-            track_number: new_info.track_number.or(existing.track_number),
-            year: new_info.year.or(existing.year),
-            genre: new_info.genre.clone().or_else(|| existing.genre.clone()),
-            album_art: new_info
-                .album_art
-                .clone()
-                .or_else(|| existing.album_art.clone()),
-            album_art_url: new_info
-                .album_art_url
-                .clone()
-                .or_else(|| existing.album_art_url.clone()),
-            source: new_info.source.clone(),
-        },
-        None => new_info.clone(),
+/// 소유자 쓰기 권한만 추가한 권한을 반환한다.
+fn writable_permissions(perms: &std::fs::Permissions) -> std::fs::Permissions {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::Permissions::from_mode(perms.mode() | 0o200)
+    }
+    #[cfg(not(unix))]
+    {
+        let mut perms = perms.clone();
+        #[allow(clippy::permissions_set_readonly_false)]
+        perms.set_readonly(false);
+        perms
     }
 }
 
-/// 이미지 바이너리의 매직 바이트로 MIME 타입을 판별한다.
-fn detect_mime_type(data: &[u8]) -> String {
-    if data.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
-        "image/png".to_string()
+/// 시각을 UTC (연, 월, 일, 시, 분, 초)로 나눈다.
+pub fn utc_date_time(time: SystemTime) -> (i64, u64, u64, u64, u64, u64) {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    // 0000-03-01 기준 일 수를 400년 주기로 나눠 그레고리력 날짜를 구한다
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u64;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u64;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    let rem = secs % 86_400;
+    (year, month, day, rem / 3600, rem % 3600 / 60, rem % 60)
+}
+
+/// 해당 연도 1월 1일 00:00 UTC의 시각을 반환한다.
+fn year_start(year: i32) -> SystemTime {
+    // 1970년부터 해당 연도 직전까지의 일 수 (그레고리력 윤년 규칙)
+    let leap_days_before = |y: i64| (y - 1) / 4 - (y - 1) / 100 + (y - 1) / 400;
+    let year = year as i64;
+    let days = 365 * (year - 1970) + leap_days_before(year) - leap_days_before(1970);
+    let secs = days * 86_400;
+
+    if secs >= 0 {
+        UNIX_EPOCH + Duration::from_secs(secs as u64)
     } else {
-        "image/jpeg".to_string()
+        UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs())
+    }
+}
+
+/// 같은 디렉토리의 임시 파일에 태그를 기록하고 원본 위로 rename한다.
+/// 권한은 복사 시 그대로 유지되며, 실패하면 임시 파일을 지우고 원본은 건드리지 않는다.
+/// `sync`면 rename 전에 임시 파일을, rename 뒤에 디렉토리를 디스크에 동기화한다.
+fn write_atomic(path: &Path, tag: &Tag, sync: bool) -> Result<()> {
+    let tmp = temp_path(path);
+
+    let result = (|| -> Result<()> {
+        std::fs::copy(path, &tmp)?;
+        tag.write_to_path(&tmp, Version::Id3v24)?;
+        if sync {
+            sync_file(&tmp)?;
+        }
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result?;
+    if sync {
+        sync_dir(path.parent().unwrap_or(Path::new("")))
+            .context("디렉토리를 디스크에 동기화하지 못했습니다")?;
+    }
+    Ok(())
+}
+
+/// 파일 내용을 디스크에 동기화한다. 읽기 전용 핸들의 fsync를 무시하는 플랫폼(Windows)이 있어
+/// 쓰기 핸들로 연다.
+fn sync_file(path: &Path) -> std::io::Result<()> {
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)?
+        .sync_all()
+}
+
+/// rename이 디스크에 남도록 디렉토리를 동기화한다. 디렉토리를 열 수 없는 Windows에서는 하지 않는다.
+fn sync_dir(dir: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        std::fs::File::open(dir)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+/// 원본과 같은 디렉토리에 숨김 임시 파일 경로를 만든다 (스캔 대상에서 제외되는 확장자).
+pub fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.mp3tag-tmp", name))
+}
+
+/// 기존 태그와 새 태그를 병합한다. 두 태그에 모두 값이 있는 필드는 `policy`를 따른다.
+/// 물을 수 없는 여기서는 `Interactive`도 `Fill`처럼 기존 값을 지킨다.
+pub fn merge_tags(
+    existing: &Option<TrackInfo>,
+    new_info: &TrackInfo,
+    policy: MergePolicy,
+) -> TrackInfo {
+    if policy == MergePolicy::Overwrite {
+        return merge_trusted(existing, new_info, &TrustConfig::default());
+    }
+    let mut new_info = new_info.clone();
+    drop_existing_fields(existing, &mut new_info, |_, _, _| true);
+    merge_trusted(existing, &new_info, &TrustConfig::default())
+}
+
+/// 병합 단위 필드: `[trust]`의 필드 이름, 비교/표시용 값, 다른 트랙의 값을 옮겨 오는 함수.
+type MergeField = (
+    &'static str,
+    fn(&TrackInfo) -> Option<String>,
+    fn(&mut TrackInfo, &TrackInfo),
+);
+
+fn text(value: &Option<String>) -> Option<String> {
+    value.clone()
+}
+
+fn number<T: ToString>(value: &Option<T>) -> Option<String> {
+    value.as_ref().map(T::to_string)
+}
+
+/// 병합 단위 필드. 함께 움직이는 값(연도와 발매일 등)은 한 항목으로 묶는다.
+const MERGE_FIELDS: &[MergeField] = &[
+    (
+        "title",
+        |t| text(&t.title),
+        |m, e| m.title = e.title.clone(),
+    ),
+    (
+        "artist",
+        |t| t.artist(),
+        |m, e| m.artists = e.artists.clone(),
+    ),
+    (
+        "album",
+        |t| text(&t.album),
+        |m, e| m.album = e.album.clone(),
+    ),
+    (
+        "album_artist",
+        |t| text(&t.album_artist),
+        |m, e| m.album_artist = e.album_artist.clone(),
+    ),
+    (
+        "track_number",
+        |t| number(&t.track_number),
+        |m, e| {
+            m.track_number = e.track_number;
+            m.total_tracks = e.total_tracks;
+        },
+    ),
+    (
+        "disc_number",
+        |t| number(&t.disc_number),
+        |m, e| {
+            m.disc_number = e.disc_number;
+            m.total_discs = e.total_discs;
+        },
+    ),
+    (
+        "year",
+        |t| text(&t.release_date).or_else(|| number(&t.year)),
+        |m, e| {
+            m.year = e.year;
+            m.release_date = e.release_date.clone();
+        },
+    ),
+    (
+        "original_year",
+        |t| text(&t.original_date).or_else(|| number(&t.original_year)),
+        |m, e| {
+            m.original_year = e.original_year;
+            m.original_date = e.original_date.clone();
+        },
+    ),
+    (
+        "genre",
+        |t| text(&t.genre),
+        |m, e| m.genre = e.genre.clone(),
+    ),
+    (
+        "grouping",
+        |t| text(&t.grouping),
+        |m, e| m.grouping = e.grouping.clone(),
+    ),
+    ("work", |t| text(&t.work), |m, e| m.work = e.work.clone()),
+    (
+        "movement",
+        |t| text(&t.movement),
+        |m, e| m.movement = e.movement.clone(),
+    ),
+    (
+        "label",
+        |t| text(&t.label),
+        |m, e| m.label = e.label.clone(),
+    ),
+    (
+        "composer",
+        |t| text(&t.composer),
+        |m, e| m.composer = e.composer.clone(),
+    ),
+    (
+        "producer",
+        |t| text(&t.producer),
+        |m, e| m.producer = e.producer.clone(),
+    ),
+    (
+        "lyrics",
+        |t| text(&t.lyrics),
+        |m, e| m.lyrics = e.lyrics.clone(),
+    ),
+    (
+        "album_art",
+        |t| {
+            t.album_art
+                .as_ref()
+                .map(|data| format!("이미지 {}KB", data.len().div_ceil(1024)))
+                .or_else(|| t.album_art_url.clone())
+        },
+        |m, e| {
+            m.album_art = e.album_art.clone();
+            m.album_art_url = e.album_art_url.clone();
+        },
+    ),
+];
+
+/// `[write] protected` 필드 중 파일에 이미 다른 값이 있는 필드를 `info`에서 현재 값으로 되돌린다.
+/// 직접 편집한 값(`source`가 "manual")은 막지 않는다. 되돌린 필드 이름을 반환한다.
+pub fn keep_protected(
+    current: &Option<TrackInfo>,
+    info: &mut TrackInfo,
+    config: &WriteConfig,
+) -> Vec<&'static str> {
+    let mut kept = Vec::new();
+    let Some(current) = current else {
+        return kept;
+    };
+    if info.source == "manual" {
+        return kept;
+    }
+    for &(field, value, take) in MERGE_FIELDS {
+        if !config.protected.iter().any(|p| p == field) {
+            continue;
+        }
+        let old = value(current);
+        if old.is_some() && value(info).is_some_and(|new| Some(new) != old) {
+            take(info, current);
+            info.field_sources.remove(field);
+            kept.push(field);
+        }
+    }
+    kept
+}
+
+/// 기존 태그에 이미 값이 있는 필드를 `new_info`에서 비운다. 비운 필드는 기록할 때 파일의 기존 프레임이
+/// 그대로 남는다. 두 값이 다르면 `keep_existing(필드, 기존 값, 새 값)`이 true일 때만 비운다.
+pub fn drop_existing_fields(
+    existing: &Option<TrackInfo>,
+    new_info: &mut TrackInfo,
+    mut keep_existing: impl FnMut(&str, &str, &str) -> bool,
+) {
+    let Some(existing) = existing else {
+        return;
+    };
+    let empty = TrackInfo::default();
+    for &(field, value, take) in MERGE_FIELDS {
+        let (Some(old), Some(new)) = (value(existing), value(new_info)) else {
+            continue;
+        };
+        if old == new || keep_existing(field, &old, &new) {
+            take(new_info, &empty);
+            new_info.field_sources.remove(field);
+        }
+    }
+}
+
+/// `merge_tags`와 같되, `trust`에서 기존 값의 출처가 새 값의 출처보다 앞서는 필드는 기존 값을 둔다.
+/// 결과에 필드별 출처를 남겨 이어지는 병합에서도 같은 순서를 적용한다.
+pub fn merge_trusted(
+    existing: &Option<TrackInfo>,
+    new_info: &TrackInfo,
+    trust: &TrustConfig,
+) -> TrackInfo {
+    let Some(existing) = existing else {
+        return new_info.clone();
+    };
+    let mut merged = TrackInfo {
+        title: new_info.title.clone().or_else(|| existing.title.clone()),
+        artists: if new_info.artists.is_empty() {
+            existing.artists.clone()
+        } else {
+            new_info.artists.clone()
+        },
+        album: new_info.album.clone().or_else(|| existing.album.clone()),
+        album_artist: new_info
+            .album_artist
+            .clone()
+            .or_else(|| existing.album_artist.clone()),
+        track_number: new_info.track_number.or(existing.track_number),
+        total_tracks: new_info.total_tracks.or(existing.total_tracks),
+        disc_number: new_info.disc_number.or(existing.disc_number),
+        total_discs: new_info.total_discs.or(existing.total_discs),
+        year: new_info.year.or(existing.year),
+        // 새 연도와 맞지 않는 기존 발매일은 버린다
+        release_date: merge_date(
+            new_info.year,
+            &new_info.release_date,
+            &existing.release_date,
+        ),
+        original_year: new_info.original_year.or(existing.original_year),
+        original_date: merge_date(
+            new_info.original_year,
+            &new_info.original_date,
+            &existing.original_date,
+        ),
+        genre: new_info.genre.clone().or_else(|| existing.genre.clone()),
+        grouping: new_info
+            .grouping
+            .clone()
+            .or_else(|| existing.grouping.clone()),
+        work: new_info.work.clone().or_else(|| existing.work.clone()),
+        movement: new_info
+            .movement
+            .clone()
+            .or_else(|| existing.movement.clone()),
+        movement_number: new_info.movement_number.or(existing.movement_number),
+        movement_total: new_info.movement_total.or(existing.movement_total),
+        label: new_info.label.clone().or_else(|| existing.label.clone()),
+        composer: new_info
+            .composer
+            .clone()
+            .or_else(|| existing.composer.clone()),
+        producer: new_info
+            .producer
+            .clone()
+            .or_else(|| existing.producer.clone()),
+        lyrics: new_info.lyrics.clone().or_else(|| existing.lyrics.clone()),
+        musicbrainz_album_id: new_info
+            .musicbrainz_album_id
+            .clone()
+            .or_else(|| existing.musicbrainz_album_id.clone()),
+        duration_ms: new_info.duration_ms.or(existing.duration_ms),
+        explicit: new_info.explicit.or(existing.explicit),
+        album_art: new_info
+            .album_art
+            .clone()
+            .or_else(|| existing.album_art.clone()),
+        album_art_url: new_info
+            .album_art_url
+            .clone()
+            .or_else(|| existing.album_art_url.clone()),
+        source_id: new_info
+            .source_id
+            .clone()
+            .or_else(|| existing.source_id.clone()),
+        source: new_info.source.clone(),
+        field_sources: Default::default(),
+        match_score: new_info.match_score,
+    };
+
+    for &(field, value, restore) in MERGE_FIELDS {
+        let has = |t: &TrackInfo| value(t).is_some();
+        let from = if has(existing)
+            && (!has(new_info)
+                || trust.prefers(
+                    field,
+                    existing.field_source(field),
+                    new_info.field_source(field),
+                )) {
+            restore(&mut merged, existing);
+            existing
+        } else if has(new_info) {
+            new_info
+        } else {
+            continue;
+        };
+        let source = from.field_source(field);
+        if source != merged.source {
+            merged
+                .field_sources
+                .insert(field.to_string(), source.to_string());
+        }
+    }
+    merged
+}
+
+/// 새 날짜가 없으면 기존 날짜를 쓰되, 새 연도와 맞지 않으면 버린다.
+fn merge_date(
+    year: Option<i32>,
+    new: &Option<String>,
+    existing: &Option<String>,
+) -> Option<String> {
+    new.clone().or_else(|| {
+        existing
+            .clone()
+            .filter(|date| year.is_none_or(|year| date.starts_with(&format!("{:04}-", year))))
+    })
+}
+
+/// 이미지 바이너리의 시그니처로 MIME 타입을 판별한다. 알 수 없는 형식은 JPEG로 본다.
+pub fn detect_mime_type(data: &[u8]) -> String {
+    ArtFormat::detect(data)
+        .map_or("image/jpeg", ArtFormat::mime_type)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    /// 태그 없이 오디오 바이트만 있는 임시 파일을 만든다. 디렉토리는 반환값이 drop될 때 지워진다.
+    fn temp_audio_file(name: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new(&format!("tagger-{}", name));
+        let path = dir.join("song.mp3");
+        std::fs::write(&path, b"AUDIO-DATA").unwrap();
+        (dir, path)
+    }
+
+    fn sample_info() -> TrackInfo {
+        TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["IU".to_string()],
+            source: "manual".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write_atomic_keeps_audio_and_cleans_up() {
+        let (_dir, path) = temp_audio_file("atomic");
+        write_tags(&path, &sample_info(), &WriteConfig::default()).unwrap();
+
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!(tags.title.as_deref(), Some("Blueming"));
+        assert!(std::fs::read(&path).unwrap().ends_with(b"AUDIO-DATA"));
+        assert!(!temp_path(&path).exists());
+    }
+
+    #[test]
+    fn test_write_credits_and_lyrics() {
+        let (_dir, path) = temp_audio_file("credits");
+        let info = TrackInfo {
+            composer: Some("IU, 이종훈".to_string()),
+            producer: Some("이종훈".to_string()),
+            lyrics: Some("첫 줄\n둘째 줄".to_string()),
+            ..sample_info()
+        };
+        write_tags(&path, &info, &WriteConfig::default()).unwrap();
+        // 다시 기록해도 가사/프로듀서 프레임이 중복되지 않는다
+        write_tags(&path, &info, &WriteConfig::default()).unwrap();
+
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!(tags.composer.as_deref(), Some("IU, 이종훈"));
+        assert_eq!(tags.producer.as_deref(), Some("이종훈"));
+        assert_eq!(tags.lyrics.as_deref(), Some("첫 줄\n둘째 줄"));
+
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(tag.lyrics().count(), 1);
+        assert_eq!(tag.extended_texts().count(), 1);
+    }
+
+    #[test]
+    fn test_write_provenance() {
+        let (_dir, path) = temp_audio_file("provenance");
+        let config = WriteConfig {
+            provenance: true,
+            ..Default::default()
+        };
+        // 직접 편집은 남기지 않는다
+        write_tags(&path, &sample_info(), &config).unwrap();
+        assert_eq!(provenance(&path), None);
+
+        let matched = TrackInfo {
+            source: "spotify".to_string(),
+            field_sources: [("lyrics".to_string(), "genius".to_string())].into(),
+            match_score: Some(0.934),
+            ..sample_info()
+        };
+        write_tags(&path, &matched, &config).unwrap();
+        let inferred = TrackInfo {
+            source: "filename".to_string(),
+            ..sample_info()
+        };
+        write_tags(&path, &inferred, &config).unwrap();
+        let recorded = provenance(&path).unwrap();
+        let entries: Vec<&str> = recorded.split("; ").collect();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].ends_with(" spotify+genius 0.93"));
+        assert!(entries[1].ends_with(" filename"));
+        assert_eq!(match_tier(&path), None);
+
+        let tiered = WriteConfig {
+            match_tier: true,
+            ..Default::default()
+        };
+        write_tags(&path, &matched, &tiered).unwrap();
+        assert_eq!(match_tier(&path).as_deref(), Some("high"));
+
+        let many = (0..7).fold(None, |acc: Option<String>, n| {
+            Some(append_provenance(acc.as_deref(), &n.to_string()))
+        });
+        assert_eq!(many.as_deref(), Some("2; 3; 4; 5; 6"));
+    }
+
+    #[test]
+    fn test_write_romanized_sort_frames() {
+        let (_dir, path) = temp_audio_file("romanize-sort");
+        let info = TrackInfo {
+            title: Some("밤편지".to_string()),
+            artists: vec!["IU".to_string()],
+            album: Some("팔레트".to_string()),
+            ..sample_info()
+        };
+        let config = WriteConfig {
+            romanize_sort: true,
+            ..Default::default()
+        };
+        write_tags(&path, &info, &config).unwrap();
+
+        let tag = Tag::read_from_path(&path).unwrap();
+        let text = |id| tag.get(id).and_then(|f| f.content().text());
+        assert_eq!(text("TSOT"), Some("bampyeonji"));
+        assert_eq!(text("TSOA"), Some("palleteu"));
+        assert_eq!(text("TSOP"), None);
+    }
+
+    #[test]
+    fn test_write_release_date_as_tdrc() {
+        let (_dir, path) = temp_audio_file("tdrc");
+        // 예전 버전이 남긴 TYER만 있는 파일도 연도를 읽는다
+        let mut tag = Tag::new();
+        tag.set_title("Blueming");
+        tag.set_year(2018);
+        tag.write_to_path(&path, Version::Id3v23).unwrap();
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!((tags.year, tags.release_date), (Some(2018), None));
+
+        let info = TrackInfo {
+            year: Some(2019),
+            release_date: Some("2019-11-18".to_string()),
+            original_year: Some(2017),
+            ..sample_info()
+        };
+        write_tags(&path, &info, &WriteConfig::default()).unwrap();
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(
+            tag.get("TDRC").and_then(|f| f.content().text()),
+            Some("2019-11-18")
+        );
+        assert!(tag.get("TYER").is_none());
+        assert_eq!(
+            tag.get("TDOR").and_then(|f| f.content().text()),
+            Some("2017")
+        );
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!(tags.year, Some(2019));
+        assert_eq!(tags.original_year, Some(2017));
+        assert_eq!(tags.release_date.as_deref(), Some("2019-11-18"));
+
+        // 연도만 바꾸면 맞지 않는 발매일은 버리고 연도만 남긴다
+        let merged = merge_tags(
+            &Some(tags),
+            &TrackInfo {
+                year: Some(2020),
+                ..Default::default()
+            },
+            MergePolicy::Overwrite,
+        );
+        assert_eq!(merged.release_date, None);
+        write_tags(&path, &merged, &WriteConfig::default()).unwrap();
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!((tags.year, tags.release_date), (Some(2020), None));
+    }
+
+    #[test]
+    fn test_write_artist_list() {
+        let (_dir, path) = temp_audio_file("artists");
+        let info = TrackInfo {
+            artists: vec!["IU".to_string(), "SUGA".to_string()],
+            ..sample_info()
+        };
+        let config = WriteConfig {
+            artist_separator: "; ".to_string(),
+            ..Default::default()
+        };
+        write_tags(&path, &info, &config).unwrap();
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(tag.artist(), Some("IU; SUGA"));
+        assert_eq!(read_tags(&path).unwrap().unwrap().artists, ["IU; SUGA"]);
+
+        let config = WriteConfig {
+            artist_multi_value: true,
+            ..Default::default()
+        };
+        write_tags(&path, &info, &config).unwrap();
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(tag.artists(), Some(vec!["IU", "SUGA"]));
+        assert_eq!(read_tags(&path).unwrap().unwrap().artists, ["IU", "SUGA"]);
+
+        // 구분자처럼 보이는 문자가 들어간 이름은 읽고 다시 써도 그대로다
+        for name in [
+            "Tyler, The Creator",
+            "Earth, Wind & Fire",
+            "Crush feat. 태연",
+        ] {
+            let mut tag = Tag::read_from_path(&path).unwrap();
+            tag.set_artist(name);
+            tag.write_to_path(&path, Version::Id3v24).unwrap();
+            filelock::remember(&path);
+            let info = read_tags(&path).unwrap().unwrap();
+            assert_eq!(info.artists, [name]);
+            let info = TrackInfo {
+                artists: info.artists,
+                ..sample_info()
+            };
+            write_tags(&path, &info, &WriteConfig::default()).unwrap();
+            assert_eq!(Tag::read_from_path(&path).unwrap().artist(), Some(name));
+        }
+    }
+
+    #[test]
+    fn test_write_classical_fields() {
+        let (_dir, path) = temp_audio_file("classical");
+        let info = TrackInfo {
+            grouping: Some("Beethoven Symphonies".to_string()),
+            work: Some("Symphony No. 5 in C minor, Op. 67".to_string()),
+            movement: Some("Allegro con brio".to_string()),
+            movement_number: Some(1),
+            movement_total: Some(4),
+            ..sample_info()
+        };
+        write_tags(&path, &info, &WriteConfig::default()).unwrap();
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(
+            tag.get("TIT1").and_then(|f| f.content().text()),
+            Some("Beethoven Symphonies")
+        );
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!(tags.grouping, info.grouping);
+        assert_eq!(tags.work, info.work);
+        assert_eq!(tags.movement.as_deref(), Some("Allegro con brio"));
+        assert_eq!(
+            (tags.movement_number, tags.movement_total),
+            (Some(1), Some(4))
+        );
+        // 다시 기록해도 악장 프레임이 쌓이지 않는다
+        write_tags(&path, &info, &WriteConfig::default()).unwrap();
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(tag.frames().filter(|f| f.id() == "MVIN").count(), 1);
+
+        // iTunes 방식: 그룹은 GRP1, 작품명은 TIT1
+        let (_dir, path) = temp_audio_file("classical-itunes");
+        let config = WriteConfig {
+            itunes_grouping: true,
+            ..Default::default()
+        };
+        write_tags(&path, &info, &config).unwrap();
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(
+            tag.get("GRP1").and_then(|f| f.content().text()),
+            Some("Beethoven Symphonies")
+        );
+        assert_eq!(
+            tag.get("TIT1").and_then(|f| f.content().text()),
+            info.work.as_deref()
+        );
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!((tags.grouping, tags.work), (info.grouping, info.work));
+    }
+
+    #[test]
+    fn test_write_in_place() {
+        let (_dir, path) = temp_audio_file("in-place");
+        write_tags(
+            &path,
+            &sample_info(),
+            &WriteConfig {
+                atomic: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!(tags.artist().as_deref(), Some("IU"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_dir, path) = temp_audio_file("perms");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+        write_tags(&path, &sample_info(), &WriteConfig::default()).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    #[test]
+    fn test_year_start() {
+        let secs = |y| year_start(y).duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(secs(1970), 0);
+        assert_eq!(secs(2000), 946_684_800);
+        assert_eq!(secs(2019), 1_546_300_800);
+        assert_eq!(
+            UNIX_EPOCH
+                .duration_since(year_start(1969))
+                .unwrap()
+                .as_secs(),
+            365 * 86_400
+        );
+    }
+
+    #[test]
+    fn test_write_preserves_mtime() {
+        let (_dir, path) = temp_audio_file("mtime-preserve");
+        let old = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let config = WriteConfig {
+            mtime: MtimePolicy::Preserve,
+            ..Default::default()
+        };
+        write_tags(&path, &sample_info(), &config).unwrap();
+
+        let mtime = std::fs::metadata(&path).unwrap().modified().unwrap();
+        assert_eq!(mtime, old);
+    }
+
+    #[test]
+    fn test_write_sets_mtime_from_release_year() {
+        let (_dir, path) = temp_audio_file("mtime-year");
+        let info = TrackInfo {
+            year: Some(2019),
+            ..sample_info()
+        };
+        let config = WriteConfig {
+            mtime: MtimePolicy::ReleaseYear,
+            ..Default::default()
+        };
+        write_tags(&path, &info, &config).unwrap();
+
+        let mtime = std::fs::metadata(&path).unwrap().modified().unwrap();
+        assert_eq!(mtime, year_start(2019));
+    }
+
+    #[test]
+    fn test_write_readonly_requires_force() {
+        let (_dir, path) = temp_audio_file("readonly");
+        let mut perms = std::fs::metadata(&path).unwrap().permissions();
+        perms.set_readonly(true);
+        std::fs::set_permissions(&path, perms).unwrap();
+
+        let result = write_tags(&path, &sample_info(), &WriteConfig::default());
+        assert!(result.is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"AUDIO-DATA");
+
+        let config = WriteConfig {
+            force: true,
+            ..Default::default()
+        };
+        write_tags(&path, &sample_info(), &config).unwrap();
+        assert!(read_tags(&path).unwrap().is_some());
+        assert!(std::fs::metadata(&path).unwrap().permissions().readonly());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_hooks() {
+        let (_dir, path) = temp_audio_file("hooks");
+        let marker = path.with_file_name("post-hook");
+
+        let rejecting = WriteConfig {
+            pre_write_hook: Some("exit 1".to_string()),
+            ..Default::default()
+        };
+        assert!(write_tags(&path, &sample_info(), &rejecting).is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"AUDIO-DATA");
+
+        let config = WriteConfig {
+            pre_write_hook: Some(r#"test "$MP3TAG_TITLE" = Blueming"#.to_string()),
+            post_write_hook: Some(format!(r#"echo "$MP3TAG_ARTIST" > "{}""#, marker.display())),
+            ..Default::default()
+        };
+        write_tags(&path, &sample_info(), &config).unwrap();
+        assert!(read_tags(&path).unwrap().is_some());
+        assert_eq!(std::fs::read_to_string(&marker).unwrap().trim(), "IU");
+    }
+
+    #[test]
+    fn test_write_batch_collects_failures() {
+        let (_dir, path) = temp_audio_file("batch");
+        let missing = path.with_file_name("missing.mp3");
+        let info = sample_info();
+        let config = WriteConfig {
+            fsync: FsyncPolicy::Batch,
+            ..Default::default()
+        };
+
+        let report = write_batch(
+            [(missing.as_path(), &info), (path.as_path(), &info)],
+            &config,
+        );
+        assert_eq!(report.written, std::slice::from_ref(&path));
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, missing);
+        assert_eq!(report.summary(), "기록 1건, 실패 1건");
+        assert_eq!(
+            read_tags(&path).unwrap().unwrap().title.as_deref(),
+            Some("Blueming")
+        );
+        assert!(!temp_path(&path).exists());
+
+        // 제자리 기록은 모두 기록한 뒤 한 번에 동기화한다
+        let config = WriteConfig {
+            atomic: false,
+            ..config
+        };
+        let report = write_batch(
+            [(missing.as_path(), &info), (path.as_path(), &info)],
+            &config,
+        );
+        assert_eq!(report.written, std::slice::from_ref(&path));
+        assert_eq!(report.failed.len(), 1);
+    }
+
+    #[test]
+    fn test_write_batch_stops_when_cancelled() {
+        let (dir, first) = temp_audio_file("cancel");
+        let rest = [dir.join("b.mp3"), dir.join("c.mp3")];
+        for path in &rest {
+            std::fs::write(path, b"AUDIO-DATA").unwrap();
+        }
+        let info = sample_info();
+
+        // 첫 파일을 기록하는 도중 Ctrl-C를 누른 것처럼 그 뒤로는 중단을 요청한다
+        let checks = std::cell::Cell::new(0);
+        let report = write_batch_until(
+            [&first, &rest[0], &rest[1]].map(|p| (p.as_path(), &info)),
+            &WriteConfig::default(),
+            || {
+                checks.set(checks.get() + 1);
+                checks.get() > 1
+            },
+        );
+        assert_eq!(report.written, std::slice::from_ref(&first));
+        assert_eq!(report.cancelled, 2);
+        assert_eq!(
+            report.summary(),
+            "기록 1건, 실패 0건, 중단으로 남은 파일 2건"
+        );
+        assert!(read_tags(&first).unwrap().is_some());
+        for path in &rest {
+            assert!(read_tags(path).unwrap().is_none());
+            assert_eq!(std::fs::read(path).unwrap(), b"AUDIO-DATA");
+        }
+    }
+
+    #[test]
+    fn test_merge_trusted_keeps_preferred_source() {
+        let trust: TrustConfig = toml::from_str(
+            r#"
+            default = ["musicbrainz"]
+            genre = ["melon", "spotify"]
+            "#,
+        )
+        .unwrap();
+        let melon = TrackInfo {
+            title: Some("Blueming".to_string()),
+            genre: Some("발라드".to_string()),
+            year: Some(2019),
+            release_date: Some("2019-11-18".to_string()),
+            source: "melon".to_string(),
+            ..Default::default()
+        };
+        let spotify = TrackInfo {
+            title: Some("Blueming (Live)".to_string()),
+            genre: Some("k-pop".to_string()),
+            year: Some(2020),
+            source: "spotify".to_string(),
+            ..Default::default()
+        };
+
+        // 장르는 Melon이 앞서고, 목록에 없는 필드는 나중 값이 이긴다
+        let merged = merge_trusted(&Some(melon.clone()), &spotify, &trust);
+        assert_eq!(merged.genre.as_deref(), Some("발라드"));
+        assert_eq!(merged.field_source("genre"), "melon");
+        assert_eq!(merged.title.as_deref(), Some("Blueming (Live)"));
+        assert_eq!(merged.field_source("title"), "spotify");
+        assert_eq!(merged.release_date, None);
+
+        // 이어서 병합해도 필드별 출처를 기억한다
+        let musicbrainz = TrackInfo {
+            year: Some(2019),
+            genre: Some("pop".to_string()),
+            source: "musicbrainz".to_string(),
+            ..Default::default()
+        };
+        let merged = merge_trusted(&Some(merged), &musicbrainz, &trust);
+        assert_eq!(merged.genre.as_deref(), Some("발라드"));
+        assert_eq!(merged.year, Some(2019));
+        assert_eq!(merged.field_source("year"), "musicbrainz");
+        assert_eq!(merged.field_source("title"), "spotify");
+
+        // 순서가 없으면 기존 동작대로 새 값이 이긴다
+        let merged = merge_tags(&Some(melon), &spotify, MergePolicy::Overwrite);
+        assert_eq!(merged.genre.as_deref(), Some("k-pop"));
+    }
+
+    #[test]
+    fn test_merge_policy() {
+        let existing = Some(TrackInfo {
+            title: Some("Blueming".to_string()),
+            genre: Some("발라드".to_string()),
+            track_number: Some(4),
+            total_tracks: Some(10),
+            source: "id3".to_string(),
+            ..Default::default()
+        });
+        let fetched = TrackInfo {
+            title: Some("Blueming".to_string()),
+            genre: Some("k-pop".to_string()),
+            track_number: Some(5),
+            year: Some(2019),
+            source: "spotify".to_string(),
+            ..Default::default()
+        };
+
+        let merged = merge_tags(&existing, &fetched, MergePolicy::Overwrite);
+        assert_eq!(merged.genre.as_deref(), Some("k-pop"));
+        assert_eq!(merged.track_number, Some(5));
+
+        // 빈 필드만 채우고, 트랙 번호와 전체 트랙 수는 함께 지킨다
+        let merged = merge_tags(&existing, &fetched, MergePolicy::Fill);
+        assert_eq!(merged.genre.as_deref(), Some("발라드"));
+        assert_eq!(
+            (merged.track_number, merged.total_tracks),
+            (Some(4), Some(10))
+        );
+        assert_eq!(merged.year, Some(2019));
+
+        // 값이 다른 필드만 묻고, 같은 값은 기록 대상에서 뺀다
+        let mut asked = Vec::new();
+        let mut update = fetched.clone();
+        drop_existing_fields(&existing, &mut update, |field, old, new| {
+            asked.push(format!("{}: {} → {}", field, old, new));
+            field == "genre"
+        });
+        assert_eq!(asked, ["track_number: 4 → 5", "genre: 발라드 → k-pop"]);
+        assert_eq!(update.title, None);
+        assert_eq!(update.genre, None);
+        assert_eq!(update.track_number, Some(5));
+    }
+
+    #[test]
+    fn test_protected_fields_not_overwritten() {
+        let (_dir, path) = temp_audio_file("protected");
+        let config = WriteConfig {
+            protected: vec!["genre".to_string(), "lyrics".to_string()],
+            ..Default::default()
+        };
+        let info = TrackInfo {
+            genre: Some("Ballad".to_string()),
+            ..sample_info()
+        };
+        write_tags(&path, &info, &WriteConfig::default()).unwrap();
+        let before = read_tags(&path).unwrap();
+
+        let fetched = TrackInfo {
+            title: Some("Blueming (Remastered)".to_string()),
+            genre: Some("k-pop".to_string()),
+            lyrics: Some("새 가사".to_string()),
+            source: "spotify".to_string(),
+            ..Default::default()
+        };
+        let mut kept = fetched.clone();
+        // 파일에 가사가 없으므로 가사는 채운다
+        assert_eq!(keep_protected(&before, &mut kept, &config), ["genre"]);
+        assert_eq!(kept.genre, before.as_ref().unwrap().genre);
+
+        write_tags(&path, &fetched, &config).unwrap();
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!(tags.title.as_deref(), Some("Blueming (Remastered)"));
+        assert_eq!(tags.genre, before.unwrap().genre);
+        assert_eq!(tags.lyrics.as_deref(), Some("새 가사"));
+
+        // 직접 편집한 값은 막지 않는다
+        let manual = TrackInfo {
+            genre: Some("Dance".to_string()),
+            source: "manual".to_string(),
+            ..Default::default()
+        };
+        write_tags(&path, &manual, &config).unwrap();
+        let tags = read_tags(&path).unwrap().unwrap();
+        assert_eq!(tags.genre.as_deref(), Some("Dance"));
+    }
+
+    #[test]
+    fn test_edit_frames() {
+        let (_dir, path) = temp_audio_file("frames");
+        write_tags(&path, &sample_info(), &WriteConfig::default()).unwrap();
+        let set = |id: &str, value: &str| FrameEdit::Set(id.to_string(), value.to_string());
+        let edits = [
+            set("TIT3", "Live"),
+            set("TXXX:MOOD", "calm"),
+            set("WOAR", "https://example.com/iu"),
+            set("COMM", "메모"),
+        ];
+        edit_frames(&path, &edits, &WriteConfig::default()).unwrap();
+
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(
+            tag.get("TIT3").and_then(|f| f.content().text()),
+            Some("Live")
+        );
+        assert_eq!(extended_text(&tag, "mood").as_deref(), Some("calm"));
+        assert_eq!(
+            tag.get("WOAR").and_then(|f| f.content().link()),
+            Some("https://example.com/iu")
+        );
+        assert_eq!(tag.comments().next().map(|c| c.text.as_str()), Some("메모"));
+        assert_eq!(tag.title(), Some("Blueming"));
+
+        let edits = [
+            FrameEdit::Delete("TXXX:MOOD".to_string()),
+            FrameEdit::Delete("TIT3".to_string()),
+        ];
+        edit_frames(&path, &edits, &WriteConfig::default()).unwrap();
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert!(tag.get("TIT3").is_none());
+        assert!(extended_text(&tag, "MOOD").is_none());
+        assert!(tag.get("WOAR").is_some());
+
+        // 잘못된 변경이 하나라도 있으면 아무것도 기록하지 않는다
+        let edits = [set("TIT3", "Live"), FrameEdit::Delete("PRIV".to_string())];
+        assert!(edit_frames(&path, &edits, &WriteConfig::default()).is_err());
+        assert!(Tag::read_from_path(&path).unwrap().get("TIT3").is_none());
+        assert!(parse_frame_spec("tit3").is_err());
+        assert!(apply_frame_edit(&mut Tag::new(), &set("APIC", "x")).is_err());
+        assert!(apply_frame_edit(&mut Tag::new(), &set("TIT3:x", "x")).is_err());
     }
 }
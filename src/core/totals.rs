@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use crate::models::{Mp3File, TrackInfo};
+
+/// 파일 하나에 새로 기록할 전체 트랙 수/디스크 수.
+#[derive(Debug, Clone)]
+pub struct TotalsChange<'a> {
+    pub file: &'a Mp3File,
+    /// 바꿀 전체 트랙 수 (바꾸지 않으면 None)
+    pub total_tracks: Option<u32>,
+    /// 바꿀 전체 디스크 수 (바꾸지 않으면 None)
+    pub total_discs: Option<u32>,
+}
+
+impl TotalsChange<'_> {
+    /// 기록용 TrackInfo. 전체 수만 담아 다른 필드는 건드리지 않는다.
+    pub fn update(&self) -> TrackInfo {
+        TrackInfo {
+            total_tracks: self.total_tracks,
+            total_discs: self.total_discs,
+            source: "id3".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// 앨범 폴더 하나의 파일들을 보고 "3/?"처럼 비어 있거나 맞지 않는 전체 트랙/디스크 수를 고친다.
+/// 디스크마다 전체 트랙 수는 그 디스크의 파일 수, 가장 큰 트랙 번호, 이미 기록된 가장 큰 전체 수 중
+/// 큰 값으로 맞춘다. 일부 트랙만 있는 앨범에서 소스가 채운 전체 수를 줄이지 않기 위해서다.
+/// 전체 디스크 수도 같은 방식으로 맞추며, 디스크 번호가 있는 파일에만 기록한다.
+/// 트랙 번호가 없는 파일에는 전체 트랙 수를 기록하지 않는다.
+pub fn normalize_totals<'a>(album: &[&'a Mp3File]) -> Vec<TotalsChange<'a>> {
+    let tags = |file: &'a Mp3File| file.current_tags.as_ref();
+
+    let total_discs = album
+        .iter()
+        .filter_map(|f| tags(f))
+        .flat_map(|t| [t.disc_number, t.total_discs])
+        .flatten()
+        .max();
+
+    // 디스크 번호 → (파일 수, 가장 큰 트랙 번호/전체 수)
+    let mut discs: BTreeMap<u32, (u32, u32)> = BTreeMap::new();
+    for file in album {
+        let info = tags(file);
+        let disc = info.and_then(|t| t.disc_number).unwrap_or(1);
+        let entry = discs.entry(disc).or_default();
+        entry.0 += 1;
+        if let Some(t) = info {
+            let highest = t.track_number.max(t.total_tracks).unwrap_or(0);
+            entry.1 = entry.1.max(highest);
+        }
+    }
+
+    album
+        .iter()
+        .filter_map(|&file| {
+            let info = tags(file)?;
+            let disc = info.disc_number.unwrap_or(1);
+            let tracks = discs.get(&disc).map(|&(count, highest)| count.max(highest));
+            let total_tracks = tracks
+                .filter(|_| info.track_number.is_some())
+                .filter(|&n| info.total_tracks != Some(n));
+            let total_discs = total_discs
+                .filter(|_| info.disc_number.is_some())
+                .filter(|&n| info.total_discs != Some(n));
+            (total_tracks.is_some() || total_discs.is_some()).then_some(TotalsChange {
+                file,
+                total_tracks,
+                total_discs,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn file(track: u32, total: Option<u32>, disc: Option<(u32, Option<u32>)>) -> Mp3File {
+        Mp3File {
+            path: PathBuf::from(format!("{}.mp3", track)),
+            current_tags: Some(TrackInfo {
+                track_number: Some(track),
+                total_tracks: total,
+                disc_number: disc.map(|d| d.0),
+                total_discs: disc.and_then(|d| d.1),
+                ..Default::default()
+            }),
+            has_tags: true,
+            encoder: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_totals() {
+        // 한 장짜리 앨범: "3/?"를 파일 수로 채우고, 이미 맞는 파일은 건드리지 않는다
+        let files = [
+            file(1, Some(3), None),
+            file(2, None, None),
+            file(3, None, None),
+        ];
+        let album: Vec<&Mp3File> = files.iter().collect();
+        let changes = normalize_totals(&album);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.total_tracks == Some(3)));
+        assert!(changes.iter().all(|c| c.total_discs.is_none()));
+
+        // 일부 트랙만 있으면 기록된 전체 수(12)를 유지한다
+        let files = [file(1, Some(12), None), file(5, None, None)];
+        let album: Vec<&Mp3File> = files.iter().collect();
+        let changes = normalize_totals(&album);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].total_tracks, Some(12));
+
+        // 두 장: 디스크마다 트랙 수를 따로 세고 전체 디스크 수를 채운다
+        let files = [
+            file(1, None, Some((1, None))),
+            file(2, None, Some((1, None))),
+            file(1, None, Some((2, None))),
+        ];
+        let album: Vec<&Mp3File> = files.iter().collect();
+        let totals: Vec<_> = normalize_totals(&album)
+            .iter()
+            .map(|c| (c.total_tracks, c.total_discs))
+            .collect();
+        assert_eq!(
+            totals,
+            [(Some(2), Some(2)), (Some(2), Some(2)), (Some(1), Some(2))]
+        );
+    }
+}
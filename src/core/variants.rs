@@ -0,0 +1,441 @@
+use crate::config::AlbumEdition;
+use crate::core::{compilation, matcher};
+use crate::models::TrackInfo;
+
+/// 앨범 이름에 이 말이 들어 있으면 해당 판으로 본다 (소문자 비교, 앞쪽 판이 우선).
+/// 영문은 단어 앞부분으로만 찾는다 ("Remastered"는 리마스터, "Alive"는 라이브가 아님).
+const EDITION_WORDS: &[(AlbumEdition, &[&str])] = &[
+    (
+        AlbumEdition::Compilation,
+        &[
+            "greatest hits",
+            "best of",
+            "the best",
+            "collection",
+            "compilation",
+            "anthology",
+            "베스트",
+            "히트곡",
+            "모음",
+        ],
+    ),
+    (
+        AlbumEdition::Live,
+        &["live", "concert", "unplugged", "라이브", "콘서트"],
+    ),
+    (
+        AlbumEdition::Region,
+        &[
+            "japanese",
+            "japan ver",
+            "japan edition",
+            "chinese ver",
+            "international ver",
+            "international edition",
+            "일본반",
+            "일본어",
+            "중국어",
+        ],
+    ),
+    (
+        AlbumEdition::Remaster,
+        &["remaster", "anniversary", "리마스터", "주년"],
+    ),
+    (
+        AlbumEdition::Deluxe,
+        &[
+            "deluxe",
+            "expanded",
+            "special edition",
+            "repackage",
+            "디럭스",
+            "리패키지",
+            "스페셜",
+        ],
+    ),
+];
+
+/// 같은 곡의 다른 버전을 가리키는 표시 ("(Explicit)", "(Clean)", "(Inst.)").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Marker {
+    /// 가사를 고치지 않은 판
+    Explicit,
+    /// 가사를 고친 판
+    Clean,
+    /// 반주/노래방 버전
+    Instrumental,
+}
+
+impl Marker {
+    /// 화면 표시용 이름.
+    pub fn label(self) -> &'static str {
+        match self {
+            Marker::Explicit => "Explicit",
+            Marker::Clean => "Clean",
+            Marker::Instrumental => "Inst.",
+        }
+    }
+}
+
+/// 표시마다 (어디에 있어도 찾는 말, 괄호 안이나 " - " 뒤에서만 찾는 말).
+/// "Clean Bandit"이나 "Instinct" 같은 이름을 표시로 보지 않도록 짧은 말은 버전 표기 자리에서만 찾는다.
+const MARKER_WORDS: &[(Marker, &[&str], &[&str])] = &[
+    (Marker::Explicit, &[], &["explicit", "19금"]),
+    (Marker::Clean, &[], &["clean"]),
+    (
+        Marker::Instrumental,
+        &["karaoke", "instrumental", "노래방", "반주"],
+        &["inst", "mr"],
+    ),
+];
+
+/// 같은 곡을 다시 녹음하거나 편집한 버전 ("(Live)", "(Remix)", "(Sped Up Ver.)").
+/// 파일과 검색 결과의 버전이 다르면 `auto`는 그 결과를 고르지 않는다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Version {
+    Live,
+    Remix,
+    Acoustic,
+    SpedUp,
+    RadioEdit,
+}
+
+/// 버전마다 괄호 안이나 " - " 뒤에서 찾는 말. "Live Your Life" 같은 제목을 버전으로 보지 않도록
+/// 버전 표기 자리에서만 찾는다.
+const VERSION_WORDS: &[(Version, &[&str])] = &[
+    (Version::Live, &["live", "라이브"]),
+    (Version::Remix, &["remix", "rmx", "리믹스"]),
+    (Version::Acoustic, &["acoustic", "어쿠스틱"]),
+    (
+        Version::SpedUp,
+        &["sped up", "sped-up", "speed up", "nightcore"],
+    ),
+    (
+        Version::RadioEdit,
+        &["radio edit", "radio ver", "radio mix"],
+    ),
+];
+
+/// 제목에 있는 버전 표시.
+pub fn versions(title: &str) -> Vec<Version> {
+    let lower = title.to_lowercase();
+    let parts = version_parts(&lower);
+    VERSION_WORDS
+        .iter()
+        .filter(|(_, words)| parts.iter().any(|p| words.iter().any(|w| has_word(p, w))))
+        .map(|(version, _)| *version)
+        .collect()
+}
+
+/// 트랙 제목의 버전 표시. 제목이 없으면 빈 목록.
+pub fn track_versions(track: &TrackInfo) -> Vec<Version> {
+    track.title.as_deref().map(versions).unwrap_or_default()
+}
+
+/// 검색 결과가 실린 앨범의 판을 앨범 이름, 앨범 아티스트, 트랙 수로 추정한다.
+pub fn edition(track: &TrackInfo) -> AlbumEdition {
+    if compilation::is_various(track.album_artist.as_deref()) {
+        return AlbumEdition::Compilation;
+    }
+    let Some(album) = track.album.as_deref() else {
+        return AlbumEdition::Original;
+    };
+    let lower = album.to_lowercase();
+    for (edition, words) in EDITION_WORDS {
+        if words.iter().any(|w| has_word(&lower, w)) {
+            return *edition;
+        }
+    }
+
+    let key = |s: &str| matcher::normalize(&matcher::strip_brackets(s));
+    let titled = track.title.as_deref().is_some_and(|t| key(t) == key(album));
+    if titled || track.total_tracks.is_some_and(|n| n <= 3) {
+        return AlbumEdition::Single;
+    }
+    AlbumEdition::Original
+}
+
+/// `text`(파일 경로, 앨범 이름 등)에 `edition` 판을 나타내는 말이 있는지.
+pub fn mentions(text: &str, edition: AlbumEdition) -> bool {
+    let lower = text.to_lowercase();
+    EDITION_WORDS
+        .iter()
+        .filter(|(e, _)| *e == edition)
+        .any(|(_, words)| words.iter().any(|w| has_word(&lower, w)))
+}
+
+/// `text`(제목, 앨범 이름, 파일 경로)에 있는 버전 표시.
+pub fn markers(text: &str) -> Vec<Marker> {
+    let lower = text.to_lowercase();
+    let parts = version_parts(&lower);
+    MARKER_WORDS
+        .iter()
+        .filter(|(_, anywhere, tagged)| {
+            anywhere.iter().any(|w| has_word(&lower, w))
+                || parts.iter().any(|p| tagged.iter().any(|w| has_word(p, w)))
+        })
+        .map(|(marker, _, _)| *marker)
+        .collect()
+}
+
+/// 검색 결과의 버전 표시. 제목과 앨범 이름의 표시에 소스가 알려 준 explicit 여부를 더한다.
+pub fn track_markers(track: &TrackInfo) -> Vec<Marker> {
+    let mut found: Vec<Marker> = [&track.title, &track.album]
+        .into_iter()
+        .flatten()
+        .flat_map(|text| markers(text))
+        .collect();
+    if track.explicit == Some(true) {
+        found.push(Marker::Explicit);
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+/// 후보 목록에 덧붙일 " [Explicit] [Inst.]". 표시가 없으면 빈 문자열.
+pub fn marker_suffix(track: &TrackInfo) -> String {
+    track_markers(track)
+        .iter()
+        .map(|m| format!(" [{}]", m.label()))
+        .collect()
+}
+
+/// 버전 표기가 들어가는 자리: 괄호/대괄호 안과 " - " 뒤 ("Blueming - Instrumental").
+fn version_parts(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' => start = Some(i + 1),
+            ')' | ']' => {
+                if let Some(s) = start.take() {
+                    parts.push(&text[s..i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    parts.extend(text.split(" - ").skip(1));
+    parts
+}
+
+/// `text`에 `word`가 있는지. 영문 단어는 앞이 글자/숫자가 아닌 자리에서만 찾는다.
+fn has_word(text: &str, word: &str) -> bool {
+    if !word.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return text.contains(word);
+    }
+    text.match_indices(word)
+        .any(|(i, _)| !text[..i].ends_with(|c: char| c.is_alphanumeric()))
+}
+
+/// `preference`에서 결과 앨범 판의 순위 (작을수록 선호). 목록에 없는 판은 맨 뒤.
+pub fn rank(track: &TrackInfo, preference: &[AlbumEdition]) -> usize {
+    let edition = edition(track);
+    preference
+        .iter()
+        .position(|e| *e == edition)
+        .unwrap_or(preference.len())
+}
+
+/// 같은 곡이 여러 앨범에 실린 검색 결과 묶음. 번호는 검색 결과 목록의 위치이다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultGroup {
+    /// 선호하는 판에 실린 대표 결과
+    pub primary: usize,
+    /// 같은 곡의 다른 판 결과 (선호 순)
+    pub others: Vec<usize>,
+}
+
+/// 두 결과가 같은 녹음인지. 괄호를 뺀 제목과 아티스트, 버전(Live/Remix 등)과 표시(Explicit/Clean/Inst.)가
+/// 같고, 양쪽에 재생 시간이 있으면 허용 오차 안이어야 한다.
+fn same_recording(a: &TrackInfo, b: &TrackInfo) -> bool {
+    if !matcher::is_same_song(a, b)
+        || track_versions(a) != track_versions(b)
+        || track_markers(a) != track_markers(b)
+    {
+        return false;
+    }
+    match (a.duration_ms, b.duration_ms) {
+        (Some(x), Some(y)) => x.abs_diff(y) <= matcher::DURATION_TOLERANCE_MS,
+        _ => true,
+    }
+}
+
+/// 같은 곡을 담은 검색 결과를 묶는다. 묶음은 처음 나온 결과의 순서를 따르고, 묶음 안에서는
+/// `preference`(`MatchingConfig::album_preference`)에서 앞선 판이, 같은 판이면 먼저 나온 결과가 대표가 된다.
+pub fn group_results(results: &[TrackInfo], preference: &[AlbumEdition]) -> Vec<ResultGroup> {
+    let mut members: Vec<Vec<usize>> = Vec::new();
+    for (i, track) in results.iter().enumerate() {
+        match members
+            .iter_mut()
+            .find(|m| same_recording(&results[m[0]], track))
+        {
+            Some(m) => m.push(i),
+            None => members.push(vec![i]),
+        }
+    }
+    members
+        .into_iter()
+        .map(|mut m| {
+            m.sort_by_key(|&i| rank(&results[i], preference));
+            ResultGroup {
+                primary: m[0],
+                others: m[1..].to_vec(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(title: &str, album: &str, duration_ms: u64) -> TrackInfo {
+        TrackInfo {
+            title: Some(title.to_string()),
+            artists: vec!["IU".to_string()],
+            album: Some(album.to_string()),
+            duration_ms: Some(duration_ms),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_edition() {
+        assert_eq!(
+            edition(&track("밤편지", "Palette", 253_000)),
+            AlbumEdition::Original
+        );
+        assert_eq!(
+            edition(&track("Blueming", "Love poem (Deluxe Edition)", 217_000)),
+            AlbumEdition::Deluxe
+        );
+        assert_eq!(
+            edition(&track("좋은 날", "IU Greatest Hits", 234_000)),
+            AlbumEdition::Compilation
+        );
+        assert_eq!(
+            edition(&track("밤편지", "밤편지", 253_000)),
+            AlbumEdition::Single
+        );
+        assert_eq!(
+            edition(&track(
+                "Love wins all",
+                "Love wins all (Remastered)",
+                271_000
+            )),
+            AlbumEdition::Remaster
+        );
+        assert_eq!(
+            edition(&track("Alive", "Alive Forever", 200_000)),
+            AlbumEdition::Original
+        );
+        assert_eq!(
+            edition(&track("Blueming", "Love poem (Japanese Ver.)", 217_000)),
+            AlbumEdition::Region
+        );
+        let various = TrackInfo {
+            album_artist: Some("Various Artists".to_string()),
+            ..track("밤편지", "2017 K-POP", 253_000)
+        };
+        assert_eq!(edition(&various), AlbumEdition::Compilation);
+    }
+
+    #[test]
+    fn test_mentions() {
+        assert!(mentions(
+            "Music/IU/Greatest Hits/01 좋은 날.mp3",
+            AlbumEdition::Compilation
+        ));
+        assert!(!mentions(
+            "Music/IU/Palette/01 밤편지.mp3",
+            AlbumEdition::Compilation
+        ));
+    }
+
+    #[test]
+    fn test_markers() {
+        assert_eq!(markers("밤편지 (Inst.)"), [Marker::Instrumental]);
+        assert_eq!(markers("Blueming (Karaoke Ver.)"), [Marker::Instrumental]);
+        assert_eq!(markers("Blueming - Instrumental"), [Marker::Instrumental]);
+        assert_eq!(markers("삐삐 [MR]"), [Marker::Instrumental]);
+        assert_eq!(markers("Bad Guy (Explicit)"), [Marker::Explicit]);
+        assert_eq!(
+            markers("Music/Clean Bandit/Rockabye (Clean).mp3"),
+            [Marker::Clean]
+        );
+        assert!(markers("Music/Clean Bandit/Instinct.mp3").is_empty());
+
+        let flagged = TrackInfo {
+            title: Some("Bad Guy".to_string()),
+            explicit: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(track_markers(&flagged), [Marker::Explicit]);
+        assert_eq!(marker_suffix(&flagged), " [Explicit]");
+    }
+
+    #[test]
+    fn test_versions() {
+        assert_eq!(versions("밤편지 (Live)"), [Version::Live]);
+        assert_eq!(versions("Celebrity - Live"), [Version::Live]);
+        assert_eq!(versions("사랑이 잘 (With 오혁) [라이브]"), [Version::Live]);
+        assert_eq!(versions("Butter (Hotter Remix)"), [Version::Remix]);
+        assert_eq!(versions("Dynamite (Slow Jam Remix)"), [Version::Remix]);
+        assert_eq!(versions("Next Level (Remix) [feat. ...]"), [Version::Remix]);
+        assert_eq!(versions("Blueming (Acoustic Ver.)"), [Version::Acoustic]);
+        assert_eq!(versions("Hype Boy (Sped Up)"), [Version::SpedUp]);
+        assert_eq!(versions("LOVE DIVE (Sped Up Ver.)"), [Version::SpedUp]);
+        assert_eq!(versions("ETA (Radio Edit)"), [Version::RadioEdit]);
+        assert_eq!(
+            versions("Ditto (Remix) (Live)"),
+            [Version::Live, Version::Remix]
+        );
+
+        // 버전 표기가 아닌 괄호와 제목 속 단어는 무시한다
+        assert!(versions("에잇 (Prod. & Feat. SUGA of BTS)").is_empty());
+        assert!(versions("LOVE DIVE (English Ver.)").is_empty());
+        assert!(versions("Live Your Life").is_empty());
+        assert!(versions("Celebrity").is_empty());
+    }
+
+    #[test]
+    fn test_group_results_prefers_original_album() {
+        let results = vec![
+            track("Blueming", "Love poem (Deluxe Edition)", 217_000),
+            track("밤편지", "Palette", 253_000),
+            track("Blueming", "Love poem", 217_500),
+            track("Blueming", "K-POP Best Collection", 217_000),
+            track("Blueming (Live)", "Love, poem Concert", 260_000),
+            track("Blueming (Inst.)", "Love poem", 217_000),
+        ];
+        let preference = AlbumEdition::DEFAULT_ORDER;
+        let groups = group_results(&results, &preference);
+        assert_eq!(
+            groups,
+            [
+                ResultGroup {
+                    primary: 2,
+                    others: vec![0, 3],
+                },
+                ResultGroup {
+                    primary: 1,
+                    others: vec![],
+                },
+                ResultGroup {
+                    primary: 4,
+                    others: vec![],
+                },
+                ResultGroup {
+                    primary: 5,
+                    others: vec![],
+                },
+            ]
+        );
+
+        // 설정한 순서가 기본 순서보다 우선한다
+        let deluxe_first = [AlbumEdition::Deluxe, AlbumEdition::Original];
+        assert_eq!(group_results(&results, &deluxe_first)[0].primary, 0);
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt;
+
+use crate::core::cancel;
+use crate::i18n::{self, tr};
+use crate::sources::http::HttpStatusError;
+
+/// 스크립트가 오류 문구 대신 분기할 수 있는 종료 코드. 성공은 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// 그 밖의 오류
+    Failure = 1,
+    /// 일부 파일만 실패
+    Partial = 2,
+    /// 처리할 파일이나 일치하는 결과가 없음
+    NothingMatched = 3,
+    /// 설정 파일 오류, 자격증명 없음, 잘못된 명령줄 인자
+    Config = 4,
+    /// 네트워크/API 요청 실패
+    Network = 5,
+    /// Ctrl-C로 중단됨 (셸의 SIGINT 관례)
+    Cancelled = 130,
+}
+
+/// 명령이 끝까지 실행됐지만 완전히 성공하지 못한 결과.
+/// 에러로 돌려주면 main이 요약 줄을 출력하고 해당 종료 코드로 끝낸다.
+#[derive(Debug)]
+pub enum Outcome {
+    /// `total`건 중 `failed`건 실패
+    Partial { failed: usize, total: usize },
+    /// 처리할 파일이나 일치하는 결과가 없음
+    NothingMatched,
+    /// Ctrl-C로 남은 파일을 처리하지 않고 멈춤
+    Cancelled,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Partial { failed, total } => f.write_str(&tr!(
+                "{}건 중 {}건 실패",
+                "{1} of {0} failed",
+                total,
+                failed
+            )),
+            Outcome::NothingMatched => f.write_str(i18n::pick("대상 없음", "nothing matched")),
+            Outcome::Cancelled => f.write_str(i18n::pick(
+                "중단됨 (다시 실행하면 남은 파일을 처리합니다)",
+                "interrupted (run again to process the remaining files)",
+            )),
+        }
+    }
+}
+
+impl std::error::Error for Outcome {}
+
+/// 설정 문제로 실행할 수 없음 (설정 값 오류, 자격증명 없음 등).
+#[derive(Debug)]
+pub struct ConfigError(pub String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// 설정 오류를 만든다. `anyhow::bail!` 대신 `return Err(exit::config_error(...))`로 쓴다.
+pub fn config_error(message: impl Into<String>) -> anyhow::Error {
+    ConfigError(message.into()).into()
+}
+
+/// 명령줄 인자를 해석하지 못했을 때의 종료 코드. `--help`는 0, 잘못된 인자는 `Config`.
+pub fn usage_code(err: &clap::Error) -> i32 {
+    if err.use_stderr() {
+        ExitCode::Config as i32
+    } else {
+        0
+    }
+}
+
+/// Ctrl-C로 중단됐으면 `Outcome::Cancelled`로 끝낸다.
+pub fn check_cancelled() -> anyhow::Result<()> {
+    if cancel::requested() {
+        return Err(Outcome::Cancelled.into());
+    }
+    Ok(())
+}
+
+/// 중단됐거나 실패가 있으면 `Outcome::Cancelled`/`Outcome::Partial`로 끝낸다.
+/// 일괄 작업의 요약을 출력한 뒤 호출한다.
+pub fn check_failures(failed: usize, total: usize) -> anyhow::Result<()> {
+    check_cancelled()?;
+    if failed > 0 {
+        return Err(Outcome::Partial { failed, total }.into());
+    }
+    Ok(())
+}
+
+/// 처리할 대상이 없다는 결과. `context`로 사유를 붙인다.
+pub fn nothing_matched(context: impl fmt::Display + Send + Sync + 'static) -> anyhow::Error {
+    anyhow::Error::new(Outcome::NothingMatched).context(context)
+}
+
+/// 에러 체인에서 종료 코드를 고른다. 가장 바깥쪽에서 먼저 찾은 원인을 따른다.
+pub fn code(err: &anyhow::Error) -> ExitCode {
+    for cause in err.chain() {
+        if let Some(outcome) = cause.downcast_ref::<Outcome>() {
+            return match outcome {
+                Outcome::Partial { .. } => ExitCode::Partial,
+                Outcome::NothingMatched => ExitCode::NothingMatched,
+                Outcome::Cancelled => ExitCode::Cancelled,
+            };
+        }
+        if cause.is::<ConfigError>() || cause.is::<toml::de::Error>() {
+            return ExitCode::Config;
+        }
+        if cause.is::<reqwest::Error>() || cause.is::<HttpStatusError>() {
+            return ExitCode::Network;
+        }
+    }
+    ExitCode::Failure
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_code() {
+        assert_eq!(code(&check_failures(2, 10).unwrap_err()), ExitCode::Partial);
+        assert!(check_failures(0, 10).is_ok());
+        assert_eq!(code(&nothing_matched("no files")), ExitCode::NothingMatched);
+        assert_eq!(code(&Outcome::Cancelled.into()), ExitCode::Cancelled);
+        let err = Err::<(), _>(config_error("no client_id"))
+            .context("Spotify")
+            .unwrap_err();
+        assert_eq!(code(&err), ExitCode::Config);
+        assert_eq!(
+            code(&toml::from_str::<toml::Table>("= 1").unwrap_err().into()),
+            ExitCode::Config
+        );
+        assert_eq!(code(&anyhow::anyhow!("other")), ExitCode::Failure);
+    }
+
+    #[test]
+    fn test_usage_code() {
+        use clap::Parser;
+
+        let parse = |args: &[&str]| crate::cli::Cli::try_parse_from(args).err().unwrap();
+        assert_eq!(usage_code(&parse(&["mp3tag", "--help"])), 0);
+        assert_eq!(usage_code(&parse(&["mp3tag", "scan", "--help"])), 0);
+        assert_eq!(
+            usage_code(&parse(&["mp3tag", "--no-such-flag"])),
+            ExitCode::Config as i32
+        );
+        assert_eq!(
+            usage_code(&parse(&["mp3tag", "search", "--limit", "abc", "IU"])),
+            ExitCode::Config as i32
+        );
+    }
+}
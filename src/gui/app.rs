@@ -1,36 +1,82 @@
-use std::path::PathBuf;
-use std::sync::mpsc;
-
-use egui::{ColorImage, TextureHandle};
-
-use crate::config;
-use crate::core::{parser, renamer, scanner, tagger};
-use crate::models::{Mp3File, TrackInfo};
-use crate::sources::melon::MelonClient;
-use crate::sources::spotify::SpotifyClient;
-use crate::sources::MusicSource;
-
-/// 검색 소스 선택.
-#[derive(PartialEq, Clone, Copy)]
-enum SearchSource {
-    Spotify,
-    Melon,
-}
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+
+use egui::{ColorImage, Key, TextureHandle};
+
+use crate::config::{
+    self, AlbumEdition, ArtConfig, FileSort, GuiConfig, MergePolicy, Setup, TagPreset,
+};
+use crate::core::artwork::ArtInfo;
+use crate::core::fetch_state::{FetchState, AUTO_STATE_FILE};
+use crate::core::renamer::{self, RenameOutcome};
+use crate::core::review::ReviewQueue;
+use crate::core::staging::StagedChanges;
+use crate::core::variants::{self, ResultGroup};
+use crate::core::{parser, scanner, tagger};
+use crate::gui::clipboard;
+use crate::gui::folder_tree::FolderTree;
+use crate::models::{split_artists, Mp3File, TrackInfo};
+use crate::sources::art_search::{self, ArtCandidate};
+use crate::sources::cover_art::CoverArtClient;
+use crate::sources::{self, MusicSource, Page, SourceKind};
 
 /// 백그라운드 스레드에서 GUI 스레드로 전달되는 결과.
 enum BgResult {
+    /// 라이브러리 데이터베이스에 저장된 목록. 스캔이 끝나면 `ScanDone`으로 바뀐다.
+    #[cfg_attr(not(feature = "library"), allow(dead_code))]
+    ScanCached(Vec<Mp3File>),
     ScanDone(Vec<Mp3File>),
-    SearchDone(Vec<TrackInfo>),
-    DetailDone(usize, TrackInfo),
+    /// 검색 결과 일부 (검색 번호, 한 소스의 결과). 소스가 응답하는 순서대로 도착한다.
+    SearchResults(u64, Vec<TrackInfo>),
+    /// 검색 끝 (검색 번호, 실패하거나 시간 초과된 소스의 메시지)
+    SearchFinished(u64, Vec<String>),
+    DetailDone(usize, Box<TrackInfo>),
+    /// 앨범 아트 검색 결과
+    ArtDone(Vec<ArtCandidate>),
+    /// 검토 탭에서 고른 태그를 기록함 (파일 경로, 기록한 태그)
+    ReviewApplied(PathBuf, Box<TrackInfo>),
     Error(String),
 }
 
+/// 중앙 화면.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    /// 파일별 태그 편집과 온라인 검색
+    Edit,
+    /// `auto`가 남긴 검토 대기 목록
+    Review,
+}
+
+/// 검토 탭의 한 행에 보여 줄 값.
+type FieldText = fn(&TrackInfo) -> String;
+
+/// 검토 탭에서 버튼이나 단축키로 고른 동작.
+enum ReviewAction {
+    Select(usize),
+    Move(isize),
+    /// 후보 적용. None이면 추론 값 적용.
+    Accept(Option<usize>),
+    Reject,
+}
+
 /// egui 기반 MP3 태그 편집기 앱.
 pub struct Mp3TagApp {
     // 파일 목록
     dir_path: String,
+    /// 폴더 트리 패널을 보일지
+    show_folders: bool,
+    folder_tree: FolderTree,
     files: Vec<Mp3File>,
     selected_index: Option<usize>,
+    /// 스캔이 끝나면 선택할 파일 (명령줄로 연 파일)
+    pending_select: Option<PathBuf>,
+    file_sort: FileSort,
+    sort_descending: bool,
+
+    // 패널 너비 (세션에 저장)
+    folder_panel_width: f32,
+    file_panel_width: f32,
 
     // 태그 편집
     edit_title: String,
@@ -39,18 +85,69 @@ pub struct Mp3TagApp {
     edit_album_artist: String,
     edit_track: String,
     edit_year: String,
+    /// 발매일 ("2019-11-18"). 비워 두면 연도만 기록한다.
+    edit_date: String,
     edit_genre: String,
+    // 클래식 음악 필드 (콘텐츠 그룹, 작품명, 악장)
+    edit_grouping: String,
+    edit_work: String,
+    edit_movement: String,
+    /// 악장 번호 ("1" 또는 "1/4")
+    edit_movement_number: String,
+    /// 편집 필드를 채울 태그 묶음 (`[presets.이름]`)
+    presets: BTreeMap<String, TagPreset>,
 
     // 검색
-    search_source: SearchSource,
+    search_source: SourceKind,
+    /// 검색 소스 목록 (내장 소스 + 설정의 명령 소스)
+    source_choices: Vec<SourceKind>,
+    /// 선택한 소스 대신 모든 소스를 동시에 검색
+    search_all: bool,
+    /// 진행 중인 검색 번호. 이전 검색의 늦은 결과를 버리는 데 쓴다.
+    search_id: u64,
     search_query: String,
     search_results: Vec<TrackInfo>,
     selected_result: Option<usize>,
+    /// 마지막 검색의 검색어와 소스. "결과 더 보기"는 같은 조건으로 다음 페이지를 가져온다.
+    last_search: Option<(String, Vec<SourceKind>)>,
+    /// 마지막으로 가져온 결과 페이지
+    search_page: Page,
+    /// 어느 소스든 한 페이지를 가득 채워 응답했으면 다음 페이지가 있을 수 있다
+    search_has_more: bool,
+    /// 같은 곡을 담은 검색 결과 묶음. 결과가 도착할 때마다 다시 묶는다.
+    result_groups: Vec<ResultGroup>,
+    /// 묶음의 대표로 고를 앨범 판 순서 (`[matching] album_preference`)
+    album_preference: Vec<AlbumEdition>,
+
+    // 검토
+    tab: Tab,
+    review: Option<ReviewQueue>,
+    review_index: usize,
+    /// 파일에 기록하지 않고 쌓아 둔 변경 (열린 디렉토리의 `.mp3tag-staged/`)
+    staged: Option<StagedChanges>,
 
     // 앨범 아트
     album_art_texture: Option<TextureHandle>,
+    /// 선택한 파일의 앨범 아트 크기/형식 (디코딩하지 못해도 표시)
+    album_art_info: Option<ArtInfo>,
+    /// 앨범 아트 경고 기준 (`[art] warn_max_bytes`, `warn_min_size`)
+    art_config: ArtConfig,
     result_art_textures: Vec<Option<TextureHandle>>,
 
+    // 앨범 아트 검색 창
+    art_dialog_open: bool,
+    art_query: String,
+    art_candidates: Vec<ArtCandidate>,
+    art_textures: Vec<Option<TextureHandle>>,
+    /// 같은 폴더의 같은 앨범 파일 전체에 적용할지
+    art_whole_album: bool,
+    /// Ctrl+V를 Paste 이벤트로 이미 처리함 (V 키를 뗄 때 다시 붙이지 않음)
+    paste_handled: bool,
+    /// 처음 실행 설정 창. 설정 파일이 없을 때만 연다.
+    setup: Option<Setup>,
+    /// 처음 실행 설정 창의 오류 메시지
+    setup_error: String,
+
     // 백그라운드 작업
     tx: mpsc::Sender<BgResult>,
     rx: mpsc::Receiver<BgResult>,
@@ -59,10 +156,32 @@ pub struct Mp3TagApp {
 }
 
 impl Mp3TagApp {
-    /// 앱을 초기화한다. 폰트를 로드하고, directory가 주어지면 스캔을 시작한다.
-    pub fn new(cc: &eframe::CreationContext<'_>, directory: Option<PathBuf>) -> Self {
+    /// 앱을 초기화한다. 폰트를 로드하고 지난 세션(`[gui]`)을 복원한 뒤 `paths`를 연다.
+    /// - 없음: 마지막으로 연 디렉토리를 스캔한다.
+    /// - 디렉토리 하나: 그 디렉토리를 스캔한다.
+    /// - 파일 하나: 파일이 있는 디렉토리를 스캔하고 그 파일을 선택한다.
+    /// - 여러 개: 주어진 파일(디렉토리는 그 안의 MP3)만 목록에 올리고 첫 파일을 선택한다.
+    pub fn new(cc: &eframe::CreationContext<'_>, paths: Vec<PathBuf>) -> Self {
         Self::setup_fonts(&cc.egui_ctx);
         let (tx, rx) = mpsc::channel();
+        // 설정 파일을 해석할 수 없으면 기본값으로 띄우고 상태 표시줄에 알린다
+        let (cfg, config_error) = match config::load_config() {
+            Ok(cfg) => (cfg, None),
+            Err(e) => (config::Config::default(), Some(e.to_string())),
+        };
+        let session = cfg.gui.clone();
+        // 탐색기에서 넘어온 경로와 스캔 결과의 경로를 비교할 수 있도록 절대 경로로 맞춘다
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .map(|p| std::fs::canonicalize(&p).unwrap_or(p))
+            .collect();
+        let last_dir = session.last_dir.or_else(|| cfg.music_dir.clone());
+        let (directory, pending_select) = startup_target(&paths, last_dir);
+        let search_source = session
+            .source
+            .or_else(|| cfg.default_source.clone())
+            .and_then(|name| SourceKind::from_source_name(&name, &cfg))
+            .unwrap_or(SourceKind::Spotify);
 
         let dir_path = directory
             .as_ref()
@@ -71,30 +190,70 @@ impl Mp3TagApp {
 
         let mut app = Self {
             dir_path,
+            show_folders: !session.hide_folders,
+            folder_tree: FolderTree::new(),
             files: Vec::new(),
             selected_index: None,
+            pending_select,
+            file_sort: session.sort,
+            sort_descending: session.sort_descending,
+            folder_panel_width: session.folder_panel_width.unwrap_or(220.0),
+            file_panel_width: session.file_panel_width.unwrap_or(300.0),
             edit_title: String::new(),
             edit_artist: String::new(),
             edit_album: String::new(),
             edit_album_artist: String::new(),
             edit_track: String::new(),
             edit_year: String::new(),
+            edit_date: String::new(),
+            edit_grouping: String::new(),
+            edit_work: String::new(),
+            edit_movement: String::new(),
+            edit_movement_number: String::new(),
             edit_genre: String::new(),
-            search_source: SearchSource::Spotify,
+            presets: cfg.presets.clone(),
+            search_source,
+            source_choices: SourceKind::all(&cfg),
+            search_all: session.search_all,
+            search_id: 0,
             search_query: String::new(),
             search_results: Vec::new(),
             selected_result: None,
+            last_search: None,
+            search_page: Page::first(),
+            search_has_more: false,
+            result_groups: Vec::new(),
+            album_preference: cfg.matching.album_preference(),
+            tab: Tab::Edit,
+            review: None,
+            review_index: 0,
+            staged: None,
             album_art_texture: None,
+            album_art_info: None,
+            art_config: cfg.art.clone(),
             result_art_textures: Vec::new(),
+            art_dialog_open: false,
+            art_query: String::new(),
+            art_candidates: Vec::new(),
+            art_textures: Vec::new(),
+            art_whole_album: true,
+            paste_handled: false,
+            setup: (!config::config_exists()).then(|| Setup::from_config(&cfg)),
+            setup_error: String::new(),
             tx,
             rx,
             is_loading: false,
             status_msg: String::new(),
         };
 
-        if directory.is_some() {
+        if paths.len() > 1 {
+            app.start_open(paths);
+        } else if directory.is_some() {
             app.start_scan();
         }
+        if let Some(e) = config_error {
+            app.status_msg = e;
+        }
 
         app
     }
@@ -157,14 +316,61 @@ impl Mp3TagApp {
         ctx.set_fonts(fonts);
     }
 
+    /// 세션 상태(창 크기, 디렉토리, 소스, 정렬, 패널 너비)를 config.toml의 `[gui]`에 저장한다.
+    fn save_session(&self, ctx: &egui::Context) {
+        let last_dir = (!self.dir_path.trim().is_empty()).then(|| {
+            std::fs::canonicalize(&self.dir_path).unwrap_or_else(|_| PathBuf::from(&self.dir_path))
+        });
+        let session = GuiConfig {
+            window_size: ctx
+                .input(|i| i.viewport().inner_rect)
+                .map(|rect| [rect.width(), rect.height()]),
+            last_dir,
+            source: Some(self.search_source.name().to_string()),
+            search_all: self.search_all,
+            sort: self.file_sort,
+            sort_descending: self.sort_descending,
+            hide_folders: !self.show_folders,
+            folder_panel_width: Some(self.folder_panel_width),
+            file_panel_width: Some(self.file_panel_width),
+        };
+        if let Err(e) = config::update_config(|c| c.gui = session) {
+            eprintln!("GUI 상태를 저장하지 못했습니다: {}", e);
+        }
+    }
+
+    /// 파일 목록을 현재 정렬 기준으로 정렬한다. 내림차순이어도 값이 빈 파일은 뒤에 둔다.
+    /// 선택된 파일은 그대로 선택해 둔다.
+    fn sort_files(&mut self) {
+        let selected = self
+            .selected_index
+            .and_then(|i| self.files.get(i))
+            .map(|f| f.path.clone());
+        let sort = self.file_sort;
+        self.files.sort_by_cached_key(|f| sort_key(f, sort));
+        if self.sort_descending {
+            let filled = self
+                .files
+                .iter()
+                .position(|f| sort_key(f, sort).0)
+                .unwrap_or(self.files.len());
+            self.files[..filled].reverse();
+        }
+        self.selected_index =
+            selected.and_then(|path| self.files.iter().position(|f| f.path == path));
+    }
+
     /// 백그라운드 스레드에서 디렉토리 스캔을 시작한다.
     fn start_scan(&mut self) {
         let dir = PathBuf::from(&self.dir_path);
         let tx = self.tx.clone();
         self.is_loading = true;
         self.status_msg = "스캔 중...".to_string();
+        self.review = ReviewQueue::load(&dir).ok();
+        self.review_index = 0;
+        self.load_staged(&dir);
 
-        std::thread::spawn(move || match scanner::scan_directory(&dir) {
+        std::thread::spawn(move || match scan_directory(&dir, &tx) {
             Ok(files) => {
                 let _ = tx.send(BgResult::ScanDone(files));
             }
@@ -174,60 +380,188 @@ impl Mp3TagApp {
         });
     }
 
-    /// 백그라운드 스레드에서 검색을 시작한다.
+    /// 주어진 파일들만 백그라운드에서 읽어 목록에 올린다. 디렉토리는 그 안의 MP3를 모두 넣는다.
+    /// MP3가 아니거나 읽을 수 없는 경로는 건너뛰고, 하나도 열지 못하면 에러로 보고한다.
+    fn start_open(&mut self, paths: Vec<PathBuf>) {
+        let tx = self.tx.clone();
+        self.is_loading = true;
+        self.status_msg = "파일 여는 중...".to_string();
+        self.review = ReviewQueue::load(Path::new(&self.dir_path)).ok();
+        self.review_index = 0;
+        self.load_staged(&PathBuf::from(&self.dir_path));
+
+        std::thread::spawn(move || {
+            let mut files: Vec<Mp3File> = Vec::new();
+            let mut errors = Vec::new();
+            for path in &paths {
+                match scanner::scan_path(path) {
+                    Ok(found) => {
+                        for file in found {
+                            if !files.iter().any(|f| f.path == file.path) {
+                                files.push(file);
+                            }
+                        }
+                    }
+                    Err(e) => errors.push(e.to_string()),
+                }
+            }
+            let result = if files.is_empty() && !errors.is_empty() {
+                BgResult::Error(format!("열기 실패: {}", errors.join(", ")))
+            } else {
+                BgResult::ScanDone(files)
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    /// 백그라운드 스레드에서 검색을 시작한다. `search_all`이면 모든 소스를 동시에 검색하고
+    /// 응답하는 순서대로 결과를 목록에 더한다.
     fn start_search(&mut self) {
         let query = self.search_query.clone();
+        let kinds = if self.search_all {
+            self.source_choices.clone()
+        } else {
+            vec![self.search_source.clone()]
+        };
+        self.last_search = Some((query.clone(), kinds.clone()));
+        self.search_id += 1;
+        self.search_results.clear();
+        self.result_groups.clear();
+        self.result_art_textures.clear();
+        self.selected_result = None;
+        self.search_page = Page::first();
+        self.status_msg = "검색 중...".to_string();
+        self.spawn_search(query, kinds);
+    }
+
+    /// 마지막 검색의 다음 페이지를 가져와 결과 목록 뒤에 붙인다.
+    /// 검색 번호를 그대로 쓰므로 결과는 `start_search`와 같은 경로로 도착한다.
+    fn start_search_more(&mut self) {
+        let Some((query, kinds)) = self.last_search.clone() else {
+            return;
+        };
+        self.search_page = self.search_page.next();
+        self.status_msg = "결과 더 불러오는 중...".to_string();
+        self.spawn_search(query, kinds);
+    }
+
+    /// `search_page` 페이지를 소스마다 동시에 검색한다.
+    fn spawn_search(&mut self, query: String, kinds: Vec<SourceKind>) {
         let tx = self.tx.clone();
-        let cfg = config::load_config();
-        let source = self.search_source;
+        let cfg = config::load_config().unwrap_or_default();
+        let id = self.search_id;
+        let page = self.search_page;
         self.is_loading = true;
-        self.status_msg = "검색 중...".to_string();
+        self.search_has_more = false;
 
         std::thread::spawn(move || {
-            let result = (|| -> anyhow::Result<Vec<TrackInfo>> {
-                match source {
-                    SearchSource::Spotify => {
-                        let client = SpotifyClient::new(&cfg.spotify)?;
-                        client.search(&query)
-                    }
-                    SearchSource::Melon => {
-                        let client = MelonClient::new()?;
-                        client.search(&query)
+            let mut errors = Vec::new();
+            let mut clients: Vec<Arc<dyn MusicSource>> = Vec::new();
+            let mut labels = Vec::new();
+            for kind in &kinds {
+                match sources::create_source(kind, &cfg) {
+                    Ok(client) => {
+                        clients.push(Arc::from(client));
+                        labels.push(kind.label().to_string());
                     }
+                    Err(e) => errors.push(format!("{}: {}", kind.label(), e)),
                 }
-            })();
+            }
+            let timed_out = sources::race(
+                &clients,
+                cfg.network.search_timeout(),
+                move |client| client.search_page(&query, page),
+                |i, result| {
+                    match result {
+                        Ok(tracks) => {
+                            let _ = tx.send(BgResult::SearchResults(id, tracks));
+                        }
+                        Err(e) => errors.push(format!("{}: {}", labels[i], e)),
+                    }
+                    false
+                },
+            );
+            errors.extend(
+                timed_out
+                    .into_iter()
+                    .map(|i| format!("{}: 응답 시간 초과", labels[i])),
+            );
+            let _ = tx.send(BgResult::SearchFinished(id, errors));
+        });
+    }
 
-            match result {
-                Ok(tracks) => {
-                    let _ = tx.send(BgResult::SearchDone(tracks));
+    /// 미리보기 값 중 현재 편집 필드와 다른 것의 개수.
+    fn preview_changes(&self, preview: &[Option<String>; 8]) -> usize {
+        let current = [
+            &self.edit_title,
+            &self.edit_artist,
+            &self.edit_album,
+            &self.edit_album_artist,
+            &self.edit_track,
+            &self.edit_year,
+            &self.edit_date,
+            &self.edit_genre,
+        ];
+        count_changes(&current, preview)
+    }
+
+    /// 검색 결과 한 줄 (썸네일, 제목/아티스트/앨범, 미리보기 버튼). `edition`이 있으면 앨범 판을 함께 보인다.
+    /// 미리보기 버튼을 눌렀으면 true.
+    fn result_row(&self, ui: &mut egui::Ui, i: usize, edition: Option<AlbumEdition>) -> bool {
+        let result = &self.search_results[i];
+        let mut clicked = false;
+        ui.horizontal(|ui| {
+            // 앨범 아트 썸네일
+            if let Some(Some(texture)) = self.result_art_textures.get(i) {
+                let size = texture.size_vec2();
+                let scale = (48.0 / size.x).min(48.0 / size.y).min(1.0);
+                ui.image(egui::load::SizedTexture::new(texture.id(), size * scale));
+            } else {
+                ui.allocate_space(egui::vec2(48.0, 48.0));
+            }
+
+            ui.vertical(|ui| {
+                let title = result.display_title().to_string() + &variants::marker_suffix(result);
+                ui.label(egui::RichText::new(title).strong());
+                ui.label(format!(
+                    "{} - {}",
+                    result.display_artist(),
+                    result.display_album()
+                ));
+                if let Some(year) = result.year {
+                    ui.label(format!("연도: {}", year));
                 }
-                Err(e) => {
-                    let _ = tx.send(BgResult::Error(format!("검색 실패: {}", e)));
+                if let Some(edition) = edition {
+                    ui.weak(format!("앨범 판: {}", edition.label()));
                 }
-            }
+                if self.search_all {
+                    ui.weak(format!("출처: {}", result.source));
+                }
+            });
+
+            let previewing = self.selected_result == Some(i);
+            clicked = ui.selectable_label(previewing, "미리보기").clicked();
         });
+        clicked
     }
 
     /// 검색 결과의 상세 정보(메타데이터 + 앨범 아트)를 백그라운드에서 가져온다.
     fn fetch_result_detail(&self, index: usize, track: &TrackInfo) {
         let tx = self.tx.clone();
         let track = track.clone();
-        let cfg = config::load_config();
+        let cfg = config::load_config().unwrap_or_default();
 
         std::thread::spawn(move || {
             let result = (|| -> anyhow::Result<TrackInfo> {
-                if track.source == "melon" {
-                    let client = MelonClient::new()?;
-                    client.fetch_detail(&track)
-                } else {
-                    let client = SpotifyClient::new(&cfg.spotify)?;
-                    client.fetch_detail(&track)
-                }
+                let kind = SourceKind::from_source_name(&track.source, &cfg)
+                    .unwrap_or(SourceKind::Spotify);
+                let client = sources::create_source(&kind, &cfg)?;
+                client.fetch_detail(&track)
             })();
 
             match result {
                 Ok(detailed) => {
-                    let _ = tx.send(BgResult::DetailDone(index, detailed));
+                    let _ = tx.send(BgResult::DetailDone(index, Box::new(detailed)));
                 }
                 Err(e) => {
                     let _ = tx.send(BgResult::Error(format!("상세 정보 실패: {}", e)));
@@ -236,17 +570,174 @@ impl Mp3TagApp {
         });
     }
 
+    /// 앨범 아트 검색 창을 열고 편집 중인 앨범으로 검색을 시작한다.
+    fn open_art_search(&mut self) {
+        let artist = if self.edit_album_artist.trim().is_empty() {
+            &self.edit_artist
+        } else {
+            &self.edit_album_artist
+        };
+        self.art_query = format!("{} {}", artist.trim(), self.edit_album.trim())
+            .trim()
+            .to_string();
+        self.art_dialog_open = true;
+        self.start_art_search();
+    }
+
+    /// 모든 소스에서 앨범 아트 후보를 백그라운드로 모은다.
+    /// 선택된 파일에 MusicBrainz 릴리스 ID가 있으면 Cover Art Archive 원본도 후보에 넣는다.
+    fn start_art_search(&mut self) {
+        let query = self.art_query.clone();
+        let release_id = self
+            .selected_index
+            .and_then(|i| self.files.get(i))
+            .and_then(|f| f.current_tags.as_ref())
+            .and_then(|t| t.musicbrainz_album_id.clone());
+        let tx = self.tx.clone();
+        let cfg = config::load_config().unwrap_or_default();
+        let kinds = self.source_choices.clone();
+        self.art_candidates.clear();
+        self.art_textures.clear();
+        self.is_loading = true;
+        self.status_msg = "앨범 아트 검색 중...".to_string();
+
+        std::thread::spawn(move || {
+            let mut first = Vec::new();
+            if let Some(id) = release_id.filter(|_| !cfg.network.offline) {
+                if let Ok(data) = CoverArtClient::new().and_then(|c| c.fetch_front(&id)) {
+                    first.push(ArtCandidate::new("coverartarchive", query.clone(), data));
+                }
+            }
+            let clients: Vec<_> = kinds
+                .iter()
+                .filter_map(|kind| sources::create_source(kind, &cfg).ok())
+                .collect();
+            let candidates = art_search::search_art(&clients, &query, first);
+            let _ = tx.send(BgResult::ArtDone(candidates));
+        });
+    }
+
+    /// 고른 앨범 아트만 기록한다. 텍스트 태그는 건드리지 않는다.
+    /// `art_whole_album`이면 선택된 파일과 같은 폴더, 같은 앨범 태그의 파일 전체에 기록한다.
+    fn apply_art(&mut self, index: usize) {
+        let (Some(selected), Some(candidate)) =
+            (self.selected_index, self.art_candidates.get(index))
+        else {
+            return;
+        };
+        let data = candidate.data.clone();
+        let album_key = |file: &Mp3File| {
+            (
+                file.path.parent().map(Path::to_path_buf),
+                file.current_tags.as_ref().and_then(|t| t.album.clone()),
+            )
+        };
+        let key = album_key(&self.files[selected]);
+        let whole_album = self.art_whole_album && key.1.is_some();
+        let targets: Vec<usize> = (0..self.files.len())
+            .filter(|&i| i == selected || (whole_album && album_key(&self.files[i]) == key))
+            .collect();
+        self.write_album_art(targets, data);
+    }
+
+    /// Ctrl+V로 클립보드 이미지를 붙여 넣는다. 입력란에 포커스가 있으면 텍스트 붙여넣기로 둔다.
+    /// egui는 클립보드에 텍스트가 있을 때만 Paste 이벤트를 보내고 V 키 입력은 삼키므로,
+    /// 이미지만 있는 경우를 위해 V 키를 뗄 때도 확인한다. 한 번 누른 것을 두 번 붙이지 않도록 표시해 둔다.
+    fn handle_paste_shortcut(&mut self, ctx: &egui::Context) {
+        if self.selected_index.is_none() || ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        let mut paste = false;
+        ctx.input(|i| {
+            for event in &i.events {
+                match event {
+                    egui::Event::Paste(_) => {
+                        paste = true;
+                        self.paste_handled = true;
+                    }
+                    egui::Event::Key {
+                        key: Key::V,
+                        pressed: false,
+                        modifiers,
+                        ..
+                    } => {
+                        let handled = std::mem::take(&mut self.paste_handled);
+                        paste |= !handled && modifiers.command;
+                    }
+                    _ => {}
+                }
+            }
+        });
+        if paste {
+            self.paste_album_art();
+            self.load_album_art_texture(ctx);
+        }
+    }
+
+    /// 클립보드의 이미지를 선택된 파일의 앨범 아트로 기록한다.
+    fn paste_album_art(&mut self) {
+        let Some(selected) = self.selected_index else {
+            return;
+        };
+        match clipboard::paste_image() {
+            Ok(data) => self.write_album_art(vec![selected], data),
+            Err(e) => self.status_msg = format!("붙여넣기 실패: {:#}", e),
+        }
+    }
+
+    /// 파일들에 앨범 아트만 기록한다. 텍스트 태그는 건드리지 않는다.
+    fn write_album_art(&mut self, targets: Vec<usize>, data: Vec<u8>) {
+        let cfg = config::load_config().unwrap_or_default();
+        let info = TrackInfo {
+            album_art: Some(data.clone()),
+            ..Default::default()
+        };
+        let report = tagger::write_batch(
+            targets
+                .iter()
+                .map(|&i| (self.files[i].path.as_path(), &info)),
+            &cfg.write,
+        );
+        for file in &mut self.files {
+            if report.written.contains(&file.path) {
+                file.current_tags
+                    .get_or_insert_with(Default::default)
+                    .album_art = Some(data.clone());
+                file.has_tags = true;
+            }
+        }
+        self.status_msg = match report.failed.first() {
+            None => format!("앨범 아트를 파일 {}개에 기록했습니다", report.written.len()),
+            Some((path, e)) => format!(
+                "앨범 아트 {} (첫 실패: {}: {:#})",
+                report.summary(),
+                path.display(),
+                e
+            ),
+        };
+    }
+
     /// 선택된 파일의 태그 정보를 편집 필드에 로드한다.
     fn load_edit_fields(&mut self) {
         if let Some(idx) = self.selected_index {
+            let tags = self.shown_tags(idx);
             if let Some(file) = self.files.get(idx) {
-                if let Some(ref tags) = file.current_tags {
+                if let Some(ref tags) = tags {
                     self.edit_title = tags.title.clone().unwrap_or_default();
-                    self.edit_artist = tags.artist.clone().unwrap_or_default();
+                    self.edit_artist = tags.artist().unwrap_or_default();
                     self.edit_album = tags.album.clone().unwrap_or_default();
                     self.edit_album_artist = tags.album_artist.clone().unwrap_or_default();
                     self.edit_track = tags.track_number.map(|n| n.to_string()).unwrap_or_default();
                     self.edit_year = tags.year.map(|y| y.to_string()).unwrap_or_default();
+                    self.edit_date = tags.release_date.clone().unwrap_or_default();
+                    self.edit_grouping = tags.grouping.clone().unwrap_or_default();
+                    self.edit_work = tags.work.clone().unwrap_or_default();
+                    self.edit_movement = tags.movement.clone().unwrap_or_default();
+                    self.edit_movement_number = match (tags.movement_number, tags.movement_total) {
+                        (Some(n), Some(total)) => format!("{}/{}", n, total),
+                        (Some(n), None) => n.to_string(),
+                        _ => String::new(),
+                    };
                     self.edit_genre = tags.genre.clone().unwrap_or_default();
 
                     // 현재 태그로 검색 쿼리 생성
@@ -259,12 +750,17 @@ impl Mp3TagApp {
                 // 태그 없음 — 파일명에서 검색 쿼리 파싱
                 let parsed = parser::parse_filename(&file.path);
                 self.search_query = parser::build_search_query(&parsed);
+                self.edit_artist = parsed.artist().unwrap_or_default();
                 self.edit_title = parsed.title.unwrap_or_default();
-                self.edit_artist = parsed.artist.unwrap_or_default();
                 self.edit_album.clear();
                 self.edit_album_artist.clear();
                 self.edit_track.clear();
                 self.edit_year.clear();
+                self.edit_date.clear();
+                self.edit_grouping.clear();
+                self.edit_work.clear();
+                self.edit_movement.clear();
+                self.edit_movement_number.clear();
                 self.edit_genre.clear();
                 return;
             }
@@ -280,37 +776,97 @@ impl Mp3TagApp {
         self.edit_album_artist.clear();
         self.edit_track.clear();
         self.edit_year.clear();
+        self.edit_date.clear();
+        self.edit_grouping.clear();
+        self.edit_work.clear();
+        self.edit_movement.clear();
+        self.edit_movement_number.clear();
         self.edit_genre.clear();
         self.search_query.clear();
     }
 
     /// 편집 필드의 내용을 선택된 파일에 ID3 태그로 저장한다.
+    /// 저장 대기 중인 변경(앨범 아트 등)도 함께 기록한다.
     fn save_current_tags(&mut self) {
         let Some(idx) = self.selected_index else {
             return;
         };
+        let release_date = match self.edit_date.trim() {
+            "" => None,
+            date => match parser::normalize_release_date(date) {
+                Some(date) => Some(date),
+                None => {
+                    self.status_msg =
+                        format!("발매일 형식이 올바르지 않습니다: {} (예: 2019-11-18)", date);
+                    return;
+                }
+            },
+        };
+        let movement = self.edit_movement_number.trim();
+        let mut parts = movement.splitn(2, '/').map(|n| n.trim().parse::<u32>());
+        let (movement_number, movement_total) = match (parts.next(), parts.next()) {
+            _ if movement.is_empty() => (None, None),
+            (Some(Ok(n)), None) => (Some(n), None),
+            (Some(Ok(n)), Some(Ok(total))) => (Some(n), Some(total)),
+            _ => {
+                self.status_msg =
+                    format!("악장 번호 형식이 올바르지 않습니다: {} (예: 1/4)", movement);
+                return;
+            }
+        };
+        let staged = self.staged_tags(idx).cloned();
         let Some(file) = self.files.get_mut(idx) else {
             return;
         };
 
         let info = TrackInfo {
             title: non_empty(&self.edit_title),
-            artist: non_empty(&self.edit_artist),
+            artists: split_artists(&self.edit_artist),
             album: non_empty(&self.edit_album),
             album_artist: non_empty(&self.edit_album_artist),
             track_number: self.edit_track.parse().ok(),
-            year: self.edit_year.parse().ok(),
+            year: self
+                .edit_year
+                .parse()
+                .ok()
+                .or_else(|| release_date.as_deref().and_then(|d| d[..4].parse().ok())),
+            release_date,
             genre: non_empty(&self.edit_genre),
+            grouping: non_empty(&self.edit_grouping),
+            work: non_empty(&self.edit_work),
+            movement: non_empty(&self.edit_movement),
+            movement_number,
+            movement_total,
             album_art: file.current_tags.as_ref().and_then(|t| t.album_art.clone()),
-            album_art_url: None,
             source: "manual".to_string(),
+            ..Default::default()
+        };
+        // 편집 필드에 없는 필드(음반사, 작곡가 등)와 앨범 아트는 저장 대기 값을 함께 기록한다
+        let info = match staged {
+            Some(staged) => {
+                let mut info =
+                    tagger::merge_tags(&Some(staged.clone()), &info, MergePolicy::Overwrite);
+                if staged.album_art.is_some() {
+                    info.album_art = staged.album_art;
+                }
+                info
+            }
+            None => info,
         };
 
-        match tagger::write_tags(&file.path, &info) {
+        let cfg = config::load_config().unwrap_or_default();
+
+        match tagger::write_tags(&file.path, &info, &cfg.write) {
             Ok(_) => {
+                let path = file.path.clone();
                 file.current_tags = Some(info);
                 file.has_tags = true;
                 self.status_msg = "태그가 저장되었습니다!".to_string();
+                if let Some(staged) = self.staged.as_mut() {
+                    if staged.discard(&path) {
+                        self.save_staged();
+                    }
+                }
             }
             Err(e) => {
                 self.status_msg = format!("저장 실패: {}", e);
@@ -318,6 +874,64 @@ impl Mp3TagApp {
         }
     }
 
+    /// 프리셋 값으로 편집 필드를 채운다. 편집 필드에 없는 음반사와 앨범 아트는
+    /// 저장 대기에 쌓아 '저장'할 때 함께 기록한다.
+    fn apply_preset(&mut self, name: &str) {
+        let (Some(idx), Some(preset)) = (self.selected_index, self.presets.get(name)) else {
+            return;
+        };
+        let info = match preset.track_info() {
+            Ok(info) => info,
+            Err(e) => {
+                self.status_msg = format!("프리셋 적용 실패: {:#}", e);
+                return;
+            }
+        };
+        let fill = |field: &mut String, value: &Option<String>| {
+            if let Some(value) = value {
+                *field = value.clone();
+            }
+        };
+        fill(&mut self.edit_artist, &info.artist());
+        fill(&mut self.edit_album, &info.album);
+        fill(&mut self.edit_album_artist, &info.album_artist);
+        fill(&mut self.edit_genre, &info.genre);
+        fill(&mut self.edit_grouping, &info.grouping);
+        if let Some(year) = info.year {
+            self.edit_year = year.to_string();
+            // 프리셋 연도와 맞지 않는 발매일은 비운다
+            if !self.edit_date.starts_with(&format!("{:04}", year)) {
+                self.edit_date.clear();
+            }
+        }
+
+        if info.label.is_some() || info.album_art.is_some() {
+            let Some(staged) = self.staged.as_mut() else {
+                self.status_msg =
+                    "저장 대기 목록을 열지 못해 음반사/앨범 아트를 채우지 않았습니다".to_string();
+                return;
+            };
+            let extra = TrackInfo {
+                label: info.label,
+                album_art: info.album_art,
+                source: "manual".to_string(),
+                ..Default::default()
+            };
+            staged.stage(
+                &self.files[idx].path,
+                extra,
+                &config::load_config().unwrap_or_default().trust,
+            );
+            if !self.save_staged() {
+                return;
+            }
+        }
+        self.status_msg = format!(
+            "프리셋 '{}'을(를) 채웠습니다. '저장'을 눌러 기록하세요",
+            name
+        );
+    }
+
     /// 선택된 파일의 이름을 "{아티스트} - {제목}.mp3" 형식으로 변경한다.
     fn rename_current_file(&mut self) {
         let Some(idx) = self.selected_index else {
@@ -330,15 +944,19 @@ impl Mp3TagApp {
             self.status_msg = "태그 정보가 없어 파일명을 변경할 수 없습니다".to_string();
             return;
         };
+        let cfg = config::load_config().unwrap_or_default();
 
-        match renamer::rename_file(&file.path, tags) {
-            Ok(new_path) => {
-                if new_path == file.path {
-                    self.status_msg = "파일명이 이미 동일합니다".to_string();
-                } else {
-                    self.status_msg = format!("파일명 변경: {}", new_path.display());
-                    file.path = new_path;
-                }
+        match renamer::rename_file(&file.path, tags, &cfg.rename) {
+            Ok(RenameOutcome::Renamed(new_path)) => {
+                self.status_msg = format!("파일명 변경: {}", new_path.display());
+                file.path = new_path;
+            }
+            Ok(RenameOutcome::Unchanged) => {
+                self.status_msg = "파일명이 이미 동일합니다".to_string();
+            }
+            Ok(RenameOutcome::Skipped(target)) | Ok(RenameOutcome::Conflict(target)) => {
+                self.status_msg =
+                    format!("파일이 이미 존재하여 건너뛰었습니다: {}", target.display());
             }
             Err(e) => {
                 self.status_msg = format!("파일명 변경 실패: {}", e);
@@ -351,25 +969,25 @@ impl Mp3TagApp {
         let mut success = 0;
         let mut failed = 0;
         let mut skipped = 0;
+        let cfg = config::load_config().unwrap_or_default();
 
         for file in &mut self.files {
             let Some(ref tags) = file.current_tags else {
                 skipped += 1;
                 continue;
             };
-            if tags.artist.is_none() || tags.title.is_none() {
+            if tags.artists.is_empty() || tags.title.is_none() {
                 skipped += 1;
                 continue;
             }
 
-            match renamer::rename_file(&file.path, tags) {
-                Ok(new_path) => {
-                    if new_path == file.path {
-                        skipped += 1;
-                    } else {
-                        file.path = new_path;
-                        success += 1;
-                    }
+            match renamer::rename_file(&file.path, tags, &cfg.rename) {
+                Ok(RenameOutcome::Renamed(new_path)) => {
+                    file.path = new_path;
+                    success += 1;
+                }
+                Ok(_) => {
+                    skipped += 1;
                 }
                 Err(_) => {
                     failed += 1;
@@ -383,7 +1001,8 @@ impl Mp3TagApp {
         );
     }
 
-    /// 검색 결과를 선택된 파일에 적용하고 태그를 기록한다.
+    /// 검색 결과를 선택된 파일의 저장 대기 목록에 쌓는다.
+    /// 파일에는 "저장" 또는 "모두 저장"을 누를 때 기록된다.
     fn apply_search_result(&mut self, result_idx: usize) {
         let Some(file_idx) = self.selected_index else {
             return;
@@ -394,35 +1013,627 @@ impl Mp3TagApp {
             None => return,
         };
 
-        self.edit_title = track.title.clone().unwrap_or_default();
-        self.edit_artist = track.artist.clone().unwrap_or_default();
-        self.edit_album = track.album.clone().unwrap_or_default();
-        self.edit_album_artist = track.album_artist.clone().unwrap_or_default();
-        self.edit_track = track
-            .track_number
-            .map(|n| n.to_string())
-            .unwrap_or_default();
-        self.edit_year = track.year.map(|y| y.to_string()).unwrap_or_default();
-        self.edit_genre = track.genre.clone().unwrap_or_default();
+        // 결과에 없는 필드는 기존 값을 유지한다 (파일에도 기록하지 않는다)
+        let fields = [
+            &mut self.edit_title,
+            &mut self.edit_artist,
+            &mut self.edit_album,
+            &mut self.edit_album_artist,
+            &mut self.edit_track,
+            &mut self.edit_year,
+            &mut self.edit_date,
+            &mut self.edit_genre,
+        ];
+        for (field, value) in fields.into_iter().zip(preview_fields(&track)) {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+
+        // 앨범 아트를 포함하여 저장 대기로 쌓는다
+        let Some(path) = self.files.get(file_idx).map(|f| f.path.clone()) else {
+            return;
+        };
+        let source_name = match track.source.as_str() {
+            "melon" => "Melon",
+            "spotify" => "Spotify",
+            _ => &track.source,
+        }
+        .to_string();
+        let Some(staged) = self.staged.as_mut() else {
+            self.status_msg = "저장 대기 목록을 열지 못해 적용하지 않았습니다".to_string();
+            return;
+        };
+        staged.stage(
+            &path,
+            track,
+            &config::load_config().unwrap_or_default().trust,
+        );
+        let count = staged.len();
+        if self.save_staged() {
+            self.status_msg = format!(
+                "{}의 태그를 저장 대기에 추가했습니다 (대기 {}건). '저장'을 눌러 기록하세요",
+                source_name, count
+            );
+        }
+    }
 
-        // 앨범 아트를 포함하여 태그 기록
-        if let Some(file) = self.files.get_mut(file_idx) {
-            let source_name = match track.source.as_str() {
-                "melon" => "Melon",
-                "spotify" => "Spotify",
-                _ => &track.source,
+    /// 열린 디렉토리의 저장 대기 목록을 읽는다. 읽지 못하면 상태 메시지로 알린다.
+    fn load_staged(&mut self, dir: &Path) {
+        match StagedChanges::load(dir) {
+            Ok(staged) => self.staged = Some(staged),
+            Err(e) => {
+                self.staged = None;
+                self.status_msg = format!("저장 대기 목록을 읽을 수 없습니다: {:#}", e);
             }
-            .to_string();
-            match tagger::write_tags(&file.path, &track) {
+        }
+    }
+
+    /// 저장 대기 목록을 디스크에 남긴다. 실패하면 상태 메시지로 알리고 false.
+    fn save_staged(&mut self) -> bool {
+        let Some(staged) = self.staged.as_ref() else {
+            return true;
+        };
+        match staged.save() {
+            Ok(()) => true,
+            Err(e) => {
+                self.status_msg = format!("저장 대기 목록 저장 실패: {:#}", e);
+                false
+            }
+        }
+    }
+
+    /// 파일에 쌓인 저장 대기 변경.
+    fn staged_tags(&self, idx: usize) -> Option<&TrackInfo> {
+        let file = self.files.get(idx)?;
+        self.staged.as_ref()?.get(&file.path)
+    }
+
+    fn staged_count(&self) -> usize {
+        self.staged.as_ref().map_or(0, StagedChanges::len)
+    }
+
+    /// 편집기에 보여 줄 태그. 저장 대기 중인 변경이 있으면 현재 태그 위에 덮는다.
+    fn shown_tags(&self, idx: usize) -> Option<TrackInfo> {
+        let file = self.files.get(idx)?;
+        match self.staged_tags(idx) {
+            Some(staged) => Some(tagger::merge_tags(
+                &file.current_tags,
+                staged,
+                MergePolicy::Overwrite,
+            )),
+            None => file.current_tags.clone(),
+        }
+    }
+
+    /// 선택된 파일의 저장 대기 변경을 버리고 편집 필드를 현재 태그로 되돌린다.
+    fn discard_current(&mut self) {
+        let Some(path) = self
+            .selected_index
+            .and_then(|i| self.files.get(i))
+            .map(|f| f.path.clone())
+        else {
+            return;
+        };
+        if self.staged.as_mut().is_some_and(|s| s.discard(&path)) && self.save_staged() {
+            self.status_msg = "저장 대기 변경을 취소했습니다".to_string();
+        }
+        self.load_edit_fields();
+    }
+
+    /// 저장 대기 중인 변경을 모두 파일에 기록한다. 실패한 변경은 목록에 남긴다.
+    fn save_all_staged(&mut self) {
+        let Some(staged) = self.staged.as_mut() else {
+            return;
+        };
+        let cfg = config::load_config().unwrap_or_default();
+        let results = staged.commit_all(&cfg.write);
+        let mut saved = 0;
+        let mut errors = Vec::new();
+        for (path, result) in results {
+            match result {
+                Ok(info) => {
+                    if let Some(file) = self.files.iter_mut().find(|f| f.path == path) {
+                        file.current_tags = Some(tagger::merge_tags(
+                            &file.current_tags,
+                            &info,
+                            MergePolicy::Overwrite,
+                        ));
+                        file.has_tags = true;
+                    }
+                    saved += 1;
+                }
+                Err(e) => errors.push(format!("{}: {:#}", path.display(), e)),
+            }
+        }
+        if self.save_staged() {
+            self.status_msg = if errors.is_empty() {
+                format!("저장 대기 변경 {}건을 기록했습니다", saved)
+            } else {
+                format!(
+                    "기록 {}건, 실패 {}건 ({})",
+                    saved,
+                    errors.len(),
+                    errors.join(", ")
+                )
+            };
+        }
+    }
+
+    /// 저장 대기 중인 변경을 모두 버린다.
+    fn discard_all_staged(&mut self) {
+        let Some(staged) = self.staged.as_mut() else {
+            return;
+        };
+        let count = staged.len();
+        staged.discard_all();
+        if self.save_staged() {
+            self.status_msg = format!("저장 대기 변경 {}건을 취소했습니다", count);
+        }
+        self.load_edit_fields();
+    }
+
+    fn review_count(&self) -> usize {
+        self.review.as_ref().map_or(0, |q| q.items.len())
+    }
+
+    /// 검토 중인 파일에 후보(None이면 추론 값)를 적용한다.
+    /// 후보의 상세 정보와 앨범 아트는 백그라운드에서 가져와 기존 태그 위에 덮어쓴다.
+    fn accept_review(&mut self, candidate: Option<usize>) {
+        let Some(item) = self
+            .review
+            .as_ref()
+            .and_then(|q| q.items.get(self.review_index))
+            .cloned()
+        else {
+            return;
+        };
+        let track = match candidate {
+            Some(i) => match item.candidates.get(i) {
+                Some(c) => Some(c.track.clone()),
+                None => return,
+            },
+            None if item.can_infer => None,
+            None => return,
+        };
+        let tx = self.tx.clone();
+        self.is_loading = true;
+        self.status_msg = "적용 중...".to_string();
+
+        std::thread::spawn(move || {
+            let cfg = config::load_config().unwrap_or_default();
+            let info = match track {
+                Some(track) => {
+                    let detailed = SourceKind::from_source_name(&track.source, &cfg)
+                        .and_then(|kind| sources::create_source(&kind, &cfg).ok())
+                        .and_then(|client| client.fetch_detail(&track).ok())
+                        .unwrap_or(track);
+                    tagger::merge_trusted(&Some(item.local), &detailed, &cfg.trust)
+                }
+                None => item.local,
+            };
+            match tagger::write_tags(&item.path, &info, &cfg.write) {
                 Ok(_) => {
-                    file.current_tags = Some(track);
-                    file.has_tags = true;
-                    self.status_msg = format!("{}에서 태그가 적용되었습니다!", source_name);
+                    let _ = tx.send(BgResult::ReviewApplied(item.path, Box::new(info)));
                 }
                 Err(e) => {
-                    self.status_msg = format!("적용 실패: {}", e);
+                    let _ = tx.send(BgResult::Error(format!("적용 실패: {}", e)));
+                }
+            }
+        });
+    }
+
+    /// 검토 중인 파일을 적용하지 않고 넘긴다.
+    fn reject_review(&mut self) {
+        let Some(path) = self
+            .review
+            .as_ref()
+            .and_then(|q| q.items.get(self.review_index))
+            .map(|item| item.path.clone())
+        else {
+            return;
+        };
+        self.status_msg = format!("건너뛰었습니다: {}", path.display());
+        self.finish_review(&path);
+    }
+
+    /// 검토를 마친 파일을 목록에서 빼고, 다음 `auto` 실행에서 다시 묻지 않도록 처리 완료로 표시한다.
+    /// 목록에서 빠지면 다음 파일이 같은 자리로 올라온다.
+    fn finish_review(&mut self, path: &Path) {
+        let Some(queue) = self.review.as_mut() else {
+            return;
+        };
+        queue.remove(path);
+        self.review_index = self.review_index.min(queue.items.len().saturating_sub(1));
+        let result = queue.save().and_then(|_| {
+            FetchState::load(Path::new(&self.dir_path), AUTO_STATE_FILE)?.mark_done(path)
+        });
+        if let Err(e) = result {
+            self.status_msg = format!("검토 목록 저장 실패: {}", e);
+        }
+    }
+
+    /// 검토 탭. 왼쪽에 검토 대기 파일, 가운데에 현재 태그와 후보를 나란히 보여 준다.
+    /// 단축키: ↑/↓(J/K) 이동, 1~5 후보 적용, I 추론 값 적용, R 건너뛰기.
+    fn show_review(&mut self, ctx: &egui::Context) {
+        let count = self.review_count();
+        let busy = self.is_loading;
+        let mut action = None;
+
+        // 입력란에 포커스가 있으면 단축키로 보지 않는다
+        if count > 0 && ctx.memory(|m| m.focused().is_none()) {
+            ctx.input(|i| {
+                let number_keys = [Key::Num1, Key::Num2, Key::Num3, Key::Num4, Key::Num5];
+                if i.key_pressed(Key::ArrowDown) || i.key_pressed(Key::J) {
+                    action = Some(ReviewAction::Move(1));
+                } else if i.key_pressed(Key::ArrowUp) || i.key_pressed(Key::K) {
+                    action = Some(ReviewAction::Move(-1));
+                } else if busy {
+                } else if let Some(n) = number_keys.iter().position(|&k| i.key_pressed(k)) {
+                    action = Some(ReviewAction::Accept(Some(n)));
+                } else if i.key_pressed(Key::I) {
+                    action = Some(ReviewAction::Accept(None));
+                } else if i.key_pressed(Key::R) || i.key_pressed(Key::Delete) {
+                    action = Some(ReviewAction::Reject);
+                }
+            });
+        }
+        let moved = matches!(action, Some(ReviewAction::Move(_)));
+
+        egui::SidePanel::left("review_panel")
+            .default_width(300.0)
+            .show(ctx, |ui| {
+                ui.heading(format!("검토 대기 {}개", count));
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let Some(ref queue) = self.review else {
+                        return;
+                    };
+                    for (i, item) in queue.items.iter().enumerate() {
+                        let name = item
+                            .path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        let best = item.candidates.first().map_or(0.0, |c| c.score);
+                        let response = ui.selectable_label(
+                            self.review_index == i,
+                            format!("{:.2}  {}", best, name),
+                        );
+                        if response.clicked() {
+                            action = Some(ReviewAction::Select(i));
+                        }
+                        if moved && self.review_index == i {
+                            response.scroll_to_me(None);
+                        }
+                    }
+                });
+            });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let Some(item) = self
+                .review
+                .as_ref()
+                .and_then(|q| q.items.get(self.review_index))
+            else {
+                ui.centered_and_justified(|ui| {
+                    ui.label("검토할 파일이 없습니다. 'mp3tag auto'가 점수가 애매한 결과를 여기에 남깁니다.");
+                });
+                return;
+            };
+
+            ui.heading(item.path.display().to_string());
+            ui.label("↑/↓ 이동 · 1~5 후보 적용 · I 추론 값 적용 · R 건너뛰기");
+            ui.separator();
+
+            let rows: [(&str, FieldText); 8] = [
+                ("제목", |t| t.title.clone().unwrap_or_default()),
+                ("아티스트", |t| t.artist().unwrap_or_default()),
+                ("앨범", |t| t.album.clone().unwrap_or_default()),
+                ("앨범 아티스트", |t| {
+                    t.album_artist.clone().unwrap_or_default()
+                }),
+                ("트랙 번호", |t| {
+                    t.track_number.map(|n| n.to_string()).unwrap_or_default()
+                }),
+                ("연도", |t| t.year.map(|y| y.to_string()).unwrap_or_default()),
+                ("장르", |t| t.genre.clone().unwrap_or_default()),
+                ("재생 시간", |t| {
+                    t.duration_ms
+                        .map(|ms| format!("{}:{:02}", ms / 60_000, ms / 1000 % 60))
+                        .unwrap_or_default()
+                }),
+            ];
+
+            egui::ScrollArea::both().show(ui, |ui| {
+                egui::Grid::new("review_grid")
+                    .striped(true)
+                    .spacing([16.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.label(egui::RichText::new("현재 (추론 포함)").strong());
+                        for (i, c) in item.candidates.iter().enumerate() {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{}. {} · 점수 {:.2}{}",
+                                    i + 1,
+                                    c.track.source,
+                                    c.score,
+                                    variants::marker_suffix(&c.track)
+                                ))
+                                .strong(),
+                            );
+                        }
+                        ui.end_row();
+
+                        for (label, value) in rows {
+                            ui.label(label);
+                            let local = value(&item.local);
+                            ui.label(&local);
+                            for c in &item.candidates {
+                                let v = value(&c.track);
+                                // 현재 값과 다른 칸은 눈에 띄게 표시한다
+                                let text = egui::RichText::new(&v);
+                                ui.label(if !v.is_empty() && v != local {
+                                    text.color(ui.visuals().warn_fg_color)
+                                } else {
+                                    text
+                                });
+                            }
+                            ui.end_row();
+                        }
+
+                        if item.candidates.iter().any(|c| c.note.is_some()) {
+                            ui.label("안내");
+                            ui.label("");
+                            for c in &item.candidates {
+                                ui.label(c.note.as_deref().unwrap_or(""));
+                            }
+                            ui.end_row();
+                        }
+
+                        ui.label("");
+                        if item.can_infer {
+                            if ui
+                                .add_enabled(!busy, egui::Button::new("추론 값 적용 (I)"))
+                                .clicked()
+                            {
+                                action = Some(ReviewAction::Accept(None));
+                            }
+                        } else {
+                            ui.label("");
+                        }
+                        for i in 0..item.candidates.len() {
+                            if ui
+                                .add_enabled(!busy, egui::Button::new(format!("적용 ({})", i + 1)))
+                                .clicked()
+                            {
+                                action = Some(ReviewAction::Accept(Some(i)));
+                            }
+                        }
+                        ui.end_row();
+                    });
+            });
+
+            ui.separator();
+            if ui
+                .add_enabled(!busy, egui::Button::new("건너뛰기 (R)"))
+                .clicked()
+            {
+                action = Some(ReviewAction::Reject);
+            }
+        });
+
+        match action {
+            Some(ReviewAction::Select(i)) => self.review_index = i,
+            Some(ReviewAction::Move(step)) => {
+                self.review_index = self
+                    .review_index
+                    .saturating_add_signed(step)
+                    .min(count.saturating_sub(1));
+            }
+            Some(ReviewAction::Accept(candidate)) => self.accept_review(candidate),
+            Some(ReviewAction::Reject) => self.reject_review(),
+            None => {}
+        }
+    }
+
+    /// 처음 실행 설정 창. 기본 검색 소스, 자격증명(선택), 파일명 템플릿, 음악 디렉토리를 고른다.
+    /// 저장하거나 건너뛰면 설정 파일이 생겨 다음부터는 열리지 않는다.
+    fn show_setup_dialog(&mut self, ctx: &egui::Context) {
+        let Some(setup) = self.setup.as_mut() else {
+            return;
+        };
+        let mut save = false;
+        let mut skip = false;
+
+        egui::Window::new("처음 설정")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("나중에 'mp3tag config setup' 또는 config.toml에서 바꿀 수 있습니다.");
+                ui.separator();
+                egui::Grid::new("setup_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label("기본 검색 소스");
+                        let current = self
+                            .source_choices
+                            .iter()
+                            .find(|k| k.name() == setup.default_source)
+                            .map_or(setup.default_source.clone(), |k| k.label().to_string());
+                        egui::ComboBox::from_id_salt("setup_source")
+                            .selected_text(current)
+                            .show_ui(ui, |ui| {
+                                for kind in &self.source_choices {
+                                    ui.selectable_value(
+                                        &mut setup.default_source,
+                                        kind.name().to_string(),
+                                        kind.label(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Spotify Client ID");
+                        ui.text_edit_singleline(&mut setup.client_id);
+                        ui.end_row();
+                        ui.label("Spotify Client Secret");
+                        ui.add(egui::TextEdit::singleline(&mut setup.client_secret).password(true));
+                        ui.end_row();
+                        ui.label("Genius Access Token");
+                        ui.add(egui::TextEdit::singleline(&mut setup.genius_token).password(true));
+                        ui.end_row();
+
+                        ui.label("파일명 형식");
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut setup.file_template);
+                            egui::ComboBox::from_id_salt("setup_template")
+                                .selected_text("예시")
+                                .show_ui(ui, |ui| {
+                                    for template in config::TEMPLATE_CHOICES {
+                                        if ui.selectable_label(false, *template).clicked() {
+                                            setup.file_template = template.to_string();
+                                        }
+                                    }
+                                });
+                        });
+                        ui.end_row();
+
+                        ui.label("음악 디렉토리");
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut setup.music_dir);
+                            if ui.button("찾아보기").clicked() {
+                                if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                                    setup.music_dir = folder.display().to_string();
+                                }
+                            }
+                        });
+                        ui.end_row();
+                    });
+                ui.label("자격증명은 모두 선택 사항입니다. Melon은 자격증명 없이 쓸 수 있습니다.");
+
+                if !self.setup_error.is_empty() {
+                    ui.colored_label(egui::Color32::RED, &self.setup_error);
                 }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    save = ui.button("저장").clicked();
+                    skip = ui.button("건너뛰기").clicked();
+                });
+            });
+
+        if save {
+            let music_dir = setup.music_dir.trim().to_string();
+            let checked = renamer::check_template(&setup.file_template).and_then(|()| {
+                if !music_dir.is_empty() && !Path::new(&music_dir).is_dir() {
+                    anyhow::bail!("음악 디렉토리가 없습니다: {}", music_dir);
+                }
+                Ok(())
+            });
+            if let Err(e) = checked {
+                self.setup_error = e.to_string();
+                return;
             }
+            let setup = setup.clone();
+            if let Err(e) = config::update_config(|c| setup.apply(c)) {
+                self.setup_error = format!("설정 저장 실패: {}", e);
+                return;
+            }
+            let cfg = config::load_config().unwrap_or_default();
+            self.source_choices = SourceKind::all(&cfg);
+            if let Some(kind) = cfg
+                .default_source
+                .clone()
+                .and_then(|name| SourceKind::from_source_name(&name, &cfg))
+            {
+                self.search_source = kind;
+            }
+            self.setup = None;
+            self.status_msg = "설정을 저장했습니다".to_string();
+            if self.dir_path.is_empty() && !music_dir.is_empty() {
+                self.dir_path = music_dir;
+                self.start_scan();
+            }
+        } else if skip {
+            if let Err(e) = config::update_config(|_| {}) {
+                self.setup_error = format!("설정 저장 실패: {}", e);
+                return;
+            }
+            self.setup = None;
+        }
+    }
+
+    /// 앨범 아트 검색 창. 후보를 크기 정보와 함께 격자로 보여 주고, 고른 아트만 기록한다.
+    fn show_art_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.art_dialog_open;
+        let mut search = false;
+        let mut apply_idx = None;
+
+        egui::Window::new("앨범 아트 검색")
+            .open(&mut open)
+            .default_size([760.0, 560.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("검색어:");
+                    let response = ui.text_edit_singleline(&mut self.art_query);
+                    if ui.button("검색").clicked()
+                        || (response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)))
+                    {
+                        search = true;
+                    }
+                    ui.checkbox(&mut self.art_whole_album, "같은 앨범의 모든 파일에 적용");
+                });
+                ui.separator();
+
+                if self.art_candidates.is_empty() {
+                    ui.label(if self.is_loading {
+                        "검색 중..."
+                    } else {
+                        "후보가 없습니다"
+                    });
+                    return;
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        for (i, candidate) in self.art_candidates.iter().enumerate() {
+                            ui.group(|ui| {
+                                ui.set_width(180.0);
+                                ui.vertical(|ui| {
+                                    if let Some(Some(texture)) = self.art_textures.get(i) {
+                                        let size = texture.size_vec2();
+                                        let scale = (180.0 / size.x).min(180.0 / size.y);
+                                        ui.image(egui::load::SizedTexture::new(
+                                            texture.id(),
+                                            size * scale,
+                                        ));
+                                    } else {
+                                        ui.allocate_space(egui::vec2(180.0, 180.0));
+                                    }
+                                    ui.label(egui::RichText::new(&candidate.source).strong());
+                                    ui.label(candidate.size_label());
+                                    ui.add(egui::Label::new(&candidate.album).truncate());
+                                    if ui.button("이 아트 적용").clicked() {
+                                        apply_idx = Some(i);
+                                    }
+                                });
+                            });
+                        }
+                    });
+                });
+            });
+
+        self.art_dialog_open = open;
+        if search {
+            self.start_art_search();
+        }
+        if let Some(idx) = apply_idx {
+            self.apply_art(idx);
+            self.load_album_art_texture(ctx);
         }
     }
 
@@ -432,12 +1643,12 @@ impl Mp3TagApp {
 
         let art_data = self
             .selected_index
-            .and_then(|idx| self.files.get(idx))
-            .and_then(|f| f.current_tags.as_ref())
-            .and_then(|t| t.album_art.as_ref());
+            .and_then(|idx| self.shown_tags(idx))
+            .and_then(|t| t.album_art);
+        self.album_art_info = art_data.as_deref().map(ArtInfo::of);
 
         if let Some(data) = art_data {
-            if let Ok(img) = image::load_from_memory(data) {
+            if let Ok(img) = image::load_from_memory(&data) {
                 let rgba = img.to_rgba8();
                 let size = [rgba.width() as usize, rgba.height() as usize];
                 let pixels = rgba.into_raw();
@@ -452,32 +1663,91 @@ impl Mp3TagApp {
     fn process_bg_results(&mut self, ctx: &egui::Context) {
         while let Ok(result) = self.rx.try_recv() {
             match result {
+                BgResult::ScanCached(files) => {
+                    self.files = files;
+                    self.selected_index = None;
+                    self.sort_files();
+                    self.status_msg = format!(
+                        "저장된 목록 {}개를 표시했습니다. 바뀐 파일을 스캔하는 중...",
+                        self.files.len()
+                    );
+                }
                 BgResult::ScanDone(files) => {
+                    // 저장된 목록에서 고른 파일은 스캔 뒤에도 선택을 유지한다
+                    if let Some(file) = self.selected_index.and_then(|i| self.files.get(i)) {
+                        self.pending_select.get_or_insert(file.path.clone());
+                    }
                     self.files = files;
                     self.selected_index = None;
+                    self.sort_files();
+                    // 명령줄로 연 파일을 선택한다. 여러 파일을 열었으면 첫 파일.
+                    if let Some(path) = self.pending_select.take() {
+                        self.selected_index = self
+                            .files
+                            .iter()
+                            .position(|f| f.path == path)
+                            .or((!self.files.is_empty()).then_some(0));
+                        self.load_edit_fields();
+                        self.load_album_art_texture(ctx);
+                    }
                     self.is_loading = false;
                     self.status_msg = format!("MP3 파일 {}개를 찾았습니다", self.files.len());
+                    let waiting = self.review_count();
+                    if waiting > 0 {
+                        self.status_msg
+                            .push_str(&format!(" (검토 대기 {}개)", waiting));
+                    }
                 }
-                BgResult::SearchDone(results) => {
+                BgResult::SearchResults(id, results) => {
+                    if id != self.search_id {
+                        continue;
+                    }
+                    if results.len() >= self.search_page.limit {
+                        self.search_has_more = true;
+                    }
                     // 각 검색 결과의 상세 정보 가져오기
+                    let start = self.search_results.len();
                     for (i, track) in results.iter().enumerate() {
                         if track.album_art_url.is_some() {
-                            self.fetch_result_detail(i, track);
+                            self.fetch_result_detail(start + i, track);
                         }
                     }
-                    self.result_art_textures = vec![None; results.len()];
-                    self.search_results = results;
-                    self.selected_result = None;
+                    self.search_results.extend(results);
+                    self.result_art_textures
+                        .resize(self.search_results.len(), None);
+                    self.result_groups =
+                        variants::group_results(&self.search_results, &self.album_preference);
+                    self.status_msg =
+                        format!("검색 결과 {}건 (검색 중...)", self.search_results.len());
+                }
+                BgResult::SearchFinished(id, errors) => {
+                    if id != self.search_id {
+                        continue;
+                    }
                     self.is_loading = false;
-                    self.status_msg = format!("검색 결과 {}건", self.search_results.len());
+                    self.status_msg = if self.search_results.is_empty() && !errors.is_empty() {
+                        format!("검색 실패: {}", errors.join(", "))
+                    } else if errors.is_empty() {
+                        format!("검색 결과 {}건", self.search_results.len())
+                    } else {
+                        format!(
+                            "검색 결과 {}건 (실패: {})",
+                            self.search_results.len(),
+                            errors.join(", ")
+                        )
+                    };
                 }
                 BgResult::DetailDone(index, detailed) => {
                     // 검색 결과를 상세 정보로 갱신
                     if let Some(track) = self.search_results.get_mut(index) {
-                        *track = detailed;
+                        *track = *detailed;
                     }
                     // 앨범 아트 텍스처 생성
-                    if let Some(ref data) = self.search_results.get(index).and_then(|t| t.album_art.clone()) {
+                    if let Some(ref data) = self
+                        .search_results
+                        .get(index)
+                        .and_then(|t| t.album_art.clone())
+                    {
                         if let Ok(img) = image::load_from_memory(data) {
                             let rgba = img.to_rgba8();
                             let size = [rgba.width() as usize, rgba.height() as usize];
@@ -494,6 +1764,35 @@ impl Mp3TagApp {
                         }
                     }
                 }
+                BgResult::ArtDone(candidates) => {
+                    self.is_loading = false;
+                    self.status_msg = format!("앨범 아트 후보 {}개", candidates.len());
+                    self.art_textures = candidates
+                        .iter()
+                        .enumerate()
+                        .map(|(i, c)| {
+                            let img = image::load_from_memory(&c.data).ok()?.to_rgba8();
+                            let size = [img.width() as usize, img.height() as usize];
+                            let color_image =
+                                ColorImage::from_rgba_unmultiplied(size, &img.into_raw());
+                            Some(ctx.load_texture(
+                                format!("art_candidate_{}", i),
+                                color_image,
+                                Default::default(),
+                            ))
+                        })
+                        .collect();
+                    self.art_candidates = candidates;
+                }
+                BgResult::ReviewApplied(path, info) => {
+                    self.is_loading = false;
+                    self.status_msg = format!("적용했습니다: {}", info.summary());
+                    if let Some(file) = self.files.iter_mut().find(|f| f.path == path) {
+                        file.current_tags = Some(*info);
+                        file.has_tags = true;
+                    }
+                    self.finish_review(&path);
+                }
                 BgResult::Error(msg) => {
                     self.is_loading = false;
                     self.status_msg = msg;
@@ -510,6 +1809,11 @@ impl eframe::App for Mp3TagApp {
         // 상단 패널: 디렉토리 입력
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.tab, Tab::Edit, "태그 편집");
+                let review_label = format!("검토 ({})", self.review_count());
+                ui.selectable_value(&mut self.tab, Tab::Review, review_label);
+                ui.separator();
+                ui.toggle_value(&mut self.show_folders, "폴더");
                 ui.label("디렉토리:");
                 let response = ui.text_edit_singleline(&mut self.dir_path);
                 if ui.button("폴더 열기").clicked() {
@@ -526,6 +1830,19 @@ impl eframe::App for Mp3TagApp {
                 if ui.button("태그 기반으로 모든 파일명 변경").clicked() {
                     self.rename_all_files();
                 }
+                let staged = self.staged_count();
+                if staged > 0 {
+                    ui.separator();
+                    if ui.button(format!("모두 저장 ({})", staged)).clicked() {
+                        self.save_all_staged();
+                        self.load_edit_fields();
+                        self.load_album_art_texture(ctx);
+                    }
+                    if ui.button("모두 취소").clicked() {
+                        self.discard_all_staged();
+                        self.load_album_art_texture(ctx);
+                    }
+                }
                 if self.is_loading {
                     ui.spinner();
                 }
@@ -533,17 +1850,72 @@ impl eframe::App for Mp3TagApp {
             });
         });
 
+        // 왼쪽 접이식 패널: 폴더 트리
+        let folder_panel = egui::SidePanel::left("folder_panel")
+            .default_width(self.folder_panel_width)
+            .show_animated(ctx, self.show_folders, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("폴더");
+                    if ui.small_button("새로 고침").clicked() {
+                        self.folder_tree.refresh();
+                    }
+                });
+                ui.separator();
+
+                let current = std::fs::canonicalize(&self.dir_path).unwrap_or_default();
+                egui::ScrollArea::both().show(ui, |ui| {
+                    if let Some(dir) = self.folder_tree.show(ui, &current) {
+                        self.dir_path = dir.display().to_string();
+                        self.start_scan();
+                    }
+                });
+            });
+        if let Some(panel) = folder_panel {
+            self.folder_panel_width = panel.response.rect.width();
+        }
+
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.save_session(ctx);
+        }
+
+        if self.tab == Tab::Review {
+            self.show_review(ctx);
+            return;
+        }
+
         // 좌측 패널: 파일 목록
-        egui::SidePanel::left("file_panel")
-            .default_width(300.0)
+        let file_panel = egui::SidePanel::left("file_panel")
+            .default_width(self.file_panel_width)
             .show(ctx, |ui| {
                 ui.heading("파일 목록");
+                ui.horizontal(|ui| {
+                    ui.label("정렬:");
+                    let before = (self.file_sort, self.sort_descending);
+                    egui::ComboBox::from_id_salt("file_sort")
+                        .selected_text(sort_label(self.file_sort))
+                        .show_ui(ui, |ui| {
+                            for sort in [
+                                FileSort::Path,
+                                FileSort::Title,
+                                FileSort::Artist,
+                                FileSort::Album,
+                            ] {
+                                ui.selectable_value(&mut self.file_sort, sort, sort_label(sort));
+                            }
+                        });
+                    ui.toggle_value(&mut self.sort_descending, "내림차순");
+                    if before != (self.file_sort, self.sort_descending) {
+                        self.sort_files();
+                    }
+                });
                 ui.separator();
 
                 egui::ScrollArea::vertical().show(ui, |ui| {
                     let mut new_selection = None;
                     for (i, file) in self.files.iter().enumerate() {
-                        let label = if file.has_tags {
+                        let label = if self.staged_tags(i).is_some() {
+                            format!("[*] {}", file.filename())
+                        } else if file.has_tags {
                             format!("[T] {}", file.filename())
                         } else {
                             format!("[ ] {}", file.filename())
@@ -560,10 +1932,14 @@ impl eframe::App for Mp3TagApp {
                         self.load_edit_fields();
                         self.load_album_art_texture(ctx);
                         self.search_results.clear();
+                        self.result_groups.clear();
+                        self.selected_result = None;
                         self.result_art_textures.clear();
+                        self.search_has_more = false;
                     }
                 });
             });
+        self.file_panel_width = file_panel.response.rect.width();
 
         // 중앙 패널: 태그 편집기 + 검색
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -579,57 +1955,170 @@ impl eframe::App for Mp3TagApp {
                 ui.heading("태그 편집기");
                 ui.separator();
 
+                if !self.presets.is_empty() && self.selected_index.is_some() {
+                    let mut chosen = None;
+                    egui::ComboBox::from_label("프리셋")
+                        .selected_text("채울 프리셋 선택")
+                        .show_ui(ui, |ui| {
+                            for name in self.presets.keys() {
+                                if ui.selectable_label(false, name).clicked() {
+                                    chosen = Some(name.clone());
+                                }
+                            }
+                        });
+                    if let Some(name) = chosen {
+                        self.apply_preset(&name);
+                        self.load_album_art_texture(ctx);
+                    }
+                }
+
+                // 미리보기 중인 검색 결과가 있으면 바뀔 필드를 강조한다
+                let preview = self
+                    .selected_result
+                    .and_then(|i| self.search_results.get(i))
+                    .map(preview_fields)
+                    .unwrap_or_default();
                 egui::Grid::new("tag_grid")
                     .num_columns(2)
                     .spacing([10.0, 6.0])
                     .show(ui, |ui| {
-                        ui.label("제목:");
-                        ui.text_edit_singleline(&mut self.edit_title);
-                        ui.end_row();
-
-                        ui.label("아티스트:");
-                        ui.text_edit_singleline(&mut self.edit_artist);
-                        ui.end_row();
-
-                        ui.label("앨범:");
-                        ui.text_edit_singleline(&mut self.edit_album);
-                        ui.end_row();
-
-                        ui.label("앨범 아티스트:");
-                        ui.text_edit_singleline(&mut self.edit_album_artist);
-                        ui.end_row();
-
-                        ui.label("트랙 번호:");
-                        ui.text_edit_singleline(&mut self.edit_track);
-                        ui.end_row();
+                        edit_row(ui, "제목:", &mut self.edit_title, preview[0].as_deref());
+                        edit_row(
+                            ui,
+                            "아티스트:",
+                            &mut self.edit_artist,
+                            preview[1].as_deref(),
+                        );
+                        edit_row(ui, "앨범:", &mut self.edit_album, preview[2].as_deref());
+                        edit_row(
+                            ui,
+                            "앨범 아티스트:",
+                            &mut self.edit_album_artist,
+                            preview[3].as_deref(),
+                        );
+                        edit_row(
+                            ui,
+                            "트랙 번호:",
+                            &mut self.edit_track,
+                            preview[4].as_deref(),
+                        );
+                        edit_row(ui, "연도:", &mut self.edit_year, preview[5].as_deref());
+                        edit_row(ui, "발매일:", &mut self.edit_date, preview[6].as_deref());
+                        edit_row(ui, "장르:", &mut self.edit_genre, preview[7].as_deref());
+
+                        let encoder = self
+                            .selected_index
+                            .and_then(|i| self.files.get(i))
+                            .and_then(|f| f.encoder.as_ref());
+                        if let Some(encoder) = encoder {
+                            ui.label("인코더:");
+                            ui.label(format!(
+                                "{} (지연 {}, 패딩 {})",
+                                encoder.summary(),
+                                encoder.delay,
+                                encoder.padding
+                            ));
+                            ui.end_row();
+                        }
+                    });
 
-                        ui.label("연도:");
-                        ui.text_edit_singleline(&mut self.edit_year);
-                        ui.end_row();
+                egui::CollapsingHeader::new("클래식 (그룹/작품/악장)")
+                    .default_open(!self.edit_work.is_empty() || !self.edit_grouping.is_empty())
+                    .show(ui, |ui| {
+                        egui::Grid::new("classical_grid")
+                            .num_columns(2)
+                            .spacing([10.0, 6.0])
+                            .show(ui, |ui| {
+                                edit_row(ui, "그룹:", &mut self.edit_grouping, None);
+                                edit_row(ui, "작품:", &mut self.edit_work, None);
+                                edit_row(ui, "악장:", &mut self.edit_movement, None);
+                                edit_row(ui, "악장 번호:", &mut self.edit_movement_number, None);
+                            });
+                    });
 
-                        ui.label("장르:");
-                        ui.text_edit_singleline(&mut self.edit_genre);
-                        ui.end_row();
+                if let Some(idx) = self.selected_result {
+                    let changed = self.preview_changes(&preview);
+                    let art_changes = self.search_results.get(idx).is_some_and(|track| {
+                        track.album_art.is_some()
+                            && track.album_art
+                                != self
+                                    .selected_index
+                                    .and_then(|i| self.files.get(i))
+                                    .and_then(|f| f.current_tags.as_ref())
+                                    .and_then(|t| t.album_art.clone())
                     });
+                    let mut summary = format!("검색 결과 미리보기: 바뀌는 필드 {}개", changed);
+                    if art_changes {
+                        summary.push_str(", 앨범 아트 변경");
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(summary).color(ui.visuals().warn_fg_color));
+                        if ui.button("적용 확인").clicked() {
+                            self.apply_search_result(idx);
+                            self.selected_result = None;
+                            self.load_album_art_texture(ctx);
+                        }
+                        if ui.button("취소").clicked() || ui.input(|i| i.key_pressed(Key::Escape))
+                        {
+                            self.selected_result = None;
+                        }
+                    });
+                }
 
+                let is_staged = self
+                    .selected_index
+                    .is_some_and(|i| self.staged_tags(i).is_some());
+                if is_staged {
+                    ui.label(
+                        egui::RichText::new(
+                            "저장 대기 중인 변경이 있습니다 (아직 파일에 기록되지 않음)",
+                        )
+                        .color(ui.visuals().warn_fg_color),
+                    );
+                }
                 ui.horizontal(|ui| {
                     if ui.button("태그 저장").clicked() {
                         self.save_current_tags();
                         self.load_album_art_texture(ctx);
                     }
+                    if is_staged && ui.button("변경 취소").clicked() {
+                        self.discard_current();
+                        self.load_album_art_texture(ctx);
+                    }
                     if ui.button("파일명 변경").clicked() {
                         self.rename_current_file();
                     }
                 });
 
                 // 앨범 아트 미리보기
+                ui.separator();
+                if let Some(info) = self.album_art_info {
+                    ui.label(format!("현재 앨범 아트: {}", info.summary()));
+                    let warning = info.warning(
+                        self.art_config.warn_max_bytes(),
+                        self.art_config.warn_min_size(),
+                    );
+                    if let Some(warning) = warning {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(220, 140, 0),
+                            format!("경고: {}", warning),
+                        );
+                    }
+                }
                 if let Some(ref texture) = self.album_art_texture {
-                    ui.separator();
-                    ui.label("현재 앨범 아트:");
                     let size = texture.size_vec2();
                     let scale = (150.0 / size.x).min(150.0 / size.y).min(1.0);
                     ui.image(egui::load::SizedTexture::new(texture.id(), size * scale));
                 }
+                ui.horizontal(|ui| {
+                    if ui.button("앨범 아트만 검색").clicked() {
+                        self.open_art_search();
+                    }
+                    if ui.button("클립보드 이미지 붙여넣기 (Ctrl+V)").clicked() {
+                        self.paste_album_art();
+                        self.load_album_art_texture(ctx);
+                    }
+                });
 
                 ui.add_space(20.0);
                 ui.separator();
@@ -638,15 +2127,20 @@ impl eframe::App for Mp3TagApp {
                 ui.heading("온라인 검색");
                 ui.horizontal(|ui| {
                     ui.label("소스:");
-                    egui::ComboBox::from_id_salt("search_source")
-                        .selected_text(match self.search_source {
-                            SearchSource::Spotify => "Spotify",
-                            SearchSource::Melon => "Melon",
-                        })
-                        .show_ui(ui, |ui| {
-                            ui.selectable_value(&mut self.search_source, SearchSource::Spotify, "Spotify");
-                            ui.selectable_value(&mut self.search_source, SearchSource::Melon, "Melon");
-                        });
+                    ui.add_enabled_ui(!self.search_all, |ui| {
+                        egui::ComboBox::from_id_salt("search_source")
+                            .selected_text(self.search_source.label())
+                            .show_ui(ui, |ui| {
+                                for kind in &self.source_choices {
+                                    ui.selectable_value(
+                                        &mut self.search_source,
+                                        kind.clone(),
+                                        kind.label(),
+                                    );
+                                }
+                            });
+                    });
+                    ui.checkbox(&mut self.search_all, "모든 소스 동시 검색");
                 });
                 ui.horizontal(|ui| {
                     ui.label("검색어:");
@@ -660,48 +2154,166 @@ impl eframe::App for Mp3TagApp {
 
                 if !self.search_results.is_empty() {
                     ui.separator();
-                    let mut apply_idx = None;
+                    let mut preview_idx = None;
 
-                    for (i, result) in self.search_results.iter().enumerate() {
-                        ui.horizontal(|ui| {
-                            // 앨범 아트 썸네일
-                            if let Some(Some(texture)) = self.result_art_textures.get(i) {
-                                let size = texture.size_vec2();
-                                let scale = (48.0 / size.x).min(48.0 / size.y).min(1.0);
-                                ui.image(egui::load::SizedTexture::new(texture.id(), size * scale));
-                            } else {
-                                ui.allocate_space(egui::vec2(48.0, 48.0));
-                            }
-
-                            ui.vertical(|ui| {
-                                ui.label(egui::RichText::new(result.display_title()).strong());
-                                ui.label(format!(
-                                    "{} - {}",
-                                    result.display_artist(),
-                                    result.display_album()
-                                ));
-                                if let Some(year) = result.year {
-                                    ui.label(format!("연도: {}", year));
-                                }
-                            });
-
-                            if ui.button("적용").clicked() {
-                                apply_idx = Some(i);
-                            }
-                        });
+                    // 같은 곡의 다른 앨범 판은 대표 결과 아래에 접어 둔다
+                    for group in &self.result_groups {
+                        if self.result_row(ui, group.primary, None) {
+                            preview_idx = Some(group.primary);
+                        }
+                        if !group.others.is_empty() {
+                            let header = format!("같은 곡의 다른 앨범 {}개", group.others.len());
+                            egui::CollapsingHeader::new(header)
+                                .id_salt(("result_group", group.primary))
+                                .show(ui, |ui| {
+                                    for &i in &group.others {
+                                        let edition = variants::edition(&self.search_results[i]);
+                                        if self.result_row(ui, i, Some(edition)) {
+                                            preview_idx = Some(i);
+                                        }
+                                    }
+                                });
+                        }
                         ui.separator();
                     }
 
-                    if let Some(idx) = apply_idx {
-                        self.apply_search_result(idx);
-                        self.load_album_art_texture(ctx);
+                    if self.search_has_more
+                        && !self.is_loading
+                        && ui.button("결과 더 보기").clicked()
+                    {
+                        self.start_search_more();
+                    }
+
+                    if let Some(i) = preview_idx {
+                        self.selected_result = match self.selected_result == Some(i) {
+                            true => None,
+                            false => Some(i),
+                        };
                     }
                 }
             });
         });
+
+        self.show_art_dialog(ctx);
+        self.show_setup_dialog(ctx);
+        self.handle_paste_shortcut(ctx);
+    }
+}
+
+/// 명령줄로 받은 경로에서 처음 스캔할 디렉토리와 스캔 후 선택할 파일을 정한다.
+/// 경로가 없으면 `last_dir`(있는 디렉토리일 때만), 파일이면 그 폴더를 열고 첫 파일을 선택한다.
+fn startup_target(
+    paths: &[PathBuf],
+    last_dir: Option<PathBuf>,
+) -> (Option<PathBuf>, Option<PathBuf>) {
+    match paths {
+        [] => (last_dir.filter(|d| d.is_dir()), None),
+        [dir] if dir.is_dir() => (Some(dir.clone()), None),
+        [file, ..] => (
+            Some(file.parent().map(Path::to_path_buf).unwrap_or_default()),
+            Some(file.clone()),
+        ),
+    }
+}
+
+/// 검색 결과가 편집 필드(제목, 아티스트, 앨범, 앨범 아티스트, 트랙 번호, 연도, 장르 순)에 넣을 값.
+/// 결과에 없는 필드는 None (적용해도 기존 값을 유지).
+fn preview_fields(track: &TrackInfo) -> [Option<String>; 8] {
+    [
+        track.title.clone(),
+        track.artist(),
+        track.album.clone(),
+        track.album_artist.clone(),
+        track.track_number.map(|n| n.to_string()),
+        track.year.map(|y| y.to_string()),
+        track.release_date.clone(),
+        track.genre.clone(),
+    ]
+}
+
+/// 미리보기 값 중 현재 값과 다른 것의 개수. 앞뒤 공백만 다르거나 결과에 없는 필드는 세지 않는다.
+fn count_changes(current: &[&String], preview: &[Option<String>]) -> usize {
+    current
+        .iter()
+        .zip(preview)
+        .filter(|(old, new)| new.as_deref().is_some_and(|n| n.trim() != old.trim()))
+        .count()
+}
+
+/// 편집 필드 한 줄. 미리보기 값이 현재 값과 다르면 라벨을 강조하고 "이전 → 새 값"을 툴팁으로 보여 준다.
+fn edit_row(ui: &mut egui::Ui, label: &str, value: &mut String, preview: Option<&str>) {
+    match preview.filter(|new| new.trim() != value.trim()) {
+        Some(new) => {
+            let old = if value.trim().is_empty() {
+                "(없음)"
+            } else {
+                value.as_str()
+            };
+            let tip = format!("{} → {}", old, new);
+            let text = egui::RichText::new(label)
+                .strong()
+                .color(ui.visuals().warn_fg_color);
+            ui.label(text).on_hover_text(&tip);
+            ui.text_edit_singleline(value).on_hover_text(tip);
+        }
+        None => {
+            ui.label(label);
+            ui.text_edit_singleline(value);
+        }
+    }
+    ui.end_row();
+}
+
+/// 디렉토리를 스캔한다. `[library] enabled`면 저장된 목록을 먼저 보내고, 바뀐 파일만 다시 읽는다.
+/// 데이터베이스를 쓸 수 없으면 모두 읽는다.
+fn scan_directory(dir: &Path, tx: &mpsc::Sender<BgResult>) -> anyhow::Result<Vec<Mp3File>> {
+    #[cfg(feature = "library")]
+    {
+        let cfg = config::load_config().unwrap_or_default();
+        if cfg.library.enabled {
+            let synced =
+                crate::core::library::Library::open(&cfg.library_path()).and_then(|mut db| {
+                    let cached = db.load(dir)?;
+                    if !cached.is_empty() {
+                        let _ = tx.send(BgResult::ScanCached(cached));
+                    }
+                    let paths = scanner::find_mp3_paths(dir)?;
+                    db.sync(&paths, &[dir.to_path_buf()], |_| {})
+                });
+            if let Ok((files, _)) = synced {
+                return Ok(files);
+            }
+        }
+    }
+    #[cfg(not(feature = "library"))]
+    let _ = tx;
+    scanner::scan_directory(dir)
+}
+
+/// 파일 목록 정렬 기준의 표시 이름.
+fn sort_label(sort: FileSort) -> &'static str {
+    match sort {
+        FileSort::Path => "파일명",
+        FileSort::Title => "제목",
+        FileSort::Artist => "아티스트",
+        FileSort::Album => "앨범",
     }
 }
 
+/// 정렬 키. 태그가 비어 있는 파일은 뒤로 보내고, 같은 값끼리는 경로순으로 둔다.
+fn sort_key(file: &Mp3File, sort: FileSort) -> (bool, String, PathBuf) {
+    let tags = file.current_tags.as_ref();
+    let value = match sort {
+        FileSort::Path => Some(file.path.display().to_string()),
+        FileSort::Title => tags.and_then(|t| t.title.clone()),
+        FileSort::Artist => tags.and_then(|t| t.artist()),
+        FileSort::Album => tags.and_then(|t| t.album.clone()),
+    }
+    .map(|v| v.to_lowercase())
+    .unwrap_or_default();
+    (value.is_empty(), value, file.path.clone())
+}
+
 /// 빈 문자열이면 None, 아니면 Some으로 반환한다.
 fn non_empty(s: &str) -> Option<String> {
     let trimmed = s.trim();
@@ -711,3 +2323,69 @@ fn non_empty(s: &str) -> Option<String> {
         Some(trimmed.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_preview_changes() {
+        let track = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["아이유".to_string()],
+            album: Some("Love poem".to_string()),
+            track_number: Some(3),
+            ..Default::default()
+        };
+        let preview = preview_fields(&track);
+        assert_eq!(preview[0].as_deref(), Some("Blueming"));
+        assert_eq!(preview[1].as_deref(), Some("아이유"));
+        assert_eq!(preview[4].as_deref(), Some("3"));
+        // 결과에 없는 필드는 적용해도 기존 값을 유지한다
+        assert_eq!(preview[7], None);
+
+        let [title, artist, album, album_artist, track_no, year, date, genre] =
+            ["Blueming ", "IU", "Love poem", "", "", "2019", "", "Ballad"].map(String::from);
+        let current = [
+            &title,
+            &artist,
+            &album,
+            &album_artist,
+            &track_no,
+            &year,
+            &date,
+            &genre,
+        ];
+        // 아티스트와 트랙 번호만 바뀐다 (제목은 공백만 다름)
+        assert_eq!(count_changes(&current, &preview), 2);
+    }
+
+    #[test]
+    fn test_startup_target() {
+        let dir = TempDir::new("gui-open");
+        let song = dir.join("song.mp3");
+        let other = dir.join("other.mp3");
+        let missing = dir.join("missing");
+
+        assert_eq!(
+            startup_target(&[], Some(dir.to_path_buf())),
+            (Some(dir.to_path_buf()), None)
+        );
+        assert_eq!(startup_target(&[], Some(missing)), (None, None));
+        assert_eq!(
+            startup_target(&[dir.to_path_buf()], None),
+            (Some(dir.to_path_buf()), None)
+        );
+        // 파일 하나: 그 폴더를 열고 파일을 선택한다
+        assert_eq!(
+            startup_target(std::slice::from_ref(&song), Some(PathBuf::from("/music"))),
+            (Some(dir.to_path_buf()), Some(song.clone()))
+        );
+        // 여러 개: 첫 파일을 선택한다
+        assert_eq!(
+            startup_target(&[song.clone(), other], None),
+            (Some(dir.to_path_buf()), Some(song))
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+
+/// 클립보드의 이미지를 JPEG로 인코딩해 반환한다. 이미지가 없으면 에러.
+pub fn paste_image() -> Result<Vec<u8>> {
+    let mut clipboard = arboard::Clipboard::new().context("클립보드를 열 수 없습니다")?;
+    let image = clipboard
+        .get_image()
+        .context("클립보드에 이미지가 없습니다")?;
+    encode_jpeg(image.width as u32, image.height as u32, &image.bytes)
+}
+
+/// RGBA 픽셀을 JPEG(품질 90)로 인코딩한다.
+/// JPEG에는 투명도가 없으므로 투명한 부분은 흰 배경에 합성한다.
+fn encode_jpeg(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>> {
+    let rgb: Vec<u8> = rgba
+        .chunks_exact(4)
+        .flat_map(|px| {
+            let alpha = px[3] as u32;
+            [0, 1, 2].map(|c| ((px[c] as u32 * alpha + 255 * (255 - alpha)) / 255) as u8)
+        })
+        .collect();
+    let img = image::RgbImage::from_raw(width, height, rgb)
+        .context("클립보드 이미지 크기가 올바르지 않습니다")?;
+    let mut out = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, 90)
+        .encode_image(&img)
+        .context("클립보드 이미지를 JPEG로 인코딩할 수 없습니다")?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_jpeg_flattens_alpha_on_white() {
+        // 불투명한 검정 한 칸, 완전히 투명한 검정 한 칸
+        let rgba = [0, 0, 0, 255, 0, 0, 0, 0];
+        let data = encode_jpeg(2, 1, &rgba).unwrap();
+        assert_eq!(crate::core::artwork::image_dimensions(&data), Some((2, 1)));
+
+        let decoded = image::load_from_memory(&data).unwrap().to_rgb8();
+        assert!(decoded.get_pixel(0, 0)[0] < 64);
+        assert!(decoded.get_pixel(1, 0)[0] > 192);
+        assert!(encode_jpeg(3, 1, &rgba).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use egui::collapsing_header::CollapsingState;
+
+/// 왼쪽 접이식 패널의 폴더 트리. 하위 폴더 목록은 처음 펼칠 때 읽어 캐시한다.
+pub struct FolderTree {
+    roots: Vec<PathBuf>,
+    children: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl FolderTree {
+    /// 홈 디렉토리와 작업 디렉토리가 속한 파일 시스템 루트를 최상위로 둔다.
+    pub fn new() -> Self {
+        let mut roots = Vec::new();
+        if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+            roots.push(PathBuf::from(home));
+        }
+        let root = std::env::current_dir()
+            .ok()
+            .and_then(|dir| dir.ancestors().last().map(Path::to_path_buf));
+        if let Some(root) = root.filter(|r| !roots.contains(r)) {
+            roots.push(root);
+        }
+        Self {
+            roots,
+            children: HashMap::new(),
+        }
+    }
+
+    /// 캐시한 하위 폴더 목록을 버린다. 다음에 그릴 때 다시 읽는다.
+    pub fn refresh(&mut self) {
+        self.children.clear();
+    }
+
+    /// 트리를 그리고, 폴더 이름을 클릭하면 그 경로를 반환한다.
+    /// `current`(절대 경로)의 상위 폴더는 처음부터 펼쳐 둔다.
+    pub fn show(&mut self, ui: &mut egui::Ui, current: &Path) -> Option<PathBuf> {
+        let mut picked = None;
+        for root in self.roots.clone() {
+            let name = root.display().to_string();
+            self.show_dir(ui, &root, name, current, &mut picked);
+        }
+        picked
+    }
+
+    fn show_dir(
+        &mut self,
+        ui: &mut egui::Ui,
+        path: &Path,
+        name: String,
+        current: &Path,
+        picked: &mut Option<PathBuf>,
+    ) {
+        let id = ui.make_persistent_id(path);
+        let expanded = current.starts_with(path) && current != path;
+        CollapsingState::load_with_default_open(ui.ctx(), id, expanded)
+            .show_header(ui, |ui| {
+                let response = ui.selectable_label(current == path, name);
+                if response.clicked() {
+                    *picked = Some(path.to_path_buf());
+                }
+                if current == path && expanded {
+                    response.scroll_to_me(None);
+                }
+            })
+            .body(|ui| {
+                let children = self
+                    .children
+                    .entry(path.to_path_buf())
+                    .or_insert_with(|| subdirs(path))
+                    .clone();
+                if children.is_empty() {
+                    ui.weak("하위 폴더 없음");
+                }
+                for child in children {
+                    let name = child
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    self.show_dir(ui, &child, name, current, picked);
+                }
+            });
+    }
+}
+
+/// 숨김 폴더를 뺀 하위 폴더를 이름순으로 반환한다. 읽을 수 없으면 빈 목록.
+fn subdirs(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort_by_key(|p| p.file_name().map(|n| n.to_string_lossy().to_lowercase()));
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_subdirs_skips_files_and_hidden() {
+        let dir = TempDir::new("tree");
+        for sub in ["b", "A", ".cache"] {
+            std::fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        std::fs::write(dir.join("song.mp3"), b"").unwrap();
+
+        assert_eq!(subdirs(&dir), [dir.join("A"), dir.join("b")]);
+        assert!(subdirs(&dir.join("missing")).is_empty());
+    }
+}
@@ -1,17 +1,27 @@
 #[cfg(feature = "gui")]
 mod app;
+#[cfg(feature = "gui")]
+mod clipboard;
+#[cfg(feature = "gui")]
+mod folder_tree;
 
-/// GUI 창을 실행한다. directory가 주어지면 해당 디렉토리를 자동으로 스캔한다.
+/// GUI 창을 실행한다. `paths`로 디렉토리나 MP3 파일을 열 수 있다 (`app::Mp3TagApp::new` 참고).
+/// 창 크기는 지난 세션(`[gui] window_size`)을 따른다.
 #[cfg(feature = "gui")]
-pub fn launch(directory: Option<std::path::PathBuf>) {
+pub fn launch(paths: Vec<std::path::PathBuf>) {
+    let size = crate::config::load_config()
+        .unwrap_or_default()
+        .gui
+        .window_size
+        .unwrap_or([1000.0, 700.0]);
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([1000.0, 700.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size(size),
         ..Default::default()
     };
 
     let _ = eframe::run_native(
         "MP3 태그 편집기",
         options,
-        Box::new(move |cc| Ok(Box::new(app::Mp3TagApp::new(cc, directory)))),
+        Box::new(move |cc| Ok(Box::new(app::Mp3TagApp::new(cc, paths)))),
     );
 }
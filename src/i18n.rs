@@ -0,0 +1,81 @@
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+
+/// CLI 출력 언어. 한국어가 기본이다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Lang {
+    #[default]
+    Ko,
+    En,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// 출력 언어를 정한다. `--lang`, `MP3TAG_LANG`, 로캘(LC_ALL, LC_MESSAGES, LANG) 순으로 보고,
+/// 아무것도 없거나 C/POSIX 로캘이면 한국어를 쓴다. 처음 한 번만 적용된다.
+pub fn init(flag: Option<Lang>) {
+    let env = |key: &str| std::env::var(key).ok().filter(|v| !v.is_empty());
+    let lang = flag
+        .or_else(|| env("MP3TAG_LANG").and_then(|v| Lang::from_str(&v, true).ok()))
+        .or_else(|| {
+            ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .into_iter()
+                .find_map(env)
+                .and_then(|v| from_locale(&v))
+        })
+        .unwrap_or_default();
+    let _ = LANG.set(lang);
+}
+
+/// 현재 출력 언어. `init` 전에는 한국어.
+pub fn lang() -> Lang {
+    LANG.get().copied().unwrap_or_default()
+}
+
+/// 고정 문자열 중 현재 언어에 맞는 것을 고른다.
+pub fn pick(ko: &'static str, en: &'static str) -> &'static str {
+    match lang() {
+        Lang::Ko => ko,
+        Lang::En => en,
+    }
+}
+
+/// "ko_KR.UTF-8", "en_US" 같은 로캘 이름에서 언어를 고른다. C/POSIX는 정하지 않는다.
+fn from_locale(locale: &str) -> Option<Lang> {
+    let language = locale.split(['_', '.', '@', '-']).next()?;
+    match language {
+        "" | "C" | "POSIX" => None,
+        "ko" => Some(Lang::Ko),
+        _ => Some(Lang::En),
+    }
+}
+
+/// 한국어와 영어 형식 문자열 중 현재 언어에 맞는 것으로 `format!`한다.
+///
+/// ```ignore
+/// println!("{}", tr!("총 {}개 파일", "{} files in total", files.len()));
+/// ```
+macro_rules! tr {
+    ($ko:literal, $en:literal $(, $arg:expr)* $(,)?) => {
+        match $crate::i18n::lang() {
+            $crate::i18n::Lang::Ko => format!($ko $(, $arg)*),
+            $crate::i18n::Lang::En => format!($en $(, $arg)*),
+        }
+    };
+}
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(from_locale("ko_KR.UTF-8"), Some(Lang::Ko));
+        assert_eq!(from_locale("en_US.UTF-8"), Some(Lang::En));
+        assert_eq!(from_locale("de"), Some(Lang::En));
+        assert_eq!(from_locale("C.UTF-8"), None);
+        assert_eq!(from_locale("POSIX"), None);
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::{bail, Context, Result};
+use md5::{Digest, Md5};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::config::{MediaServerConfig, MediaServerKind};
+
+/// Subsonic API 버전. 토큰 인증은 1.13.0부터 지원한다.
+const SUBSONIC_API_VERSION: &str = "1.16.1";
+
+/// 태깅이 끝난 뒤 라이브러리 스캔을 요청할 미디어 서버.
+pub struct MediaServer {
+    client: reqwest::blocking::Client,
+    url: String,
+    auth: Auth,
+}
+
+enum Auth {
+    Jellyfin { api_key: String },
+    Subsonic { username: String, password: String },
+}
+
+#[derive(Deserialize)]
+struct SubsonicEnvelope {
+    #[serde(rename = "subsonic-response")]
+    response: SubsonicResponse,
+}
+
+#[derive(Deserialize)]
+struct SubsonicResponse {
+    status: String,
+    error: Option<SubsonicError>,
+}
+
+#[derive(Deserialize)]
+struct SubsonicError {
+    message: String,
+}
+
+impl MediaServer {
+    /// 설정에 서버 종류와 주소가 있으면 클라이언트를 생성한다. 없으면 None.
+    /// 종류에 필요한 자격증명이 빠져 있으면 에러를 반환한다.
+    pub fn from_config(config: &MediaServerConfig) -> Result<Option<Self>> {
+        let Some(kind) = config.kind else {
+            return Ok(None);
+        };
+        let Some(url) = config.url.as_ref().filter(|u| !u.trim().is_empty()) else {
+            return Ok(None);
+        };
+
+        let auth = match kind {
+            MediaServerKind::Jellyfin => Auth::Jellyfin {
+                api_key: config
+                    .api_key
+                    .clone()
+                    .context("Jellyfin 연동에는 media_server.api_key가 필요합니다")?,
+            },
+            MediaServerKind::Subsonic => Auth::Subsonic {
+                username: config
+                    .username
+                    .clone()
+                    .context("Subsonic 연동에는 media_server.username이 필요합니다")?,
+                password: config
+                    .password
+                    .clone()
+                    .context("Subsonic 연동에는 media_server.password가 필요합니다")?,
+            },
+        };
+
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("mp3tag/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("미디어 서버 HTTP 클라이언트 생성에 실패했습니다")?;
+
+        Ok(Some(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            auth,
+        }))
+    }
+
+    /// 화면 표시용 서버 이름.
+    pub fn label(&self) -> &'static str {
+        match self.auth {
+            Auth::Jellyfin { .. } => "Jellyfin",
+            Auth::Subsonic { .. } => "Subsonic",
+        }
+    }
+
+    /// 라이브러리 스캔을 요청한다. 스캔은 서버에서 비동기로 진행된다.
+    pub fn refresh(&self) -> Result<()> {
+        match self.auth {
+            Auth::Jellyfin { ref api_key } => {
+                self.client
+                    .post(format!("{}/Library/Refresh", self.url))
+                    .header("X-Emby-Token", api_key)
+                    .send()
+                    .context("Jellyfin 연결에 실패했습니다")?
+                    .error_for_status()
+                    .context(
+                        "Jellyfin 라이브러리 스캔 요청이 거부되었습니다. api_key를 확인하세요",
+                    )?;
+            }
+            Auth::Subsonic {
+                ref username,
+                ref password,
+            } => {
+                let salt: String = rand::thread_rng()
+                    .sample_iter(&Alphanumeric)
+                    .take(16)
+                    .map(char::from)
+                    .collect();
+                let token = subsonic_token(password, &salt);
+                let envelope: SubsonicEnvelope = self
+                    .client
+                    .get(format!("{}/rest/startScan", self.url))
+                    .query(&[
+                        ("u", username.as_str()),
+                        ("t", token.as_str()),
+                        ("s", salt.as_str()),
+                        ("v", SUBSONIC_API_VERSION),
+                        ("c", "mp3tag"),
+                        ("f", "json"),
+                    ])
+                    .send()
+                    .context("Subsonic 서버 연결에 실패했습니다")?
+                    .error_for_status()?
+                    .json()
+                    .context("Subsonic 응답 파싱에 실패했습니다")?;
+                if envelope.response.status != "ok" {
+                    let message = envelope
+                        .response
+                        .error
+                        .map(|e| e.message)
+                        .unwrap_or_default();
+                    bail!("Subsonic 라이브러리 스캔 요청이 실패했습니다: {}", message);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Subsonic 토큰 인증값: md5(비밀번호 + 솔트)의 16진수 소문자.
+fn subsonic_token(password: &str, salt: &str) -> String {
+    let digest = Md5::digest(format!("{}{}", password, salt));
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsonic_token() {
+        // Subsonic API 문서의 예시
+        assert_eq!(
+            subsonic_token("sesame", "c19b2d"),
+            "26719a1196d2a940705a59634eb18eab"
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert!(MediaServer::from_config(&MediaServerConfig::default())
+            .unwrap()
+            .is_none());
+
+        let missing_key = MediaServerConfig {
+            kind: Some(MediaServerKind::Jellyfin),
+            url: Some("http://nas:8096".to_string()),
+            ..Default::default()
+        };
+        assert!(MediaServer::from_config(&missing_key).is_err());
+
+        let subsonic = MediaServerConfig {
+            kind: Some(MediaServerKind::Subsonic),
+            url: Some("http://nas:4533/".to_string()),
+            username: Some("admin".to_string()),
+            password: Some("sesame".to_string()),
+            ..Default::default()
+        };
+        let server = MediaServer::from_config(&subsonic).unwrap().unwrap();
+        assert_eq!(server.label(), "Subsonic");
+        assert_eq!(server.url, "http://nas:4533");
+    }
+}
@@ -0,0 +1,2 @@
+pub mod media_server;
+pub mod now_playing;
@@ -0,0 +1,174 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::MpdConfig;
+use crate::exit;
+
+/// 현재 재생 중인 곡을 조회할 플레이어.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Player {
+    Mpd,
+    /// Linux 데스크톱 플레이어 (`playerctl` 필요)
+    Mpris,
+}
+
+/// 현재 재생 중인 로컬 파일 경로를 찾는다.
+/// `player`가 없으면 MPD를 먼저 시도하고, 실패하면 MPRIS를 시도한다.
+pub fn current_file(cfg: &MpdConfig, player: Option<Player>) -> Result<PathBuf> {
+    match player {
+        Some(Player::Mpd) => mpd_current_file(cfg),
+        Some(Player::Mpris) => mpris_current_file(),
+        None => mpd_current_file(cfg).or_else(|mpd_err| {
+            mpris_current_file().map_err(|mpris_err| {
+                anyhow::anyhow!(
+                    "재생 중인 곡을 찾을 수 없습니다.\n  MPD: {:#}\n  MPRIS: {:#}",
+                    mpd_err,
+                    mpris_err
+                )
+            })
+        }),
+    }
+}
+
+/// MPD의 `currentsong` 명령으로 재생 중인 파일을 조회한다.
+fn mpd_current_file(cfg: &MpdConfig) -> Result<PathBuf> {
+    let addr = format!("{}:{}", cfg.host(), cfg.port());
+    let stream =
+        TcpStream::connect(&addr).with_context(|| format!("MPD({})에 연결할 수 없습니다", addr))?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    let mut greeting = String::new();
+    reader.read_line(&mut greeting)?;
+    if !greeting.starts_with("OK MPD") {
+        bail!("MPD 서버가 아닙니다: {}", greeting.trim());
+    }
+
+    if let Some(ref password) = cfg.password {
+        writeln!(writer, "password {}", quote(password))?;
+        read_response(&mut reader).context("MPD 비밀번호가 올바르지 않습니다")?;
+    }
+    writeln!(writer, "currentsong")?;
+    let song = read_response(&mut reader)?;
+    let _ = writeln!(writer, "close");
+
+    let file = parse_field(&song, "file").context("MPD에서 재생 중인 곡이 없습니다")?;
+    resolve_mpd_path(&file, cfg.music_dir.as_deref())
+}
+
+/// "OK" 또는 "ACK ..."까지 응답 줄을 읽는다.
+fn read_response(reader: &mut impl BufRead) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            bail!("MPD 연결이 끊어졌습니다");
+        }
+        let line = line.trim_end().to_string();
+        if line == "OK" {
+            return Ok(lines);
+        }
+        if line.starts_with("ACK") {
+            bail!("MPD 오류: {}", line);
+        }
+        lines.push(line);
+    }
+}
+
+/// "key: value" 응답 줄에서 값을 찾는다.
+fn parse_field(lines: &[String], key: &str) -> Option<String> {
+    lines.iter().find_map(|line| {
+        let (k, v) = line.split_once(": ")?;
+        (k == key).then(|| v.to_string())
+    })
+}
+
+/// MPD 인자 따옴표 처리.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// MPD의 `file` 값(음악 디렉토리 기준 상대 경로)을 로컬 경로로 바꾼다.
+fn resolve_mpd_path(file: &str, music_dir: Option<&Path>) -> Result<PathBuf> {
+    if file.contains("://") {
+        bail!("재생 중인 곡이 로컬 파일이 아닙니다: {}", file);
+    }
+    let path = Path::new(file);
+    if path.is_absolute() {
+        return Ok(path.to_path_buf());
+    }
+    let music_dir = music_dir.ok_or_else(|| {
+        exit::config_error("MPD 파일 경로를 찾으려면 config.toml의 [mpd] music_dir을 설정하세요")
+    })?;
+    Ok(music_dir.join(path))
+}
+
+/// `playerctl`로 MPRIS 플레이어의 `xesam:url`을 조회한다.
+fn mpris_current_file() -> Result<PathBuf> {
+    let output = Command::new("playerctl")
+        .args(["metadata", "xesam:url"])
+        .output()
+        .context("playerctl을 실행할 수 없습니다")?;
+    if !output.status.success() {
+        bail!("MPRIS 플레이어에서 재생 중인 곡이 없습니다");
+    }
+    file_url_to_path(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// `file://` URL을 로컬 경로로 바꾼다.
+fn file_url_to_path(url: &str) -> Result<PathBuf> {
+    let parsed = reqwest::Url::parse(url)
+        .with_context(|| format!("재생 중인 곡의 주소를 해석할 수 없습니다: {}", url))?;
+    if parsed.scheme() != "file" {
+        bail!("재생 중인 곡이 로컬 파일이 아닙니다: {}", url);
+    }
+    parsed
+        .to_file_path()
+        .map_err(|_| anyhow::anyhow!("로컬 파일 경로가 아닙니다: {}", url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_currentsong() {
+        let response = "file: IU/Palette/01 이 지금.mp3\nTitle: 이 지금\nPos: 0\nOK\n";
+        let lines = read_response(&mut response.as_bytes()).unwrap();
+        assert_eq!(
+            parse_field(&lines, "file").as_deref(),
+            Some("IU/Palette/01 이 지금.mp3")
+        );
+        assert_eq!(parse_field(&lines, "Artist"), None);
+
+        let err = read_response(&mut "ACK [4@0] {password} incorrect password\n".as_bytes());
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_resolve_mpd_path() {
+        assert_eq!(
+            resolve_mpd_path("IU/a.mp3", Some(Path::new("/music"))).unwrap(),
+            PathBuf::from("/music/IU/a.mp3")
+        );
+        assert!(resolve_mpd_path("IU/a.mp3", None).is_err());
+        assert!(resolve_mpd_path("http://radio/stream", Some(Path::new("/music"))).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_url_to_path() {
+        assert_eq!(
+            file_url_to_path("file:///home/me/Music/%EB%B0%A4%ED%8E%B8%EC%A7%80%20(Live).mp3")
+                .unwrap(),
+            PathBuf::from("/home/me/Music/밤편지 (Live).mp3")
+        );
+        assert!(file_url_to_path("https://open.spotify.com/track/1").is_err());
+    }
+}
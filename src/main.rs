@@ -1,8 +1,14 @@
 mod cli;
 mod config;
 mod core;
+mod exit;
+mod i18n;
+mod integrations;
 mod models;
+mod server;
 mod sources;
+#[cfg(test)]
+mod test_support;
 
 #[cfg(feature = "gui")]
 mod gui;
@@ -10,10 +16,25 @@ mod gui;
 use clap::Parser;
 
 fn main() {
-    let cli = cli::Cli::parse();
+    // clap은 잘못된 인자에 2로 끝내지만 2는 `ExitCode::Partial`이므로 종료 코드를 직접 정한다
+    let cli = match cli::Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            std::process::exit(exit::usage_code(&e));
+        }
+    };
+    i18n::init(cli.lang);
 
     if let Err(e) = cli::run(cli) {
-        eprintln!("오류: {:#}", e);
-        std::process::exit(1);
+        let code = exit::code(&e);
+        let label = match code {
+            exit::ExitCode::Partial
+            | exit::ExitCode::NothingMatched
+            | exit::ExitCode::Cancelled => i18n::pick("결과", "result"),
+            _ => i18n::pick("오류", "error"),
+        };
+        eprintln!("{}: {:#}", label, e);
+        std::process::exit(code as i32);
     }
 }
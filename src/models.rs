@@ -1,22 +1,74 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
+
 /// 트랙의 메타데이터를 담는 구조체.
 /// ID3 태그, Spotify 검색 결과, 파일명 파싱 결과 등 다양한 소스에서 생성된다.
-#[derive(Debug, Clone, Default)]
+/// `serve` API에서 JSON으로 주고받으며, 앨범 아트 바이너리는 직렬화하지 않는다.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TrackInfo {
     pub title: Option<String>,
-    pub artist: Option<String>,
+    /// 참여 아티스트 목록. JSON의 "artist"에는 ", "로 이어 붙인 문자열로 주고받는다.
+    #[serde(rename = "artist", with = "artist_list")]
+    pub artists: Vec<String>,
     pub album: Option<String>,
     pub album_artist: Option<String>,
     pub track_number: Option<u32>,
+    /// 앨범의 전체 트랙 수 (TRCK의 "3/12"에서 12)
+    pub total_tracks: Option<u32>,
+    /// 디스크 번호와 전체 디스크 수 (TPOS의 "1/2")
+    pub disc_number: Option<u32>,
+    pub total_discs: Option<u32>,
     pub year: Option<i32>,
+    /// 정밀한 발매일 ("2019-11-18" 또는 "2019-11"). 있으면 TDRC에 연도 대신 기록한다.
+    pub release_date: Option<String>,
+    /// 최초 발매 연도. 리마스터/재발매반에서 `year`와 다를 수 있다 (TDOR).
+    pub original_year: Option<i32>,
+    /// 최초 발매일 ("1985-06-01" 또는 "1985-06"). 있으면 TDOR에 날짜까지 기록한다.
+    pub original_date: Option<String>,
     pub genre: Option<String>,
+    /// 콘텐츠 그룹 (TIT1, iTunes 호환 모드에서는 GRP1)
+    pub grouping: Option<String>,
+    /// 클래식 작품명 (TXXX:WORK, iTunes 호환 모드에서는 TIT1에도 기록)
+    pub work: Option<String>,
+    /// 악장 이름 (MVNM)
+    pub movement: Option<String>,
+    /// 악장 번호와 전체 악장 수 (MVIN의 "2/4")
+    pub movement_number: Option<u32>,
+    pub movement_total: Option<u32>,
+    /// 음반사 (TPUB)
+    pub label: Option<String>,
+    /// 작곡/작사가 (TCOM). 여러 명이면 ", "로 구분한다.
+    pub composer: Option<String>,
+    /// 프로듀서 (TXXX:PRODUCER)
+    pub producer: Option<String>,
+    /// 가사 (USLT)
+    pub lyrics: Option<String>,
+    /// MusicBrainz 릴리스 ID (TXXX:MusicBrainz Album Id). Cover Art Archive 조회에 사용한다.
+    pub musicbrainz_album_id: Option<String>,
+    /// 재생 시간 (밀리초). 외부 소스 결과를 로컬 파일과 짝지을 때 사용한다.
+    pub duration_ms: Option<u64>,
     /// 앨범 아트 바이너리 (JPEG/PNG)
+    #[serde(skip)]
     pub album_art: Option<Vec<u8>>,
     /// 앨범 아트 다운로드 URL (Spotify 등 외부 소스용)
     pub album_art_url: Option<String>,
+    /// 소스 내 고유 트랙 ID (Spotify 트랙 ID, Melon 곡 ID 등)
+    pub source_id: Option<String>,
     /// 데이터 출처 ("id3", "spotify", "filename", "manual")
     pub source: String,
+    /// `source`와 다른 소스에서 가져온 필드의 출처 (필드 이름 → 소스 이름).
+    /// 여러 소스를 합칠 때 `[trust]` 순서를 적용하는 데 쓴다.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub field_sources: BTreeMap<String, String>,
+    /// 소스가 알려 준 explicit(가사 수정 전) 여부. 알 수 없으면 None.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explicit: Option<bool>,
+    /// 소스 결과를 고를 때의 일치 점수 (0.0~1.0). `[write] provenance` 기록에 쓴다.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_score: Option<f64>,
 }
 
 impl TrackInfo {
@@ -25,9 +77,22 @@ impl TrackInfo {
         self.title.as_deref().unwrap_or("알 수 없음")
     }
 
+    /// 필드 값의 출처. 따로 기록되지 않았으면 트랙의 `source`.
+    pub fn field_source(&self, field: &str) -> &str {
+        self.field_sources
+            .get(field)
+            .map(String::as_str)
+            .unwrap_or(&self.source)
+    }
+
+    /// 아티스트 목록을 ", "로 이어 붙인다. 없으면 None.
+    pub fn artist(&self) -> Option<String> {
+        (!self.artists.is_empty()).then(|| self.artists.join(", "))
+    }
+
     /// 아티스트를 표시용 문자열로 반환한다. 없으면 "알 수 없음".
-    pub fn display_artist(&self) -> &str {
-        self.artist.as_deref().unwrap_or("알 수 없음")
+    pub fn display_artist(&self) -> String {
+        self.artist().unwrap_or_else(|| "알 수 없음".to_string())
     }
 
     /// 앨범을 표시용 문자열로 반환한다. 없으면 "알 수 없음".
@@ -44,14 +109,202 @@ impl TrackInfo {
             self.display_album()
         )
     }
+
+    /// 후보 목록에 덧붙일 "2019, 4번 트랙, 3:32, spotify". 값이 없는 항목은 뺀다.
+    pub fn details(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ref date) = self.release_date {
+            parts.push(date.clone());
+        } else if let Some(year) = self.year {
+            parts.push(year.to_string());
+        }
+        if let Some(number) = self.track_number {
+            parts.push(format!("{}번 트랙", number));
+        }
+        if let Some(ms) = self.duration_ms {
+            parts.push(format!("{}:{:02}", ms / 60_000, ms / 1000 % 60));
+        }
+        if !self.source.is_empty() {
+            parts.push(self.source.clone());
+        }
+        parts.join(", ")
+    }
+}
+
+/// 아티스트 문자열을 이름 목록으로 나눈다.
+/// ";", " / ", ", ", "feat."/"ft." 구분을 인식하며, 공백 없는 "/"("AC/DC")는 이름의 일부로 둔다.
+pub fn split_artists(text: &str) -> Vec<String> {
+    let mut text = text.replace('\0', ";");
+    for feat in [
+        " feat. ",
+        " ft. ",
+        " featuring ",
+        " Feat. ",
+        " Ft. ",
+        " FEAT. ",
+    ] {
+        text = text.replace(feat, ";");
+    }
+    // "(feat. X)" / "[feat. X]" 형식은 괄호를 벗겨 낸다
+    for open in ["(feat. ", "[feat. ", "(ft. ", "[ft. ", "(Feat. ", "[Feat. "] {
+        if let Some(pos) = text.find(open) {
+            let inner = text[pos + open.len()..]
+                .trim_end_matches([')', ']'])
+                .to_string();
+            text = format!("{};{}", &text[..pos], inner);
+        }
+    }
+    text.split([';', ','])
+        .flat_map(|part| part.split(" / "))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .fold(Vec::new(), |mut names, name| {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+            names
+        })
+}
+
+/// 태그의 다중 값(ID3v2.4 NUL 구분, Vorbis의 ARTIST 여러 개)을 아티스트 목록으로 만든다.
+/// 값 안의 ", "나 "feat."는 나누지 않는다.
+pub fn artist_values<S: AsRef<str>>(values: impl IntoIterator<Item = S>) -> Vec<String> {
+    values
+        .into_iter()
+        .map(|value| value.as_ref().trim().to_string())
+        .filter(|name| !name.is_empty())
+        .fold(Vec::new(), |mut names, name| {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+            names
+        })
+}
+
+/// `TrackInfo::artists`를 JSON의 "artist" 문자열로 주고받는다.
+/// 문자열 대신 배열도 받는다.
+mod artist_list {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Artists {
+        Text(String),
+        List(Vec<String>),
+    }
+
+    pub fn serialize<S: Serializer>(artists: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+        (!artists.is_empty())
+            .then(|| artists.join(", "))
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        Ok(match Option::<Artists>::deserialize(deserializer)? {
+            Some(Artists::Text(text)) => super::split_artists(&text),
+            Some(Artists::List(names)) => names
+                .into_iter()
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+                .collect(),
+            None => Vec::new(),
+        })
+    }
+}
+
+/// 앨범 단위 메타데이터. 앨범 모드 태깅에서 트랙 목록과 함께 사용한다.
+#[derive(Debug, Clone, Default)]
+pub struct AlbumInfo {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub year: Option<i32>,
+    /// 정밀한 발매일 ("2019-11-18"). 소스가 제공하지 않으면 None.
+    pub release_date: Option<String>,
+    pub genre: Option<String>,
+    /// 음반사
+    pub label: Option<String>,
+    /// 전체 트랙 수. 트랙 목록을 가져오지 않은 검색 결과에서도 채워질 수 있다.
+    pub total_tracks: Option<u32>,
+    /// 앨범 아트 바이너리 (JPEG/PNG)
+    pub album_art: Option<Vec<u8>>,
+    /// 앨범 아트 다운로드 URL
+    pub album_art_url: Option<String>,
+    /// 소스의 앨범 상세 페이지 URL (트랙 목록 조회용)
+    pub detail_url: Option<String>,
+    /// 트랙 목록 (트랙 번호 순). 검색 결과에서는 비어 있다.
+    pub tracks: Vec<TrackInfo>,
+    /// 데이터 출처 ("melon", "spotify" 등)
+    pub source: String,
+}
+
+impl AlbumInfo {
+    /// "아티스트 - 앨범 (발매일, N곡)" 형식의 요약 문자열을 반환한다.
+    pub fn summary(&self) -> String {
+        let mut s = format!(
+            "{} - {}",
+            self.artist.as_deref().unwrap_or("알 수 없음"),
+            self.title.as_deref().unwrap_or("알 수 없음")
+        );
+
+        let mut extra = Vec::new();
+        if let Some(ref date) = self.release_date {
+            extra.push(date.clone());
+        } else if let Some(year) = self.year {
+            extra.push(year.to_string());
+        }
+        if let Some(total) = self.total_tracks {
+            extra.push(format!("{}곡", total));
+        }
+        if !extra.is_empty() {
+            s.push_str(&format!(" ({})", extra.join(", ")));
+        }
+        s
+    }
+}
+
+/// 첫 프레임의 LAME 태그에서 읽은 인코더 정보.
+/// 인코더 지연/패딩은 갭리스 재생에 쓰이므로 태그를 다시 써도 그대로 남아야 한다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncoderInfo {
+    /// 인코더 버전 (예: "LAME3.100")
+    pub encoder: String,
+    /// "CBR", "ABR", "VBR"
+    pub mode: Option<String>,
+    /// LAME 프리셋 (예: "V0", "192kbps", "extreme")
+    pub preset: Option<String>,
+    /// 앞쪽 인코더 지연 샘플 수
+    pub delay: u16,
+    /// 끝쪽 패딩 샘플 수
+    pub padding: u16,
+}
+
+impl EncoderInfo {
+    /// "LAME3.100 VBR V0" 형식의 요약.
+    pub fn summary(&self) -> String {
+        [
+            Some(&self.encoder),
+            self.mode.as_ref(),
+            self.preset.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
 }
 
 /// 스캔된 MP3 파일 하나를 나타내는 구조체.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Mp3File {
     pub path: PathBuf,
     pub current_tags: Option<TrackInfo>,
     pub has_tags: bool,
+    /// LAME 태그가 있으면 인코더 정보
+    pub encoder: Option<EncoderInfo>,
 }
 
 impl Mp3File {
@@ -63,3 +316,48 @@ impl Mp3File {
             .unwrap_or("알 수 없음")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_artists() {
+        assert_eq!(split_artists("IU; SUGA"), ["IU", "SUGA"]);
+        assert_eq!(split_artists("IU, SUGA / 지코"), ["IU", "SUGA", "지코"]);
+        assert_eq!(split_artists("Crush feat. 태연"), ["Crush", "태연"]);
+        assert_eq!(split_artists("Crush (Feat. 태연)"), ["Crush", "태연"]);
+        assert_eq!(split_artists("AC/DC"), ["AC/DC"]);
+        assert_eq!(split_artists("IU\0SUGA;IU"), ["IU", "SUGA"]);
+        assert!(split_artists(" ").is_empty());
+        assert_eq!(
+            artist_values(["Tyler, The Creator", " IU ", "", "IU"]),
+            ["Tyler, The Creator", "IU"]
+        );
+    }
+
+    #[test]
+    fn test_track_details() {
+        let track = TrackInfo {
+            year: Some(2019),
+            track_number: Some(4),
+            duration_ms: Some(212_000),
+            source: "spotify".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(track.details(), "2019, 4번 트랙, 3:32, spotify");
+        assert_eq!(TrackInfo::default().details(), "");
+    }
+
+    #[test]
+    fn test_artist_json() {
+        let info: TrackInfo = serde_json::from_str(r#"{"artist": "IU, SUGA"}"#).unwrap();
+        assert_eq!(info.artists, ["IU", "SUGA"]);
+        let info: TrackInfo = serde_json::from_str(r#"{"artist": ["IU", "SUGA"]}"#).unwrap();
+        assert_eq!(info.artists, ["IU", "SUGA"]);
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["artist"], "IU, SUGA");
+        let json = serde_json::to_value(TrackInfo::default()).unwrap();
+        assert!(json["artist"].is_null());
+    }
+}
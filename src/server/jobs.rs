@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+/// 큐에서 실행할 작업. 결과 메시지 또는 에러 메시지를 반환한다.
+type Task = Box<dyn FnOnce() -> Result<String, String> + Send>;
+
+/// 작업 상태.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status", content = "message")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done(String),
+    Failed(String),
+}
+
+/// 작업 하나의 조회 결과.
+#[derive(Debug, Clone, Serialize)]
+pub struct Job {
+    pub id: u64,
+    /// 작업 종류 ("apply", "rename")
+    pub kind: String,
+    #[serde(flatten)]
+    pub status: JobStatus,
+}
+
+/// 파일을 바꾸는 작업을 순서대로 하나씩 실행하는 백그라운드 큐.
+/// 같은 파일을 동시에 기록하지 않도록 작업자 스레드는 하나만 둔다.
+#[derive(Clone)]
+pub struct JobQueue {
+    jobs: Arc<Mutex<HashMap<u64, Job>>>,
+    next_id: Arc<Mutex<u64>>,
+    tx: mpsc::Sender<(u64, Task)>,
+}
+
+impl JobQueue {
+    /// 큐와 작업자 스레드를 시작한다.
+    pub fn start() -> Self {
+        let (tx, rx) = mpsc::channel::<(u64, Task)>();
+        let jobs: Arc<Mutex<HashMap<u64, Job>>> = Arc::new(Mutex::new(HashMap::new()));
+
+        let worker_jobs = Arc::clone(&jobs);
+        std::thread::spawn(move || {
+            for (id, task) in rx {
+                set_status(&worker_jobs, id, JobStatus::Running);
+                let status = match task() {
+                    Ok(message) => JobStatus::Done(message),
+                    Err(message) => JobStatus::Failed(message),
+                };
+                set_status(&worker_jobs, id, status);
+            }
+        });
+
+        Self {
+            jobs,
+            next_id: Arc::new(Mutex::new(1)),
+            tx,
+        }
+    }
+
+    /// 작업을 큐에 넣고 작업 ID를 반환한다.
+    pub fn submit(
+        &self,
+        kind: &str,
+        task: impl FnOnce() -> Result<String, String> + Send + 'static,
+    ) -> u64 {
+        let id = {
+            let mut next = self.next_id.lock().unwrap();
+            let id = *next;
+            *next += 1;
+            id
+        };
+        self.jobs.lock().unwrap().insert(
+            id,
+            Job {
+                id,
+                kind: kind.to_string(),
+                status: JobStatus::Queued,
+            },
+        );
+        if self.tx.send((id, Box::new(task))).is_err() {
+            set_status(
+                &self.jobs,
+                id,
+                JobStatus::Failed("작업자 스레드가 종료되었습니다".to_string()),
+            );
+        }
+        id
+    }
+
+    /// 작업 상태를 조회한다.
+    pub fn get(&self, id: u64) -> Option<Job> {
+        self.jobs.lock().unwrap().get(&id).cloned()
+    }
+}
+
+fn set_status(jobs: &Mutex<HashMap<u64, Job>>, id: u64, status: JobStatus) {
+    if let Some(job) = jobs.lock().unwrap().get_mut(&id) {
+        job.status = status;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn wait_finished(queue: &JobQueue, id: u64) -> JobStatus {
+        for _ in 0..100 {
+            let status = queue.get(id).unwrap().status;
+            if matches!(status, JobStatus::Done(_) | JobStatus::Failed(_)) {
+                return status;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("작업이 끝나지 않았습니다");
+    }
+
+    #[test]
+    fn test_jobs_run_in_order() {
+        let queue = JobQueue::start();
+        let first = queue.submit("apply", || Ok("1건 적용".to_string()));
+        let second = queue.submit("rename", || Err("실패".to_string()));
+
+        assert_eq!(first, 1);
+        assert_eq!(second, 2);
+        assert_eq!(
+            wait_finished(&queue, first),
+            JobStatus::Done("1건 적용".to_string())
+        );
+        assert_eq!(
+            wait_finished(&queue, second),
+            JobStatus::Failed("실패".to_string())
+        );
+        assert!(queue.get(3).is_none());
+    }
+
+    #[test]
+    fn test_job_json() {
+        let job = Job {
+            id: 7,
+            kind: "apply".to_string(),
+            status: JobStatus::Done("ok".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(&job).unwrap(),
+            serde_json::json!({"id": 7, "kind": "apply", "status": "done", "message": "ok"})
+        );
+    }
+}
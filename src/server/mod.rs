@@ -0,0 +1,651 @@
+pub mod jobs;
+#[cfg(feature = "web")]
+mod web;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::core::renamer::{self, RenameOutcome};
+use crate::core::{scanner, tagger};
+use crate::models::TrackInfo;
+use crate::sources::enrich::Enricher;
+use crate::sources::{self, SourceKind};
+use jobs::JobQueue;
+
+/// `mp3tag serve`의 HTTP+JSON API 서버.
+/// 조회(scan, search)는 요청 스레드에서 바로 처리하고,
+/// 파일을 바꾸는 작업(apply, rename)은 작업 큐에 넣어 작업 ID를 돌려준다.
+/// `web` 기능으로 빌드하면 `/`에서 이 API를 쓰는 웹 UI도 제공한다.
+///
+/// 모든 요청은 Host가 localhost(또는 `--addr`로 지정한 주소)여야 하고, Origin이 있으면 Host와
+/// 같아야 한다. `/api` 요청은 실행할 때마다 새로 만드는 토큰을 `X-Mp3tag-Token` 헤더나
+/// `token` 쿼리로 보내야 하며, 경로를 받는 API는 라이브러리 루트(`music_dir`, 없으면 현재
+/// 디렉토리) 밖의 경로를 거부한다 (`ApiServer::authorize`, `ApiServer::library_path`).
+///
+/// | 메서드 | 경로 | 설명 |
+/// |---|---|---|
+/// | GET | `/api/health` | 버전 확인 |
+/// | GET | `/api/sources` | 검색 소스 목록 |
+/// | GET | `/api/scan?path=` | 파일 목록과 현재 태그 |
+/// | GET | `/api/art?path=` | 파일의 앨범 아트 이미지 |
+/// | GET | `/api/search?q=&source=` | 소스 검색 (기본 spotify) |
+/// | POST | `/api/apply` | `{"path", "track", "detail"}` 태그 기록 작업 |
+/// | POST | `/api/rename` | `{"path"}` 태그 기반 파일명 변경 작업 |
+/// | GET | `/api/jobs/{id}` | 작업 상태 |
+pub struct ApiServer {
+    cfg: Arc<Config>,
+    jobs: JobQueue,
+    /// 이번 실행에서만 쓰는 API 토큰
+    token: String,
+    /// 경로를 받는 API가 허용하는 디렉토리 (정규화된 경로)
+    root: Option<PathBuf>,
+    /// localhost 말고 Host로 받아들일 이름 (바인딩 주소, `--allow-host`)
+    allowed_hosts: Vec<String>,
+}
+
+/// `/api` 요청에 토큰을 담는 헤더.
+pub const TOKEN_HEADER: &str = "X-Mp3tag-Token";
+
+/// Host 헤더로 받아들이는 루프백 이름.
+const LOOPBACK_HOSTS: &[&str] = &["localhost", "127.0.0.1", "[::1]"];
+
+/// API 응답: HTTP 상태 코드와 JSON 본문.
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn accepted(job_id: u64) -> Self {
+        Self {
+            status: 202,
+            body: json!({ "job_id": job_id }),
+        }
+    }
+
+    fn error(status: u16, message: impl std::fmt::Display) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.to_string() }),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ApplyRequest {
+    path: PathBuf,
+    track: TrackInfo,
+    /// 기록 전에 트랙의 소스에서 상세 정보(장르, 앨범 아트 등)를 가져온다.
+    #[serde(default)]
+    detail: bool,
+}
+
+#[derive(Deserialize)]
+struct RenameRequest {
+    path: PathBuf,
+}
+
+impl ApiServer {
+    pub fn new(cfg: Config) -> Self {
+        let root = cfg
+            .music_dir
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .and_then(|dir| std::fs::canonicalize(dir).ok());
+        Self {
+            cfg: Arc::new(cfg),
+            jobs: JobQueue::start(),
+            token: format!("{:032x}", rand::random::<u128>()),
+            root,
+            allowed_hosts: Vec::new(),
+        }
+    }
+
+    /// 이번 실행의 API 토큰.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// `addr`(예: `192.168.0.10:7878`)의 호스트도 Host 헤더로 받아들인다.
+    /// 모든 주소(`0.0.0.0`, `[::]`)에 바인딩하면 [`allow_host`](Self::allow_host)로
+    /// 지정한 이름과 localhost만 받는다.
+    fn allow_bind_host(&mut self, addr: &str) {
+        let host = addr.rsplit_once(':').map_or(addr, |(host, _)| host);
+        if !matches!(host, "0.0.0.0" | "[::]" | "") {
+            self.allow_host(host);
+        }
+    }
+
+    /// `host`(예: `nas.local`, `192.168.0.10`)를 Host 헤더로 받아들인다.
+    /// IPv6 주소는 `[::1]`처럼 대괄호로 감싼다.
+    fn allow_host(&mut self, host: &str) {
+        let host = host.to_string();
+        if !self.allowed_hosts.contains(&host) {
+            self.allowed_hosts.push(host);
+        }
+    }
+
+    /// 요청을 처리하기 전에 Host, Origin, 토큰, Content-Type을 확인한다.
+    /// 거부하면 그 응답을 반환한다. `headers`의 이름은 대소문자를 가리지 않는다.
+    pub fn authorize(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(String, String)],
+    ) -> Result<(), Response> {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        };
+
+        // DNS 리바인딩 방지: 다른 도메인 이름으로 들어온 요청은 받지 않는다
+        let host = header("Host").unwrap_or_default();
+        let host_name = match host.strip_prefix('[') {
+            Some(rest) => rest
+                .split_once(']')
+                .map_or(host, |(name, _)| &host[..name.len() + 2]),
+            None => host.split(':').next().unwrap_or_default(),
+        };
+        if !LOOPBACK_HOSTS.contains(&host_name)
+            && !self
+                .allowed_hosts
+                .iter()
+                .any(|allowed| allowed == host_name)
+        {
+            return Err(Response::error(403, "허용되지 않은 Host입니다"));
+        }
+        // 다른 사이트의 페이지에서 보낸 요청은 받지 않는다
+        if let Some(origin) = header("Origin") {
+            if origin != format!("http://{}", host) {
+                return Err(Response::error(403, "허용되지 않은 Origin입니다"));
+            }
+        }
+
+        let Ok(parsed) = reqwest::Url::parse(&format!("http://localhost{}", url)) else {
+            return Err(Response::error(400, "잘못된 요청 경로입니다"));
+        };
+        if !parsed.path().starts_with("/api/") {
+            return Ok(());
+        }
+        let query_token = parsed
+            .query_pairs()
+            .find(|(k, _)| k == "token")
+            .map(|(_, v)| v.into_owned());
+        let token = header(TOKEN_HEADER).map(str::to_string).or(query_token);
+        if token.as_deref() != Some(self.token.as_str()) {
+            return Err(Response::error(401, "API 토큰이 필요합니다"));
+        }
+
+        // 폼 전송처럼 사전 요청 없이 보낼 수 있는 형식은 받지 않는다
+        let mutating = matches!(parsed.path(), "/api/apply" | "/api/rename");
+        let json = header("Content-Type")
+            .and_then(|v| v.split(';').next())
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("application/json"));
+        if method == "POST" && mutating && !json {
+            return Err(Response::error(
+                415,
+                "Content-Type: application/json이 필요합니다",
+            ));
+        }
+        Ok(())
+    }
+
+    /// `path`를 정규화해 라이브러리 루트 아래에 있으면 반환하고, 아니면 403 응답을 반환한다.
+    fn library_path(&self, path: &Path) -> Result<PathBuf, Response> {
+        let Some(root) = &self.root else {
+            return Err(Response::error(
+                403,
+                "라이브러리 루트를 찾을 수 없습니다. config.toml의 music_dir을 설정하세요",
+            ));
+        };
+        match std::fs::canonicalize(path) {
+            Ok(path) if path.starts_with(root) => Ok(path),
+            _ => Err(Response::error(
+                403,
+                format!("라이브러리 루트({}) 밖의 경로입니다", root.display()),
+            )),
+        }
+    }
+
+    /// 요청 하나를 처리한다. `url`은 쿼리 문자열을 포함한 경로다.
+    pub fn handle(&self, method: &str, url: &str, body: &str) -> Response {
+        let Ok(parsed) = reqwest::Url::parse(&format!("http://localhost{}", url)) else {
+            return Response::error(400, "잘못된 요청 경로입니다");
+        };
+        let query = |key: &str| {
+            parsed
+                .query_pairs()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.into_owned())
+        };
+
+        let result = match (method, parsed.path()) {
+            ("GET", "/api/health") => Ok(Response::ok(
+                json!({ "version": env!("CARGO_PKG_VERSION") }),
+            )),
+            ("GET", "/api/sources") => Ok(Response::ok(json!(SourceKind::all(&self.cfg)
+                .iter()
+                .map(|k| json!({ "name": k.name(), "label": k.label() }))
+                .collect::<Vec<_>>()))),
+            ("GET", "/api/scan") => self.scan(query("path")),
+            // 앨범 아트가 있으면 `handle_file`이 먼저 응답한다
+            ("GET", "/api/art") => match query("path") {
+                Some(path) => Ok(match self.library_path(Path::new(&path)) {
+                    Ok(_) => Response::error(404, "앨범 아트가 없습니다"),
+                    Err(res) => res,
+                }),
+                None => Err(anyhow!("path 파라미터가 필요합니다")),
+            },
+            ("GET", "/api/search") => self.search(query("q"), query("source")),
+            ("POST", "/api/apply") => self.apply(body),
+            ("POST", "/api/rename") => self.rename(body),
+            ("GET", path) if path.starts_with("/api/jobs/") => {
+                let id = path["/api/jobs/".len()..].parse::<u64>().ok();
+                Ok(match id.and_then(|id| self.jobs.get(id)) {
+                    Some(job) => Response::ok(json!(job)),
+                    None => Response::error(404, "작업을 찾을 수 없습니다"),
+                })
+            }
+            _ => Ok(Response::error(404, "지원하지 않는 API입니다")),
+        };
+        result.unwrap_or_else(|e| Response::error(400, format!("{:#}", e)))
+    }
+
+    /// JSON이 아닌 응답(웹 UI 정적 파일, 앨범 아트)을 처리한다.
+    /// 해당하지 않는 경로면 None을 반환하고 `handle`로 넘긴다.
+    pub fn handle_file(&self, url: &str) -> Option<(String, Vec<u8>)> {
+        let parsed = reqwest::Url::parse(&format!("http://localhost{}", url)).ok()?;
+        #[cfg(feature = "web")]
+        if let Some((content_type, body)) = web::asset(parsed.path()) {
+            return Some((content_type.to_string(), body.as_bytes().to_vec()));
+        }
+        if parsed.path() != "/api/art" {
+            return None;
+        }
+        let path = parsed
+            .query_pairs()
+            .find(|(k, _)| k == "path")
+            .map(|(_, v)| PathBuf::from(v.as_ref()))?;
+        let path = self.library_path(&path).ok()?;
+        let art = tagger::read_tags(&path).ok()??.album_art?;
+        Some((tagger::detect_mime_type(&art), art))
+    }
+
+    fn scan(&self, path: Option<String>) -> Result<Response> {
+        let path = path.context("path 파라미터가 필요합니다")?;
+        let path = match self.library_path(Path::new(&path)) {
+            Ok(path) => path,
+            Err(res) => return Ok(res),
+        };
+        let files = scanner::scan_path(&path)?;
+        Ok(Response::ok(json!(files)))
+    }
+
+    fn search(&self, q: Option<String>, source: Option<String>) -> Result<Response> {
+        let q = q.context("q 파라미터가 필요합니다")?;
+        let kind: SourceKind = source
+            .as_deref()
+            .unwrap_or("spotify")
+            .parse()
+            .map_err(|e: String| anyhow!(e))?;
+        let client = sources::create_source(&kind, &self.cfg)?;
+        Ok(Response::ok(json!(client.search(&q)?)))
+    }
+
+    fn apply(&self, body: &str) -> Result<Response> {
+        let mut req: ApplyRequest =
+            serde_json::from_str(body).context("요청 본문 파싱에 실패했습니다")?;
+        req.path = match self.library_path(&req.path) {
+            Ok(path) => path,
+            Err(res) => return Ok(res),
+        };
+        tagger::check_writable(&req.path, &self.cfg.write)?;
+
+        let cfg = Arc::clone(&self.cfg);
+        let id = self.jobs.submit("apply", move || {
+            apply_track(&cfg, &req).map_err(|e| format!("{:#}", e))
+        });
+        Ok(Response::accepted(id))
+    }
+
+    fn rename(&self, body: &str) -> Result<Response> {
+        let mut req: RenameRequest =
+            serde_json::from_str(body).context("요청 본문 파싱에 실패했습니다")?;
+        req.path = match self.library_path(&req.path) {
+            Ok(path) => path,
+            Err(res) => return Ok(res),
+        };
+        let cfg = Arc::clone(&self.cfg);
+        let id = self.jobs.submit("rename", move || {
+            rename_files(&cfg, &req.path).map_err(|e| format!("{:#}", e))
+        });
+        Ok(Response::accepted(id))
+    }
+}
+
+/// 트랙을 보충(상세 정보, Genius, 커버)한 뒤 파일에 기록한다.
+fn apply_track(cfg: &Config, req: &ApplyRequest) -> Result<String> {
+    let mut track = req.track.clone();
+    if req.detail {
+        if let Some(kind) = SourceKind::from_source_name(&track.source, cfg) {
+            let client = sources::create_source(&kind, cfg)?;
+            track = client.fetch_detail(&track).unwrap_or(track);
+        }
+    }
+
+    // 직접 입력한 태그는 CLI의 edit처럼 그대로 기록한다.
+    let (track, note) = if track.source == "manual" {
+        (track, None)
+    } else {
+        let existing = tagger::read_tags(&req.path).ok().flatten();
+        Enricher::new(cfg)?.apply(existing.as_ref(), track)
+    };
+    tagger::write_tags(&req.path, &track, &cfg.write)?;
+
+    let mut message = format!("태그가 적용되었습니다: {}", track.summary());
+    if let Some(note) = note {
+        message.push_str(&format!(" ({})", note));
+    }
+    Ok(message)
+}
+
+/// 경로의 파일들을 태그 기반으로 이름을 바꾼다. 충돌 전략이 prompt이면 건너뛴다.
+fn rename_files(cfg: &Config, path: &Path) -> Result<String> {
+    let files = scanner::scan_path(path)?;
+    let (mut success, mut failed, mut skipped) = (0, 0, 0);
+    for file in &files {
+        let Some(ref tags) = file.current_tags else {
+            skipped += 1;
+            continue;
+        };
+        match renamer::rename_file(&file.path, tags, &cfg.rename) {
+            Ok(RenameOutcome::Renamed(_)) => success += 1,
+            Ok(_) => skipped += 1,
+            Err(_) => failed += 1,
+        }
+    }
+    Ok(format!(
+        "파일명 변경 완료: 성공 {}건, 실패 {}건, 스킵 {}건",
+        success, failed, skipped
+    ))
+}
+
+/// 요청을 동시에 처리하는 작업 스레드 수.
+const WORKERS: usize = 8;
+
+/// `addr`에서 요청을 받아 처리한다. `allow_hosts`는 바인딩 주소 외에 Host로 받아들일 이름이다.
+/// 요청은 [`WORKERS`]개의 스레드가 나눠 처리하고, 모두 바쁘면 앞의 요청이 끝날 때까지 기다린다.
+pub fn serve(cfg: Config, addr: &str, allow_hosts: &[String]) -> Result<()> {
+    let http = tiny_http::Server::http(addr)
+        .map_err(|e| anyhow!("{}에서 서버를 시작할 수 없습니다: {}", addr, e))?;
+    let http = Arc::new(http);
+    let mut api = ApiServer::new(cfg);
+    api.allow_bind_host(addr);
+    for host in allow_hosts {
+        api.allow_host(host);
+    }
+    let api = Arc::new(api);
+    println!(
+        "API 서버를 시작했습니다: http://{}/?token={}",
+        addr,
+        api.token()
+    );
+    println!("API 토큰 ({} 헤더): {}", TOKEN_HEADER, api.token());
+
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            let http = Arc::clone(&http);
+            let api = Arc::clone(&api);
+            std::thread::spawn(move || {
+                while let Ok(request) = http.recv() {
+                    handle_request(&api, request);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+    Ok(())
+}
+
+/// 요청 하나의 권한을 확인하고 파일 또는 API 응답을 보낸다.
+fn handle_request(api: &ApiServer, mut request: tiny_http::Request) {
+    let headers: Vec<(String, String)> = request
+        .headers()
+        .iter()
+        .map(|h| (h.field.as_str().to_string(), h.value.to_string()))
+        .collect();
+    if let Err(response) = api.authorize(request.method().as_str(), request.url(), &headers) {
+        return respond_json(request, response);
+    }
+    if *request.method() == tiny_http::Method::Get {
+        if let Some((content_type, data)) = api.handle_file(request.url()) {
+            return respond(request, 200, content_type, data);
+        }
+    }
+    let mut body = String::new();
+    let response = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => api.handle(request.method().as_str(), request.url(), &body),
+        Err(_) => Response::error(400, "요청 본문을 읽을 수 없습니다"),
+    };
+    respond_json(request, response);
+}
+
+/// JSON 응답을 보낸다.
+fn respond_json(request: tiny_http::Request, response: Response) {
+    respond(
+        request,
+        response.status,
+        "application/json".to_string(),
+        response.body.to_string().into_bytes(),
+    );
+}
+
+/// `content_type`의 본문으로 응답한다.
+fn respond(request: tiny_http::Request, status: u16, content_type: String, data: Vec<u8>) {
+    let header =
+        tiny_http::Header::from_bytes("Content-Type", content_type).expect("ASCII Content-Type");
+    let http_response = tiny_http::Response::from_data(data)
+        .with_status_code(status)
+        .with_header(header);
+    let _ = request.respond(http_response);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::time::Duration;
+
+    fn wait_job(api: &ApiServer, id: u64) -> Value {
+        for _ in 0..200 {
+            let res = api.handle("GET", &format!("/api/jobs/{}", id), "");
+            if res.body["status"] != "queued" && res.body["status"] != "running" {
+                return res.body;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("작업이 끝나지 않았습니다");
+    }
+
+    #[test]
+    fn test_routes() {
+        let api = ApiServer::new(Config::default());
+        assert_eq!(api.handle("GET", "/api/health", "").status, 200);
+        assert_eq!(
+            api.handle("GET", "/api/sources", "").body[1]["name"],
+            "melon"
+        );
+        assert!(api.handle_file("/api/health").is_none());
+        assert!(api.handle_file("/api/art?path=/nonexistent.mp3").is_none());
+        assert_eq!(api.handle("GET", "/api/unknown", "").status, 404);
+        assert_eq!(api.handle("GET", "/api/jobs/99", "").status, 404);
+        assert_eq!(api.handle("GET", "/api/scan", "").status, 400);
+        assert_eq!(api.handle("POST", "/api/apply", "not json").status, 400);
+    }
+
+    #[test]
+    fn test_scan_apply_and_rename() {
+        let dir = TempDir::new("server-apply");
+        let path = dir.join("track01.mp3");
+        std::fs::write(&path, b"AUDIO-DATA").unwrap();
+
+        let cfg = Config {
+            music_dir: Some(dir.to_path_buf()),
+            ..Default::default()
+        };
+        let api = ApiServer::new(cfg);
+        let scan = api.handle("GET", &format!("/api/scan?path={}", dir.display()), "");
+        assert_eq!(scan.status, 200);
+        assert_eq!(scan.body[0]["has_tags"], false);
+
+        let body = json!({
+            "path": path,
+            "track": {"title": "Blueming", "artist": "IU", "source": "manual"},
+        });
+        let res = api.handle("POST", "/api/apply", &body.to_string());
+        assert_eq!(res.status, 202);
+        let job = wait_job(&api, res.body["job_id"].as_u64().unwrap());
+        assert_eq!(job["status"], "done", "{}", job);
+        assert_eq!(
+            tagger::read_tags(&path).unwrap().unwrap().title.as_deref(),
+            Some("Blueming")
+        );
+
+        let res = api.handle("POST", "/api/rename", &json!({ "path": &*dir }).to_string());
+        let job = wait_job(&api, res.body["job_id"].as_u64().unwrap());
+        assert_eq!(job["status"], "done", "{}", job);
+        assert!(dir.join("IU - Blueming.mp3").exists());
+    }
+
+    #[test]
+    fn test_rejects_paths_outside_library() {
+        let root = TempDir::new("server-root");
+        let outside = TempDir::new("server-outside");
+        let path = outside.join("track01.mp3");
+        std::fs::write(&path, b"AUDIO-DATA").unwrap();
+        let cfg = Config {
+            music_dir: Some(root.to_path_buf()),
+            ..Default::default()
+        };
+        let api = ApiServer::new(cfg);
+
+        let scan = format!("/api/scan?path={}", outside.display());
+        assert_eq!(api.handle("GET", &scan, "").status, 403);
+        let escape = format!("/api/scan?path={}/../{}", root.display(), outside.display());
+        assert_eq!(api.handle("GET", &escape, "").status, 403);
+        assert_eq!(
+            api.handle("GET", &format!("/api/scan?path={}", root.display()), "")
+                .status,
+            200
+        );
+        let art = format!("/api/art?path={}", path.display());
+        assert!(api.handle_file(&art).is_none());
+        assert_eq!(api.handle("GET", &art, "").status, 403);
+        let body = json!({ "path": path, "track": {"title": "x", "source": "manual"} });
+        assert_eq!(
+            api.handle("POST", "/api/apply", &body.to_string()).status,
+            403
+        );
+        let body = json!({ "path": &*outside });
+        assert_eq!(
+            api.handle("POST", "/api/rename", &body.to_string()).status,
+            403
+        );
+    }
+
+    #[test]
+    fn test_authorize() {
+        let mut api = ApiServer::new(Config::default());
+        api.allow_bind_host("192.168.0.10:7878");
+        api.allow_host("nas.local");
+        let token = api.token().to_string();
+        let headers = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let status = |method: &str, url: &str, pairs: &[(&str, &str)]| {
+            api.authorize(method, url, &headers(pairs))
+                .err()
+                .map(|res| res.status)
+        };
+        let host = ("Host", "127.0.0.1:7878");
+        let auth = (TOKEN_HEADER, token.as_str());
+        let json = ("Content-Type", "application/json; charset=utf-8");
+
+        // 정적 파일은 토큰 없이, API는 헤더나 쿼리의 토큰으로
+        assert_eq!(status("GET", "/", &[host]), None);
+        assert_eq!(status("GET", "/api/health", &[host, auth]), None);
+        assert_eq!(
+            status("GET", "/api/health", &[("host", "[::1]:7878"), auth]),
+            None
+        );
+        assert_eq!(
+            status("GET", "/api/health", &[("Host", "192.168.0.10:7878"), auth]),
+            None
+        );
+        assert_eq!(
+            status("GET", "/api/health", &[("Host", "nas.local:7878"), auth]),
+            None
+        );
+        let art = format!("/api/art?path=a.mp3&token={}", token);
+        assert_eq!(status("GET", &art, &[host]), None);
+        assert_eq!(status("GET", "/api/health", &[host]), Some(401));
+        assert_eq!(
+            status("GET", "/api/health", &[host, (TOKEN_HEADER, "wrong")]),
+            Some(401)
+        );
+
+        // 다른 이름으로 들어온 요청(DNS 리바인딩)과 다른 사이트의 Origin은 거부
+        assert_eq!(status("GET", "/", &[]), Some(403));
+        assert_eq!(
+            status("GET", "/", &[("Host", "evil.example:7878")]),
+            Some(403)
+        );
+        let mut wildcard = ApiServer::new(Config::default());
+        wildcard.allow_bind_host("0.0.0.0:7878");
+        assert!(wildcard.allowed_hosts.is_empty());
+        assert_eq!(
+            status(
+                "GET",
+                "/api/health",
+                &[host, auth, ("Origin", "http://evil.example")]
+            ),
+            Some(403)
+        );
+        assert_eq!(
+            status(
+                "GET",
+                "/api/health",
+                &[host, auth, ("Origin", "http://127.0.0.1:7878")]
+            ),
+            None
+        );
+
+        // 파일을 바꾸는 요청은 JSON 본문만 받는다
+        assert_eq!(status("POST", "/api/apply", &[host, auth, json]), None);
+        assert_eq!(status("POST", "/api/rename", &[host, auth]), Some(415));
+        assert_eq!(
+            status(
+                "POST",
+                "/api/apply",
+                &[host, auth, ("Content-Type", "text/plain")]
+            ),
+            Some(415)
+        );
+    }
+}
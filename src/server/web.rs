@@ -0,0 +1,28 @@
+/// serve 모드에 내장된 웹 UI 정적 파일.
+const INDEX_HTML: &str = include_str!("web/index.html");
+const APP_JS: &str = include_str!("web/app.js");
+const STYLE_CSS: &str = include_str!("web/style.css");
+
+/// 경로에 해당하는 정적 파일의 (Content-Type, 내용)을 반환한다.
+pub fn asset(path: &str) -> Option<(&'static str, &'static str)> {
+    match path {
+        "/" | "/index.html" => Some(("text/html; charset=utf-8", INDEX_HTML)),
+        "/app.js" => Some(("text/javascript; charset=utf-8", APP_JS)),
+        "/style.css" => Some(("text/css; charset=utf-8", STYLE_CSS)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assets() {
+        let (content_type, body) = asset("/").unwrap();
+        assert!(content_type.starts_with("text/html"));
+        assert!(body.contains("/app.js"));
+        assert!(asset("/app.js").is_some());
+        assert!(asset("/api/health").is_none());
+    }
+}
@@ -0,0 +1,186 @@
+// mp3tag 웹 UI. serve 모드의 /api를 사용해 GUI와 같은 흐름(스캔 → 선택 → 편집/검색 → 적용)을 제공한다.
+
+const state = { files: [], selected: null, results: [] };
+
+const $ = (id) => document.getElementById(id);
+
+// serve가 시작할 때 출력한 주소(`/?token=...`)의 토큰을 모든 API 요청에 붙인다.
+const token = new URLSearchParams(location.search).get("token") || "";
+
+function setStatus(message) {
+  $("status").textContent = message;
+}
+
+async function api(method, path, body) {
+  const res = await fetch(path, {
+    method,
+    headers: {
+      "X-Mp3tag-Token": token,
+      ...(body ? { "Content-Type": "application/json" } : {}),
+    },
+    body: body ? JSON.stringify(body) : undefined,
+  });
+  const data = await res.json();
+  if (!res.ok) {
+    throw new Error(data.error || res.statusText);
+  }
+  return data;
+}
+
+// 작업 큐에 들어간 작업이 끝날 때까지 기다린다.
+async function waitJob(jobId) {
+  for (;;) {
+    const job = await api("GET", `/api/jobs/${jobId}`);
+    if (job.status === "done") return job.message;
+    if (job.status === "failed") throw new Error(job.message);
+    await new Promise((r) => setTimeout(r, 300));
+  }
+}
+
+function fileName(path) {
+  return path.split(/[\\/]/).pop();
+}
+
+function renderFiles() {
+  const rows = $("file-rows");
+  rows.replaceChildren();
+  state.files.forEach((file, i) => {
+    const tags = file.current_tags || {};
+    const tr = document.createElement("tr");
+    if (!file.has_tags) tr.classList.add("untagged");
+    if (i === state.selected) tr.classList.add("selected");
+    for (const text of [fileName(file.path), tags.title, tags.artist, tags.album]) {
+      const td = document.createElement("td");
+      td.textContent = text || "";
+      tr.appendChild(td);
+    }
+    tr.addEventListener("click", () => selectFile(i));
+    rows.appendChild(tr);
+  });
+}
+
+function selectFile(index) {
+  state.selected = index;
+  const file = state.files[index];
+  const tags = file.current_tags || {};
+  const form = $("edit");
+  for (const input of form.querySelectorAll("input")) {
+    const value = tags[input.name];
+    input.value = value === null || value === undefined ? "" : value;
+  }
+  $("art").src = `/api/art?path=${encodeURIComponent(file.path)}&token=${token}&t=${Date.now()}`;
+  $("query").value = [tags.artist, tags.title].filter(Boolean).join(" ") ||
+    fileName(file.path).replace(/\.mp3$/i, "");
+  renderFiles();
+}
+
+async function scan() {
+  const dir = $("dir").value.trim();
+  if (!dir) return;
+  setStatus("스캔 중...");
+  try {
+    state.files = await api("GET", `/api/scan?path=${encodeURIComponent(dir)}`);
+    state.selected = null;
+    renderFiles();
+    const untagged = state.files.filter((f) => !f.has_tags).length;
+    setStatus(`${state.files.length}개 파일 (태그 없음 ${untagged}개)`);
+    localStorage.setItem("mp3tag.dir", dir);
+  } catch (e) {
+    setStatus(`스캔 실패: ${e.message}`);
+  }
+}
+
+// 파일 하나의 태그를 다시 읽어 목록을 갱신한다.
+async function reloadFile(index) {
+  const [file] = await api("GET", `/api/scan?path=${encodeURIComponent(state.files[index].path)}`);
+  state.files[index] = file;
+  selectFile(index);
+}
+
+async function applyTrack(track, detail) {
+  if (state.selected === null) {
+    setStatus("파일을 먼저 선택하세요");
+    return;
+  }
+  const index = state.selected;
+  setStatus("태그 적용 중...");
+  try {
+    const { job_id } = await api("POST", "/api/apply", {
+      path: state.files[index].path,
+      track,
+      detail,
+    });
+    setStatus(await waitJob(job_id));
+    await reloadFile(index);
+  } catch (e) {
+    setStatus(`적용 실패: ${e.message}`);
+  }
+}
+
+function saveEdits(event) {
+  event.preventDefault();
+  const track = { source: "manual" };
+  for (const input of $("edit").querySelectorAll("input")) {
+    const value = input.value.trim();
+    if (!value) continue;
+    track[input.name] = input.inputMode === "numeric" ? Number(value) : value;
+  }
+  applyTrack(track, false);
+}
+
+async function search(event) {
+  event.preventDefault();
+  const q = $("query").value.trim();
+  if (!q) return;
+  setStatus("검색 중...");
+  try {
+    const source = encodeURIComponent($("source").value);
+    state.results = await api("GET", `/api/search?q=${encodeURIComponent(q)}&source=${source}`);
+    const list = $("results");
+    list.replaceChildren();
+    for (const track of state.results) {
+      const li = document.createElement("li");
+      li.textContent = `${track.artist || "알 수 없음"} - ${track.title || "알 수 없음"} [${track.album || "알 수 없음"}]`;
+      li.title = "클릭하면 선택한 파일에 적용";
+      li.addEventListener("click", () => applyTrack(track, true));
+      list.appendChild(li);
+    }
+    setStatus(`검색 결과 ${state.results.length}건`);
+  } catch (e) {
+    setStatus(`검색 실패: ${e.message}`);
+  }
+}
+
+async function renameSelected() {
+  if (state.selected === null) {
+    setStatus("파일을 먼저 선택하세요");
+    return;
+  }
+  try {
+    const { job_id } = await api("POST", "/api/rename", { path: state.files[state.selected].path });
+    setStatus(await waitJob(job_id));
+    await scan();
+  } catch (e) {
+    setStatus(`파일명 변경 실패: ${e.message}`);
+  }
+}
+
+async function loadSources() {
+  const sources = await api("GET", "/api/sources");
+  for (const source of sources) {
+    const option = document.createElement("option");
+    option.value = source.name;
+    option.textContent = source.label;
+    $("source").appendChild(option);
+  }
+}
+
+$("scan").addEventListener("click", scan);
+$("dir").addEventListener("keydown", (e) => e.key === "Enter" && scan());
+$("rename").addEventListener("click", renameSelected);
+$("edit").addEventListener("submit", saveEdits);
+$("search").addEventListener("submit", search);
+$("art").addEventListener("error", (e) => e.target.removeAttribute("src"));
+
+$("dir").value = localStorage.getItem("mp3tag.dir") || "";
+loadSources().catch((e) => setStatus(`소스 목록을 불러오지 못했습니다: ${e.message}`));
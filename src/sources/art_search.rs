@@ -0,0 +1,222 @@
+use crate::core::{artwork, matcher};
+use crate::models::TrackInfo;
+use crate::sources::MusicSource;
+
+/// 소스마다 앨범 아트를 받아 볼 앨범 수.
+const ALBUMS_PER_SOURCE: usize = 6;
+
+/// 앨범 아트 후보 하나.
+#[derive(Debug, Clone)]
+pub struct ArtCandidate {
+    /// 출처 ("melon", "spotify", "coverartarchive" 등)
+    pub source: String,
+    /// 아트가 속한 앨범 ("아티스트 - 앨범")
+    pub album: String,
+    pub data: Vec<u8>,
+    /// (가로, 세로) 픽셀 크기. 헤더를 읽지 못하면 None.
+    pub dimensions: Option<(u32, u32)>,
+}
+
+impl ArtCandidate {
+    pub fn new(source: &str, album: String, data: Vec<u8>) -> Self {
+        Self {
+            source: source.to_string(),
+            album,
+            dimensions: artwork::image_dimensions(&data),
+            data,
+        }
+    }
+
+    /// "640x640, 85KB" 형식의 크기 설명을 반환한다.
+    pub fn size_label(&self) -> String {
+        let kb = self.data.len().div_ceil(1024);
+        match self.dimensions {
+            Some((w, h)) => format!("{}x{}, {}KB", w, h, kb),
+            None => format!("크기 알 수 없음, {}KB", kb),
+        }
+    }
+
+    /// 짧은 변 픽셀 수. 크기를 알 수 없으면 0.
+    pub fn short_side(&self) -> u32 {
+        self.dimensions.map_or(0, |(w, h)| w.min(h))
+    }
+
+    fn pixels(&self) -> u64 {
+        self.dimensions.map_or(0, |(w, h)| w as u64 * h as u64)
+    }
+}
+
+/// 여러 소스에서 쿼리로 트랙을 검색해 앨범마다 앨범 아트를 하나씩 받아 `first` 뒤에 더한다.
+/// 같은 이미지는 한 번만 남기고 픽셀 수가 큰 순서로 정렬한다 (같으면 먼저 찾은 순서).
+/// 검색이나 다운로드에 실패한 소스와 앨범은 건너뛴다.
+#[cfg(feature = "gui")]
+pub fn search_art<S: AsRef<dyn MusicSource>>(
+    sources: &[S],
+    query: &str,
+    first: Vec<ArtCandidate>,
+) -> Vec<ArtCandidate> {
+    collect_art(sources, query, first, |_| true)
+}
+
+/// `search_art`와 같지만 앨범 이름이 같고(공백/구두점/대소문자 무시) 아티스트가 맞는 검색 결과의
+/// 아트만 모은다. 파일에 있는 앨범의 커버를 찾을 때 다른 앨범의 커버가 섞이지 않게 한다.
+pub fn search_album_art<S: AsRef<dyn MusicSource>>(
+    sources: &[S],
+    artist: &str,
+    album: &str,
+    first: Vec<ArtCandidate>,
+) -> Vec<ArtCandidate> {
+    let query = format!("{} {}", artist, album);
+    collect_art(sources, query.trim(), first, |track| {
+        is_same_album(track, artist, album)
+    })
+}
+
+/// 큰 순서로 정렬된 후보 중 짧은 변이 `current` 픽셀보다 큰 첫 후보를 고른다.
+pub fn pick_larger(candidates: Vec<ArtCandidate>, current: u32) -> Option<ArtCandidate> {
+    candidates.into_iter().find(|c| c.short_side() > current)
+}
+
+/// 검색 결과가 주어진 아티스트(비어 있으면 확인하지 않음)의 같은 앨범인지 확인한다.
+fn is_same_album(track: &TrackInfo, artist: &str, album: &str) -> bool {
+    let aliases = matcher::Aliases::default();
+    let same = |a: &str, b: &str| aliases.match_key(a) == aliases.match_key(b);
+    let album_matches = track.album.as_deref().is_some_and(|a| same(a, album));
+    let artist_matches = artist.is_empty()
+        || track
+            .album_artist
+            .as_deref()
+            .is_some_and(|a| same(a, artist))
+        || track.artists.iter().any(|a| same(a, artist));
+    album_matches && artist_matches
+}
+
+fn collect_art<S: AsRef<dyn MusicSource>>(
+    sources: &[S],
+    query: &str,
+    first: Vec<ArtCandidate>,
+    keep: impl Fn(&TrackInfo) -> bool,
+) -> Vec<ArtCandidate> {
+    let mut candidates = first;
+    for source in sources {
+        let source = source.as_ref();
+        let Ok(results) = source.search(query) else {
+            continue;
+        };
+        let mut albums = Vec::new();
+        for track in results.into_iter().filter(|t| keep(t)) {
+            let key = (
+                track.album_artist.clone().or_else(|| track.artist()),
+                track.album.clone(),
+            );
+            if albums.contains(&key) {
+                continue;
+            }
+            if albums.len() >= ALBUMS_PER_SOURCE {
+                break;
+            }
+            albums.push(key);
+
+            let Ok(data) = source.fetch_album_art(&track) else {
+                continue;
+            };
+            if data.is_empty() || candidates.iter().any(|c| c.data == data) {
+                continue;
+            }
+            let album = format!("{} - {}", track.display_artist(), track.display_album());
+            candidates.push(ArtCandidate::new(&track.source, album, data));
+        }
+    }
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.pixels()));
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TrackInfo;
+    use anyhow::{bail, Result};
+
+    /// 앨범 이름을 PNG 가로 크기로 삼아 아트를 돌려주는 소스.
+    struct FakeSource(Vec<(&'static str, u32)>);
+
+    fn png(width: u32) -> Vec<u8> {
+        let mut data = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&width.to_be_bytes());
+        data
+    }
+
+    impl MusicSource for FakeSource {
+        fn search(&self, _query: &str) -> Result<Vec<TrackInfo>> {
+            Ok(self
+                .0
+                .iter()
+                .map(|(album, size)| TrackInfo {
+                    album: Some(album.to_string()),
+                    artists: vec!["IU".to_string()],
+                    track_number: Some(*size),
+                    source: "fake".to_string(),
+                    ..Default::default()
+                })
+                .collect())
+        }
+
+        fn fetch_album_art(&self, track: &TrackInfo) -> Result<Vec<u8>> {
+            match track.track_number {
+                Some(0) => bail!("없음"),
+                Some(size) => Ok(png(size)),
+                None => bail!("없음"),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "gui")]
+    fn test_search_art_dedupes_and_sorts_by_size() {
+        let sources: Vec<Box<dyn MusicSource>> = vec![
+            Box::new(FakeSource(vec![
+                ("Palette", 300),
+                ("Palette", 640), // 같은 앨범은 하나만
+                ("Broken", 0),    // 다운로드 실패
+            ])),
+            Box::new(FakeSource(vec![("Palette", 300), ("Love poem", 1000)])),
+        ];
+        let first = vec![ArtCandidate::new(
+            "coverartarchive",
+            "IU - Palette".to_string(),
+            png(500),
+        )];
+
+        let candidates = search_art(&sources, "IU Palette", first);
+        let sizes: Vec<String> = candidates.iter().map(|c| c.size_label()).collect();
+        assert_eq!(sizes, ["1000x1000, 1KB", "500x500, 1KB", "300x300, 1KB"]);
+        assert_eq!(candidates[1].source, "coverartarchive");
+        assert_eq!(candidates[2].album, "IU - Palette");
+    }
+
+    #[test]
+    fn test_search_album_art_keeps_same_album() {
+        let sources: Vec<Box<dyn MusicSource>> = vec![Box::new(FakeSource(vec![
+            ("Love poem", 1000), // 다른 앨범
+            ("palette", 640),
+            ("Palette (Deluxe)", 1200),
+        ]))];
+        let candidates = search_album_art(&sources, "아이유", "Palette", Vec::new());
+        assert!(
+            candidates.is_empty(),
+            "아티스트 표기가 다르면 별칭 없이는 맞추지 않는다"
+        );
+
+        let candidates = search_album_art(&sources, "IU", "Palette", Vec::new());
+        let sizes: Vec<String> = candidates.iter().map(|c| c.size_label()).collect();
+        assert_eq!(sizes, ["640x640, 1KB"]);
+
+        assert_eq!(
+            pick_larger(candidates.clone(), 300).map(|c| c.short_side()),
+            Some(640)
+        );
+        assert!(pick_larger(candidates, 640).is_none());
+    }
+}
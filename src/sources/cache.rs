@@ -0,0 +1,354 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{MusicSource, Page};
+use crate::models::{AlbumInfo, TrackInfo};
+
+/// 소스 응답을 디스크에 보관하는 캐시.
+/// 온라인에서 받은 검색 결과, 트랙 상세 정보, 앨범 아트를 저장해 두고 오프라인 모드에서 다시 사용한다.
+/// 캐시 기록 실패는 무시한다.
+pub struct SourceCache {
+    dir: PathBuf,
+}
+
+impl SourceCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn load_search(&self, source: &str, query: &str) -> Option<Vec<TrackInfo>> {
+        self.load_json("search", source, &normalize_query(query))
+    }
+
+    pub fn store_search(&self, source: &str, query: &str, results: &[TrackInfo]) {
+        self.store_json("search", source, &normalize_query(query), &results);
+    }
+
+    /// 트랙 상세 정보를 읽는다. 앨범 아트가 캐시되어 있으면 함께 채운다.
+    pub fn load_track(&self, source: &str, id: &str) -> Option<TrackInfo> {
+        let mut track: TrackInfo = self.load_json("track", source, id)?;
+        track.album_art = self.load_art(source, &track);
+        Some(track)
+    }
+
+    pub fn store_track(&self, source: &str, id: &str, track: &TrackInfo) {
+        self.store_json("track", source, id, track);
+        if let Some(ref art) = track.album_art {
+            self.store_art(source, track, art);
+        }
+    }
+
+    pub fn load_art(&self, source: &str, track: &TrackInfo) -> Option<Vec<u8>> {
+        let key = art_key(track)?;
+        std::fs::read(self.path("art", source, key)).ok()
+    }
+
+    pub fn store_art(&self, source: &str, track: &TrackInfo, art: &[u8]) {
+        if let Some(key) = art_key(track) {
+            self.store_bytes("art", source, key, art);
+        }
+    }
+
+    /// 같은 오디오(`audio::content_hash`)에 이전에 적용한 결과를 읽는다.
+    /// 앨범 아트가 캐시되어 있으면 함께 채운다.
+    pub fn load_match(&self, content_hash: &str) -> Option<RememberedMatch> {
+        let mut remembered: RememberedMatch = self.load_json("match", "", content_hash)?;
+        let track = &mut remembered.track;
+        track.album_art = self.load_art(&track.source, track);
+        Some(remembered)
+    }
+
+    /// 오디오에 적용한 결과를 기억한다. 파일명이 바뀌거나 다른 곳으로 옮겨져도 다시 쓸 수 있다.
+    pub fn store_match(&self, content_hash: &str, remembered: &RememberedMatch) {
+        self.store_json("match", "", content_hash, remembered);
+        let track = &remembered.track;
+        if let Some(ref art) = track.album_art {
+            self.store_art(&track.source, track, art);
+        }
+    }
+
+    /// 오디오(`audio::content_hash`) 앞부분 `length`초의 Chromaprint 지문을 읽는다.
+    pub fn load_fingerprint(&self, content_hash: &str, length: u32) -> Option<Vec<u32>> {
+        self.load_json("fingerprint", &length.to_string(), content_hash)
+    }
+
+    pub fn store_fingerprint(&self, content_hash: &str, length: u32, fingerprint: &[u32]) {
+        self.store_json(
+            "fingerprint",
+            &length.to_string(),
+            content_hash,
+            fingerprint,
+        );
+    }
+
+    /// `<dir>/<kind>/<sha256(소스, 키)>`
+    fn path(&self, kind: &str, source: &str, key: &str) -> PathBuf {
+        let digest = Sha256::digest(format!("{}\n{}", source, key).as_bytes());
+        let name: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        self.dir.join(kind).join(name)
+    }
+
+    fn load_json<T: DeserializeOwned>(&self, kind: &str, source: &str, key: &str) -> Option<T> {
+        let content = std::fs::read(self.path(kind, source, key)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    fn store_json<T: Serialize + ?Sized>(&self, kind: &str, source: &str, key: &str, value: &T) {
+        if let Ok(content) = serde_json::to_vec(value) {
+            self.store_bytes(kind, source, key, &content);
+        }
+    }
+
+    fn store_bytes(&self, kind: &str, source: &str, key: &str, content: &[u8]) {
+        let path = self.path(kind, source, key);
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = std::fs::write(path, content);
+    }
+}
+
+/// 오디오 내용에 대해 기억해 둔 결과.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RememberedMatch {
+    /// 적용한 태그 (앨범 아트는 아트 캐시에 따로 저장)
+    pub track: TrackInfo,
+    /// 적용할 때의 일치 점수
+    pub score: f64,
+}
+
+/// 같은 검색어를 같은 키로 찾도록 공백과 대소문자를 정리한다.
+fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// 트랙 상세 정보의 캐시 키 (소스 ID, 없으면 앨범 아트 URL).
+fn track_key(track: &TrackInfo) -> Option<&str> {
+    track
+        .source_id
+        .as_deref()
+        .or(track.album_art_url.as_deref())
+}
+
+/// 앨범 아트의 캐시 키 (앨범 아트 URL, 없으면 소스 ID).
+fn art_key(track: &TrackInfo) -> Option<&str> {
+    track
+        .album_art_url
+        .as_deref()
+        .or(track.source_id.as_deref())
+}
+
+/// 소스 클라이언트를 캐시로 감싼다. 응답은 캐시에 저장하고,
+/// 클라이언트가 없으면(오프라인 모드) 캐시에서만 응답한다.
+pub struct CachedSource {
+    name: String,
+    client: Option<Box<dyn MusicSource>>,
+    cache: SourceCache,
+}
+
+impl CachedSource {
+    pub fn new(name: &str, client: Option<Box<dyn MusicSource>>, cache: SourceCache) -> Self {
+        Self {
+            name: name.to_string(),
+            client,
+            cache,
+        }
+    }
+}
+
+impl MusicSource for CachedSource {
+    fn search(&self, query: &str) -> Result<Vec<TrackInfo>> {
+        match self.client {
+            Some(ref client) => {
+                let results = client.search(query)?;
+                self.cache.store_search(&self.name, query, &results);
+                Ok(results)
+            }
+            None => self
+                .cache
+                .load_search(&self.name, query)
+                .with_context(|| format!("오프라인 모드: '{}' 검색 결과가 캐시에 없습니다", query)),
+        }
+    }
+
+    /// 첫 페이지는 `search`와 같은 캐시를, 다음 페이지는 구간을 붙인 검색어로 따로 캐시한다.
+    fn search_page(&self, query: &str, page: Page) -> Result<Vec<TrackInfo>> {
+        if page == Page::first() {
+            return self.search(query);
+        }
+        let key = format!("{} #{}+{}", query, page.offset, page.limit);
+        match self.client {
+            Some(ref client) => {
+                let results = client.search_page(query, page)?;
+                self.cache.store_search(&self.name, &key, &results);
+                Ok(results)
+            }
+            None => self.cache.load_search(&self.name, &key).with_context(|| {
+                format!(
+                    "오프라인 모드: '{}' 검색 결과 {}번째부터가 캐시에 없습니다",
+                    query,
+                    page.offset + 1
+                )
+            }),
+        }
+    }
+
+    fn fetch_album_art(&self, track: &TrackInfo) -> Result<Vec<u8>> {
+        match self.client {
+            Some(ref client) => {
+                let art = client.fetch_album_art(track)?;
+                self.cache.store_art(&self.name, track, &art);
+                Ok(art)
+            }
+            None => self
+                .cache
+                .load_art(&self.name, track)
+                .context("오프라인 모드: 캐시된 앨범 아트가 없습니다"),
+        }
+    }
+
+    /// 오프라인 모드에서 캐시된 상세 정보가 없으면 캐시된 앨범 아트만 덧붙여 반환한다.
+    fn fetch_detail(&self, track: &TrackInfo) -> Result<TrackInfo> {
+        let key = track_key(track);
+        match self.client {
+            Some(ref client) => {
+                let detailed = client.fetch_detail(track)?;
+                if let Some(key) = key {
+                    self.cache.store_track(&self.name, key, &detailed);
+                }
+                Ok(detailed)
+            }
+            None => {
+                if let Some(cached) = key.and_then(|k| self.cache.load_track(&self.name, k)) {
+                    return Ok(cached);
+                }
+                let mut track = track.clone();
+                track.album_art = self.cache.load_art(&self.name, &track);
+                Ok(track)
+            }
+        }
+    }
+
+    fn search_album(&self, query: &str) -> Result<Vec<AlbumInfo>> {
+        match self.client {
+            Some(ref client) => client.search_album(query),
+            None => bail!("오프라인 모드에서는 앨범을 검색할 수 없습니다"),
+        }
+    }
+
+    fn fetch_album(&self, album: &AlbumInfo) -> Result<AlbumInfo> {
+        match self.client {
+            Some(ref client) => client.fetch_album(album),
+            None => bail!("오프라인 모드에서는 앨범 정보를 가져올 수 없습니다"),
+        }
+    }
+
+    fn get_track(&self, id: &str) -> Result<TrackInfo> {
+        match self.client {
+            Some(ref client) => {
+                let track = client.get_track(id)?;
+                self.cache.store_track(&self.name, id, &track);
+                Ok(track)
+            }
+            None => self
+                .cache
+                .load_track(&self.name, id)
+                .with_context(|| format!("오프라인 모드: 트랙 {}이(가) 캐시에 없습니다", id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    struct FakeSource;
+
+    impl MusicSource for FakeSource {
+        fn search(&self, query: &str) -> Result<Vec<TrackInfo>> {
+            Ok(vec![TrackInfo {
+                title: Some(query.to_string()),
+                source_id: Some("id-1".to_string()),
+                album_art_url: Some("https://example.com/art.jpg".to_string()),
+                source: "fake".to_string(),
+                ..Default::default()
+            }])
+        }
+
+        fn fetch_album_art(&self, _track: &TrackInfo) -> Result<Vec<u8>> {
+            Ok(vec![0xFF, 0xD8, 0xFF])
+        }
+    }
+
+    #[test]
+    fn test_offline_uses_cached_responses() {
+        let dir = TempDir::new("cache-roundtrip");
+        let online = CachedSource::new(
+            "fake",
+            Some(Box::new(FakeSource)),
+            SourceCache::new(dir.to_path_buf()),
+        );
+        let results = online.search("IU  Blueming").unwrap();
+        online.fetch_detail(&results[0]).unwrap();
+
+        let offline = CachedSource::new("fake", None, SourceCache::new(dir.to_path_buf()));
+        let cached = offline.search("iu blueming").unwrap();
+        assert_eq!(cached[0].title.as_deref(), Some("IU  Blueming"));
+        let detail = offline.fetch_detail(&cached[0]).unwrap();
+        assert_eq!(detail.album_art.as_deref(), Some(&[0xFF, 0xD8, 0xFF][..]));
+
+        // 캐시에 없는 검색어와 다른 소스 이름은 찾지 않는다
+        assert!(offline.search("밤편지").is_err());
+        let other = CachedSource::new("other", None, SourceCache::new(dir.to_path_buf()));
+        assert!(other.search("iu blueming").is_err());
+        assert!(offline.search_album("Palette").is_err());
+
+        // 다음 페이지는 첫 페이지와 따로 캐시한다
+        let page = Page {
+            offset: 0,
+            limit: 1,
+        };
+        assert_eq!(
+            offline
+                .search_page("IU  Blueming", Page::first())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(offline.search_page("IU  Blueming", page).is_err());
+        assert_eq!(online.search_page("IU  Blueming", page).unwrap().len(), 1);
+        assert_eq!(offline.search_page("iu blueming", page).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_remembered_match_roundtrip() {
+        let dir = TempDir::new("cache-match");
+        let cache = SourceCache::new(dir.to_path_buf());
+        let track = TrackInfo {
+            title: Some("Blueming".to_string()),
+            album_art_url: Some("https://example.com/art.jpg".to_string()),
+            album_art: Some(vec![0xFF, 0xD8, 0xFF]),
+            source: "fake".to_string(),
+            ..Default::default()
+        };
+        cache.store_match("abc", &RememberedMatch { track, score: 0.9 });
+
+        let remembered = cache.load_match("abc").unwrap();
+        assert_eq!(remembered.score, 0.9);
+        assert_eq!(remembered.track.title.as_deref(), Some("Blueming"));
+        assert_eq!(
+            remembered.track.album_art.as_deref(),
+            Some(&[0xFF, 0xD8, 0xFF][..])
+        );
+        assert!(cache.load_match("other").is_none());
+    }
+}
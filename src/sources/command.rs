@@ -0,0 +1,244 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::config::CommandSourceConfig;
+use crate::core::parser;
+use crate::models::{split_artists, TrackInfo};
+use crate::sources::MusicSource;
+
+/// 외부 실행 파일을 호출하는 소스.
+/// 요청 하나마다 프로세스를 실행해 stdin으로 JSON 요청을 보내고 stdout의 JSON 응답을 읽는다.
+///
+/// - 검색: `{"action": "search", "query": "..."}` → 트랙 객체 배열
+/// - ID 조회: `{"action": "get_track", "id": "..."}` → 트랙 객체 하나
+///
+/// 트랙 객체의 필드는 모두 선택 사항이다 ([`CommandTrack`] 참고).
+/// 0이 아닌 종료 코드는 실패로 처리하고 stderr를 오류 메시지에 포함한다.
+pub struct CommandSource {
+    name: String,
+    command: String,
+    args: Vec<String>,
+    client: reqwest::blocking::Client,
+}
+
+/// 외부 소스에 보내는 요청. 명령 소스와 플러그인이 같은 JSON 형식을 쓴다.
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub(super) enum Request<'a> {
+    Search { query: &'a str },
+    GetTrack { id: &'a str },
+}
+
+/// 외부 소스가 반환하는 트랙 하나.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub(super) struct CommandTrack {
+    /// 소스 내 고유 ID. `get_track`에 그대로 다시 전달된다.
+    id: Option<String>,
+    title: Option<String>,
+    artist: Option<String>,
+    album: Option<String>,
+    album_artist: Option<String>,
+    track_number: Option<u32>,
+    total_tracks: Option<u32>,
+    year: Option<i32>,
+    /// "2019-11-18" 또는 "2019-11". 형식이 맞지 않으면 무시한다.
+    release_date: Option<String>,
+    /// 리마스터/재발매반의 최초 발매 연도와 날짜
+    original_year: Option<i32>,
+    original_date: Option<String>,
+    genre: Option<String>,
+    /// 클래식 음악의 콘텐츠 그룹, 작품명, 악장
+    grouping: Option<String>,
+    work: Option<String>,
+    movement: Option<String>,
+    movement_number: Option<u32>,
+    movement_total: Option<u32>,
+    label: Option<String>,
+    composer: Option<String>,
+    producer: Option<String>,
+    lyrics: Option<String>,
+    musicbrainz_album_id: Option<String>,
+    duration_ms: Option<u64>,
+    album_art_url: Option<String>,
+}
+
+impl CommandSource {
+    /// config.toml에 등록된 명령 소스를 생성한다.
+    pub fn new(config: &CommandSourceConfig) -> Result<Self> {
+        if config.command.trim().is_empty() {
+            bail!("'{}' 소스의 command가 비어 있습니다", config.name);
+        }
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("mp3tag/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("HTTP 클라이언트 생성에 실패했습니다")?;
+        Ok(Self {
+            name: config.name.clone(),
+            command: config.command.clone(),
+            args: config.args.clone(),
+            client,
+        })
+    }
+
+    /// 실행 파일을 호출해 요청을 보내고 응답 JSON을 파싱한다.
+    fn call<T: DeserializeOwned>(&self, request: &Request) -> Result<T> {
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| {
+                format!("'{}' 소스 실행에 실패했습니다: {}", self.name, self.command)
+            })?;
+
+        let input = serde_json::to_vec(request)?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // 요청을 읽지 않고 종료하는 실행 파일도 있으므로 쓰기 오류는 무시한다.
+            let _ = stdin.write_all(&input);
+        }
+
+        let output = child
+            .wait_with_output()
+            .with_context(|| format!("'{}' 소스 실행에 실패했습니다", self.name))?;
+        if !output.status.success() {
+            bail!(
+                "'{}' 소스가 실패했습니다 ({}): {}",
+                self.name,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("'{}' 소스의 응답 파싱에 실패했습니다", self.name))
+    }
+}
+
+impl CommandTrack {
+    /// 응답 트랙을 TrackInfo로 변환한다. `source`는 설정에 등록된 소스 이름이다.
+    pub(super) fn into_track(self, source: &str) -> TrackInfo {
+        TrackInfo {
+            title: self.title,
+            artists: self
+                .artist
+                .as_deref()
+                .map(split_artists)
+                .unwrap_or_default(),
+            album: self.album,
+            album_artist: self.album_artist,
+            track_number: self.track_number,
+            total_tracks: self.total_tracks,
+            year: self.year,
+            release_date: self
+                .release_date
+                .as_deref()
+                .and_then(parser::normalize_release_date),
+            original_year: self.original_year,
+            original_date: self
+                .original_date
+                .as_deref()
+                .and_then(parser::normalize_release_date),
+            genre: self.genre,
+            grouping: self.grouping,
+            work: self.work,
+            movement: self.movement,
+            movement_number: self.movement_number,
+            movement_total: self.movement_total,
+            label: self.label,
+            composer: self.composer,
+            producer: self.producer,
+            lyrics: self.lyrics,
+            musicbrainz_album_id: self.musicbrainz_album_id,
+            duration_ms: self.duration_ms,
+            album_art_url: self.album_art_url,
+            source_id: self.id,
+            source: source.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// 트랙의 album_art_url에서 앨범 아트를 다운로드한다.
+pub(super) fn download_art(
+    client: &reqwest::blocking::Client,
+    track: &TrackInfo,
+) -> Result<Vec<u8>> {
+    let url = track
+        .album_art_url
+        .as_ref()
+        .context("앨범 아트 URL이 없습니다")?;
+    let data = client
+        .get(url)
+        .send()
+        .context("앨범 아트 다운로드에 실패했습니다")?
+        .error_for_status()?
+        .bytes()?
+        .to_vec();
+    Ok(data)
+}
+
+impl MusicSource for CommandSource {
+    fn search(&self, query: &str) -> Result<Vec<TrackInfo>> {
+        let tracks: Vec<CommandTrack> = self.call(&Request::Search { query })?;
+        Ok(tracks
+            .into_iter()
+            .map(|t| t.into_track(&self.name))
+            .collect())
+    }
+
+    fn fetch_album_art(&self, track: &TrackInfo) -> Result<Vec<u8>> {
+        download_art(&self.client, track)
+    }
+
+    fn get_track(&self, id: &str) -> Result<TrackInfo> {
+        let track: CommandTrack = self.call(&Request::GetTrack { id })?;
+        Ok(track.into_track(&self.name))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell_source(script: &str) -> CommandSource {
+        CommandSource::new(&CommandSourceConfig {
+            name: "vgmdb".to_string(),
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_search_parses_tracks() {
+        // 요청을 그대로 제목으로 돌려주는 실행 파일
+        let source = shell_source(
+            r#"req=$(cat); printf '[{"id":"42","title":"%s","year":2019,"release_date":"2019.11.18","unknown":1},{}]' "$(echo "$req" | tr -d '"')""#,
+        );
+        let tracks = source.search("Love poem").unwrap();
+
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(
+            tracks[0].title.as_deref(),
+            Some("{action:search,query:Love poem}")
+        );
+        assert_eq!(tracks[0].year, Some(2019));
+        assert_eq!(tracks[0].release_date.as_deref(), Some("2019-11-18"));
+        assert_eq!(tracks[0].source_id.as_deref(), Some("42"));
+        assert_eq!(tracks[0].source, "vgmdb");
+        assert_eq!(tracks[1].title, None);
+    }
+
+    #[test]
+    fn test_failure_reports_stderr() {
+        let source = shell_source("cat >/dev/null; echo 'rate limited' >&2; exit 3");
+        let err = source.get_track("42").unwrap_err().to_string();
+        assert!(err.contains("rate limited"), "{}", err);
+    }
+}
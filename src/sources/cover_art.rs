@@ -0,0 +1,37 @@
+use anyhow::{Context, Result};
+
+/// Cover Art Archive 클라이언트.
+/// MusicBrainz 릴리스 ID로 원본 해상도의 앞면 커버 이미지를 가져온다.
+pub struct CoverArtClient {
+    client: reqwest::blocking::Client,
+}
+
+impl CoverArtClient {
+    /// 새 CoverArtClient를 생성한다. MusicBrainz 정책에 따라 User-Agent를 설정한다.
+    pub fn new() -> Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("mp3tag/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Cover Art Archive HTTP 클라이언트 생성에 실패했습니다")?;
+        Ok(Self { client })
+    }
+
+    /// 릴리스의 앞면 커버를 원본 해상도로 다운로드한다.
+    /// `/front`는 실제 이미지 주소로 리다이렉트되며, 등록된 커버가 없으면 404를 반환한다.
+    pub fn fetch_front(&self, release_id: &str) -> Result<Vec<u8>> {
+        let url = format!(
+            "https://coverartarchive.org/release/{}/front",
+            release_id.trim()
+        );
+        let data = self
+            .client
+            .get(&url)
+            .send()
+            .context("Cover Art Archive 연결에 실패했습니다")?
+            .error_for_status()
+            .context("Cover Art Archive에 이 릴리스의 앞면 커버가 없습니다")?
+            .bytes()?
+            .to_vec();
+        Ok(data)
+    }
+}
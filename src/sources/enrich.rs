@@ -0,0 +1,176 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::config::{Config, TrustConfig};
+use crate::core::{artwork, matcher, tagger};
+use crate::models::TrackInfo;
+use crate::sources::cover_art::CoverArtClient;
+use crate::sources::genius::GeniusClient;
+
+/// 소스에서 가져온 트랙에 기록 직전 덧붙이는 보충 단계.
+/// Genius 크레딧/가사, Cover Art Archive 원본 아트, 앨범 아트 최소 크기 기준을 설정에 따라 적용한다.
+/// 어떤 단계가 실패해도 트랙 기록은 막지 않는다.
+pub struct Enricher {
+    genius: Option<GeniusClient>,
+    cover_art: Option<CoverArtClient>,
+    min_art_size: u32,
+    /// Genius 값과 소스 값이 겹칠 때 따를 필드별 신뢰 순서
+    trust: TrustConfig,
+    /// 릴리스 ID별 Cover Art Archive 이미지. 앨범 단위 작업에서 같은 커버를 반복해 받지 않는다.
+    cover_cache: RefCell<HashMap<String, Option<Vec<u8>>>>,
+}
+
+impl Enricher {
+    /// 설정에서 활성화된 보충 단계만 준비한다. 오프라인 모드에서는 네트워크 단계를 모두 끈다.
+    pub fn new(cfg: &Config) -> Result<Self> {
+        let online = !cfg.network.offline;
+        Ok(Self {
+            genius: if online {
+                GeniusClient::from_config(&cfg.genius)?
+            } else {
+                None
+            },
+            cover_art: if online && cfg.art.cover_art_archive {
+                Some(CoverArtClient::new()?)
+            } else {
+                None
+            },
+            min_art_size: cfg.art.min_size,
+            trust: cfg.trust.clone(),
+            cover_cache: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// 트랙을 보충한다. `existing`은 대상 파일의 현재 태그로, 같은 앨범이면
+    /// 그 MusicBrainz 릴리스 ID로 Cover Art Archive를 조회한다.
+    /// 최소 크기에 못 미쳐 앨범 아트를 버린 경우 그 안내 문구를 함께 반환한다.
+    pub fn apply(
+        &self,
+        existing: Option<&TrackInfo>,
+        track: TrackInfo,
+    ) -> (TrackInfo, Option<String>) {
+        let mut track = match self.genius.as_ref().map(|g| g.supplement(&track)) {
+            Some(Ok(mut supplemented)) => {
+                // Genius가 채운 필드는 출처를 "genius"로 남겨 `[trust]` 순서로 소스 값과 견준다
+                for (field, changed) in [
+                    ("composer", supplemented.composer != track.composer),
+                    ("producer", supplemented.producer != track.producer),
+                    ("lyrics", supplemented.lyrics != track.lyrics),
+                ] {
+                    if changed {
+                        supplemented
+                            .field_sources
+                            .insert(field.to_string(), "genius".to_string());
+                    }
+                }
+                tagger::merge_trusted(&Some(track), &supplemented, &self.trust)
+            }
+            _ => track,
+        };
+
+        if let Some(ref client) = self.cover_art {
+            if let Some(release_id) = release_id(existing, &track) {
+                let art = self
+                    .cover_cache
+                    .borrow_mut()
+                    .entry(release_id.clone())
+                    .or_insert_with(|| client.fetch_front(&release_id).ok())
+                    .clone();
+                if art.is_some() {
+                    track.album_art = art;
+                }
+            }
+        }
+
+        let mut note = None;
+        if let Some(ref art) = track.album_art {
+            if !artwork::meets_min_size(art, self.min_art_size) {
+                let size = artwork::image_dimensions(art)
+                    .map(|(w, h)| format!("{}x{}", w, h))
+                    .unwrap_or_else(|| "알 수 없는 크기".to_string());
+                note = Some(format!(
+                    "앨범 아트가 최소 크기 {}px보다 작아 기록하지 않습니다 ({})",
+                    self.min_art_size, size
+                ));
+                track.album_art = None;
+            }
+        }
+
+        (track, note)
+    }
+}
+
+/// 트랙의 MusicBrainz 릴리스 ID. 트랙에 없으면 파일의 기존 태그가 같은 앨범일 때 그 ID를 쓴다.
+fn release_id(existing: Option<&TrackInfo>, track: &TrackInfo) -> Option<String> {
+    if let Some(ref id) = track.musicbrainz_album_id {
+        return Some(id.clone());
+    }
+    let existing = existing?;
+    let same_album = match (&existing.album, &track.album) {
+        (Some(a), Some(b)) => matcher::normalize(a) == matcher::normalize(b),
+        (_, None) => true,
+        (None, Some(_)) => false,
+    };
+    if same_album {
+        existing.musicbrainz_album_id.clone()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tagged(album: &str, mbid: Option<&str>) -> TrackInfo {
+        TrackInfo {
+            album: Some(album.to_string()),
+            musicbrainz_album_id: mbid.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_release_id_from_same_album_only() {
+        let existing = tagged("Love poem", Some("mbid-1"));
+        assert_eq!(
+            release_id(Some(&existing), &tagged("Love Poem", None)).as_deref(),
+            Some("mbid-1")
+        );
+        assert_eq!(release_id(Some(&existing), &tagged("Palette", None)), None);
+        assert_eq!(
+            release_id(Some(&existing), &tagged("Palette", Some("mbid-2"))).as_deref(),
+            Some("mbid-2")
+        );
+        assert_eq!(release_id(None, &tagged("Palette", None)), None);
+    }
+
+    #[test]
+    fn test_small_art_is_dropped() {
+        let cfg = Config {
+            art: crate::config::ArtConfig {
+                min_size: 1000,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let enricher = Enricher::new(&cfg).unwrap();
+
+        // 64x64 PNG 헤더
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        png.extend_from_slice(&13u32.to_be_bytes());
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&64u32.to_be_bytes());
+        png.extend_from_slice(&64u32.to_be_bytes());
+
+        let track = TrackInfo {
+            album_art: Some(png),
+            ..Default::default()
+        };
+        let (track, note) = enricher.apply(None, track);
+        assert!(track.album_art.is_none());
+        assert!(note.unwrap().contains("64x64"));
+    }
+}
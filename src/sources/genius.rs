@@ -0,0 +1,280 @@
+use anyhow::{Context, Result};
+use scraper::{ElementRef, Html, Node, Selector};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::config::{GeniusConfig, ScrapeConfig};
+use crate::core::matcher;
+use crate::models::TrackInfo;
+use crate::sources::scrape::PoliteClient;
+
+const API_BASE: &str = "https://api.genius.com";
+
+/// Genius API 클라이언트.
+/// 다른 소스에서 가져온 곡에 작곡/프로듀서 크레딧과 가사를 보충한다.
+/// 가사는 API에서 제공하지 않으므로 곡 페이지를 스크래핑한다.
+pub struct GeniusClient {
+    client: reqwest::blocking::Client,
+    access_token: String,
+    pages: PoliteClient,
+}
+
+#[derive(Deserialize)]
+struct ApiResponse<T> {
+    response: T,
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Deserialize)]
+struct SearchHit {
+    result: GeniusSong,
+}
+
+#[derive(Deserialize)]
+struct SongResponse {
+    song: GeniusSong,
+}
+
+#[derive(Deserialize)]
+struct GeniusSong {
+    id: u64,
+    title: String,
+    primary_artist: GeniusArtist,
+    url: String,
+    #[serde(default)]
+    writer_artists: Vec<GeniusArtist>,
+    #[serde(default)]
+    producer_artists: Vec<GeniusArtist>,
+}
+
+#[derive(Deserialize)]
+struct GeniusArtist {
+    name: String,
+}
+
+impl GeniusClient {
+    /// 설정에 토큰이 있으면 클라이언트를 생성한다. 없으면 None.
+    pub fn from_config(config: &GeniusConfig) -> Result<Option<Self>> {
+        let Some(token) = config.access_token.as_ref().filter(|t| !t.is_empty()) else {
+            return Ok(None);
+        };
+
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("mp3tag/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Genius HTTP 클라이언트 생성에 실패했습니다")?;
+
+        Ok(Some(Self {
+            pages: PoliteClient::new("Genius", client.clone(), &ScrapeConfig::default()),
+            client,
+            access_token: token.clone(),
+        }))
+    }
+
+    /// 인증된 API GET 요청을 보내 `response` 필드를 파싱한다.
+    fn get_api<T: DeserializeOwned>(&self, path: &str, query: &[(&str, &str)]) -> Result<T> {
+        let resp: ApiResponse<T> = self
+            .client
+            .get(format!("{}{}", API_BASE, path))
+            .bearer_auth(&self.access_token)
+            .query(query)
+            .send()
+            .context("Genius 연결에 실패했습니다")?
+            .error_for_status()
+            .context("Genius 요청이 실패했습니다. access_token을 확인하세요")?
+            .json()
+            .context("Genius 응답 파싱에 실패했습니다")?;
+        Ok(resp.response)
+    }
+
+    /// 트랙과 같은 곡을 Genius에서 찾아 작곡가(작사/작곡), 프로듀서, 가사를 채운다.
+    /// Genius에 있는 값만 덮어쓰고 나머지 필드는 그대로 둔다.
+    /// 가사 페이지는 스크래핑이라 실패해도 경고만 남기고 크레딧은 돌려준다.
+    pub fn supplement(&self, track: &TrackInfo) -> Result<TrackInfo> {
+        let title = track
+            .title
+            .as_deref()
+            .context("제목이 없어 Genius에서 찾을 수 없습니다")?;
+        let artist = track
+            .artists
+            .first()
+            .map(String::as_str)
+            .unwrap_or_default();
+        let query = format!("{} {}", artist, title);
+
+        let search: SearchResponse = self.get_api("/search", &[("q", query.trim())])?;
+        let hit = search
+            .hits
+            .into_iter()
+            .map(|h| h.result)
+            .find(|song| {
+                let candidate = TrackInfo {
+                    title: Some(song.title.clone()),
+                    artists: vec![song.primary_artist.name.clone()],
+                    ..Default::default()
+                };
+                matcher::is_same_song(track, &candidate)
+            })
+            .context("Genius에서 일치하는 곡을 찾지 못했습니다")?;
+
+        let detail: SongResponse = self.get_api(&format!("/songs/{}", hit.id), &[])?;
+        let song = detail.song;
+        let lyrics_page = self
+            .pages
+            .get_text(&song.url)
+            .context("Genius 가사 페이지 로딩에 실패했습니다");
+        Ok(apply_song(track, &song, lyrics_page))
+    }
+}
+
+/// 곡 정보의 크레딧과 가사 페이지의 가사를 트랙에 채운다.
+/// 가사 페이지를 가져오지 못했으면 경고를 출력하고 크레딧만 채운다.
+fn apply_song(track: &TrackInfo, song: &GeniusSong, lyrics_page: Result<String>) -> TrackInfo {
+    let mut supplemented = track.clone();
+    if let Some(writers) = join_names(&song.writer_artists) {
+        supplemented.composer = Some(writers);
+    }
+    if let Some(producers) = join_names(&song.producer_artists) {
+        supplemented.producer = Some(producers);
+    }
+
+    match lyrics_page {
+        Ok(html) => {
+            if let Some(lyrics) = extract_lyrics(&html) {
+                supplemented.lyrics = Some(lyrics);
+            }
+        }
+        Err(e) => eprintln!("가사 없이 크레딧만 채웁니다: {:#}", e),
+    }
+    supplemented
+}
+
+/// 아티스트 이름들을 ", "로 잇는다. 비어 있으면 None.
+fn join_names(artists: &[GeniusArtist]) -> Option<String> {
+    if artists.is_empty() {
+        return None;
+    }
+    Some(
+        artists
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Genius 곡 페이지에서 가사를 추출한다. `<br>`은 줄바꿈으로 바꾸고,
+/// 가사 사이에 끼어 있는 안내 문구(`data-exclude-from-selection`)는 제외한다.
+fn extract_lyrics(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let container_sel = Selector::parse("div[data-lyrics-container='true']").unwrap();
+
+    let mut lyrics = String::new();
+    for container in document.select(&container_sel) {
+        if !lyrics.is_empty() {
+            lyrics.push('\n');
+        }
+        append_lyrics_text(container, &mut lyrics);
+    }
+
+    let lyrics = lyrics
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    if lyrics.is_empty() {
+        None
+    } else {
+        Some(lyrics)
+    }
+}
+
+fn append_lyrics_text(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(el) if el.name() == "br" => out.push('\n'),
+            Node::Element(el) if el.attr("data-exclude-from-selection") == Some("true") => {}
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    append_lyrics_text(child, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_lyrics() {
+        let html = r#"<html><body>
+            <div data-lyrics-container="true"><div data-exclude-from-selection="true">1 Contributor</div>[Verse 1]<br>이 밤 그날의 반딧불을<br><a href="/x"><span>당신의 창 가까이</span></a></div>
+            <div data-lyrics-container="true">[Chorus]<br>음</div>
+        </body></html>"#;
+
+        assert_eq!(
+            extract_lyrics(html).as_deref(),
+            Some("[Verse 1]\n이 밤 그날의 반딧불을\n당신의 창 가까이\n[Chorus]\n음")
+        );
+        assert_eq!(extract_lyrics("<html><body></body></html>"), None);
+    }
+
+    #[test]
+    fn test_join_names() {
+        let artists = vec![
+            GeniusArtist {
+                name: "IU".to_string(),
+            },
+            GeniusArtist {
+                name: "이종훈".to_string(),
+            },
+        ];
+        assert_eq!(join_names(&artists).as_deref(), Some("IU, 이종훈"));
+        assert_eq!(join_names(&[]), None);
+    }
+
+    #[test]
+    fn test_lyrics_failure_keeps_credits() {
+        let song = GeniusSong {
+            id: 1,
+            title: "밤편지".to_string(),
+            primary_artist: GeniusArtist {
+                name: "IU".to_string(),
+            },
+            url: "https://genius.com/Iu-through-the-night-lyrics".to_string(),
+            writer_artists: vec![GeniusArtist {
+                name: "IU".to_string(),
+            }],
+            producer_artists: vec![GeniusArtist {
+                name: "제휘".to_string(),
+            }],
+        };
+        let track = TrackInfo {
+            title: Some("밤편지".to_string()),
+            lyrics: Some("기존 가사".to_string()),
+            ..Default::default()
+        };
+
+        let supplemented = apply_song(&track, &song, Err(anyhow::anyhow!("HTTP 403")));
+        assert_eq!(supplemented.composer.as_deref(), Some("IU"));
+        assert_eq!(supplemented.producer.as_deref(), Some("제휘"));
+        assert_eq!(supplemented.lyrics.as_deref(), Some("기존 가사"));
+
+        let html = r#"<div data-lyrics-container="true">이 밤 그날의 반딧불을</div>"#;
+        let supplemented = apply_song(&track, &song, Ok(html.to_string()));
+        assert_eq!(
+            supplemented.lyrics.as_deref(),
+            Some("이 밤 그날의 반딧불을")
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+
+/// 소스 클라이언트가 보내는 HTTP 요청.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: Method,
+    /// 쿼리 문자열까지 붙인 URL
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// `application/x-www-form-urlencoded` 본문. 비어 있으면 본문 없음
+    pub form: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+/// HTTP 응답 (상태 코드와 본문).
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// 요청을 보내는 전송 계층. 소스 클라이언트는 reqwest 대신 이 트레이트로 요청하므로,
+/// 테스트에서는 가짜 응답을 주는 구현을, mp3tag를 포함해 쓰는 쪽에서는 자체 전송(프록시, 기록 등)을 넣을 수 있다.
+pub trait HttpClient: Send + Sync {
+    /// 요청을 보내 응답을 받는다. 연결 실패만 에러이고, 4xx/5xx는 응답으로 반환한다.
+    fn send(&self, request: &Request) -> Result<Response>;
+}
+
+impl Request {
+    pub fn get(url: impl Into<String>) -> Self {
+        Self::new(Method::Get, url)
+    }
+
+    pub fn post(url: impl Into<String>) -> Self {
+        Self::new(Method::Post, url)
+    }
+
+    fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            form: Vec::new(),
+        }
+    }
+
+    /// URL에 쿼리 파라미터를 붙인다. URL이 잘못됐으면 그대로 둔다 (보낼 때 에러가 난다).
+    pub fn query(mut self, params: &[(&str, &str)]) -> Self {
+        if params.is_empty() {
+            return self;
+        }
+        if let Ok(mut url) = reqwest::Url::parse(&self.url) {
+            url.query_pairs_mut().extend_pairs(params);
+            self.url = url.into();
+        }
+        self
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn bearer_auth(self, token: &str) -> Self {
+        self.header("Authorization", format!("Bearer {}", token))
+    }
+
+    pub fn form(mut self, form: &[(&str, &str)]) -> Self {
+        self.form = form
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self
+    }
+}
+
+/// 4xx/5xx 응답. `exit::code`가 네트워크 오류(종료 코드 5)로 분류한다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpStatusError {
+    pub status: u16,
+    pub url: String,
+}
+
+impl fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HTTP {} 응답 ({})", self.status, self.url)
+    }
+}
+
+impl std::error::Error for HttpStatusError {}
+
+impl Response {
+    /// 4xx/5xx 응답이면 `HttpStatusError`.
+    pub fn error_for_status(self, url: &str) -> Result<Self> {
+        if self.status >= 400 {
+            return Err(HttpStatusError {
+                status: self.status,
+                url: url.to_string(),
+            }
+            .into());
+        }
+        Ok(self)
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// reqwest로 요청을 보내는 기본 전송 계층.
+#[derive(Default)]
+pub struct ReqwestClient {
+    client: reqwest::blocking::Client,
+}
+
+impl HttpClient for ReqwestClient {
+    fn send(&self, request: &Request) -> Result<Response> {
+        let mut builder = match request.method {
+            Method::Get => self.client.get(&request.url),
+            Method::Post => self.client.post(&request.url),
+        };
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if !request.form.is_empty() {
+            builder = builder.form(&request.form);
+        }
+        let resp = builder.send()?;
+        let status = resp.status().as_u16();
+        let body = resp.bytes().context("응답 읽기에 실패했습니다")?.to_vec();
+        Ok(Response { status, body })
+    }
+}
+
+/// 테스트용 전송 계층. URL 앞부분으로 정한 응답을 돌려주고 보낸 요청을 기록한다.
+#[cfg(test)]
+pub mod mock {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    pub struct MockHttp {
+        routes: Vec<(Method, String, u16, Vec<u8>)>,
+        requests: Mutex<Vec<Request>>,
+    }
+
+    impl MockHttp {
+        /// `url_prefix`로 시작하는 `method` 요청에 `status`와 `body`로 응답한다. 먼저 등록한 것이 우선이다.
+        pub fn on(
+            mut self,
+            method: Method,
+            url_prefix: &str,
+            status: u16,
+            body: impl Into<Vec<u8>>,
+        ) -> Self {
+            self.routes
+                .push((method, url_prefix.to_string(), status, body.into()));
+            self
+        }
+
+        /// 지금까지 보낸 요청.
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl HttpClient for MockHttp {
+        fn send(&self, request: &Request) -> Result<Response> {
+            self.requests.lock().unwrap().push(request.clone());
+            let (_, _, status, body) = self
+                .routes
+                .iter()
+                .find(|(method, prefix, _, _)| {
+                    *method == request.method && request.url.starts_with(prefix.as_str())
+                })
+                .with_context(|| format!("연결할 수 없습니다: {}", request.url))?;
+            Ok(Response {
+                status: *status,
+                body: body.clone(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_builder() {
+        let request = Request::get("https://api.example.com/v1/search")
+            .query(&[("q", "아이유 밤편지"), ("limit", "10")])
+            .bearer_auth("token");
+        assert_eq!(
+            request.url,
+            "https://api.example.com/v1/search?q=%EC%95%84%EC%9D%B4%EC%9C%A0+%EB%B0%A4%ED%8E%B8%EC%A7%80&limit=10"
+        );
+        assert_eq!(
+            request.headers,
+            [("Authorization".to_string(), "Bearer token".to_string())]
+        );
+
+        let resp = Response {
+            status: 404,
+            body: Vec::new(),
+        };
+        let err = resp.error_for_status(&request.url).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<HttpStatusError>().map(|e| e.status),
+            Some(404)
+        );
+    }
+}
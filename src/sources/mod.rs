@@ -1,15 +1,51 @@
+pub mod art_search;
+pub mod cache;
+pub mod command;
+pub mod cover_art;
+pub mod enrich;
+pub mod genius;
+pub mod http;
 pub mod melon;
+pub mod open_library;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+pub mod scrape;
+pub mod selectors;
 pub mod spotify;
+pub mod spotify_auth;
 
-use anyhow::Result;
+use std::str::FromStr;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
-use crate::models::TrackInfo;
+use anyhow::{bail, Result};
+
+use crate::config::Config;
+use crate::core::parser::{self, QueryVariant};
+use crate::exit;
+use crate::models::{AlbumInfo, TrackInfo};
+use cache::{CachedSource, SourceCache};
+use command::CommandSource;
+use melon::MelonClient;
+use spotify::SpotifyClient;
 
 /// 음악 메타데이터 소스 트레이트.
 /// Spotify, Bugs, Melon 등 다양한 소스를 이 트레이트로 추상화한다.
-pub trait MusicSource {
+/// 여러 소스를 동시에 검색할 수 있도록(`race`) 스레드 사이에서 공유할 수 있어야 한다.
+pub trait MusicSource: Send + Sync {
     /// 쿼리 문자열로 트랙을 검색한다.
     fn search(&self, query: &str) -> Result<Vec<TrackInfo>>;
+    /// 검색 결과 중 `page` 구간만 가져온다 ("결과 더 보기", `search --page`).
+    /// 기본 구현은 `search` 결과를 잘라내므로, 첫 검색 결과 밖은 가져오지 못한다.
+    /// 서버에서 페이지를 넘길 수 있는 소스는 이 메서드를 구현한다.
+    fn search_page(&self, query: &str, page: Page) -> Result<Vec<TrackInfo>> {
+        Ok(self
+            .search(query)?
+            .into_iter()
+            .skip(page.offset)
+            .take(page.limit)
+            .collect())
+    }
     /// 트랙의 앨범 아트 이미지를 다운로드한다.
     fn fetch_album_art(&self, track: &TrackInfo) -> Result<Vec<u8>>;
     /// 트랙의 상세 정보(메타데이터 + 앨범 아트)를 가져온다.
@@ -20,4 +56,394 @@ pub trait MusicSource {
         detailed.album_art = Some(art);
         Ok(detailed)
     }
+    /// 쿼리 문자열로 앨범을 검색한다. 결과에는 트랙 목록이 없을 수 있다.
+    fn search_album(&self, _query: &str) -> Result<Vec<AlbumInfo>> {
+        bail!("이 소스는 앨범 검색을 지원하지 않습니다")
+    }
+    /// 앨범의 상세 정보(트랙 목록, 발매일, 장르, 앨범 아트 등)를 가져온다.
+    fn fetch_album(&self, _album: &AlbumInfo) -> Result<AlbumInfo> {
+        bail!("이 소스는 앨범 상세 조회를 지원하지 않습니다")
+    }
+    /// 소스 고유 ID(또는 URL)로 트랙 하나를 가져온다.
+    fn get_track(&self, _id: &str) -> Result<TrackInfo> {
+        bail!("이 소스는 ID로 트랙 조회를 지원하지 않습니다")
+    }
+}
+
+/// 검색 결과의 한 페이지 (건너뛸 결과 수와 최대 결과 수).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Page {
+    /// 한 번에 가져오는 기본 결과 수.
+    pub const LIMIT: usize = 10;
+    /// 한 페이지의 최대 결과 수 (Spotify 검색 API의 한도).
+    pub const MAX_LIMIT: usize = 50;
+
+    pub fn first() -> Self {
+        Self {
+            offset: 0,
+            limit: Self::LIMIT,
+        }
+    }
+
+    /// 이 페이지 바로 다음 페이지.
+    pub fn next(self) -> Self {
+        Self {
+            offset: self.offset + self.limit,
+            ..self
+        }
+    }
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self::first()
+    }
+}
+
+/// 검색 결과가 없으면 `parser::query_variants`의 단순화한 검색어로 차례로 다시 검색한다.
+/// 원래 검색어가 아닌 검색어로 찾았으면 그 검색어를 함께 반환한다.
+/// 모든 검색어가 결과 없이 끝나면 빈 목록, 원래 검색어가 실패했고 다른 검색어로도 찾지 못하면 원래 에러.
+pub fn search_variants(
+    source: &dyn MusicSource,
+    info: &TrackInfo,
+) -> Result<(Vec<TrackInfo>, Option<QueryVariant>)> {
+    let mut first_error = None;
+    for (i, variant) in parser::query_variants(info).into_iter().enumerate() {
+        match source.search(&variant.query) {
+            Ok(results) if !results.is_empty() => return Ok((results, (i > 0).then_some(variant))),
+            Ok(_) => {}
+            Err(e) if i == 0 => first_error = Some(e),
+            Err(_) => {}
+        }
+    }
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok((Vec::new(), None)),
+    }
+}
+
+/// 소스마다 스레드를 띄워 `search`를 동시에 실행하고, 끝나는 순서대로 `on_result(소스 번호, 결과)`를
+/// 호출한다. `on_result`가 true를 반환하면(충분한 결과를 얻음) 나머지를 기다리지 않는다.
+/// `timeout`이 지나도 끝나지 않은 소스도 기다리지 않으며, 그런 소스의 번호를 반환한다.
+/// 기다리지 않은 스레드는 백그라운드에서 끝나고 결과는 버린다.
+pub fn race<T: Send + 'static>(
+    clients: &[Arc<dyn MusicSource>],
+    timeout: Duration,
+    search: impl Fn(&dyn MusicSource) -> Result<T> + Send + Sync + 'static,
+    mut on_result: impl FnMut(usize, Result<T>) -> bool,
+) -> Vec<usize> {
+    let search = Arc::new(search);
+    let (tx, rx) = mpsc::channel();
+    for (i, client) in clients.iter().enumerate() {
+        let (client, search, tx) = (client.clone(), search.clone(), tx.clone());
+        std::thread::spawn(move || {
+            let _ = tx.send((i, search(client.as_ref())));
+        });
+    }
+    drop(tx);
+
+    let deadline = Instant::now() + timeout;
+    let mut pending: Vec<usize> = (0..clients.len()).collect();
+    while !pending.is_empty() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let Ok((i, result)) = rx.recv_timeout(remaining) else {
+            break;
+        };
+        pending.retain(|&p| p != i);
+        if on_result(i, result) {
+            break;
+        }
+    }
+    pending
+}
+
+/// 검색 소스 종류.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceKind {
+    Spotify,
+    Melon,
+    /// 설정에 등록된 외부 소스 이름. `[[command_sources]]`의 명령 소스 또는
+    /// `[plugins] dir`의 플러그인을 이 순서로 찾는다.
+    External(String),
+}
+
+impl SourceKind {
+    /// 화면 표시용 이름.
+    pub fn label(&self) -> &str {
+        match self {
+            SourceKind::Spotify => "Spotify",
+            SourceKind::Melon => "Melon",
+            SourceKind::External(name) => name,
+        }
+    }
+
+    /// `--source` 인자와 TrackInfo.source에 쓰는 이름.
+    pub fn name(&self) -> &str {
+        match self {
+            SourceKind::Spotify => "spotify",
+            SourceKind::Melon => "melon",
+            SourceKind::External(name) => name,
+        }
+    }
+
+    /// TrackInfo.source 문자열("spotify", "melon", 외부 소스 이름)에 해당하는 소스를 찾는다.
+    pub fn from_source_name(name: &str, cfg: &Config) -> Option<Self> {
+        match name {
+            "spotify" => Some(SourceKind::Spotify),
+            "melon" => Some(SourceKind::Melon),
+            _ if external_names(cfg).iter().any(|n| n == name) => {
+                Some(SourceKind::External(name.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// 내장 소스와 설정에 등록된 외부 소스 전체.
+    pub fn all(cfg: &Config) -> Vec<Self> {
+        let mut kinds = vec![SourceKind::Spotify, SourceKind::Melon];
+        for name in external_names(cfg) {
+            if !kinds.iter().any(|k| k.label() == name) {
+                kinds.push(SourceKind::External(name));
+            }
+        }
+        kinds
+    }
+}
+
+/// `--source` 인자 파싱. 내장 소스가 아닌 이름은 명령 소스로 보고,
+/// 등록 여부는 소스를 생성할 때 확인한다.
+impl FromStr for SourceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim() {
+            "" => Err("소스 이름이 비어 있습니다".to_string()),
+            "spotify" => Ok(SourceKind::Spotify),
+            "melon" => Ok(SourceKind::Melon),
+            name => Ok(SourceKind::External(name.to_string())),
+        }
+    }
+}
+
+/// 설정을 바탕으로 캐시를 거치는 소스를 생성한다.
+/// 오프라인 모드에서는 네트워크 클라이언트를 만들지 않고 캐시에서만 응답한다.
+pub fn create_source(kind: &SourceKind, cfg: &Config) -> Result<Box<dyn MusicSource>> {
+    let client = if cfg.network.offline {
+        if let SourceKind::External(name) = kind {
+            if !external_names(cfg).contains(name) {
+                bail!("알 수 없는 소스입니다: {}", name);
+            }
+        }
+        None
+    } else {
+        Some(create_client(kind, cfg)?)
+    };
+    let cache = SourceCache::new(cfg.network.cache_dir());
+    Ok(Box::new(CachedSource::new(kind.name(), client, cache)))
+}
+
+/// 소스 종류에 맞는 클라이언트를 생성한다.
+fn create_client(kind: &SourceKind, cfg: &Config) -> Result<Box<dyn MusicSource>> {
+    match kind {
+        SourceKind::Spotify => {
+            if !cfg.spotify.is_configured() {
+                return Err(exit::config_error(
+                    "Spotify가 설정되지 않았습니다. 먼저 'mp3tag config'를 실행하세요.",
+                ));
+            }
+            Ok(Box::new(SpotifyClient::new(&cfg.spotify)?))
+        }
+        SourceKind::Melon => Ok(Box::new(MelonClient::new(&cfg.melon)?)),
+        SourceKind::External(name) => {
+            if let Some(command) = cfg.command_sources.iter().find(|c| &c.name == name) {
+                return Ok(Box::new(CommandSource::new(command)?));
+            }
+            #[cfg(feature = "plugins")]
+            if let Some((_, path)) = plugin_files(cfg).into_iter().find(|(n, _)| n == name) {
+                return Ok(Box::new(plugin::PluginSource::load(&path)?));
+            }
+            bail!(
+                "알 수 없는 소스입니다: {}. config.toml의 [[command_sources]]에 등록하거나 \
+                 [plugins] dir에 플러그인을 넣으세요.",
+                name
+            )
+        }
+    }
+}
+
+/// 설정에 등록된 외부 소스 이름 (명령 소스, 플러그인 순).
+fn external_names(cfg: &Config) -> Vec<String> {
+    let names = cfg.command_sources.iter().map(|c| c.name.clone());
+    #[cfg(feature = "plugins")]
+    let names = names.chain(plugin_files(cfg).into_iter().map(|(name, _)| name));
+    names.collect()
+}
+
+/// 플러그인 디렉토리의 (소스 이름, 경로) 목록.
+#[cfg(feature = "plugins")]
+fn plugin_files(cfg: &Config) -> Vec<(String, std::path::PathBuf)> {
+    cfg.plugins
+        .dir
+        .as_deref()
+        .map(plugin::discover)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// "IU Blueming"으로만 결과를 돌려주는 소스.
+    struct FakeSource;
+
+    impl MusicSource for FakeSource {
+        fn search(&self, query: &str) -> Result<Vec<TrackInfo>> {
+            Ok(match query {
+                "IU Blueming" => vec![TrackInfo {
+                    title: Some("Blueming".to_string()),
+                    ..Default::default()
+                }],
+                _ => Vec::new(),
+            })
+        }
+
+        fn fetch_album_art(&self, _track: &TrackInfo) -> Result<Vec<u8>> {
+            bail!("없음")
+        }
+    }
+
+    #[test]
+    fn test_search_variants_retries_simplified_query() {
+        let info = TrackInfo {
+            artists: vec!["IU".to_string()],
+            title: Some("Blueming (Live)".to_string()),
+            ..Default::default()
+        };
+        let (results, variant) = search_variants(&FakeSource, &info).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(variant.unwrap().label, "괄호 제거");
+
+        let exact = TrackInfo {
+            title: Some("Blueming".to_string()),
+            artists: vec!["IU".to_string()],
+            ..Default::default()
+        };
+        assert!(search_variants(&FakeSource, &exact).unwrap().1.is_none());
+
+        let missing = TrackInfo {
+            title: Some("밤편지".to_string()),
+            ..Default::default()
+        };
+        assert!(search_variants(&FakeSource, &missing).unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn test_default_search_page_slices_results() {
+        let first = Page {
+            offset: 0,
+            limit: 1,
+        };
+        assert_eq!(
+            FakeSource.search_page("IU Blueming", first).unwrap().len(),
+            1
+        );
+        assert!(FakeSource
+            .search_page("IU Blueming", first.next())
+            .unwrap()
+            .is_empty());
+        assert_eq!(first.next().offset, 1);
+        assert_eq!(Page::first().next().offset, Page::LIMIT);
+    }
+
+    /// 테스트가 신호를 보낼 때까지 검색을 끝내지 않고, 소스 번호를 제목으로 돌려주는 소스.
+    struct GatedSource {
+        id: usize,
+        gate: Mutex<mpsc::Receiver<()>>,
+    }
+
+    impl MusicSource for GatedSource {
+        fn search(&self, _query: &str) -> Result<Vec<TrackInfo>> {
+            let _ = self.gate.lock().unwrap().recv();
+            Ok(vec![TrackInfo {
+                title: Some(self.id.to_string()),
+                ..Default::default()
+            }])
+        }
+
+        fn fetch_album_art(&self, _track: &TrackInfo) -> Result<Vec<u8>> {
+            bail!("없음")
+        }
+    }
+
+    /// `count`개의 `GatedSource`와 각 소스의 검색을 끝내는 송신자.
+    fn gated_sources(count: usize) -> (Vec<Arc<dyn MusicSource>>, Vec<mpsc::Sender<()>>) {
+        (0..count)
+            .map(|id| {
+                let (tx, rx) = mpsc::channel();
+                let source: Arc<dyn MusicSource> = Arc::new(GatedSource {
+                    id,
+                    gate: Mutex::new(rx),
+                });
+                (source, tx)
+            })
+            .unzip()
+    }
+
+    fn title(result: Result<Vec<TrackInfo>>) -> String {
+        result.unwrap()[0].title.clone().unwrap()
+    }
+
+    #[test]
+    fn test_race_reports_in_arrival_order_and_times_out() {
+        let search = |client: &dyn MusicSource| client.search("IU");
+
+        // 2 → 0 → 1 순서로 끝나게 하면 그 순서대로 보고한다
+        let (clients, gates) = gated_sources(3);
+        gates[2].send(()).unwrap();
+        let mut order = Vec::new();
+        let pending = race(&clients, Duration::from_secs(60), search, |i, result| {
+            order.push((i, title(result)));
+            match i {
+                2 => gates[0].send(()).unwrap(),
+                0 => gates[1].send(()).unwrap(),
+                _ => {}
+            }
+            false
+        });
+        assert_eq!(
+            order,
+            [
+                (2, "2".to_string()),
+                (0, "0".to_string()),
+                (1, "1".to_string())
+            ]
+        );
+        assert!(pending.is_empty());
+
+        // 끝나지 않는 소스는 timeout 뒤 기다리지 않고 번호를 돌려준다
+        let (clients, _gates) = gated_sources(1);
+        let pending = race(&clients, Duration::from_millis(10), search, |_, _| {
+            panic!("끝나지 않은 소스의 결과를 보고했습니다")
+        });
+        assert_eq!(pending, [0]);
+
+        // 충분한 결과를 얻으면 나머지가 끝나도 기다리지 않는다
+        let (clients, gates) = gated_sources(3);
+        gates[2].send(()).unwrap();
+        let mut calls = 0;
+        let pending = race(&clients, Duration::from_secs(60), search, |_, _| {
+            calls += 1;
+            gates[0].send(()).unwrap();
+            gates[1].send(()).unwrap();
+            true
+        });
+        assert_eq!(calls, 1);
+        assert_eq!(pending, [0, 1]);
+    }
 }
@@ -0,0 +1,120 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Open Library 클라이언트. 오디오북의 책 제목, 저자, 첫 출간 연도, 표지를 찾는다.
+pub struct OpenLibraryClient {
+    client: reqwest::blocking::Client,
+}
+
+/// 검색한 책 한 권.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Book {
+    pub title: String,
+    pub author: Option<String>,
+    pub year: Option<i32>,
+    /// 표지 이미지 ID (covers.openlibrary.org)
+    cover_id: Option<u64>,
+}
+
+impl Book {
+    /// 선택 목록에 보일 "제목 - 저자 (연도)".
+    pub fn summary(&self) -> String {
+        let mut summary = self.title.clone();
+        if let Some(author) = &self.author {
+            summary += &format!(" - {}", author);
+        }
+        if let Some(year) = self.year {
+            summary += &format!(" ({})", year);
+        }
+        summary
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    docs: Vec<SearchDoc>,
+}
+
+#[derive(Deserialize)]
+struct SearchDoc {
+    title: String,
+    #[serde(default)]
+    author_name: Vec<String>,
+    first_publish_year: Option<i32>,
+    cover_i: Option<u64>,
+}
+
+impl From<SearchDoc> for Book {
+    fn from(doc: SearchDoc) -> Self {
+        Self {
+            title: doc.title,
+            author: (!doc.author_name.is_empty()).then(|| doc.author_name.join(", ")),
+            year: doc.first_publish_year,
+            cover_id: doc.cover_i,
+        }
+    }
+}
+
+impl OpenLibraryClient {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("mp3tag/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Open Library HTTP 클라이언트 생성에 실패했습니다")?;
+        Ok(Self { client })
+    }
+
+    /// 제목/저자 검색어로 책을 찾는다. 결과는 Open Library의 관련도순이다.
+    pub fn search(&self, query: &str) -> Result<Vec<Book>> {
+        let resp: SearchResponse = self
+            .client
+            .get("https://openlibrary.org/search.json")
+            .query(&[
+                ("q", query),
+                ("limit", "10"),
+                ("fields", "title,author_name,first_publish_year,cover_i"),
+            ])
+            .send()
+            .context("Open Library 연결에 실패했습니다")?
+            .error_for_status()?
+            .json()
+            .context("Open Library 응답을 해석할 수 없습니다")?;
+        Ok(resp.docs.into_iter().map(Book::from).collect())
+    }
+
+    /// 책 표지를 큰 크기로 다운로드한다. 표지가 없으면 None.
+    pub fn fetch_cover(&self, book: &Book) -> Result<Option<Vec<u8>>> {
+        let Some(id) = book.cover_id else {
+            return Ok(None);
+        };
+        let url = format!("https://covers.openlibrary.org/b/id/{}-L.jpg", id);
+        let data = self
+            .client
+            .get(&url)
+            .send()
+            .context("Open Library 표지 다운로드에 실패했습니다")?
+            .error_for_status()?
+            .bytes()?
+            .to_vec();
+        Ok(Some(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_response() {
+        let json = r#"{"numFound": 2, "docs": [
+            {"title": "채식주의자", "author_name": ["한강"], "first_publish_year": 2007, "cover_i": 8231856},
+            {"title": "Untitled"}
+        ]}"#;
+        let resp: SearchResponse = serde_json::from_str(json).unwrap();
+        let books: Vec<Book> = resp.docs.into_iter().map(Book::from).collect();
+        assert_eq!(books[0].summary(), "채식주의자 - 한강 (2007)");
+        assert_eq!(books[0].cover_id, Some(8231856));
+        assert_eq!(books[1].summary(), "Untitled");
+        assert_eq!(books[1].author, None);
+    }
+}
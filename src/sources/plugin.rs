@@ -0,0 +1,204 @@
+use std::ffi::{c_char, CStr, CString};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+
+use crate::models::TrackInfo;
+use crate::sources::command::{self, CommandTrack, Request};
+use crate::sources::MusicSource;
+
+/// 호스트가 지원하는 플러그인 ABI 버전.
+pub const ABI_VERSION: u32 = 1;
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type CallFn = unsafe extern "C" fn(*const c_char) -> *mut c_char;
+type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+/// 플러그인 디렉토리의 동적 라이브러리(.so/.dylib/.dll)로 구현된 소스.
+///
+/// 플러그인은 다음 C 함수를 내보내야 한다.
+///
+/// - `u32 mp3tag_plugin_abi_version()`: [`ABI_VERSION`]과 같아야 한다.
+/// - `char *mp3tag_plugin_call(const char *request)`: 명령 소스와 같은 JSON 요청을 받아
+///   JSON 응답을 반환한다. 실패하면 `{"error": "메시지"}`를 반환한다.
+/// - `void mp3tag_plugin_free(char *response)`: `mp3tag_plugin_call`이 반환한 문자열을 해제한다.
+///
+/// 문자열은 모두 NUL로 끝나는 UTF-8이다. 소스 이름은 파일명에서 `lib` 접두사와 확장자를 뺀 것이다.
+/// 소스는 동시에 검색되므로(`sources::race`) `mp3tag_plugin_call`은 여러 스레드에서 동시에 호출될 수 있다.
+pub struct PluginSource {
+    name: String,
+    call: CallFn,
+    free: FreeFn,
+    client: reqwest::blocking::Client,
+    // 함수 포인터가 유효하도록 라이브러리를 소스와 함께 유지한다. 마지막에 해제되어야 한다.
+    _library: libloading::Library,
+}
+
+impl PluginSource {
+    /// 동적 라이브러리를 로드하고 ABI 버전을 확인한다.
+    pub fn load(path: &Path) -> Result<Self> {
+        let name = plugin_name(path).context("플러그인 파일명이 올바르지 않습니다")?;
+
+        // SAFETY: 라이브러리의 초기화 코드가 실행된다. 신뢰할 수 있는 플러그인 디렉토리의 파일만
+        // 로드한다는 것이 전제다.
+        let library = unsafe { libloading::Library::new(path) }
+            .with_context(|| format!("플러그인 로드에 실패했습니다: {}", path.display()))?;
+
+        // SAFETY: 심볼의 시그니처는 구조체 문서의 C 함수 선언과 같아야 한다 (ABI 버전으로 확인).
+        // 복사한 함수 포인터는 `_library`와 함께 보관해 라이브러리보다 오래 쓰이지 않는다.
+        let (version, call, free) = unsafe {
+            let version = library
+                .get::<AbiVersionFn>(b"mp3tag_plugin_abi_version\0")
+                .context("mp3tag_plugin_abi_version 함수가 없습니다")?;
+            let call = library
+                .get::<CallFn>(b"mp3tag_plugin_call\0")
+                .context("mp3tag_plugin_call 함수가 없습니다")?;
+            let free = library
+                .get::<FreeFn>(b"mp3tag_plugin_free\0")
+                .context("mp3tag_plugin_free 함수가 없습니다")?;
+            (version(), *call, *free)
+        };
+        if version != ABI_VERSION {
+            bail!(
+                "'{}' 플러그인의 ABI 버전({})이 지원하는 버전({})과 다릅니다",
+                name,
+                version,
+                ABI_VERSION
+            );
+        }
+
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("mp3tag/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("HTTP 클라이언트 생성에 실패했습니다")?;
+
+        Ok(Self {
+            name,
+            call,
+            free,
+            client,
+            _library: library,
+        })
+    }
+
+    /// 플러그인에 JSON 요청을 보내고 응답을 파싱한다.
+    fn request<T: DeserializeOwned>(&self, request: &Request) -> Result<T> {
+        let input = CString::new(serde_json::to_vec(request)?)?;
+        // SAFETY: `input`은 호출 동안 살아 있는 NUL로 끝나는 문자열이다. 플러그인은 NUL로 끝나는
+        // 문자열을 반환하고, 그 문자열은 복사한 뒤 한 번만 플러그인의 free 함수로 해제한다.
+        // 함수 포인터는 `_library`가 살아 있는 동안 유효하다.
+        let output = unsafe {
+            let ptr = (self.call)(input.as_ptr());
+            if ptr.is_null() {
+                bail!("'{}' 플러그인이 응답을 반환하지 않았습니다", self.name);
+            }
+            let text = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+            (self.free)(ptr);
+            text
+        };
+        parse_response(&self.name, &output)
+    }
+}
+
+impl MusicSource for PluginSource {
+    fn search(&self, query: &str) -> Result<Vec<TrackInfo>> {
+        let tracks: Vec<CommandTrack> = self.request(&Request::Search { query })?;
+        Ok(tracks
+            .into_iter()
+            .map(|t| t.into_track(&self.name))
+            .collect())
+    }
+
+    fn fetch_album_art(&self, track: &TrackInfo) -> Result<Vec<u8>> {
+        command::download_art(&self.client, track)
+    }
+
+    fn get_track(&self, id: &str) -> Result<TrackInfo> {
+        let track: CommandTrack = self.request(&Request::GetTrack { id })?;
+        Ok(track.into_track(&self.name))
+    }
+}
+
+/// 응답 JSON을 파싱한다. `{"error": "..."}` 형식이면 그 메시지로 실패한다.
+fn parse_response<T: DeserializeOwned>(name: &str, output: &str) -> Result<T> {
+    let value: serde_json::Value = serde_json::from_str(output)
+        .with_context(|| format!("'{}' 플러그인의 응답 파싱에 실패했습니다", name))?;
+    if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+        bail!("'{}' 플러그인이 실패했습니다: {}", name, error);
+    }
+    serde_json::from_value(value)
+        .with_context(|| format!("'{}' 플러그인의 응답 형식이 올바르지 않습니다", name))
+}
+
+/// 플러그인 파일의 소스 이름. 플랫폼의 동적 라이브러리 확장자가 아니면 None.
+fn plugin_name(path: &Path) -> Option<String> {
+    if path.extension()? != std::env::consts::DLL_EXTENSION {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    let name = stem.strip_prefix("lib").unwrap_or(stem);
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// 플러그인 디렉토리의 (소스 이름, 경로) 목록. 디렉토리가 없으면 빈 목록.
+pub fn discover(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<(String, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter_map(|path| plugin_name(&path).map(|name| (name, path)))
+        .collect();
+    plugins.sort();
+    plugins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_plugin_name() {
+        let ext = std::env::consts::DLL_EXTENSION;
+        assert_eq!(
+            plugin_name(Path::new(&format!("/plugins/libvgmdb.{}", ext))).as_deref(),
+            Some("vgmdb")
+        );
+        assert_eq!(
+            plugin_name(Path::new(&format!("bandcamp.{}", ext))).as_deref(),
+            Some("bandcamp")
+        );
+        assert_eq!(plugin_name(Path::new("/plugins/readme.txt")), None);
+    }
+
+    #[test]
+    fn test_parse_response_error() {
+        let err = parse_response::<Vec<CommandTrack>>("vgmdb", r#"{"error": "not found"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not found"), "{}", err);
+
+        let tracks: Vec<CommandTrack> =
+            parse_response("vgmdb", r#"[{"title": "Snow Flower"}]"#).unwrap();
+        assert_eq!(tracks.len(), 1);
+    }
+
+    #[test]
+    fn test_discover_and_reject_invalid_library() {
+        let dir = TempDir::new("plugin");
+        let fake = dir.join(format!("libfake.{}", std::env::consts::DLL_EXTENSION));
+        std::fs::write(&fake, b"not a library").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"").unwrap();
+
+        let found = discover(&dir);
+        assert_eq!(found, vec![("fake".to_string(), fake.clone())]);
+        assert!(PluginSource::load(&fake).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+
+use crate::config::ScrapeConfig;
+
+/// 호스트별 마지막 요청 예약 시각. 여러 스레드/클라이언트가 같은 호스트에 요청해도
+/// 간격이 지켜지도록 프로세스 전체에서 공유한다.
+type Slot = Arc<Mutex<Option<Instant>>>;
+
+fn host_slot(host: &str) -> Slot {
+    static SLOTS: OnceLock<Mutex<HashMap<String, Slot>>> = OnceLock::new();
+    let slots = SLOTS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut slots = slots.lock().unwrap_or_else(|e| e.into_inner());
+    slots.entry(host.to_string()).or_default().clone()
+}
+
+/// 스크래핑용 reqwest 클라이언트를 만든다. User-Agent는 설정값이 있으면 그것을, 없으면 `default_user_agent`를 쓰고
+/// 설정의 `headers`를 모든 요청에 더한다. 헤더 이름이나 값이 잘못됐으면 에러.
+pub fn http_client(
+    name: &str,
+    default_user_agent: &str,
+    config: &ScrapeConfig,
+) -> Result<reqwest::blocking::Client> {
+    reqwest::blocking::Client::builder()
+        .user_agent(
+            config
+                .user_agent
+                .as_deref()
+                .filter(|ua| !ua.trim().is_empty())
+                .unwrap_or(default_user_agent),
+        )
+        .default_headers(header_map(config)?)
+        .build()
+        .with_context(|| format!("{} HTTP 클라이언트 생성에 실패했습니다", name))
+}
+
+fn header_map(config: &ScrapeConfig) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.headers {
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .with_context(|| format!("헤더 이름이 잘못됐습니다: {}", name))?;
+        let value = HeaderValue::from_str(value.trim())
+            .with_context(|| format!("{} 헤더 값이 잘못됐습니다", name))?;
+        headers.insert(name, value);
+    }
+    Ok(headers)
+}
+
+/// 스크래핑 소스용 HTTP 클라이언트.
+/// 호스트별 요청 간격(지연 + 무작위 지터)을 지키고, 일시적 오류는 지수 백오프로 재시도한다.
+/// 대량 가져오기에서 사용자의 IP가 차단되지 않도록 하기 위함이다.
+pub struct PoliteClient {
+    name: &'static str,
+    client: reqwest::blocking::Client,
+    config: ScrapeConfig,
+}
+
+impl PoliteClient {
+    /// `name`은 에러 메시지에 표시할 소스 이름이다.
+    pub fn new(
+        name: &'static str,
+        client: reqwest::blocking::Client,
+        config: &ScrapeConfig,
+    ) -> Self {
+        Self {
+            name,
+            client,
+            config: config.clone(),
+        }
+    }
+
+    /// GET 요청을 보내 본문을 문자열로 반환한다.
+    pub fn get_text(&self, url: &str) -> Result<String> {
+        self.get(url)?
+            .text()
+            .with_context(|| format!("{} 응답 읽기에 실패했습니다", self.name))
+    }
+
+    /// GET 요청을 보내 본문을 바이트로 반환한다.
+    pub fn get_bytes(&self, url: &str) -> Result<Vec<u8>> {
+        Ok(self
+            .get(url)?
+            .bytes()
+            .with_context(|| format!("{} 응답 읽기에 실패했습니다", self.name))?
+            .to_vec())
+    }
+
+    /// 요청 간격을 지키며 GET 요청을 보낸다.
+    /// 네트워크 오류, 429, 5xx는 `max_retries`번까지 재시도하고 403은 차단으로 보고 즉시 실패한다.
+    fn get(&self, url: &str) -> Result<reqwest::blocking::Response> {
+        let mut attempt = 0;
+        loop {
+            self.wait_turn(url);
+            let can_retry = attempt < self.config.max_retries;
+
+            match self.client.get(url).send() {
+                Ok(resp) if resp.status() == StatusCode::FORBIDDEN => {
+                    bail!(
+                        "{}이(가) 접근을 차단했습니다 (HTTP 403). 잠시 후 다시 시도하거나 요청 간격(delay_ms)을 늘리세요",
+                        self.name
+                    );
+                }
+                Ok(resp) if is_retryable(resp.status()) && can_retry => {}
+                Ok(resp) => {
+                    return resp
+                        .error_for_status()
+                        .with_context(|| format!("{} 요청이 실패했습니다", self.name));
+                }
+                Err(_) if can_retry => {}
+                Err(e) => {
+                    return Err(e).with_context(|| format!("{} 연결에 실패했습니다", self.name));
+                }
+            }
+
+            attempt += 1;
+            std::thread::sleep(self.backoff(attempt));
+        }
+    }
+
+    /// 같은 호스트에 대한 직전 요청으로부터 지연 + 지터만큼 지날 때까지 기다린다.
+    fn wait_turn(&self, url: &str) {
+        if self.config.delay_ms == 0 && self.config.jitter_ms == 0 {
+            return;
+        }
+
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .unwrap_or_default();
+        let slot = host_slot(&host);
+
+        let jitter = if self.config.jitter_ms > 0 {
+            rand::thread_rng().gen_range(0..=self.config.jitter_ms)
+        } else {
+            0
+        };
+        let gap = Duration::from_millis(self.config.delay_ms + jitter);
+
+        // 다음 요청 시각을 예약한 뒤 잠금을 풀고 기다린다
+        let scheduled = {
+            let mut last = slot.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let next = match *last {
+                Some(prev) => (prev + gap).max(now),
+                None => now,
+            };
+            *last = Some(next);
+            next
+        };
+
+        let now = Instant::now();
+        if scheduled > now {
+            std::thread::sleep(scheduled - now);
+        }
+    }
+
+    /// n번째 재시도 전 대기 시간 (지연의 2^n배, 최소 500ms).
+    fn backoff(&self, attempt: u32) -> Duration {
+        let base = self.config.delay_ms.max(500);
+        Duration::from_millis(base.saturating_mul(1 << attempt.min(6)))
+    }
+}
+
+/// 잠시 후 재시도하면 성공할 수 있는 상태 코드인지 확인한다.
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable(StatusCode::NOT_FOUND));
+        assert!(!is_retryable(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn test_header_map() {
+        let mut config = ScrapeConfig::default();
+        config
+            .headers
+            .insert("Accept-Language".to_string(), "ko-KR,ko".to_string());
+        config
+            .headers
+            .insert("Cookie".to_string(), "PCID=1; AUTH=abc".to_string());
+        let headers = header_map(&config).unwrap();
+        assert_eq!(headers["accept-language"], "ko-KR,ko");
+        assert_eq!(headers["cookie"], "PCID=1; AUTH=abc");
+
+        config
+            .headers
+            .insert("Bad Header".to_string(), "x".to_string());
+        assert!(header_map(&config).is_err());
+        assert!(http_client("test", "mp3tag", &config).is_err());
+    }
+
+    #[test]
+    fn test_backoff_grows() {
+        let client = PoliteClient::new(
+            "test",
+            reqwest::blocking::Client::new(),
+            &ScrapeConfig {
+                delay_ms: 1000,
+                jitter_ms: 0,
+                max_retries: 3,
+                ..Default::default()
+            },
+        );
+        assert_eq!(client.backoff(1), Duration::from_millis(2000));
+        assert_eq!(client.backoff(2), Duration::from_millis(4000));
+    }
+
+    #[test]
+    fn test_wait_turn_spaces_requests() {
+        let client = PoliteClient::new(
+            "test",
+            reqwest::blocking::Client::new(),
+            &ScrapeConfig {
+                delay_ms: 50,
+                jitter_ms: 0,
+                max_retries: 0,
+                ..Default::default()
+            },
+        );
+        let url = "https://throttle-test.invalid/";
+        let start = Instant::now();
+        client.wait_turn(url);
+        client.wait_turn(url);
+        client.wait_turn(url);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use scraper::Selector;
+
+/// 스크래핑 소스의 CSS 선택자 모음 ("페이지.이름" → 선택자).
+/// 기본값은 바이너리에 넣은 TOML이고, 사용자 파일에는 바꿀 키만 쓰면 된다.
+/// 사이트 구조가 바뀌었을 때 다시 빌드하지 않고 고칠 수 있게 하기 위함이다.
+pub struct Selectors {
+    css: BTreeMap<String, String>,
+    compiled: BTreeMap<String, Selector>,
+}
+
+impl Selectors {
+    /// 기본 선택자(`defaults`, TOML 텍스트)에 `user` 파일의 값을 덮어써 읽는다.
+    /// 기본값에 없는 키나 읽을 수 없는 선택자가 있으면 에러.
+    pub fn load(defaults: &str, user: Option<&Path>) -> Result<Self> {
+        let mut css = flatten(defaults).context("기본 선택자 형식이 잘못됐습니다")?;
+        if let Some(path) = user {
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("선택자 파일을 읽을 수 없습니다: {}", path.display()))?;
+            let overrides = flatten(&text)
+                .with_context(|| format!("선택자 파일 형식이 잘못됐습니다: {}", path.display()))?;
+            for (key, value) in overrides {
+                if !css.contains_key(&key) {
+                    bail!("알 수 없는 선택자 키입니다: {} ({})", key, path.display());
+                }
+                css.insert(key, value);
+            }
+        }
+
+        let compiled = css
+            .iter()
+            .map(|(key, value)| {
+                Selector::parse(value)
+                    .map(|selector| (key.clone(), selector))
+                    .map_err(|e| {
+                        anyhow!("{} 선택자를 읽을 수 없습니다: \"{}\" ({})", key, value, e)
+                    })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { css, compiled })
+    }
+
+    /// 키의 선택자. 기본값에 없는 키는 코드와 기본 파일이 맞지 않는 버그이므로 패닉한다.
+    pub fn get(&self, key: &str) -> &Selector {
+        self.compiled
+            .get(key)
+            .unwrap_or_else(|| panic!("기본 선택자에 {} 키가 없습니다", key))
+    }
+
+    /// "페이지." 으로 시작하는 키와 선택자 문자열, 키 순서.
+    pub fn page<'a>(&'a self, page: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.css
+            .iter()
+            .filter(move |(key, _)| key.split_once('.').is_some_and(|(p, _)| p == page))
+            .map(|(key, css)| (key.as_str(), css.as_str()))
+    }
+}
+
+/// `[페이지] 이름 = "선택자"` 표를 "페이지.이름" 키로 편다.
+fn flatten(text: &str) -> Result<BTreeMap<String, String>> {
+    let table: toml::Table = toml::from_str(text)?;
+    let mut css = BTreeMap::new();
+    for (page, entries) in table {
+        let entries = entries
+            .as_table()
+            .with_context(|| format!("[{}]는 표여야 합니다", page))?;
+        for (name, value) in entries {
+            let value = value
+                .as_str()
+                .with_context(|| format!("{}.{} 값은 문자열이어야 합니다", page, name))?;
+            css.insert(format!("{}.{}", page, name), value.to_string());
+        }
+    }
+    Ok(css)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    const DEFAULTS: &str =
+        "[search]\nrow = \"tr\"\ntitle = \"a.title\"\n\n[detail]\nart = \"img\"\n";
+
+    #[test]
+    fn test_user_file_overrides_defaults() {
+        let dir = TempDir::new("selectors");
+        let path = dir.join("selectors.toml");
+
+        std::fs::write(&path, "[search]\ntitle = \"a.song_title\"\n").unwrap();
+        let selectors = Selectors::load(DEFAULTS, Some(&path)).unwrap();
+        let search: Vec<_> = selectors.page("search").collect();
+        assert_eq!(
+            search,
+            [("search.row", "tr"), ("search.title", "a.song_title")]
+        );
+        let html = scraper::Html::parse_fragment("<a class=\"song_title\">밤편지</a>");
+        assert_eq!(html.select(selectors.get("search.title")).count(), 1);
+
+        std::fs::write(&path, "[search]\ntitel = \"a\"\n").unwrap();
+        let err = Selectors::load(DEFAULTS, Some(&path)).err().unwrap();
+        assert!(err.to_string().contains("search.titel"));
+
+        std::fs::write(&path, "[detail]\nart = \"img[\"\n").unwrap();
+        let err = Selectors::load(DEFAULTS, Some(&path)).err().unwrap();
+        assert!(err.to_string().contains("detail.art"));
+    }
+}
@@ -1,15 +1,27 @@
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
 use base64::Engine;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 
-use crate::config::SpotifyConfig;
-use crate::models::TrackInfo;
-use crate::sources::MusicSource;
+use crate::config::{self, SpotifyConfig};
+use crate::core::parser;
+use crate::exit;
+use crate::models::{AlbumInfo, TrackInfo};
+use crate::sources::http::{HttpClient, Request, ReqwestClient};
+use crate::sources::spotify_auth;
+use crate::sources::{MusicSource, Page};
+
+const API_BASE: &str = "https://api.spotify.com/v1";
+const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
 
 /// Spotify Web API 클라이언트.
 /// Client Credentials Flow로 인증하여 검색 및 앨범 아트 다운로드를 수행한다.
+/// `with_user_login`으로 만들면 사용자 토큰으로 인증하여 좋아요 표시한 곡도 읽을 수 있다.
+/// 요청은 `HttpClient`로 보내므로 `with_http`로 다른 전송 계층을 넣을 수 있다.
 pub struct SpotifyClient {
-    client: reqwest::blocking::Client,
+    http: Arc<dyn HttpClient>,
     access_token: String,
 }
 
@@ -28,26 +40,101 @@ struct TracksResult {
     items: Vec<SpotifyTrack>,
 }
 
+#[derive(Deserialize)]
+struct AlbumSearchResponse {
+    albums: AlbumsResult,
+}
+
+#[derive(Deserialize)]
+struct AlbumsResult {
+    items: Vec<SpotifyAlbum>,
+}
+
 #[derive(Deserialize)]
 struct SpotifyTrack {
+    id: Option<String>,
     name: String,
     artists: Vec<SpotifyArtist>,
     album: SpotifyAlbum,
     track_number: u32,
+    disc_number: Option<u32>,
+    duration_ms: Option<u64>,
+    #[serde(default)]
+    explicit: Option<bool>,
 }
 
 #[derive(Deserialize)]
 struct SpotifyArtist {
+    id: Option<String>,
     name: String,
 }
 
 #[derive(Deserialize)]
 struct SpotifyAlbum {
+    id: Option<String>,
     name: String,
+    #[serde(default)]
+    artists: Vec<SpotifyArtist>,
     release_date: Option<String>,
+    total_tracks: Option<u32>,
     images: Vec<SpotifyImage>,
 }
 
+/// `/albums/{id}` 응답. 검색 결과의 앨범보다 음반사와 트랙 목록이 추가로 있다.
+#[derive(Deserialize)]
+struct SpotifyAlbumFull {
+    #[serde(flatten)]
+    album: SpotifyAlbum,
+    label: Option<String>,
+    #[serde(default)]
+    genres: Vec<String>,
+    tracks: AlbumTracks,
+}
+
+/// 앨범 트랙 목록의 한 페이지. 50곡이 넘으면 `next`로 다음 페이지를 가져온다.
+#[derive(Deserialize)]
+struct AlbumTracks {
+    items: Vec<SpotifyAlbumTrack>,
+    next: Option<String>,
+}
+
+/// 앨범 트랙 목록의 항목 (앨범 정보가 없는 simplified track).
+#[derive(Deserialize)]
+struct SpotifyAlbumTrack {
+    id: Option<String>,
+    name: String,
+    artists: Vec<SpotifyArtist>,
+    track_number: u32,
+    disc_number: Option<u32>,
+    duration_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct SpotifyArtistFull {
+    #[serde(default)]
+    genres: Vec<String>,
+}
+
+/// `/me/tracks`, `/playlists/{id}/tracks` 응답의 한 페이지.
+#[derive(Deserialize)]
+struct TrackPage {
+    items: Vec<TrackPageItem>,
+    next: Option<String>,
+    total: usize,
+}
+
+/// 페이지 항목. 플레이리스트에는 삭제된 곡(null)이나 팟캐스트 에피소드가 섞여 있을 수 있어
+/// 트랙으로 해석되는 항목만 사용한다.
+#[derive(Deserialize)]
+struct TrackPageItem {
+    track: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct PlaylistName {
+    name: String,
+}
+
 #[derive(Deserialize)]
 struct SpotifyImage {
     url: String,
@@ -57,40 +144,117 @@ struct SpotifyImage {
 impl SpotifyClient {
     /// 설정에서 자격증명을 읽어 인증 후 클라이언트를 생성한다.
     pub fn new(config: &SpotifyConfig) -> Result<Self> {
+        Self::with_http(config, Arc::new(ReqwestClient::default()))
+    }
+
+    /// `new`와 같지만 모든 요청을 `http`로 보낸다.
+    pub fn with_http(config: &SpotifyConfig, http: Arc<dyn HttpClient>) -> Result<Self> {
         let client_id = config
             .client_id
             .as_ref()
-            .context("Spotify client_id가 설정되지 않았습니다")?;
+            .ok_or_else(|| exit::config_error("Spotify client_id가 설정되지 않았습니다"))?;
         let client_secret = config
             .client_secret
             .as_ref()
-            .context("Spotify client_secret가 설정되지 않았습니다")?;
+            .ok_or_else(|| exit::config_error("Spotify client_secret가 설정되지 않았습니다"))?;
+
+        let access_token = Self::authenticate(http.as_ref(), client_id, client_secret)?;
 
-        let client = reqwest::blocking::Client::new();
-        let access_token = Self::authenticate(&client, client_id, client_secret)?;
+        Ok(Self { http, access_token })
+    }
+
+    /// `mp3tag login`으로 저장한 refresh token으로 사용자 인증된 클라이언트를 생성한다.
+    /// Spotify가 refresh token을 새로 발급하면 설정 파일에 저장한다.
+    pub fn with_user_login(config: &SpotifyConfig) -> Result<Self> {
+        let (Some(client_id), Some(refresh_token)) = (&config.client_id, &config.refresh_token)
+        else {
+            anyhow::bail!("Spotify에 로그인되어 있지 않습니다. 먼저 'mp3tag login'을 실행하세요.");
+        };
+
+        let http: Arc<dyn HttpClient> = Arc::new(ReqwestClient::default());
+        let tokens = spotify_auth::refresh(http.as_ref(), client_id, refresh_token)?;
+        if let Some(new_token) = tokens.refresh_token.filter(|t| t != refresh_token) {
+            config::update_config(|c| c.spotify.refresh_token = Some(new_token))
+                .context("새 Spotify refresh token 저장에 실패했습니다")?;
+        }
 
         Ok(Self {
-            client,
-            access_token,
+            http,
+            access_token: tokens.access_token,
         })
     }
 
+    /// 좋아요 표시한 곡 전체를 가져온다. 페이지마다 (가져온 수, 전체 수)로 `on_page`를 호출한다.
+    pub fn liked_tracks(&self, on_page: impl FnMut(usize, usize)) -> Result<Vec<TrackInfo>> {
+        self.paged_tracks(format!("{}/me/tracks?limit=50", API_BASE), on_page)
+            .context("좋아요 표시한 곡을 가져오지 못했습니다")
+    }
+
+    /// 플레이리스트(URL, URI 또는 ID)의 이름과 트랙 목록을 플레이리스트 순서대로 가져온다.
+    /// 공개 플레이리스트는 로그인 없이도 읽을 수 있다.
+    pub fn playlist(
+        &self,
+        playlist: &str,
+        on_page: impl FnMut(usize, usize),
+    ) -> Result<(String, Vec<TrackInfo>)> {
+        let id = parse_spotify_id(playlist);
+        let info: PlaylistName = self
+            .get_json(
+                &format!("{}/playlists/{}", API_BASE, id),
+                &[("fields", "name")],
+            )
+            .context("Spotify 플레이리스트를 찾을 수 없습니다")?;
+        let tracks = self
+            .paged_tracks(
+                format!("{}/playlists/{}/tracks?limit=100", API_BASE, id),
+                on_page,
+            )
+            .context("플레이리스트 트랙 목록을 가져오지 못했습니다")?;
+        Ok((info.name, tracks))
+    }
+
+    /// `next` 링크를 따라가며 모든 페이지의 트랙을 가져온다.
+    fn paged_tracks(
+        &self,
+        first_url: String,
+        mut on_page: impl FnMut(usize, usize),
+    ) -> Result<Vec<TrackInfo>> {
+        let mut tracks = Vec::new();
+        let mut fetched = 0;
+        let mut next = Some(first_url);
+
+        while let Some(url) = next {
+            let page: TrackPage = self.get_json(&url, &[])?;
+            fetched += page.items.len();
+            tracks.extend(Self::convert_page_items(page.items));
+            on_page(fetched, page.total);
+            next = page.next;
+        }
+
+        Ok(tracks)
+    }
+
+    /// 페이지 항목 중 트랙으로 해석되는 것만 TrackInfo로 변환한다.
+    fn convert_page_items(items: Vec<TrackPageItem>) -> Vec<TrackInfo> {
+        items
+            .into_iter()
+            .filter_map(|item| serde_json::from_value::<SpotifyTrack>(item.track?).ok())
+            .map(|t| Self::convert_track(&t))
+            .collect()
+    }
+
     /// Client Credentials Flow로 access token을 발급받는다.
-    fn authenticate(
-        client: &reqwest::blocking::Client,
-        client_id: &str,
-        client_secret: &str,
-    ) -> Result<String> {
+    fn authenticate(http: &dyn HttpClient, client_id: &str, client_secret: &str) -> Result<String> {
         let credentials = format!("{}:{}", client_id, client_secret);
         let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
 
-        let resp: TokenResponse = client
-            .post("https://accounts.spotify.com/api/token")
+        let request = Request::post(TOKEN_URL)
             .header("Authorization", format!("Basic {}", encoded))
-            .form(&[("grant_type", "client_credentials")])
-            .send()
+            .form(&[("grant_type", "client_credentials")]);
+        let resp: TokenResponse = http
+            .send(&request)
             .context("Spotify 연결에 실패했습니다")?
-            .error_for_status()
+            .error_for_status(&request.url)
             .context("Spotify 인증에 실패했습니다. client_id와 client_secret를 확인하세요.")?
             .json()
             .context("Spotify 토큰 응답 파싱에 실패했습니다")?;
@@ -98,6 +262,20 @@ impl SpotifyClient {
         Ok(resp.access_token)
     }
 
+    /// 인증된 GET 요청을 보내 JSON 응답을 파싱한다.
+    fn get_json<T: DeserializeOwned>(&self, url: &str, query: &[(&str, &str)]) -> Result<T> {
+        let request = Request::get(url)
+            .query(query)
+            .bearer_auth(&self.access_token);
+        self.http
+            .send(&request)
+            .context("Spotify 요청에 실패했습니다")?
+            .error_for_status(&request.url)
+            .context("Spotify 요청이 실패했습니다")?
+            .json()
+            .context("Spotify 응답 파싱에 실패했습니다")
+    }
+
     /// 발매일 문자열에서 연도를 추출한다 (예: "2019-11-18" -> 2019).
     fn parse_year(release_date: &Option<String>) -> Option<i32> {
         release_date
@@ -106,57 +284,101 @@ impl SpotifyClient {
             .and_then(|y| y.parse().ok())
     }
 
-    /// Spotify API의 트랙 응답을 TrackInfo로 변환한다.
-    fn convert_track(track: &SpotifyTrack) -> TrackInfo {
-        let artist = track
-            .artists
-            .iter()
-            .map(|a| a.name.as_str())
-            .collect::<Vec<_>>()
-            .join(", ");
+    /// 아티스트 이름 목록.
+    fn artist_names(artists: &[SpotifyArtist]) -> Vec<String> {
+        artists.iter().map(|a| a.name.clone()).collect()
+    }
 
-        let album_art_url = track
-            .album
-            .images
+    /// 가장 큰 앨범 이미지의 URL을 반환한다.
+    fn largest_image(images: &[SpotifyImage]) -> Option<String> {
+        images
             .iter()
             .max_by_key(|img| img.width.unwrap_or(0))
-            .map(|img| img.url.clone());
+            .map(|img| img.url.clone())
+    }
 
+    /// Spotify API의 트랙 응답을 TrackInfo로 변환한다.
+    fn convert_track(track: &SpotifyTrack) -> TrackInfo {
         TrackInfo {
             title: Some(track.name.clone()),
-            artist: Some(artist),
+            artists: Self::artist_names(&track.artists),
             album: Some(track.album.name.clone()),
             album_artist: track.artists.first().map(|a| a.name.clone()),
             track_number: Some(track.track_number),
+            total_tracks: track.album.total_tracks,
+            disc_number: track.disc_number,
             year: Self::parse_year(&track.album.release_date),
-            genre: None,
-            album_art: None,
-            album_art_url,
+            release_date: track
+                .album
+                .release_date
+                .as_deref()
+                .and_then(parser::normalize_release_date),
+            duration_ms: track.duration_ms,
+            explicit: track.explicit,
+            album_art_url: Self::largest_image(&track.album.images),
+            source_id: track.id.clone(),
             source: "spotify".to_string(),
+            ..Default::default()
         }
     }
+
+    /// Spotify API의 앨범 응답을 AlbumInfo로 변환한다 (트랙 목록 제외).
+    fn convert_album(album: &SpotifyAlbum) -> AlbumInfo {
+        AlbumInfo {
+            title: Some(album.name.clone()),
+            artist: Some(Self::artist_names(&album.artists).join(", ")).filter(|a| !a.is_empty()),
+            year: Self::parse_year(&album.release_date),
+            release_date: album.release_date.clone(),
+            total_tracks: album.total_tracks,
+            album_art_url: Self::largest_image(&album.images),
+            detail_url: album
+                .id
+                .as_ref()
+                .map(|id| format!("{}/albums/{}", API_BASE, id)),
+            source: "spotify".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// 아티스트의 첫 번째 장르를 가져온다. Spotify는 트랙/앨범 장르를 거의 제공하지 않는다.
+    fn artist_genre(&self, artist_id: &str) -> Option<String> {
+        let artist: SpotifyArtistFull = self
+            .get_json(&format!("{}/artists/{}", API_BASE, artist_id), &[])
+            .ok()?;
+        artist.genres.into_iter().next()
+    }
+}
+
+/// Spotify 트랙/앨범 URL이나 URI에서 ID만 추출한다.
+/// "https://open.spotify.com/track/ID?si=..", "spotify:track:ID", "ID" 모두 지원.
+pub fn parse_spotify_id(input: &str) -> &str {
+    let input = input.trim();
+    let last = input.rsplit(['/', ':']).next().unwrap_or(input);
+    last.split('?').next().unwrap_or(last)
 }
 
 impl MusicSource for SpotifyClient {
     fn search(&self, query: &str) -> Result<Vec<TrackInfo>> {
+        self.search_page(query, Page::first())
+    }
+
+    /// Spotify 검색 API의 offset/limit으로 페이지를 넘긴다. limit은 API 한도인 `Page::MAX_LIMIT`까지.
+    fn search_page(&self, query: &str, page: Page) -> Result<Vec<TrackInfo>> {
+        let offset = page.offset.to_string();
+        let limit = page.limit.clamp(1, Page::MAX_LIMIT).to_string();
         let resp: SearchResponse = self
-            .client
-            .get("https://api.spotify.com/v1/search")
-            .bearer_auth(&self.access_token)
-            .query(&[("q", query), ("type", "track"), ("limit", "10")])
-            .send()
-            .context("Spotify 검색에 실패했습니다")?
-            .error_for_status()
-            .context("Spotify 검색 요청이 실패했습니다")?
-            .json()
-            .context("Spotify 검색 응답 파싱에 실패했습니다")?;
+            .get_json(
+                &format!("{}/search", API_BASE),
+                &[
+                    ("q", query),
+                    ("type", "track"),
+                    ("limit", &limit),
+                    ("offset", &offset),
+                ],
+            )
+            .context("Spotify 검색에 실패했습니다")?;
 
-        let results = resp
-            .tracks
-            .items
-            .iter()
-            .map(Self::convert_track)
-            .collect();
+        let results = resp.tracks.items.iter().map(Self::convert_track).collect();
 
         Ok(results)
     }
@@ -167,15 +389,465 @@ impl MusicSource for SpotifyClient {
             .as_ref()
             .context("앨범 아트 URL이 없습니다")?;
 
-        let data = self
-            .client
-            .get(url)
-            .send()
+        let resp = self
+            .http
+            .send(&Request::get(url))
             .context("앨범 아트 다운로드에 실패했습니다")?
-            .error_for_status()?
-            .bytes()?
-            .to_vec();
+            .error_for_status(url)?;
+
+        Ok(resp.body)
+    }
+
+    /// 앨범 아트에 더해 앨범(음반사, 전체 트랙 수)과 아티스트 장르를 채운다.
+    /// 트랙/앨범/아티스트 조회 실패는 무시하고 가능한 정보만 채운다.
+    /// 트랙 조회가 실패해도 검색 결과의 앨범 아트는 받는다.
+    fn fetch_detail(&self, track: &TrackInfo) -> Result<TrackInfo> {
+        let mut detailed = track.clone();
+
+        let full: Option<SpotifyTrack> = track.source_id.as_ref().and_then(|id| {
+            self.get_json(&format!("{}/tracks/{}", API_BASE, id), &[])
+                .ok()
+        });
+        if let Some(full) = full {
+            if let Some(ref album_id) = full.album.id {
+                let album: Result<SpotifyAlbumFull> =
+                    self.get_json(&format!("{}/albums/{}", API_BASE, album_id), &[]);
+                if let Ok(album) = album {
+                    detailed.label = album.label;
+                    detailed.total_tracks = album.album.total_tracks.or(detailed.total_tracks);
+                    if let Some(genre) = album.genres.into_iter().next() {
+                        detailed.genre = Some(genre);
+                    }
+                }
+            }
+
+            if detailed.genre.is_none() {
+                if let Some(artist_id) = full.artists.first().and_then(|a| a.id.as_deref()) {
+                    detailed.genre = self.artist_genre(artist_id);
+                }
+            }
+        }
+
+        if let Ok(art) = self.fetch_album_art(track) {
+            detailed.album_art = Some(art);
+        }
+        Ok(detailed)
+    }
+
+    fn search_album(&self, query: &str) -> Result<Vec<AlbumInfo>> {
+        let resp: AlbumSearchResponse = self
+            .get_json(
+                &format!("{}/search", API_BASE),
+                &[("q", query), ("type", "album"), ("limit", "10")],
+            )
+            .context("Spotify 앨범 검색에 실패했습니다")?;
+
+        Ok(resp.albums.items.iter().map(Self::convert_album).collect())
+    }
+
+    fn fetch_album(&self, album: &AlbumInfo) -> Result<AlbumInfo> {
+        let url = album
+            .detail_url
+            .as_ref()
+            .context("앨범 상세 URL이 없습니다")?;
+        let mut full: SpotifyAlbumFull = self
+            .get_json(url, &[("limit", "50")])
+            .context("Spotify 앨범 조회에 실패했습니다")?;
+        while let Some(next) = full.tracks.next.take() {
+            let page: AlbumTracks = self
+                .get_json(&next, &[])
+                .context("Spotify 앨범 트랙 목록을 가져오지 못했습니다")?;
+            full.tracks.items.extend(page.items);
+            full.tracks.next = page.next;
+        }
+
+        let mut detailed = Self::convert_album(&full.album);
+        detailed.label = full.label.clone();
+        detailed.genre = full.genres.first().cloned().or_else(|| {
+            full.album
+                .artists
+                .first()
+                .and_then(|a| a.id.as_deref())
+                .and_then(|id| self.artist_genre(id))
+        });
+
+        let total_discs = full.tracks.items.iter().filter_map(|t| t.disc_number).max();
+        detailed.tracks = full
+            .tracks
+            .items
+            .iter()
+            .map(|t| TrackInfo {
+                title: Some(t.name.clone()),
+                artists: Self::artist_names(&t.artists),
+                album: detailed.title.clone(),
+                album_artist: detailed.artist.clone(),
+                track_number: Some(t.track_number),
+                total_tracks: detailed.total_tracks,
+                disc_number: t.disc_number,
+                total_discs,
+                year: detailed.year,
+                release_date: detailed
+                    .release_date
+                    .as_deref()
+                    .and_then(parser::normalize_release_date),
+                genre: detailed.genre.clone(),
+                label: detailed.label.clone(),
+                duration_ms: t.duration_ms,
+                album_art_url: detailed.album_art_url.clone(),
+                source_id: t.id.clone(),
+                source: "spotify".to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        if let Some(ref art_url) = detailed.album_art_url {
+            let art = TrackInfo {
+                album_art_url: Some(art_url.clone()),
+                ..Default::default()
+            };
+            detailed.album_art = self.fetch_album_art(&art).ok();
+        }
+
+        Ok(detailed)
+    }
+
+    fn get_track(&self, id: &str) -> Result<TrackInfo> {
+        let track: SpotifyTrack = self
+            .get_json(
+                &format!("{}/tracks/{}", API_BASE, parse_spotify_id(id)),
+                &[],
+            )
+            .context("Spotify 트랙 조회에 실패했습니다")?;
+        Ok(Self::convert_track(&track))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::http::mock::MockHttp;
+    use crate::sources::http::Method;
+
+    const TOKEN: &str = r#"{"access_token": "abc", "token_type": "Bearer", "expires_in": 3600}"#;
+    const TRACK: &str = r#"{
+        "id": "t1", "name": "밤편지", "track_number": 3, "disc_number": 1, "duration_ms": 253800,
+        "explicit": false,
+        "artists": [{"id": "iu", "name": "IU"}],
+        "album": {"id": "a1", "name": "Palette", "release_date": "2017-04-21", "total_tracks": 10,
+                  "images": [{"url": "https://i.scdn.co/image/640", "width": 640}]}
+    }"#;
+
+    fn config() -> SpotifyConfig {
+        SpotifyConfig {
+            client_id: Some("id".to_string()),
+            client_secret: Some("secret".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn client(http: MockHttp) -> (SpotifyClient, Arc<MockHttp>) {
+        let http = Arc::new(http.on(Method::Post, TOKEN_URL, 200, TOKEN));
+        let client = SpotifyClient::with_http(&config(), http.clone()).unwrap();
+        (client, http)
+    }
+
+    #[test]
+    fn test_authenticate_and_search() {
+        let search = format!(r#"{{"tracks": {{"items": [{}]}}}}"#, TRACK);
+        let (client, http) = client(MockHttp::default().on(
+            Method::Get,
+            "https://api.spotify.com/v1/search",
+            200,
+            search,
+        ));
+
+        let results = client.search("아이유 밤편지").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title.as_deref(), Some("밤편지"));
+        assert_eq!(results[0].album.as_deref(), Some("Palette"));
+        assert_eq!(results[0].release_date.as_deref(), Some("2017-04-21"));
+        assert_eq!(results[0].total_tracks, Some(10));
+        assert_eq!(results[0].explicit, Some(false));
+        assert_eq!(
+            results[0].album_art_url.as_deref(),
+            Some("https://i.scdn.co/image/640")
+        );
+
+        let requests = http.requests();
+        let basic = base64::engine::general_purpose::STANDARD.encode("id:secret");
+        assert!(requests[0]
+            .headers
+            .contains(&("Authorization".to_string(), format!("Basic {}", basic))));
+        assert_eq!(
+            requests[0].form,
+            [("grant_type".to_string(), "client_credentials".to_string())]
+        );
+        assert!(requests[1].url.contains("type=track"));
+        assert!(requests[1]
+            .headers
+            .contains(&("Authorization".to_string(), "Bearer abc".to_string())));
+    }
+
+    #[test]
+    fn test_search_page_sends_offset_and_limit() {
+        let search = format!(r#"{{"tracks": {{"items": [{}]}}}}"#, TRACK);
+        let (client, http) = client(MockHttp::default().on(
+            Method::Get,
+            "https://api.spotify.com/v1/search",
+            200,
+            search,
+        ));
+
+        let page = Page {
+            offset: 20,
+            limit: 100,
+        };
+        assert_eq!(client.search_page("밤편지", page).unwrap().len(), 1);
+        let url = &http.requests()[1].url;
+        assert!(url.contains("offset=20"), "{}", url);
+        assert!(url.contains("limit=50"), "{}", url);
+    }
+
+    #[test]
+    fn test_authentication_failure() {
+        let http = Arc::new(MockHttp::default().on(
+            Method::Post,
+            TOKEN_URL,
+            400,
+            r#"{"error": "invalid_client"}"#,
+        ));
+        let err = SpotifyClient::with_http(&config(), http).err().unwrap();
+        assert!(format!("{:#}", err).contains("client_secret를 확인하세요"));
+
+        let missing = SpotifyConfig::default();
+        let http = Arc::new(MockHttp::default());
+        assert!(SpotifyClient::with_http(&missing, http.clone()).is_err());
+        assert!(http.requests().is_empty());
+    }
+
+    #[test]
+    fn test_fetch_detail_fills_label_and_artist_genre() {
+        let album = r#"{
+            "id": "a1", "name": "Palette", "release_date": "2017-04-21", "total_tracks": 10,
+            "images": [], "label": "LOEN Entertainment", "genres": [],
+            "tracks": {"items": []}
+        }"#;
+        let (client, http) = client(
+            MockHttp::default()
+                .on(
+                    Method::Get,
+                    "https://api.spotify.com/v1/tracks/t1",
+                    200,
+                    TRACK,
+                )
+                .on(
+                    Method::Get,
+                    "https://api.spotify.com/v1/albums/a1",
+                    200,
+                    album,
+                )
+                .on(
+                    Method::Get,
+                    "https://api.spotify.com/v1/artists/iu",
+                    200,
+                    r#"{"genres": ["k-pop", "k-ballad"]}"#,
+                )
+                .on(
+                    Method::Get,
+                    "https://i.scdn.co/image/640",
+                    200,
+                    b"JPEG".to_vec(),
+                ),
+        );
+
+        let track = client.get_track("spotify:track:t1").unwrap();
+        let detailed = client.fetch_detail(&track).unwrap();
+        assert_eq!(detailed.label.as_deref(), Some("LOEN Entertainment"));
+        assert_eq!(detailed.genre.as_deref(), Some("k-pop"));
+        assert_eq!(detailed.album_art.as_deref(), Some(b"JPEG".as_slice()));
+        // 앨범 아트는 인증 없이 받는다
+        let art = http.requests().pop().unwrap();
+        assert!(art.headers.is_empty());
+    }
+
+    #[test]
+    fn test_fetch_detail_keeps_art_when_track_lookup_fails() {
+        let (client, _) = client(
+            MockHttp::default()
+                .on(Method::Get, "https://api.spotify.com/v1/tracks/t1", 503, "")
+                .on(
+                    Method::Get,
+                    "https://i.scdn.co/image/640",
+                    200,
+                    b"JPEG".to_vec(),
+                ),
+        );
+        let track = TrackInfo {
+            source_id: Some("t1".to_string()),
+            album_art_url: Some("https://i.scdn.co/image/640".to_string()),
+            ..Default::default()
+        };
+        let detailed = client.fetch_detail(&track).unwrap();
+        assert_eq!(detailed.album_art.as_deref(), Some(b"JPEG".as_slice()));
+        assert_eq!(detailed.label, None);
+    }
+
+    #[test]
+    fn test_fetch_album_follows_track_pages() {
+        let album_track = |n: u32| {
+            format!(
+                r#"{{"id": "t{n}", "name": "Track {n}", "artists": [{{"name": "IU"}}],
+                    "track_number": {n}, "disc_number": 1, "duration_ms": 200000}}"#
+            )
+        };
+        let items = |range: std::ops::RangeInclusive<u32>| {
+            range.map(album_track).collect::<Vec<_>>().join(",")
+        };
+        let next = "https://api.spotify.com/v1/albums/a1/tracks?offset=50&limit=50";
+        let album = format!(
+            r#"{{"id": "a1", "name": "Big Album", "release_date": "2020-01-01",
+                "total_tracks": 52, "images": [], "artists": [{{"name": "IU"}}],
+                "label": "EDAM", "genres": ["k-pop"],
+                "tracks": {{"items": [{}], "next": "{}"}}}}"#,
+            items(1..=50),
+            next
+        );
+        let rest = format!(r#"{{"items": [{}], "next": null}}"#, items(51..=52));
+        let (client, http) = client(MockHttp::default().on(Method::Get, next, 200, rest).on(
+            Method::Get,
+            "https://api.spotify.com/v1/albums/a1",
+            200,
+            album,
+        ));
+        let info = AlbumInfo {
+            detail_url: Some("https://api.spotify.com/v1/albums/a1".to_string()),
+            ..Default::default()
+        };
+
+        let detailed = client.fetch_album(&info).unwrap();
+        assert_eq!(detailed.tracks.len(), 52);
+        assert_eq!(detailed.tracks[51].title.as_deref(), Some("Track 52"));
+        assert!(http.requests().iter().any(|r| r.url == next));
+    }
+
+    #[test]
+    fn test_liked_tracks_follows_next_pages() {
+        let page = |next: &str| {
+            format!(
+                r#"{{"items": [{{"track": {}}}], "next": {}, "total": 2}}"#,
+                TRACK, next
+            )
+        };
+        let (client, _) = client(
+            MockHttp::default()
+                .on(
+                    Method::Get,
+                    "https://api.spotify.com/v1/me/tracks?limit=50&offset=50",
+                    200,
+                    page("null"),
+                )
+                .on(
+                    Method::Get,
+                    "https://api.spotify.com/v1/me/tracks",
+                    200,
+                    page(r#""https://api.spotify.com/v1/me/tracks?limit=50&offset=50""#),
+                ),
+        );
+
+        let mut progress = Vec::new();
+        let tracks = client
+            .liked_tracks(|fetched, total| progress.push((fetched, total)))
+            .unwrap();
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(progress, [(1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_request_error_is_reported() {
+        let (client, _) = client(MockHttp::default().on(
+            Method::Get,
+            "https://api.spotify.com/v1/tracks/",
+            404,
+            r#"{"error": {"status": 404}}"#,
+        ));
+        let err = client.get_track("missing").err().unwrap();
+        assert!(format!("{:#}", err).contains("HTTP 404"));
+    }
+
+    #[test]
+    fn test_server_error_exits_with_network_code() {
+        let (client, _) = client(MockHttp::default().on(
+            Method::Get,
+            "https://api.spotify.com/v1/search",
+            500,
+            "Internal Server Error",
+        ));
+        let err = client.search("밤편지").unwrap_err();
+        assert!(format!("{:#}", err).contains("HTTP 500"));
+        assert_eq!(exit::code(&err), exit::ExitCode::Network);
+    }
+
+    #[test]
+    fn test_parse_spotify_id() {
+        assert_eq!(
+            parse_spotify_id("https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh?si=abc"),
+            "4iV5W9uYEdYUVa79Axb7Rh"
+        );
+        assert_eq!(
+            parse_spotify_id("spotify:track:4iV5W9uYEdYUVa79Axb7Rh"),
+            "4iV5W9uYEdYUVa79Axb7Rh"
+        );
+        assert_eq!(
+            parse_spotify_id("4iV5W9uYEdYUVa79Axb7Rh"),
+            "4iV5W9uYEdYUVa79Axb7Rh"
+        );
+    }
+
+    #[test]
+    fn test_playlist_page_skips_non_tracks() {
+        let json = r#"{
+            "next": null,
+            "total": 3,
+            "items": [
+                {"track": {
+                    "id": "t1", "name": "밤편지", "track_number": 1, "duration_ms": 253000,
+                    "artists": [{"id": "iu", "name": "IU"}],
+                    "album": {"name": "Palette", "release_date": "2017-04-21", "images": []}
+                }},
+                {"track": null},
+                {"track": {"id": "e1", "name": "Podcast", "type": "episode"}}
+            ]
+        }"#;
+        let page: TrackPage = serde_json::from_str(json).unwrap();
+        let tracks = SpotifyClient::convert_page_items(page.items);
+
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].title.as_deref(), Some("밤편지"));
+        assert_eq!(tracks[0].duration_ms, Some(253000));
+        assert_eq!(tracks[0].source_id.as_deref(), Some("t1"));
+    }
+
+    #[test]
+    fn test_convert_album_from_json() {
+        let json = r#"{
+            "id": "abc",
+            "name": "Love poem",
+            "artists": [{"id": "iu", "name": "IU"}],
+            "release_date": "2019-11-18",
+            "total_tracks": 6,
+            "images": [{"url": "small", "width": 64}, {"url": "large", "width": 640}]
+        }"#;
+        let album: SpotifyAlbum = serde_json::from_str(json).unwrap();
+        let info = SpotifyClient::convert_album(&album);
 
-        Ok(data)
+        assert_eq!(info.title.as_deref(), Some("Love poem"));
+        assert_eq!(info.artist.as_deref(), Some("IU"));
+        assert_eq!(info.year, Some(2019));
+        assert_eq!(info.total_tracks, Some(6));
+        assert_eq!(info.album_art_url.as_deref(), Some("large"));
+        assert_eq!(
+            info.detail_url.as_deref(),
+            Some("https://api.spotify.com/v1/albums/abc")
+        );
     }
 }
@@ -0,0 +1,223 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::sources::http::{HttpClient, Request};
+
+const AUTHORIZE_URL: &str = "https://accounts.spotify.com/authorize";
+const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+
+/// 사용자 로그인에 요청하는 권한 범위. 좋아요 표시한 곡과 플레이리스트 읽기.
+const SCOPES: &str = "user-library-read playlist-read-private playlist-read-collaborative";
+
+/// Authorization Code + PKCE 흐름에서 발급받은 사용자 토큰.
+#[derive(Deserialize)]
+pub struct UserTokens {
+    pub access_token: String,
+    /// 갱신 시 새 refresh token이 오지 않을 수 있다.
+    pub refresh_token: Option<String>,
+}
+
+/// PKCE code verifier와 code challenge(S256) 쌍.
+pub struct Pkce {
+    pub verifier: String,
+    pub challenge: String,
+}
+
+impl Pkce {
+    /// 무작위 code verifier를 만들고 그 challenge를 계산한다.
+    pub fn generate() -> Self {
+        Self::from_verifier(random_string(64))
+    }
+
+    fn from_verifier(verifier: String) -> Self {
+        let digest = Sha256::digest(verifier.as_bytes());
+        let challenge = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest);
+        Self {
+            verifier,
+            challenge,
+        }
+    }
+}
+
+/// 영숫자로 된 무작위 문자열을 만든다 (code verifier, state용).
+pub fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// 사용자가 브라우저에서 열어야 할 Spotify 로그인 URL을 만든다.
+pub fn authorize_url(client_id: &str, redirect_uri: &str, pkce: &Pkce, state: &str) -> String {
+    let url = reqwest::Url::parse_with_params(
+        AUTHORIZE_URL,
+        &[
+            ("client_id", client_id),
+            ("response_type", "code"),
+            ("redirect_uri", redirect_uri),
+            ("code_challenge_method", "S256"),
+            ("code_challenge", pkce.challenge.as_str()),
+            ("scope", SCOPES),
+            ("state", state),
+        ],
+    )
+    .expect("Spotify 로그인 URL이 올바르지 않습니다");
+    url.to_string()
+}
+
+/// 로컬 포트에서 Spotify의 리다이렉트를 한 번 받아 authorization code를 반환한다.
+/// 콜백이 아닌 요청(favicon 등)은 무시하고 계속 기다린다.
+pub fn wait_for_code(port: u16, state: &str) -> Result<String> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .with_context(|| format!("로그인 콜백용 포트 {}를 열 수 없습니다", port))?;
+
+    for stream in listener.incoming() {
+        let mut stream = stream.context("로그인 콜백 연결에 실패했습니다")?;
+
+        let mut request_line = String::new();
+        BufReader::new(&stream).read_line(&mut request_line)?;
+
+        let Some(result) = parse_callback(&request_line, state) else {
+            let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+            continue;
+        };
+
+        let body = match result {
+            Ok(_) => "로그인되었습니다. 이 창을 닫고 터미널로 돌아가세요.",
+            Err(_) => "로그인에 실패했습니다. 터미널의 안내를 확인하세요.",
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes());
+        return result;
+    }
+
+    bail!("로그인 콜백을 받지 못했습니다")
+}
+
+/// 콜백 요청 첫 줄(`GET /callback?code=..&state=.. HTTP/1.1`)을 해석한다.
+/// 콜백 경로가 아니면 None, 거부/state 불일치면 에러를 반환한다.
+fn parse_callback(request_line: &str, expected_state: &str) -> Option<Result<String>> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let url = reqwest::Url::parse(&format!("http://127.0.0.1{}", target)).ok()?;
+    if url.path() != "/callback" {
+        return None;
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    Some((|| {
+        if let Some(error) = param("error") {
+            bail!("Spotify 로그인이 거부되었습니다: {}", error);
+        }
+        if param("state").as_deref() != Some(expected_state) {
+            bail!("로그인 응답의 state가 일치하지 않습니다");
+        }
+        param("code").context("로그인 응답에 code가 없습니다")
+    })())
+}
+
+/// authorization code를 사용자 토큰으로 교환한다.
+pub fn exchange_code(
+    http: &dyn HttpClient,
+    client_id: &str,
+    code: &str,
+    pkce: &Pkce,
+    redirect_uri: &str,
+) -> Result<UserTokens> {
+    request_tokens(
+        http,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("client_id", client_id),
+            ("code_verifier", pkce.verifier.as_str()),
+        ],
+    )
+    .context("Spotify 로그인 토큰 발급에 실패했습니다")
+}
+
+/// refresh token으로 새 access token을 발급받는다.
+pub fn refresh(http: &dyn HttpClient, client_id: &str, refresh_token: &str) -> Result<UserTokens> {
+    request_tokens(
+        http,
+        &[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", client_id),
+        ],
+    )
+    .context("Spotify 로그인이 만료되었습니다. 'mp3tag login'을 다시 실행하세요")
+}
+
+fn request_tokens(http: &dyn HttpClient, form: &[(&str, &str)]) -> Result<UserTokens> {
+    let tokens = http
+        .send(&Request::post(TOKEN_URL).form(form))
+        .context("Spotify 연결에 실패했습니다")?
+        .error_for_status(TOKEN_URL)?
+        .json()
+        .context("Spotify 토큰 응답 파싱에 실패했습니다")?;
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_challenge() {
+        // RFC 7636 Appendix B의 예시 값
+        let pkce = Pkce::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string());
+        assert_eq!(
+            pkce.challenge,
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+
+        let generated = Pkce::generate();
+        assert_eq!(generated.verifier.len(), 64);
+    }
+
+    #[test]
+    fn test_parse_callback() {
+        let ok = parse_callback("GET /callback?code=abc&state=xyz HTTP/1.1\r\n", "xyz");
+        assert_eq!(ok.unwrap().unwrap(), "abc");
+
+        let mismatch = parse_callback("GET /callback?code=abc&state=evil HTTP/1.1", "xyz");
+        assert!(mismatch.unwrap().is_err());
+
+        let denied = parse_callback(
+            "GET /callback?error=access_denied&state=xyz HTTP/1.1",
+            "xyz",
+        );
+        assert!(denied.unwrap().is_err());
+
+        assert!(parse_callback("GET /favicon.ico HTTP/1.1", "xyz").is_none());
+    }
+
+    #[test]
+    fn test_authorize_url() {
+        let pkce = Pkce::from_verifier("verifier".to_string());
+        let url = authorize_url("my-id", "http://127.0.0.1:8888/callback", &pkce, "st");
+        assert!(url.starts_with(AUTHORIZE_URL));
+        assert!(url.contains("client_id=my-id"));
+        assert!(url.contains("code_challenge_method=S256"));
+        assert!(url.contains("redirect_uri=http%3A%2F%2F127.0.0.1%3A8888%2Fcallback"));
+        assert!(url.contains("state=st"));
+    }
+}